@@ -0,0 +1,47 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use std::time::Duration;
+
+use macroquest::mq;
+use macroquest::plugin::{Hooks, Plugin};
+
+const URL: &str = "https://www.macroquest.org/";
+
+macroquest::plugin::setup!(MQRustAsync);
+
+#[derive(Debug)]
+struct MQRustAsync {}
+
+impl Plugin for MQRustAsync {
+    fn new() -> Self {
+        MQRustAsync {}
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustAsync {
+    fn initialize(&self) {
+        // Allow the runtime slightly more time than the default on each pulse.
+        macroquest::rt::set_budget(Duration::from_millis(2));
+
+        // The request itself doesn't need to touch the game, but writing the
+        // result to chat does, so we use spawn_local to make sure the whole
+        // task is polled on the game's main thread.
+        macroquest::rt::spawn_local(async {
+            match reqwest::get(URL).await {
+                Ok(response) => mq::write_chat(format!(
+                    "{URL} -> {:?} {}",
+                    response.version(),
+                    response.status()
+                )),
+                Err(error) => mq::write_chat(format!("failed to fetch {URL}: {error}")),
+            }
+        });
+    }
+}
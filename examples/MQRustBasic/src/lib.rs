@@ -12,6 +12,7 @@ const PLUGIN_NAME: &str = env!("CARGO_PKG_NAME");
 
 use macroquest::eq;
 use macroquest::log::{ConsoleLogger, FileLogger, LevelFilter, Logger};
+use macroquest::mq::hud::HudFrame;
 use macroquest::plugin::{Hooks, Plugin};
 
 macroquest::plugin::setup!(MQRustSimple);
@@ -48,6 +49,8 @@ impl Hooks for MQRustSimple {
 
     fn draw_hud(&self) {}
 
+    fn draw_hud_frame(&self, frame: &HudFrame) {}
+
     fn pulse(&self) {}
 
     fn begin_zone(&self) {}
@@ -0,0 +1,100 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use macroquest::mq;
+use macroquest::mq::datatypes::{self, DataType, TopLevelObject, Value};
+use macroquest::plugin::{Hooks, Plugin};
+
+macroquest::plugin::setup!(MQRustDataType);
+
+// The name of the datatype, and of the TLO that returns it. From a macro,
+// `/echo ${RustSample.Counter}`, and from Lua,
+// `/lua parse mq.TLO.RustSample.Counter()`, both print the same number.
+const NAME: &str = "RustSample";
+
+// How many pulses between checking that macros see the same counter that we do.
+const CHECK_EVERY: i64 = 1000;
+
+#[derive(Debug)]
+struct MQRustDataType {
+    counter: Arc<AtomicI64>,
+    tlo:     Mutex<Option<TopLevelObject>>,
+}
+
+/// The data behind `${RustSample}`, which shares its counter with the plugin.
+struct Sample {
+    counter: Arc<AtomicI64>,
+}
+
+impl DataType for Sample {
+    fn members(&self) -> &[&str] {
+        &["Counter", "Even", "Label"]
+    }
+
+    fn member(&self, member: &str, _index: Option<&str>) -> Value {
+        let counter = self.counter.load(Ordering::Relaxed);
+
+        match member {
+            "Counter" => counter.into(),
+            "Even" => (counter % 2 == 0).into(),
+            "Label" => format!("{counter} pulses").into(),
+            _ => Value::Null,
+        }
+    }
+
+    fn text(&self) -> Option<String> {
+        Some(self.counter.load(Ordering::Relaxed).to_string())
+    }
+}
+
+impl Plugin for MQRustDataType {
+    fn new() -> Self {
+        MQRustDataType {
+            counter: Arc::new(AtomicI64::new(0)),
+            tlo:     Mutex::new(None),
+        }
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustDataType {
+    fn initialize(&self) {
+        let sample = Sample {
+            counter: Arc::clone(&self.counter),
+        };
+
+        match datatypes::register(NAME, sample) {
+            Ok(tlo) => *self.tlo.lock().unwrap() = Some(tlo),
+            Err(err) => mq::write_chat(format!("[MQRustDataType] {err}")),
+        }
+    }
+
+    fn shutdown(&self) {
+        self.tlo.lock().unwrap().take();
+    }
+
+    fn pulse(&self) {
+        let counter = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
+        if counter % CHECK_EVERY != 0 {
+            return;
+        }
+
+        // Read the counter back the way that a macro would, which goes through
+        // MacroQuest's datatype the same way that Lua does.
+        let expression = format!("${{{NAME}.Counter}}");
+        let parsed = mq::parse_macro_data(&expression);
+        if parsed.as_deref() != Some(counter.to_string().as_str()) {
+            mq::write_chat(format!(
+                "[MQRustDataType] {expression} was {parsed:?}, expected {counter}"
+            ));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use std::sync::Mutex;
+
+use macroquest::eq::ChatColor;
+use macroquest::interop::dannet::{self, Observer, Recipient};
+use macroquest::mq;
+use macroquest::plugin::{Hooks, Plugin};
+
+macroquest::plugin::setup!(MQRustEcho);
+
+// Messages that start with this are sent back to whoever sent them, without
+// it. With the plugin loaded on two characters, `/dtell Bob echo hello` from
+// Alice has Bob send `hello` back to Alice.
+const PREFIX: &str = "echo ";
+
+#[derive(Debug)]
+struct MQRustEcho {
+    observer: Mutex<Option<Observer>>,
+}
+
+impl Plugin for MQRustEcho {
+    fn new() -> Self {
+        MQRustEcho {
+            observer: Mutex::new(None),
+        }
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustEcho {
+    fn initialize(&self) {
+        let observer = dannet::observe(PREFIX, |message| {
+            let reply = &message.text[PREFIX.len()..];
+            let to = Recipient::Peer(message.from.clone());
+
+            if let Err(err) = dannet::send(&to, reply) {
+                mq::write_chat(format!("[MQRustEcho] could not reply: {err}"));
+            }
+        });
+
+        *self.observer.lock().unwrap() = Some(observer);
+    }
+
+    fn shutdown(&self) {
+        if let Some(observer) = self.observer.lock().unwrap().take() {
+            observer.cancel();
+        }
+    }
+
+    fn write_chat(&self, line: &str, _color: ChatColor) {
+        dannet::handle_chat(line);
+    }
+}
@@ -0,0 +1,31 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use macroquest::mq;
+use macroquest::mq::hud::{Color, HudFrame};
+use macroquest::plugin::{Hooks, Plugin};
+
+macroquest::plugin::setup!(MQRustHud);
+
+#[derive(Debug)]
+struct MQRustHud {}
+
+impl Plugin for MQRustHud {
+    fn new() -> Self {
+        MQRustHud {}
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustHud {
+    fn draw_hud_frame(&self, frame: &HudFrame) {
+        if let Some(game) = mq::in_game() {
+            frame.draw_text(10, 10, Color::YELLOW, &game.zone_name());
+        }
+    }
+}
@@ -0,0 +1,74 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use macroquest::eq::PickUpError;
+use macroquest::mq;
+use macroquest::mq::commands::{ArgValue, Args};
+use macroquest::plugin::{Hooks, Plugin};
+
+macroquest::plugin::setup!(MQRustLoot);
+
+#[derive(Debug)]
+struct MQRustLoot;
+
+impl Plugin for MQRustLoot {
+    fn new() -> Self {
+        MQRustLoot
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustLoot {}
+
+#[derive(ArgValue)]
+enum Subcommand {
+    Nearest,
+}
+
+#[macroquest::plugin::command("/rustloot")]
+fn rustloot(args: &str) {
+    match Args::new(args).get(0) {
+        Some(Subcommand::Nearest) => loot_nearest(),
+        None => mq::write_chat("[MQRustLoot] Usage: /rustloot nearest"),
+    }
+}
+
+fn loot_nearest() {
+    let Some(game) = mq::in_game()
+    else {
+        mq::write_chat("[MQRustLoot] Not in game");
+        return;
+    };
+
+    let Some(item) = game.nearest_ground_item()
+    else {
+        mq::write_chat("[MQRustLoot] There's nothing on the ground");
+        return;
+    };
+
+    // Target the item first, so that it's clear which one we tried to pick up
+    // even if we couldn't.
+    if let Err(err) = mq::item_target(item) {
+        mq::write_chat(format!("[MQRustLoot] {err}"));
+        return;
+    }
+
+    match item.pick_up() {
+        Ok(()) => mq::write_chat(format!("[MQRustLoot] Picked up {}", item.name())),
+        Err(PickUpError::OutOfRange { distance }) => {
+            mq::write_chat(format!(
+                "[MQRustLoot] {} is {distance:.0} away, move closer",
+                item.name()
+            ));
+        }
+        Err(PickUpError::CursorOccupied) => {
+            mq::write_chat("[MQRustLoot] Put away the item on your cursor first");
+        }
+        Err(err) => mq::write_chat(format!("[MQRustLoot] {err}")),
+    }
+}
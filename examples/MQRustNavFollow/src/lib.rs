@@ -0,0 +1,78 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use macroquest::interop::nav::{self, Destination, NavError};
+use macroquest::mq;
+use macroquest::plugin::{Hooks, Plugin};
+
+macroquest::plugin::setup!(MQRustNavFollow);
+
+// How often to check on the target, and how close to stay to it.
+const CHECK_EVERY: Duration = Duration::from_secs(1);
+const FOLLOW_DISTANCE: f32 = 15.0;
+
+#[derive(Debug)]
+struct MQRustNavFollow {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    last_check: Option<Instant>,
+    // The last error, so that it's only reported when it changes.
+    last_error: Option<NavError>,
+}
+
+impl Plugin for MQRustNavFollow {
+    fn new() -> Self {
+        MQRustNavFollow {
+            state: Mutex::new(State::default()),
+        }
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustNavFollow {
+    fn pulse(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state
+            .last_check
+            .is_some_and(|last_check| last_check.elapsed() < CHECK_EVERY)
+        {
+            return;
+        }
+        state.last_check = Some(Instant::now());
+
+        let result = follow(&Destination::Target);
+        match result {
+            Ok(()) => state.last_error = None,
+            Err(err) if state.last_error.as_ref() != Some(&err) => {
+                mq::write_chat(format!("[MQRustNavFollow] not following: {err}"));
+                state.last_error = Some(err);
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Navigate towards `destination`, stopping once we're close enough to it.
+fn follow(destination: &Destination) -> Result<(), NavError> {
+    if nav::path_length(destination)? > FOLLOW_DISTANCE {
+        if !nav::is_navigating()? {
+            nav::navigate_to(destination)?;
+        }
+    }
+    else if nav::is_navigating()? {
+        nav::stop()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use macroquest::interface::{self, Endpoint, InterfaceError};
+use macroquest::mq;
+use macroquest::plugin::{Hooks, Plugin};
+
+macroquest::plugin::setup!(MQRustPing);
+macroquest::interface::export!();
+
+// The plugin that we ping, and how often.
+const PONG: &str = "MQRustPong";
+const PING_EVERY: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+struct MQRustPing {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    endpoint:   Option<Endpoint>,
+    last_ping:  Option<Instant>,
+    // The last error, so that it's only reported when it changes.
+    last_error: Option<InterfaceError>,
+}
+
+impl Plugin for MQRustPing {
+    fn new() -> Self {
+        MQRustPing {
+            state: Mutex::new(State::default()),
+        }
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustPing {
+    fn initialize(&self) {
+        let endpoint = interface::serve("MQRustPing", &[], |message| {
+            mq::write_chat(format!(
+                "[MQRustPing] {} from {}",
+                String::from_utf8_lossy(&message.payload),
+                message.sender
+            ));
+        });
+
+        match endpoint {
+            Ok(endpoint) => self.state.lock().unwrap().endpoint = Some(endpoint),
+            Err(err) => mq::write_chat(format!("[MQRustPing] {err}")),
+        }
+    }
+
+    fn shutdown(&self) {
+        self.state.lock().unwrap().endpoint.take();
+    }
+
+    fn pulse(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state
+            .last_ping
+            .is_some_and(|last_ping| last_ping.elapsed() < PING_EVERY)
+        {
+            return;
+        }
+        state.last_ping = Some(Instant::now());
+
+        // MQRustPong replies from within send(), which calls back into our
+        // handler, so the state must not be locked while sending.
+        drop(state);
+        let result = ping();
+
+        let mut state = self.state.lock().unwrap();
+        match result {
+            Ok(()) => state.last_error = None,
+            Err(err) if state.last_error.as_ref() != Some(&err) => {
+                mq::write_chat(format!("[MQRustPing] not pinging {PONG}: {err}"));
+                state.last_error = Some(err);
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Ping MQRustPong, if it says that it understands pings.
+fn ping() -> Result<(), InterfaceError> {
+    if interface::has_capability(PONG, "ping")? {
+        interface::send(PONG, b"ping")?;
+    }
+
+    Ok(())
+}
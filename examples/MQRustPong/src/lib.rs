@@ -0,0 +1,52 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use std::sync::Mutex;
+
+use macroquest::interface::{self, Endpoint};
+use macroquest::mq;
+use macroquest::plugin::{Hooks, Plugin};
+
+macroquest::plugin::setup!(MQRustPong);
+macroquest::interface::export!();
+
+#[derive(Debug)]
+struct MQRustPong {
+    endpoint: Mutex<Option<Endpoint>>,
+}
+
+impl Plugin for MQRustPong {
+    fn new() -> Self {
+        MQRustPong {
+            endpoint: Mutex::new(None),
+        }
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustPong {
+    fn initialize(&self) {
+        // Answer every ping with a pong, sent back to whoever pinged us.
+        let endpoint = interface::serve("MQRustPong", &["ping"], |message| {
+            if message.payload == b"ping" {
+                if let Err(err) = interface::send(&message.sender, b"pong") {
+                    mq::write_chat(format!("[MQRustPong] could not reply: {err}"));
+                }
+            }
+        });
+
+        match endpoint {
+            Ok(endpoint) => *self.endpoint.lock().unwrap() = Some(endpoint),
+            Err(err) => mq::write_chat(format!("[MQRustPong] {err}")),
+        }
+    }
+
+    fn shutdown(&self) {
+        self.endpoint.lock().unwrap().take();
+    }
+}
@@ -0,0 +1,50 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use macroquest::mq::commands::{ArgValue, Args};
+use macroquest::plugin::{Hooks, Plugin};
+use macroquest::{export, mq};
+
+macroquest::plugin::setup!(MQRustProfile);
+
+#[derive(Debug)]
+struct MQRustProfile;
+
+impl Plugin for MQRustProfile {
+    fn new() -> Self {
+        MQRustProfile
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustProfile {}
+
+#[derive(ArgValue)]
+enum Subcommand {
+    Export,
+}
+
+#[macroquest::plugin::command("/rustprofile")]
+fn rustprofile(args: &str) {
+    match Args::new(args).get(0) {
+        Some(Subcommand::Export) => export_profile(),
+        None => mq::write_chat("[MQRustProfile] Usage: /rustprofile export"),
+    }
+}
+
+fn export_profile() {
+    let written = export::character_profile()
+        .and_then(|profile| profile.write_to(mq::paths().config()));
+
+    match written {
+        Ok(path) => {
+            mq::write_chat(format!("[MQRustProfile] Exported to {}", path.display()));
+        }
+        Err(err) => mq::write_chat(format!("[MQRustProfile] {err}")),
+    }
+}
@@ -0,0 +1,63 @@
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use macroquest::mq;
+use macroquest::mq::hud::{Color, HudFrame};
+use macroquest::plugin::{Hooks, Plugin};
+
+macroquest::plugin::setup!(MQRustTasks);
+
+// Where the overlay is drawn, and how far apart its lines are.
+const LEFT: i32 = 10;
+const TOP: i32 = 30;
+const INDENT: i32 = 12;
+const LINE_HEIGHT: i32 = 14;
+
+#[derive(Debug)]
+struct MQRustTasks {}
+
+impl Plugin for MQRustTasks {
+    fn new() -> Self {
+        MQRustTasks {}
+    }
+}
+
+#[macroquest::plugin::hooks]
+impl Hooks for MQRustTasks {
+    fn draw_hud_frame(&self, frame: &HudFrame) {
+        // The task journal belongs to the character, so there's nothing to
+        // draw until we're playing one.
+        if mq::in_game().is_none() {
+            return;
+        }
+
+        let mut y = TOP;
+        for task in mq::tasks() {
+            let title = match task.remaining() {
+                Some(remaining) => {
+                    format!("{task} ({}m left)", remaining.as_secs() / 60)
+                }
+                None => task.to_string(),
+            };
+            frame.draw_text(LEFT, y, Color::YELLOW, &title);
+            y += LINE_HEIGHT;
+
+            for objective in &task.objectives {
+                let color = if objective.is_complete() {
+                    Color::GREEN
+                }
+                else {
+                    Color::WHITE
+                };
+
+                frame.draw_text(LEFT + INDENT, y, color, &objective.to_string());
+                y += LINE_HEIGHT;
+            }
+        }
+    }
+}
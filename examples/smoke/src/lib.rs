@@ -0,0 +1,271 @@
+//! Smoke tests for built MacroQuest plugin DLLs.
+//!
+//! It's easy for a change to the plugin macros to silently stop exporting a
+//! symbol that MacroQuest requires, or to export one under the wrong name, and
+//! nothing will tell us until MacroQuest refuses to load the plugin (or simply
+//! never calls the hook). These checks load a built plugin and verify that
+//! every symbol is exported, and that the data symbols have the expected shape.
+
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+
+use std::fmt;
+use std::path::Path;
+
+use libloading::{Library, Symbol};
+
+/// The symbols that every plugin must export, which are always exported by
+/// `macroquest::plugin::setup!`.
+pub const REQUIRED: &[&str] = &[
+    "IsBuiltForNext",
+    "EverQuestVersion",
+    "ThisPlugin",
+    "InitializePlugin",
+    "ShutdownPlugin",
+    "OnPulse",
+    "OnUpdateImGui",
+    "OnCleanUI",
+    "OnReloadUI",
+];
+
+/// Every hook that a plugin may export, other than the [`REQUIRED`] ones.
+pub const HOOKS: &[&str] = &[
+    "OnDrawHUD",
+    "OnBeginZone",
+    "OnEndZone",
+    "OnZoned",
+    "SetGameState",
+    "OnWriteChatColor",
+    "OnIncomingChat",
+    "OnAddSpawn",
+    "OnRemoveSpawn",
+    "OnAddGroundItem",
+    "OnRemoveGroundItem",
+    "OnMacroStart",
+    "OnMacroStop",
+    "OnLoadPlugin",
+    "OnUnloadPlugin",
+];
+
+/// The hooks that take no arguments, and are thus safe to call blindly.
+///
+/// This deliberately excludes `InitializePlugin` and `ShutdownPlugin`, which
+/// require MacroQuest to actually be running.
+pub const NULLARY: &[&str] = &[
+    "OnPulse",
+    "OnDrawHUD",
+    "OnBeginZone",
+    "OnEndZone",
+    "OnZoned",
+    "OnUpdateImGui",
+    "OnCleanUI",
+    "OnReloadUI",
+];
+
+/// The problems found with a plugin.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Symbols that should have been exported, but weren't.
+    pub missing:    Vec<String>,
+    /// Hooks that were exported, but that the plugin doesn't implement.
+    pub unexpected: Vec<String>,
+    /// Symbols that were exported, but with the wrong shape.
+    pub malformed:  Vec<String>,
+}
+
+impl Report {
+    /// Whether no problems were found.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty()
+            && self.unexpected.is_empty()
+            && self.malformed.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return write!(f, "ok");
+        }
+
+        for symbol in &self.missing {
+            writeln!(f, "missing: {symbol}")?;
+        }
+        for symbol in &self.unexpected {
+            writeln!(f, "unexpected: {symbol}")?;
+        }
+        for problem in &self.malformed {
+            writeln!(f, "malformed: {problem}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Load the plugin at `path`, and check that it exports all of the
+/// [`REQUIRED`] symbols, plus exactly the `hooks` given.
+///
+/// # Errors
+///
+/// Returns an error if the plugin could not be loaded.
+pub fn check<P>(path: P, hooks: &[&str]) -> Result<Report, libloading::Error>
+where
+    P: AsRef<Path>,
+{
+    // SAFETY: Loading a plugin runs its initialization routines, which for a
+    //         plugin built with this crate doesn't do anything beyond what the
+    //         Rust runtime does.
+    let library = unsafe { Library::new(path.as_ref())? };
+    let mut report = Report::default();
+
+    for symbol in REQUIRED.iter().chain(hooks) {
+        if !has_symbol(&library, symbol) {
+            report.missing.push((*symbol).to_owned());
+        }
+    }
+
+    for symbol in HOOKS {
+        if !hooks.contains(symbol) && has_symbol(&library, symbol) {
+            report.unexpected.push((*symbol).to_owned());
+        }
+    }
+
+    // SAFETY: This is exported by setup! as a `bool`.
+    if let Ok(value) = unsafe { library.get::<*const u8>(b"IsBuiltForNext\0") } {
+        // SAFETY: The symbol points to a bool, which is a single byte.
+        match unsafe { **value } {
+            1 => {}
+            0 => report.malformed.push("IsBuiltForNext is false".to_owned()),
+            other => report
+                .malformed
+                .push(format!("IsBuiltForNext is not a bool ({other})")),
+        }
+    }
+
+    // SAFETY: This is exported by setup! as an `EQVersion`, which is a
+    //         transparent wrapper around a [u8; 21].
+    if let Ok(value) = unsafe { library.get::<*const [u8; 21]>(b"EverQuestVersion\0") }
+    {
+        // SAFETY: The symbol points to a [u8; 21].
+        let version = unsafe { **value };
+        if let Err(problem) = check_eq_version(&version) {
+            report.malformed.push(format!("EverQuestVersion {problem}"));
+        }
+    }
+
+    Ok(report)
+}
+
+/// Load the plugin at `path`, and call the nullary hook named `symbol`.
+///
+/// This is intended to be run in a child process, as a plugin that misbehaves
+/// will crash the process.
+///
+/// # Errors
+///
+/// Returns an error if the plugin could not be loaded, or if it doesn't export
+/// `symbol`.
+///
+/// # Panics
+///
+/// Panics if `symbol` isn't one of the [`NULLARY`] hooks.
+pub fn call<P>(path: P, symbol: &str) -> Result<(), libloading::Error>
+where
+    P: AsRef<Path>,
+{
+    assert!(
+        NULLARY.contains(&symbol),
+        "{symbol} is not a nullary hook, and cannot be called"
+    );
+
+    // SAFETY: See check().
+    let library = unsafe { Library::new(path.as_ref())? };
+
+    // SAFETY: Every one of the nullary hooks is exported by the plugin macros
+    //         as an `extern "C" fn()`.
+    let hook: Symbol<extern "C" fn()> =
+        unsafe { library.get(format!("{symbol}\0").as_bytes())? };
+    hook();
+
+    Ok(())
+}
+
+fn has_symbol(library: &Library, symbol: &str) -> bool {
+    // SAFETY: We never use the symbol, we only check that it exists.
+    unsafe { library.get::<*const ()>(format!("{symbol}\0").as_bytes()) }.is_ok()
+}
+
+/// Check that `version` looks like `Jan 02 2006 15:04:05` followed by a null
+/// byte.
+fn check_eq_version(version: &[u8; 21]) -> Result<(), String> {
+    const SHAPE: &[u8; 21] = b"Aaa 00 0000 00:00:00\0";
+
+    let matches = version.iter().zip(SHAPE).all(|(c, s)| match s {
+        b'A' => c.is_ascii_uppercase(),
+        b'a' => c.is_ascii_lowercase(),
+        // Single digit days are padded with a space rather than a zero.
+        b'0' => c.is_ascii_digit() || *c == b' ',
+        _ => c == s,
+    });
+
+    if matches {
+        Ok(())
+    }
+    else {
+        Err(format!(
+            "is not a build date and time ({:?})",
+            String::from_utf8_lossy(version)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_eq_version() {
+        assert!(check_eq_version(b"Jan 02 2006 15:04:05\0").is_ok());
+        assert!(check_eq_version(b"Feb  2 2006 15:04:05\0").is_ok());
+        assert!(check_eq_version(b"Jan 02 2006 15:04:05x").is_err());
+        assert!(check_eq_version(b"jan 02 2006 15:04:05\0").is_err());
+        assert!(check_eq_version(b"Jan 02 2006T15:04:05\0").is_err());
+        assert!(check_eq_version(&[0; 21]).is_err());
+    }
+
+    #[test]
+    fn test_hook_lists_are_disjoint() {
+        for hook in HOOKS {
+            assert!(!REQUIRED.contains(hook), "{hook} is in HOOKS and REQUIRED");
+        }
+
+        for hook in NULLARY {
+            assert!(
+                REQUIRED.contains(hook) || HOOKS.contains(hook),
+                "{hook} is not a known hook"
+            );
+        }
+    }
+
+    #[test]
+    fn test_report_display() {
+        let report = Report {
+            missing:    vec!["OnZoned".into()],
+            unexpected: vec!["OnPulse".into()],
+            malformed:  vec!["IsBuiltForNext is false".into()],
+        };
+
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.to_string(),
+            "missing: OnZoned\nunexpected: OnPulse\nmalformed: IsBuiltForNext is false\n"
+        );
+        assert_eq!(Report::default().to_string(), "ok");
+    }
+}
@@ -0,0 +1,39 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["check", path, hooks @ ..] => match examples_smoke::check(path, hooks) {
+            Ok(report) if report.is_ok() => {
+                println!("{path}: ok");
+                ExitCode::SUCCESS
+            }
+            Ok(report) => {
+                eprint!("{path}:\n{report}");
+                ExitCode::FAILURE
+            }
+            Err(error) => {
+                eprintln!("{path}: {error}");
+                ExitCode::FAILURE
+            }
+        },
+        ["call", path, symbol] => match examples_smoke::call(path, symbol) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("{path}: {error}");
+                ExitCode::FAILURE
+            }
+        },
+        _ => {
+            eprintln!("usage: examples-smoke check <plugin.dll> [hooks...]");
+            eprintln!("       examples-smoke call <plugin.dll> <hook>");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use examples_smoke::{check, HOOKS, NULLARY, REQUIRED};
+
+/// Build the example plugin `name`, returning the path to its DLL.
+fn build(name: &str) -> PathBuf {
+    let workspace = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "-p", name])
+        .current_dir(&workspace)
+        .status()
+        .expect("could not run cargo");
+    assert!(status.success(), "failed to build {name}");
+
+    let target = std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| workspace.join("target"), PathBuf::from);
+
+    target
+        .join("debug")
+        .join(libloading::library_filename(name))
+}
+
+/// Check the plugin `name`, which should export exactly `hooks`, and then call
+/// each of its nullary hooks in a child process.
+fn smoke(name: &str, hooks: &[&str]) {
+    let path = build(name);
+
+    let report = check(&path, hooks).expect("could not load plugin");
+    assert!(report.is_ok(), "{name}:\n{report}");
+
+    for symbol in NULLARY {
+        if !hooks.contains(symbol) && !REQUIRED.contains(symbol) {
+            continue;
+        }
+
+        let status = Command::new(env!("CARGO_BIN_EXE_examples-smoke"))
+            .arg("call")
+            .arg(&path)
+            .arg(symbol)
+            .status()
+            .expect("could not run examples-smoke");
+        assert!(status.success(), "{name}: calling {symbol} failed");
+    }
+}
+
+#[test]
+fn test_mqrustbasic() {
+    // MQRustBasic implements every hook.
+    smoke("MQRustBasic", HOOKS);
+}
+
+#[test]
+fn test_mqrustasync() {
+    smoke("MQRustAsync", &[]);
+}
+
+#[test]
+fn test_mqrustdatatype() {
+    smoke("MQRustDataType", &[]);
+}
+
+#[test]
+fn test_mqrustecho() {
+    smoke("MQRustEcho", &["OnWriteChatColor"]);
+}
+
+#[test]
+fn test_mqrusthud() {
+    smoke("MQRustHud", &["OnDrawHUD"]);
+}
+
+#[test]
+fn test_mqrustloot() {
+    smoke("MQRustLoot", &[]);
+}
+
+#[test]
+fn test_mqrustnavfollow() {
+    smoke("MQRustNavFollow", &["OnPulse"]);
+}
+
+#[test]
+fn test_mqrustping() {
+    smoke("MQRustPing", &[]);
+}
+
+#[test]
+fn test_mqrustpong() {
+    smoke("MQRustPong", &[]);
+}
+
+#[test]
+fn test_mqrustprofile() {
+    smoke("MQRustProfile", &[]);
+}
+
+#[test]
+fn test_mqrusttasks() {
+    smoke("MQRustTasks", &["OnDrawHUD"]);
+}
@@ -0,0 +1,128 @@
+//! The stable interface that macroquest-rs plugins use to talk to each other.
+//!
+//! Rust doesn't have a stable ABI, so two plugins that were compiled separately
+//! (perhaps with different versions of Rust, or of `macroquest`) can't safely
+//! pass Rust types to each other. Instead, a plugin exports an [`Interface`],
+//! which only uses `repr(C)` types, from its DLL under the name
+//! [`EXPORT_SYMBOL`], and other plugins look it up through MacroQuest.
+//!
+//! Plugins shouldn't normally need to use this crate directly, as the
+//! `interface` feature of `macroquest` both exports and imports it.
+//!
+//! # Versions
+//!
+//! [`Interface::version`] is always the first field of the [`Interface`], and
+//! is the only part of it that may be read before checking that it matches
+//! [`VERSION`]. Any change to the layout of the [`Interface`] changes
+//! [`VERSION`], and plugins refuse to use an interface with a different version
+//! than their own.
+
+#![warn(missing_docs)]
+#![warn(clippy::cargo)]
+#![warn(clippy::correctness)]
+#![warn(clippy::suspicious)]
+#![warn(clippy::complexity)]
+#![warn(clippy::perf)]
+#![warn(clippy::style)]
+#![warn(clippy::pedantic)]
+#![no_std]
+
+use core::ffi::c_void;
+
+/// The version of the [`Interface`] declared by this crate.
+pub const VERSION: u32 = 1;
+
+/// The name that the function returning a plugin's [`Interface`] is exported
+/// under.
+pub const EXPORT_SYMBOL: &str = "MQRustInterface";
+
+/// The function exported as [`EXPORT_SYMBOL`], which returns a pointer to the
+/// plugin's [`Interface`].
+///
+/// The pointer is valid for as long as the plugin is loaded.
+pub type ExportFn = unsafe extern "C" fn() -> *const Interface;
+
+/// A function that's called with each of a plugin's capabilities, along with
+/// the `context` that was given alongside it.
+pub type VisitFn = unsafe extern "C" fn(context: *mut c_void, capability: Bytes);
+
+/// A borrowed slice of bytes, which is only valid for the duration of the call
+/// that it's passed to.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Bytes {
+    /// A pointer to the first byte, which may be null if `len` is 0.
+    pub ptr: *const u8,
+    /// The number of bytes.
+    pub len: usize,
+}
+
+impl Bytes {
+    /// Borrow `bytes`.
+    #[must_use]
+    pub const fn new(bytes: &[u8]) -> Bytes {
+        Bytes {
+            ptr: bytes.as_ptr(),
+            len: bytes.len(),
+        }
+    }
+
+    /// The bytes, as a slice.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` bytes (or `len` must be 0) that stay valid,
+    /// and unchanged, for as long as the returned slice is used.
+    #[must_use]
+    pub unsafe fn as_slice<'a>(self) -> &'a [u8] {
+        if self.ptr.is_null() || self.len == 0 {
+            return &[];
+        }
+
+        // SAFETY: Our caller guarantees that ptr points to len valid bytes.
+        unsafe { core::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+/// The interface that a plugin exports for other plugins to use.
+///
+/// Every function must be called from the game's main thread.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Interface {
+    /// The [`VERSION`] of the interface.
+    pub version:      u32,
+    /// Send the message `payload` to the plugin, from the plugin named
+    /// `sender`, returning whether the plugin accepted it.
+    pub send:         unsafe extern "C" fn(sender: Bytes, payload: Bytes) -> bool,
+    /// Call `visit` with each of the plugin's capabilities, which are strings
+    /// that describe what messages it understands.
+    pub capabilities: unsafe extern "C" fn(context: *mut c_void, visit: VisitFn),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let payload = b"hello";
+        let bytes = Bytes::new(payload);
+
+        assert_eq!(bytes.len, 5);
+        // SAFETY: The bytes borrow payload, which outlives them.
+        assert_eq!(unsafe { bytes.as_slice() }, payload);
+    }
+
+    #[test]
+    fn test_bytes_empty() {
+        let null = Bytes {
+            ptr: core::ptr::null(),
+            len: 0,
+        };
+
+        // SAFETY: An empty slice never reads through the pointer.
+        assert!(unsafe { null.as_slice() }.is_empty());
+        assert!(unsafe { Bytes::new(&[]).as_slice() }.is_empty());
+    }
+}
@@ -0,0 +1,370 @@
+use heck::ToKebabCase;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    Data,
+    DeriveInput,
+    Error,
+    Fields,
+    GenericArgument,
+    PathArguments,
+    Result,
+    Type,
+};
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Required,
+    Optional,
+    Rest,
+}
+
+impl Kind {
+    /// The kind of argument that a field of type `ty` is.
+    fn of(ty: &Type) -> Kind {
+        let Type::Path(path) = ty
+        else {
+            return Kind::Required;
+        };
+
+        let Some(segment) = path.path.segments.last()
+        else {
+            return Kind::Required;
+        };
+
+        let has_one_type_argument = matches!(
+            &segment.arguments,
+            PathArguments::AngleBracketed(args)
+                if args.args.len() == 1
+                    && matches!(args.args.first(), Some(GenericArgument::Type(_)))
+        );
+
+        match segment.ident.to_string().as_str() {
+            "Option" if has_one_type_argument => Kind::Optional,
+            "Vec" if has_one_type_argument => Kind::Rest,
+            _ => Kind::Required,
+        }
+    }
+}
+
+/// Expand `#[derive(CommandArgs)]`.
+pub(crate) fn command_args(input: &DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data
+    else {
+        return Err(Error::new_spanned(
+            input,
+            "CommandArgs can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields
+    else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "CommandArgs can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut parsed = vec![];
+    let mut usage = vec![];
+    let mut previous = None;
+
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named fields have names");
+        let name = ident.to_string();
+        let name = name.trim_start_matches("r#");
+        let kind = Kind::of(&field.ty);
+
+        match (&previous, &kind) {
+            (Some(Kind::Rest), _) => {
+                return Err(Error::new_spanned(
+                    field,
+                    "a Vec field consumes the remaining arguments, so it must be the last field",
+                ));
+            }
+            (Some(Kind::Optional), Kind::Required) => {
+                return Err(Error::new_spanned(
+                    field,
+                    "a required argument can't follow an optional one",
+                ));
+            }
+            _ => {}
+        }
+
+        let (method, text) = match kind {
+            Kind::Required => (quote!(required), format!("<{name}>")),
+            Kind::Optional => (quote!(optional), format!("[{name}]")),
+            Kind::Rest => (quote!(rest), format!("[{name}...]")),
+        };
+
+        parsed.push(quote! { #ident: parser.#method(#name)? });
+        usage.push(text);
+        previous = Some(kind);
+    }
+
+    let ty = &input.ident;
+    let usage = usage.join(" ");
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::macroquest::mq::commands::CommandArgs for #ty #ty_generics #where_clause {
+            fn parse(
+                args: &[::std::string::String],
+            ) -> ::std::result::Result<Self, ::macroquest::mq::commands::ArgsError> {
+                let mut parser = ::macroquest::mq::commands::Parser::new(args);
+                let value = #ty { #(#parsed,)* };
+
+                parser.finish()?;
+                ::std::result::Result::Ok(value)
+            }
+
+            fn usage() -> ::std::string::String {
+                ::std::string::String::from(#usage)
+            }
+        }
+    })
+}
+
+/// Expand `#[derive(ArgValue)]`.
+pub(crate) fn arg_value(input: &DeriveInput) -> Result<TokenStream> {
+    let Data::Enum(data) = &input.data
+    else {
+        return Err(Error::new_spanned(
+            input,
+            "ArgValue can only be derived for enums",
+        ));
+    };
+
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            input,
+            "ArgValue can't be derived for an enum without variants",
+        ));
+    }
+
+    let mut arms = vec![];
+    let mut names = vec![];
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "ArgValue can only be derived for enums with unit variants",
+            ));
+        }
+
+        let ident = &variant.ident;
+        let name = ident.to_string().to_kebab_case();
+
+        arms.push(quote! {
+            if arg.eq_ignore_ascii_case(#name) {
+                return ::std::option::Option::Some(Self::#ident);
+            }
+        });
+        names.push(name);
+    }
+
+    let ty = &input.ident;
+    let expected = format!("one of {}", names.join(", "));
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::macroquest::mq::commands::ArgValue for #ty #ty_generics #where_clause {
+            fn parse_arg(arg: &str) -> ::std::option::Option<Self> {
+                #(#arms)*
+                ::std::option::Option::None
+            }
+
+            fn expected() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#expected)
+            }
+        }
+    })
+}
+
+/// Expand a derive, turning any error into a `compile_error!`.
+pub(crate) fn expand<F>(input: &DeriveInput, derive: F) -> TokenStream
+where
+    F: FnOnce(&DeriveInput) -> Result<TokenStream>,
+{
+    derive(input).unwrap_or_else(|error| error.into_compile_error().into_token_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn assert_tokens_eq(actual: &TokenStream, expected: &TokenStream) {
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    fn error(result: Result<TokenStream>) -> String {
+        result.expect_err("expected an error").to_string()
+    }
+
+    #[test]
+    fn test_kind_of() {
+        assert_eq!(Kind::of(&parse_quote!(u32)), Kind::Required);
+        assert_eq!(Kind::of(&parse_quote!(String)), Kind::Required);
+        assert_eq!(Kind::of(&parse_quote!(Option<u32>)), Kind::Optional);
+        assert_eq!(
+            Kind::of(&parse_quote!(std::option::Option<u32>)),
+            Kind::Optional
+        );
+        assert_eq!(Kind::of(&parse_quote!(Vec<String>)), Kind::Rest);
+        assert_eq!(Kind::of(&parse_quote!(&'static str)), Kind::Required);
+    }
+
+    #[test]
+    fn test_command_args_expansion() {
+        let input: DeriveInput = parse_quote! {
+            struct Args {
+                count: u32,
+                r#type: Option<Mode>,
+                rest: Vec<String>,
+            }
+        };
+
+        assert_tokens_eq(
+            &command_args(&input).unwrap(),
+            &quote! {
+                impl ::macroquest::mq::commands::CommandArgs for Args {
+                    fn parse(
+                        args: &[::std::string::String],
+                    ) -> ::std::result::Result<Self, ::macroquest::mq::commands::ArgsError> {
+                        let mut parser = ::macroquest::mq::commands::Parser::new(args);
+                        let value = Args {
+                            count: parser.required("count")?,
+                            r#type: parser.optional("type")?,
+                            rest: parser.rest("rest")?,
+                        };
+
+                        parser.finish()?;
+                        ::std::result::Result::Ok(value)
+                    }
+
+                    fn usage() -> ::std::string::String {
+                        ::std::string::String::from("<count> [type] [rest...]")
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_command_args_generics() {
+        let input: DeriveInput = parse_quote! {
+            struct Args<T: ArgValue> {
+                value: T,
+            }
+        };
+
+        let expanded = command_args(&input).unwrap().to_string();
+        assert!(expanded.starts_with(
+            &quote!(impl<T: ArgValue> ::macroquest::mq::commands::CommandArgs for Args<T>)
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn test_command_args_errors() {
+        assert_eq!(
+            error(command_args(&parse_quote!(
+                enum Args {}
+            ))),
+            "CommandArgs can only be derived for structs"
+        );
+        assert_eq!(
+            error(command_args(&parse_quote!(
+                struct Args(u32);
+            ))),
+            "CommandArgs can only be derived for structs with named fields"
+        );
+        assert_eq!(
+            error(command_args(&parse_quote!(
+                struct Args {
+                    a: Option<u32>,
+                    b: u32,
+                }
+            ))),
+            "a required argument can't follow an optional one"
+        );
+        assert_eq!(
+            error(command_args(&parse_quote!(
+                struct Args {
+                    a: Vec<u32>,
+                    b: Option<u32>,
+                }
+            ))),
+            "a Vec field consumes the remaining arguments, so it must be the last field"
+        );
+    }
+
+    #[test]
+    fn test_arg_value_expansion() {
+        let input: DeriveInput = parse_quote! {
+            enum Mode {
+                Fast,
+                SlowAndSteady,
+            }
+        };
+
+        assert_tokens_eq(
+            &arg_value(&input).unwrap(),
+            &quote! {
+                impl ::macroquest::mq::commands::ArgValue for Mode {
+                    fn parse_arg(arg: &str) -> ::std::option::Option<Self> {
+                        if arg.eq_ignore_ascii_case("fast") {
+                            return ::std::option::Option::Some(Self::Fast);
+                        }
+                        if arg.eq_ignore_ascii_case("slow-and-steady") {
+                            return ::std::option::Option::Some(Self::SlowAndSteady);
+                        }
+                        ::std::option::Option::None
+                    }
+
+                    fn expected() -> ::std::borrow::Cow<'static, str> {
+                        ::std::borrow::Cow::Borrowed("one of fast, slow-and-steady")
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_arg_value_errors() {
+        assert_eq!(
+            error(arg_value(&parse_quote!(
+                struct Mode;
+            ))),
+            "ArgValue can only be derived for enums"
+        );
+        assert_eq!(
+            error(arg_value(&parse_quote!(
+                enum Mode {}
+            ))),
+            "ArgValue can't be derived for an enum without variants"
+        );
+        assert_eq!(
+            error(arg_value(&parse_quote!(
+                enum Mode {
+                    Fast,
+                    Slow(u32),
+                }
+            ))),
+            "ArgValue can only be derived for enums with unit variants"
+        );
+    }
+
+    #[test]
+    fn test_expand_reports_errors() {
+        let input: DeriveInput = parse_quote!(
+            struct Mode;
+        );
+
+        let expanded = expand(&input, arg_value).to_string();
+        assert!(expanded.contains("compile_error"), "{expanded}");
+    }
+}
@@ -10,7 +10,9 @@ use proc_macro::TokenStream;
 use proc_macro_error::{abort_call_site, proc_macro_error};
 use quote::quote;
 
+mod commands;
 mod plugin;
+mod settings;
 
 /// Defines the plugin hooks for an `impl Hooks` block.
 ///
@@ -26,7 +28,7 @@ mod plugin;
 /// # use macroquest::plugin::Hooks;
 /// # use macroquest_proc_macros::plugin_hooks as hooks;
 /// # use std::sync::OnceLock;
-/// # static PLUGIN: OnceLock<MyPlugin> = OnceLock::new();
+/// # static __MACROQUEST_PLUGIN: OnceLock<MyPlugin> = OnceLock::new();
 /// struct MyPlugin;
 ///
 /// #[hooks]
@@ -45,3 +47,124 @@ pub fn plugin_hooks(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     quote! { #hooks }.into()
 }
+
+/// Declares a function as the handler of a MacroQuest command.
+///
+/// The function is called with the command's arguments whenever the command
+/// is used. The command is added when the plugin is initialized, and removed
+/// when it's shut down, by the glue that `macroquest::plugin::setup!`
+/// generates.
+///
+/// The command must start with `/`, and declaring the same command twice in
+/// one module fails to compile (ignoring case, as MacroQuest does).
+///
+/// # Examples
+///
+/// ```
+/// # use macroquest::plugin::{Hooks, Plugin};
+/// # struct MyPlugin;
+/// # impl Plugin for MyPlugin {
+/// #     fn new() -> Self {
+/// #         MyPlugin
+/// #     }
+/// # }
+/// # impl Hooks for MyPlugin {}
+/// # macroquest::plugin::setup!(MyPlugin);
+/// #[macroquest::plugin::command("/hello")]
+/// fn hello(args: &str) {
+///     macroquest::mq::write_chat(format!("hello {args}"));
+/// }
+/// ```
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn plugin_command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let name = syn::parse_macro_input!(attr as syn::LitStr);
+    let body = syn::parse_macro_input!(item as syn::ItemFn);
+
+    match plugin::command::Command::new(name, body) {
+        Ok(command) => quote! { #command }.into(),
+        Err(error) => error.into_compile_error().into(),
+    }
+}
+
+/// Derives `macroquest::mq::commands::CommandArgs` for a struct, parsing each
+/// of its fields from a command's arguments in order.
+///
+/// A field of type `Option<T>` is an optional argument, a field of type
+/// `Vec<T>` takes all of the remaining arguments, and a field of any other
+/// type is a required argument.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::mq::commands::CommandArgs;
+///
+/// #[derive(macroquest::CommandArgs)]
+/// struct SayArgs {
+///     times: Option<u32>,
+///     words: Vec<String>,
+/// }
+///
+/// assert_eq!(SayArgs::usage(), "[times] [words...]");
+/// ```
+#[proc_macro_derive(CommandArgs)]
+pub fn command_args(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+
+    commands::expand(&input, commands::command_args).into()
+}
+
+/// Derives `macroquest::mq::commands::ArgValue` for an enum of unit variants,
+/// parsing each variant (ignoring case) from its name in kebab-case.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::mq::commands::ArgValue;
+///
+/// #[derive(Debug, PartialEq, macroquest::ArgValue)]
+/// enum Stance {
+///     Defensive,
+///     AllOut,
+/// }
+///
+/// assert_eq!(Stance::parse_arg("all-out"), Some(Stance::AllOut));
+/// ```
+#[proc_macro_derive(ArgValue)]
+pub fn arg_value(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+
+    commands::expand(&input, commands::arg_value).into()
+}
+
+/// Derives `macroquest::config::SettingsUi`, drawing an ImGui widget for each
+/// field of a struct, or a combo box choosing between the unit variants of an
+/// enum.
+///
+/// Fields and variants are labelled with their name in title case. The label
+/// can be replaced with `#[settings(label = "...")]`, a number can be limited
+/// to a range with `#[settings(range = min..=max)]`, and a field can be left
+/// out with `#[settings(skip)]`.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(macroquest::SettingsUi)]
+/// enum Mode {
+///     Passive,
+///     Aggressive,
+/// }
+///
+/// #[derive(macroquest::SettingsUi)]
+/// struct Settings {
+///     enabled: bool,
+///     mode:    Mode,
+///     #[settings(label = "Pull Radius", range = 10..=500)]
+///     radius:  u32,
+/// }
+/// ```
+#[proc_macro_derive(SettingsUi, attributes(settings))]
+pub fn settings_ui(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    commands::expand(&input, settings::settings_ui).into()
+}
@@ -0,0 +1,157 @@
+use std::fmt::Write;
+
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+use syn::{Error, ItemFn, LitStr, Result};
+
+pub(crate) struct Command {
+    name: LitStr,
+    body: ItemFn,
+}
+
+impl Command {
+    pub(crate) fn new(name: LitStr, body: ItemFn) -> Result<Command> {
+        let value = name.value();
+        let Some(rest) = value.strip_prefix('/')
+        else {
+            return Err(Error::new(name.span(), "The command must start with `/`"));
+        };
+
+        if rest.is_empty() || rest.contains(char::is_whitespace) {
+            return Err(Error::new(
+                name.span(),
+                "The command must have a name after the `/`, without any whitespace",
+            ));
+        }
+
+        Ok(Command { name, body })
+    }
+
+    /// The name of a constant that's unique to the command, so that declaring
+    /// the same command twice in one module fails to compile. MacroQuest
+    /// ignores the case of commands, so this does too.
+    fn marker(&self) -> proc_macro2::Ident {
+        let mut marker = String::from("__MACROQUEST_COMMAND_");
+        for c in self.name.value().chars().skip(1) {
+            if c.is_ascii_alphanumeric() {
+                marker.push(c.to_ascii_uppercase());
+            }
+            else {
+                write!(marker, "_{:X}_", u32::from(c)).unwrap();
+            }
+        }
+
+        format_ident!("{}", marker, span = self.name.span())
+    }
+}
+
+impl ToTokens for Command {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.body.to_tokens(tokens);
+
+        let name = &self.name;
+        let handler = &self.body.sig.ident;
+        let marker = self.marker();
+        let check = quote_spanned! {self.body.sig.span()=>
+            let _: fn(&::std::primitive::str) = #handler;
+        };
+
+        quote! {
+            #[doc(hidden)]
+            #[allow(non_upper_case_globals)]
+            const #marker: () = {
+                #check
+
+                // MSVC's C runtime calls every function in the .CRT$XCU
+                // section when the plugin is loaded, before InitializePlugin,
+                // which lets the command be declared without the plugin
+                // having to list its commands anywhere.
+                #[used]
+                #[link_section = ".CRT$XCU"]
+                static DECLARE: extern "C" fn() = {
+                    extern "C" fn declare() {
+                        ::macroquest::plugin::declare_command(#name, #handler);
+                    }
+
+                    declare
+                };
+            };
+        }
+        .to_tokens(tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn command(name: &str) -> Result<Command> {
+        Command::new(
+            LitStr::new(name, proc_macro2::Span::call_site()),
+            parse_quote! {
+                fn hello(args: &str) {}
+            },
+        )
+    }
+
+    fn error(name: &str) -> String {
+        match command(name) {
+            Ok(_) => panic!("expected an error for {name:?}"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_command_expansion() {
+        let command = command("/hello").unwrap();
+
+        assert_eq!(
+            quote!(#command).to_string(),
+            quote! {
+                fn hello(args: &str) {}
+
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const __MACROQUEST_COMMAND_HELLO: () = {
+                    let _: fn(&::std::primitive::str) = hello;
+
+                    #[used]
+                    #[link_section = ".CRT$XCU"]
+                    static DECLARE: extern "C" fn() = {
+                        extern "C" fn declare() {
+                            ::macroquest::plugin::declare_command("/hello", hello);
+                        }
+
+                        declare
+                    };
+                };
+            }
+            .to_string(),
+        );
+    }
+
+    #[test]
+    fn test_command_marker() {
+        let marker = |name| command(name).unwrap().marker().to_string();
+
+        assert_eq!(marker("/Foo"), marker("/foo"));
+        assert_eq!(marker("/foo"), "__MACROQUEST_COMMAND_FOO");
+        assert_eq!(marker("/foo-bar"), "__MACROQUEST_COMMAND_FOO_2D_BAR");
+        assert_ne!(marker("/foo-bar"), marker("/foo_bar"));
+    }
+
+    #[test]
+    fn test_command_errors() {
+        assert_eq!(error("hello"), "The command must start with `/`");
+        assert_eq!(error(""), "The command must start with `/`");
+
+        for name in ["/", "/hello world", "/hello\t"] {
+            assert_eq!(
+                error(name),
+                "The command must have a name after the `/`, without any whitespace"
+            );
+        }
+    }
+}
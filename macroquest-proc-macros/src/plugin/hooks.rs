@@ -17,7 +17,11 @@ enum Kind {
     OnCleanUI,
     #[strum(serialize = "reload_ui", to_string = "OnReloadUI")]
     OnReloadUI,
-    #[strum(serialize = "draw_hud", to_string = "OnDrawHUD")]
+    #[strum(
+        serialize = "draw_hud",
+        serialize = "draw_hud_frame",
+        to_string = "OnDrawHUD"
+    )]
     OnDrawHUD,
     #[strum(serialize = "game_state", to_string = "SetGameState")]
     SetGameState,
@@ -83,26 +87,44 @@ impl ToTokens for Hooks {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         self.body.to_tokens(tokens);
 
+        let mut exported = vec![];
+
         for hook in &self.implemented {
             let Ok(kind) = Kind::from_str(hook.sig.ident.to_string().as_str())
             else {
                 abort!(hook, "The hook must be a supported MacroQuest hook");
             };
 
+            // Some hooks have multiple methods (draw_hud and draw_hud_frame),
+            // which share a single exported function.
+            if exported.contains(&kind) {
+                continue;
+            }
+
             match kind {
-                // InitializePlugin and ShutdownPlugin are handled by
+                // InitializePlugin, ShutdownPlugin, OnPulse, OnUpdateImGui,
+                // OnCleanUI, and OnReloadUI are handled by
                 // macroquest::plugin::setup!.
-                Kind::InitializePlugin | Kind::ShutdownPlugin => quote! {},
+                Kind::InitializePlugin
+                | Kind::ShutdownPlugin
+                | Kind::OnPulse
+                | Kind::OnUpdateImGui
+                | Kind::OnCleanUI
+                | Kind::OnReloadUI => {
+                    quote! {}
+                }
                 // Everything else is handled here, and we just emit the private
                 // macroquest::plugin::hook! invocation for each defined hook.
                 _ => {
                     let hook_kind = format_ident!("{}", kind.to_string());
                     quote! {
-                        macroquest::plugin::hook!(#hook_kind(PLUGIN));
+                        ::macroquest::plugin::hook!(#hook_kind(__MACROQUEST_PLUGIN));
                     }
                 }
             }
             .to_tokens(tokens);
+
+            exported.push(kind);
         }
     }
 }
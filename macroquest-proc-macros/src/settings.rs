@@ -0,0 +1,325 @@
+use heck::ToTitleCase;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute,
+    Data,
+    DataEnum,
+    DataStruct,
+    DeriveInput,
+    Error,
+    Expr,
+    Fields,
+    LitStr,
+    Result,
+};
+
+/// The options given in the `#[settings(...)]` attributes of a field or
+/// variant.
+#[derive(Default)]
+struct Options {
+    label: Option<String>,
+    range: Option<Expr>,
+    skip:  bool,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> Result<Options> {
+        let mut options = Options::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("settings")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    options.label = Some(meta.value()?.parse::<LitStr>()?.value());
+                }
+                else if meta.path.is_ident("range") {
+                    options.range = Some(meta.value()?.parse()?);
+                }
+                else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                }
+                else {
+                    return Err(meta.error("expected `label`, `range`, or `skip`"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(options)
+    }
+
+    /// The label to use for something named `name`.
+    fn label(&self, name: &str) -> String {
+        self.label
+            .clone()
+            .unwrap_or_else(|| name.trim_start_matches("r#").to_title_case())
+    }
+}
+
+/// Expand `#[derive(SettingsUi)]`.
+pub(crate) fn settings_ui(input: &DeriveInput) -> Result<TokenStream> {
+    match &input.data {
+        Data::Struct(data) => struct_ui(input, data),
+        Data::Enum(data) => enum_ui(input, data),
+        Data::Union(_) => Err(Error::new_spanned(
+            input,
+            "SettingsUi can only be derived for structs and enums",
+        )),
+    }
+}
+
+/// Expand `#[derive(SettingsUi)]` for a struct, with a widget for each field.
+fn struct_ui(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream> {
+    let Fields::Named(fields) = &data.fields
+    else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "SettingsUi can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut widgets = vec![];
+    for field in &fields.named {
+        let options = Options::parse(&field.attrs)?;
+        if options.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named fields have names");
+        let label = options.label(&ident.to_string());
+
+        widgets.push(if let Some(range) = &options.range {
+            quote! {
+                changed |= ::macroquest::config::SettingsRange::settings_ui_range(
+                    &mut self.#ident, ui, #label, #range,
+                );
+            }
+        }
+        else {
+            quote! {
+                changed |= ::macroquest::config::SettingsUi::settings_ui(
+                    &mut self.#ident, ui, #label,
+                );
+            }
+        });
+    }
+
+    Ok(implement(
+        input,
+        &quote! {
+            fn settings_ui(&mut self, ui: &::macroquest::imgui::Ui, label: &str) -> bool {
+                let mut changed = false;
+                ui.collapsing_header(label, |ui| {
+                    changed = ::macroquest::config::SettingsUi::settings_fields(self, ui);
+                });
+                changed
+            }
+
+            fn settings_fields(&mut self, ui: &::macroquest::imgui::Ui) -> bool {
+                let mut changed = false;
+                #(#widgets)*
+                changed
+            }
+        },
+    ))
+}
+
+/// Expand `#[derive(SettingsUi)]` for an enum, as a combo box of its variants.
+fn enum_ui(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            input,
+            "SettingsUi can't be derived for an enum without variants",
+        ));
+    }
+
+    let mut indexes = vec![];
+    let mut variants = vec![];
+    let mut labels = vec![];
+
+    for (index, variant) in data.variants.iter().enumerate() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "SettingsUi can only be derived for enums with unit variants",
+            ));
+        }
+
+        let options = Options::parse(&variant.attrs)?;
+        if options.skip || options.range.is_some() {
+            return Err(Error::new_spanned(
+                variant,
+                "only `label` can be given for an enum variant",
+            ));
+        }
+
+        indexes.push(index);
+        variants.push(&variant.ident);
+        labels.push(options.label(&variant.ident.to_string()));
+    }
+
+    Ok(implement(
+        input,
+        &quote! {
+            fn settings_ui(&mut self, ui: &::macroquest::imgui::Ui, label: &str) -> bool {
+                let mut current = match self {
+                    #(Self::#variants => #indexes,)*
+                };
+
+                if !ui.combo(label, &[#(#labels),*], &mut current) {
+                    return false;
+                }
+
+                *self = match current {
+                    #(#indexes => Self::#variants,)*
+                    _ => return false,
+                };
+                true
+            }
+        },
+    ))
+}
+
+fn implement(input: &DeriveInput, body: &TokenStream) -> TokenStream {
+    let ty = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::macroquest::config::SettingsUi for #ty #ty_generics #where_clause {
+            #body
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn assert_tokens_eq(actual: &TokenStream, expected: &TokenStream) {
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    fn error(result: Result<TokenStream>) -> String {
+        result.expect_err("expected an error").to_string()
+    }
+
+    #[test]
+    fn test_struct_expansion() {
+        let input: DeriveInput = parse_quote! {
+            struct Settings {
+                max_distance: u32,
+                #[settings(label = "Say Hello", range = 1..=10)]
+                hello: u8,
+                #[settings(skip)]
+                cache: Vec<u32>,
+            }
+        };
+
+        assert_tokens_eq(
+            &settings_ui(&input).unwrap(),
+            &quote! {
+                impl ::macroquest::config::SettingsUi for Settings {
+                    fn settings_ui(&mut self, ui: &::macroquest::imgui::Ui, label: &str) -> bool {
+                        let mut changed = false;
+                        ui.collapsing_header(label, |ui| {
+                            changed = ::macroquest::config::SettingsUi::settings_fields(self, ui);
+                        });
+                        changed
+                    }
+
+                    fn settings_fields(&mut self, ui: &::macroquest::imgui::Ui) -> bool {
+                        let mut changed = false;
+                        changed |= ::macroquest::config::SettingsUi::settings_ui(
+                            &mut self.max_distance, ui, "Max Distance",
+                        );
+                        changed |= ::macroquest::config::SettingsRange::settings_ui_range(
+                            &mut self.hello, ui, "Say Hello", 1..=10,
+                        );
+                        changed
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_enum_expansion() {
+        let input: DeriveInput = parse_quote! {
+            enum Mode {
+                Passive,
+                #[settings(label = "All Out!")]
+                AllOut,
+            }
+        };
+
+        assert_tokens_eq(
+            &settings_ui(&input).unwrap(),
+            &quote! {
+                impl ::macroquest::config::SettingsUi for Mode {
+                    fn settings_ui(&mut self, ui: &::macroquest::imgui::Ui, label: &str) -> bool {
+                        let mut current = match self {
+                            Self::Passive => 0usize,
+                            Self::AllOut => 1usize,
+                        };
+
+                        if !ui.combo(label, &["Passive", "All Out!"], &mut current) {
+                            return false;
+                        }
+
+                        *self = match current {
+                            0usize => Self::Passive,
+                            1usize => Self::AllOut,
+                            _ => return false,
+                        };
+                        true
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            error(settings_ui(&parse_quote!(
+                struct Settings(u32);
+            ))),
+            "SettingsUi can only be derived for structs with named fields"
+        );
+        assert_eq!(
+            error(settings_ui(&parse_quote!(
+                enum Mode {}
+            ))),
+            "SettingsUi can't be derived for an enum without variants"
+        );
+        assert_eq!(
+            error(settings_ui(&parse_quote!(
+                enum Mode {
+                    Fast(u32),
+                }
+            ))),
+            "SettingsUi can only be derived for enums with unit variants"
+        );
+        assert_eq!(
+            error(settings_ui(&parse_quote!(
+                struct Settings {
+                    #[settings(colour = "red")]
+                    enabled: bool,
+                }
+            ))),
+            "expected `label`, `range`, or `skip`"
+        );
+        assert_eq!(
+            error(settings_ui(&parse_quote!(
+                enum Mode {
+                    #[settings(skip)]
+                    Fast,
+                }
+            ))),
+            "only `label` can be given for an enum variant"
+        );
+    }
+}
@@ -108,6 +108,14 @@ fn main() {
     println!("cargo:rerun-if-changed=src/eqlib.cc");
     println!("cargo:rerun-if-changed=include/mq.h");
     println!("cargo:rerun-if-changed=src/mq.cc");
+    println!("cargo:rerun-if-changed=include/imgui.h");
+    println!("cargo:rerun-if-changed=src/imgui.cc");
+    println!("cargo:rerun-if-changed=include/ui.h");
+    println!("cargo:rerun-if-changed=src/ui.cc");
+    println!("cargo:rerun-if-changed=include/postoffice.h");
+    println!("cargo:rerun-if-changed=src/postoffice.cc");
+    println!("cargo:rerun-if-changed=include/datatypes.h");
+    println!("cargo:rerun-if-changed=src/datatypes.cc");
 
     let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
 
@@ -124,13 +132,21 @@ fn main() {
         println!("cargo:rustc-link-lib=MQ2Main");
         println!("cargo:rustc-link-lib=eqlib");
         println!("cargo:rustc-link-lib=pluginapi");
+        println!("cargo:rustc-link-lib=imgui");
 
         // Build our bridge between C++ and Rust
         cxx_build::bridge("src/lib.rs")
             .std("c++17")
             .includes(config.include_dirs())
             .define("NOMINMAX", None)
-            .files(["src/eqlib.cc", "src/mq.cc"])
+            .files([
+                "src/eqlib.cc",
+                "src/mq.cc",
+                "src/imgui.cc",
+                "src/ui.cc",
+                "src/postoffice.cc",
+                "src/datatypes.cc",
+            ])
             .compile("mqrust");
 
         // Write out the EQVersion string
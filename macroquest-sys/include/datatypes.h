@@ -0,0 +1,25 @@
+#pragma once
+#include "rust/cxx.h"
+#pragma warning(push)
+#pragma warning(disable : 4100 4189 4201 4245 4458)
+#include "mq/Plugin.h"
+#pragma warning(pop)
+
+namespace mqrust
+{
+    namespace datatypes
+    {
+        struct MemberValue;
+
+        using GetMemberFn = rust::Fn<bool(rust::Str, rust::Str, rust::Str, MemberValue&)>;
+        using ToStringFn = rust::Fn<bool(rust::Str, rust::String&)>;
+
+        // DataType Functions
+        bool add_datatype(
+            rust::Str name,
+            rust::Slice<const rust::String> members,
+            GetMemberFn get_member,
+            ToStringFn to_string);
+        void remove_datatype(rust::Str name);
+    }
+}
@@ -9,16 +9,215 @@ namespace mqrust
 {
     namespace eqlib
     {
+        struct WorldTime;
+        struct AuraInfo;
+        struct BuffInfo;
+        struct Location;
+        struct FellowshipMemberInfo;
+        struct GroupMemberInfo;
+        struct CampfireInfo;
+        struct CoinInfo;
+        struct ZoneInfo;
+        struct DynamicZoneInfo;
+        struct DynamicZoneMemberInfo;
+        struct DynamicZoneTimerInfo;
+        struct RaidInfo;
+        struct RaidMemberInfo;
+        struct TaskInfo;
+        struct TaskObjectiveInfo;
+        struct SpellEffect;
+
+        class ItemClient;
+
+        bool get_world_time(WorldTime& time);
+
+        // Zone Functions
+        bool get_zone_name(rust::String& name);
+        uint32_t zone_count();
+        bool get_zone_info(uint32_t index, ZoneInfo& info);
+
+        class EQZoneInfo : ::eqlib::EQZoneInfo
+        {
+        public:
+            rust::Str name() const;
+            rust::Str short_name() const;
+            uint32_t id() const;
+            int32_t zone_type() const;
+        };
+
+        const EQZoneInfo* get_current_zone();
+
+        // Dynamic Zone Functions
+        bool get_dynamic_zone(DynamicZoneInfo& info);
+        uint32_t dynamic_zone_member_count();
+        bool get_dynamic_zone_member(uint32_t index, DynamicZoneMemberInfo& member);
+        uint32_t dynamic_zone_timer_count();
+        bool get_dynamic_zone_timer(uint32_t index, DynamicZoneTimerInfo& timer);
+
+        // Raid Functions
+        bool get_raid(RaidInfo& info);
+        uint32_t raid_member_count();
+        bool get_raid_member(uint32_t index, RaidMemberInfo& member);
+
+        // Task Functions
+        uint32_t task_count();
+        bool get_task(uint32_t index, TaskInfo& task);
+        uint32_t task_objective_count();
+        bool get_task_objective(uint32_t index, uint32_t objective_index, TaskObjectiveInfo& objective);
+
+        // Player Functions
+        bool get_player_location(Location& location);
+
         class PlayerClient : ::eqlib::PlayerClient
         {
         public:
             rust::Str name() const;
+            uint32_t spawn_id() const;
+            uint32_t master_id() const;
+            uint32_t pet_id() const;
+            int32_t spawn_type() const;
+            int32_t deity() const;
+            int32_t class_id() const;
+            int32_t race_id() const;
+            int32_t level() const;
+            rust::Str guild() const;
+            rust::Str title() const;
+            rust::Str suffix() const;
+            bool is_afk() const;
+            bool is_lfg() const;
+            bool is_trader() const;
+            bool is_buyer() const;
+            bool is_linkdead() const;
+            bool is_feigned() const;
+            bool is_invisible() const;
+            bool is_mounted() const;
+            float x() const;
+            float y() const;
+            float z() const;
+            float heading() const;
+            uint8_t stand_state() const;
+            int32_t casting_spell_id() const;
+            float speed_run() const;
+            float speed_multiplier() const;
+            int64_t hp_current() const;
+            int64_t hp_max() const;
+            int32_t mana_current() const;
+            int32_t mana_max() const;
+            int32_t endurance_current() const;
+            int32_t endurance_max() const;
+            const PlayerClient* next() const;
         };
 
+        // Spawn Functions
+        const PlayerClient* first_spawn();
+        const PlayerClient* get_local_player();
+
+        // Target Functions
+        const PlayerClient* get_target();
+        bool set_target(uint32_t spawn_id);
+        void clear_target();
+
         class EQGroundItem : ::eqlib::EQGroundItem
         {
         public:
             rust::Str name() const;
+            uint32_t item_id() const;
+            uint32_t icon_id() const;
+            uint32_t drop_id() const;
+            uint32_t zone_id() const;
+            float x() const;
+            float y() const;
+            float z() const;
+            float heading() const;
+            Location location() const;
+            const EQGroundItem* next() const;
+        };
+
+        // Ground Item Functions
+        const EQGroundItem* first_ground_item();
+
+        class PcClient : ::eqlib::PcClient
+        {
+        public:
+            rust::Str name() const;
+            int32_t aura_count() const;
+            bool aura(int32_t index, AuraInfo& aura) const;
+            int32_t fellowship_member_count() const;
+            bool fellowship_member(int32_t index, FellowshipMemberInfo& member) const;
+            bool campfire(CampfireInfo& campfire) const;
+            int32_t group_member_count() const;
+            bool group_member(int32_t index, GroupMemberInfo& member) const;
+            int32_t level() const;
+            int32_t class_id() const;
+            int32_t aa_points() const;
+            int32_t aa_points_spent() const;
+            int32_t buff_count() const;
+            bool buff(int32_t index, BuffInfo& buff) const;
+            int32_t song_buff_count() const;
+            bool song_buff(int32_t index, BuffInfo& buff) const;
+            int64_t experience() const;
+            int32_t aa_experience() const;
+            uint32_t zone_id() const;
+            const ItemClient* equipped_item(int32_t slot) const;
+            const ItemClient* cursor_item() const;
+            CoinInfo carried_coin() const;
+            CoinInfo bank_coin() const;
+        };
+
+        // Character Functions
+        const PcClient* get_local_pc();
+
+        class EQSwitch : ::eqlib::EQSwitch
+        {
+        public:
+            int32_t id() const;
+            rust::Str name() const;
+            Location location() const;
+            uint8_t state() const;
+            bool click() const;
+        };
+
+        // Switch Functions
+        uint32_t switch_count();
+        const EQSwitch* get_switch(uint32_t index);
+
+        class ItemClient : ::eqlib::ItemClient
+        {
+        public:
+            rust::Str name() const;
+            int32_t id() const;
+            int32_t icon() const;
+            int32_t stack() const;
+            int32_t stack_max() const;
+            int32_t value_in_copper() const;
+            bool is_no_drop() const;
+            bool is_lore() const;
+            bool is_attunable() const;
+            bool is_container() const;
+            int32_t container_slots() const;
+            const ItemClient* item_in_slot(int32_t slot) const;
+        };
+
+        class EQ_Spell : ::eqlib::EQ_Spell
+        {
+        public:
+            uint32_t id() const;
+            rust::Str name() const;
+            int32_t mana_cost() const;
+            uint32_t cast_time_ms() const;
+            uint32_t recast_time_ms() const;
+            float range() const;
+            uint8_t spell_type() const;
+            uint8_t target_type() const;
+            uint8_t duration_formula() const;
+            int32_t duration_cap() const;
+            uint8_t class_level(int32_t class_id) const;
+            int32_t effect_count() const;
+            bool effect(int32_t index, SpellEffect& effect) const;
         };
+
+        // Spell Functions
+        const EQ_Spell* get_spell_by_id(uint32_t id);
+        const EQ_Spell* get_spell_by_name(rust::Str name);
     }
 }
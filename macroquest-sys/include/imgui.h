@@ -0,0 +1,66 @@
+#pragma once
+#include "rust/cxx.h"
+#pragma warning(push)
+#pragma warning(disable : 4100 4189 4201 4245 4458)
+#include <imgui.h>
+#pragma warning(pop)
+
+namespace mqrust
+{
+    namespace imgui
+    {
+        // Window Functions
+        bool begin(rust::Str name);
+        bool begin_closable(rust::Str name, bool& open);
+        void end();
+        bool begin_child(rust::Str id);
+        void end_child();
+
+        // Scrolling Functions
+        float get_scroll_y();
+        float get_scroll_max_y();
+        void set_scroll_here_y(float ratio);
+
+        // Menu Functions
+        bool begin_main_menu_bar();
+        void end_main_menu_bar();
+        bool begin_menu(rust::Str label);
+        void end_menu();
+        bool menu_item(rust::Str label, bool selected);
+
+        // Table Functions
+        bool begin_table(rust::Str id, int columns);
+        void table_setup_column(rust::Str label);
+        void table_headers_row();
+        void table_next_row();
+        bool table_next_column();
+        void end_table();
+
+        // Tree Functions
+        bool collapsing_header(rust::Str label);
+        void push_id(rust::Str id);
+        void pop_id();
+
+        // Combo Functions
+        bool begin_combo(rust::Str label, rust::Str preview);
+        bool selectable(rust::Str label, bool selected);
+        void end_combo();
+
+        // Widget Functions
+        void text(rust::Str text);
+        void text_disabled(rust::Str text);
+        void text_colored(float r, float g, float b, float a, rust::Str text);
+        bool button(rust::Str label);
+        bool radio_button(rust::Str label, bool active);
+        bool input_text(rust::Str label, rust::String& text);
+        bool checkbox(rust::Str label, bool& value);
+        bool drag_i64(rust::Str label, int64_t& value, int64_t min, int64_t max);
+        bool drag_f64(rust::Str label, double& value, double min, double max);
+        void separator();
+        void same_line();
+        void progress_bar(float fraction, rust::Str overlay);
+
+        // Clipboard Functions
+        void set_clipboard_text(rust::Str text);
+    }
+}
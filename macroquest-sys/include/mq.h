@@ -21,13 +21,47 @@ namespace mqrust
         rust::Str get_path_EverQuest();
 
         // General Functions
-        void write_chat_color(rust::Str line, int color);
+        void write_chat_color(rust::Slice<const uint8_t> line, int color);
+        void write_chat_lines(rust::Slice<const uint8_t> lines, int color);
+        void do_command(rust::Str command);
+        bool parse_macro_data(rust::Str expression, rust::String& result);
+        int32_t get_game_state();
+
+        // Plugin Functions
+        bool is_plugin_loaded(rust::Str name);
+        size_t get_plugin_proc(rust::Str name, rust::Str proc_name);
+        class MQPlugin;
+        const MQPlugin* first_plugin();
+
+        // HUD Functions
+        void draw_hud_text(rust::Str text, int x, int y, uint32_t argb);
+
+        // KeyBind Functions
+        bool is_valid_key_combo(rust::Str keys);
+        bool add_keybind(rust::Str name, rust::Str keys, rust::Fn<void(rust::Str, bool)> callback);
+        void remove_keybind(rust::Str name);
+
+        // Command Functions
+        size_t max_commands();
+        bool add_command(rust::Str name, bool parse, bool in_game, rust::Fn<void(rust::Str, rust::Slice<const uint8_t>)> callback);
+        void remove_command(rust::Str name);
+
+        // Benchmark Functions
+        uint32_t add_benchmark(rust::Str name);
+        void remove_benchmark(uint32_t id);
+        void enter_benchmark(uint32_t id);
+        void exit_benchmark(uint32_t id);
+
+        // Ground Item Functions
+        bool set_ground_target(uint32_t drop_id);
+        bool pick_up_ground_item(uint32_t drop_id);
 
         // MQPlugin Functions
         class MQPlugin : ::mq::MQPlugin
         {
         public:
             rust::Str plugin_name() const;
+            const MQPlugin* next() const;
         };
     }
 }
@@ -0,0 +1,23 @@
+#pragma once
+#include "rust/cxx.h"
+#pragma warning(push)
+#pragma warning(disable : 4100 4189 4201 4245 4458)
+#include "mq/Plugin.h"
+#pragma warning(pop)
+
+#include <cstdint>
+
+namespace mqrust
+{
+    namespace postoffice
+    {
+        struct ActorAddress;
+
+        // Dropbox Functions
+        bool add_dropbox(
+            rust::Str mailbox,
+            rust::Fn<void(rust::Str, const ActorAddress&, rust::Slice<const uint8_t>)> callback);
+        void remove_dropbox(rust::Str mailbox);
+        bool post(rust::Str mailbox, const ActorAddress& address, rust::Slice<const uint8_t> payload);
+    }
+}
@@ -0,0 +1,24 @@
+#pragma once
+#include "rust/cxx.h"
+#pragma warning(push)
+#pragma warning(disable : 4100 4189 4201 4245 4458)
+#include "mq/Plugin.h"
+#pragma warning(pop)
+
+#include <cstdint>
+
+namespace mqrust
+{
+    namespace ui
+    {
+        // XML Functions
+        void add_xml_file(rust::Str xml);
+        void remove_xml_file(rust::Str xml);
+
+        // Window Functions
+        uint64_t create_window(rust::Str screen);
+        void destroy_window(uint64_t id);
+        void set_window_visible(uint64_t id, bool visible);
+        bool set_window_child_text(uint64_t id, rust::Str child, rust::Str text);
+    }
+}
@@ -0,0 +1,188 @@
+#include "macroquest-sys/include/datatypes.h"
+#include "macroquest-sys/src/lib.rs.h"
+
+#include <algorithm>
+#include <memory>
+#include <string>
+#include <unordered_map>
+#include <vector>
+
+namespace mqrust
+{
+    namespace datatypes
+    {
+        namespace
+        {
+            // A datatype whose members and text come from Rust. MacroQuest
+            // looks members up by the names that are added here, which is also
+            // how Lua lists the members of a datatype, so macros and Lua both
+            // see exactly the members that were registered from Rust.
+            class RustDataType : public ::mq::MQ2Type
+            {
+            public:
+                RustDataType(
+                    const std::string& name,
+                    std::vector<std::string> members,
+                    GetMemberFn get_member,
+                    ToStringFn to_string)
+                    : MQ2Type(name.c_str())
+                    , m_name(name)
+                    , m_members(std::move(members))
+                    , m_get_member(get_member)
+                    , m_to_string(to_string)
+                {
+                    // MQ2Type keeps the pointers to the member names, which
+                    // live as long as we do.
+                    for (size_t i = 0; i < m_members.size(); ++i)
+                    {
+                        AddMember(static_cast<int>(i), m_members[i].c_str());
+                    }
+                }
+
+                bool GetMember(
+                    ::mq::MQVarPtr VarPtr,
+                    const char* Member,
+                    char* Index,
+                    ::mq::MQTypeVar& Dest) override
+                {
+                    auto member = FindMember(Member);
+                    if (!member)
+                    {
+                        return false;
+                    }
+
+                    MemberValue value{};
+                    if (!m_get_member(
+                            rust::Str(m_name),
+                            rust::Str(member->Name),
+                            rust::Str(Index ? Index : ""),
+                            value))
+                    {
+                        return false;
+                    }
+
+                    switch (value.kind)
+                    {
+                    case MemberKind::Int:
+                        Dest.Int64 = value.integer;
+                        Dest.Type = ::mq::datatypes::pInt64Type;
+                        return true;
+
+                    case MemberKind::Float:
+                        Dest.Double = value.number;
+                        Dest.Type = ::mq::datatypes::pDoubleType;
+                        return true;
+
+                    case MemberKind::Bool:
+                        Dest.Set(value.boolean);
+                        Dest.Type = ::mq::datatypes::pBoolType;
+                        return true;
+
+                    case MemberKind::Text:
+                        strncpy_s(
+                            ::mq::DataTypeTemp,
+                            value.text.data(),
+                            std::min(value.text.size(), sizeof(::mq::DataTypeTemp) - 1));
+                        Dest.Ptr = &::mq::DataTypeTemp[0];
+                        Dest.Type = ::mq::datatypes::pStringType;
+                        return true;
+
+                    default:
+                        // Anything else is NULL.
+                        return false;
+                    }
+                }
+
+                bool ToString(::mq::MQVarPtr VarPtr, char* Destination) override
+                {
+                    rust::String text;
+                    if (!m_to_string(rust::Str(m_name), text))
+                    {
+                        // Without any text of its own, a datatype is its name.
+                        strcpy_s(Destination, ::mq::MAX_STRING, m_name.c_str());
+                        return true;
+                    }
+
+                    strncpy_s(
+                        Destination,
+                        ::mq::MAX_STRING,
+                        text.data(),
+                        std::min(text.size(), static_cast<size_t>(::mq::MAX_STRING - 1)));
+                    return true;
+                }
+
+                // The datatype is a view of the plugin's data, which can't be
+                // set from a macro.
+                bool FromString(::mq::MQVarPtr& VarPtr, const char* Source) override
+                {
+                    return false;
+                }
+
+            private:
+                std::string m_name;
+                std::vector<std::string> m_members;
+                GetMemberFn m_get_member;
+                ToStringFn m_to_string;
+            };
+
+            // The datatypes we've added, keyed by their name, which is also the
+            // name of the top level object that returns them. MacroQuest calls
+            // into each of these, which calls into Rust, so every datatype must
+            // be removed before the plugin is unloaded.
+            std::unordered_map<std::string, std::unique_ptr<RustDataType>> datatypes;
+        }
+
+        // DataType Functions
+        bool add_datatype(
+            rust::Str name,
+            rust::Slice<const rust::String> members,
+            GetMemberFn get_member,
+            ToStringFn to_string)
+        {
+            std::string type_name(name);
+            if (datatypes.count(type_name)
+                || ::mq::FindMQ2DataType(type_name.c_str())
+                || ::mq::FindMQ2Data(type_name.c_str()))
+            {
+                return false;
+            }
+
+            std::vector<std::string> member_names;
+            for (const auto& member : members)
+            {
+                member_names.emplace_back(member);
+            }
+
+            auto datatype = std::make_unique<RustDataType>(
+                type_name, std::move(member_names), get_member, to_string);
+            auto type = datatype.get();
+
+            bool added = ::mq::AddTopLevelObject(
+                type_name.c_str(),
+                [type](const char* Index, ::mq::MQTypeVar& Dest) {
+                    Dest.DWord = 0;
+                    Dest.Type = type;
+                    return true;
+                });
+            if (!added)
+            {
+                return false;
+            }
+
+            datatypes.emplace(std::move(type_name), std::move(datatype));
+            return true;
+        }
+
+        void remove_datatype(rust::Str name)
+        {
+            std::string type_name(name);
+
+            auto it = datatypes.find(type_name);
+            if (it != datatypes.end())
+            {
+                ::mq::RemoveTopLevelObject(type_name.c_str());
+                datatypes.erase(it);
+            }
+        }
+    }
+}
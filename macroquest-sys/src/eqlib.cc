@@ -1,13 +1,934 @@
 #include "macroquest-sys/include/eqlib.h"
 #include "macroquest-sys/src/lib.rs.h"
 
+#include <algorithm>
+
 namespace mqrust
 {
     namespace eqlib
     {
+        bool get_world_time(WorldTime& time)
+        {
+            if (!::eqlib::pWorldData)
+            {
+                return false;
+            }
+
+            time.year = ::eqlib::pWorldData->Year;
+            time.month = ::eqlib::pWorldData->Month;
+            time.day = ::eqlib::pWorldData->Day;
+            // EverQuest stores the hour as 1-24, with midnight being 1.
+            time.hour = ::eqlib::pWorldData->Hour - 1;
+            time.minute = ::eqlib::pWorldData->Minute;
+
+            return true;
+        }
+
+        bool get_zone_name(rust::String& name)
+        {
+            if (!::eqlib::pZoneInfo)
+            {
+                return false;
+            }
+
+            name = ::eqlib::pZoneInfo->LongName;
+
+            return true;
+        }
+
+        uint32_t zone_count()
+        {
+            if (!::eqlib::pWorldData)
+            {
+                return 0;
+            }
+
+            return ::eqlib::MAX_ZONES;
+        }
+
+        bool get_zone_info(uint32_t index, ZoneInfo& info)
+        {
+            if (!::eqlib::pWorldData || index >= ::eqlib::MAX_ZONES)
+            {
+                return false;
+            }
+
+            // The table has gaps, for the zone ids that aren't in use.
+            auto zone = ::eqlib::pWorldData->ZoneArray[index];
+            if (!zone || zone->Id <= 0)
+            {
+                return false;
+            }
+
+            info.id = static_cast<uint32_t>(zone->Id);
+            info.short_name = zone->ShortName;
+            info.long_name = zone->LongName;
+
+            return true;
+        }
+
+        rust::Str EQZoneInfo::name() const { return this->LongName; }
+
+        rust::Str EQZoneInfo::short_name() const { return this->ShortName; }
+
+        uint32_t EQZoneInfo::id() const
+        {
+            // The zone info doesn't include the zone's id, so it comes from the
+            // local character instead, who is always in the current zone.
+            if (!::eqlib::pLocalPC)
+            {
+                return 0;
+            }
+
+            return static_cast<uint32_t>(EQWORLDID(::eqlib::pLocalPC->get_zoneId()));
+        }
+
+        int32_t EQZoneInfo::zone_type() const { return static_cast<int32_t>(this->OutDoor); }
+
+        const EQZoneInfo* get_current_zone()
+        {
+            const ::eqlib::EQZoneInfo* zone = ::eqlib::pZoneInfo;
+            return reinterpret_cast<const EQZoneInfo*>(zone);
+        }
+
+        bool get_dynamic_zone(DynamicZoneInfo& info)
+        {
+            if (!::eqlib::pDynamicZone || !::eqlib::pDynamicZone->ExpeditionName[0])
+            {
+                return false;
+            }
+
+            info.name = ::eqlib::pDynamicZone->ExpeditionName;
+            info.leader = ::eqlib::pDynamicZone->LeaderName;
+            info.max_members = static_cast<uint32_t>(std::max(::eqlib::pDynamicZone->MaxPlayers, 0));
+
+            return true;
+        }
+
+        // The members and timers are both linked lists, so we walk them to
+        // find the one at an index. They're short enough that this is fine.
+        static const ::eqlib::DynamicZonePlayerInfo* dynamic_zone_member_at(uint32_t index)
+        {
+            if (!::eqlib::pDynamicZone)
+            {
+                return nullptr;
+            }
+
+            auto member = ::eqlib::pDynamicZone->pFirstMember;
+            for (; member && index > 0; --index)
+            {
+                member = member->pNext;
+            }
+
+            return member;
+        }
+
+        static const ::eqlib::DynamicZoneTimerData* dynamic_zone_timer_at(uint32_t index)
+        {
+            if (!::eqlib::pDynamicZone)
+            {
+                return nullptr;
+            }
+
+            auto timer = ::eqlib::pDynamicZone->pFirstTimer;
+            for (; timer && index > 0; --index)
+            {
+                timer = timer->pNext;
+            }
+
+            return timer;
+        }
+
+        uint32_t dynamic_zone_member_count()
+        {
+            uint32_t count = 0;
+            while (dynamic_zone_member_at(count))
+            {
+                ++count;
+            }
+
+            return count;
+        }
+
+        bool get_dynamic_zone_member(uint32_t index, DynamicZoneMemberInfo& member)
+        {
+            auto data = dynamic_zone_member_at(index);
+            if (!data)
+            {
+                return false;
+            }
+
+            member.name = data->Name;
+            member.status = static_cast<int32_t>(data->Status);
+
+            return true;
+        }
+
+        uint32_t dynamic_zone_timer_count()
+        {
+            uint32_t count = 0;
+            while (dynamic_zone_timer_at(count))
+            {
+                ++count;
+            }
+
+            return count;
+        }
+
+        bool get_dynamic_zone_timer(uint32_t index, DynamicZoneTimerInfo& timer)
+        {
+            auto data = dynamic_zone_timer_at(index);
+            if (!data)
+            {
+                return false;
+            }
+
+            timer.expedition_name = data->ExpeditionName;
+            timer.event_name = data->EventName;
+            timer.expires_at = data->TimeStamp;
+
+            return true;
+        }
+
+        bool get_raid(RaidInfo& info)
+        {
+            if (!::eqlib::pRaid || ::eqlib::pRaid->RaidMemberCount <= 0)
+            {
+                return false;
+            }
+
+            info.leader = ::eqlib::pRaid->RaidLeaderName;
+            info.loot_type = ::eqlib::pRaid->LootType;
+
+            return true;
+        }
+
+        uint32_t raid_member_count() { return ::eqlib::pRaid ? ::eqlib::MAX_RAID_SIZE : 0; }
+
+        bool get_raid_member(uint32_t index, RaidMemberInfo& member)
+        {
+            if (!::eqlib::pRaid || index >= ::eqlib::MAX_RAID_SIZE)
+            {
+                return false;
+            }
+
+            // The member slots aren't packed, so there are empty slots between
+            // the members, which have no name.
+            const auto& data = ::eqlib::pRaid->RaidMember[index];
+            if (!data.Name[0])
+            {
+                return false;
+            }
+
+            member.name = data.Name;
+            member.class_id = data.nClass;
+            member.level = static_cast<uint32_t>(std::max(data.nLevel, 0));
+            member.group = data.GroupNumber;
+            member.raid_leader = data.RaidLeader;
+            member.group_leader = data.GroupLeader;
+            member.main_assist = data.RaidMainAssist;
+
+            return true;
+        }
+
+        // The shared tasks come first, followed by the quests (solo tasks).
+        static const ::eqlib::CTaskEntry* task_entry_at(uint32_t index, bool& shared)
+        {
+            if (!::eqlib::pTaskManager)
+            {
+                return nullptr;
+            }
+
+            const ::eqlib::CTaskEntry* entry = nullptr;
+            shared = index < ::eqlib::MAX_SHARED_TASK_ENTRIES;
+            if (shared)
+            {
+                entry = &::eqlib::pTaskManager->SharedTaskEntries[index];
+            }
+            else if (index - ::eqlib::MAX_SHARED_TASK_ENTRIES < ::eqlib::MAX_QUEST_ENTRIES)
+            {
+                entry = &::eqlib::pTaskManager->QuestEntries[index - ::eqlib::MAX_SHARED_TASK_ENTRIES];
+            }
+
+            // Empty task slots are left zeroed out.
+            if (!entry || entry->TaskID <= 0 || !entry->TaskTitle[0])
+            {
+                return nullptr;
+            }
+
+            return entry;
+        }
+
+        uint32_t task_count()
+        {
+            return ::eqlib::MAX_SHARED_TASK_ENTRIES + ::eqlib::MAX_QUEST_ENTRIES;
+        }
+
+        bool get_task(uint32_t index, TaskInfo& task)
+        {
+            bool shared = false;
+            auto entry = task_entry_at(index, shared);
+            if (!entry)
+            {
+                return false;
+            }
+
+            task.id = entry->TaskID;
+            task.title = entry->TaskTitle;
+            task.shared = shared;
+            task.time_limit = static_cast<uint32_t>(std::max(entry->TimeLimit, 0));
+            task.started_at = entry->TimeStarted;
+
+            return true;
+        }
+
+        uint32_t task_objective_count()
+        {
+            return ::eqlib::MAX_TASK_ELEMENTS;
+        }
+
+        bool get_task_objective(uint32_t index, uint32_t objective_index, TaskObjectiveInfo& objective)
+        {
+            bool shared = false;
+            auto entry = task_entry_at(index, shared);
+            if (!entry || objective_index >= ::eqlib::MAX_TASK_ELEMENTS)
+            {
+                return false;
+            }
+
+            const auto& element = entry->Elements[objective_index];
+            if (element.Type == ::eqlib::cTaskElementTypeNone)
+            {
+                return false;
+            }
+
+            char description[0x800] = { 0 };
+            ::eqlib::pTaskManager->GetElementDescription(&element, description);
+
+            objective.objective_type = static_cast<int32_t>(element.Type);
+            objective.description = description;
+            objective.current = entry->CurrentCounts[objective_index];
+            objective.required = element.RequiredCount;
+            objective.optional = element.bOptional;
+
+            return true;
+        }
+
+        bool get_player_location(Location& location)
+        {
+            if (!::eqlib::pLocalPlayer)
+            {
+                return false;
+            }
+
+            location.x = ::eqlib::pLocalPlayer->X;
+            location.y = ::eqlib::pLocalPlayer->Y;
+            location.z = ::eqlib::pLocalPlayer->Z;
+
+            return true;
+        }
+
         rust::Str PlayerClient::name() const { return this->Name; }
 
+        uint32_t PlayerClient::spawn_id() const { return this->SpawnID; }
+
+        uint32_t PlayerClient::master_id() const { return this->MasterID; }
+
+        uint32_t PlayerClient::pet_id() const
+        {
+            // Spawns without a pet have a pet id of either 0 or -1.
+            return this->PetID > 0 ? static_cast<uint32_t>(this->PetID) : 0;
+        }
+
+        int32_t PlayerClient::spawn_type() const
+        {
+            // MacroQuest takes a mutable spawn, but only reads from it.
+            auto spawn = const_cast<::eqlib::PlayerClient*>(
+                static_cast<const ::eqlib::PlayerClient*>(this));
+            return static_cast<int32_t>(::mq::GetSpawnType(spawn));
+        }
+
+        int32_t PlayerClient::deity() const { return this->Deity; }
+
+        int32_t PlayerClient::class_id() const { return this->GetClass(); }
+
+        int32_t PlayerClient::race_id() const { return this->GetRace(); }
+
+        int32_t PlayerClient::level() const { return this->Level; }
+
+        rust::Str PlayerClient::guild() const
+        {
+            // Unguilded spawns have a guild id of -1, which has no name.
+            if (this->GuildID == -1)
+            {
+                return "";
+            }
+
+            auto name = ::mq::GetGuildByID(this->GuildID);
+            return name ? name : "";
+        }
+
+        rust::Str PlayerClient::title() const { return this->Title; }
+
+        rust::Str PlayerClient::suffix() const { return this->Suffix; }
+
+        bool PlayerClient::is_afk() const { return this->AFK != 0; }
+
+        bool PlayerClient::is_lfg() const { return this->LFG != 0; }
+
+        bool PlayerClient::is_trader() const { return this->Trader != 0; }
+
+        bool PlayerClient::is_buyer() const { return this->Buyer != 0; }
+
+        bool PlayerClient::is_linkdead() const { return this->Linkdead != 0; }
+
+        bool PlayerClient::is_feigned() const { return this->StandState == ::eqlib::STANDSTATE_FEIGN; }
+
+        bool PlayerClient::is_invisible() const { return this->HideMode != 0; }
+
+        bool PlayerClient::is_mounted() const { return this->Mount != nullptr; }
+
+        float PlayerClient::x() const { return this->X; }
+
+        float PlayerClient::y() const { return this->Y; }
+
+        float PlayerClient::z() const { return this->Z; }
+
+        float PlayerClient::heading() const { return this->Heading; }
+
+        uint8_t PlayerClient::stand_state() const { return this->StandState; }
+
+        int32_t PlayerClient::casting_spell_id() const { return this->CastingData.SpellID; }
+
+        float PlayerClient::speed_run() const { return this->SpeedRun; }
+
+        float PlayerClient::speed_multiplier() const { return this->SpeedMultiplier; }
+
+        int64_t PlayerClient::hp_current() const { return this->HPCurrent; }
+
+        int64_t PlayerClient::hp_max() const { return this->HPMax; }
+
+        int32_t PlayerClient::mana_current() const { return this->GetCurrentMana(); }
+
+        int32_t PlayerClient::mana_max() const { return this->GetMaxMana(); }
+
+        int32_t PlayerClient::endurance_current() const { return this->GetCurrentEndurance(); }
+
+        int32_t PlayerClient::endurance_max() const { return this->GetMaxEndurance(); }
+
+        const PlayerClient* PlayerClient::next() const
+        {
+            return reinterpret_cast<const PlayerClient*>(this->GetNext());
+        }
+
+        const PlayerClient* first_spawn()
+        {
+            if (!::eqlib::pSpawnManager)
+            {
+                return nullptr;
+            }
+
+            return reinterpret_cast<const PlayerClient*>(::eqlib::pSpawnManager->FirstSpawn);
+        }
+
+        const PlayerClient* get_local_player()
+        {
+            const ::eqlib::PlayerClient* player = ::eqlib::pLocalPlayer;
+            return reinterpret_cast<const PlayerClient*>(player);
+        }
+
+        const PlayerClient* get_target()
+        {
+            const ::eqlib::PlayerClient* target = ::eqlib::pTarget;
+            return reinterpret_cast<const PlayerClient*>(target);
+        }
+
+        bool set_target(uint32_t spawn_id)
+        {
+            if (!::eqlib::pLocalPlayer || !::eqlib::pSpawnManager)
+            {
+                return false;
+            }
+
+            auto spawn = ::eqlib::pSpawnManager->GetSpawnByID(spawn_id);
+            if (!spawn)
+            {
+                return false;
+            }
+
+            // This is the same as what `/target id` does, without having to go
+            // through parsing a command.
+            ::eqlib::pTarget = spawn;
+            return true;
+        }
+
+        void clear_target()
+        {
+            ::eqlib::pTarget = nullptr;
+        }
+
         rust::Str EQGroundItem::name() const { return this->Name; }
+
+        uint32_t EQGroundItem::item_id() const
+        {
+            auto definition = this->Item ? this->Item->GetItemDefinition() : nullptr;
+            return definition ? static_cast<uint32_t>(std::max(definition->ItemNumber, 0)) : 0;
+        }
+
+        uint32_t EQGroundItem::icon_id() const
+        {
+            auto definition = this->Item ? this->Item->GetItemDefinition() : nullptr;
+            return definition ? static_cast<uint32_t>(std::max(definition->IconNumber, 0)) : 0;
+        }
+
+        uint32_t EQGroundItem::drop_id() const { return this->DropID; }
+
+        uint32_t EQGroundItem::zone_id() const { return static_cast<uint32_t>(EQWORLDID(this->ZoneID)); }
+
+        float EQGroundItem::x() const { return this->X; }
+
+        float EQGroundItem::y() const { return this->Y; }
+
+        float EQGroundItem::z() const { return this->Z; }
+
+        float EQGroundItem::heading() const { return this->Heading; }
+
+        Location EQGroundItem::location() const { return Location{this->X, this->Y, this->Z}; }
+
+        const EQGroundItem* EQGroundItem::next() const
+        {
+            return reinterpret_cast<const EQGroundItem*>(this->pNext);
+        }
+
+        const EQGroundItem* first_ground_item()
+        {
+            if (!::eqlib::pItemList)
+            {
+                return nullptr;
+            }
+
+            return reinterpret_cast<const EQGroundItem*>(::eqlib::pItemList->Top);
+        }
+
+        rust::Str PcClient::name() const { return this->Name; }
+
+        int32_t PcClient::aura_count() const
+        {
+            auto manager = ::eqlib::GetAuraMgr();
+            return manager ? manager->Auras.GetCount() : 0;
+        }
+
+        bool PcClient::aura(int32_t index, AuraInfo& aura) const
+        {
+            auto manager = ::eqlib::GetAuraMgr();
+            if (!manager || index < 0 || index >= manager->Auras.GetCount())
+            {
+                return false;
+            }
+
+            const auto& data = manager->Auras[index];
+            aura.name = data.Name;
+            aura.spawn_id = static_cast<uint32_t>(data.SpawnID);
+
+            return true;
+        }
+
+        // Read the buff in the character's (long or short) buff slot `slot`.
+        static bool buff_info(const ::eqlib::PcClient* pc, int slot, BuffInfo& buff)
+        {
+            auto profile = pc->GetCurrentPcProfile();
+            if (!profile)
+            {
+                return false;
+            }
+
+            const auto& effect = profile->GetEffect(slot);
+            if (effect.SpellID <= 0)
+            {
+                return false;
+            }
+
+            buff.spell_id = effect.SpellID;
+            buff.level = effect.Level;
+            buff.duration = effect.Duration;
+            buff.counters = ::mq::GetTotalSpellCounters(effect);
+
+            return true;
+        }
+
+        int32_t PcClient::buff_count() const { return ::eqlib::NUM_LONG_BUFFS; }
+
+        bool PcClient::buff(int32_t index, BuffInfo& buff) const
+        {
+            if (index < 0 || index >= ::eqlib::NUM_LONG_BUFFS)
+            {
+                return false;
+            }
+
+            return buff_info(this, index, buff);
+        }
+
+        int32_t PcClient::song_buff_count() const { return ::eqlib::NUM_SHORT_BUFFS; }
+
+        bool PcClient::song_buff(int32_t index, BuffInfo& buff) const
+        {
+            if (index < 0 || index >= ::eqlib::NUM_SHORT_BUFFS)
+            {
+                return false;
+            }
+
+            // The song slots come after the long buff slots.
+            return buff_info(this, ::eqlib::NUM_LONG_BUFFS + index, buff);
+        }
+
+        int32_t PcClient::fellowship_member_count() const
+        {
+            // The fellowship is kept on the spawn, rather than on the character.
+            if (!::eqlib::pLocalPlayer)
+            {
+                return 0;
+            }
+
+            return std::clamp(::eqlib::pLocalPlayer->Fellowship.Members, 0, ::eqlib::MAX_FELLOWSHIP_MEMBERS);
+        }
+
+        bool PcClient::fellowship_member(int32_t index, FellowshipMemberInfo& member) const
+        {
+            if (index < 0 || index >= this->fellowship_member_count())
+            {
+                return false;
+            }
+
+            const auto& data = ::eqlib::pLocalPlayer->Fellowship.FellowshipMember[index];
+            member.name = data.Name;
+            member.class_id = data.Class;
+            member.level = static_cast<uint32_t>(std::max(data.Level, 0));
+            member.zone_id = static_cast<uint32_t>(EQWORLDID(data.ZoneID));
+            // The game only records when a member was last on once they've logged off.
+            member.online = data.LastOn == 0;
+
+            return true;
+        }
+
+        bool PcClient::campfire(CampfireInfo& campfire) const
+        {
+            if (!::eqlib::pLocalPlayer || !::eqlib::pLocalPlayer->Fellowship.Campfire)
+            {
+                return false;
+            }
+
+            const auto& fellowship = ::eqlib::pLocalPlayer->Fellowship;
+            campfire.zone_id = static_cast<uint32_t>(EQWORLDID(fellowship.CampfireZoneID));
+            campfire.location = Location{fellowship.CampfireX, fellowship.CampfireY, fellowship.CampfireZ};
+
+            return true;
+        }
+
+        int32_t PcClient::group_member_count() const { return this->Group ? ::eqlib::MAX_GROUP_SIZE : 0; }
+
+        bool PcClient::group_member(int32_t index, GroupMemberInfo& member) const
+        {
+            if (!this->Group || index < 0 || index >= ::eqlib::MAX_GROUP_SIZE)
+            {
+                return false;
+            }
+
+            // Empty slots have no member, or a member with no name.
+            auto data = this->Group->GetGroupMember(index);
+            if (!data || data->Name.empty())
+            {
+                return false;
+            }
+
+            member.name = data->Name.c_str();
+            member.level = static_cast<uint32_t>(std::max<int>(data->Level, 0));
+            member.class_id = data->pSpawn ? data->pSpawn->GetClass() : 0;
+            member.spawn_id = data->pSpawn ? static_cast<uint32_t>(data->pSpawn->SpawnID) : 0;
+            member.online = !data->Offline;
+            member.leader = data == this->Group->GetGroupLeader();
+
+            return true;
+        }
+
+        int32_t PcClient::level() const
+        {
+            auto profile = this->GetCurrentPcProfile();
+            return profile ? profile->Level : 0;
+        }
+
+        int32_t PcClient::class_id() const
+        {
+            auto profile = this->GetCurrentPcProfile();
+            return profile ? profile->Class : 0;
+        }
+
+        int32_t PcClient::aa_points() const
+        {
+            auto profile = this->GetCurrentPcProfile();
+            return profile ? profile->AAPoints : 0;
+        }
+
+        int32_t PcClient::aa_points_spent() const
+        {
+            auto profile = this->GetCurrentPcProfile();
+            return profile ? profile->AAPointsSpent : 0;
+        }
+
+        int64_t PcClient::experience() const { return this->Exp; }
+
+        int32_t PcClient::aa_experience() const { return this->AAExp; }
+
+        uint32_t PcClient::zone_id() const { return static_cast<uint32_t>(EQWORLDID(this->get_zoneId())); }
+
+        const ItemClient* PcClient::equipped_item(int32_t slot) const
+        {
+            auto profile = this->GetCurrentPcProfile();
+            if (!profile || slot < ::eqlib::InvSlot_FirstWornItem || slot > ::eqlib::InvSlot_LastWornItem)
+            {
+                return nullptr;
+            }
+
+            auto item = profile->GetInventorySlot(slot);
+            return reinterpret_cast<const ItemClient*>(item.get());
+        }
+
+        const ItemClient* PcClient::cursor_item() const
+        {
+            auto profile = this->GetCurrentPcProfile();
+            if (!profile)
+            {
+                return nullptr;
+            }
+
+            auto item = profile->GetInventorySlot(::eqlib::InvSlot_Cursor);
+            return reinterpret_cast<const ItemClient*>(item.get());
+        }
+
+        CoinInfo PcClient::carried_coin() const
+        {
+            auto profile = this->GetCurrentPcProfile();
+            if (!profile)
+            {
+                return CoinInfo{};
+            }
+
+            return CoinInfo{profile->Plat, profile->Gold, profile->Silver, profile->Copper};
+        }
+
+        CoinInfo PcClient::bank_coin() const
+        {
+            return CoinInfo{this->BankPlat, this->BankGold, this->BankSilver, this->BankCopper};
+        }
+
+        const PcClient* get_local_pc()
+        {
+            return reinterpret_cast<const PcClient*>(::eqlib::pLocalPC);
+        }
+
+        int32_t EQSwitch::id() const { return this->ID; }
+
+        rust::Str EQSwitch::name() const { return this->Name; }
+
+        Location EQSwitch::location() const { return Location{this->X, this->Y, this->Z}; }
+
+        uint8_t EQSwitch::state() const { return this->State; }
+
+        bool EQSwitch::click() const
+        {
+            if (!::eqlib::pLocalPlayer)
+            {
+                return false;
+            }
+
+            // This is the same as what `/click door` does, and UseSwitch doesn't
+            // modify the switch even though it isn't const.
+            auto sw = const_cast<::eqlib::EQSwitch*>(static_cast<const ::eqlib::EQSwitch*>(this));
+            sw->UseSwitch(::eqlib::pLocalPlayer->SpawnID, -1, 0, nullptr);
+
+            return true;
+        }
+
+        uint32_t switch_count()
+        {
+            if (!::eqlib::pSwitchMgr)
+            {
+                return 0;
+            }
+
+            return static_cast<uint32_t>(::eqlib::pSwitchMgr->GetCount());
+        }
+
+        const EQSwitch* get_switch(uint32_t index)
+        {
+            if (!::eqlib::pSwitchMgr || index >= switch_count())
+            {
+                return nullptr;
+            }
+
+            auto sw = ::eqlib::pSwitchMgr->GetSwitch(static_cast<int>(index));
+            return reinterpret_cast<const EQSwitch*>(sw);
+        }
+
+        rust::Str ItemClient::name() const
+        {
+            auto definition = this->GetItemDefinition();
+            return definition ? definition->Name : "";
+        }
+
+        int32_t ItemClient::id() const
+        {
+            auto definition = this->GetItemDefinition();
+            return definition ? definition->ItemNumber : 0;
+        }
+
+        int32_t ItemClient::icon() const
+        {
+            auto definition = this->GetItemDefinition();
+            return definition ? definition->IconNumber : 0;
+        }
+
+        int32_t ItemClient::stack() const { return this->GetItemCount(); }
+
+        int32_t ItemClient::stack_max() const
+        {
+            auto definition = this->GetItemDefinition();
+            return definition ? definition->StackSize : 0;
+        }
+
+        int32_t ItemClient::value_in_copper() const
+        {
+            auto definition = this->GetItemDefinition();
+            return definition ? definition->Cost : 0;
+        }
+
+        bool ItemClient::is_no_drop() const
+        {
+            // Despite the name, the game sets NoDrop on items that *can* be dropped.
+            auto definition = this->GetItemDefinition();
+            return definition && !definition->NoDrop;
+        }
+
+        bool ItemClient::is_lore() const
+        {
+            auto definition = this->GetItemDefinition();
+            return definition && definition->Lore != 0;
+        }
+
+        bool ItemClient::is_attunable() const
+        {
+            auto definition = this->GetItemDefinition();
+            return definition && definition->Attuneable;
+        }
+
+        bool ItemClient::is_container() const { return this->IsContainer(); }
+
+        int32_t ItemClient::container_slots() const
+        {
+            if (!this->IsContainer())
+            {
+                return 0;
+            }
+
+            auto definition = this->GetItemDefinition();
+            return definition ? definition->Slots : 0;
+        }
+
+        const ItemClient* ItemClient::item_in_slot(int32_t slot) const
+        {
+            if (slot < 0 || slot >= this->container_slots())
+            {
+                return nullptr;
+            }
+
+            auto item = this->GetHeldItem(slot);
+            return reinterpret_cast<const ItemClient*>(item.get());
+        }
+
+        uint32_t EQ_Spell::id() const { return static_cast<uint32_t>(this->ID); }
+
+        rust::Str EQ_Spell::name() const { return this->Name; }
+
+        int32_t EQ_Spell::mana_cost() const { return this->ManaCost; }
+
+        uint32_t EQ_Spell::cast_time_ms() const { return this->CastTime; }
+
+        uint32_t EQ_Spell::recast_time_ms() const { return this->RecastTime; }
+
+        float EQ_Spell::range() const { return this->Range; }
+
+        uint8_t EQ_Spell::spell_type() const { return this->SpellType; }
+
+        uint8_t EQ_Spell::target_type() const { return this->TargetType; }
+
+        uint8_t EQ_Spell::duration_formula() const { return this->DurationType; }
+
+        int32_t EQ_Spell::duration_cap() const { return this->DurationCap; }
+
+        uint8_t EQ_Spell::class_level(int32_t class_id) const
+        {
+            // The levels are indexed by class id, which starts from 1.
+            if (class_id < 1 || class_id >= static_cast<int32_t>(std::size(this->ClassLevel)))
+            {
+                return 255;
+            }
+
+            return this->ClassLevel[class_id];
+        }
+
+        int32_t EQ_Spell::effect_count() const { return this->GetNumEffects(); }
+
+        bool EQ_Spell::effect(int32_t index, SpellEffect& effect) const
+        {
+            if (index < 0 || index >= this->GetNumEffects())
+            {
+                return false;
+            }
+
+            // The MQ accessors aren't const, but they don't modify the spell.
+            auto spell = const_cast<::eqlib::EQ_Spell*>(static_cast<const ::eqlib::EQ_Spell*>(this));
+
+            effect.spa = ::mq::GetSpellAttrib(spell, index);
+            effect.base = ::mq::GetSpellBase(spell, index);
+            effect.base2 = ::mq::GetSpellBase2(spell, index);
+            effect.max = ::mq::GetSpellMax(spell, index);
+
+            return true;
+        }
+
+        const EQ_Spell* get_spell_by_id(uint32_t id)
+        {
+            if (!::eqlib::pSpellMgr)
+            {
+                return nullptr;
+            }
+
+            auto spell = ::eqlib::pSpellMgr->GetSpellByID(static_cast<int>(id));
+
+            // The spell manager hands back a placeholder spell for ids that don't
+            // exist, rather than nothing.
+            if (!spell || spell->ID <= 0 || static_cast<uint32_t>(spell->ID) != id)
+            {
+                return nullptr;
+            }
+
+            return reinterpret_cast<const EQ_Spell*>(spell);
+        }
+
+        const EQ_Spell* get_spell_by_name(rust::Str name)
+        {
+            if (!::eqlib::pSpellMgr)
+            {
+                return nullptr;
+            }
+
+            auto spell = ::mq::GetSpellByName(std::string(name));
+            if (!spell || spell->ID <= 0)
+            {
+                return nullptr;
+            }
+
+            return reinterpret_cast<const EQ_Spell*>(spell);
+        }
     }
 
 }
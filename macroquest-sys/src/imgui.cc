@@ -0,0 +1,135 @@
+#include "macroquest-sys/include/imgui.h"
+#include "macroquest-sys/src/lib.rs.h"
+
+#include <string>
+
+namespace mqrust
+{
+    namespace imgui
+    {
+        bool begin(rust::Str name) { return ImGui::Begin(std::string(name).c_str()); }
+
+        bool begin_closable(rust::Str name, bool& open) { return ImGui::Begin(std::string(name).c_str(), &open); }
+
+        void end() { ImGui::End(); }
+
+        bool begin_child(rust::Str id) { return ImGui::BeginChild(std::string(id).c_str(), ImVec2(0, 0), true); }
+
+        void end_child() { ImGui::EndChild(); }
+
+        float get_scroll_y() { return ImGui::GetScrollY(); }
+
+        float get_scroll_max_y() { return ImGui::GetScrollMaxY(); }
+
+        void set_scroll_here_y(float ratio) { ImGui::SetScrollHereY(ratio); }
+
+        bool begin_main_menu_bar() { return ImGui::BeginMainMenuBar(); }
+
+        void end_main_menu_bar() { ImGui::EndMainMenuBar(); }
+
+        bool begin_menu(rust::Str label) { return ImGui::BeginMenu(std::string(label).c_str()); }
+
+        void end_menu() { ImGui::EndMenu(); }
+
+        bool menu_item(rust::Str label, bool selected)
+        {
+            return ImGui::MenuItem(std::string(label).c_str(), nullptr, selected);
+        }
+
+        bool begin_table(rust::Str id, int columns)
+        {
+            return ImGui::BeginTable(std::string(id).c_str(), columns, ImGuiTableFlags_Borders | ImGuiTableFlags_RowBg);
+        }
+
+        void table_setup_column(rust::Str label) { ImGui::TableSetupColumn(std::string(label).c_str()); }
+
+        void table_headers_row() { ImGui::TableHeadersRow(); }
+
+        void table_next_row() { ImGui::TableNextRow(); }
+
+        bool table_next_column() { return ImGui::TableNextColumn(); }
+
+        void end_table() { ImGui::EndTable(); }
+
+        bool collapsing_header(rust::Str label) { return ImGui::CollapsingHeader(std::string(label).c_str()); }
+
+        void push_id(rust::Str id) { ImGui::PushID(id.data(), id.data() + id.size()); }
+
+        void pop_id() { ImGui::PopID(); }
+
+        bool begin_combo(rust::Str label, rust::Str preview)
+        {
+            return ImGui::BeginCombo(std::string(label).c_str(), std::string(preview).c_str());
+        }
+
+        bool selectable(rust::Str label, bool selected) { return ImGui::Selectable(std::string(label).c_str(), selected); }
+
+        void end_combo() { ImGui::EndCombo(); }
+
+        void text(rust::Str text) { ImGui::TextUnformatted(text.data(), text.data() + text.size()); }
+
+        void text_disabled(rust::Str text)
+        {
+            ImGui::PushStyleColor(ImGuiCol_Text, ImGui::GetStyleColorVec4(ImGuiCol_TextDisabled));
+            ImGui::TextUnformatted(text.data(), text.data() + text.size());
+            ImGui::PopStyleColor();
+        }
+
+        void text_colored(float r, float g, float b, float a, rust::Str text)
+        {
+            ImGui::PushStyleColor(ImGuiCol_Text, ImVec4(r, g, b, a));
+            ImGui::TextUnformatted(text.data(), text.data() + text.size());
+            ImGui::PopStyleColor();
+        }
+
+        bool button(rust::Str label) { return ImGui::Button(std::string(label).c_str()); }
+
+        bool radio_button(rust::Str label, bool active) { return ImGui::RadioButton(std::string(label).c_str(), active); }
+
+        bool input_text(rust::Str label, rust::String& text)
+        {
+            char buffer[256] = {};
+            std::string current(text);
+            current.copy(buffer, sizeof(buffer) - 1);
+
+            if (ImGui::InputText(std::string(label).c_str(), buffer, sizeof(buffer)))
+            {
+                text = rust::String(buffer);
+                return true;
+            }
+
+            return false;
+        }
+
+        bool checkbox(rust::Str label, bool& value) { return ImGui::Checkbox(std::string(label).c_str(), &value); }
+
+        // For both of the drags, a min that isn't less than the max means that
+        // the value is unbounded.
+        bool drag_i64(rust::Str label, int64_t& value, int64_t min, int64_t max)
+        {
+            bool bounded = min < max;
+            return ImGui::DragScalar(std::string(label).c_str(), ImGuiDataType_S64, &value, 1.0f,
+                bounded ? &min : nullptr, bounded ? &max : nullptr, nullptr,
+                bounded ? ImGuiSliderFlags_AlwaysClamp : ImGuiSliderFlags_None);
+        }
+
+        bool drag_f64(rust::Str label, double& value, double min, double max)
+        {
+            bool bounded = min < max;
+            return ImGui::DragScalar(std::string(label).c_str(), ImGuiDataType_Double, &value, 0.01f,
+                bounded ? &min : nullptr, bounded ? &max : nullptr, "%.3f",
+                bounded ? ImGuiSliderFlags_AlwaysClamp : ImGuiSliderFlags_None);
+        }
+
+        void separator() { ImGui::Separator(); }
+
+        void same_line() { ImGui::SameLine(); }
+
+        void progress_bar(float fraction, rust::Str overlay)
+        {
+            ImGui::ProgressBar(fraction, ImVec2(-FLT_MIN, 0), std::string(overlay).c_str());
+        }
+
+        void set_clipboard_text(rust::Str text) { ImGui::SetClipboardText(std::string(text).c_str()); }
+    }
+}
@@ -11,6 +11,247 @@ pub const EQ_VERSION: &[u8; 21] = include!(concat!(env!("OUT_DIR"), "/eq_version
 
 #[cxx::bridge(namespace = "mqrust::eqlib")]
 pub mod eqlib {
+    /// The in-game (Norrathian) date and time, as stored in `pWorldData`.
+    #[derive(Debug, Default, Clone, Copy)]
+    struct WorldTime {
+        year:   i32,
+        month:  u8,
+        day:    u8,
+        hour:   u8,
+        minute: u8,
+    }
+
+    /// A location in the world.
+    #[derive(Debug, Default, Clone, Copy)]
+    struct Location {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    /// An aura in one of the local character's aura slots.
+    #[derive(Debug, Default, Clone)]
+    struct AuraInfo {
+        name:     String,
+        spawn_id: u32,
+    }
+
+    /// A buff in one of the local character's buff (or song) slots.
+    #[derive(Debug, Default, Clone, Copy)]
+    struct BuffInfo {
+        spell_id: i32,
+        level:    u8,
+        duration: i32,
+        counters: i32,
+    }
+
+    /// A member of the local character's fellowship.
+    #[derive(Debug, Default, Clone)]
+    struct FellowshipMemberInfo {
+        name:     String,
+        class_id: i32,
+        level:    u32,
+        zone_id:  u32,
+        online:   bool,
+    }
+
+    /// A member of the local character's group.
+    #[derive(Debug, Default, Clone)]
+    struct GroupMemberInfo {
+        name:     String,
+        level:    u32,
+        class_id: i32,
+        spawn_id: u32,
+        online:   bool,
+        leader:   bool,
+    }
+
+    /// An amount of coin, as held by the local character.
+    #[derive(Debug, Default, Clone, Copy)]
+    struct CoinInfo {
+        platinum: i32,
+        gold:     i32,
+        silver:   i32,
+        copper:   i32,
+    }
+
+    /// The local character's fellowship campfire.
+    #[derive(Debug, Default, Clone, Copy)]
+    struct CampfireInfo {
+        zone_id:  u32,
+        location: Location,
+    }
+
+    /// The dynamic zone (expedition) that the local character is in.
+    #[derive(Debug, Default, Clone)]
+    struct DynamicZoneInfo {
+        name:        String,
+        leader:      String,
+        max_members: u32,
+    }
+
+    /// A member of the local character's dynamic zone.
+    #[derive(Debug, Default, Clone)]
+    struct DynamicZoneMemberInfo {
+        name:   String,
+        status: i32,
+    }
+
+    /// A dynamic zone lockout timer of the local character.
+    #[derive(Debug, Default, Clone)]
+    struct DynamicZoneTimerInfo {
+        expedition_name: String,
+        event_name:      String,
+        expires_at:      u32,
+    }
+
+    /// A task (quest or shared task) in the local character's task journal.
+    #[derive(Debug, Default, Clone)]
+    struct TaskInfo {
+        id:         i32,
+        title:      String,
+        shared:     bool,
+        time_limit: u32,
+        started_at: u32,
+    }
+
+    /// An objective (element) of a task.
+    #[derive(Debug, Default, Clone)]
+    struct TaskObjectiveInfo {
+        objective_type: i32,
+        description:    String,
+        current:        i32,
+        required:       i32,
+        optional:       bool,
+    }
+
+    /// The raid that the local character is in.
+    #[derive(Debug, Default, Clone)]
+    struct RaidInfo {
+        leader:    String,
+        loot_type: i32,
+    }
+
+    /// A member of the local character's raid.
+    #[derive(Debug, Default, Clone)]
+    struct RaidMemberInfo {
+        name:         String,
+        class_id:     i32,
+        level:        u32,
+        group:        i32,
+        raid_leader:  bool,
+        group_leader: bool,
+        main_assist:  bool,
+    }
+
+    /// An entry in the game's zone table.
+    #[derive(Debug, Default, Clone)]
+    struct ZoneInfo {
+        id:         u32,
+        short_name: String,
+        long_name:  String,
+    }
+
+    /// A single effect (SPA) slot of a spell.
+    #[derive(Debug, Default, Clone, Copy)]
+    struct SpellEffect {
+        spa:   i32,
+        base:  i64,
+        base2: i64,
+        max:   i64,
+    }
+
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/eqlib.h");
+
+        // World Functions
+        #[must_use]
+        fn get_world_time(time: &mut WorldTime) -> bool;
+
+        // Zone Functions
+        #[must_use]
+        fn get_zone_name(name: &mut String) -> bool;
+
+        #[must_use]
+        fn zone_count() -> u32;
+
+        #[must_use]
+        fn get_zone_info(index: u32, info: &mut ZoneInfo) -> bool;
+
+        // Dynamic Zone Functions
+        #[must_use]
+        fn get_dynamic_zone(info: &mut DynamicZoneInfo) -> bool;
+
+        #[must_use]
+        fn dynamic_zone_member_count() -> u32;
+
+        #[must_use]
+        fn get_dynamic_zone_member(
+            index: u32,
+            member: &mut DynamicZoneMemberInfo,
+        ) -> bool;
+
+        #[must_use]
+        fn dynamic_zone_timer_count() -> u32;
+
+        #[must_use]
+        fn get_dynamic_zone_timer(index: u32, timer: &mut DynamicZoneTimerInfo)
+            -> bool;
+
+        // Raid Functions
+        #[must_use]
+        fn get_raid(info: &mut RaidInfo) -> bool;
+
+        #[must_use]
+        fn raid_member_count() -> u32;
+
+        #[must_use]
+        fn get_raid_member(index: u32, member: &mut RaidMemberInfo) -> bool;
+
+        // Task Functions
+        #[must_use]
+        fn task_count() -> u32;
+
+        #[must_use]
+        fn get_task(index: u32, task: &mut TaskInfo) -> bool;
+
+        #[must_use]
+        fn task_objective_count() -> u32;
+
+        #[must_use]
+        fn get_task_objective(
+            index: u32,
+            objective_index: u32,
+            objective: &mut TaskObjectiveInfo,
+        ) -> bool;
+
+        // Player Functions
+        #[must_use]
+        fn get_player_location(location: &mut Location) -> bool;
+    }
+
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/eqlib.h");
+
+        pub type EQZoneInfo;
+
+        #[must_use]
+        fn name(&self) -> &str;
+
+        #[must_use]
+        fn short_name(&self) -> &str;
+
+        #[must_use]
+        fn id(&self) -> u32;
+
+        #[must_use]
+        fn zone_type(&self) -> i32;
+
+        // Current Zone Functions
+        #[must_use]
+        fn get_current_zone() -> *const EQZoneInfo;
+    }
+
     unsafe extern "C++" {
         include!("macroquest-sys/include/eqlib.h");
 
@@ -19,6 +260,123 @@ pub mod eqlib {
         #[must_use]
         fn name(&self) -> &str;
 
+        #[must_use]
+        fn spawn_id(&self) -> u32;
+
+        #[must_use]
+        fn master_id(&self) -> u32;
+
+        #[must_use]
+        fn pet_id(&self) -> u32;
+
+        #[must_use]
+        fn spawn_type(&self) -> i32;
+
+        #[must_use]
+        fn deity(&self) -> i32;
+
+        #[must_use]
+        fn class_id(&self) -> i32;
+
+        #[must_use]
+        fn race_id(&self) -> i32;
+
+        #[must_use]
+        fn level(&self) -> i32;
+
+        #[must_use]
+        fn guild(&self) -> &str;
+
+        #[must_use]
+        fn title(&self) -> &str;
+
+        #[must_use]
+        fn suffix(&self) -> &str;
+
+        #[must_use]
+        fn is_afk(&self) -> bool;
+
+        #[must_use]
+        fn is_lfg(&self) -> bool;
+
+        #[must_use]
+        fn is_trader(&self) -> bool;
+
+        #[must_use]
+        fn is_buyer(&self) -> bool;
+
+        #[must_use]
+        fn is_linkdead(&self) -> bool;
+
+        #[must_use]
+        fn is_feigned(&self) -> bool;
+
+        #[must_use]
+        fn is_invisible(&self) -> bool;
+
+        #[must_use]
+        fn is_mounted(&self) -> bool;
+
+        #[must_use]
+        fn x(&self) -> f32;
+
+        #[must_use]
+        fn y(&self) -> f32;
+
+        #[must_use]
+        fn z(&self) -> f32;
+
+        #[must_use]
+        fn heading(&self) -> f32;
+
+        #[must_use]
+        fn stand_state(&self) -> u8;
+
+        #[must_use]
+        fn casting_spell_id(&self) -> i32;
+
+        #[must_use]
+        fn speed_run(&self) -> f32;
+
+        #[must_use]
+        fn speed_multiplier(&self) -> f32;
+
+        #[must_use]
+        fn hp_current(&self) -> i64;
+
+        #[must_use]
+        fn hp_max(&self) -> i64;
+
+        #[must_use]
+        fn mana_current(&self) -> i32;
+
+        #[must_use]
+        fn mana_max(&self) -> i32;
+
+        #[must_use]
+        fn endurance_current(&self) -> i32;
+
+        #[must_use]
+        fn endurance_max(&self) -> i32;
+
+        #[must_use]
+        fn next(&self) -> *const PlayerClient;
+
+        // Spawn Functions
+        #[must_use]
+        fn first_spawn() -> *const PlayerClient;
+
+        #[must_use]
+        fn get_local_player() -> *const PlayerClient;
+
+        // Target Functions
+        #[must_use]
+        fn get_target() -> *const PlayerClient;
+
+        #[must_use]
+        fn set_target(spawn_id: u32) -> bool;
+
+        fn clear_target();
     }
 
     unsafe extern "C++" {
@@ -28,6 +386,354 @@ pub mod eqlib {
 
         #[must_use]
         fn name(&self) -> &str;
+
+        #[must_use]
+        fn item_id(&self) -> u32;
+
+        #[must_use]
+        fn icon_id(&self) -> u32;
+
+        #[must_use]
+        fn drop_id(&self) -> u32;
+
+        #[must_use]
+        fn zone_id(&self) -> u32;
+
+        #[must_use]
+        fn x(&self) -> f32;
+
+        #[must_use]
+        fn y(&self) -> f32;
+
+        #[must_use]
+        fn z(&self) -> f32;
+
+        #[must_use]
+        fn heading(&self) -> f32;
+
+        #[must_use]
+        fn location(&self) -> Location;
+
+        #[must_use]
+        fn next(&self) -> *const EQGroundItem;
+
+        // Ground Item Functions
+        #[must_use]
+        fn first_ground_item() -> *const EQGroundItem;
+    }
+
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/eqlib.h");
+
+        pub type PcClient;
+
+        #[must_use]
+        fn name(&self) -> &str;
+
+        #[must_use]
+        fn aura_count(&self) -> i32;
+
+        #[must_use]
+        fn aura(&self, index: i32, aura: &mut AuraInfo) -> bool;
+
+        #[must_use]
+        fn buff_count(&self) -> i32;
+
+        #[must_use]
+        fn buff(&self, index: i32, buff: &mut BuffInfo) -> bool;
+
+        #[must_use]
+        fn song_buff_count(&self) -> i32;
+
+        #[must_use]
+        fn song_buff(&self, index: i32, buff: &mut BuffInfo) -> bool;
+
+        #[must_use]
+        fn fellowship_member_count(&self) -> i32;
+
+        #[must_use]
+        fn fellowship_member(
+            &self,
+            index: i32,
+            member: &mut FellowshipMemberInfo,
+        ) -> bool;
+
+        #[must_use]
+        fn campfire(&self, campfire: &mut CampfireInfo) -> bool;
+
+        #[must_use]
+        fn group_member_count(&self) -> i32;
+
+        #[must_use]
+        fn group_member(&self, index: i32, member: &mut GroupMemberInfo) -> bool;
+
+        #[must_use]
+        fn level(&self) -> i32;
+
+        #[must_use]
+        fn class_id(&self) -> i32;
+
+        #[must_use]
+        fn aa_points(&self) -> i32;
+
+        #[must_use]
+        fn aa_points_spent(&self) -> i32;
+
+        #[must_use]
+        fn experience(&self) -> i64;
+
+        #[must_use]
+        fn aa_experience(&self) -> i32;
+
+        #[must_use]
+        fn zone_id(&self) -> u32;
+
+        #[must_use]
+        fn equipped_item(&self, slot: i32) -> *const ItemClient;
+
+        #[must_use]
+        fn cursor_item(&self) -> *const ItemClient;
+
+        #[must_use]
+        fn carried_coin(&self) -> CoinInfo;
+
+        #[must_use]
+        fn bank_coin(&self) -> CoinInfo;
+
+        // Character Functions
+        #[must_use]
+        fn get_local_pc() -> *const PcClient;
+    }
+
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/eqlib.h");
+
+        pub type EQSwitch;
+
+        #[must_use]
+        fn id(&self) -> i32;
+
+        #[must_use]
+        fn name(&self) -> &str;
+
+        #[must_use]
+        fn location(&self) -> Location;
+
+        #[must_use]
+        fn state(&self) -> u8;
+
+        #[must_use]
+        fn click(&self) -> bool;
+
+        // Switch Functions
+        #[must_use]
+        fn switch_count() -> u32;
+
+        #[must_use]
+        fn get_switch(index: u32) -> *const EQSwitch;
+    }
+
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/eqlib.h");
+
+        pub type ItemClient;
+
+        #[must_use]
+        fn name(&self) -> &str;
+
+        #[must_use]
+        fn id(&self) -> i32;
+
+        #[must_use]
+        fn icon(&self) -> i32;
+
+        #[must_use]
+        fn stack(&self) -> i32;
+
+        #[must_use]
+        fn stack_max(&self) -> i32;
+
+        #[must_use]
+        fn value_in_copper(&self) -> i32;
+
+        #[must_use]
+        fn is_no_drop(&self) -> bool;
+
+        #[must_use]
+        fn is_lore(&self) -> bool;
+
+        #[must_use]
+        fn is_attunable(&self) -> bool;
+
+        #[must_use]
+        fn is_container(&self) -> bool;
+
+        #[must_use]
+        fn container_slots(&self) -> i32;
+
+        #[must_use]
+        fn item_in_slot(&self, slot: i32) -> *const ItemClient;
+    }
+
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/eqlib.h");
+
+        #[cxx_name = "EQ_Spell"]
+        pub type EqSpell;
+
+        #[must_use]
+        fn id(&self) -> u32;
+
+        #[must_use]
+        fn name(&self) -> &str;
+
+        #[must_use]
+        fn mana_cost(&self) -> i32;
+
+        #[must_use]
+        fn cast_time_ms(&self) -> u32;
+
+        #[must_use]
+        fn recast_time_ms(&self) -> u32;
+
+        #[must_use]
+        fn range(&self) -> f32;
+
+        #[must_use]
+        fn spell_type(&self) -> u8;
+
+        #[must_use]
+        fn target_type(&self) -> u8;
+
+        #[must_use]
+        fn duration_formula(&self) -> u8;
+
+        #[must_use]
+        fn duration_cap(&self) -> i32;
+
+        #[must_use]
+        fn class_level(&self, class_id: i32) -> u8;
+
+        #[must_use]
+        fn effect_count(&self) -> i32;
+
+        #[must_use]
+        fn effect(&self, index: i32, effect: &mut SpellEffect) -> bool;
+
+        // Spell Functions
+        #[must_use]
+        fn get_spell_by_id(id: u32) -> *const EqSpell;
+
+        #[must_use]
+        fn get_spell_by_name(name: &str) -> *const EqSpell;
+    }
+}
+
+#[cxx::bridge(namespace = "mqrust::imgui")]
+pub mod imgui {
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/imgui.h");
+
+        // Window Functions
+        #[must_use]
+        fn begin(name: &str) -> bool;
+
+        #[must_use]
+        fn begin_closable(name: &str, open: &mut bool) -> bool;
+
+        fn end();
+
+        #[must_use]
+        fn begin_child(id: &str) -> bool;
+
+        fn end_child();
+
+        // Scrolling Functions
+        fn get_scroll_y() -> f32;
+
+        fn get_scroll_max_y() -> f32;
+
+        fn set_scroll_here_y(ratio: f32);
+
+        // Menu Functions
+        #[must_use]
+        fn begin_main_menu_bar() -> bool;
+
+        fn end_main_menu_bar();
+
+        #[must_use]
+        fn begin_menu(label: &str) -> bool;
+
+        fn end_menu();
+
+        #[must_use]
+        fn menu_item(label: &str, selected: bool) -> bool;
+
+        // Table Functions
+        #[must_use]
+        fn begin_table(id: &str, columns: i32) -> bool;
+
+        fn table_setup_column(label: &str);
+
+        fn table_headers_row();
+
+        fn table_next_row();
+
+        #[must_use]
+        fn table_next_column() -> bool;
+
+        fn end_table();
+
+        // Tree Functions
+        #[must_use]
+        fn collapsing_header(label: &str) -> bool;
+
+        fn push_id(id: &str);
+
+        fn pop_id();
+
+        // Combo Functions
+        #[must_use]
+        fn begin_combo(label: &str, preview: &str) -> bool;
+
+        #[must_use]
+        fn selectable(label: &str, selected: bool) -> bool;
+
+        fn end_combo();
+
+        // Widget Functions
+        fn text(text: &str);
+
+        fn text_disabled(text: &str);
+
+        fn text_colored(r: f32, g: f32, b: f32, a: f32, text: &str);
+
+        #[must_use]
+        fn button(label: &str) -> bool;
+
+        #[must_use]
+        fn radio_button(label: &str, active: bool) -> bool;
+
+        #[must_use]
+        fn input_text(label: &str, text: &mut String) -> bool;
+
+        #[must_use]
+        fn checkbox(label: &str, value: &mut bool) -> bool;
+
+        #[must_use]
+        fn drag_i64(label: &str, value: &mut i64, min: i64, max: i64) -> bool;
+
+        #[must_use]
+        fn drag_f64(label: &str, value: &mut f64, min: f64, max: f64) -> bool;
+
+        fn separator();
+
+        fn same_line();
+
+        fn progress_bar(fraction: f32, overlay: &str);
+
+        // Clipboard Functions
+        fn set_clipboard_text(text: &str);
     }
 }
 
@@ -65,13 +771,178 @@ pub mod mq {
         fn get_path_EverQuest() -> &'static str;
 
         // General Functions
-        fn write_chat_color(line: &str, color: i32);
+        fn write_chat_color(line: &[u8], color: i32);
+
+        fn write_chat_lines(lines: &[u8], color: i32);
+
+        fn do_command(command: &str);
+
+        #[must_use]
+        fn parse_macro_data(expression: &str, result: &mut String) -> bool;
+
+        #[must_use]
+        fn get_game_state() -> i32;
+
+        // Plugin Functions
+        #[must_use]
+        fn is_plugin_loaded(name: &str) -> bool;
+
+        #[must_use]
+        fn get_plugin_proc(name: &str, proc_name: &str) -> usize;
+
+        #[must_use]
+        fn first_plugin() -> *const MQPlugin;
+
+        // HUD Functions
+        fn draw_hud_text(text: &str, x: i32, y: i32, argb: u32);
+
+        // KeyBind Functions
+        #[must_use]
+        fn is_valid_key_combo(keys: &str) -> bool;
+
+        #[must_use]
+        fn add_keybind(
+            name: &str,
+            keys: &str,
+            callback: fn(name: &str, down: bool),
+        ) -> bool;
+
+        fn remove_keybind(name: &str);
+
+        // Command Functions
+        #[must_use]
+        fn max_commands() -> usize;
+
+        #[must_use]
+        fn add_command(
+            name: &str,
+            parse: bool,
+            in_game: bool,
+            callback: fn(name: &str, args: &[u8]),
+        ) -> bool;
+
+        fn remove_command(name: &str);
+
+        // Benchmark Functions
+        #[must_use]
+        fn add_benchmark(name: &str) -> u32;
+
+        fn remove_benchmark(id: u32);
+
+        fn enter_benchmark(id: u32);
+
+        fn exit_benchmark(id: u32);
+
+        // Ground Item Functions
+        #[must_use]
+        fn set_ground_target(drop_id: u32) -> bool;
+
+        #[must_use]
+        fn pick_up_ground_item(drop_id: u32) -> bool;
 
         // MQPlugin
         pub type MQPlugin;
 
         #[must_use]
         fn plugin_name(&self) -> &str;
+
+        #[must_use]
+        fn next(&self) -> *const MQPlugin;
+    }
+}
+
+#[cxx::bridge(namespace = "mqrust::ui")]
+pub mod ui {
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/ui.h");
+
+        // XML Functions
+        fn add_xml_file(xml: &str);
+
+        fn remove_xml_file(xml: &str);
+
+        // Window Functions
+        #[must_use]
+        fn create_window(screen: &str) -> u64;
+
+        fn destroy_window(id: u64);
+
+        fn set_window_visible(id: u64, visible: bool);
+
+        #[must_use]
+        fn set_window_child_text(id: u64, child: &str, text: &str) -> bool;
+    }
+}
+
+#[cxx::bridge(namespace = "mqrust::postoffice")]
+pub mod postoffice {
+    /// The address of an actor's mailbox, where an empty string is unset.
+    #[derive(Debug, Default, Clone)]
+    struct ActorAddress {
+        mailbox:   String,
+        plugin:    String,
+        server:    String,
+        character: String,
+    }
+
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/postoffice.h");
+
+        // Dropbox Functions
+        #[must_use]
+        fn add_dropbox(
+            mailbox: &str,
+            callback: fn(mailbox: &str, sender: &ActorAddress, payload: &[u8]),
+        ) -> bool;
+
+        fn remove_dropbox(mailbox: &str);
+
+        #[must_use]
+        fn post(mailbox: &str, address: &ActorAddress, payload: &[u8]) -> bool;
+    }
+}
+
+#[cxx::bridge(namespace = "mqrust::datatypes")]
+pub mod datatypes {
+    /// The kind of value that a member of a datatype has.
+    #[derive(Debug)]
+    enum MemberKind {
+        Null,
+        Int,
+        Float,
+        Bool,
+        Text,
+    }
+
+    /// The value of a member of a datatype, where only the field for its kind
+    /// is set.
+    #[derive(Debug, Clone)]
+    struct MemberValue {
+        kind:    MemberKind,
+        integer: i64,
+        number:  f64,
+        boolean: bool,
+        text:    String,
+    }
+
+    unsafe extern "C++" {
+        include!("macroquest-sys/include/datatypes.h");
+
+        // DataType Functions
+        #[must_use]
+        fn add_datatype(
+            name: &str,
+            members: &[String],
+            get_member: fn(
+                datatype: &str,
+                member: &str,
+                index: &str,
+                value: &mut MemberValue,
+            ) -> bool,
+            to_string: fn(datatype: &str, text: &mut String) -> bool,
+        ) -> bool;
+
+        fn remove_datatype(name: &str);
     }
 }
 
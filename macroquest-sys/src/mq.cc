@@ -1,6 +1,13 @@
 #include "macroquest-sys/include/mq.h"
 #include "macroquest-sys/src/lib.rs.h"
 
+#include <algorithm>
+#include <array>
+#include <cstring>
+#include <string>
+#include <unordered_map>
+#include <utility>
+
 namespace mqrust
 {
     namespace mq
@@ -17,12 +24,280 @@ namespace mqrust
         rust::Str get_path_EverQuest() { return ::mq::gPathEverQuest; }
 
         // General Functions
-        void write_chat_color(rust::Str line, int color)
+        void write_chat_color(rust::Slice<const uint8_t> line, int color)
+        {
+            // The line is already in the game's character set, rather than in
+            // UTF-8, so it's copied byte for byte.
+            std::string buffer(reinterpret_cast<const char*>(line.data()), line.size());
+            ::mq::WriteChatColor(buffer.c_str(), color);
+        }
+
+        void write_chat_lines(rust::Slice<const uint8_t> lines, int color)
+        {
+            // Each line is written on its own, as MacroQuest only handles a
+            // single line at a time, but they only cross over from Rust once.
+            auto begin = reinterpret_cast<const char*>(lines.data());
+            auto end = begin + lines.size();
+
+            std::string buffer;
+            while (begin != end)
+            {
+                auto newline = std::find(begin, end, '\n');
+                buffer.assign(begin, newline);
+                ::mq::WriteChatColor(buffer.c_str(), color);
+
+                begin = newline == end ? end : newline + 1;
+            }
+        }
+
+        void do_command(rust::Str command)
+        {
+            ::mq::EzCommand(static_cast<std::string>(command).c_str());
+        }
+
+        bool parse_macro_data(rust::Str expression, rust::String& result)
+        {
+            char buffer[::mq::MAX_STRING] = { 0 };
+            strncpy_s(buffer, expression.data(), std::min(expression.size(), sizeof(buffer) - 1));
+
+            if (!::mq::ParseMacroData(buffer, sizeof(buffer)))
+            {
+                return false;
+            }
+
+            result = buffer;
+            return true;
+        }
+
+        int32_t get_game_state() { return ::mq::GetGameState(); }
+
+        // Plugin Functions
+        bool is_plugin_loaded(rust::Str name)
+        {
+            return ::mq::GetPlugin(static_cast<std::string>(name)) != nullptr;
+        }
+
+        size_t get_plugin_proc(rust::Str name, rust::Str proc_name)
+        {
+            auto proc = ::mq::GetPluginProc(
+                static_cast<std::string>(name).c_str(),
+                static_cast<std::string>(proc_name).c_str());
+
+            return reinterpret_cast<size_t>(proc);
+        }
+
+        const MQPlugin* first_plugin()
+        {
+            return reinterpret_cast<const MQPlugin*>(::mq::pPlugins);
+        }
+
+        // HUD Functions
+        void draw_hud_text(rust::Str text, int x, int y, uint32_t argb)
+        {
+            // This is the font that MQ2HUD draws with, unless told otherwise.
+            constexpr int font = 2;
+
+            ::mq::DrawHUDText(static_cast<std::string>(text).c_str(), x, y, argb, font);
+        }
+
+        // KeyBind Functions
+        //
+        // MacroQuest calls a keybind's function with just its name, so the Rust
+        // callbacks are looked up by name from a single function.
+        static std::unordered_map<std::string, rust::Fn<void(rust::Str, bool)>> s_keybinds;
+
+        static void on_keybind(const char* name, bool down)
+        {
+            auto it = s_keybinds.find(name);
+            if (it != s_keybinds.end())
+            {
+                it->second(rust::Str(name), down);
+            }
+        }
+
+        bool is_valid_key_combo(rust::Str keys)
+        {
+            ::mq::KeyCombo combo;
+            return ::mq::ParseKeyCombo(std::string(keys).c_str(), combo);
+        }
+
+        bool add_keybind(rust::Str name, rust::Str keys, rust::Fn<void(rust::Str, bool)> callback)
+        {
+            std::string bind_name(name);
+
+            ::mq::KeyCombo combo;
+            if (!::mq::ParseKeyCombo(std::string(keys).c_str(), combo))
+            {
+                return false;
+            }
+
+            if (!::mq::AddMQ2KeyBind(bind_name.c_str(), on_keybind))
+            {
+                return false;
+            }
+
+            ::mq::SetMQ2KeyBind(bind_name.c_str(), false, combo);
+            s_keybinds.insert_or_assign(bind_name, callback);
+            return true;
+        }
+
+        void remove_keybind(rust::Str name)
+        {
+            std::string bind_name(name);
+
+            ::mq::RemoveMQ2KeyBind(bind_name.c_str());
+            s_keybinds.erase(bind_name);
+        }
+
+        // Command Functions
+        //
+        // MacroQuest calls a command's function with just its arguments, not
+        // its name, so each command is given one of a fixed number of
+        // functions, which looks its Rust callback up by the slot it was given.
+        constexpr size_t MAX_COMMANDS = 64;
+
+        struct Command
+        {
+            std::string name;
+            rust::Fn<void(rust::Str, rust::Slice<const uint8_t>)> callback;
+        };
+
+        static std::unordered_map<size_t, Command> s_commands;
+
+        template <size_t Slot>
+        static void on_command(::eqlib::PlayerClient*, const char* line)
+        {
+            auto it = s_commands.find(Slot);
+            if (it != s_commands.end())
+            {
+                // The arguments are in the game's character set, rather than
+                // in UTF-8, so they're passed over as bytes.
+                rust::Slice<const uint8_t> args(reinterpret_cast<const uint8_t*>(line), std::strlen(line));
+                it->second.callback(rust::Str(it->second.name), args);
+            }
+        }
+
+        template <size_t... Slots>
+        static constexpr auto command_functions(std::index_sequence<Slots...>)
         {
-            ::mq::WriteChatColor(static_cast<std::string>(line).c_str(), color);
+            return std::array<::mq::fEQCommand, sizeof...(Slots)>{&on_command<Slots>...};
+        }
+
+        static constexpr auto s_command_functions = command_functions(std::make_index_sequence<MAX_COMMANDS>{});
+
+        size_t max_commands()
+        {
+            return MAX_COMMANDS;
+        }
+
+        bool add_command(rust::Str name, bool parse, bool in_game, rust::Fn<void(rust::Str, rust::Slice<const uint8_t>)> callback)
+        {
+            std::string command(name);
+
+            if (::mq::IsCommand(command.c_str()))
+            {
+                return false;
+            }
+
+            for (size_t slot = 0; slot < MAX_COMMANDS; ++slot)
+            {
+                if (s_commands.find(slot) == s_commands.end())
+                {
+                    ::mq::AddCommand(command.c_str(), s_command_functions[slot], false, parse, in_game);
+                    s_commands.emplace(slot, Command{command, callback});
+                    return true;
+                }
+            }
+
+            return false;
+        }
+
+        void remove_command(rust::Str name)
+        {
+            std::string command(name);
+
+            auto it = std::find_if(s_commands.begin(), s_commands.end(),
+                [&](const auto& entry) { return entry.second.name == command; });
+            if (it != s_commands.end())
+            {
+                ::mq::RemoveCommand(command.c_str());
+                s_commands.erase(it);
+            }
+        }
+
+        // Benchmark Functions
+        uint32_t add_benchmark(rust::Str name)
+        {
+            return ::mq::AddMQ2Benchmark(static_cast<std::string>(name).c_str());
+        }
+
+        void remove_benchmark(uint32_t id)
+        {
+            ::mq::RemoveMQ2Benchmark(id);
+        }
+
+        void enter_benchmark(uint32_t id)
+        {
+            ::mq::EnterMQ2Benchmark(id);
+        }
+
+        void exit_benchmark(uint32_t id)
+        {
+            ::mq::ExitMQ2Benchmark(id);
+        }
+
+        // Ground Item Functions
+        static ::eqlib::EQGroundItem* find_ground_item(uint32_t drop_id)
+        {
+            if (!::eqlib::pLocalPlayer || !::eqlib::pItemList)
+            {
+                return nullptr;
+            }
+
+            for (auto item = ::eqlib::pItemList->Top; item; item = item->pNext)
+            {
+                if (item->DropID == drop_id)
+                {
+                    return item;
+                }
+            }
+
+            return nullptr;
+        }
+
+        bool set_ground_target(uint32_t drop_id)
+        {
+            auto item = find_ground_item(drop_id);
+            if (!item)
+            {
+                return false;
+            }
+
+            // This is the same as what `/itemtarget` does.
+            ::mq::pGroundTarget = item;
+            return true;
+        }
+
+        bool pick_up_ground_item(uint32_t drop_id)
+        {
+            auto item = find_ground_item(drop_id);
+            if (!item)
+            {
+                return false;
+            }
+
+            // This is the same as what `/click left item` does with the item
+            // targeted, which picks it up onto the cursor.
+            ::mq::pGroundTarget = item;
+            return ::mq::ClickMouseItem(::mq::MQGroundSpawn(item), true);
         }
 
         // MQPlugin Functions
         rust::Str MQPlugin::plugin_name() const { return this->name; }
+
+        const MQPlugin* MQPlugin::next() const
+        {
+            return reinterpret_cast<const MQPlugin*>(this->pNext);
+        }
     }
 }
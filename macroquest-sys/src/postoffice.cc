@@ -0,0 +1,125 @@
+#include "macroquest-sys/include/postoffice.h"
+#include "macroquest-sys/src/lib.rs.h"
+
+#include <optional>
+#include <string>
+#include <unordered_map>
+
+namespace mqrust
+{
+    namespace postoffice
+    {
+        namespace
+        {
+            // The dropboxes we've added, keyed by their mailbox name. The post
+            // office holds onto the callback of a dropbox until it's removed,
+            // and that callback calls into Rust, so every dropbox must be
+            // removed before the plugin is unloaded.
+            std::unordered_map<std::string, ::mq::postoffice::Dropbox> dropboxes;
+
+            std::optional<std::string> optional(const rust::String& value)
+            {
+                if (value.empty())
+                {
+                    return std::nullopt;
+                }
+
+                return std::string(value);
+            }
+
+            rust::String from_optional(const std::optional<std::string>& value)
+            {
+                return value ? rust::String(*value) : rust::String();
+            }
+
+            ::mq::postoffice::Address to_address(const ActorAddress& address)
+            {
+                ::mq::postoffice::Address result;
+                result.Mailbox = std::string(address.mailbox);
+                result.Name = optional(address.plugin);
+                result.Server = optional(address.server);
+                result.Character = optional(address.character);
+
+                return result;
+            }
+
+            ActorAddress from_address(const ::mq::postoffice::Address& address)
+            {
+                ActorAddress result;
+                result.mailbox = from_optional(address.Mailbox);
+                result.plugin = from_optional(address.Name);
+                result.server = from_optional(address.Server);
+                result.character = from_optional(address.Character);
+
+                return result;
+            }
+        }
+
+        // Dropbox Functions
+        bool add_dropbox(
+            rust::Str mailbox,
+            rust::Fn<void(rust::Str, const ActorAddress&, rust::Slice<const uint8_t>)> callback)
+        {
+            std::string name(mailbox);
+            if (dropboxes.count(name))
+            {
+                return false;
+            }
+
+            auto dropbox = ::mq::postoffice::AddActor(
+                name.c_str(),
+                [name, callback](const ::mq::postoffice::MessagePtr& message) {
+                    ActorAddress sender;
+                    if (message->Sender)
+                    {
+                        sender = from_address(*message->Sender);
+                    }
+
+                    rust::Slice<const uint8_t> payload;
+                    if (message->Payload)
+                    {
+                        payload = rust::Slice<const uint8_t>(
+                            reinterpret_cast<const uint8_t*>(message->Payload->data()),
+                            message->Payload->size());
+                    }
+
+                    callback(rust::Str(name), sender, payload);
+                });
+
+            // The post office hands back an invalid dropbox when the mailbox is
+            // already taken by someone else.
+            if (!dropbox.IsValid())
+            {
+                return false;
+            }
+
+            dropboxes.emplace(std::move(name), std::move(dropbox));
+            return true;
+        }
+
+        void remove_dropbox(rust::Str mailbox)
+        {
+            auto it = dropboxes.find(std::string(mailbox));
+            if (it != dropboxes.end())
+            {
+                it->second.Remove();
+                dropboxes.erase(it);
+            }
+        }
+
+        bool post(rust::Str mailbox, const ActorAddress& address, rust::Slice<const uint8_t> payload)
+        {
+            auto it = dropboxes.find(std::string(mailbox));
+            if (it == dropboxes.end())
+            {
+                return false;
+            }
+
+            it->second.Post(
+                to_address(address),
+                std::string(reinterpret_cast<const char*>(payload.data()), payload.size()));
+
+            return true;
+        }
+    }
+}
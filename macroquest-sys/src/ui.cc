@@ -0,0 +1,78 @@
+#include "macroquest-sys/include/ui.h"
+#include "macroquest-sys/src/lib.rs.h"
+
+#include <memory>
+#include <string>
+#include <unordered_map>
+
+namespace mqrust
+{
+    namespace ui
+    {
+        namespace
+        {
+            // The windows we've created, keyed by an id that we hand out to
+            // Rust, so that Rust never has to hold onto a pointer into the UI.
+            std::unordered_map<uint64_t, std::unique_ptr<::mq::CCustomWnd>> windows;
+            uint64_t next_id = 1;
+
+            ::mq::CCustomWnd* find_window(uint64_t id)
+            {
+                auto it = windows.find(id);
+                return it == windows.end() ? nullptr : it->second.get();
+            }
+        }
+
+        // XML Functions
+        void add_xml_file(rust::Str xml) { ::mq::AddXMLFile(std::string(xml).c_str()); }
+
+        void remove_xml_file(rust::Str xml) { ::mq::RemoveXMLFile(std::string(xml).c_str()); }
+
+        // Window Functions
+        uint64_t create_window(rust::Str screen)
+        {
+            ::eqlib::CXStr name(std::string(screen).c_str());
+
+            // Creating a window from a screen that doesn't exist will crash the
+            // game, and the screens only exist while the UI is loaded.
+            if (!::eqlib::pSidlMgr || !::eqlib::pSidlMgr->FindScreenPieceTemplate(name))
+            {
+                return 0;
+            }
+
+            uint64_t id = next_id++;
+            windows.emplace(id, std::make_unique<::mq::CCustomWnd>(name));
+
+            return id;
+        }
+
+        void destroy_window(uint64_t id) { windows.erase(id); }
+
+        void set_window_visible(uint64_t id, bool visible)
+        {
+            if (auto window = find_window(id))
+            {
+                window->SetVisible(visible);
+            }
+        }
+
+        bool set_window_child_text(uint64_t id, rust::Str child, rust::Str text)
+        {
+            auto window = find_window(id);
+            if (!window)
+            {
+                return false;
+            }
+
+            auto item = window->GetChildItem(::eqlib::CXStr(std::string(child).c_str()));
+            if (!item)
+            {
+                return false;
+            }
+
+            item->SetWindowText(::eqlib::CXStr(std::string(text).c_str()));
+
+            return true;
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+use std::{env, fs};
+
+#[path = "build/ffi_stub.rs"]
+mod ffi_stub;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=build/ffi_stub.rs");
+    println!("cargo:rerun-if-changed=ffi_stub.txt");
+
+    // docs.rs can't build macroquest-sys, so the docs are built against a stub
+    // of it instead, which we generate from the list of the items in it.
+    let list = fs::read_to_string("ffi_stub.txt").expect("could not read ffi_stub.txt");
+    let stub = ffi_stub::generate(&ffi_stub::parse(&list));
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("ffi_stub.rs"), stub).expect("could not write ffi_stub.rs");
+}
@@ -0,0 +1,285 @@
+// Parsing and generating the stub of macroquest-sys that the docs.rs build
+// uses.
+//
+// This is shared between build.rs, which generates the stub, and
+// tests/ffi_stub.rs, which checks that the list of items it's generated from
+// matches macroquest-sys, so not everything is used by both.
+#![allow(dead_code)]
+
+use std::fmt::Write;
+
+/// A module of the bridge, or the crate root (which has an empty name).
+#[derive(Debug, Default)]
+pub struct Module {
+    pub name:  String,
+    pub items: Vec<Item>,
+}
+
+/// An item within a module of the bridge.
+#[derive(Debug)]
+pub enum Item {
+    /// A constant, along with the value that the stub gives it.
+    Const { decl: String, value: String },
+    /// A function signature (ex: `fn zone_count() -> u32`).
+    Fn { signature: String },
+    /// A shared struct, with its fields (ex: `x: f32`).
+    Struct {
+        attrs:  Vec<String>,
+        name:   String,
+        fields: Vec<String>,
+    },
+    /// A shared enum, with its variants.
+    Enum {
+        attrs:    Vec<String>,
+        name:     String,
+        variants: Vec<String>,
+    },
+    /// An opaque C++ type, with its method signatures.
+    Type {
+        name:    String,
+        methods: Vec<String>,
+    },
+}
+
+/// Parse a list of the items in the bridge.
+///
+/// Each line is either a `mod` (at the top level), or an item within the
+/// module above it (indented once), or a field, variant, or method of the item
+/// above it (indented twice). Blank lines and `//` comments are skipped, and
+/// `#[...]` attributes apply to the next item.
+///
+/// # Panics
+///
+/// Panics on any line that can't be parsed, naming the line.
+#[must_use]
+pub fn parse(list: &str) -> Vec<Module> {
+    let mut modules = vec![Module::default()];
+    let mut attrs = Vec::new();
+
+    for (number, line) in list.lines().enumerate() {
+        let content = line.trim_start();
+        if content.is_empty() || content.starts_with("//") {
+            continue;
+        }
+
+        let fail = |reason: &str| -> ! {
+            panic!("line {}: {reason}: {line:?}", number + 1);
+        };
+
+        let module = modules.last_mut().unwrap();
+        match ((line.len() - content.len()) / 4, content) {
+            (0, content) if content.starts_with("mod ") => {
+                modules.push(Module {
+                    name:  content["mod ".len()..].to_owned(),
+                    items: Vec::new(),
+                });
+            }
+            (0, content) if content.starts_with("const ") => {
+                let Some((decl, value)) = content.split_once(" = ")
+                else {
+                    fail("a const needs a value")
+                };
+
+                modules[0].items.push(Item::Const {
+                    decl:  decl.to_owned(),
+                    value: value.to_owned(),
+                });
+            }
+            (1, content) if content.starts_with("#[") => {
+                attrs.push(content.to_owned());
+            }
+            (1, content) => {
+                let attrs = std::mem::take(&mut attrs);
+                let item = match content.split_once(' ') {
+                    Some(("fn", _)) => Item::Fn {
+                        signature: content.to_owned(),
+                    },
+                    Some(("struct", name)) => Item::Struct {
+                        attrs,
+                        name: name.to_owned(),
+                        fields: Vec::new(),
+                    },
+                    Some(("enum", name)) => Item::Enum {
+                        attrs,
+                        name: name.to_owned(),
+                        variants: Vec::new(),
+                    },
+                    Some(("type", name)) => Item::Type {
+                        name:    name.to_owned(),
+                        methods: Vec::new(),
+                    },
+                    _ => fail("unknown item"),
+                };
+
+                module.items.push(item);
+            }
+            (2, content) => match module.items.last_mut() {
+                Some(
+                    Item::Struct { fields: parts, .. }
+                    | Item::Enum {
+                        variants: parts, ..
+                    }
+                    | Item::Type { methods: parts, .. },
+                ) => {
+                    parts.push(content.to_owned());
+                }
+                _ => fail("nothing to add this to"),
+            },
+            _ => fail("unexpected indentation"),
+        }
+    }
+
+    modules
+}
+
+/// Generate the stub of the bridge, as Rust source, where every function
+/// panics with `unimplemented!()`.
+#[must_use]
+pub fn generate(modules: &[Module]) -> String {
+    let mut out = String::from("// @generated by build.rs from ffi_stub.txt\n");
+
+    for module in modules {
+        let indent = if module.name.is_empty() {
+            ""
+        }
+        else {
+            writeln!(out, "\npub mod {} {{", module.name).unwrap();
+            "    "
+        };
+
+        for item in &module.items {
+            out.push('\n');
+            generate_item(&mut out, indent, item);
+        }
+
+        if !module.name.is_empty() {
+            out.push_str("}\n");
+        }
+    }
+
+    out
+}
+
+fn generate_item(out: &mut String, indent: &str, item: &Item) {
+    match item {
+        Item::Const { decl, value } => {
+            writeln!(out, "{indent}pub {decl} = {value};").unwrap();
+        }
+        Item::Fn { signature } => {
+            writeln!(out, "{indent}pub {signature} {{").unwrap();
+            writeln!(out, "{indent}    unimplemented!()").unwrap();
+            writeln!(out, "{indent}}}").unwrap();
+        }
+        Item::Struct {
+            attrs,
+            name,
+            fields,
+        } => {
+            for attr in attrs {
+                writeln!(out, "{indent}{attr}").unwrap();
+            }
+            writeln!(out, "{indent}pub struct {name} {{").unwrap();
+            for field in fields {
+                writeln!(out, "{indent}    pub {field},").unwrap();
+            }
+            writeln!(out, "{indent}}}").unwrap();
+        }
+        Item::Enum {
+            attrs,
+            name,
+            variants,
+        } => {
+            // cxx represents shared enums as a struct wrapping their
+            // discriminant, with a constant for each variant.
+            for attr in attrs {
+                writeln!(out, "{indent}{attr}").unwrap();
+            }
+            writeln!(out, "{indent}#[derive(Clone, Copy, PartialEq, Eq)]").unwrap();
+            writeln!(out, "{indent}#[repr(transparent)]").unwrap();
+            writeln!(out, "{indent}pub struct {name} {{").unwrap();
+            writeln!(out, "{indent}    pub repr: u8,").unwrap();
+            writeln!(out, "{indent}}}\n").unwrap();
+            writeln!(out, "{indent}#[allow(non_upper_case_globals)]").unwrap();
+            writeln!(out, "{indent}impl {name} {{").unwrap();
+            for (repr, variant) in variants.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{indent}    pub const {variant}: Self = {name} {{ repr: {repr} }};"
+                )
+                .unwrap();
+            }
+            writeln!(out, "{indent}}}").unwrap();
+        }
+        Item::Type { name, methods } => {
+            writeln!(out, "{indent}pub struct {name};").unwrap();
+            if !methods.is_empty() {
+                writeln!(out, "\n{indent}impl {name} {{").unwrap();
+                for method in methods {
+                    writeln!(out, "{indent}    pub {method} {{").unwrap();
+                    writeln!(out, "{indent}        unimplemented!()").unwrap();
+                    writeln!(out, "{indent}    }}").unwrap();
+                }
+                writeln!(out, "{indent}}}").unwrap();
+            }
+        }
+    }
+}
+
+/// Every item in `modules`, each as a single normalized line, for comparing
+/// against the items that are actually in the bridge.
+#[must_use]
+pub fn signatures(modules: &[Module]) -> Vec<String> {
+    let mut signatures = Vec::new();
+
+    for module in modules {
+        let path = |item: &str| {
+            if module.name.is_empty() {
+                normalize(item)
+            }
+            else {
+                format!("{}::{}", module.name, normalize(item))
+            }
+        };
+
+        for item in &module.items {
+            match item {
+                Item::Const { decl, .. } => signatures.push(path(decl)),
+                Item::Fn { signature } => signatures.push(path(signature)),
+                Item::Struct { name, fields, .. } => {
+                    signatures.push(path(&format!(
+                        "struct {name} {{ {} }}",
+                        fields.join(", ")
+                    )));
+                }
+                Item::Enum { name, variants, .. } => {
+                    signatures.push(path(&format!(
+                        "enum {name} {{ {} }}",
+                        variants.join(", ")
+                    )));
+                }
+                Item::Type { name, methods } => {
+                    signatures.push(path(&format!("type {name}")));
+                    for method in methods {
+                        signatures.push(path(&format!("{name}::{method}")));
+                    }
+                }
+            }
+        }
+    }
+
+    signatures.sort();
+    signatures
+}
+
+/// Normalize the whitespace (and trailing commas) of a piece of Rust source,
+/// so that an item spread across lines matches the same item on one line.
+#[must_use]
+pub fn normalize(source: &str) -> String {
+    source
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(",)", ")")
+}
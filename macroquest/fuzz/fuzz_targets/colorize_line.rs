@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use macroquest::mq::fuzzing::colorize_line;
+
+fuzz_target!(|line: &str| {
+    let colorized = colorize_line(line);
+
+    // Lines without any escape sequences or control characters (other than
+    // tabs and MacroQuest's own color codes) are passed through untouched.
+    if !line.chars().any(|c| c.is_control() && c != '\t' && c != '\x07') {
+        assert_eq!(colorized, line);
+    }
+
+    // Only SGR sequences are converted, everything else is removed.
+    assert!(!colorized.contains('\x1b'));
+
+    // Each escape sequence is at least three bytes, and is replaced by at most
+    // five bytes of color codes, so the output can't be much larger than the
+    // input.
+    assert!(colorized.len() <= line.len() * 2 + 5);
+});
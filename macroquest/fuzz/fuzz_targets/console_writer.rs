@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use macroquest::mq::fuzzing::{console_lines, MAX_CONSOLE_LINE_LENGTH};
+
+fuzz_target!(|writes: Vec<&[u8]>| {
+    let (lines, results) = console_lines(writes.iter().copied());
+
+    for line in &lines {
+        assert!(line.len() <= MAX_CONSOLE_LINE_LENGTH);
+        assert!(!line.contains('\n'));
+    }
+
+    // When every write succeeds, nothing is lost or invented, other than the
+    // newlines, and the trailing partial line that is still buffered.
+    if results.iter().all(|ok| *ok) {
+        let input = writes.concat();
+        let output = lines.concat();
+        let input = String::from_utf8_lossy(&input).replace('\n', "");
+
+        assert!(input.starts_with(&output));
+    }
+});
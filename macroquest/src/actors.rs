@@ -0,0 +1,466 @@
+//! Messaging other plugins and other clients, through MacroQuest's post office.
+//!
+//! The post office (also called "actors") delivers messages between mailboxes,
+//! which may belong to a plugin in the same client, or to a plugin (or Lua
+//! script) in another client that's connected through the MacroQuest launcher.
+//! A plugin gets a mailbox by [registering](register()) one, and then can both
+//! receive messages sent to it and send messages from it.
+//!
+//! Messages are just bytes, and how they're encoded is left to the sender and
+//! receiver to agree on. MacroQuest's own actors use protobuf, but anything
+//! works as long as both ends understand it. With the `serde` feature enabled,
+//! [`Mailbox::send_json()`] and [`Envelope::json()`] encode and decode messages
+//! as JSON.
+//!
+//! ```no_run
+//! use macroquest::actors::{self, Address};
+//!
+//! let mailbox = actors::register("my_plugin", |envelope| {
+//!     macroquest::log::info!(len = envelope.payload.len(), "got a message");
+//! })
+//! .unwrap();
+//!
+//! mailbox
+//!     .send(
+//!         &Address::new("my_plugin").character("server", "Bob"),
+//!         b"hello",
+//!     )
+//!     .unwrap();
+//! ```
+//!
+//! # Lifetimes
+//!
+//! The post office keeps hold of the function that it calls for a mailbox
+//! until the mailbox is removed, and that function lives in this plugin. If
+//! the plugin were unloaded with a mailbox still registered, the next message
+//! sent to it would call into a plugin that's no longer there, and crash the
+//! game. To prevent that:
+//!
+//! - A mailbox is removed when its [`Mailbox`] is dropped.
+//! - Every mailbox that's still registered is removed when the plugin is
+//!   unloaded, after the plugin itself has been dropped. A [`Mailbox`] that
+//!   outlives that (such as one in a `static`) stops receiving messages, and
+//!   sending from it fails with [`ActorError::Removed`].
+//!
+//! Messages are delivered on the game's main thread, and everything in here
+//! must be called from it too.
+
+use std::collections::BTreeMap;
+use std::{error, fmt};
+
+use parking_lot::Mutex;
+
+use crate::ffi::postoffice as polib;
+use crate::log::error;
+use crate::macros::debug_assert_main_thread;
+
+type Handler = Box<dyn FnMut(Envelope) + Send>;
+
+static MAILBOXES: Mutex<Registry> = Mutex::new(Registry::new());
+
+/// Register a mailbox named `mailbox`, calling `handler` with every message
+/// that's sent to it.
+///
+/// The mailbox is removed when the returned [`Mailbox`] is dropped, or when
+/// the plugin is unloaded, whichever comes first.
+///
+/// # Errors
+///
+/// Returns an error if there's already a mailbox named `mailbox` (whether
+/// from this plugin or another).
+pub fn register<F>(mailbox: &str, handler: F) -> Result<Mailbox, ActorError>
+where
+    F: FnMut(Envelope) + Send + 'static,
+{
+    debug_assert_main_thread!("macroquest::actors::register");
+
+    let mut mailboxes = MAILBOXES.lock();
+    if !mailboxes.insert(mailbox, Box::new(handler)) {
+        return Err(ActorError::AlreadyRegistered {
+            mailbox: mailbox.to_owned(),
+        });
+    }
+
+    if !polib::add_dropbox(mailbox, dispatch) {
+        mailboxes.remove(mailbox);
+        return Err(ActorError::AlreadyRegistered {
+            mailbox: mailbox.to_owned(),
+        });
+    }
+
+    Ok(Mailbox {
+        name: mailbox.to_owned(),
+    })
+}
+
+/// A mailbox registered with the post office.
+///
+/// The mailbox is removed when this is dropped.
+#[derive(Debug)]
+pub struct Mailbox {
+    name: String,
+}
+
+impl Mailbox {
+    /// The name that the mailbox was registered with.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Send `payload` from this mailbox to the mailbox at `address`.
+    ///
+    /// The post office requires every message to come from a mailbox, which
+    /// is why this is a method rather than a free function. The message is
+    /// delivered later, and sending it succeeds even if there's nothing at
+    /// `address` to receive it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this mailbox has already been removed, as the plugin
+    /// is being unloaded.
+    pub fn send(&self, address: &Address, payload: &[u8]) -> Result<(), ActorError> {
+        debug_assert_main_thread!("macroquest::actors::Mailbox::send");
+
+        if !polib::post(&self.name, &address.to_raw(), payload) {
+            return Err(ActorError::Removed {
+                mailbox: self.name.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Send `value`, encoded as JSON, from this mailbox to the mailbox at
+    /// `address`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` can't be encoded as JSON, or if this mailbox
+    /// has already been removed.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    #[cfg(feature = "serde")]
+    pub fn send_json<T>(&self, address: &Address, value: &T) -> Result<(), ActorError>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        let payload = serde_json::to_vec(value)
+            .map_err(|err| ActorError::Encode(err.to_string()))?;
+
+        self.send(address, &payload)
+    }
+}
+
+impl Drop for Mailbox {
+    fn drop(&mut self) {
+        debug_assert_main_thread!("macroquest::actors::Mailbox::drop");
+
+        if MAILBOXES.lock().remove(&self.name) {
+            polib::remove_dropbox(&self.name);
+        }
+    }
+}
+
+/// The address of a mailbox.
+///
+/// An address with just a mailbox name is delivered to that mailbox in the
+/// same client, and the other parts narrow it down to a specific plugin or a
+/// specific character in another client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Address {
+    /// The name of the mailbox.
+    pub mailbox:   String,
+    /// The name of the plugin that owns the mailbox.
+    pub plugin:    Option<String>,
+    /// The server of the client that owns the mailbox.
+    pub server:    Option<String>,
+    /// The name of the character logged in to the client that owns the
+    /// mailbox.
+    pub character: Option<String>,
+}
+
+impl Address {
+    /// The address of the mailbox named `mailbox`.
+    #[must_use]
+    pub fn new(mailbox: &str) -> Address {
+        Address {
+            mailbox:   mailbox.to_owned(),
+            plugin:    None,
+            server:    None,
+            character: None,
+        }
+    }
+
+    /// Narrow the address down to the mailbox owned by the plugin named
+    /// `plugin`.
+    #[must_use]
+    pub fn plugin(mut self, plugin: &str) -> Address {
+        self.plugin = Some(plugin.to_owned());
+        self
+    }
+
+    /// Narrow the address down to the client where `character` is logged in
+    /// to `server`.
+    #[must_use]
+    pub fn character(mut self, server: &str, character: &str) -> Address {
+        self.server = Some(server.to_owned());
+        self.character = Some(character.to_owned());
+        self
+    }
+
+    fn from_raw(raw: &polib::ActorAddress) -> Option<Address> {
+        let optional = |value: &str| (!value.is_empty()).then(|| value.to_owned());
+
+        if raw.mailbox.is_empty()
+            && raw.plugin.is_empty()
+            && raw.server.is_empty()
+            && raw.character.is_empty()
+        {
+            return None;
+        }
+
+        Some(Address {
+            mailbox:   raw.mailbox.clone(),
+            plugin:    optional(&raw.plugin),
+            server:    optional(&raw.server),
+            character: optional(&raw.character),
+        })
+    }
+
+    fn to_raw(&self) -> polib::ActorAddress {
+        polib::ActorAddress {
+            mailbox:   self.mailbox.clone(),
+            plugin:    self.plugin.clone().unwrap_or_default(),
+            server:    self.server.clone().unwrap_or_default(),
+            character: self.character.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// A message delivered to a [`Mailbox`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    /// The address of the mailbox that sent the message, if the post office
+    /// knows it.
+    pub sender:  Option<Address>,
+    /// The message itself.
+    pub payload: Vec<u8>,
+}
+
+impl Envelope {
+    /// Decode the message as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message isn't JSON, or doesn't match `T`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    #[cfg(feature = "serde")]
+    pub fn json<T>(&self) -> Result<T, ActorError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        serde_json::from_slice(&self.payload)
+            .map_err(|err| ActorError::Decode(err.to_string()))
+    }
+}
+
+/// An error using the post office.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ActorError {
+    /// There's already a mailbox with the same name.
+    AlreadyRegistered {
+        /// The name of the mailbox.
+        mailbox: String,
+    },
+    /// The mailbox has been removed, as the plugin is being unloaded.
+    Removed {
+        /// The name of the mailbox.
+        mailbox: String,
+    },
+    /// A message couldn't be encoded.
+    Encode(String),
+    /// A message couldn't be decoded.
+    Decode(String),
+}
+
+impl fmt::Display for ActorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActorError::AlreadyRegistered { mailbox } => {
+                write!(f, "a mailbox named {mailbox:?} already exists")
+            }
+            ActorError::Removed { mailbox } => {
+                write!(f, "the mailbox named {mailbox:?} has been removed")
+            }
+            ActorError::Encode(err) => write!(f, "could not encode message: {err}"),
+            ActorError::Decode(err) => write!(f, "could not decode message: {err}"),
+        }
+    }
+}
+
+impl error::Error for ActorError {}
+
+/// Remove every mailbox, as the plugin is being unloaded.
+pub(crate) fn clear() {
+    for name in MAILBOXES.lock().clear() {
+        polib::remove_dropbox(&name);
+    }
+}
+
+/// Called by the post office with every message sent to one of our mailboxes.
+fn dispatch(mailbox: &str, sender: &polib::ActorAddress, payload: &[u8]) {
+    // The handler is taken out of the registry while it's called, so that it
+    // can register or drop mailboxes itself without deadlocking.
+    let Some(mut handler) = MAILBOXES.lock().take(mailbox)
+    else {
+        return;
+    };
+
+    let envelope = Envelope {
+        sender:  Address::from_raw(sender),
+        payload: payload.to_vec(),
+    };
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        handler(envelope);
+    }));
+
+    if result.is_err() {
+        error!(mailbox, "mailbox handler panicked");
+    }
+
+    MAILBOXES.lock().restore(mailbox, handler);
+}
+
+struct Registry {
+    // A handler is None while it's being called.
+    handlers: BTreeMap<String, Option<Handler>>,
+}
+
+impl Registry {
+    const fn new() -> Registry {
+        Registry {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    /// Add a handler, returning false if there's already one with that name.
+    fn insert(&mut self, name: &str, handler: Handler) -> bool {
+        if self.handlers.contains_key(name) {
+            return false;
+        }
+
+        self.handlers.insert(name.to_owned(), Some(handler));
+        true
+    }
+
+    /// Remove a handler, returning whether there was one with that name.
+    fn remove(&mut self, name: &str) -> bool {
+        self.handlers.remove(name).is_some()
+    }
+
+    /// Take a handler out to call it.
+    fn take(&mut self, name: &str) -> Option<Handler> {
+        self.handlers.get_mut(name)?.take()
+    }
+
+    /// Put a handler back after calling it, unless it was removed meanwhile.
+    fn restore(&mut self, name: &str, handler: Handler) {
+        if let Some(slot @ None) = self.handlers.get_mut(name) {
+            *slot = Some(handler);
+        }
+    }
+
+    /// Remove every handler, returning their names.
+    fn clear(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.handlers).into_keys().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_address_round_trip() {
+        let address = Address::new("inbox")
+            .plugin("MQRustActors")
+            .character("firiona", "Alice");
+
+        let raw = address.to_raw();
+        assert_eq!(raw.plugin, "MQRustActors");
+        assert_eq!(Address::from_raw(&raw), Some(address));
+    }
+
+    #[test]
+    fn test_address_unset() {
+        let raw = Address::new("inbox").to_raw();
+        assert_eq!(raw.server, "");
+        assert_eq!(raw.character, "");
+
+        let address = Address::from_raw(&raw).unwrap();
+        assert_eq!(address.plugin, None);
+        assert_eq!(address.server, None);
+
+        assert_eq!(Address::from_raw(&polib::ActorAddress::default()), None);
+    }
+
+    #[test]
+    fn test_registry_take_and_restore() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = Registry::new();
+
+        let handler = {
+            let received = Arc::clone(&received);
+            Box::new(move |envelope: Envelope| received.lock().push(envelope.payload))
+        };
+        assert!(registry.insert("inbox", handler));
+        assert!(!registry.insert("inbox", Box::new(|_| {})));
+
+        let mut handler = registry.take("inbox").unwrap();
+        assert!(registry.take("inbox").is_none());
+
+        handler(Envelope {
+            sender:  None,
+            payload: b"hello".to_vec(),
+        });
+        registry.restore("inbox", handler);
+
+        assert!(registry.take("inbox").is_some());
+        assert_eq!(*received.lock(), vec![b"hello".to_vec()]);
+    }
+
+    #[test]
+    fn test_registry_removed_while_taken() {
+        let mut registry = Registry::new();
+        registry.insert("inbox", Box::new(|_| {}));
+
+        let handler = registry.take("inbox").unwrap();
+        assert!(registry.remove("inbox"));
+        registry.restore("inbox", handler);
+
+        assert!(registry.take("inbox").is_none());
+        assert!(registry.clear().is_empty());
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            ActorError::AlreadyRegistered {
+                mailbox: "inbox".into(),
+            }
+            .to_string(),
+            "a mailbox named \"inbox\" already exists"
+        );
+        assert_eq!(
+            ActorError::Removed {
+                mailbox: "inbox".into(),
+            }
+            .to_string(),
+            "the mailbox named \"inbox\" has been removed"
+        );
+    }
+}
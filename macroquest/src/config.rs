@@ -0,0 +1,249 @@
+//! Editing a plugin's settings in game.
+//!
+//! Deriving [`SettingsUi`](macro@crate::SettingsUi) for a settings struct
+//! draws an ImGui widget for each of its fields: a checkbox for a `bool`, a
+//! draggable number for an integer or float, a text input for a `String`, a
+//! combo box for an enum that also derives it, and a collapsing header for a
+//! nested struct that also derives it. [`settings_panel()`] draws them all,
+//! calling back whenever the settings have been changed so they can be saved.
+//!
+//! Fields can be customized with a `#[settings(...)]` attribute:
+//!
+//! - `label = "..."` replaces the label, which is otherwise the field's name in
+//!   title case.
+//! - `range = min..=max` limits a number to a range.
+//! - `skip` leaves the field out of the panel.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use macroquest::config::settings_panel;
+//! use macroquest::imgui::windows;
+//! use macroquest::SettingsUi;
+//! use parking_lot::Mutex;
+//!
+//! #[derive(SettingsUi)]
+//! enum Mode {
+//!     Passive,
+//!     Aggressive,
+//! }
+//!
+//! #[derive(SettingsUi)]
+//! struct Alerts {
+//!     enabled: bool,
+//!     #[settings(label = "Alert Text")]
+//!     text:    String,
+//! }
+//!
+//! #[derive(SettingsUi)]
+//! struct Settings {
+//!     mode:   Mode,
+//!     #[settings(range = 10..=500)]
+//!     radius: u32,
+//!     alerts: Alerts,
+//! }
+//!
+//! fn register(settings: Arc<Mutex<Settings>>) -> macroquest::imgui::WindowHandle {
+//!     windows().register("My Plugin Settings", move |ui| {
+//!         settings_panel(ui, &mut *settings.lock(), |_settings| {
+//!             // Save the settings, now that they have changed.
+//!         });
+//!     })
+//! }
+//! ```
+
+use std::ops::RangeInclusive;
+
+use crate::imgui::Ui;
+
+/// A value that can be edited with ImGui widgets.
+///
+/// This is implemented for `bool`, the integer and float types, and `String`,
+/// and can be derived for structs with named fields and for enums with unit
+/// variants (see [`SettingsUi`](macro@crate::SettingsUi)).
+pub trait SettingsUi {
+    /// Draw the widgets editing this value, labelled `label`, returning
+    /// whether it was changed.
+    fn settings_ui(&mut self, ui: &Ui, label: &str) -> bool;
+
+    /// Draw the widgets editing this value as the whole of a settings panel,
+    /// returning whether it was changed.
+    ///
+    /// By default this is the same as [`SettingsUi::settings_ui()`] without a
+    /// label, while a derived struct draws each of its fields without putting
+    /// them under a collapsing header.
+    fn settings_fields(&mut self, ui: &Ui) -> bool {
+        self.settings_ui(ui, "")
+    }
+}
+
+/// A number that can be edited with ImGui widgets while limited to a range,
+/// which is used for fields with a `#[settings(range = ...)]` attribute.
+pub trait SettingsRange: SettingsUi + Sized {
+    /// Draw the widgets editing this value, labelled `label` and limited to
+    /// `range`, returning whether it was changed.
+    fn settings_ui_range(
+        &mut self,
+        ui: &Ui,
+        label: &str,
+        range: RangeInclusive<Self>,
+    ) -> bool;
+}
+
+/// Draw the widgets editing `settings`, calling `on_changed` with the new
+/// settings if any of them were changed.
+pub fn settings_panel<T, F>(ui: &Ui, settings: &mut T, on_changed: F)
+where
+    T: SettingsUi,
+    F: FnOnce(&T),
+{
+    if settings.settings_fields(ui) {
+        on_changed(settings);
+    }
+}
+
+impl SettingsUi for bool {
+    fn settings_ui(&mut self, ui: &Ui, label: &str) -> bool {
+        ui.checkbox(label, self)
+    }
+}
+
+impl SettingsUi for String {
+    fn settings_ui(&mut self, ui: &Ui, label: &str) -> bool {
+        ui.input_text(label, self)
+    }
+}
+
+macro_rules! impl_int {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl SettingsUi for $ty {
+                fn settings_ui(&mut self, ui: &Ui, label: &str) -> bool {
+                    self.settings_ui_range(ui, label, <$ty>::MIN..=<$ty>::MAX)
+                }
+            }
+
+            impl SettingsRange for $ty {
+                fn settings_ui_range(
+                    &mut self,
+                    ui: &Ui,
+                    label: &str,
+                    range: RangeInclusive<Self>,
+                ) -> bool {
+                    let range = to_i64(*range.start())..=to_i64(*range.end());
+                    let mut value = to_i64(*self);
+
+                    ui.drag_int(label, &mut value, Some(range.clone()))
+                        && <$ty>::try_from(clamp(value, &range))
+                            .is_ok_and(|value| update(self, value))
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_float {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl SettingsUi for $ty {
+                fn settings_ui(&mut self, ui: &Ui, label: &str) -> bool {
+                    let mut value = f64::from(*self);
+
+                    if ui.drag_float(label, &mut value, None) {
+                        #[allow(clippy::cast_possible_truncation)]
+                        return update(self, value as $ty);
+                    }
+
+                    false
+                }
+            }
+
+            impl SettingsRange for $ty {
+                fn settings_ui_range(
+                    &mut self,
+                    ui: &Ui,
+                    label: &str,
+                    range: RangeInclusive<Self>,
+                ) -> bool {
+                    let range = f64::from(*range.start())..=f64::from(*range.end());
+                    let mut value = f64::from(*self);
+
+                    if ui.drag_float(label, &mut value, Some(range.clone())) {
+                        #[allow(clippy::cast_possible_truncation)]
+                        return update(self, clamp(value, &range) as $ty);
+                    }
+
+                    false
+                }
+            }
+        )+
+    };
+}
+
+impl_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_float!(f32, f64);
+
+/// Convert an integer to an `i64`, saturating at `i64::MAX` for the values of
+/// the unsigned types that don't fit.
+fn to_i64<T>(value: T) -> i64
+where
+    T: TryInto<i64>,
+{
+    value.try_into().unwrap_or(i64::MAX)
+}
+
+/// Clamp `value` to `range`, without panicking if the range is backwards.
+fn clamp<T>(value: T, range: &RangeInclusive<T>) -> T
+where
+    T: PartialOrd + Copy,
+{
+    if value < *range.start() {
+        *range.start()
+    }
+    else if value > *range.end() {
+        *range.end()
+    }
+    else {
+        value
+    }
+}
+
+/// Set `target` to `value`, returning whether that changed it.
+fn update<T>(target: &mut T, value: T) -> bool
+where
+    T: PartialEq,
+{
+    let changed = *target != value;
+    *target = value;
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_i64() {
+        assert_eq!(to_i64(-5_i8), -5);
+        assert_eq!(to_i64(u32::MAX), i64::from(u32::MAX));
+        assert_eq!(to_i64(u64::MAX), i64::MAX);
+    }
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(clamp(5, &(1..=10)), 5);
+        assert_eq!(clamp(-5, &(1..=10)), 1);
+        assert_eq!(clamp(50, &(1..=10)), 10);
+        assert!((clamp(0.5_f64, &(1.0..=2.0)) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut value = 1;
+        assert!(!update(&mut value, 1));
+        assert!(update(&mut value, 2));
+        assert_eq!(value, 2);
+    }
+}
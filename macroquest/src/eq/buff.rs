@@ -0,0 +1,241 @@
+//! Buffs, which are the spells currently affecting a spawn.
+//!
+//! The game counts the duration of a buff in ticks of 6 seconds, which are
+//! only brought up to date when the server sends them, so a [`Buff`] records
+//! the tick it was read at (see [`mq::local_tick()`](crate::mq::local_tick))
+//! and works out how long it has left from there.
+
+use std::fmt;
+use std::time::Duration;
+
+use super::Spell;
+
+/// The length of a game tick, which is what buff durations are counted in.
+pub const TICK: Duration = Duration::from_secs(6);
+
+/// Convert a number of game ticks into a [`Duration`].
+#[must_use]
+pub fn ticks_to_duration(ticks: u64) -> Duration {
+    Duration::from_secs(ticks.saturating_mul(TICK.as_secs()))
+}
+
+/// How long a [`Buff`] had left when it was read.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum BuffDuration {
+    /// The buff lasts until it's removed.
+    Permanent,
+    /// The buff has just landed, and the game hasn't filled in its duration
+    /// yet, so it's assumed to have its full (base) duration left.
+    Pending,
+    /// The buff had this many ticks left.
+    Ticks(u32),
+}
+
+impl BuffDuration {
+    /// Interpret the number of ticks left as the game stores it, where a
+    /// negative number means that the buff is permanent and `0` means that
+    /// its duration hasn't been filled in yet.
+    #[must_use]
+    pub fn from_raw(ticks: i32) -> BuffDuration {
+        match u32::try_from(ticks) {
+            Ok(0) => BuffDuration::Pending,
+            Ok(ticks) => BuffDuration::Ticks(ticks),
+            Err(_) => BuffDuration::Permanent,
+        }
+    }
+}
+
+/// A buff (or debuff) in one of the buff slots of a spawn.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Buff {
+    /// The id of the spell that the buff is from.
+    pub spell_id:      u32,
+    /// The buff slot that the buff is in.
+    pub slot:          usize,
+    /// The name of whoever cast the buff, when the game knows it.
+    pub caster:        Option<String>,
+    /// How long the buff had left when it was read.
+    pub duration:      BuffDuration,
+    /// The number of ticks that the buff lasts for in full.
+    pub base_duration: u32,
+    /// The counters (such as poison or disease counters) that the buff has
+    /// left, or `0` if it doesn't have any.
+    pub counters:      u32,
+    /// The [local tick](crate::mq::local_tick) that the buff was read at.
+    pub observed_at:   u64,
+}
+
+impl Buff {
+    /// The spell that the buff is from, or `None` if it isn't in the spell
+    /// database.
+    ///
+    /// Like [`mq::spell_by_id()`](crate::mq::spell_by_id), this can only be
+    /// used on the game's main thread.
+    #[must_use]
+    pub fn spell(&self) -> Option<&'static Spell> {
+        crate::mq::spell_by_id(self.spell_id)
+    }
+
+    /// The name of the spell that the buff is from, or `None` if it isn't in
+    /// the spell database.
+    ///
+    /// Like [`Buff::spell()`], this can only be used on the game's main thread.
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        self.spell().map(Spell::name)
+    }
+
+    /// Whether the buff lasts until it's removed.
+    #[must_use]
+    pub fn is_permanent(&self) -> bool {
+        self.duration == BuffDuration::Permanent
+    }
+
+    /// How long the buff has left, as of the current local tick, or `None` if
+    /// it's permanent.
+    ///
+    /// This is accurate to within a tick, as that's all the game tracks.
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining_at(crate::mq::local_tick())
+    }
+
+    /// How long the buff has left as of the local tick `now`, or `None` if
+    /// it's permanent.
+    #[must_use]
+    pub fn remaining_at(&self, now: u64) -> Option<Duration> {
+        let ticks = match self.duration {
+            BuffDuration::Permanent => return None,
+            BuffDuration::Pending => self.base_duration,
+            BuffDuration::Ticks(ticks) => ticks,
+        };
+
+        let elapsed = now.saturating_sub(self.observed_at);
+        Some(ticks_to_duration(u64::from(ticks).saturating_sub(elapsed)))
+    }
+}
+
+impl fmt::Debug for Buff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let caster = self.caster.as_deref().unwrap_or("unknown");
+        let remaining = Remaining(self.remaining());
+
+        f.debug_struct("Buff")
+            .field("slot", &self.slot)
+            .field("spell_id", &self.spell_id)
+            .field("caster", &format_args!("{caster}"))
+            .field("remaining", &format_args!("{remaining}"))
+            .field("counters", &self.counters)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Formats how long a buff has left, the way a person would write it.
+struct Remaining(Option<Duration>);
+
+impl fmt::Display for Remaining {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(remaining) = self.0
+        else {
+            return f.write_str("permanent");
+        };
+
+        let seconds = remaining.as_secs();
+        match (seconds / 3600, seconds / 60 % 60, seconds % 60) {
+            (0, 0, s) => write!(f, "{s}s"),
+            (0, m, s) => write!(f, "{m}m {s:02}s"),
+            (h, m, s) => write!(f, "{h}h {m:02}m {s:02}s"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buff(duration: BuffDuration) -> Buff {
+        Buff {
+            spell_id: 1447,
+            slot: 3,
+            caster: Some("Soandso".into()),
+            duration,
+            base_duration: 100,
+            counters: 0,
+            observed_at: 1000,
+        }
+    }
+
+    #[test]
+    fn test_ticks_to_duration() {
+        assert_eq!(ticks_to_duration(0), Duration::ZERO);
+        assert_eq!(ticks_to_duration(10), Duration::from_secs(60));
+        assert_eq!(ticks_to_duration(u64::MAX), Duration::from_secs(u64::MAX));
+    }
+
+    #[test]
+    fn test_duration_from_raw() {
+        assert_eq!(BuffDuration::from_raw(-1), BuffDuration::Permanent);
+        assert_eq!(BuffDuration::from_raw(0), BuffDuration::Pending);
+        assert_eq!(BuffDuration::from_raw(15), BuffDuration::Ticks(15));
+    }
+
+    #[test]
+    fn test_remaining() {
+        let buff = buff(BuffDuration::Ticks(10));
+
+        assert_eq!(buff.remaining_at(1000), Some(Duration::from_secs(60)));
+        assert_eq!(buff.remaining_at(1004), Some(Duration::from_secs(36)));
+        assert_eq!(buff.remaining_at(1010), Some(Duration::ZERO));
+        assert_eq!(buff.remaining_at(2000), Some(Duration::ZERO));
+
+        // A tick from before the buff was read (like after the clock is
+        // reset) doesn't add any time.
+        assert_eq!(buff.remaining_at(0), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_remaining_permanent() {
+        let buff = buff(BuffDuration::Permanent);
+
+        assert!(buff.is_permanent());
+        assert_eq!(buff.remaining_at(1000), None);
+        assert_eq!(buff.remaining_at(5000), None);
+    }
+
+    #[test]
+    fn test_remaining_pending() {
+        let buff = buff(BuffDuration::Pending);
+
+        assert!(!buff.is_permanent());
+        assert_eq!(buff.remaining_at(1000), Some(Duration::from_secs(600)));
+        assert_eq!(buff.remaining_at(1050), Some(Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn test_remaining_display() {
+        let remaining = |secs| Remaining(Some(Duration::from_secs(secs))).to_string();
+
+        assert_eq!(Remaining(None).to_string(), "permanent");
+        assert_eq!(remaining(42), "42s");
+        assert_eq!(remaining(90), "1m 30s");
+        assert_eq!(remaining(3 * 3600 + 65), "3h 01m 05s");
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut buff = buff(BuffDuration::Permanent);
+        assert_eq!(
+            format!("{buff:?}"),
+            "Buff { slot: 3, spell_id: 1447, caster: Soandso, remaining: permanent, \
+             counters: 0, .. }"
+        );
+
+        buff.caster = None;
+        buff.counters = 4;
+        assert_eq!(
+            format!("{buff:?}"),
+            "Buff { slot: 3, spell_id: 1447, caster: unknown, remaining: permanent, \
+             counters: 4, .. }"
+        );
+    }
+}
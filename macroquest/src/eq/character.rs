@@ -0,0 +1,361 @@
+//! The local character, which is the one that we're playing.
+
+use std::fmt;
+
+use ref_cast::RefCast;
+
+use super::macros::getter;
+use super::{Buff, BuffDuration, Class, Coin, Fellowship, Group, Item};
+use crate::ffi;
+
+/// The names of the slots that the character can equip items in (its worn
+/// inventory slots), indexed by slot.
+pub const EQUIPMENT_SLOTS: [&str; 23] = [
+    "Charm",
+    "Left Ear",
+    "Head",
+    "Face",
+    "Right Ear",
+    "Neck",
+    "Shoulders",
+    "Arms",
+    "Back",
+    "Left Wrist",
+    "Right Wrist",
+    "Range",
+    "Hands",
+    "Primary",
+    "Secondary",
+    "Left Finger",
+    "Right Finger",
+    "Chest",
+    "Legs",
+    "Feet",
+    "Waist",
+    "Power Source",
+    "Ammo",
+];
+
+/// How much experience there is in each level (and each AA point), which is
+/// what [`Character::experience()`] and [`Character::aa_experience()`] count
+/// up to.
+pub const EXPERIENCE_PER_LEVEL: u32 = 100_000;
+
+/// The local character, which is the one that we're playing.
+///
+/// The character is looked up with [`mq::character()`](crate::mq::character),
+/// and can only be used on the game's main thread.
+///
+/// This is the character's own data (such as their experience, coin, and
+/// inventory), which the game keeps separately from their spawn. The spawn,
+/// which is what other players see (such as their location and hit points),
+/// is looked up with [`mq::me()`](crate::mq::me). The character is only gone
+/// when we're not playing one, while the spawn also goes away while zoning.
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Character(ffi::eqlib::PcClient);
+
+impl Character {
+    getter!(
+        /// The name of the character.
+        name -> &str
+    );
+
+    /// The level of the character.
+    #[must_use]
+    pub fn level(&self) -> u32 {
+        u32::try_from(self.raw().level()).unwrap_or(0)
+    }
+
+    /// The class of the character.
+    #[must_use]
+    pub fn class(&self) -> Class {
+        Class::from(self.raw().class_id())
+    }
+
+    /// How many alternate advancement (AA) points the character has available
+    /// to spend.
+    #[must_use]
+    pub fn aa_points(&self) -> u32 {
+        u32::try_from(self.raw().aa_points()).unwrap_or(0)
+    }
+
+    /// How many alternate advancement (AA) points the character has spent.
+    #[must_use]
+    pub fn aa_points_spent(&self) -> u32 {
+        u32::try_from(self.raw().aa_points_spent()).unwrap_or(0)
+    }
+
+    /// The character's experience towards their next level, out of
+    /// [`EXPERIENCE_PER_LEVEL`].
+    #[must_use]
+    pub fn experience(&self) -> u32 {
+        u32::try_from(self.raw().experience()).unwrap_or(0)
+    }
+
+    /// The character's experience towards their next level, as a percentage
+    /// from `0.0` to `100.0`.
+    #[must_use]
+    pub fn experience_percent(&self) -> f32 {
+        experience_percent(self.experience())
+    }
+
+    /// The character's alternate advancement (AA) experience towards their
+    /// next AA point, out of [`EXPERIENCE_PER_LEVEL`].
+    #[must_use]
+    pub fn aa_experience(&self) -> u32 {
+        u32::try_from(self.raw().aa_experience()).unwrap_or(0)
+    }
+
+    /// The character's alternate advancement (AA) experience towards their
+    /// next AA point, as a percentage from `0.0` to `100.0`.
+    #[must_use]
+    pub fn aa_experience_percent(&self) -> f32 {
+        experience_percent(self.aa_experience())
+    }
+
+    /// The id of the zone that the character is in.
+    #[must_use]
+    pub fn current_zone_id(&self) -> u32 {
+        self.raw().zone_id()
+    }
+
+    /// The item that the character has equipped in `slot` (an index into
+    /// [`EQUIPMENT_SLOTS`]), or `None` if the slot is empty.
+    #[must_use]
+    pub fn equipped(&self, slot: usize) -> Option<&Item> {
+        if slot >= EQUIPMENT_SLOTS.len() {
+            return None;
+        }
+
+        let slot = i32::try_from(slot).ok()?;
+
+        // SAFETY: The pointer is either null or points to an item held by the
+        //         character, which lives for at least as long as it does.
+        unsafe { self.raw().equipped_item(slot).as_ref() }.map(AsRef::as_ref)
+    }
+
+    /// Iterate over the items that the character has equipped, along with the
+    /// slot that each is in, skipping any empty slots.
+    pub fn iter_equipped(&self) -> impl Iterator<Item = (usize, &Item)> + '_ {
+        (0..EQUIPMENT_SLOTS.len())
+            .filter_map(|slot| self.equipped(slot).map(|item| (slot, item)))
+    }
+
+    /// The item that the character is holding on the cursor, or `None` if the
+    /// cursor is empty.
+    #[must_use]
+    pub fn cursor(&self) -> Option<&Item> {
+        // SAFETY: The pointer is either null or points to an item held by the
+        //         character, which lives for at least as long as it does.
+        unsafe { self.raw().cursor_item().as_ref() }.map(AsRef::as_ref)
+    }
+
+    /// The coin that the character is carrying.
+    #[must_use]
+    pub fn coin(&self) -> Coin {
+        Coin::from(self.raw().carried_coin())
+    }
+
+    /// The platinum pieces that the character is carrying.
+    #[must_use]
+    pub fn platinum(&self) -> u32 {
+        self.coin().platinum
+    }
+
+    /// The gold pieces that the character is carrying.
+    #[must_use]
+    pub fn gold(&self) -> u32 {
+        self.coin().gold
+    }
+
+    /// The silver pieces that the character is carrying.
+    #[must_use]
+    pub fn silver(&self) -> u32 {
+        self.coin().silver
+    }
+
+    /// The copper pieces that the character is carrying.
+    #[must_use]
+    pub fn copper(&self) -> u32 {
+        self.coin().copper
+    }
+
+    /// The coin that the character has in the bank.
+    #[must_use]
+    pub fn bank_coin(&self) -> Coin {
+        Coin::from(self.raw().bank_coin())
+    }
+
+    /// Iterate over the auras that the character has active, skipping any
+    /// empty aura slots.
+    ///
+    /// Auras come from the game's aura window rather than from the buff
+    /// windows, so an aura is listed here even when its buff icon isn't
+    /// showing (such as when the buff windows are full).
+    pub fn auras(&self) -> impl Iterator<Item = AuraSnapshot> + '_ {
+        (0..self.raw().aura_count()).filter_map(|index| {
+            let mut info = ffi::eqlib::AuraInfo::default();
+            if !self.raw().aura(index, &mut info) {
+                return None;
+            }
+
+            AuraSnapshot::new(usize::try_from(index).ok()?, &info.name)
+        })
+    }
+
+    /// Iterate over the buffs in the character's buff slots, skipping any empty
+    /// slots.
+    ///
+    /// The buffs are read as they're iterated over, without allocating, so
+    /// this is cheap enough to do on every pulse. The game doesn't keep who
+    /// cast the character's own buffs, so [`Buff::caster`] is always
+    /// `None`.
+    pub fn buffs(&self) -> impl Iterator<Item = Buff> + '_ {
+        self.read_buffs(self.raw().buff_count(), ffi::eqlib::PcClient::buff)
+    }
+
+    /// Iterate over the buffs in the character's song (short duration buff)
+    /// slots, skipping any empty slots.
+    ///
+    /// See [`Character::buffs()`] for the details.
+    pub fn song_buffs(&self) -> impl Iterator<Item = Buff> + '_ {
+        self.read_buffs(
+            self.raw().song_buff_count(),
+            ffi::eqlib::PcClient::song_buff,
+        )
+    }
+
+    /// Read the first `count` buff slots with `read`.
+    fn read_buffs<F>(&self, count: i32, read: F) -> impl Iterator<Item = Buff> + '_
+    where
+        F: Fn(&ffi::eqlib::PcClient, i32, &mut ffi::eqlib::BuffInfo) -> bool + 'static,
+    {
+        let observed_at = crate::mq::local_tick();
+
+        (0..count).filter_map(move |index| {
+            let mut info = ffi::eqlib::BuffInfo::default();
+            if !read(self.raw(), index, &mut info) {
+                return None;
+            }
+
+            let spell_id = u32::try_from(info.spell_id).ok()?;
+            let base_duration = crate::mq::spell_by_id(spell_id)
+                .map_or(0, |spell| spell.duration_ticks(info.level));
+
+            Some(Buff {
+                spell_id,
+                slot: usize::try_from(index).ok()?,
+                caster: None,
+                duration: BuffDuration::from_raw(info.duration),
+                base_duration,
+                counters: u32::try_from(info.counters).unwrap_or(0),
+                observed_at,
+            })
+        })
+    }
+
+    /// Take a snapshot of the character's fellowship, returning `None` if the
+    /// character isn't in one.
+    #[must_use]
+    pub fn fellowship(&self) -> Option<Fellowship> {
+        Fellowship::from_raw(self.raw())
+    }
+
+    /// Take a snapshot of the character's group, returning `None` if the
+    /// character isn't in one.
+    #[must_use]
+    pub fn group(&self) -> Option<Group> {
+        Group::from_raw(self.raw())
+    }
+
+    fn raw(&self) -> &ffi::eqlib::PcClient {
+        crate::macros::debug_assert_main_thread!(
+            ::std::any::type_name::<Self>(),
+            "raw"
+        );
+
+        &self.0
+    }
+}
+
+impl AsRef<Character> for ffi::eqlib::PcClient {
+    fn as_ref(&self) -> &Character {
+        Character::ref_cast(self)
+    }
+}
+
+impl fmt::Debug for Character {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Character")
+            .field("name", &self.name())
+            .field("level", &self.level())
+            .field("class", &self.class())
+            .field("experience", &self.experience())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A snapshot of an aura that the local character has active.
+///
+/// Auras last until they're removed (or the character zones or dies), so the
+/// game doesn't keep a remaining time for them.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuraSnapshot {
+    /// The aura slot that the aura is in.
+    pub slot: usize,
+    /// The name of the aura.
+    pub name: String,
+}
+
+impl AuraSnapshot {
+    /// Create an [`AuraSnapshot`] from the contents of an aura slot, returning
+    /// `None` if the slot is empty.
+    fn new(slot: usize, name: &str) -> Option<AuraSnapshot> {
+        let name = name.trim();
+        (!name.is_empty()).then(|| AuraSnapshot {
+            slot,
+            name: name.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for AuraSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// `experience` as a percentage of [`EXPERIENCE_PER_LEVEL`].
+#[allow(clippy::cast_precision_loss)]
+fn experience_percent(experience: u32) -> f32 {
+    (experience.min(EXPERIENCE_PER_LEVEL) as f32 / EXPERIENCE_PER_LEVEL as f32) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_experience_percent() {
+        assert_eq!(experience_percent(0), 0.0);
+        assert_eq!(experience_percent(25_000), 25.0);
+        assert_eq!(experience_percent(EXPERIENCE_PER_LEVEL), 100.0);
+        assert_eq!(experience_percent(u32::MAX), 100.0);
+    }
+
+    #[test]
+    fn test_aura_empty_slot() {
+        assert_eq!(AuraSnapshot::new(0, ""), None);
+        assert_eq!(AuraSnapshot::new(1, "  "), None);
+    }
+
+    #[test]
+    fn test_aura() {
+        let aura = AuraSnapshot::new(1, "Aura of the Pious ").unwrap();
+
+        assert_eq!(aura.slot, 1);
+        assert_eq!(aura.to_string(), "Aura of the Pious");
+    }
+}
@@ -0,0 +1,287 @@
+//! The classes of spawns, and the archetypes that they fall into.
+
+use std::str::FromStr;
+use std::{error, fmt};
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+/// The class of a spawn, which for players is one of the 16 player classes,
+/// while NPCs may also have one of the NPC only classes (like
+/// [`Class::Banker`]).
+///
+/// A class parses from either its full name (ex: `Shadow Knight`) or its short
+/// name (ex: `SHD`), ignoring case.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+pub enum Class {
+    /// Warrior
+    Warrior          = 1,
+    /// Cleric
+    Cleric           = 2,
+    /// Paladin
+    Paladin          = 3,
+    /// Ranger
+    Ranger           = 4,
+    /// Shadow Knight
+    ShadowKnight     = 5,
+    /// Druid
+    Druid            = 6,
+    /// Monk
+    Monk             = 7,
+    /// Bard
+    Bard             = 8,
+    /// Rogue
+    Rogue            = 9,
+    /// Shaman
+    Shaman           = 10,
+    /// Necromancer
+    Necromancer      = 11,
+    /// Wizard
+    Wizard           = 12,
+    /// Magician
+    Magician         = 13,
+    /// Enchanter
+    Enchanter        = 14,
+    /// Beastlord
+    Beastlord        = 15,
+    /// Berserker
+    Berserker        = 16,
+    /// Banker (NPC only)
+    Banker           = 40,
+    /// Merchant (NPC only)
+    Merchant         = 41,
+    /// Tribute Master (NPC only)
+    TributeMaster    = 63,
+    /// Guild Banker (NPC only)
+    GuildBanker      = 66,
+    /// Mercenary Liaison (NPC only)
+    MercenaryLiaison = 71,
+
+    /// A class unknown to this crate.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+impl Class {
+    /// Whether the class is one of the pure casters (Necromancer, Wizard,
+    /// Magician, and Enchanter).
+    #[must_use]
+    pub fn is_caster(self) -> bool {
+        matches!(
+            self,
+            Class::Necromancer | Class::Wizard | Class::Magician | Class::Enchanter
+        )
+    }
+
+    /// Whether the class is one of the priests (Cleric, Druid, and Shaman).
+    #[must_use]
+    pub fn is_priest(self) -> bool {
+        matches!(self, Class::Cleric | Class::Druid | Class::Shaman)
+    }
+
+    /// Whether the class is one of the pure melee classes (Warrior, Monk,
+    /// Rogue, and Berserker).
+    #[must_use]
+    pub fn is_melee(self) -> bool {
+        matches!(
+            self,
+            Class::Warrior | Class::Monk | Class::Rogue | Class::Berserker
+        )
+    }
+
+    /// Whether the class is one of the hybrids, which fight in melee while
+    /// also casting spells (Paladin, Ranger, Shadow Knight, Bard, and
+    /// Beastlord).
+    #[must_use]
+    pub fn is_hybrid(self) -> bool {
+        matches!(
+            self,
+            Class::Paladin
+                | Class::Ranger
+                | Class::ShadowKnight
+                | Class::Bard
+                | Class::Beastlord
+        )
+    }
+
+    /// The full name of the class (ex: `Shadow Knight`).
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        self.names().0
+    }
+
+    /// The short name of the class (ex: `SHD`), as used by the game and
+    /// MacroQuest.
+    #[must_use]
+    pub fn short_name(self) -> &'static str {
+        self.names().1
+    }
+
+    fn names(self) -> (&'static str, &'static str) {
+        match self {
+            Class::Warrior => ("Warrior", "WAR"),
+            Class::Cleric => ("Cleric", "CLR"),
+            Class::Paladin => ("Paladin", "PAL"),
+            Class::Ranger => ("Ranger", "RNG"),
+            Class::ShadowKnight => ("Shadow Knight", "SHD"),
+            Class::Druid => ("Druid", "DRU"),
+            Class::Monk => ("Monk", "MNK"),
+            Class::Bard => ("Bard", "BRD"),
+            Class::Rogue => ("Rogue", "ROG"),
+            Class::Shaman => ("Shaman", "SHM"),
+            Class::Necromancer => ("Necromancer", "NEC"),
+            Class::Wizard => ("Wizard", "WIZ"),
+            Class::Magician => ("Magician", "MAG"),
+            Class::Enchanter => ("Enchanter", "ENC"),
+            Class::Beastlord => ("Beastlord", "BST"),
+            Class::Berserker => ("Berserker", "BER"),
+            Class::Banker => ("Banker", "BANKER"),
+            Class::Merchant => ("Merchant", "MERCHANT"),
+            Class::TributeMaster => ("Tribute Master", "TRIBUTE"),
+            Class::GuildBanker => ("Guild Banker", "GUILDBANKER"),
+            Class::MercenaryLiaison => ("Mercenary Liaison", "MERCENARY"),
+            Class::Unknown(_) => ("Unknown", "UNK"),
+        }
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Class {
+    type Err = ParseClassError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        CLASSES
+            .into_iter()
+            .find(|class| {
+                class.name().eq_ignore_ascii_case(s)
+                    || class.short_name().eq_ignore_ascii_case(s)
+            })
+            .ok_or_else(|| ParseClassError {
+                input: s.to_owned(),
+            })
+    }
+}
+
+/// An error parsing a [`Class`] from a string that isn't the name of one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseClassError {
+    input: String,
+}
+
+impl fmt::Display for ParseClassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown class {:?}", self.input)
+    }
+}
+
+impl error::Error for ParseClassError {}
+
+// Every known class, in id order, for parsing.
+const CLASSES: [Class; 21] = [
+    Class::Warrior,
+    Class::Cleric,
+    Class::Paladin,
+    Class::Ranger,
+    Class::ShadowKnight,
+    Class::Druid,
+    Class::Monk,
+    Class::Bard,
+    Class::Rogue,
+    Class::Shaman,
+    Class::Necromancer,
+    Class::Wizard,
+    Class::Magician,
+    Class::Enchanter,
+    Class::Beastlord,
+    Class::Berserker,
+    Class::Banker,
+    Class::Merchant,
+    Class::TributeMaster,
+    Class::GuildBanker,
+    Class::MercenaryLiaison,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classes_is_complete() {
+        let known: Vec<Class> = (-10..=300)
+            .map(Class::from)
+            .filter(|class| !matches!(class, Class::Unknown(_)))
+            .collect();
+
+        assert_eq!(known, CLASSES);
+    }
+
+    #[test]
+    fn test_archetypes() {
+        for class in CLASSES {
+            let archetypes = [
+                class.is_caster(),
+                class.is_priest(),
+                class.is_melee(),
+                class.is_hybrid(),
+            ];
+            let count = archetypes.into_iter().filter(|is| *is).count();
+
+            // Every player class has exactly one archetype, and NPC only
+            // classes have none.
+            if (1..=16).contains(&i32::from(class)) {
+                assert_eq!(count, 1, "{class}");
+            }
+            else {
+                assert_eq!(count, 0, "{class}");
+            }
+        }
+
+        assert!(Class::Wizard.is_caster());
+        assert!(Class::Shaman.is_priest());
+        assert!(Class::Berserker.is_melee());
+        assert!(Class::Beastlord.is_hybrid());
+        assert!(!Class::Unknown(99).is_melee());
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(Class::ShadowKnight.to_string(), "Shadow Knight");
+        assert_eq!(Class::ShadowKnight.short_name(), "SHD");
+        assert_eq!(Class::Cleric.short_name(), "CLR");
+        assert_eq!(Class::Unknown(99).to_string(), "Unknown");
+
+        let mut short_names: Vec<_> = CLASSES.map(Class::short_name).to_vec();
+        short_names.sort_unstable();
+        short_names.dedup();
+        assert_eq!(short_names.len(), CLASSES.len());
+    }
+
+    #[test]
+    fn test_from_str() {
+        for class in CLASSES {
+            assert_eq!(class.name().parse(), Ok(class));
+            assert_eq!(class.short_name().parse(), Ok(class));
+            assert_eq!(class.name().to_lowercase().parse(), Ok(class));
+            assert_eq!(class.short_name().to_lowercase().parse(), Ok(class));
+        }
+
+        assert_eq!(" sHaDoW kNiGhT ".parse(), Ok(Class::ShadowKnight));
+        assert_eq!(
+            "Necro".parse::<Class>().unwrap_err().to_string(),
+            "unknown class \"Necro\""
+        );
+        assert!("Unknown".parse::<Class>().is_err());
+    }
+}
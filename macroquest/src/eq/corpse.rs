@@ -0,0 +1,74 @@
+//! Working out whose corpse a corpse is, from its name.
+//!
+//! The game names a corpse after whoever died, followed by `'s corpse` and
+//! usually a number to keep the name unique, while the names of spawns use
+//! underscores in place of spaces. This gives names like `Bob's_corpse0`,
+//! `Bob's corpse`, and `a_rat's_corpse12`, with some older corpses using a
+//! backtick (`` ` ``) in place of the apostrophe.
+
+// The length of "'s corpse".
+const SUFFIX_LEN: usize = 9;
+
+/// The name of whoever a corpse named `name` belongs to (with any underscores
+/// as spaces), or `None` if `name` isn't the name of a corpse.
+pub(super) fn owner_name(name: &str) -> Option<String> {
+    let name = name.trim_end_matches(|c: char| c.is_ascii_digit());
+
+    let split = name.len().checked_sub(SUFFIX_LEN)?;
+    let (owner, suffix) = (name.get(..split)?, name.get(split..)?);
+    let mut suffix = suffix.chars();
+
+    let apostrophe = matches!(suffix.next(), Some('\'' | '`'));
+    let s = suffix.next() == Some('s');
+    let separator = matches!(suffix.next(), Some(' ' | '_'));
+
+    let corpse = suffix.as_str().eq_ignore_ascii_case("corpse");
+
+    if !(apostrophe && s && separator && corpse) {
+        return None;
+    }
+
+    let owner = owner.replace('_', " ");
+    let owner = owner.trim();
+    (!owner.is_empty()).then(|| owner.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_player_corpses() {
+        assert_eq!(owner_name("Bob's corpse0").as_deref(), Some("Bob"));
+        assert_eq!(owner_name("Bob's_corpse0").as_deref(), Some("Bob"));
+        assert_eq!(owner_name("Bob's_corpse").as_deref(), Some("Bob"));
+        assert_eq!(owner_name("Bob's corpse").as_deref(), Some("Bob"));
+        assert_eq!(owner_name("Bob`s_corpse12").as_deref(), Some("Bob"));
+        assert_eq!(owner_name("Bob's Corpse3").as_deref(), Some("Bob"));
+    }
+
+    #[test]
+    fn test_npc_corpses() {
+        assert_eq!(owner_name("a_rat's_corpse0").as_deref(), Some("a rat"));
+        assert_eq!(
+            owner_name("Lord_Nagafen`s_corpse").as_deref(),
+            Some("Lord Nagafen")
+        );
+        assert_eq!(
+            owner_name("a gnoll pup's corpse").as_deref(),
+            Some("a gnoll pup")
+        );
+    }
+
+    #[test]
+    fn test_not_corpses() {
+        assert_eq!(owner_name(""), None);
+        assert_eq!(owner_name("Bob"), None);
+        assert_eq!(owner_name("a_rat01"), None);
+        assert_eq!(owner_name("'s corpse0"), None);
+        assert_eq!(owner_name("Bob's corpses"), None);
+        assert_eq!(owner_name("Bob's_corpse_run"), None);
+        assert_eq!(owner_name("Bobs corpse0"), None);
+        assert_eq!(owner_name("Bob's çorpse"), None);
+    }
+}
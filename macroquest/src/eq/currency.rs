@@ -0,0 +1,90 @@
+//! Coin, the game's four denominations of money.
+
+use std::fmt;
+
+use crate::ffi;
+
+/// An amount of coin, in each of the game's denominations.
+///
+/// The game keeps each denomination separately (it doesn't convert copper into
+/// silver, and so on, unless asked to), so this does too.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coin {
+    /// Platinum pieces, which are each worth 1000 copper.
+    pub platinum: u32,
+    /// Gold pieces, which are each worth 100 copper.
+    pub gold:     u32,
+    /// Silver pieces, which are each worth 10 copper.
+    pub silver:   u32,
+    /// Copper pieces.
+    pub copper:   u32,
+}
+
+impl Coin {
+    /// The total value of the coin, in copper.
+    #[must_use]
+    pub fn total_copper(&self) -> u64 {
+        u64::from(self.platinum) * 1000
+            + u64::from(self.gold) * 100
+            + u64::from(self.silver) * 10
+            + u64::from(self.copper)
+    }
+}
+
+impl From<ffi::eqlib::CoinInfo> for Coin {
+    fn from(info: ffi::eqlib::CoinInfo) -> Self {
+        // The game never has a negative amount of coin.
+        let unsigned = |value: i32| u32::try_from(value).unwrap_or(0);
+
+        Coin {
+            platinum: unsigned(info.platinum),
+            gold:     unsigned(info.gold),
+            silver:   unsigned(info.silver),
+            copper:   unsigned(info.copper),
+        }
+    }
+}
+
+impl fmt::Display for Coin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}p {}g {}s {}c",
+            self.platinum, self.gold, self.silver, self.copper
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_copper() {
+        let coin = Coin {
+            platinum: 12,
+            gold:     3,
+            silver:   4,
+            copper:   5,
+        };
+
+        assert_eq!(coin.total_copper(), 12_345);
+        assert_eq!(coin.to_string(), "12p 3g 4s 5c");
+        assert_eq!(Coin::default().total_copper(), 0);
+    }
+
+    #[test]
+    fn test_from_raw() {
+        let coin = Coin::from(ffi::eqlib::CoinInfo {
+            platinum: 1,
+            gold:     -2,
+            silver:   3,
+            copper:   0,
+        });
+
+        assert_eq!(coin.platinum, 1);
+        assert_eq!(coin.gold, 0);
+        assert_eq!(coin.silver, 3);
+    }
+}
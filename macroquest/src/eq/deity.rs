@@ -0,0 +1,259 @@
+//! The deities that spawns worship, and the pantheons that they belong to.
+
+use std::str::FromStr;
+use std::{error, fmt};
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+/// The deity that a spawn worships.
+///
+/// A deity parses from its name (ex: `Erollisi Marr`), ignoring case.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+pub enum Deity {
+    /// Agnostic, from before the current id was used.
+    AgnosticLegacy = 140,
+    /// Bertoxxulous
+    Bertoxxulous   = 201,
+    /// Brell Serilis
+    BrellSerilis   = 202,
+    /// Cazic-Thule
+    CazicThule     = 203,
+    /// Erollisi Marr
+    ErollisiMarr   = 204,
+    /// Bristlebane
+    Bristlebane    = 205,
+    /// Innoruuk
+    Innoruuk       = 206,
+    /// Karana
+    Karana         = 207,
+    /// Mithaniel Marr
+    MithanielMarr  = 208,
+    /// Prexus
+    Prexus         = 209,
+    /// Quellious
+    Quellious      = 210,
+    /// Rallos Zek
+    RallosZek      = 211,
+    /// Rodcet Nife
+    RodcetNife     = 212,
+    /// Solusek Ro
+    SolusekRo      = 213,
+    /// The Tribunal
+    TheTribunal    = 214,
+    /// Tunare
+    Tunare         = 215,
+    /// Veeshan
+    Veeshan        = 216,
+    /// Agnostic
+    Agnostic       = 396,
+
+    /// A deity unknown to this crate.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+/// The pantheons that the deities are grouped into, by their alignment.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Pantheon {
+    /// The good deities.
+    Good,
+    /// The neutral deities.
+    Neutral,
+    /// The evil deities.
+    Evil,
+}
+
+impl Deity {
+    /// The name of the deity (ex: `Erollisi Marr`).
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Deity::AgnosticLegacy | Deity::Agnostic => "Agnostic",
+            Deity::Bertoxxulous => "Bertoxxulous",
+            Deity::BrellSerilis => "Brell Serilis",
+            Deity::CazicThule => "Cazic-Thule",
+            Deity::ErollisiMarr => "Erollisi Marr",
+            Deity::Bristlebane => "Bristlebane",
+            Deity::Innoruuk => "Innoruuk",
+            Deity::Karana => "Karana",
+            Deity::MithanielMarr => "Mithaniel Marr",
+            Deity::Prexus => "Prexus",
+            Deity::Quellious => "Quellious",
+            Deity::RallosZek => "Rallos Zek",
+            Deity::RodcetNife => "Rodcet Nife",
+            Deity::SolusekRo => "Solusek Ro",
+            Deity::TheTribunal => "The Tribunal",
+            Deity::Tunare => "Tunare",
+            Deity::Veeshan => "Veeshan",
+            Deity::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// The pantheon that the deity belongs to, or `None` for agnostics (and
+    /// unknown deities).
+    #[must_use]
+    pub fn pantheon(self) -> Option<Pantheon> {
+        match self {
+            Deity::ErollisiMarr
+            | Deity::Karana
+            | Deity::MithanielMarr
+            | Deity::Quellious
+            | Deity::RodcetNife
+            | Deity::Tunare => Some(Pantheon::Good),
+            Deity::BrellSerilis
+            | Deity::Bristlebane
+            | Deity::Prexus
+            | Deity::SolusekRo
+            | Deity::TheTribunal
+            | Deity::Veeshan => Some(Pantheon::Neutral),
+            Deity::Bertoxxulous
+            | Deity::CazicThule
+            | Deity::Innoruuk
+            | Deity::RallosZek => Some(Pantheon::Evil),
+            Deity::AgnosticLegacy | Deity::Agnostic | Deity::Unknown(_) => None,
+        }
+    }
+
+    /// Whether the deity is agnostic, under either of its ids.
+    #[must_use]
+    pub fn is_agnostic(self) -> bool {
+        matches!(self, Deity::AgnosticLegacy | Deity::Agnostic)
+    }
+}
+
+impl fmt::Display for Deity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Deity {
+    type Err = ParseDeityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        DEITIES
+            .into_iter()
+            .find(|deity| deity.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseDeityError {
+                input: s.to_owned(),
+            })
+    }
+}
+
+/// An error parsing a [`Deity`] from a string that isn't the name of one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDeityError {
+    input: String,
+}
+
+impl fmt::Display for ParseDeityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown deity {:?}", self.input)
+    }
+}
+
+impl error::Error for ParseDeityError {}
+
+// Every known deity, for parsing, with the current agnostic id ahead of the
+// legacy one so that it's the one that "Agnostic" parses to.
+const DEITIES: [Deity; 18] = [
+    Deity::Agnostic,
+    Deity::AgnosticLegacy,
+    Deity::Bertoxxulous,
+    Deity::BrellSerilis,
+    Deity::CazicThule,
+    Deity::ErollisiMarr,
+    Deity::Bristlebane,
+    Deity::Innoruuk,
+    Deity::Karana,
+    Deity::MithanielMarr,
+    Deity::Prexus,
+    Deity::Quellious,
+    Deity::RallosZek,
+    Deity::RodcetNife,
+    Deity::SolusekRo,
+    Deity::TheTribunal,
+    Deity::Tunare,
+    Deity::Veeshan,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deities_is_complete() {
+        let mut known: Vec<Deity> = (-10..=1000)
+            .map(Deity::from)
+            .filter(|deity| !matches!(deity, Deity::Unknown(_)))
+            .collect();
+        let mut deities = DEITIES.to_vec();
+
+        known.sort_by_key(|deity| i32::from(*deity));
+        deities.sort_by_key(|deity| i32::from(*deity));
+        assert_eq!(known, deities);
+    }
+
+    #[test]
+    fn test_names() {
+        let expected = [
+            "Agnostic",
+            "Agnostic",
+            "Bertoxxulous",
+            "Brell Serilis",
+            "Cazic-Thule",
+            "Erollisi Marr",
+            "Bristlebane",
+            "Innoruuk",
+            "Karana",
+            "Mithaniel Marr",
+            "Prexus",
+            "Quellious",
+            "Rallos Zek",
+            "Rodcet Nife",
+            "Solusek Ro",
+            "The Tribunal",
+            "Tunare",
+            "Veeshan",
+        ];
+
+        assert_eq!(DEITIES.map(|deity| deity.to_string()), expected);
+        assert_eq!(Deity::Unknown(1).to_string(), "Unknown");
+    }
+
+    #[test]
+    fn test_from_str() {
+        for deity in DEITIES.into_iter().filter(|deity| !deity.is_agnostic()) {
+            assert_eq!(deity.name().parse(), Ok(deity));
+            assert_eq!(deity.name().to_uppercase().parse(), Ok(deity));
+        }
+
+        assert_eq!("agnostic".parse(), Ok(Deity::Agnostic));
+        assert_eq!(" rallos zek ".parse(), Ok(Deity::RallosZek));
+        assert_eq!(
+            "Rallos".parse::<Deity>().unwrap_err().to_string(),
+            "unknown deity \"Rallos\""
+        );
+        assert!("Unknown".parse::<Deity>().is_err());
+    }
+
+    #[test]
+    fn test_pantheon() {
+        for deity in DEITIES {
+            assert_eq!(deity.pantheon().is_none(), deity.is_agnostic(), "{deity}");
+        }
+
+        assert_eq!(Deity::Tunare.pantheon(), Some(Pantheon::Good));
+        assert_eq!(Deity::TheTribunal.pantheon(), Some(Pantheon::Neutral));
+        assert_eq!(Deity::Innoruuk.pantheon(), Some(Pantheon::Evil));
+        assert_eq!(Deity::Unknown(1).pantheon(), None);
+    }
+}
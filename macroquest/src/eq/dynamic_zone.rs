@@ -0,0 +1,280 @@
+//! Dynamic zones (expeditions), and the lockout timers that they leave behind.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+use crate::ffi;
+
+/// A snapshot of the dynamic zone (expedition) that the local character is in.
+///
+/// The snapshot is taken with [`mq::dynamic_zone()`](crate::mq::dynamic_zone),
+/// and doesn't change after it's been taken, so it can be held onto and used
+/// from any thread.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynamicZone {
+    /// The name of the expedition.
+    pub name:        String,
+    /// The name of the leader of the expedition.
+    pub leader:      String,
+    /// The most members that the expedition can have.
+    pub max_members: usize,
+    /// The members of the expedition.
+    pub members:     Vec<DynamicZoneMember>,
+    /// The local character's lockout timers, for every expedition and not
+    /// just this one.
+    pub lockouts:    Vec<Lockout>,
+}
+
+impl DynamicZone {
+    /// Take a snapshot of the dynamic zone that the local character is in,
+    /// returning `None` if they're not in one.
+    pub(crate) fn load() -> Option<DynamicZone> {
+        let mut info = ffi::eqlib::DynamicZoneInfo::default();
+        if !ffi::eqlib::get_dynamic_zone(&mut info) {
+            return None;
+        }
+
+        let members = (0..ffi::eqlib::dynamic_zone_member_count())
+            .filter_map(|index| {
+                let mut member = ffi::eqlib::DynamicZoneMemberInfo::default();
+                ffi::eqlib::get_dynamic_zone_member(index, &mut member).then(|| {
+                    DynamicZoneMember {
+                        name:   member.name,
+                        status: MemberStatus::from(member.status),
+                    }
+                })
+            })
+            .collect();
+
+        Some(DynamicZone {
+            name: info.name,
+            leader: info.leader,
+            max_members: usize::try_from(info.max_members).unwrap_or(usize::MAX),
+            members,
+            lockouts: Lockout::load(),
+        })
+    }
+
+    /// Whether the expedition has as many members as it can have.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= self.max_members
+    }
+
+    /// Find the member named `name`, ignoring case.
+    #[must_use]
+    pub fn member(&self, name: &str) -> Option<&DynamicZoneMember> {
+        self.members
+            .iter()
+            .find(|member| member.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterate over the members that haven't entered the dynamic zone.
+    pub fn not_in_zone(&self) -> impl Iterator<Item = &DynamicZoneMember> {
+        self.members.iter().filter(|member| !member.is_in_zone())
+    }
+
+    /// Find the lockout for the event named `event` of this expedition,
+    /// ignoring case.
+    #[must_use]
+    pub fn lockout(&self, event: &str) -> Option<&Lockout> {
+        self.lockouts.iter().find(|lockout| {
+            lockout.expedition_name.eq_ignore_ascii_case(&self.name)
+                && lockout.event_name.eq_ignore_ascii_case(event)
+        })
+    }
+}
+
+/// A member of a [`DynamicZone`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynamicZoneMember {
+    /// The name of the member.
+    pub name:   String,
+    /// The status of the member.
+    pub status: MemberStatus,
+}
+
+impl DynamicZoneMember {
+    /// Whether the member is online, whether or not they're in the dynamic
+    /// zone.
+    #[must_use]
+    pub fn is_online(&self) -> bool {
+        matches!(self.status, MemberStatus::Online | MemberStatus::InZone)
+    }
+
+    /// Whether the member is in the dynamic zone.
+    #[must_use]
+    pub fn is_in_zone(&self) -> bool {
+        self.status == MemberStatus::InZone
+    }
+}
+
+/// The status of a [`DynamicZoneMember`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+pub enum MemberStatus {
+    /// The member is online, but not in the dynamic zone.
+    Online   = 1,
+    /// The member is offline.
+    Offline  = 2,
+    /// The member is in the dynamic zone.
+    InZone   = 3,
+    /// The member is link dead.
+    LinkDead = 4,
+
+    /// The game doesn't know the status of the member.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+/// A lockout timer, which stops the local character from doing an event of
+/// an expedition again until it expires.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lockout {
+    /// The name of the expedition that the lockout is for.
+    pub expedition_name: String,
+    /// The name of the event that the lockout is for.
+    pub event_name:      String,
+    /// When the lockout expires.
+    pub expires_at:      SystemTime,
+}
+
+impl Lockout {
+    /// Load all of the local character's lockout timers.
+    pub(crate) fn load() -> Vec<Lockout> {
+        (0..ffi::eqlib::dynamic_zone_timer_count())
+            .filter_map(|index| {
+                let mut timer = ffi::eqlib::DynamicZoneTimerInfo::default();
+                ffi::eqlib::get_dynamic_zone_timer(index, &mut timer)
+                    .then(|| Lockout::new(timer))
+            })
+            .collect()
+    }
+
+    fn new(timer: ffi::eqlib::DynamicZoneTimerInfo) -> Lockout {
+        Lockout {
+            expedition_name: timer.expedition_name,
+            event_name:      timer.event_name,
+            expires_at:      from_timestamp(timer.expires_at),
+        }
+    }
+
+    /// How long until the lockout expires, or [`Duration::ZERO`] if it already
+    /// has.
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.remaining_at(SystemTime::now())
+    }
+
+    /// How long the lockout will have left at `now`, or [`Duration::ZERO`] if
+    /// it will have expired by then.
+    #[must_use]
+    pub fn remaining_at(&self, now: SystemTime) -> Duration {
+        self.expires_at
+            .duration_since(now)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Whether the lockout has expired.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+}
+
+/// Turn a timestamp from the game, which is in seconds since the Unix epoch,
+/// into a [`SystemTime`].
+fn from_timestamp(timestamp: u32) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(u64::from(timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, status: MemberStatus) -> DynamicZoneMember {
+        DynamicZoneMember {
+            name: name.to_owned(),
+            status,
+        }
+    }
+
+    fn lockout(event_name: &str, expires_at: u32) -> Lockout {
+        Lockout::new(ffi::eqlib::DynamicZoneTimerInfo {
+            expedition_name: "Plane of Time".to_owned(),
+            event_name: event_name.to_owned(),
+            expires_at,
+        })
+    }
+
+    fn dynamic_zone(members: Vec<DynamicZoneMember>) -> DynamicZone {
+        DynamicZone {
+            name: "Plane of Time".to_owned(),
+            leader: "Alice".to_owned(),
+            max_members: 3,
+            members,
+            lockouts: vec![lockout("Quarm", 1_000), lockout("Phase 1", 2_000)],
+        }
+    }
+
+    #[test]
+    fn test_member_status() {
+        assert_eq!(MemberStatus::from(0), MemberStatus::Unknown(0));
+        assert_eq!(MemberStatus::from(3), MemberStatus::InZone);
+
+        assert!(member("Alice", MemberStatus::InZone).is_online());
+        assert!(member("Alice", MemberStatus::InZone).is_in_zone());
+        assert!(member("Bob", MemberStatus::Online).is_online());
+        assert!(!member("Bob", MemberStatus::Online).is_in_zone());
+        assert!(!member("Carol", MemberStatus::LinkDead).is_online());
+        assert!(!member("Dave", MemberStatus::Unknown(0)).is_online());
+    }
+
+    #[test]
+    fn test_members() {
+        let dz = dynamic_zone(vec![
+            member("Alice", MemberStatus::InZone),
+            member("Bob", MemberStatus::Online),
+            member("Carol", MemberStatus::Offline),
+        ]);
+
+        let waiting: Vec<&str> = dz.not_in_zone().map(|m| m.name.as_str()).collect();
+        assert_eq!(waiting, ["Bob", "Carol"]);
+        assert_eq!(dz.member("carol").unwrap().status, MemberStatus::Offline);
+        assert!(dz.member("Dave").is_none());
+        assert!(dz.is_full());
+        assert!(!dynamic_zone(vec![]).is_full());
+    }
+
+    #[test]
+    fn test_lockout_remaining() {
+        let lockout = lockout("Quarm", 1_000);
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+
+        assert_eq!(lockout.expires_at, at(1_000));
+        assert_eq!(lockout.remaining_at(at(400)), Duration::from_secs(600));
+        assert_eq!(lockout.remaining_at(at(1_000)), Duration::ZERO);
+        assert_eq!(lockout.remaining_at(at(5_000)), Duration::ZERO);
+        assert!(lockout.is_expired());
+    }
+
+    #[test]
+    fn test_lockout_lookup() {
+        let dz = dynamic_zone(vec![]);
+
+        assert_eq!(
+            dz.lockout("quarm").unwrap().expires_at,
+            from_timestamp(1_000)
+        );
+        assert!(dz.lockout("Phase 2").is_none());
+    }
+}
@@ -0,0 +1,203 @@
+//! The local character's fellowship, and its campfire.
+
+use std::fmt;
+
+use super::{Class, Location};
+use crate::ffi;
+use crate::mq::zones::{self, ZoneEntry};
+
+/// A snapshot of the local character's fellowship.
+///
+/// The snapshot is taken with [`mq::fellowship()`](crate::mq::fellowship), and
+/// doesn't change after it's been taken, so it can be held onto and used from
+/// any thread.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fellowship {
+    /// The members of the fellowship, including the local character.
+    pub members:  Vec<FellowshipMember>,
+    /// The fellowship's campfire, if one has been placed.
+    pub campfire: Option<Campfire>,
+}
+
+impl Fellowship {
+    /// Take a snapshot of the fellowship of `character`, returning `None` if
+    /// the character isn't in one.
+    pub(crate) fn from_raw(character: &ffi::eqlib::PcClient) -> Option<Fellowship> {
+        let members = (0..character.fellowship_member_count())
+            .filter_map(|index| {
+                let mut info = ffi::eqlib::FellowshipMemberInfo::default();
+                character
+                    .fellowship_member(index, &mut info)
+                    .then(|| FellowshipMember::from(info))
+            })
+            .collect();
+
+        let mut info = ffi::eqlib::CampfireInfo::default();
+        let campfire = character.campfire(&mut info).then(|| Campfire::from(info));
+
+        Fellowship::new(members, campfire)
+    }
+
+    /// Create a [`Fellowship`], skipping any empty member slots, and returning
+    /// `None` if there aren't any members left.
+    fn new(
+        mut members: Vec<FellowshipMember>,
+        campfire: Option<Campfire>,
+    ) -> Option<Fellowship> {
+        members.retain(|member| !member.name.is_empty());
+
+        (!members.is_empty()).then_some(Fellowship { members, campfire })
+    }
+
+    /// Find the member named `name`, ignoring case.
+    #[must_use]
+    pub fn member(&self, name: &str) -> Option<&FellowshipMember> {
+        self.members
+            .iter()
+            .find(|member| member.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterate over the members of the fellowship that are online.
+    pub fn online(&self) -> impl Iterator<Item = &FellowshipMember> {
+        self.members.iter().filter(|member| member.online)
+    }
+}
+
+/// A member of a [`Fellowship`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FellowshipMember {
+    /// The name of the member.
+    pub name:    String,
+    /// The class of the member.
+    pub class:   Class,
+    /// The level of the member.
+    pub level:   u32,
+    /// The id of the zone that the member is in, or was last in if they're
+    /// offline.
+    pub zone_id: u32,
+    /// Whether the member is online.
+    pub online:  bool,
+}
+
+impl FellowshipMember {
+    /// The zone that the member is in, or was last in if they're offline.
+    #[must_use]
+    pub fn zone(&self) -> Option<&'static ZoneEntry> {
+        zones::by_id(self.zone_id)
+    }
+}
+
+impl From<ffi::eqlib::FellowshipMemberInfo> for FellowshipMember {
+    fn from(info: ffi::eqlib::FellowshipMemberInfo) -> Self {
+        FellowshipMember {
+            name:    info.name.trim().to_owned(),
+            class:   Class::from(info.class_id),
+            level:   info.level,
+            zone_id: info.zone_id,
+            online:  info.online,
+        }
+    }
+}
+
+impl fmt::Display for FellowshipMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({} {})",
+            self.name,
+            self.level,
+            self.class.short_name()
+        )
+    }
+}
+
+/// A fellowship campfire, which fellowship members can click their fellowship
+/// insignia to be teleported to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Campfire {
+    /// The id of the zone that the campfire is in.
+    pub zone_id:  u32,
+    /// Where the campfire is.
+    pub location: Location,
+}
+
+impl Campfire {
+    /// The zone that the campfire is in.
+    #[must_use]
+    pub fn zone(&self) -> Option<&'static ZoneEntry> {
+        zones::by_id(self.zone_id)
+    }
+}
+
+impl From<ffi::eqlib::CampfireInfo> for Campfire {
+    fn from(info: ffi::eqlib::CampfireInfo) -> Self {
+        Campfire {
+            zone_id:  info.zone_id,
+            location: Location::from(info.location),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, online: bool) -> FellowshipMember {
+        FellowshipMember {
+            name: name.to_owned(),
+            class: Class::Cleric,
+            level: 65,
+            zone_id: 202,
+            online,
+        }
+    }
+
+    #[test]
+    fn test_not_in_fellowship() {
+        assert_eq!(Fellowship::new(vec![], None), None);
+        assert_eq!(Fellowship::new(vec![member("", false)], None), None);
+    }
+
+    #[test]
+    fn test_skips_empty_slots() {
+        let fellowship = Fellowship::new(
+            vec![
+                member("Alice", true),
+                member("", false),
+                member("Bob", false),
+            ],
+            None,
+        )
+        .unwrap();
+
+        let names: Vec<&str> = fellowship
+            .members
+            .iter()
+            .map(|member| member.name.as_str())
+            .collect();
+        assert_eq!(names, ["Alice", "Bob"]);
+        assert_eq!(fellowship.campfire, None);
+    }
+
+    #[test]
+    fn test_member_lookup() {
+        let campfire = Campfire {
+            zone_id:  202,
+            location: Location::new(1.0, 2.0, 3.0),
+        };
+        let fellowship = Fellowship::new(
+            vec![member("Alice", true), member("Bob", false)],
+            Some(campfire),
+        )
+        .unwrap();
+
+        assert_eq!(fellowship.member("bob"), Some(&member("Bob", false)));
+        assert_eq!(fellowship.member("Carol"), None);
+        assert_eq!(fellowship.online().count(), 1);
+        assert_eq!(fellowship.campfire, Some(campfire));
+        assert_eq!(member("Alice", true).to_string(), "Alice (65 CLR)");
+    }
+}
@@ -0,0 +1,255 @@
+//! Ground items, which are the items lying on the ground in a zone.
+
+use std::{error, fmt};
+
+use ref_cast::RefCast;
+
+use super::macros::getter;
+use super::switch::INTERACT_RANGE;
+use super::Location;
+use crate::ffi;
+
+/// An item lying on the ground in the current zone.
+///
+/// Ground items are listed with
+/// [`mq::ground_items()`](crate::mq::ground_items), and can only be used on the
+/// game's main thread. They belong to the zone, so they must not be held onto
+/// past zoning.
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct GroundItem(ffi::eqlib::EQGroundItem);
+
+impl GroundItem {
+    getter!(
+        /// The name of the item, which for items that were dropped is the id
+        /// of their model (ex: `IT63_ACTORDEF`) rather than the item's name.
+        ///
+        /// Some ground items (such as those that are part of the zone) have an
+        /// empty name.
+        name -> &str
+    );
+
+    /// The id of the item (the same as [`Item::id()`](super::Item::id)),
+    /// or 0 if the game doesn't know what the item is yet.
+    ///
+    /// See [`GroundItem::item_id()`] for a version that returns `None`
+    /// instead.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.raw().item_id()
+    }
+
+    /// The id of the item (the same as [`Item::id()`](super::Item::id)),
+    /// or `None` if the game doesn't know what the item is yet.
+    ///
+    /// Unlike the [name](GroundItem::name), this identifies which item is on
+    /// the ground regardless of the language the game is in.
+    #[must_use]
+    pub fn item_id(&self) -> Option<u32> {
+        nonzero(self.raw().item_id())
+    }
+
+    /// The id of the icon of the item (the same as
+    /// [`Item::icon()`](super::Item::icon)), or `None` if the game doesn't
+    /// know what the item is yet.
+    #[must_use]
+    pub fn icon_id(&self) -> Option<u32> {
+        nonzero(self.raw().icon_id())
+    }
+
+    getter!(
+        /// The id of the ground item itself, which is unique within the zone,
+        /// and is the same for the ground item passed to
+        /// [`Hooks::add_ground_item`](crate::plugin::Hooks::add_ground_item)
+        /// and to
+        /// [`Hooks::remove_ground_item`](crate::plugin::Hooks::remove_ground_item).
+        drop_id -> u32
+    );
+
+    getter!(
+        /// The id of the zone that the item is in.
+        zone_id -> u32
+    );
+
+    getter!(
+        /// The x coordinate of the item.
+        x -> f32
+    );
+
+    getter!(
+        /// The y coordinate of the item.
+        y -> f32
+    );
+
+    getter!(
+        /// The z coordinate of the item, which is its height.
+        z -> f32
+    );
+
+    getter!(
+        /// The direction that the item is facing, in the game's units, where
+        /// a full turn is 512.
+        heading -> f32
+    );
+
+    /// Where the item is.
+    #[must_use]
+    pub fn location(&self) -> Location {
+        Location::from(self.raw().location())
+    }
+
+    /// Pick the item up onto the cursor, the same way that `/itemtarget`
+    /// followed by `/click left item` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error without picking the item up if we're not in game, if
+    /// the item is further away than [`INTERACT_RANGE`], or if there's already
+    /// an item on the cursor.
+    pub fn pick_up(&self) -> Result<(), PickUpError> {
+        let mut player = ffi::eqlib::Location::default();
+        if !ffi::eqlib::get_player_location(&mut player) {
+            return Err(PickUpError::NotInGame);
+        }
+
+        check_range(Location::from(player), self.location())?;
+
+        // SAFETY: The pointer is either null or points to the local character,
+        //         which lives until we stop playing it.
+        let Some(character) = (unsafe { ffi::eqlib::get_local_pc().as_ref() })
+        else {
+            return Err(PickUpError::NotInGame);
+        };
+
+        if !character.cursor_item().is_null() {
+            return Err(PickUpError::CursorOccupied);
+        }
+
+        if ffi::mq::pick_up_ground_item(self.drop_id()) {
+            Ok(())
+        }
+        else {
+            Err(PickUpError::NotInGame)
+        }
+    }
+
+    fn raw(&self) -> &ffi::eqlib::EQGroundItem {
+        crate::macros::debug_assert_main_thread!(
+            ::std::any::type_name::<Self>(),
+            "raw"
+        );
+
+        &self.0
+    }
+}
+
+impl AsRef<GroundItem> for ffi::eqlib::EQGroundItem {
+    fn as_ref(&self) -> &GroundItem {
+        GroundItem::ref_cast(self)
+    }
+}
+
+impl fmt::Debug for GroundItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroundItem")
+            .field("drop_id", &self.drop_id())
+            .field("item_id", &self.item_id())
+            .field("icon_id", &self.icon_id())
+            .field("name", &self.name())
+            .field("zone_id", &self.zone_id())
+            .field("location", &self.location())
+            .field("heading", &self.heading())
+            .finish()
+    }
+}
+
+/// An error picking up a [`GroundItem`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum PickUpError {
+    /// We're not in game, so there's nobody to pick the item up.
+    NotInGame,
+    /// The item is too far away to pick up.
+    OutOfRange {
+        /// How far away the item is.
+        distance: f32,
+    },
+    /// There's already an item on the cursor, which has to be put away first.
+    CursorOccupied,
+}
+
+impl fmt::Display for PickUpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PickUpError::NotInGame => f.write_str("not in game"),
+            PickUpError::OutOfRange { distance } => {
+                write!(
+                    f,
+                    "item is out of range ({distance:.1} away, the most is \
+                     {INTERACT_RANGE:.1})"
+                )
+            }
+            PickUpError::CursorOccupied => {
+                f.write_str("there's already an item on the cursor")
+            }
+        }
+    }
+}
+
+impl error::Error for PickUpError {}
+
+/// The id `id`, or `None` if it's 0, which the game uses for no item.
+fn nonzero(id: u32) -> Option<u32> {
+    (id != 0).then_some(id)
+}
+
+/// Check that an item at `item` is in range of a player at `player`.
+fn check_range(player: Location, item: Location) -> Result<(), PickUpError> {
+    let distance = player.distance(&item);
+
+    if distance > INTERACT_RANGE {
+        return Err(PickUpError::OutOfRange { distance });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_range() {
+        let player = Location::new(100.0, 100.0, 0.0);
+
+        assert_eq!(check_range(player, player), Ok(()));
+        assert_eq!(
+            check_range(player, Location::new(112.0, 116.0, 0.0)),
+            Ok(())
+        );
+        assert_eq!(
+            check_range(player, Location::new(100.0, 100.0, -30.0)),
+            Err(PickUpError::OutOfRange { distance: 30.0 })
+        );
+    }
+
+    #[test]
+    fn test_nonzero() {
+        assert_eq!(nonzero(0), None);
+        assert_eq!(nonzero(1), Some(1));
+        assert_eq!(nonzero(u32::MAX), Some(u32::MAX));
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(PickUpError::NotInGame.to_string(), "not in game");
+        assert_eq!(
+            PickUpError::OutOfRange { distance: 42.25 }.to_string(),
+            "item is out of range (42.2 away, the most is 20.0)"
+        );
+        assert_eq!(
+            PickUpError::CursorOccupied.to_string(),
+            "there's already an item on the cursor"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! The local character's group.
+
+use std::fmt;
+
+use super::{Class, Spawn, SpawnId};
+use crate::ffi;
+
+/// A snapshot of the local character's group.
+///
+/// The snapshot is taken with [`mq::group()`](crate::mq::group), and doesn't
+/// change after it's been taken, so it can be held onto and used from any
+/// thread.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Group {
+    /// The members of the group, including the local character, in the order
+    /// that they're shown in the group window.
+    pub members: Vec<GroupMember>,
+}
+
+impl Group {
+    /// Take a snapshot of the group of `character`, returning `None` if the
+    /// character isn't in one.
+    pub(crate) fn from_raw(character: &ffi::eqlib::PcClient) -> Option<Group> {
+        let members = (0..character.group_member_count())
+            .filter_map(|index| {
+                let mut info = ffi::eqlib::GroupMemberInfo::default();
+                character
+                    .group_member(index, &mut info)
+                    .then(|| GroupMember::from(info))
+            })
+            .collect();
+
+        Group::new(members)
+    }
+
+    /// Create a [`Group`], skipping any empty member slots, and returning
+    /// `None` if there aren't any members left.
+    fn new(mut members: Vec<GroupMember>) -> Option<Group> {
+        members.retain(|member| !member.name.is_empty());
+
+        (!members.is_empty()).then_some(Group { members })
+    }
+
+    /// The leader of the group, or `None` if the leader has left it.
+    #[must_use]
+    pub fn leader(&self) -> Option<&GroupMember> {
+        self.members.iter().find(|member| member.leader)
+    }
+
+    /// The name of the leader of the group, or `None` if the leader has left
+    /// it.
+    #[must_use]
+    pub fn leader_name(&self) -> Option<&str> {
+        self.leader().map(|member| member.name.as_str())
+    }
+
+    /// How many members the group has, including the local character.
+    #[must_use]
+    pub fn member_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Find the member named `name`, ignoring case.
+    #[must_use]
+    pub fn member(&self, name: &str) -> Option<&GroupMember> {
+        self.members
+            .iter()
+            .find(|member| member.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterate over the members of the group that are in the same zone as the
+    /// local character.
+    pub fn in_zone(&self) -> impl Iterator<Item = &GroupMember> {
+        self.members.iter().filter(|member| member.is_in_zone())
+    }
+}
+
+/// A member of a [`Group`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupMember {
+    /// The name of the member.
+    pub name:     String,
+    /// The level of the member.
+    pub level:    u32,
+    /// The class of the member, which is only known while they're in the same
+    /// zone as the local character.
+    pub class:    Option<Class>,
+    /// The id of the member's spawn, or `None` if they're not in the same zone
+    /// as the local character.
+    pub spawn_id: Option<SpawnId>,
+    /// Whether the member is online.
+    pub online:   bool,
+    /// Whether the member is the leader of the group.
+    pub leader:   bool,
+}
+
+impl GroupMember {
+    /// Whether the member is in the same zone as the local character.
+    #[must_use]
+    pub fn is_in_zone(&self) -> bool {
+        self.spawn_id.is_some()
+    }
+
+    /// The member's spawn, or `None` if they're not in the same zone as the
+    /// local character (or have despawned since the snapshot was taken).
+    ///
+    /// Like [`mq::spawn_by_id()`](crate::mq::spawn_by_id), this can only be
+    /// used on the game's main thread, and the spawn must not be held onto
+    /// past the current hook.
+    #[must_use]
+    pub fn spawn(&self) -> Option<&'static Spawn> {
+        crate::mq::spawn_by_id(self.spawn_id?)
+    }
+}
+
+impl From<ffi::eqlib::GroupMemberInfo> for GroupMember {
+    fn from(info: ffi::eqlib::GroupMemberInfo) -> Self {
+        GroupMember {
+            name:     info.name.trim().to_owned(),
+            level:    info.level,
+            class:    (info.class_id != 0).then(|| Class::from(info.class_id)),
+            spawn_id: SpawnId::new(info.spawn_id),
+            online:   info.online,
+            leader:   info.leader,
+        }
+    }
+}
+
+impl fmt::Display for GroupMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.class {
+            Some(class) => {
+                write!(f, "{} ({} {})", self.name, self.level, class.short_name())
+            }
+            None => write!(f, "{} ({})", self.name, self.level),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, spawn_id: u32, leader: bool) -> GroupMember {
+        GroupMember {
+            name: name.to_owned(),
+            level: 65,
+            class: SpawnId::new(spawn_id).map(|_| Class::Cleric),
+            spawn_id: SpawnId::new(spawn_id),
+            online: true,
+            leader,
+        }
+    }
+
+    #[test]
+    fn test_not_in_group() {
+        assert_eq!(Group::new(vec![]), None);
+        assert_eq!(Group::new(vec![member("", 0, false)]), None);
+    }
+
+    #[test]
+    fn test_skips_empty_slots() {
+        let group = Group::new(vec![
+            member("Alice", 1, true),
+            member("", 0, false),
+            member("Bob", 0, false),
+        ])
+        .unwrap();
+
+        let names: Vec<&str> = group
+            .members
+            .iter()
+            .map(|member| member.name.as_str())
+            .collect();
+        assert_eq!(names, ["Alice", "Bob"]);
+        assert_eq!(group.member_count(), 2);
+    }
+
+    #[test]
+    fn test_leader() {
+        let group = Group::new(vec![member("Alice", 1, false), member("Bob", 2, true)])
+            .unwrap();
+        assert_eq!(group.leader_name(), Some("Bob"));
+
+        let group = Group::new(vec![member("Alice", 1, false)]).unwrap();
+        assert_eq!(group.leader_name(), None);
+    }
+
+    #[test]
+    fn test_member_lookup() {
+        let group = Group::new(vec![member("Alice", 1, true), member("Bob", 0, false)])
+            .unwrap();
+
+        assert_eq!(group.member("bob"), Some(&member("Bob", 0, false)));
+        assert_eq!(group.member("Carol"), None);
+        assert_eq!(group.in_zone().count(), 1);
+        assert!(!member("Bob", 0, false).is_in_zone());
+        assert_eq!(member("Alice", 1, true).to_string(), "Alice (65 CLR)");
+        assert_eq!(member("Bob", 0, false).to_string(), "Bob (65)");
+    }
+}
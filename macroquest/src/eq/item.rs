@@ -0,0 +1,481 @@
+//! Items, whether in an inventory, in a bag, or on the cursor.
+
+use std::fmt;
+
+use ref_cast::RefCast;
+
+use super::macros::getter;
+use crate::ffi;
+
+/// The deepest that [`Item::iter_contents()`] walks into nested containers.
+///
+/// The game never nests containers deeper than this, so anything deeper can
+/// only come from malformed item data, and isn't walked.
+pub const MAX_CONTAINER_DEPTH: usize = 2;
+
+/// An item.
+///
+/// Items can only be used on the game's main thread. Use [`Item::snapshot()`]
+/// to hold onto one elsewhere.
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Item(ffi::eqlib::ItemClient);
+
+impl Item {
+    getter!(
+        /// The name of the item.
+        name -> &str
+    );
+
+    getter!(
+        /// Whether the item is a container (such as a bag), which can hold
+        /// other items.
+        is_container -> bool
+    );
+
+    getter!(
+        /// Whether the item can't be traded or dropped.
+        is_no_drop -> bool
+    );
+
+    getter!(
+        /// Whether the item is lore, so that only one of it can be held.
+        is_lore -> bool
+    );
+
+    getter!(
+        /// Whether the item becomes no drop once it's equipped.
+        is_attunable -> bool
+    );
+
+    /// The id of the item.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        unsigned(self.raw().id())
+    }
+
+    /// The id of the icon of the item.
+    #[must_use]
+    pub fn icon(&self) -> u32 {
+        unsigned(self.raw().icon())
+    }
+
+    /// How many of the item there are in the stack.
+    #[must_use]
+    pub fn stack(&self) -> u32 {
+        unsigned(self.raw().stack())
+    }
+
+    /// How many of the item can be held in a single stack.
+    #[must_use]
+    pub fn stack_max(&self) -> u32 {
+        unsigned(self.raw().stack_max())
+    }
+
+    /// What the item is worth, in copper, when sold to a merchant.
+    #[must_use]
+    pub fn value_in_copper(&self) -> u32 {
+        unsigned(self.raw().value_in_copper())
+    }
+
+    /// How many slots the item has for holding other items, or 0 if it isn't
+    /// a container.
+    #[must_use]
+    pub fn container_slots(&self) -> usize {
+        usize::try_from(self.raw().container_slots()).unwrap_or(0)
+    }
+
+    /// The item in slot `slot` of this container, or `None` if the slot is
+    /// empty or this isn't a container.
+    #[must_use]
+    pub fn item_in_slot(&self, slot: usize) -> Option<&Item> {
+        let slot = i32::try_from(slot).ok()?;
+
+        // SAFETY: The pointer is either null or points to an item held by this
+        //         one, which lives for at least as long as this one does.
+        unsafe { self.raw().item_in_slot(slot).as_ref() }.map(AsRef::as_ref)
+    }
+
+    /// Iterate over every item within this container, including the items
+    /// within any containers that it holds, in slot order.
+    ///
+    /// Nested containers are walked no deeper than [`MAX_CONTAINER_DEPTH`].
+    pub fn iter_contents(&self) -> impl Iterator<Item = &Item> + '_ {
+        Contents::new(self)
+    }
+
+    /// A snapshot of the item's data (including its contents), which can be
+    /// sent to other threads.
+    #[must_use]
+    pub fn snapshot(&self) -> ItemSnapshot {
+        ItemSnapshot::from(self)
+    }
+
+    fn raw(&self) -> &ffi::eqlib::ItemClient {
+        crate::macros::debug_assert_main_thread!(
+            ::std::any::type_name::<Self>(),
+            "raw"
+        );
+
+        &self.0
+    }
+}
+
+impl AsRef<Item> for ffi::eqlib::ItemClient {
+    fn as_ref(&self) -> &Item {
+        Item::ref_cast(self)
+    }
+}
+
+impl fmt::Debug for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Item")
+            .field("id", &self.id())
+            .field("name", &self.name())
+            .field("stack", &self.stack())
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A snapshot of the data of an [`Item`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct ItemSnapshot {
+    /// The id of the item.
+    pub id:              u32,
+    /// The name of the item.
+    pub name:            String,
+    /// The id of the icon of the item.
+    pub icon:            u32,
+    /// How many of the item there are in the stack.
+    pub stack:           u32,
+    /// How many of the item can be held in a single stack.
+    pub stack_max:       u32,
+    /// Whether the item can't be traded or dropped.
+    pub no_drop:         bool,
+    /// Whether the item is lore, so that only one of it can be held.
+    pub lore:            bool,
+    /// Whether the item becomes no drop once it's equipped.
+    pub attunable:       bool,
+    /// What the item is worth, in copper, when sold to a merchant.
+    pub value_in_copper: u32,
+    /// The item in each slot of the container, with `None` for empty slots,
+    /// or nothing if the item isn't a container.
+    pub contents:        Vec<Option<ItemSnapshot>>,
+}
+
+impl From<&Item> for ItemSnapshot {
+    fn from(item: &Item) -> Self {
+        snapshot(item, 0)
+    }
+}
+
+impl fmt::Display for ItemSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// The item data that snapshots and walking containers work from, so that it
+/// can be backed by something other than the game's items in tests.
+trait ItemData {
+    fn id(&self) -> u32;
+    fn name(&self) -> &str;
+    fn icon(&self) -> u32;
+    fn stack(&self) -> u32;
+    fn stack_max(&self) -> u32;
+    fn is_no_drop(&self) -> bool;
+    fn is_lore(&self) -> bool;
+    fn is_attunable(&self) -> bool;
+    fn value_in_copper(&self) -> u32;
+    fn is_container(&self) -> bool;
+    fn container_slots(&self) -> usize;
+    fn item_in_slot(&self, slot: usize) -> Option<&Self>;
+}
+
+impl ItemData for Item {
+    fn id(&self) -> u32 {
+        Item::id(self)
+    }
+
+    fn name(&self) -> &str {
+        Item::name(self)
+    }
+
+    fn icon(&self) -> u32 {
+        Item::icon(self)
+    }
+
+    fn stack(&self) -> u32 {
+        Item::stack(self)
+    }
+
+    fn stack_max(&self) -> u32 {
+        Item::stack_max(self)
+    }
+
+    fn is_no_drop(&self) -> bool {
+        Item::is_no_drop(self)
+    }
+
+    fn is_lore(&self) -> bool {
+        Item::is_lore(self)
+    }
+
+    fn is_attunable(&self) -> bool {
+        Item::is_attunable(self)
+    }
+
+    fn value_in_copper(&self) -> u32 {
+        Item::value_in_copper(self)
+    }
+
+    fn is_container(&self) -> bool {
+        Item::is_container(self)
+    }
+
+    fn container_slots(&self) -> usize {
+        Item::container_slots(self)
+    }
+
+    fn item_in_slot(&self, slot: usize) -> Option<&Self> {
+        Item::item_in_slot(self, slot)
+    }
+}
+
+/// Snapshot `item`, which is nested `depth` containers deep.
+fn snapshot<T>(item: &T, depth: usize) -> ItemSnapshot
+where
+    T: ItemData,
+{
+    let contents = if item.is_container() && depth < MAX_CONTAINER_DEPTH {
+        (0..item.container_slots())
+            .map(|slot| {
+                item.item_in_slot(slot)
+                    .map(|item| snapshot(item, depth + 1))
+            })
+            .collect()
+    }
+    else {
+        vec![]
+    };
+
+    ItemSnapshot {
+        id: item.id(),
+        name: item.name().to_owned(),
+        icon: item.icon(),
+        stack: item.stack(),
+        stack_max: item.stack_max(),
+        no_drop: item.is_no_drop(),
+        lore: item.is_lore(),
+        attunable: item.is_attunable(),
+        value_in_copper: item.value_in_copper(),
+        contents,
+    }
+}
+
+/// Walks the contents of a container depth first, without recursing, so that
+/// malformed item data can't overflow the stack.
+struct Contents<'a, T> {
+    // The containers being walked, along with the next slot to look at in each.
+    stack: Vec<(&'a T, usize)>,
+}
+
+impl<'a, T> Contents<'a, T>
+where
+    T: ItemData,
+{
+    fn new(container: &'a T) -> Contents<'a, T> {
+        let stack = if container.is_container() {
+            vec![(container, 0)]
+        }
+        else {
+            vec![]
+        };
+
+        Contents { stack }
+    }
+}
+
+impl<'a, T> Iterator for Contents<'a, T>
+where
+    T: ItemData,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.stack.len();
+            let (container, next) = self.stack.last_mut()?;
+            let container: &'a T = container;
+
+            if *next >= container.container_slots() {
+                self.stack.pop();
+                continue;
+            }
+
+            let slot = *next;
+            *next += 1;
+
+            if let Some(item) = container.item_in_slot(slot) {
+                if item.is_container() && depth < MAX_CONTAINER_DEPTH {
+                    self.stack.push((item, 0));
+                }
+
+                return Some(item);
+            }
+        }
+    }
+}
+
+/// Convert a count from the game to a `u32`, treating negative counts as 0.
+fn unsigned(value: i32) -> u32 {
+    u32::try_from(value).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MockItem {
+        id:    u32,
+        name:  String,
+        // Some(...) for a container, with its slots.
+        slots: Option<Vec<Option<MockItem>>>,
+    }
+
+    impl ItemData for MockItem {
+        fn id(&self) -> u32 {
+            self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn icon(&self) -> u32 {
+            500
+        }
+
+        fn stack(&self) -> u32 {
+            1
+        }
+
+        fn stack_max(&self) -> u32 {
+            20
+        }
+
+        fn is_no_drop(&self) -> bool {
+            false
+        }
+
+        fn is_lore(&self) -> bool {
+            true
+        }
+
+        fn is_attunable(&self) -> bool {
+            false
+        }
+
+        fn value_in_copper(&self) -> u32 {
+            1234
+        }
+
+        fn is_container(&self) -> bool {
+            self.slots.is_some()
+        }
+
+        fn container_slots(&self) -> usize {
+            self.slots.as_ref().map_or(0, Vec::len)
+        }
+
+        fn item_in_slot(&self, slot: usize) -> Option<&Self> {
+            self.slots.as_ref()?.get(slot)?.as_ref()
+        }
+    }
+
+    fn item(id: u32) -> MockItem {
+        MockItem {
+            id,
+            name: format!("Item {id}"),
+            ..MockItem::default()
+        }
+    }
+
+    fn bag(id: u32, slots: Vec<Option<MockItem>>) -> MockItem {
+        MockItem {
+            slots: Some(slots),
+            ..item(id)
+        }
+    }
+
+    fn ids<'a>(contents: impl Iterator<Item = &'a MockItem>) -> Vec<u32> {
+        contents.map(|item| item.id).collect()
+    }
+
+    #[test]
+    fn test_contents_not_container() {
+        assert!(ids(Contents::new(&item(1))).is_empty());
+        assert!(ids(Contents::new(&bag(1, vec![None, None]))).is_empty());
+    }
+
+    #[test]
+    fn test_contents_nested() {
+        let bag = bag(
+            1,
+            vec![
+                Some(item(2)),
+                None,
+                Some(bag(3, vec![Some(item(4)), None, Some(item(5))])),
+                Some(item(6)),
+            ],
+        );
+
+        assert_eq!(ids(Contents::new(&bag)), vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_contents_max_depth() {
+        // Bags nested far deeper than the game allows.
+        let mut deepest = item(100);
+        for id in (1..10).rev() {
+            deepest = bag(id, vec![Some(deepest)]);
+        }
+
+        assert_eq!(ids(Contents::new(&deepest)), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let bag = bag(1, vec![None, Some(bag(2, vec![Some(bag(3, vec![]))]))]);
+        let snapshot = snapshot(&bag, 0);
+
+        assert_eq!(snapshot.to_string(), "Item 1");
+        assert_eq!(snapshot.value_in_copper, 1234);
+        assert!(snapshot.lore);
+        assert_eq!(snapshot.contents.len(), 2);
+        assert_eq!(snapshot.contents[0], None);
+
+        let inner = snapshot.contents[1].as_ref().unwrap();
+        assert_eq!(inner.id, 2);
+        assert_eq!(inner.contents[0].as_ref().unwrap().id, 3);
+
+        // Too deep to have its (empty) contents walked.
+        assert!(inner.contents[0].as_ref().unwrap().contents.is_empty());
+    }
+
+    #[test]
+    fn test_unsigned() {
+        assert_eq!(unsigned(-1), 0);
+        assert_eq!(unsigned(0), 0);
+        assert_eq!(unsigned(42), 42);
+    }
+}
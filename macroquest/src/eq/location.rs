@@ -0,0 +1,90 @@
+//! Locations in the world.
+
+use std::fmt;
+
+use crate::ffi;
+
+/// A location in the world, in the game's coordinates.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Location {
+    /// The x coordinate.
+    pub x: f32,
+    /// The y coordinate.
+    pub y: f32,
+    /// The z coordinate, which is the height.
+    pub z: f32,
+}
+
+impl Location {
+    /// Create a new [`Location`].
+    #[must_use]
+    pub fn new(x: f32, y: f32, z: f32) -> Location {
+        Location { x, y, z }
+    }
+
+    /// The straight line distance between this location and `other`.
+    #[must_use]
+    pub fn distance(&self, other: &Location) -> f32 {
+        let (dx, dy, dz) = (self.x - other.x, self.y - other.y, self.z - other.z);
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    /// The distance between this location and `other` along the ground,
+    /// ignoring the difference in height.
+    #[must_use]
+    pub fn distance_2d(&self, other: &Location) -> f32 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+impl From<ffi::eqlib::Location> for Location {
+    fn from(location: ffi::eqlib::Location) -> Self {
+        Location::new(location.x, location.y, location.z)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}, {:.2}, {:.2}", self.y, self.x, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        let a = Location::new(1.0, 2.0, 3.0);
+        let b = Location::new(4.0, 6.0, 3.0);
+
+        assert!((a.distance(&b) - 5.0).abs() < f32::EPSILON);
+        assert!((b.distance(&a) - 5.0).abs() < f32::EPSILON);
+        assert!(a.distance(&a).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_distance_2d() {
+        let a = Location::new(1.0, 2.0, 3.0);
+        let b = Location::new(4.0, 6.0, -100.0);
+
+        assert!((a.distance_2d(&b) - 5.0).abs() < f32::EPSILON);
+        assert!((b.distance_2d(&a) - 5.0).abs() < f32::EPSILON);
+        assert!(a.distance(&b) > a.distance_2d(&b));
+
+        // Straight above is no distance at all along the ground.
+        let above = Location::new(1.0, 2.0, 50.0);
+        assert!(a.distance_2d(&above).abs() < f32::EPSILON);
+        assert!((a.distance(&above) - 47.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_display() {
+        // The game shows locations as y, x, z.
+        assert_eq!(
+            Location::new(1.0, 2.5, -3.0).to_string(),
+            "2.50, 1.00, -3.00"
+        );
+    }
+}
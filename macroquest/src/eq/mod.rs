@@ -0,0 +1,1765 @@
+//!
+
+use std::str::FromStr;
+use std::{error, fmt};
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+use ref_cast::RefCast;
+
+use crate::ffi;
+
+pub mod buff;
+pub mod character;
+pub mod class;
+mod corpse;
+pub mod currency;
+pub mod deity;
+pub mod dynamic_zone;
+pub mod fellowship;
+pub mod ground_item;
+pub mod group;
+pub mod item;
+pub mod location;
+pub mod movement;
+pub mod race;
+pub mod raid;
+pub mod spawn_flags;
+pub mod spawn_type;
+pub mod spell;
+pub mod switch;
+pub mod task;
+pub mod time;
+pub mod zone;
+
+pub use self::buff::{Buff, BuffDuration};
+pub use self::character::{AuraSnapshot, Character};
+pub use self::class::{Class, ParseClassError};
+pub use self::currency::Coin;
+pub use self::deity::{Deity, Pantheon, ParseDeityError};
+pub use self::dynamic_zone::{DynamicZone, DynamicZoneMember, Lockout, MemberStatus};
+pub use self::fellowship::{Campfire, Fellowship, FellowshipMember};
+pub use self::ground_item::{GroundItem, PickUpError};
+pub use self::group::{Group, GroupMember};
+pub use self::item::{Item, ItemSnapshot};
+pub use self::location::Location;
+pub use self::movement::Movement;
+pub use self::race::Race;
+pub use self::raid::{LootType, Raid, RaidMember};
+pub use self::spawn_flags::SpawnFlags;
+pub use self::spawn_type::SpawnType;
+pub use self::spell::{Spa, Spell, SpellEffect, SpellSnapshot};
+pub use self::switch::{ClickError, Switch};
+pub use self::task::{Objective, ObjectiveType, Task, TaskKind};
+pub use self::time::GameTime;
+pub use self::zone::{Zone, ZoneType};
+
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+pub enum GameState {
+    PreCharacterSelect  = -1,
+    CharacterSelect     = 1,
+    CharacterCreate     = 2,
+    PostCharacterSelect = 3,
+    InGame              = 5,
+    LoggingIn           = 253,
+    Unloading           = 255,
+
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+impl GameState {
+    /// Every known game state, in id order.
+    pub const ALL: [GameState; 7] = [
+        GameState::PreCharacterSelect,
+        GameState::CharacterSelect,
+        GameState::CharacterCreate,
+        GameState::PostCharacterSelect,
+        GameState::InGame,
+        GameState::LoggingIn,
+        GameState::Unloading,
+    ];
+
+    /// The raw value of the game state, including for
+    /// [`GameState::Unknown`].
+    #[must_use]
+    pub fn raw(&self) -> i32 {
+        i32::from(*self)
+    }
+
+    /// Whether the game state is one known to this crate, rather than
+    /// [`GameState::Unknown`].
+    #[must_use]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, GameState::Unknown(_))
+    }
+}
+
+/// Represents the various types of chat "channels".
+///
+/// Messages in EverQuest get emitted to a specific channel, some of these
+/// channels are channels that users can talk on (like say, or group) others
+/// are channels that only the game itself can send messages on (like skills).
+///
+/// MacroQuest/EverQuest calls these the "color" of the chat message, because
+/// they control which filter the message applies under (and thus what "color"
+/// it gets printed with). Chat messages are written and hooked with a
+/// [`ChatColor`], which can be a channel or one of the plain colors, and which
+/// converts to and from a channel.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+pub enum Channel {
+    /// Say
+    Say                = 255 + 1,
+    /// Tell
+    Tell               = 255 + 2,
+    /// Group
+    Group              = 255 + 3,
+    /// Guild
+    Guild              = 255 + 4,
+    /// Out Of Character (OOC)
+    OOC                = 255 + 5,
+    /// Auction
+    Auction            = 255 + 6,
+    /// Shout
+    Shout              = 255 + 7,
+    /// Emotes
+    Emote              = 255 + 8,
+    /// Spells - Memming, scribing, casting, etc
+    Spells             = 255 + 9,
+    /// You Hit others
+    YouHitOther        = 255 + 10,
+    /// Other hits you
+    OtherHitsYou       = 255 + 11,
+    /// You miss other
+    YouMissOther       = 255 + 12,
+    /// Other misses you
+    OtherMissYou       = 255 + 13,
+    /// Broadcasts (duels, etc)
+    Duels              = 255 + 14,
+    /// Skills - Skill ups, non-combat use, etc
+    Skills             = 255 + 15,
+    /// Disciplines or special abilities
+    Disciplines        = 255 + 16,
+    /// Default text and stuff you type
+    Default            = 255 + 18,
+    /// Faction Messages
+    Faction            = 255 + 19,
+    /// Merchant Offer Price
+    MerchantOffer      = 255 + 20,
+    /// Merchant Buy/Sell
+    MerchantExchange   = 255 + 21,
+    /// Your death message
+    YourDeath          = 255 + 22,
+    /// Others death message
+    OtherDeath         = 255 + 23,
+    /// Other damage other
+    OtherHitOther      = 255 + 24,
+    /// Other miss other
+    OtherMissOther     = 255 + 25,
+    /// /who command
+    Who                = 255 + 26,
+    /// Yell for help
+    Yell               = 255 + 27,
+    /// Spell Damage
+    SpellDamage        = 255 + 28,
+    /// Spell worn off
+    SpellWornOff       = 255 + 29,
+    /// Money splits
+    MoneySplit         = 255 + 30,
+    /// Loot message
+    Loot               = 255 + 31,
+    /// Dice Roll (/random)
+    Random             = 255 + 32,
+    /// Others spells
+    OthersSpells       = 255 + 33,
+    /// Spell Failures - Resists, fizzles, missing component, bad target, etc
+    SpellFailure       = 255 + 34,
+    /// Chat Channel Messages
+    ChatChannel        = 255 + 35,
+    /// Chat Channel 1
+    ChatChannel1       = 255 + 36,
+    /// Chat Channel 2
+    ChatChannel2       = 255 + 37,
+    /// Chat Channel 3
+    ChatChannel3       = 255 + 38,
+    /// Chat Channel 4
+    ChatChannel4       = 255 + 39,
+    /// Chat Channel 5
+    ChatChannel5       = 255 + 40,
+    /// Chat Channel 6
+    ChatChannel6       = 255 + 41,
+    /// Chat Channel 7
+    ChatChannel7       = 255 + 42,
+    /// Chat Channel 8
+    ChatChannel8       = 255 + 43,
+    /// Chat Channel 9
+    ChatChannel9       = 255 + 44,
+    /// Chat Channel 10
+    ChatChannel10      = 255 + 45,
+    /// Melee Crits (Yours)
+    MeleeCrit          = 255 + 46,
+    /// Direct Damage Crits (Yours)
+    SpellCrit          = 255 + 47,
+    /// Too far away (Melee)
+    TooFarAway         = 255 + 48,
+    /// NPC Rampage
+    NPCRampage         = 255 + 49,
+    /// NPC Flurry
+    NPCFlurry          = 255 + 50,
+    /// NPC Enrage
+    NPCEnrage          = 255 + 51,
+    /// Say Echo
+    EchoSay            = 255 + 52,
+    /// Tell Echo
+    EchoTell           = 255 + 53,
+    /// Group Echo
+    EchoGroup          = 255 + 54,
+    /// Guild Echo
+    EchoGuild          = 255 + 55,
+    /// OOC Echo
+    EchoOOC            = 255 + 56,
+    /// Auction Echo
+    EchoAuction        = 255 + 57,
+    /// Shout Echo
+    EchoShout          = 255 + 58,
+    /// Emote Echo
+    EchoEmote          = 255 + 59,
+    /// Chat Channel 1 Echo
+    EchoChatChannel1   = 255 + 60,
+    /// Chat Channel 2 Echo
+    EchoChatChannel2   = 255 + 61,
+    /// Chat Channel 3 Echo
+    EchoChatChannel3   = 255 + 62,
+    /// Chat Channel 4 Echo
+    EchoChatChannel4   = 255 + 63,
+    /// Chat Channel 5 Echo
+    EchoChatChannel5   = 255 + 64,
+    /// Chat Channel 6 Echo
+    EchoChatChannel6   = 255 + 65,
+    /// Chat Channel 7 Echo
+    EchoChatChannel7   = 255 + 66,
+    /// Chat Channel 8 Echo
+    EchoChatChannel8   = 255 + 67,
+    /// Chat Channel 9 Echo
+    EchoChatChannel9   = 255 + 68,
+    /// Chat Channel 10 Echo
+    EchoChatChannel10  = 255 + 69,
+    /// Avatar Command Output
+    AvatarCommand      = 255 + 70,
+    /// Item Links
+    Link               = 255 + 71,
+    /// Raid Say
+    Raid               = 255 + 72,
+    /// Pet Melee (Yours)
+    Pet                = 255 + 73,
+    /// Damage Shield hits you
+    DamageShield       = 255 + 74,
+    /// Group / Raid Role messages
+    Leader             = 255 + 75,
+    /// Pet rampage/flurry
+    PetRampageFlurry   = 255 + 76,
+    /// Pet's critical hits
+    PetCrit            = 255 + 77,
+    /// Focus item activation
+    Focus              = 255 + 78,
+    /// XP Gain/Loss
+    Experience         = 255 + 79,
+    /// System Broadcasts
+    System             = 255 + 80,
+    /// Pet spells
+    PetSpells          = 255 + 81,
+    /// Pet responses
+    PetResponses       = 255 + 82,
+    /// Item Speech
+    ItemSpeech         = 255 + 83,
+    /// Strikethrough Messages
+    Strikethrough      = 255 + 84,
+    /// Stun Messages
+    Stun               = 255 + 85,
+    /// Swarm Pet Death
+    SwarmPetDeath      = 255 + 86,
+    /// Fellowship Messages
+    Fellowship         = 255 + 87,
+    /// NPC Dialogue
+    NPCSpeech          = 255 + 88,
+    /// NPC Dialogue to You
+    NPCSpeechToYou     = 255 + 89,
+    /// Guild Messages
+    GuildMessage       = 255 + 90,
+    /// Mercenary to Group Messages
+    MercenaryGroup     = 255 + 91,
+    /// Achievement Links
+    Achievement        = 255 + 92,
+    /// Achievement (Yours)
+    AchievementYou     = 255 + 93,
+    /// Achievement (Others)
+    AchievementOthers  = 255 + 94,
+    /// PvP Messages
+    PvP                = 255 + 95,
+    /// Hotbutton Cooldown Overlay
+    HotButtonCooldown  = 255 + 96,
+    /// Aggro Labels - Low
+    AggroLow           = 255 + 97,
+    /// Aggro Labels - Warning
+    AggroWarning       = 255 + 98,
+    /// Aggro Labels - Most
+    AggroMost          = 255 + 99,
+    /// Dialog \[Response\] Links
+    DialogLink         = 255 + 100,
+    /// Flurry (Yours)
+    YouFlurry          = 255 + 101,
+    /// Debug Output
+    Debug              = 255 + 102,
+    /// Death Notification - NPCs
+    NPCDeath           = 255 + 103,
+    /// Dice Roll (/random) - Others
+    RandomOther        = 255 + 104,
+    /// Dice Roll (/random) - Group / Raid
+    RandomGroup        = 255 + 105,
+    /// Environmental Damage (Yours)
+    YouFallDamage      = 255 + 106,
+    /// Environmental Damage (Others)
+    OtherFallDamage    = 255 + 107,
+    /// Damage Shield (Yours)
+    YouDamageSield     = 255 + 108,
+    /// Damage Shield (Other)
+    OtherDamageShield  = 255 + 109,
+    /// Event Messages
+    Event              = 255 + 110,
+    /// Spell Overwritten (Detrimental)
+    DetrimentalSpellOverwritten = 255 + 111,
+    /// Spell Overwritten (Beneficial)
+    BeneficialSpellOverwritten = 255 + 112,
+    /// Can't Use Command Warning
+    CantUseCommand     = 255 + 113,
+    /// Combat Ability Reuse - You can use [Ability Name] again in [time until]
+    AbilityCooldown    = 255 + 114,
+    /// Alt Ability Reuse
+    AltAbilityCooldown = 255 + 115,
+    /// Destroy Item Message
+    DestroyItem        = 255 + 116,
+    /// Auras (You)
+    AurasYours         = 255 + 117,
+    /// Aura (Others)
+    AurasOthers        = 255 + 118,
+    /// Heals (You)
+    HealsYours         = 255 + 119,
+    /// Heals (Others)
+    HealsOthers        = 255 + 120,
+    /// DoT (Yours)
+    DoTsYours          = 255 + 121,
+    /// DoT (Others)
+    DoTsOthers         = 255 + 122,
+    /// Bard Songs on Pets
+    PetBardSongs       = 255 + 123,
+    /// Direct Damage (Others)
+    DirectDamageOthers = 255 + 124,
+    /// Spell Emotes
+    SpellEmotes        = 255 + 125,
+    /// Faction Links
+    FactionLink        = 255 + 126,
+    /// Taunt Messages
+    Taunt              = 255 + 127,
+    /// Combat Abilities / Disciplines (Others)
+    DisciplinesOthers  = 255 + 128,
+    /// Item Stat Positive
+    ItemStatPositive   = 255 + 129,
+    /// Item Stat Negative
+    ItemStatNegative   = 255 + 130,
+    /// Encounter Lock Attackable
+    EncounterLockAttackable = 255 + 131,
+    /// Encounter Lock Unattackable
+    EncounterLockUnattackable = 255 + 132,
+    /// Food And Drink
+    FoodAndDrink       = 255 + 133,
+    /// Raid Victory
+    RaidVictory        = 255 + 134,
+
+    // MacroQuest assigns 255 + 135 through 255 + 141, and values past
+    // `MeleeOthersCrit`, but they aren't named here yet, so they come through
+    // as `Unknown` until they're added here and to `Channel::ALL`.
+    /// Direct Damage (Yours)
+    DirectDamageYours  = 255 + 142,
+    /// Direct Damage (Other Critical Hits)
+    DirectDamageOthersCrit = 255 + 143,
+    /// DoTs (Your Critical Hits)
+    DoTsYoursCrit      = 255 + 144,
+    /// DoTs (Other Critical Hits)
+    DoTsOthersCrit     = 255 + 145,
+    /// DoTs (You Being Hit)
+    DoTsDamageTaken    = 255 + 146,
+    /// Heals Received
+    HealsReceived      = 255 + 147,
+    /// Heals (Your Critical Heals)
+    HealsYoursCrit     = 255 + 148,
+    /// Heals (Other Critical Heals)
+    HealsOthersCrit    = 255 + 149,
+    /// Others Hits (Critical)
+    MeleeOthersCrit    = 255 + 150,
+
+    /// Unknown Channel
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+impl Channel {
+    /// Every known channel, in id order.
+    pub const ALL: [Channel; 142] = [
+        Channel::Say,
+        Channel::Tell,
+        Channel::Group,
+        Channel::Guild,
+        Channel::OOC,
+        Channel::Auction,
+        Channel::Shout,
+        Channel::Emote,
+        Channel::Spells,
+        Channel::YouHitOther,
+        Channel::OtherHitsYou,
+        Channel::YouMissOther,
+        Channel::OtherMissYou,
+        Channel::Duels,
+        Channel::Skills,
+        Channel::Disciplines,
+        Channel::Default,
+        Channel::Faction,
+        Channel::MerchantOffer,
+        Channel::MerchantExchange,
+        Channel::YourDeath,
+        Channel::OtherDeath,
+        Channel::OtherHitOther,
+        Channel::OtherMissOther,
+        Channel::Who,
+        Channel::Yell,
+        Channel::SpellDamage,
+        Channel::SpellWornOff,
+        Channel::MoneySplit,
+        Channel::Loot,
+        Channel::Random,
+        Channel::OthersSpells,
+        Channel::SpellFailure,
+        Channel::ChatChannel,
+        Channel::ChatChannel1,
+        Channel::ChatChannel2,
+        Channel::ChatChannel3,
+        Channel::ChatChannel4,
+        Channel::ChatChannel5,
+        Channel::ChatChannel6,
+        Channel::ChatChannel7,
+        Channel::ChatChannel8,
+        Channel::ChatChannel9,
+        Channel::ChatChannel10,
+        Channel::MeleeCrit,
+        Channel::SpellCrit,
+        Channel::TooFarAway,
+        Channel::NPCRampage,
+        Channel::NPCFlurry,
+        Channel::NPCEnrage,
+        Channel::EchoSay,
+        Channel::EchoTell,
+        Channel::EchoGroup,
+        Channel::EchoGuild,
+        Channel::EchoOOC,
+        Channel::EchoAuction,
+        Channel::EchoShout,
+        Channel::EchoEmote,
+        Channel::EchoChatChannel1,
+        Channel::EchoChatChannel2,
+        Channel::EchoChatChannel3,
+        Channel::EchoChatChannel4,
+        Channel::EchoChatChannel5,
+        Channel::EchoChatChannel6,
+        Channel::EchoChatChannel7,
+        Channel::EchoChatChannel8,
+        Channel::EchoChatChannel9,
+        Channel::EchoChatChannel10,
+        Channel::AvatarCommand,
+        Channel::Link,
+        Channel::Raid,
+        Channel::Pet,
+        Channel::DamageShield,
+        Channel::Leader,
+        Channel::PetRampageFlurry,
+        Channel::PetCrit,
+        Channel::Focus,
+        Channel::Experience,
+        Channel::System,
+        Channel::PetSpells,
+        Channel::PetResponses,
+        Channel::ItemSpeech,
+        Channel::Strikethrough,
+        Channel::Stun,
+        Channel::SwarmPetDeath,
+        Channel::Fellowship,
+        Channel::NPCSpeech,
+        Channel::NPCSpeechToYou,
+        Channel::GuildMessage,
+        Channel::MercenaryGroup,
+        Channel::Achievement,
+        Channel::AchievementYou,
+        Channel::AchievementOthers,
+        Channel::PvP,
+        Channel::HotButtonCooldown,
+        Channel::AggroLow,
+        Channel::AggroWarning,
+        Channel::AggroMost,
+        Channel::DialogLink,
+        Channel::YouFlurry,
+        Channel::Debug,
+        Channel::NPCDeath,
+        Channel::RandomOther,
+        Channel::RandomGroup,
+        Channel::YouFallDamage,
+        Channel::OtherFallDamage,
+        Channel::YouDamageSield,
+        Channel::OtherDamageShield,
+        Channel::Event,
+        Channel::DetrimentalSpellOverwritten,
+        Channel::BeneficialSpellOverwritten,
+        Channel::CantUseCommand,
+        Channel::AbilityCooldown,
+        Channel::AltAbilityCooldown,
+        Channel::DestroyItem,
+        Channel::AurasYours,
+        Channel::AurasOthers,
+        Channel::HealsYours,
+        Channel::HealsOthers,
+        Channel::DoTsYours,
+        Channel::DoTsOthers,
+        Channel::PetBardSongs,
+        Channel::DirectDamageOthers,
+        Channel::SpellEmotes,
+        Channel::FactionLink,
+        Channel::Taunt,
+        Channel::DisciplinesOthers,
+        Channel::ItemStatPositive,
+        Channel::ItemStatNegative,
+        Channel::EncounterLockAttackable,
+        Channel::EncounterLockUnattackable,
+        Channel::FoodAndDrink,
+        Channel::RaidVictory,
+        Channel::DirectDamageYours,
+        Channel::DirectDamageOthersCrit,
+        Channel::DoTsYoursCrit,
+        Channel::DoTsOthersCrit,
+        Channel::DoTsDamageTaken,
+        Channel::HealsReceived,
+        Channel::HealsYoursCrit,
+        Channel::HealsOthersCrit,
+        Channel::MeleeOthersCrit,
+    ];
+
+    /// The raw value of the channel, including for
+    /// [`Channel::Unknown`].
+    #[must_use]
+    pub fn raw(&self) -> i32 {
+        i32::from(*self)
+    }
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for Channel {
+    fn default() -> Self {
+        Channel::Default
+    }
+}
+
+impl Channel {
+    /// The name of the channel, as MacroQuest shows it in its chat filters
+    /// (ex: `Out Of Character`), or `None` for [`Channel::Unknown`].
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        self.names().map(|(name, _)| name)
+    }
+
+    /// The identifier of the channel, in snake case (ex: `ooc`), or `None`
+    /// for [`Channel::Unknown`].
+    ///
+    /// This is meant for places that users have to type a channel, such as
+    /// slash command arguments and settings files.
+    #[must_use]
+    pub fn ident(&self) -> Option<&'static str> {
+        self.names().map(|(_, ident)| ident)
+    }
+
+    // One arm for each channel, so it's as long as the enum is.
+    #[allow(clippy::too_many_lines)]
+    fn names(self) -> Option<(&'static str, &'static str)> {
+        let names = match self {
+            Channel::Say => ("Say", "say"),
+            Channel::Tell => ("Tell", "tell"),
+            Channel::Group => ("Group", "group"),
+            Channel::Guild => ("Guild", "guild"),
+            Channel::OOC => ("Out Of Character", "ooc"),
+            Channel::Auction => ("Auction", "auction"),
+            Channel::Shout => ("Shout", "shout"),
+            Channel::Emote => ("Emotes", "emote"),
+            Channel::Spells => ("Spells", "spells"),
+            Channel::YouHitOther => ("You Hit Others", "you_hit_other"),
+            Channel::OtherHitsYou => ("Others Hit You", "other_hits_you"),
+            Channel::YouMissOther => ("You Miss Others", "you_miss_other"),
+            Channel::OtherMissYou => ("Others Miss You", "other_miss_you"),
+            Channel::Duels => ("Duels", "duels"),
+            Channel::Skills => ("Skills", "skills"),
+            Channel::Disciplines => ("Disciplines", "disciplines"),
+            Channel::Default => ("Default", "default"),
+            Channel::Faction => ("Faction", "faction"),
+            Channel::MerchantOffer => ("Merchant Offer", "merchant_offer"),
+            Channel::MerchantExchange => ("Merchant Exchange", "merchant_exchange"),
+            Channel::YourDeath => ("Your Death", "your_death"),
+            Channel::OtherDeath => ("Other Deaths", "other_death"),
+            Channel::OtherHitOther => ("Others Hit Others", "other_hit_other"),
+            Channel::OtherMissOther => ("Others Miss Others", "other_miss_other"),
+            Channel::Who => ("Who", "who"),
+            Channel::Yell => ("Yell", "yell"),
+            Channel::SpellDamage => ("Spell Damage", "spell_damage"),
+            Channel::SpellWornOff => ("Spell Worn Off", "spell_worn_off"),
+            Channel::MoneySplit => ("Money Split", "money_split"),
+            Channel::Loot => ("Loot", "loot"),
+            Channel::Random => ("Random", "random"),
+            Channel::OthersSpells => ("Others Spells", "others_spells"),
+            Channel::SpellFailure => ("Spell Failures", "spell_failure"),
+            Channel::ChatChannel => ("Chat Channel", "chat_channel"),
+            Channel::ChatChannel1 => ("Chat Channel 1", "chat_channel_1"),
+            Channel::ChatChannel2 => ("Chat Channel 2", "chat_channel_2"),
+            Channel::ChatChannel3 => ("Chat Channel 3", "chat_channel_3"),
+            Channel::ChatChannel4 => ("Chat Channel 4", "chat_channel_4"),
+            Channel::ChatChannel5 => ("Chat Channel 5", "chat_channel_5"),
+            Channel::ChatChannel6 => ("Chat Channel 6", "chat_channel_6"),
+            Channel::ChatChannel7 => ("Chat Channel 7", "chat_channel_7"),
+            Channel::ChatChannel8 => ("Chat Channel 8", "chat_channel_8"),
+            Channel::ChatChannel9 => ("Chat Channel 9", "chat_channel_9"),
+            Channel::ChatChannel10 => ("Chat Channel 10", "chat_channel_10"),
+            Channel::MeleeCrit => ("Melee Crits", "melee_crit"),
+            Channel::SpellCrit => ("Spell Crits", "spell_crit"),
+            Channel::TooFarAway => ("Too Far Away", "too_far_away"),
+            Channel::NPCRampage => ("NPC Rampage", "npc_rampage"),
+            Channel::NPCFlurry => ("NPC Flurry", "npc_flurry"),
+            Channel::NPCEnrage => ("NPC Enrage", "npc_enrage"),
+            Channel::EchoSay => ("Say Echo", "echo_say"),
+            Channel::EchoTell => ("Tell Echo", "echo_tell"),
+            Channel::EchoGroup => ("Group Echo", "echo_group"),
+            Channel::EchoGuild => ("Guild Echo", "echo_guild"),
+            Channel::EchoOOC => ("OOC Echo", "echo_ooc"),
+            Channel::EchoAuction => ("Auction Echo", "echo_auction"),
+            Channel::EchoShout => ("Shout Echo", "echo_shout"),
+            Channel::EchoEmote => ("Emote Echo", "echo_emote"),
+            Channel::EchoChatChannel1 => ("Chat Channel 1 Echo", "echo_chat_channel_1"),
+            Channel::EchoChatChannel2 => ("Chat Channel 2 Echo", "echo_chat_channel_2"),
+            Channel::EchoChatChannel3 => ("Chat Channel 3 Echo", "echo_chat_channel_3"),
+            Channel::EchoChatChannel4 => ("Chat Channel 4 Echo", "echo_chat_channel_4"),
+            Channel::EchoChatChannel5 => ("Chat Channel 5 Echo", "echo_chat_channel_5"),
+            Channel::EchoChatChannel6 => ("Chat Channel 6 Echo", "echo_chat_channel_6"),
+            Channel::EchoChatChannel7 => ("Chat Channel 7 Echo", "echo_chat_channel_7"),
+            Channel::EchoChatChannel8 => ("Chat Channel 8 Echo", "echo_chat_channel_8"),
+            Channel::EchoChatChannel9 => ("Chat Channel 9 Echo", "echo_chat_channel_9"),
+            Channel::EchoChatChannel10 => {
+                ("Chat Channel 10 Echo", "echo_chat_channel_10")
+            }
+            Channel::AvatarCommand => ("Avatar Command", "avatar_command"),
+            Channel::Link => ("Item Links", "link"),
+            Channel::Raid => ("Raid Say", "raid"),
+            Channel::Pet => ("Pet Melee", "pet"),
+            Channel::DamageShield => ("Damage Shield", "damage_shield"),
+            Channel::Leader => ("Leader", "leader"),
+            Channel::PetRampageFlurry => ("Pet Rampage/Flurry", "pet_rampage_flurry"),
+            Channel::PetCrit => ("Pet Crits", "pet_crit"),
+            Channel::Focus => ("Focus", "focus"),
+            Channel::Experience => ("Experience", "experience"),
+            Channel::System => ("System", "system"),
+            Channel::PetSpells => ("Pet Spells", "pet_spells"),
+            Channel::PetResponses => ("Pet Responses", "pet_responses"),
+            Channel::ItemSpeech => ("Item Speech", "item_speech"),
+            Channel::Strikethrough => ("Strikethrough", "strikethrough"),
+            Channel::Stun => ("Stun", "stun"),
+            Channel::SwarmPetDeath => ("Swarm Pet Death", "swarm_pet_death"),
+            Channel::Fellowship => ("Fellowship", "fellowship"),
+            Channel::NPCSpeech => ("NPC Dialogue", "npc_speech"),
+            Channel::NPCSpeechToYou => ("NPC Dialogue To You", "npc_speech_to_you"),
+            Channel::GuildMessage => ("Guild Messages", "guild_message"),
+            Channel::MercenaryGroup => ("Mercenary Group", "mercenary_group"),
+            Channel::Achievement => ("Achievement Links", "achievement"),
+            Channel::AchievementYou => ("Achievements (Yours)", "achievement_you"),
+            Channel::AchievementOthers => {
+                ("Achievements (Others)", "achievement_others")
+            }
+            Channel::PvP => ("PvP", "pvp"),
+            Channel::HotButtonCooldown => ("Hotbutton Cooldown", "hot_button_cooldown"),
+            Channel::AggroLow => ("Aggro Low", "aggro_low"),
+            Channel::AggroWarning => ("Aggro Warning", "aggro_warning"),
+            Channel::AggroMost => ("Aggro Most", "aggro_most"),
+            Channel::DialogLink => ("Dialog Links", "dialog_link"),
+            Channel::YouFlurry => ("Flurry (Yours)", "you_flurry"),
+            Channel::Debug => ("Debug", "debug"),
+            Channel::NPCDeath => ("NPC Death", "npc_death"),
+            Channel::RandomOther => ("Random (Others)", "random_other"),
+            Channel::RandomGroup => ("Random (Group)", "random_group"),
+            Channel::YouFallDamage => {
+                ("Environmental Damage (Yours)", "you_fall_damage")
+            }
+            Channel::OtherFallDamage => {
+                ("Environmental Damage (Others)", "other_fall_damage")
+            }
+            Channel::YouDamageSield => ("Damage Shield (Yours)", "you_damage_shield"),
+            Channel::OtherDamageShield => {
+                ("Damage Shield (Others)", "other_damage_shield")
+            }
+            Channel::Event => ("Event", "event"),
+            Channel::DetrimentalSpellOverwritten => (
+                "Spell Overwritten (Detrimental)",
+                "detrimental_spell_overwritten",
+            ),
+            Channel::BeneficialSpellOverwritten => (
+                "Spell Overwritten (Beneficial)",
+                "beneficial_spell_overwritten",
+            ),
+            Channel::CantUseCommand => ("Can't Use Command", "cant_use_command"),
+            Channel::AbilityCooldown => ("Ability Cooldown", "ability_cooldown"),
+            Channel::AltAbilityCooldown => {
+                ("Alt Ability Cooldown", "alt_ability_cooldown")
+            }
+            Channel::DestroyItem => ("Destroy Item", "destroy_item"),
+            Channel::AurasYours => ("Auras (Yours)", "auras_yours"),
+            Channel::AurasOthers => ("Auras (Others)", "auras_others"),
+            Channel::HealsYours => ("Heals (Yours)", "heals_yours"),
+            Channel::HealsOthers => ("Heals (Others)", "heals_others"),
+            Channel::DoTsYours => ("DoTs (Yours)", "dots_yours"),
+            Channel::DoTsOthers => ("DoTs (Others)", "dots_others"),
+            Channel::PetBardSongs => ("Pet Bard Songs", "pet_bard_songs"),
+            Channel::DirectDamageOthers => {
+                ("Direct Damage (Others)", "direct_damage_others")
+            }
+            Channel::SpellEmotes => ("Spell Emotes", "spell_emotes"),
+            Channel::FactionLink => ("Faction Links", "faction_link"),
+            Channel::Taunt => ("Taunt", "taunt"),
+            Channel::DisciplinesOthers => {
+                ("Disciplines (Others)", "disciplines_others")
+            }
+            Channel::ItemStatPositive => ("Item Stat Positive", "item_stat_positive"),
+            Channel::ItemStatNegative => ("Item Stat Negative", "item_stat_negative"),
+            Channel::EncounterLockAttackable => {
+                ("Encounter Lock Attackable", "encounter_lock_attackable")
+            }
+            Channel::EncounterLockUnattackable => {
+                ("Encounter Lock Unattackable", "encounter_lock_unattackable")
+            }
+            Channel::FoodAndDrink => ("Food And Drink", "food_and_drink"),
+            Channel::RaidVictory => ("Raid Victory", "raid_victory"),
+            Channel::DirectDamageYours => {
+                ("Direct Damage (Yours)", "direct_damage_yours")
+            }
+            Channel::DirectDamageOthersCrit => {
+                ("Direct Damage (Others Crits)", "direct_damage_others_crit")
+            }
+            Channel::DoTsYoursCrit => ("DoTs (Your Crits)", "dots_yours_crit"),
+            Channel::DoTsOthersCrit => ("DoTs (Others Crits)", "dots_others_crit"),
+            Channel::DoTsDamageTaken => ("DoTs (Damage Taken)", "dots_damage_taken"),
+            Channel::HealsReceived => ("Heals Received", "heals_received"),
+            Channel::HealsYoursCrit => ("Heals (Your Crits)", "heals_yours_crit"),
+            Channel::HealsOthersCrit => ("Heals (Others Crits)", "heals_others_crit"),
+            Channel::MeleeOthersCrit => ("Melee (Others Crits)", "melee_others_crit"),
+            Channel::Unknown(_) => return None,
+        };
+
+        Some(names)
+    }
+}
+
+/// Formats the channel using its [`name`](Channel::name), or its raw
+/// value for [`Channel::Unknown`] (ex: `Unknown (400)`).
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "Unknown ({})", self.raw()),
+        }
+    }
+}
+
+/// Parses a channel from either its [`name`](Channel::name) or its
+/// [`ident`](Channel::ident), ignoring case.
+impl FromStr for Channel {
+    type Err = ParseChannelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        Channel::ALL
+            .into_iter()
+            .find(|color| {
+                color.names().is_some_and(|(name, ident)| {
+                    name.eq_ignore_ascii_case(s) || ident.eq_ignore_ascii_case(s)
+                })
+            })
+            .ok_or_else(|| ParseChannelError {
+                input: s.to_owned(),
+            })
+    }
+}
+
+/// An error parsing a [`Channel`] from a string that isn't the name of one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseChannelError {
+    input: String,
+}
+
+impl fmt::Display for ParseChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown channel {:?}", self.input)
+    }
+}
+
+impl error::Error for ParseChannelError {}
+
+/// The color that a line of chat is written with, which is what
+/// `WriteChatColor` takes, and what the chat hooks are called with.
+///
+/// This is usually a [`Channel`], which decides the filter that the line
+/// applies under, but it can also be one of the plain colors (like
+/// [`ChatColor::RED`]), which are used for things like con colors.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct ChatColor(i32);
+
+impl ChatColor {
+    /// Dark grey.
+    pub const DARKGREY: ChatColor = ChatColor(1);
+    /// Dark green, which is also the con color for green cons.
+    pub const DARKGREEN: ChatColor = ChatColor(2);
+    /// Dark blue, which is also the con color for dark blue cons.
+    pub const DARKBLUE: ChatColor = ChatColor(4);
+    /// Purple.
+    pub const PURPLE: ChatColor = ChatColor(5);
+    /// Light grey, which is also the con color for grey cons.
+    pub const LIGHTGREY: ChatColor = ChatColor(6);
+    /// White, which is also the con color for even cons.
+    pub const WHITE: ChatColor = ChatColor(10);
+    /// Red, which is also the con color for red cons.
+    pub const RED: ChatColor = ChatColor(13);
+    /// Light green.
+    pub const LIGHTGREEN: ChatColor = ChatColor(14);
+    /// Yellow, which is also the con color for yellow cons.
+    pub const YELLOW: ChatColor = ChatColor(15);
+    /// Light blue, which is also the con color for light blue cons.
+    pub const LIGHTBLUE: ChatColor = ChatColor(18);
+    /// Black.
+    pub const BLACK: ChatColor = ChatColor(20);
+
+    /// The raw value of the chat color.
+    #[must_use]
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+
+    /// The channel of the chat color, or `None` if it isn't one of the known
+    /// channels (such as when it's one of the plain colors).
+    #[must_use]
+    pub fn channel(&self) -> Option<Channel> {
+        Some(Channel::from(self.0))
+            .filter(|channel| !matches!(channel, Channel::Unknown(_)))
+    }
+}
+
+/// The default chat color is [`Channel::Default`], which is what MacroQuest
+/// uses when no color is given.
+impl Default for ChatColor {
+    fn default() -> Self {
+        ChatColor::from(Channel::Default)
+    }
+}
+
+impl From<i32> for ChatColor {
+    fn from(raw: i32) -> Self {
+        ChatColor(raw)
+    }
+}
+
+impl From<ChatColor> for i32 {
+    fn from(color: ChatColor) -> Self {
+        color.0
+    }
+}
+
+impl From<Channel> for ChatColor {
+    fn from(channel: Channel) -> Self {
+        ChatColor(channel.raw())
+    }
+}
+
+/// Chat colors that aren't a known channel convert to [`Channel::Unknown`].
+impl From<ChatColor> for Channel {
+    fn from(color: ChatColor) -> Self {
+        Channel::from(color.0)
+    }
+}
+
+impl PartialEq<Channel> for ChatColor {
+    fn eq(&self, other: &Channel) -> bool {
+        self.0 == other.raw()
+    }
+}
+
+/// The id of a spawn, which is unique among the spawns in the zone, and stays
+/// the same for as long as the spawn exists.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SpawnId(pub u32);
+
+impl SpawnId {
+    /// The id `id`, or `None` if it's 0, which the game uses for no spawn.
+    #[must_use]
+    pub fn new(id: u32) -> Option<SpawnId> {
+        (id != 0).then_some(SpawnId(id))
+    }
+}
+
+impl fmt::Display for SpawnId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[allow(missing_docs)]
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Spawn(ffi::eqlib::PlayerClient);
+
+#[allow(missing_docs)]
+impl Spawn {
+    getter!(name -> &str);
+}
+
+impl Spawn {
+    getter!(
+        /// The x coordinate of the spawn.
+        x -> f32
+    );
+
+    getter!(
+        /// The y coordinate of the spawn.
+        y -> f32
+    );
+
+    getter!(
+        /// The z coordinate of the spawn, which is its height.
+        z -> f32
+    );
+
+    getter!(
+        /// The direction that the spawn is facing, in the game's units, where
+        /// a full turn is 512 (ex: 0 is north, and 128 is west).
+        heading -> f32
+    );
+
+    getter!(
+        /// How fast the spawn is running, which is 0 while it isn't moving.
+        speed_run -> f32
+    );
+
+    getter!(
+        /// How much faster (or slower) than normal the spawn is able to move,
+        /// such as from a movement speed buff, where 1 is normal.
+        speed_multiplier -> f32
+    );
+
+    /// How the spawn is moving (or positioned, if it isn't moving).
+    #[must_use]
+    pub fn movement(&self) -> Movement {
+        Movement::new(self.raw().stand_state(), self.speed_run())
+    }
+
+    /// Where the spawn is.
+    #[must_use]
+    pub fn location(&self) -> Location {
+        Location::new(self.x(), self.y(), self.z())
+    }
+
+    /// The straight line distance between this spawn and `other`.
+    #[must_use]
+    pub fn distance_to(&self, other: &Spawn) -> f32 {
+        self.location().distance(&other.location())
+    }
+
+    /// The distance between this spawn and `other` along the ground, ignoring
+    /// the difference in height.
+    #[must_use]
+    pub fn distance_2d_to(&self, other: &Spawn) -> f32 {
+        self.location().distance_2d(&other.location())
+    }
+
+    /// A snapshot of the spawn's data, which can be held onto after the spawn
+    /// is gone, and sent to other threads.
+    #[must_use]
+    pub fn snapshot(&self) -> SpawnSnapshot {
+        SpawnSnapshot::from(self)
+    }
+
+    getter!(
+        /// The current hit points of the spawn.
+        ///
+        /// The game only tells us the actual hit points of ourselves, our
+        /// group, and our pets, so for any other spawn this is a percentage
+        /// (with [`hp_max`](Spawn::hp_max) being 100).
+        hp_current -> i64
+    );
+
+    getter!(
+        /// The maximum hit points of the spawn.
+        hp_max -> i64
+    );
+
+    getter!(
+        /// The current mana of the spawn, which is only known for ourselves
+        /// and our group (and is 0 otherwise).
+        mana_current -> i32
+    );
+
+    getter!(
+        /// The maximum mana of the spawn, which is 0 for classes without mana.
+        mana_max -> i32
+    );
+
+    getter!(
+        /// The current endurance of the spawn, which is only known for
+        /// ourselves and our group (and is 0 otherwise).
+        endurance_current -> i32
+    );
+
+    getter!(
+        /// The maximum endurance of the spawn.
+        endurance_max -> i32
+    );
+
+    /// The current hit points of the spawn, as a percentage from `0.0` to
+    /// `100.0`, which is `0.0` if the maximum isn't known.
+    #[must_use]
+    pub fn hp_percent(&self) -> f32 {
+        percent(self.hp_current(), self.hp_max())
+    }
+
+    /// The current mana of the spawn, as a percentage from `0.0` to `100.0`,
+    /// which is `0.0` if the spawn has no mana (or it isn't known).
+    #[must_use]
+    pub fn mana_percent(&self) -> f32 {
+        percent(self.mana_current().into(), self.mana_max().into())
+    }
+}
+
+/// `value`, or `None` if it's empty, as the game uses empty strings for names
+/// that a spawn doesn't have.
+fn non_empty(value: &str) -> Option<&str> {
+    let value = value.trim();
+    (!value.is_empty()).then_some(value)
+}
+
+/// `current` as a percentage of `max`, clamped to `0.0` to `100.0`, or `0.0` if
+/// there's no maximum.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn percent(current: i64, max: i64) -> f32 {
+    if max <= 0 {
+        return 0.0;
+    }
+
+    (current as f64 * 100.0 / max as f64).clamp(0.0, 100.0) as f32
+}
+
+impl Spawn {
+    /// The id of the spawn.
+    #[must_use]
+    pub fn id(&self) -> SpawnId {
+        SpawnId(self.raw().spawn_id())
+    }
+
+    /// The id of the spawn that this spawn is the pet of, or `None` if it
+    /// isn't a pet.
+    ///
+    /// The master can be looked up with
+    /// [`mq::spawn_by_id()`](crate::mq::spawn_by_id).
+    #[must_use]
+    pub fn master_id(&self) -> Option<SpawnId> {
+        SpawnId::new(self.raw().master_id())
+    }
+
+    /// The id of the spawn's pet, or `None` if it doesn't have one.
+    ///
+    /// The pet can be looked up with
+    /// [`mq::spawn_by_id()`](crate::mq::spawn_by_id).
+    #[must_use]
+    pub fn pet_id(&self) -> Option<SpawnId> {
+        SpawnId::new(self.raw().pet_id())
+    }
+
+    /// The id of the spell that the spawn is casting, or `None` if it isn't
+    /// casting anything.
+    ///
+    /// The spell can be looked up with
+    /// [`mq::spell_by_id()`](crate::mq::spell_by_id).
+    #[must_use]
+    pub fn casting_spell_id(&self) -> Option<u32> {
+        u32::try_from(self.raw().casting_spell_id())
+            .ok()
+            .filter(|&id| id != 0)
+    }
+
+    /// The type of the spawn (ex: a player, an NPC, a pet, or a corpse).
+    #[must_use]
+    pub fn spawn_type(&self) -> SpawnType {
+        SpawnType::from(self.raw().spawn_type())
+    }
+
+    /// The level of the spawn.
+    #[must_use]
+    pub fn level(&self) -> u32 {
+        u32::try_from(self.raw().level()).unwrap_or(0)
+    }
+
+    /// The name of the guild that the spawn is in, or `None` if it isn't in
+    /// one (or the guild's name isn't known).
+    #[must_use]
+    pub fn guild(&self) -> Option<&str> {
+        non_empty(self.raw().guild())
+    }
+
+    /// The title that the spawn shows before its name (ex: `Sir`), or `None`
+    /// if it doesn't have one.
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        non_empty(self.raw().title())
+    }
+
+    /// The suffix that the spawn shows after its name (ex: `the Brave`), or
+    /// `None` if it doesn't have one.
+    #[must_use]
+    pub fn suffix(&self) -> Option<&str> {
+        non_empty(self.raw().suffix())
+    }
+
+    getter!(
+        /// Whether the spawn is away from keyboard.
+        is_afk -> bool
+    );
+
+    getter!(
+        /// Whether the spawn is looking for a group.
+        is_lfg -> bool
+    );
+
+    getter!(
+        /// Whether the spawn is in trader mode, selling from the bazaar.
+        is_trader -> bool
+    );
+
+    getter!(
+        /// Whether the spawn is in buyer mode, buying in the bazaar.
+        is_buyer -> bool
+    );
+
+    getter!(
+        /// Whether the spawn is linkdead, having lost its connection to the server.
+        is_linkdead -> bool
+    );
+
+    getter!(
+        /// Whether the spawn is feigning death.
+        is_feigned -> bool
+    );
+
+    getter!(
+        /// Whether the spawn is invisible (or hidden).
+        is_invisible -> bool
+    );
+
+    getter!(
+        /// Whether the spawn is riding a mount.
+        is_mounted -> bool
+    );
+
+    /// The states that the spawn is in (ex: AFK, or a trader), as a set of
+    /// flags.
+    #[must_use]
+    pub fn flags(&self) -> SpawnFlags {
+        let mut flags = SpawnFlags::empty();
+        flags.set(SpawnFlags::AFK, self.is_afk());
+        flags.set(SpawnFlags::LFG, self.is_lfg());
+        flags.set(SpawnFlags::TRADER, self.is_trader());
+        flags.set(SpawnFlags::BUYER, self.is_buyer());
+        flags.set(SpawnFlags::LINKDEAD, self.is_linkdead());
+        flags.set(SpawnFlags::FEIGNED, self.is_feigned());
+        flags.set(SpawnFlags::INVISIBLE, self.is_invisible());
+        flags.set(SpawnFlags::MOUNTED, self.is_mounted());
+
+        flags
+    }
+
+    /// The class of the spawn.
+    #[must_use]
+    pub fn class(&self) -> Class {
+        Class::from(self.raw().class_id())
+    }
+
+    /// The race of the spawn.
+    #[must_use]
+    pub fn race(&self) -> Race {
+        Race::from(self.raw().race_id())
+    }
+
+    /// The deity that the spawn worships, which is [`Deity::Agnostic`] for
+    /// players that don't worship one, and [`Deity::Unknown`] (0) for NPCs.
+    #[must_use]
+    pub fn deity(&self) -> Deity {
+        Deity::from(self.raw().deity())
+    }
+
+    /// Whether the spawn is a corpse, of either a player or an NPC.
+    #[must_use]
+    pub fn is_corpse(&self) -> bool {
+        self.spawn_type() == SpawnType::Corpse
+    }
+
+    /// Whether the spawn is the corpse of a player.
+    #[must_use]
+    pub fn is_player_corpse(&self) -> bool {
+        // Only players worship a deity, and their corpses keep it.
+        self.is_corpse() && self.raw().deity() != 0
+    }
+
+    /// Whether the spawn is the corpse of an NPC.
+    #[must_use]
+    pub fn is_npc_corpse(&self) -> bool {
+        self.is_corpse() && self.raw().deity() == 0
+    }
+
+    /// The name of whoever the corpse belongs to (ex: `Bob` for
+    /// `Bob's_corpse0`), or `None` if the spawn isn't a corpse.
+    #[must_use]
+    pub fn corpse_owner_name(&self) -> Option<String> {
+        if !self.is_corpse() {
+            return None;
+        }
+
+        corpse::owner_name(self.name())
+    }
+
+    fn raw(&self) -> &ffi::eqlib::PlayerClient {
+        crate::macros::debug_assert_main_thread!(
+            ::std::any::type_name::<Self>(),
+            "raw"
+        );
+
+        &self.0
+    }
+}
+
+impl AsRef<Spawn> for ffi::eqlib::PlayerClient {
+    fn as_ref(&self) -> &Spawn {
+        Spawn::ref_cast(self)
+    }
+}
+
+impl fmt::Debug for Spawn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Spawn");
+        debug
+            .field("id", &self.id())
+            .field("name", &self.name())
+            .field("spawn_type", &self.spawn_type());
+
+        if let Some(guild) = self.guild() {
+            debug.field("guild", &guild);
+        }
+
+        debug
+            .field("class", &self.class())
+            .field("race", &self.race())
+            .field("x", &self.x())
+            .field("y", &self.y())
+            .field("z", &self.z())
+            .field("heading", &self.heading())
+            .finish()
+    }
+}
+
+/// A snapshot of the data of a [`Spawn`].
+///
+/// Unlike a [`Spawn`], a snapshot can be kept for as long as it's needed, such
+/// as to keep a record of the spawns that have been seen, which can be matched
+/// up with the spawns passed to
+/// [`Hooks::remove_spawn`](crate::plugin::Hooks::remove_spawn) by their
+/// [`id`](SpawnSnapshot::id).
+///
+/// With the `serde` feature, the snapshot can be serialized and deserialized,
+/// with its [`SpawnType`], [`Class`], and [`Race`] as their numeric values, so
+/// that values unknown to this crate aren't lost.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct SpawnSnapshot {
+    /// The id of the spawn.
+    pub id:         SpawnId,
+    /// The name of the spawn.
+    pub name:       String,
+    /// The level of the spawn.
+    pub level:      u32,
+    /// Where the spawn was.
+    pub location:   Location,
+    /// The type of the spawn.
+    pub spawn_type: SpawnType,
+    /// The class of the spawn.
+    pub class:      Class,
+    /// The race of the spawn.
+    pub race:       Race,
+}
+
+impl From<&Spawn> for SpawnSnapshot {
+    fn from(spawn: &Spawn) -> Self {
+        SpawnSnapshot {
+            id:         spawn.id(),
+            name:       spawn.name().to_owned(),
+            level:      spawn.level(),
+            location:   spawn.location(),
+            spawn_type: spawn.spawn_type(),
+            class:      spawn.class(),
+            race:       spawn.race(),
+        }
+    }
+}
+
+impl fmt::Display for SpawnSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+mod macros {
+    macro_rules! getter {
+        ($(#[$meta:meta])* $name:ident -> $rtype:ty) => {
+            $(#[$meta])*
+            #[must_use]
+            pub fn $name(&self) -> $rtype {
+                $crate::macros::debug_assert_main_thread!(
+                    ::std::any::type_name::<Self>(),
+                    stringify!($name)
+                );
+
+                self.0.$name()
+            }
+        };
+    }
+
+    pub(super) use getter;
+}
+
+use macros::getter;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> SpawnSnapshot {
+        SpawnSnapshot {
+            id:         SpawnId(42),
+            name:       "a_rat00".to_owned(),
+            level:      2,
+            location:   Location::new(10.0, -20.5, 3.0),
+            spawn_type: SpawnType::Npc,
+            class:      Class::Warrior,
+            race:       Race::from(36),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_is_send() {
+        fn assert_send<T: Send + Sync + 'static>() {}
+        assert_send::<SpawnSnapshot>();
+    }
+
+    #[test]
+    fn test_snapshot_display() {
+        assert_eq!(snapshot().to_string(), "a_rat00");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_serialize() {
+        let value = serde_json::to_value(snapshot()).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "id": 42,
+                "name": "a_rat00",
+                "level": 2,
+                "location": {"x": 10.0, "y": -20.5, "z": 3.0},
+                "spawn_type": 7,
+                "class": 1,
+                "race": 36,
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_deserialize() {
+        let json = serde_json::to_string(&snapshot()).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<SpawnSnapshot>(&json).unwrap(),
+            snapshot()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_enums_serialize_as_raw() {
+        for state in [GameState::InGame, GameState::Unknown(42)] {
+            let json = serde_json::to_string(&state).unwrap();
+            assert_eq!(json, state.raw().to_string());
+            assert_eq!(serde_json::from_str::<GameState>(&json).unwrap(), state);
+        }
+
+        for channel in [Channel::Tell, Channel::Unknown(9999)] {
+            let json = serde_json::to_string(&channel).unwrap();
+            assert_eq!(json, channel.raw().to_string());
+            assert_eq!(serde_json::from_str::<Channel>(&json).unwrap(), channel);
+        }
+
+        assert_eq!(serde_json::to_string(&ChatColor::RED).unwrap(), "13");
+        assert_eq!(
+            serde_json::from_str::<ChatColor>("13").unwrap(),
+            ChatColor::RED
+        );
+        assert_eq!(serde_json::from_str::<SpawnId>("42").unwrap(), SpawnId(42));
+    }
+
+    #[test]
+    fn test_spawn_id_new() {
+        assert_eq!(SpawnId::new(0), None);
+        assert_eq!(SpawnId::new(1234), Some(SpawnId(1234)));
+    }
+
+    #[test]
+    fn test_non_empty() {
+        assert_eq!(non_empty("Vox's Lair"), Some("Vox's Lair"));
+        assert_eq!(non_empty(" the Brave "), Some("the Brave"));
+        assert_eq!(non_empty(""), None);
+        assert_eq!(non_empty("  "), None);
+    }
+
+    #[test]
+    fn test_percent() {
+        assert!((percent(50, 200) - 25.0).abs() < f32::EPSILON);
+        assert!((percent(200, 200) - 100.0).abs() < f32::EPSILON);
+        assert!((percent(3_000_000_000, 6_000_000_000) - 50.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_percent_out_of_range() {
+        // No maximum (ex: a class without mana) doesn't divide by zero.
+        assert!(percent(0, 0).abs() < f32::EPSILON);
+        assert!(percent(10, 0).abs() < f32::EPSILON);
+        assert!(percent(10, -5).abs() < f32::EPSILON);
+
+        // Hit points drop below zero while dying, and can briefly be over the
+        // maximum when it's lowered.
+        assert!(percent(-20, 100).abs() < f32::EPSILON);
+        assert!((percent(150, 100) - 100.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_game_state_all_is_complete() {
+        let known: Vec<GameState> = (-10..=300)
+            .map(GameState::from)
+            .filter(GameState::is_known)
+            .collect();
+
+        assert_eq!(known, GameState::ALL);
+    }
+
+    #[test]
+    fn test_game_state_raw() {
+        for state in GameState::ALL {
+            assert_eq!(GameState::from(state.raw()), state);
+        }
+
+        assert_eq!(GameState::InGame.raw(), 5);
+        assert_eq!(GameState::PreCharacterSelect.raw(), -1);
+        assert_eq!(GameState::from(42), GameState::Unknown(42));
+        assert_eq!(GameState::from(42).raw(), 42);
+        assert_eq!(i32::from(GameState::from(-42)), -42);
+        assert!(GameState::InGame.is_known());
+        assert!(!GameState::from(42).is_known());
+    }
+
+    #[test]
+    fn test_channel_all_is_complete() {
+        let known: Vec<Channel> = (0..=1000)
+            .map(Channel::from)
+            .filter(|color| !matches!(color, Channel::Unknown(_)))
+            .collect();
+
+        assert_eq!(known, Channel::ALL);
+    }
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn test_channel_values() {
+        // Pinned to MacroQuest's USERCOLOR_* values, offset from 255, so that
+        // any drift shows up here rather than as misclassified chat.
+        let values = [
+            (Channel::Say, 1),
+            (Channel::Tell, 2),
+            (Channel::Group, 3),
+            (Channel::Guild, 4),
+            (Channel::OOC, 5),
+            (Channel::Auction, 6),
+            (Channel::Shout, 7),
+            (Channel::Emote, 8),
+            (Channel::Spells, 9),
+            (Channel::YouHitOther, 10),
+            (Channel::OtherHitsYou, 11),
+            (Channel::YouMissOther, 12),
+            (Channel::OtherMissYou, 13),
+            (Channel::Duels, 14),
+            (Channel::Skills, 15),
+            (Channel::Disciplines, 16),
+            (Channel::Default, 18),
+            (Channel::Faction, 19),
+            (Channel::MerchantOffer, 20),
+            (Channel::MerchantExchange, 21),
+            (Channel::YourDeath, 22),
+            (Channel::OtherDeath, 23),
+            (Channel::OtherHitOther, 24),
+            (Channel::OtherMissOther, 25),
+            (Channel::Who, 26),
+            (Channel::Yell, 27),
+            (Channel::SpellDamage, 28),
+            (Channel::SpellWornOff, 29),
+            (Channel::MoneySplit, 30),
+            (Channel::Loot, 31),
+            (Channel::Random, 32),
+            (Channel::OthersSpells, 33),
+            (Channel::SpellFailure, 34),
+            (Channel::ChatChannel, 35),
+            (Channel::ChatChannel1, 36),
+            (Channel::ChatChannel2, 37),
+            (Channel::ChatChannel3, 38),
+            (Channel::ChatChannel4, 39),
+            (Channel::ChatChannel5, 40),
+            (Channel::ChatChannel6, 41),
+            (Channel::ChatChannel7, 42),
+            (Channel::ChatChannel8, 43),
+            (Channel::ChatChannel9, 44),
+            (Channel::ChatChannel10, 45),
+            (Channel::MeleeCrit, 46),
+            (Channel::SpellCrit, 47),
+            (Channel::TooFarAway, 48),
+            (Channel::NPCRampage, 49),
+            (Channel::NPCFlurry, 50),
+            (Channel::NPCEnrage, 51),
+            (Channel::EchoSay, 52),
+            (Channel::EchoTell, 53),
+            (Channel::EchoGroup, 54),
+            (Channel::EchoGuild, 55),
+            (Channel::EchoOOC, 56),
+            (Channel::EchoAuction, 57),
+            (Channel::EchoShout, 58),
+            (Channel::EchoEmote, 59),
+            (Channel::EchoChatChannel1, 60),
+            (Channel::EchoChatChannel2, 61),
+            (Channel::EchoChatChannel3, 62),
+            (Channel::EchoChatChannel4, 63),
+            (Channel::EchoChatChannel5, 64),
+            (Channel::EchoChatChannel6, 65),
+            (Channel::EchoChatChannel7, 66),
+            (Channel::EchoChatChannel8, 67),
+            (Channel::EchoChatChannel9, 68),
+            (Channel::EchoChatChannel10, 69),
+            (Channel::AvatarCommand, 70),
+            (Channel::Link, 71),
+            (Channel::Raid, 72),
+            (Channel::Pet, 73),
+            (Channel::DamageShield, 74),
+            (Channel::Leader, 75),
+            (Channel::PetRampageFlurry, 76),
+            (Channel::PetCrit, 77),
+            (Channel::Focus, 78),
+            (Channel::Experience, 79),
+            (Channel::System, 80),
+            (Channel::PetSpells, 81),
+            (Channel::PetResponses, 82),
+            (Channel::ItemSpeech, 83),
+            (Channel::Strikethrough, 84),
+            (Channel::Stun, 85),
+            (Channel::SwarmPetDeath, 86),
+            (Channel::Fellowship, 87),
+            (Channel::NPCSpeech, 88),
+            (Channel::NPCSpeechToYou, 89),
+            (Channel::GuildMessage, 90),
+            (Channel::MercenaryGroup, 91),
+            (Channel::Achievement, 92),
+            (Channel::AchievementYou, 93),
+            (Channel::AchievementOthers, 94),
+            (Channel::PvP, 95),
+            (Channel::HotButtonCooldown, 96),
+            (Channel::AggroLow, 97),
+            (Channel::AggroWarning, 98),
+            (Channel::AggroMost, 99),
+            (Channel::DialogLink, 100),
+            (Channel::YouFlurry, 101),
+            (Channel::Debug, 102),
+            (Channel::NPCDeath, 103),
+            (Channel::RandomOther, 104),
+            (Channel::RandomGroup, 105),
+            (Channel::YouFallDamage, 106),
+            (Channel::OtherFallDamage, 107),
+            (Channel::YouDamageSield, 108),
+            (Channel::OtherDamageShield, 109),
+            (Channel::Event, 110),
+            (Channel::DetrimentalSpellOverwritten, 111),
+            (Channel::BeneficialSpellOverwritten, 112),
+            (Channel::CantUseCommand, 113),
+            (Channel::AbilityCooldown, 114),
+            (Channel::AltAbilityCooldown, 115),
+            (Channel::DestroyItem, 116),
+            (Channel::AurasYours, 117),
+            (Channel::AurasOthers, 118),
+            (Channel::HealsYours, 119),
+            (Channel::HealsOthers, 120),
+            (Channel::DoTsYours, 121),
+            (Channel::DoTsOthers, 122),
+            (Channel::PetBardSongs, 123),
+            (Channel::DirectDamageOthers, 124),
+            (Channel::SpellEmotes, 125),
+            (Channel::FactionLink, 126),
+            (Channel::Taunt, 127),
+            (Channel::DisciplinesOthers, 128),
+            (Channel::ItemStatPositive, 129),
+            (Channel::ItemStatNegative, 130),
+            (Channel::EncounterLockAttackable, 131),
+            (Channel::EncounterLockUnattackable, 132),
+            (Channel::FoodAndDrink, 133),
+            (Channel::RaidVictory, 134),
+            (Channel::DirectDamageYours, 142),
+            (Channel::DirectDamageOthersCrit, 143),
+            (Channel::DoTsYoursCrit, 144),
+            (Channel::DoTsOthersCrit, 145),
+            (Channel::DoTsDamageTaken, 146),
+            (Channel::HealsReceived, 147),
+            (Channel::HealsYoursCrit, 148),
+            (Channel::HealsOthersCrit, 149),
+            (Channel::MeleeOthersCrit, 150),
+        ];
+
+        assert_eq!(values.len(), Channel::ALL.len());
+        for (color, offset) in values {
+            assert_eq!(color.raw(), 255 + offset, "{color:?}");
+            assert_eq!(Channel::from(255 + offset), color);
+        }
+    }
+
+    #[test]
+    fn test_channel_unnamed() {
+        // Assigned by MacroQuest, but not named yet.
+        for offset in 135..=141 {
+            assert_eq!(Channel::from(255 + offset), Channel::Unknown(255 + offset));
+        }
+    }
+
+    #[test]
+    fn test_channel_raw() {
+        for color in Channel::ALL {
+            assert_eq!(Channel::from(color.raw()), color);
+        }
+
+        assert_eq!(Channel::Say.raw(), 256);
+        assert_eq!(Channel::default().raw(), 255 + 18);
+        assert_eq!(Channel::from(9999), Channel::Unknown(9999));
+        assert_eq!(Channel::from(9999).raw(), 9999);
+        assert_eq!(i32::from(Channel::from(9999)), 9999);
+        assert_eq!(i32::from(Channel::from(-1)), -1);
+    }
+
+    #[test]
+    fn test_channel_name_round_trip() {
+        for color in Channel::ALL {
+            let name = color.to_string();
+            assert_eq!(name.parse::<Channel>(), Ok(color), "{name}");
+            assert_eq!(name.to_uppercase().parse::<Channel>(), Ok(color), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_channel_ident_round_trip() {
+        for color in Channel::ALL {
+            let ident = color.ident().unwrap();
+            assert_eq!(ident.parse::<Channel>(), Ok(color), "{ident}");
+            assert!(
+                ident
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_'),
+                "{ident}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_channel_parse() {
+        assert_eq!("Say".parse::<Channel>(), Ok(Channel::Say));
+        assert_eq!("Out Of Character".parse::<Channel>(), Ok(Channel::OOC));
+        assert_eq!(" ooc ".parse::<Channel>(), Ok(Channel::OOC));
+        assert_eq!(
+            "Spell Failures".parse::<Channel>(),
+            Ok(Channel::SpellFailure)
+        );
+        assert_eq!(
+            "spell_failure".parse::<Channel>(),
+            Ok(Channel::SpellFailure)
+        );
+        assert!("not a channel".parse::<Channel>().is_err());
+        assert!("".parse::<Channel>().is_err());
+    }
+
+    #[test]
+    fn test_channel_display_unknown() {
+        assert_eq!(Channel::Unknown(400).name(), None);
+        assert_eq!(Channel::Unknown(400).ident(), None);
+        assert_eq!(Channel::Unknown(400).to_string(), "Unknown (400)");
+    }
+
+    #[test]
+    fn test_chat_color_channel() {
+        for channel in Channel::ALL {
+            let color = ChatColor::from(channel);
+            assert_eq!(color.raw(), channel.raw());
+            assert_eq!(color.channel(), Some(channel));
+            assert_eq!(Channel::from(color), channel);
+            assert_eq!(color, channel);
+        }
+
+        assert_eq!(ChatColor::default(), Channel::Default);
+        assert_eq!(ChatColor::RED.channel(), None);
+        assert_eq!(Channel::from(ChatColor::RED), Channel::Unknown(13));
+        assert_eq!(ChatColor::from(13), ChatColor::RED);
+        assert_eq!(i32::from(ChatColor::YELLOW), 15);
+    }
+}
@@ -0,0 +1,108 @@
+//! How spawns are moving, or not.
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+/// How a spawn is moving (or positioned, if it isn't moving), as returned by
+/// [`Spawn::movement()`](super::Spawn::movement).
+///
+/// Apart from [`Movement::Moving`], these are the game's stand states, so the
+/// raw values are those that the game uses. A spawn is only ever
+/// [`Movement::Moving`] while it's standing, as the game doesn't let spawns
+/// move while sitting, ducking, and so on.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+pub enum Movement {
+    /// Standing and moving, which isn't a stand state of the game's, so its
+    /// raw value is only meaningful to this crate.
+    Moving   = 0,
+    /// Standing still.
+    Standing = 100,
+    /// Binding wounds.
+    Binding  = 105,
+    /// Sitting, such as to meditate.
+    Sitting  = 110,
+    /// Ducking.
+    Ducking  = 111,
+    /// Feigning death.
+    Feigning = 115,
+    /// Dead.
+    Dead     = 120,
+
+    /// A stand state unknown to this crate.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+impl Movement {
+    /// Every known movement, in id order.
+    pub const ALL: [Movement; 7] = [
+        Movement::Moving,
+        Movement::Standing,
+        Movement::Binding,
+        Movement::Sitting,
+        Movement::Ducking,
+        Movement::Feigning,
+        Movement::Dead,
+    ];
+
+    /// The movement of a spawn in the stand state `stand_state`, that's
+    /// running at the speed `speed_run`.
+    pub(crate) fn new(stand_state: u8, speed_run: f32) -> Movement {
+        match Movement::from(i32::from(stand_state)) {
+            Movement::Standing if speed_run != 0.0 => Movement::Moving,
+            movement => movement,
+        }
+    }
+
+    /// The raw value of the movement, including for [`Movement::Unknown`].
+    #[must_use]
+    pub fn raw(&self) -> i32 {
+        i32::from(*self)
+    }
+
+    /// Whether the movement is one known to this crate, rather than
+    /// [`Movement::Unknown`].
+    #[must_use]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Movement::Unknown(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_is_complete() {
+        let known: Vec<Movement> = (-10..=300)
+            .map(Movement::from)
+            .filter(Movement::is_known)
+            .collect();
+
+        assert_eq!(known, Movement::ALL);
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Movement::new(100, 0.0), Movement::Standing);
+        assert_eq!(Movement::new(100, 0.7), Movement::Moving);
+        assert_eq!(Movement::new(100, -0.3), Movement::Moving);
+        assert_eq!(Movement::new(110, 0.0), Movement::Sitting);
+        assert_eq!(Movement::new(115, 0.0), Movement::Feigning);
+        assert_eq!(Movement::new(120, 0.0), Movement::Dead);
+        assert_eq!(Movement::new(7, 0.0), Movement::Unknown(7));
+    }
+
+    #[test]
+    fn test_only_moving_while_standing() {
+        // Sliding while dead, or knocked back while feigning, still isn't
+        // moving.
+        assert_eq!(Movement::new(120, 0.5), Movement::Dead);
+        assert_eq!(Movement::new(115, 0.5), Movement::Feigning);
+    }
+}
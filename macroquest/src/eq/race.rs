@@ -0,0 +1,349 @@
+//! The races of spawns.
+//!
+//! The race ids are those used by the game client, as listed in the
+//! `common/races.h` header of the `EQEmu` server. Every playable race is named,
+//! along with a selection of the NPC races that are commonly seen, while the
+//! thousands of other NPC race ids are kept as [`Race::Unknown`].
+//!
+//! Some races have been given new ids when their models were revamped, and
+//! only the current ids of the playable races are playable. In particular,
+//! playable Frogloks are [`Race::Froglok`] (330), while [`Race::FroglokNpc`]
+//! (26) is the older froglok model, which is only used by NPCs.
+
+use std::fmt;
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+/// The race of a spawn.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+pub enum Race {
+    /// Human
+    Human        = 1,
+    /// Barbarian
+    Barbarian    = 2,
+    /// Erudite
+    Erudite      = 3,
+    /// Wood Elf
+    WoodElf      = 4,
+    /// High Elf
+    HighElf      = 5,
+    /// Dark Elf
+    DarkElf      = 6,
+    /// Half Elf
+    HalfElf      = 7,
+    /// Dwarf
+    Dwarf        = 8,
+    /// Troll
+    Troll        = 9,
+    /// Ogre
+    Ogre         = 10,
+    /// Halfling
+    Halfling     = 11,
+    /// Gnome
+    Gnome        = 12,
+    /// Aviak (NPC only)
+    Aviak        = 13,
+    /// Werewolf (NPC only)
+    Werewolf     = 14,
+    /// Brownie (NPC only)
+    Brownie      = 15,
+    /// Centaur (NPC only)
+    Centaur      = 16,
+    /// Golem (NPC only)
+    Golem        = 17,
+    /// Giant (NPC only)
+    Giant        = 18,
+    /// Beetle (NPC only)
+    Beetle       = 22,
+    /// Fish (NPC only)
+    Fish         = 24,
+    /// Fairy (NPC only)
+    Fairy        = 25,
+    /// Froglok (NPC only), the older froglok model that isn't playable
+    FroglokNpc   = 26,
+    /// Gargoyle (NPC only)
+    Gargoyle     = 29,
+    /// Ghoul (NPC only)
+    Ghoul        = 33,
+    /// Bat (NPC only)
+    Bat          = 34,
+    /// Rat (NPC only)
+    Rat          = 36,
+    /// Snake (NPC only)
+    Snake        = 37,
+    /// Spider (NPC only)
+    Spider       = 38,
+    /// Gnoll (NPC only)
+    Gnoll        = 39,
+    /// Goblin (NPC only)
+    Goblin       = 40,
+    /// Gorilla (NPC only)
+    Gorilla      = 41,
+    /// Wolf (NPC only)
+    Wolf         = 42,
+    /// Bear (NPC only)
+    Bear         = 43,
+    /// Imp (NPC only)
+    Imp          = 46,
+    /// Griffin (NPC only)
+    Griffin      = 47,
+    /// Kobold (NPC only)
+    Kobold       = 48,
+    /// Dragon (NPC only)
+    Dragon       = 49,
+    /// Lion (NPC only)
+    Lion         = 50,
+    /// Lizard Man (NPC only)
+    LizardMan    = 51,
+    /// Minotaur (NPC only)
+    Minotaur     = 53,
+    /// Orc (NPC only)
+    Orc          = 54,
+    /// Pixie (NPC only)
+    Pixie        = 56,
+    /// Skeleton (NPC only)
+    Skeleton     = 60,
+    /// Tiger (NPC only)
+    Tiger        = 63,
+    /// Treant (NPC only)
+    Treant       = 64,
+    /// Vampire (NPC only)
+    Vampire      = 65,
+    /// Wisp (NPC only)
+    Wisp         = 69,
+    /// Zombie (NPC only)
+    Zombie       = 70,
+    /// Elemental (NPC only)
+    Elemental    = 75,
+    /// Puma (NPC only)
+    Puma         = 76,
+    /// Spectre (NPC only)
+    Spectre      = 85,
+    /// Drake (NPC only)
+    Drake        = 89,
+    /// Invisible Man (NPC only)
+    InvisibleMan = 127,
+    /// Iksar
+    Iksar        = 128,
+    /// Vah Shir
+    VahShir      = 130,
+    /// Horse (NPC only)
+    Horse        = 216,
+    /// Froglok
+    Froglok      = 330,
+    /// Drakkin
+    Drakkin      = 522,
+
+    /// A race unknown to this crate, which includes most NPC races.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+impl Race {
+    /// Whether the race is one that players can be.
+    #[must_use]
+    pub fn is_playable(self) -> bool {
+        self.names().1.is_some()
+    }
+
+    /// The name of the race (ex: `Dark Elf`).
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        self.names().0
+    }
+
+    /// The short name of the race (ex: `DEF`), as used by the game and
+    /// MacroQuest.
+    ///
+    /// Only the playable races have short names, so this is the same as
+    /// [`Race::name()`] for any other race.
+    #[must_use]
+    pub fn short_name(self) -> &'static str {
+        let (name, short_name) = self.names();
+        short_name.unwrap_or(name)
+    }
+
+    fn names(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Race::Human => ("Human", Some("HUM")),
+            Race::Barbarian => ("Barbarian", Some("BAR")),
+            Race::Erudite => ("Erudite", Some("ERU")),
+            Race::WoodElf => ("Wood Elf", Some("ELF")),
+            Race::HighElf => ("High Elf", Some("HIE")),
+            Race::DarkElf => ("Dark Elf", Some("DEF")),
+            Race::HalfElf => ("Half Elf", Some("HEF")),
+            Race::Dwarf => ("Dwarf", Some("DWF")),
+            Race::Troll => ("Troll", Some("TRL")),
+            Race::Ogre => ("Ogre", Some("OGR")),
+            Race::Halfling => ("Halfling", Some("HFL")),
+            Race::Gnome => ("Gnome", Some("GNM")),
+            Race::Aviak => ("Aviak", None),
+            Race::Werewolf => ("Werewolf", None),
+            Race::Brownie => ("Brownie", None),
+            Race::Centaur => ("Centaur", None),
+            Race::Golem => ("Golem", None),
+            Race::Giant => ("Giant", None),
+            Race::Beetle => ("Beetle", None),
+            Race::Fish => ("Fish", None),
+            Race::Fairy => ("Fairy", None),
+            Race::FroglokNpc => ("Froglok", None),
+            Race::Gargoyle => ("Gargoyle", None),
+            Race::Ghoul => ("Ghoul", None),
+            Race::Bat => ("Bat", None),
+            Race::Rat => ("Rat", None),
+            Race::Snake => ("Snake", None),
+            Race::Spider => ("Spider", None),
+            Race::Gnoll => ("Gnoll", None),
+            Race::Goblin => ("Goblin", None),
+            Race::Gorilla => ("Gorilla", None),
+            Race::Wolf => ("Wolf", None),
+            Race::Bear => ("Bear", None),
+            Race::Imp => ("Imp", None),
+            Race::Griffin => ("Griffin", None),
+            Race::Kobold => ("Kobold", None),
+            Race::Dragon => ("Dragon", None),
+            Race::Lion => ("Lion", None),
+            Race::LizardMan => ("Lizard Man", None),
+            Race::Minotaur => ("Minotaur", None),
+            Race::Orc => ("Orc", None),
+            Race::Pixie => ("Pixie", None),
+            Race::Skeleton => ("Skeleton", None),
+            Race::Tiger => ("Tiger", None),
+            Race::Treant => ("Treant", None),
+            Race::Vampire => ("Vampire", None),
+            Race::Wisp => ("Wisp", None),
+            Race::Zombie => ("Zombie", None),
+            Race::Elemental => ("Elemental", None),
+            Race::Puma => ("Puma", None),
+            Race::Spectre => ("Spectre", None),
+            Race::Drake => ("Drake", None),
+            Race::InvisibleMan => ("Invisible Man", None),
+            Race::Iksar => ("Iksar", Some("IKS")),
+            Race::VahShir => ("Vah Shir", Some("VAH")),
+            Race::Horse => ("Horse", None),
+            Race::Froglok => ("Froglok", Some("FRG")),
+            Race::Drakkin => ("Drakkin", Some("DRK")),
+            Race::Unknown(_) => ("Unknown", None),
+        }
+    }
+}
+
+impl fmt::Display for Race {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every known race, in id order.
+    const RACES: [Race; 58] = [
+        Race::Human,
+        Race::Barbarian,
+        Race::Erudite,
+        Race::WoodElf,
+        Race::HighElf,
+        Race::DarkElf,
+        Race::HalfElf,
+        Race::Dwarf,
+        Race::Troll,
+        Race::Ogre,
+        Race::Halfling,
+        Race::Gnome,
+        Race::Aviak,
+        Race::Werewolf,
+        Race::Brownie,
+        Race::Centaur,
+        Race::Golem,
+        Race::Giant,
+        Race::Beetle,
+        Race::Fish,
+        Race::Fairy,
+        Race::FroglokNpc,
+        Race::Gargoyle,
+        Race::Ghoul,
+        Race::Bat,
+        Race::Rat,
+        Race::Snake,
+        Race::Spider,
+        Race::Gnoll,
+        Race::Goblin,
+        Race::Gorilla,
+        Race::Wolf,
+        Race::Bear,
+        Race::Imp,
+        Race::Griffin,
+        Race::Kobold,
+        Race::Dragon,
+        Race::Lion,
+        Race::LizardMan,
+        Race::Minotaur,
+        Race::Orc,
+        Race::Pixie,
+        Race::Skeleton,
+        Race::Tiger,
+        Race::Treant,
+        Race::Vampire,
+        Race::Wisp,
+        Race::Zombie,
+        Race::Elemental,
+        Race::Puma,
+        Race::Spectre,
+        Race::Drake,
+        Race::InvisibleMan,
+        Race::Iksar,
+        Race::VahShir,
+        Race::Horse,
+        Race::Froglok,
+        Race::Drakkin,
+    ];
+
+    #[test]
+    fn test_races_is_complete() {
+        let known: Vec<Race> = (-10..=1000)
+            .map(Race::from)
+            .filter(|race| !matches!(race, Race::Unknown(_)))
+            .collect();
+
+        assert_eq!(known, RACES);
+    }
+
+    #[test]
+    fn test_playable() {
+        let playable: Vec<i32> = RACES
+            .into_iter()
+            .filter(|race| race.is_playable())
+            .map(i32::from)
+            .collect();
+
+        assert_eq!(
+            playable,
+            vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 128, 130, 330, 522]
+        );
+        assert!(!Race::FroglokNpc.is_playable());
+        assert!(!Race::Unknown(4000).is_playable());
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(Race::DarkElf.to_string(), "Dark Elf");
+        assert_eq!(Race::DarkElf.short_name(), "DEF");
+        assert_eq!(Race::VahShir.to_string(), "Vah Shir");
+        assert_eq!(Race::Froglok.to_string(), Race::FroglokNpc.to_string());
+        assert_eq!(Race::LizardMan.short_name(), "Lizard Man");
+        assert_eq!(Race::Unknown(4000).to_string(), "Unknown");
+
+        for race in RACES {
+            assert!(!race.name().is_empty());
+            assert!(!race.short_name().is_empty());
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! The local character's raid.
+
+use std::fmt;
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+use super::Class;
+use crate::ffi;
+
+/// A snapshot of the local character's raid.
+///
+/// The snapshot is taken with [`mq::raid()`](crate::mq::raid), and doesn't
+/// change after it's been taken, so it can be held onto and used from any
+/// thread.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Raid {
+    /// The members of the raid, including the local character.
+    pub members:   Vec<RaidMember>,
+    /// Who is allowed to loot in the raid.
+    pub loot_type: LootType,
+}
+
+impl Raid {
+    /// Take a snapshot of the raid, returning `None` if the local character
+    /// isn't in one.
+    pub(crate) fn load() -> Option<Raid> {
+        let mut info = ffi::eqlib::RaidInfo::default();
+        if !ffi::eqlib::get_raid(&mut info) {
+            return None;
+        }
+
+        let members = (0..ffi::eqlib::raid_member_count())
+            .filter_map(|index| {
+                let mut member = ffi::eqlib::RaidMemberInfo::default();
+                ffi::eqlib::get_raid_member(index, &mut member)
+                    .then(|| RaidMember::from(member))
+            })
+            .collect();
+
+        Raid::new(members, LootType::from(info.loot_type))
+    }
+
+    /// Create a [`Raid`], skipping any empty member slots, and returning
+    /// `None` if there aren't any members left.
+    fn new(mut members: Vec<RaidMember>, loot_type: LootType) -> Option<Raid> {
+        members.retain(|member| !member.name.is_empty());
+
+        (!members.is_empty()).then_some(Raid { members, loot_type })
+    }
+
+    /// How many members the raid has, including the local character.
+    #[must_use]
+    pub fn member_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// The leader of the raid, or `None` if the leader has left it.
+    #[must_use]
+    pub fn leader(&self) -> Option<&RaidMember> {
+        self.members.iter().find(|member| member.raid_leader)
+    }
+
+    /// Find the member named `name`, ignoring case.
+    #[must_use]
+    pub fn member(&self, name: &str) -> Option<&RaidMember> {
+        self.members
+            .iter()
+            .find(|member| member.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterate over the members of raid group `group` (counting from 1).
+    pub fn group(&self, group: u8) -> impl Iterator<Item = &RaidMember> {
+        self.members
+            .iter()
+            .filter(move |member| member.group == Some(group))
+    }
+
+    /// Iterate over the members that aren't in any of the raid's groups.
+    pub fn ungrouped(&self) -> impl Iterator<Item = &RaidMember> {
+        self.members.iter().filter(|member| member.group.is_none())
+    }
+
+    /// Iterate over the raid's main assists.
+    pub fn main_assists(&self) -> impl Iterator<Item = &RaidMember> {
+        self.members.iter().filter(|member| member.main_assist)
+    }
+}
+
+/// A member of a [`Raid`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RaidMember {
+    /// The name of the member.
+    pub name:         String,
+    /// The class of the member.
+    pub class:        Class,
+    /// The level of the member.
+    pub level:        u32,
+    /// The raid group that the member is in (counting from 1), or `None` if
+    /// they're not in one.
+    pub group:        Option<u8>,
+    /// Whether the member is the leader of the raid.
+    pub raid_leader:  bool,
+    /// Whether the member is the leader of their raid group.
+    pub group_leader: bool,
+    /// Whether the member is one of the raid's main assists.
+    pub main_assist:  bool,
+}
+
+impl From<ffi::eqlib::RaidMemberInfo> for RaidMember {
+    fn from(info: ffi::eqlib::RaidMemberInfo) -> Self {
+        RaidMember {
+            name:         info.name.trim().to_owned(),
+            class:        Class::from(info.class_id),
+            level:        info.level,
+            // The game counts the groups from 0, with -1 for ungrouped members.
+            group:        u8::try_from(info.group)
+                .ok()
+                .and_then(|group| group.checked_add(1)),
+            raid_leader:  info.raid_leader,
+            group_leader: info.group_leader,
+            main_assist:  info.main_assist,
+        }
+    }
+}
+
+impl fmt::Display for RaidMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({} {})",
+            self.name,
+            self.level,
+            self.class.short_name()
+        )
+    }
+}
+
+/// Who is allowed to loot in a [`Raid`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum LootType {
+    /// Only the raid leader.
+    RaidLeader   = 1,
+    /// The raid leader and the group leaders.
+    GroupLeaders = 2,
+    /// The raid leader and the members that they've selected.
+    Selected     = 3,
+    /// Every member of the raid.
+    EntireRaid   = 4,
+
+    /// A loot type unknown to this crate.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+impl LootType {
+    /// Every known loot type, in id order.
+    pub const ALL: [LootType; 4] = [
+        LootType::RaidLeader,
+        LootType::GroupLeaders,
+        LootType::Selected,
+        LootType::EntireRaid,
+    ];
+
+    /// The raw value of the loot type, including for [`LootType::Unknown`].
+    #[must_use]
+    pub fn raw(&self) -> i32 {
+        i32::from(*self)
+    }
+
+    /// Whether the loot type is one known to this crate, rather than
+    /// [`LootType::Unknown`].
+    #[must_use]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, LootType::Unknown(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, group: i32, raid_leader: bool) -> RaidMember {
+        RaidMember::from(ffi::eqlib::RaidMemberInfo {
+            name: name.to_owned(),
+            class_id: Class::Warrior.into(),
+            level: 70,
+            group,
+            raid_leader,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_not_in_raid() {
+        assert_eq!(Raid::new(vec![], LootType::EntireRaid), None);
+        assert_eq!(
+            Raid::new(vec![member("", -1, false)], LootType::EntireRaid),
+            None
+        );
+    }
+
+    #[test]
+    fn test_skips_empty_slots() {
+        let raid = Raid::new(
+            vec![
+                member("Alice", 0, true),
+                member("", -1, false),
+                member("Bob", 1, false),
+            ],
+            LootType::RaidLeader,
+        )
+        .unwrap();
+
+        assert_eq!(raid.member_count(), 2);
+        assert_eq!(raid.leader().map(|m| m.name.as_str()), Some("Alice"));
+        assert_eq!(raid.member("bob"), Some(&member("Bob", 1, false)));
+        assert_eq!(raid.member("Carol"), None);
+    }
+
+    #[test]
+    fn test_groups() {
+        let raid = Raid::new(
+            vec![
+                member("Alice", 0, true),
+                member("Bob", 0, false),
+                member("Carol", 11, false),
+                member("Dave", -1, false),
+            ],
+            LootType::EntireRaid,
+        )
+        .unwrap();
+
+        assert_eq!(raid.member("Alice").unwrap().group, Some(1));
+        assert_eq!(raid.member("Carol").unwrap().group, Some(12));
+        assert_eq!(raid.group(1).count(), 2);
+        assert_eq!(raid.group(12).count(), 1);
+        assert_eq!(raid.group(2).count(), 0);
+
+        let ungrouped: Vec<&str> = raid.ungrouped().map(|m| m.name.as_str()).collect();
+        assert_eq!(ungrouped, ["Dave"]);
+    }
+
+    #[test]
+    fn test_loot_type_all_is_complete() {
+        let known: Vec<LootType> = (-10..=300)
+            .map(LootType::from)
+            .filter(LootType::is_known)
+            .collect();
+
+        assert_eq!(known, LootType::ALL);
+    }
+
+    #[test]
+    fn test_loot_type_raw() {
+        for loot_type in LootType::ALL {
+            assert_eq!(LootType::from(loot_type.raw()), loot_type);
+        }
+
+        assert_eq!(LootType::from(0), LootType::Unknown(0));
+        assert_eq!(LootType::Unknown(0).raw(), 0);
+    }
+}
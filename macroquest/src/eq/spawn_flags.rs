@@ -0,0 +1,195 @@
+//! The states that a spawn can be in, as flags.
+
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// A set of the states that a spawn is in (ex: AFK, or a trader), as returned
+/// by [`Spawn::flags()`](super::Spawn::flags).
+///
+/// Flags are combined with `|`, and checked with
+/// [`contains`](SpawnFlags::contains).
+///
+/// ```
+/// use macroquest::eq::SpawnFlags;
+///
+/// let flags = SpawnFlags::AFK | SpawnFlags::LFG;
+/// assert!(flags.contains(SpawnFlags::AFK));
+/// assert!(!flags.contains(SpawnFlags::AFK | SpawnFlags::TRADER));
+/// assert_eq!(format!("{flags:?}"), "SpawnFlags(AFK | LFG)");
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct SpawnFlags(u16);
+
+impl SpawnFlags {
+    /// The spawn is away from keyboard.
+    pub const AFK: SpawnFlags = SpawnFlags(1 << 0);
+    /// The spawn is looking for a group.
+    pub const LFG: SpawnFlags = SpawnFlags(1 << 1);
+    /// The spawn is in trader mode, selling from the bazaar.
+    pub const TRADER: SpawnFlags = SpawnFlags(1 << 2);
+    /// The spawn is in buyer mode, buying in the bazaar.
+    pub const BUYER: SpawnFlags = SpawnFlags(1 << 3);
+    /// The spawn has lost its connection to the server, but is still in the
+    /// zone.
+    pub const LINKDEAD: SpawnFlags = SpawnFlags(1 << 4);
+    /// The spawn is feigning death.
+    pub const FEIGNED: SpawnFlags = SpawnFlags(1 << 5);
+    /// The spawn is invisible (or hidden), to at least some spawns.
+    pub const INVISIBLE: SpawnFlags = SpawnFlags(1 << 6);
+    /// The spawn is riding a mount.
+    pub const MOUNTED: SpawnFlags = SpawnFlags(1 << 7);
+
+    // Every flag, along with its name, in bit order.
+    const NAMED: [(SpawnFlags, &'static str); 8] = [
+        (SpawnFlags::AFK, "AFK"),
+        (SpawnFlags::LFG, "LFG"),
+        (SpawnFlags::TRADER, "TRADER"),
+        (SpawnFlags::BUYER, "BUYER"),
+        (SpawnFlags::LINKDEAD, "LINKDEAD"),
+        (SpawnFlags::FEIGNED, "FEIGNED"),
+        (SpawnFlags::INVISIBLE, "INVISIBLE"),
+        (SpawnFlags::MOUNTED, "MOUNTED"),
+    ];
+
+    /// No flags at all.
+    #[must_use]
+    pub const fn empty() -> SpawnFlags {
+        SpawnFlags(0)
+    }
+
+    /// The raw bits of the flags.
+    #[must_use]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Whether no flags are set.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether every flag in `other` is set.
+    #[must_use]
+    pub const fn contains(self, other: SpawnFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Set the flags in `other` if `value` is true, or clear them otherwise.
+    pub fn set(&mut self, other: SpawnFlags, value: bool) {
+        if value {
+            self.0 |= other.0;
+        }
+        else {
+            self.0 &= !other.0;
+        }
+    }
+
+    /// Iterate over the names of the flags that are set, in bit order (ex:
+    /// `AFK`).
+    pub fn names(self) -> impl Iterator<Item = &'static str> {
+        SpawnFlags::NAMED
+            .into_iter()
+            .filter(move |(flag, _)| self.contains(*flag))
+            .map(|(_, name)| name)
+    }
+}
+
+impl BitOr for SpawnFlags {
+    type Output = SpawnFlags;
+
+    fn bitor(self, other: SpawnFlags) -> SpawnFlags {
+        SpawnFlags(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for SpawnFlags {
+    fn bitor_assign(&mut self, other: SpawnFlags) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for SpawnFlags {
+    type Output = SpawnFlags;
+
+    fn bitand(self, other: SpawnFlags) -> SpawnFlags {
+        SpawnFlags(self.0 & other.0)
+    }
+}
+
+impl fmt::Debug for SpawnFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SpawnFlags(")?;
+
+        if self.is_empty() {
+            f.write_str("empty")?;
+        }
+
+        for (index, name) in self.names().enumerate() {
+            if index > 0 {
+                f.write_str(" | ")?;
+            }
+            f.write_str(name)?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_are_distinct() {
+        let mut all = SpawnFlags::empty();
+        for (flag, _) in SpawnFlags::NAMED {
+            assert_eq!(flag.bits().count_ones(), 1);
+            assert!(!all.contains(flag));
+            all |= flag;
+        }
+
+        assert_eq!(all.bits().count_ones(), 8);
+    }
+
+    #[test]
+    fn test_contains() {
+        let flags = SpawnFlags::TRADER | SpawnFlags::LINKDEAD;
+
+        assert!(flags.contains(SpawnFlags::TRADER));
+        assert!(flags.contains(SpawnFlags::TRADER | SpawnFlags::LINKDEAD));
+        assert!(!flags.contains(SpawnFlags::BUYER));
+        assert!(!flags.contains(SpawnFlags::TRADER | SpawnFlags::BUYER));
+        assert!(flags.contains(SpawnFlags::empty()));
+        assert_eq!(flags & SpawnFlags::TRADER, SpawnFlags::TRADER);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut flags = SpawnFlags::default();
+        assert!(flags.is_empty());
+
+        flags.set(SpawnFlags::FEIGNED, true);
+        flags.set(SpawnFlags::MOUNTED, false);
+        assert_eq!(flags, SpawnFlags::FEIGNED);
+
+        flags.set(SpawnFlags::FEIGNED, false);
+        assert!(flags.is_empty());
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", SpawnFlags::empty()), "SpawnFlags(empty)");
+        assert_eq!(
+            format!("{:?}", SpawnFlags::INVISIBLE),
+            "SpawnFlags(INVISIBLE)"
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                SpawnFlags::MOUNTED | SpawnFlags::AFK | SpawnFlags::BUYER
+            ),
+            "SpawnFlags(AFK | BUYER | MOUNTED)"
+        );
+    }
+}
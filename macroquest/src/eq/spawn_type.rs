@@ -0,0 +1,120 @@
+//! The types of spawns, as MacroQuest classifies them.
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+/// The type of a spawn, as classified by MacroQuest's `GetSpawnType` (which
+/// is what the `${Spawn[].Type}` TLO member and spawn searches use).
+///
+/// The game itself only knows whether a spawn is a player, an NPC, or a
+/// corpse, so the rest of the types are worked out by MacroQuest from the
+/// spawn's body type, owner, and so on.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum SpawnType {
+    /// A player character.
+    Pc           = 1,
+    /// A mount that's being ridden.
+    Mount        = 2,
+    /// A pet, of either a player or an NPC.
+    Pet          = 3,
+    /// An NPC.
+    Npc          = 7,
+    /// A corpse, of either a player or an NPC.
+    Corpse       = 8,
+    /// An invisible NPC that triggers an event when approached.
+    Trigger      = 9,
+    /// A trap.
+    Trap         = 10,
+    /// An invisible NPC that the zone uses as a timer.
+    Timer        = 11,
+    /// An NPC that can't be targeted.
+    Untargetable = 12,
+    /// A treasure chest.
+    Chest        = 13,
+    /// Anything that isn't a player, an NPC, or a corpse.
+    Item         = 14,
+    /// An aura.
+    Aura         = 15,
+    /// An object, such as a totem or a siege weapon.
+    Object       = 16,
+    /// A guild banner.
+    Banner       = 17,
+    /// A fellowship campfire.
+    Campfire     = 18,
+    /// A mercenary.
+    Mercenary    = 19,
+    /// A flying NPC that can't be reached, such as a bird.
+    Flyer        = 20,
+
+    /// A spawn type unknown to this crate.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+impl SpawnType {
+    /// Every known spawn type, in id order.
+    pub const ALL: [SpawnType; 17] = [
+        SpawnType::Pc,
+        SpawnType::Mount,
+        SpawnType::Pet,
+        SpawnType::Npc,
+        SpawnType::Corpse,
+        SpawnType::Trigger,
+        SpawnType::Trap,
+        SpawnType::Timer,
+        SpawnType::Untargetable,
+        SpawnType::Chest,
+        SpawnType::Item,
+        SpawnType::Aura,
+        SpawnType::Object,
+        SpawnType::Banner,
+        SpawnType::Campfire,
+        SpawnType::Mercenary,
+        SpawnType::Flyer,
+    ];
+
+    /// The raw value of the spawn type, including for
+    /// [`SpawnType::Unknown`].
+    #[must_use]
+    pub fn raw(&self) -> i32 {
+        i32::from(*self)
+    }
+
+    /// Whether the spawn type is one known to this crate, rather than
+    /// [`SpawnType::Unknown`].
+    #[must_use]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, SpawnType::Unknown(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_is_complete() {
+        let known: Vec<SpawnType> = (-10..=2100)
+            .map(SpawnType::from)
+            .filter(SpawnType::is_known)
+            .collect();
+
+        assert_eq!(known, SpawnType::ALL);
+    }
+
+    #[test]
+    fn test_raw() {
+        for spawn_type in SpawnType::ALL {
+            assert_eq!(SpawnType::from(spawn_type.raw()), spawn_type);
+        }
+
+        assert_eq!(SpawnType::from(4), SpawnType::Unknown(4));
+        assert_eq!(SpawnType::Unknown(4).raw(), 4);
+    }
+}
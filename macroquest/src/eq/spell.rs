@@ -0,0 +1,410 @@
+//! Spells, as defined in the game's spell database.
+
+use std::fmt;
+use std::time::Duration;
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+use ref_cast::RefCast;
+
+use super::macros::getter;
+use super::Class;
+use crate::ffi;
+
+// The spell types that are beneficial, rather than detrimental.
+const BENEFICIAL_SPELL_TYPES: [u8; 2] = [1, 2];
+
+// The target type of spells that only target the caster.
+const TARGET_SELF: u8 = 6;
+
+/// A spell from the game's spell database.
+///
+/// Spells are looked up with [`mq::spell_by_id()`](crate::mq::spell_by_id),
+/// and can only be used on the game's main thread. Use
+/// [`Spell::snapshot()`] to hold onto one elsewhere.
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Spell(ffi::eqlib::EqSpell);
+
+impl Spell {
+    getter!(
+        /// The id of the spell.
+        id -> u32
+    );
+
+    getter!(
+        /// The name of the spell.
+        name -> &str
+    );
+
+    getter!(
+        /// The range of the spell.
+        range -> f32
+    );
+
+    /// The mana that casting the spell costs.
+    #[must_use]
+    pub fn mana_cost(&self) -> u32 {
+        u32::try_from(self.raw().mana_cost()).unwrap_or(0)
+    }
+
+    /// How long the spell takes to cast.
+    #[must_use]
+    pub fn cast_time(&self) -> Duration {
+        Duration::from_millis(self.raw().cast_time_ms().into())
+    }
+
+    /// How long after casting the spell until it can be cast again.
+    #[must_use]
+    pub fn recast_time(&self) -> Duration {
+        Duration::from_millis(self.raw().recast_time_ms().into())
+    }
+
+    /// How many ticks (of 6 seconds) the spell lasts for when cast by a caster
+    /// of level `at_level`, or 0 if it isn't a spell that lasts.
+    #[must_use]
+    pub fn duration_ticks(&self, at_level: u8) -> u32 {
+        duration_ticks(
+            self.raw().duration_formula(),
+            self.raw().duration_cap(),
+            at_level,
+        )
+    }
+
+    /// The level that `class` can first cast the spell at, or `None` if the
+    /// class can't cast it.
+    #[must_use]
+    pub fn level_for_class(&self, class: Class) -> Option<u8> {
+        class_level(self.raw().class_level(class.into()))
+    }
+
+    /// Whether the spell is beneficial, rather than detrimental.
+    #[must_use]
+    pub fn is_beneficial(&self) -> bool {
+        is_beneficial(self.raw().spell_type())
+    }
+
+    /// Whether the spell can only target the caster.
+    #[must_use]
+    pub fn targets_self(&self) -> bool {
+        targets_self(self.raw().target_type())
+    }
+
+    /// The effects (SPAs) of the spell, in slot order.
+    pub fn effects(&self) -> impl Iterator<Item = SpellEffect> + '_ {
+        (0..self.raw().effect_count()).filter_map(|index| {
+            let mut effect = ffi::eqlib::SpellEffect::default();
+            self.raw()
+                .effect(index, &mut effect)
+                .then(|| SpellEffect::from(effect))
+        })
+    }
+
+    /// A snapshot of the spell's data, which can be sent to other threads.
+    #[must_use]
+    pub fn snapshot(&self) -> SpellSnapshot {
+        SpellSnapshot::from(self)
+    }
+
+    fn raw(&self) -> &ffi::eqlib::EqSpell {
+        crate::macros::debug_assert_main_thread!(
+            ::std::any::type_name::<Self>(),
+            "raw"
+        );
+
+        &self.0
+    }
+}
+
+impl AsRef<Spell> for ffi::eqlib::EqSpell {
+    fn as_ref(&self) -> &Spell {
+        Spell::ref_cast(self)
+    }
+}
+
+impl fmt::Debug for Spell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Spell")
+            .field("id", &self.id())
+            .field("name", &self.name())
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Spell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A snapshot of the data of a [`Spell`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct SpellSnapshot {
+    /// The id of the spell.
+    pub id:               u32,
+    /// The name of the spell.
+    pub name:             String,
+    /// The mana that casting the spell costs.
+    pub mana_cost:        u32,
+    /// How long the spell takes to cast.
+    pub cast_time:        Duration,
+    /// How long after casting the spell until it can be cast again.
+    pub recast_time:      Duration,
+    /// The range of the spell.
+    pub range:            f32,
+    /// The type of the spell, which is what decides whether it's beneficial.
+    pub spell_type:       u8,
+    /// The type of target the spell takes.
+    pub target_type:      u8,
+    /// The formula that the spell's duration is calculated with.
+    pub duration_formula: u8,
+    /// The longest (in ticks) that the spell lasts for.
+    pub duration_cap:     i32,
+    /// The effects (SPAs) of the spell, in slot order.
+    pub effects:          Vec<SpellEffect>,
+}
+
+impl SpellSnapshot {
+    /// How many ticks (of 6 seconds) the spell lasts for when cast by a caster
+    /// of level `at_level`, or 0 if it isn't a spell that lasts.
+    #[must_use]
+    pub fn duration_ticks(&self, at_level: u8) -> u32 {
+        duration_ticks(self.duration_formula, self.duration_cap, at_level)
+    }
+
+    /// Whether the spell is beneficial, rather than detrimental.
+    #[must_use]
+    pub fn is_beneficial(&self) -> bool {
+        is_beneficial(self.spell_type)
+    }
+
+    /// Whether the spell can only target the caster.
+    #[must_use]
+    pub fn targets_self(&self) -> bool {
+        targets_self(self.target_type)
+    }
+}
+
+impl From<&Spell> for SpellSnapshot {
+    fn from(spell: &Spell) -> Self {
+        SpellSnapshot {
+            id:               spell.id(),
+            name:             spell.name().to_owned(),
+            mana_cost:        spell.mana_cost(),
+            cast_time:        spell.cast_time(),
+            recast_time:      spell.recast_time(),
+            range:            spell.range(),
+            spell_type:       spell.raw().spell_type(),
+            target_type:      spell.raw().target_type(),
+            duration_formula: spell.raw().duration_formula(),
+            duration_cap:     spell.raw().duration_cap(),
+            effects:          spell.effects().collect(),
+        }
+    }
+}
+
+impl fmt::Display for SpellSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// A single effect of a spell, which is one of its SPA (spell affect) slots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpellEffect {
+    /// What the effect does.
+    pub spa:   Spa,
+    /// The base value of the effect, whose meaning depends on the SPA.
+    pub base:  i64,
+    /// The second base value of the effect, whose meaning depends on the SPA.
+    pub base2: i64,
+    /// The maximum value of the effect, whose meaning depends on the SPA.
+    pub max:   i64,
+}
+
+impl From<ffi::eqlib::SpellEffect> for SpellEffect {
+    fn from(effect: ffi::eqlib::SpellEffect) -> Self {
+        SpellEffect {
+            spa:   Spa::from(effect.spa),
+            base:  effect.base,
+            base2: effect.base2,
+            max:   effect.max,
+        }
+    }
+}
+
+/// What a spell effect does, for the common SPAs (spell affects), with any
+/// others kept as their number.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum Spa {
+    HitPoints     = 0,
+    ArmorClass    = 1,
+    Attack        = 2,
+    MovementSpeed = 3,
+    AttackSpeed   = 11,
+    Invisibility  = 12,
+    Mana          = 15,
+    Stun          = 21,
+    Charm         = 22,
+    Fear          = 23,
+    Gate          = 26,
+    CancelMagic   = 27,
+    Mesmerize     = 31,
+    SummonItem    = 32,
+    SummonPet     = 33,
+    DivineAura    = 40,
+    Rune          = 55,
+    Levitate      = 57,
+    DamageShield  = 59,
+    MaxHitPoints  = 69,
+    Root          = 99,
+    HealOverTime  = 100,
+
+    #[num_enum(catch_all)]
+    Other(i32),
+}
+
+fn is_beneficial(spell_type: u8) -> bool {
+    BENEFICIAL_SPELL_TYPES.contains(&spell_type)
+}
+
+fn targets_self(target_type: u8) -> bool {
+    target_type == TARGET_SELF
+}
+
+/// The level that a class can cast a spell at, from the game's table of them,
+/// which uses 255 for the classes that can't cast it.
+fn class_level(level: u8) -> Option<u8> {
+    (level != 0 && level != u8::MAX).then_some(level)
+}
+
+/// Calculate the duration of a spell in ticks, using the game's duration
+/// formulas, where a cap of 0 (or less) means that it isn't capped.
+fn duration_ticks(formula: u8, cap: i32, level: u8) -> u32 {
+    let level = i32::from(level);
+    let capped = |ticks: i32| -> i32 {
+        if cap > 0 {
+            ticks.clamp(1, cap)
+        }
+        else {
+            ticks.max(1)
+        }
+    };
+
+    let ticks = match formula {
+        0 => 0,
+        1 => capped((level + 1) / 2),
+        2 => capped((level * 3 + 4) / 5),
+        3 => capped(level * 30),
+        4 => capped(50),
+        5 => capped(2),
+        6 => capped((level + 1) / 2 + 2),
+        7 => capped(level),
+        8 => capped(level + 10),
+        9 => capped(level * 2 + 10),
+        10 => capped(level * 3 + 10),
+        11 => capped((level + 3) * 30),
+        // Permanent, at least for as long as anything in game lasts.
+        50 => 72_000,
+        _ => cap,
+    };
+
+    u32::try_from(ticks).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_ticks() {
+        // No duration at all.
+        assert_eq!(duration_ticks(0, 10, 50), 0);
+
+        // Half the level, rounding up, up to the cap.
+        assert_eq!(duration_ticks(1, 100, 9), 5);
+        assert_eq!(duration_ticks(1, 3, 9), 3);
+
+        // Scaling with level, up to the cap.
+        assert_eq!(duration_ticks(3, 0, 2), 60);
+        assert_eq!(duration_ticks(3, 50, 2), 50);
+        assert_eq!(duration_ticks(9, 100, 20), 50);
+        assert_eq!(duration_ticks(11, 1000, 10), 390);
+
+        // Fixed durations.
+        assert_eq!(duration_ticks(4, 0, 1), 50);
+        assert_eq!(duration_ticks(5, 1, 1), 1);
+        assert_eq!(duration_ticks(50, 0, 1), 72_000);
+
+        // Unknown formulas use the cap.
+        assert_eq!(duration_ticks(15, 36, 1), 36);
+        assert_eq!(duration_ticks(15, -1, 1), 0);
+
+        // Even a level 0 caster gets at least a tick.
+        assert_eq!(duration_ticks(7, 10, 0), 1);
+    }
+
+    #[test]
+    fn test_classification() {
+        assert!(!is_beneficial(0));
+        assert!(is_beneficial(1));
+        assert!(is_beneficial(2));
+
+        assert!(targets_self(6));
+        assert!(!targets_self(5));
+    }
+
+    #[test]
+    fn test_class_level() {
+        assert_eq!(class_level(1), Some(1));
+        assert_eq!(class_level(125), Some(125));
+        assert_eq!(class_level(255), None);
+        assert_eq!(class_level(0), None);
+    }
+
+    #[test]
+    fn test_spa_round_trip() {
+        assert_eq!(Spa::from(0), Spa::HitPoints);
+        assert_eq!(Spa::from(11), Spa::AttackSpeed);
+        assert_eq!(Spa::from(254), Spa::Other(254));
+        assert_eq!(i32::from(Spa::Other(254)), 254);
+        assert_eq!(i32::from(Spa::Root), 99);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let snapshot = SpellSnapshot {
+            id:               13,
+            name:             "Complete Healing".into(),
+            mana_cost:        400,
+            cast_time:        Duration::from_secs(10),
+            recast_time:      Duration::ZERO,
+            range:            100.0,
+            spell_type:       1,
+            target_type:      5,
+            duration_formula: 0,
+            duration_cap:     0,
+            effects:          vec![SpellEffect {
+                spa:   Spa::HitPoints,
+                base:  7500,
+                base2: 0,
+                max:   0,
+            }],
+        };
+
+        assert_eq!(snapshot.to_string(), "Complete Healing");
+        assert!(snapshot.is_beneficial());
+        assert!(!snapshot.targets_self());
+        assert_eq!(snapshot.duration_ticks(60), 0);
+    }
+}
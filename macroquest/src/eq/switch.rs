@@ -0,0 +1,169 @@
+//! Switches, which are the doors, levers, and other clickable objects in a
+//! zone.
+
+use std::{error, fmt};
+
+use ref_cast::RefCast;
+
+use super::macros::getter;
+use super::Location;
+use crate::ffi;
+
+/// The furthest that the player can be from a switch to click it.
+pub const INTERACT_RANGE: f32 = 20.0;
+
+// The states of a switch that count as it being open (open, and opening).
+const OPEN_STATES: [u8; 2] = [1, 2];
+
+/// A switch (door, lever, or other clickable object) in the current zone.
+///
+/// Switches are listed with [`mq::switches()`](crate::mq::switches), and can
+/// only be used on the game's main thread. They belong to the zone, so they
+/// must not be held onto past zoning.
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Switch(ffi::eqlib::EQSwitch);
+
+impl Switch {
+    getter!(
+        /// The name of the switch.
+        name -> &str
+    );
+
+    /// The id of the switch, which is what `/click door` and `/doortarget`
+    /// use.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        u32::try_from(self.raw().id()).unwrap_or(0)
+    }
+
+    /// Where the switch is.
+    #[must_use]
+    pub fn location(&self) -> Location {
+        Location::from(self.raw().location())
+    }
+
+    /// Whether the switch is open (or opening).
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        OPEN_STATES.contains(&self.raw().state())
+    }
+
+    /// Click the switch, the same way that `/click door` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error without clicking the switch if we're not in game, or
+    /// if the switch is further away than [`INTERACT_RANGE`].
+    pub fn click(&self) -> Result<(), ClickError> {
+        let mut player = ffi::eqlib::Location::default();
+        if !ffi::eqlib::get_player_location(&mut player) {
+            return Err(ClickError::NotInGame);
+        }
+
+        check_range(Location::from(player), self.location())?;
+
+        if self.raw().click() {
+            Ok(())
+        }
+        else {
+            Err(ClickError::NotInGame)
+        }
+    }
+
+    fn raw(&self) -> &ffi::eqlib::EQSwitch {
+        crate::macros::debug_assert_main_thread!(
+            ::std::any::type_name::<Self>(),
+            "raw"
+        );
+
+        &self.0
+    }
+}
+
+impl AsRef<Switch> for ffi::eqlib::EQSwitch {
+    fn as_ref(&self) -> &Switch {
+        Switch::ref_cast(self)
+    }
+}
+
+impl fmt::Debug for Switch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Switch")
+            .field("id", &self.id())
+            .field("name", &self.name())
+            .field("location", &self.location())
+            .field("open", &self.is_open())
+            .finish()
+    }
+}
+
+/// An error clicking a [`Switch`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ClickError {
+    /// We're not in game, so there's nobody to click the switch.
+    NotInGame,
+    /// The switch is too far away to click.
+    OutOfRange {
+        /// How far away the switch is.
+        distance: f32,
+    },
+}
+
+impl fmt::Display for ClickError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClickError::NotInGame => f.write_str("not in game"),
+            ClickError::OutOfRange { distance } => {
+                write!(
+                    f,
+                    "switch is out of range ({distance:.1} away, the most is \
+                     {INTERACT_RANGE:.1})"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for ClickError {}
+
+/// Check that a switch at `switch` is in range of a player at `player`.
+fn check_range(player: Location, switch: Location) -> Result<(), ClickError> {
+    let distance = player.distance(&switch);
+
+    if distance > INTERACT_RANGE {
+        return Err(ClickError::OutOfRange { distance });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_range() {
+        let player = Location::new(100.0, 100.0, 0.0);
+
+        assert_eq!(check_range(player, player), Ok(()));
+        assert_eq!(
+            check_range(player, Location::new(112.0, 116.0, 0.0)),
+            Ok(())
+        );
+        assert_eq!(
+            check_range(player, Location::new(100.0, 100.0, 30.0)),
+            Err(ClickError::OutOfRange { distance: 30.0 })
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(ClickError::NotInGame.to_string(), "not in game");
+        assert_eq!(
+            ClickError::OutOfRange { distance: 42.25 }.to_string(),
+            "switch is out of range (42.2 away, the most is 20.0)"
+        );
+    }
+}
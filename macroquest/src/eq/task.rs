@@ -0,0 +1,322 @@
+//! The local character's task journal, which holds both their quests (solo
+//! tasks) and their shared task.
+//!
+//! The objective types are taken from `EQEmu`'s `TaskActivityType`, in
+//! `common/tasks.h`.
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+use crate::ffi;
+
+/// A snapshot of a task in the local character's task journal.
+///
+/// The tasks are listed with [`mq::tasks()`](crate::mq::tasks), and don't
+/// change after they've been taken, so they can be held onto and used from any
+/// thread.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Task {
+    /// The id of the task.
+    pub id:         i32,
+    /// The title of the task.
+    pub title:      String,
+    /// Which kind of task this is.
+    pub kind:       TaskKind,
+    /// How long the task can take before it fails, if it has a time limit.
+    pub time_limit: Option<Duration>,
+    /// When the task was started.
+    pub started_at: SystemTime,
+    /// The objectives of the task, in the order that the game lists them.
+    pub objectives: Vec<Objective>,
+}
+
+impl Task {
+    /// Load every task in the local character's task journal.
+    pub(crate) fn load() -> Vec<Task> {
+        (0..ffi::eqlib::task_count())
+            .filter_map(|index| {
+                let mut info = ffi::eqlib::TaskInfo::default();
+                ffi::eqlib::get_task(index, &mut info)
+                    .then(|| Task::new(info, Objective::load(index)))
+            })
+            .collect()
+    }
+
+    fn new(info: ffi::eqlib::TaskInfo, objectives: Vec<Objective>) -> Task {
+        let kind = if info.shared {
+            TaskKind::Shared
+        }
+        else {
+            TaskKind::Quest
+        };
+        let time_limit = (info.time_limit > 0)
+            .then(|| Duration::from_secs(u64::from(info.time_limit)));
+
+        Task {
+            id: info.id,
+            title: info.title,
+            kind,
+            time_limit,
+            started_at: UNIX_EPOCH + Duration::from_secs(u64::from(info.started_at)),
+            objectives,
+        }
+    }
+
+    /// Whether every objective of the task, other than the optional ones, is
+    /// complete.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.objectives
+            .iter()
+            .filter(|objective| !objective.optional)
+            .all(Objective::is_complete)
+    }
+
+    /// When the task will fail, if it has a time limit.
+    #[must_use]
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.time_limit.map(|limit| self.started_at + limit)
+    }
+
+    /// How long is left to finish the task, if it has a time limit.
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining_at(SystemTime::now())
+    }
+
+    /// How long will be left to finish the task at `now`, if it has a time
+    /// limit.
+    #[must_use]
+    pub fn remaining_at(&self, now: SystemTime) -> Option<Duration> {
+        self.expires_at()
+            .map(|expires_at| expires_at.duration_since(now).unwrap_or(Duration::ZERO))
+    }
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.title)
+    }
+}
+
+/// The kinds of tasks that can be in the task journal.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaskKind {
+    /// A quest, which is a solo task.
+    Quest,
+    /// A shared task, which is done together with a group or raid.
+    Shared,
+}
+
+/// A snapshot of one of the objectives of a [`Task`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Objective {
+    /// The type of the objective.
+    pub objective_type: ObjectiveType,
+    /// The description of the objective (ex: `Kill orcs`).
+    pub description:    String,
+    /// How many times the objective has been done so far.
+    pub current:        u32,
+    /// How many times the objective has to be done to complete it.
+    pub required:       u32,
+    /// Whether the objective is optional.
+    pub optional:       bool,
+}
+
+impl Objective {
+    /// Load the objectives of the task at `index`.
+    fn load(index: u32) -> Vec<Objective> {
+        (0..ffi::eqlib::task_objective_count())
+            .filter_map(|objective_index| {
+                let mut info = ffi::eqlib::TaskObjectiveInfo::default();
+                ffi::eqlib::get_task_objective(index, objective_index, &mut info)
+                    .then(|| Objective::from(info))
+            })
+            .collect()
+    }
+
+    /// Whether the objective is complete.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.current >= self.required
+    }
+
+    /// How far along the objective is, from `0.0` to `1.0`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn progress(&self) -> f32 {
+        if self.is_complete() {
+            return 1.0;
+        }
+
+        self.current as f32 / self.required as f32
+    }
+}
+
+impl From<ffi::eqlib::TaskObjectiveInfo> for Objective {
+    fn from(info: ffi::eqlib::TaskObjectiveInfo) -> Self {
+        Objective {
+            objective_type: ObjectiveType::from(info.objective_type),
+            description:    info.description.trim().to_owned(),
+            current:        u32::try_from(info.current).unwrap_or(0),
+            required:       u32::try_from(info.required).unwrap_or(0),
+            optional:       info.optional,
+        }
+    }
+}
+
+/// Formats the objective with its progress (ex: `Kill orcs (5/10)`).
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}/{})",
+            self.description, self.current, self.required
+        )?;
+        if self.optional {
+            f.write_str(" (optional)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The type of an [`Objective`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+pub enum ObjectiveType {
+    /// Deliver an item (or money) to an NPC.
+    Deliver    = 1,
+    /// Kill an NPC.
+    Kill       = 2,
+    /// Loot an item.
+    Loot       = 3,
+    /// Speak with an NPC.
+    SpeakWith  = 4,
+    /// Explore an area.
+    Explore    = 5,
+    /// Combine an item with a tradeskill.
+    Tradeskill = 6,
+    /// Fish up an item.
+    Fish       = 7,
+    /// Forage an item.
+    Forage     = 8,
+    /// Cast a spell on a target.
+    CastOn     = 9,
+    /// Use a skill on a target.
+    SkillOn    = 10,
+    /// Touch (click) an object.
+    Touch      = 11,
+    /// Collect an item.
+    Collect    = 13,
+    /// Give money to an NPC.
+    GiveCash   = 100,
+
+    /// An objective type unknown to this crate.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn objective(current: u32, required: u32, optional: bool) -> Objective {
+        Objective {
+            objective_type: ObjectiveType::Kill,
+            description: "Kill orcs".to_owned(),
+            current,
+            required,
+            optional,
+        }
+    }
+
+    fn task(time_limit: u32, objectives: Vec<Objective>) -> Task {
+        Task::new(
+            ffi::eqlib::TaskInfo {
+                id: 1,
+                title: "Orc Hunt".to_owned(),
+                shared: false,
+                time_limit,
+                started_at: 1_000,
+            },
+            objectives,
+        )
+    }
+
+    #[test]
+    fn test_objective_progress() {
+        assert!(!objective(5, 10, false).is_complete());
+        assert!((objective(5, 10, false).progress() - 0.5).abs() < f32::EPSILON);
+        assert!(objective(10, 10, false).is_complete());
+        assert!((objective(12, 10, false).progress() - 1.0).abs() < f32::EPSILON);
+        assert!(objective(0, 0, false).is_complete());
+        assert!(objective(0, 0, false).progress().is_finite());
+    }
+
+    #[test]
+    fn test_objective_from_raw() {
+        let objective = Objective::from(ffi::eqlib::TaskObjectiveInfo {
+            objective_type: 2,
+            description:    " Kill orcs ".to_owned(),
+            current:        -1,
+            required:       10,
+            optional:       false,
+        });
+
+        assert_eq!(objective.objective_type, ObjectiveType::Kill);
+        assert_eq!(objective.description, "Kill orcs");
+        assert_eq!(objective.current, 0);
+        assert_eq!(ObjectiveType::from(12), ObjectiveType::Unknown(12));
+    }
+
+    #[test]
+    fn test_objective_display() {
+        assert_eq!(objective(5, 10, false).to_string(), "Kill orcs (5/10)");
+        assert_eq!(
+            objective(0, 1, true).to_string(),
+            "Kill orcs (0/1) (optional)"
+        );
+    }
+
+    #[test]
+    fn test_task_is_complete() {
+        assert!(task(0, vec![]).is_complete());
+        assert!(
+            task(0, vec![objective(10, 10, false), objective(0, 1, true)])
+                .is_complete()
+        );
+        assert!(
+            !task(0, vec![objective(10, 10, false), objective(0, 1, false)])
+                .is_complete()
+        );
+    }
+
+    #[test]
+    fn test_task_timer() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+
+        let untimed = task(0, vec![]);
+        assert_eq!(untimed.time_limit, None);
+        assert_eq!(untimed.remaining_at(at(0)), None);
+
+        let timed = task(600, vec![]);
+        assert_eq!(timed.kind, TaskKind::Quest);
+        assert_eq!(timed.expires_at(), Some(at(1_600)));
+        assert_eq!(
+            timed.remaining_at(at(1_100)),
+            Some(Duration::from_secs(500))
+        );
+        assert_eq!(timed.remaining_at(at(2_000)), Some(Duration::ZERO));
+    }
+}
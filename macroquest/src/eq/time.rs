@@ -0,0 +1,343 @@
+//! The in-game (Norrathian) clock, and converting between it and real time.
+//!
+//! The game clock runs 20 times faster than real time, with every game minute
+//! lasting 3 real seconds (and thus every game day lasting 72 real minutes).
+//! The Norrathian calendar has 12 months, each of which is 28 days long.
+//!
+//! The game clock is kept by the server, and the client only learns about it
+//! when entering the world, so it's unavailable while zoning or at character
+//! select, and it may jump (forwards or backwards) when it becomes available
+//! again. Anything comparing against the game clock over a long period of time
+//! has to allow for that.
+
+use std::fmt;
+use std::time::Duration;
+
+/// The number of real seconds in every game minute.
+pub const REAL_SECONDS_PER_GAME_MINUTE: u64 = 3;
+
+/// The number of game minutes in every game hour.
+pub const MINUTES_PER_HOUR: u64 = 60;
+
+/// The number of game hours in every game day.
+pub const HOURS_PER_DAY: u64 = 24;
+
+/// The number of game days in every game month.
+pub const DAYS_PER_MONTH: u64 = 28;
+
+/// The number of game months in every game year.
+pub const MONTHS_PER_YEAR: u64 = 12;
+
+const MINUTES_PER_DAY: u64 = MINUTES_PER_HOUR * HOURS_PER_DAY;
+const MINUTES_PER_MONTH: u64 = MINUTES_PER_DAY * DAYS_PER_MONTH;
+const MINUTES_PER_YEAR: u64 = MINUTES_PER_MONTH * MONTHS_PER_YEAR;
+
+/// A date and time on the in-game (Norrathian) clock.
+///
+/// The hour uses a 24 hour clock, with midnight being hour `0`, while the month
+/// and day both start at `1`.
+#[doc(alias = "WorldTime")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct GameTime {
+    year:   u16,
+    month:  u8,
+    day:    u8,
+    hour:   u8,
+    minute: u8,
+}
+
+impl GameTime {
+    /// Create a new [`GameTime`], returning `None` if any of the components are
+    /// out of range.
+    #[must_use]
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+    ) -> Option<GameTime> {
+        let valid = (1..=MONTHS_PER_YEAR).contains(&u64::from(month))
+            && (1..=DAYS_PER_MONTH).contains(&u64::from(day))
+            && u64::from(hour) < HOURS_PER_DAY
+            && u64::from(minute) < MINUTES_PER_HOUR;
+
+        valid.then_some(GameTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+        })
+    }
+
+    /// The year.
+    #[must_use]
+    pub fn year(self) -> u16 {
+        self.year
+    }
+
+    /// The month, from `1` to `12`.
+    #[must_use]
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// The day of the month, from `1` to `28`.
+    #[must_use]
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    /// The hour, from `0` to `23`.
+    #[must_use]
+    pub fn hour(self) -> u8 {
+        self.hour
+    }
+
+    /// The minute, from `0` to `59`.
+    #[must_use]
+    pub fn minute(self) -> u8 {
+        self.minute
+    }
+
+    /// The number of game minutes since the start of year `0`.
+    ///
+    /// This is mostly useful for doing arithmetic on game times, and it can be
+    /// turned back into a [`GameTime`] with [`GameTime::from_minutes`].
+    #[must_use]
+    pub fn as_minutes(self) -> u64 {
+        u64::from(self.year) * MINUTES_PER_YEAR
+            + (u64::from(self.month) - 1) * MINUTES_PER_MONTH
+            + (u64::from(self.day) - 1) * MINUTES_PER_DAY
+            + u64::from(self.hour) * MINUTES_PER_HOUR
+            + u64::from(self.minute)
+    }
+
+    /// Create a [`GameTime`] from a number of game minutes since the start of
+    /// year `0`, returning `None` if the year would be out of range.
+    #[must_use]
+    pub fn from_minutes(minutes: u64) -> Option<GameTime> {
+        let year = u16::try_from(minutes / MINUTES_PER_YEAR).ok()?;
+        let minutes = minutes % MINUTES_PER_YEAR;
+
+        // All of these are bounded by the modulo, so the casts can't truncate.
+        #[allow(clippy::cast_possible_truncation)]
+        Some(GameTime {
+            year,
+            month: (minutes / MINUTES_PER_MONTH + 1) as u8,
+            day: (minutes % MINUTES_PER_MONTH / MINUTES_PER_DAY + 1) as u8,
+            hour: (minutes % MINUTES_PER_DAY / MINUTES_PER_HOUR) as u8,
+            minute: (minutes % MINUTES_PER_HOUR) as u8,
+        })
+    }
+
+    /// Add a number of game minutes to this [`GameTime`], returning `None` if
+    /// the year would be out of range.
+    #[must_use]
+    pub fn checked_add_minutes(self, minutes: u64) -> Option<GameTime> {
+        GameTime::from_minutes(self.as_minutes().checked_add(minutes)?)
+    }
+
+    /// The next time, strictly after this one, that the game clock will read
+    /// `hour:minute`.
+    ///
+    /// This is useful for finding, for instance, the next game dawn. Returns
+    /// `None` if `hour` or `minute` are out of range.
+    #[must_use]
+    pub fn next_at(self, hour: u8, minute: u8) -> Option<GameTime> {
+        if u64::from(hour) >= HOURS_PER_DAY || u64::from(minute) >= MINUTES_PER_HOUR {
+            return None;
+        }
+
+        let now = self.as_minutes();
+        let mut next = now - now % MINUTES_PER_DAY
+            + u64::from(hour) * MINUTES_PER_HOUR
+            + u64::from(minute);
+        if next <= now {
+            next += MINUTES_PER_DAY;
+        }
+
+        GameTime::from_minutes(next)
+    }
+
+    /// Whether it's night, which the game considers to be from 7 PM until
+    /// 7 AM.
+    #[must_use]
+    pub fn is_night(self) -> bool {
+        self.hour < 7 || self.hour >= 19
+    }
+
+    /// The amount of real time until the game clock reads `other`, or
+    /// [`Duration::ZERO`] if `other` isn't after this time.
+    #[must_use]
+    pub fn until(self, other: GameTime) -> Duration {
+        real_time_between(self, other)
+    }
+}
+
+/// Formats the game time the way that the game shows it, with the day of the
+/// month and a 12 hour clock (ex: `Day 15, 8:30 PM`).
+impl fmt::Display for GameTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meridiem = if self.hour < 12 { "AM" } else { "PM" };
+        let hour = match self.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+
+        write!(f, "Day {}, {hour}:{:02} {meridiem}", self.day, self.minute)
+    }
+}
+
+/// The amount of real time that `minutes` game minutes will take.
+#[must_use]
+pub fn game_to_real(minutes: u64) -> Duration {
+    Duration::from_secs(minutes.saturating_mul(REAL_SECONDS_PER_GAME_MINUTE))
+}
+
+/// The number of whole game minutes that will pass in `duration` of real time.
+#[must_use]
+pub fn real_to_game(duration: Duration) -> u64 {
+    duration.as_secs() / REAL_SECONDS_PER_GAME_MINUTE
+}
+
+/// The amount of real time between two game times, or [`Duration::ZERO`] if
+/// `to` isn't after `from`.
+#[must_use]
+pub fn real_time_between(from: GameTime, to: GameTime) -> Duration {
+    game_to_real(to.as_minutes().saturating_sub(from.as_minutes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(year: u16, month: u8, day: u8, hour: u8, minute: u8) -> GameTime {
+        GameTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn test_new_validates() {
+        assert!(GameTime::new(3100, 0, 1, 0, 0).is_none());
+        assert!(GameTime::new(3100, 13, 1, 0, 0).is_none());
+        assert!(GameTime::new(3100, 1, 0, 0, 0).is_none());
+        assert!(GameTime::new(3100, 1, 29, 0, 0).is_none());
+        assert!(GameTime::new(3100, 1, 1, 24, 0).is_none());
+        assert!(GameTime::new(3100, 1, 1, 0, 60).is_none());
+        assert!(GameTime::new(3100, 12, 28, 23, 59).is_some());
+    }
+
+    #[test]
+    fn test_minutes_round_trip() {
+        for t in [
+            time(0, 1, 1, 0, 0),
+            time(3100, 1, 1, 0, 0),
+            time(3100, 6, 14, 12, 30),
+            time(3100, 12, 28, 23, 59),
+            time(u16::MAX, 12, 28, 23, 59),
+        ] {
+            assert_eq!(GameTime::from_minutes(t.as_minutes()), Some(t));
+        }
+    }
+
+    #[test]
+    fn test_from_minutes_out_of_range() {
+        let last = time(u16::MAX, 12, 28, 23, 59);
+        assert_eq!(last.checked_add_minutes(1), None);
+    }
+
+    #[test]
+    fn test_add_minutes_wraps() {
+        assert_eq!(
+            time(3100, 1, 1, 23, 59).checked_add_minutes(1),
+            Some(time(3100, 1, 2, 0, 0))
+        );
+        assert_eq!(
+            time(3100, 1, 28, 23, 59).checked_add_minutes(1),
+            Some(time(3100, 2, 1, 0, 0))
+        );
+        assert_eq!(
+            time(3100, 12, 28, 23, 59).checked_add_minutes(1),
+            Some(time(3101, 1, 1, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_ordering_matches_minutes() {
+        let a = time(3100, 1, 28, 23, 59);
+        let b = time(3100, 2, 1, 0, 0);
+
+        assert!(a < b);
+        assert!(a.as_minutes() < b.as_minutes());
+    }
+
+    #[test]
+    fn test_next_at() {
+        let now = time(3100, 1, 1, 6, 30);
+
+        assert_eq!(now.next_at(7, 0), Some(time(3100, 1, 1, 7, 0)));
+        assert_eq!(now.next_at(6, 30), Some(time(3100, 1, 2, 6, 30)));
+        assert_eq!(now.next_at(0, 0), Some(time(3100, 1, 2, 0, 0)));
+        assert_eq!(
+            time(3100, 12, 28, 23, 0).next_at(0, 0),
+            Some(time(3101, 1, 1, 0, 0))
+        );
+        assert_eq!(now.next_at(24, 0), None);
+        assert_eq!(now.next_at(0, 60), None);
+    }
+
+    #[test]
+    fn test_real_time_conversion() {
+        assert_eq!(game_to_real(1), Duration::from_secs(3));
+        assert_eq!(game_to_real(MINUTES_PER_DAY), Duration::from_mins(72));
+        assert_eq!(real_to_game(Duration::from_mins(72)), MINUTES_PER_DAY);
+        assert_eq!(real_to_game(Duration::from_millis(2_999)), 0);
+        assert_eq!(game_to_real(u64::MAX), Duration::from_secs(u64::MAX));
+    }
+
+    #[test]
+    fn test_real_time_between() {
+        let a = time(3100, 1, 1, 23, 0);
+        let b = time(3100, 1, 2, 1, 0);
+
+        assert_eq!(real_time_between(a, b), Duration::from_secs(120 * 3));
+        assert_eq!(real_time_between(b, a), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_is_night() {
+        assert!(time(3100, 1, 1, 0, 0).is_night());
+        assert!(time(3100, 1, 1, 6, 59).is_night());
+        assert!(!time(3100, 1, 1, 7, 0).is_night());
+        assert!(!time(3100, 1, 1, 18, 59).is_night());
+        assert!(time(3100, 1, 1, 19, 0).is_night());
+        assert!(time(3100, 1, 1, 23, 59).is_night());
+    }
+
+    #[test]
+    fn test_until() {
+        let now = time(3100, 1, 1, 20, 0);
+
+        assert_eq!(now.until(time(3100, 1, 1, 20, 1)), Duration::from_secs(3));
+        assert_eq!(
+            now.until(time(3100, 1, 2, 7, 0)),
+            Duration::from_secs(660 * 3)
+        );
+        assert_eq!(now.until(now), Duration::ZERO);
+        assert_eq!(now.until(time(3100, 1, 1, 19, 0)), Duration::ZERO);
+        assert_eq!(
+            time(3100, 12, 28, 23, 59).until(time(3101, 1, 1, 0, 0)),
+            Duration::from_secs(3)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(time(3100, 1, 15, 20, 30).to_string(), "Day 15, 8:30 PM");
+        assert_eq!(time(3100, 1, 1, 0, 0).to_string(), "Day 1, 12:00 AM");
+        assert_eq!(time(3100, 1, 1, 0, 5).to_string(), "Day 1, 12:05 AM");
+        assert_eq!(time(3100, 1, 28, 11, 59).to_string(), "Day 28, 11:59 AM");
+        assert_eq!(time(3100, 1, 28, 12, 0).to_string(), "Day 28, 12:00 PM");
+    }
+}
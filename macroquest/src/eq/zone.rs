@@ -0,0 +1,187 @@
+//! The zone that we're currently in.
+
+use std::fmt;
+
+use num_enum::{FromPrimitive, IntoPrimitive};
+use ref_cast::RefCast;
+
+use super::macros::getter;
+use crate::ffi;
+
+/// The zone that we're currently in.
+///
+/// The zone is returned by [`mq::zone()`](crate::mq::zone), and can only be
+/// used on the game's main thread. It's replaced when we zone, so it must not
+/// be held onto past zoning, but it's valid again by the time that
+/// [`Hooks::zoned`](crate::plugin::Hooks::zoned) is called.
+///
+/// See [`mq::zones`](crate::mq::zones) to look up any zone in the game, rather
+/// than just the current one.
+#[derive(RefCast)]
+#[repr(transparent)]
+pub struct Zone(ffi::eqlib::EQZoneInfo);
+
+impl Zone {
+    getter!(
+        /// The long name of the zone (ex: `The Plane of Knowledge`).
+        name -> &str
+    );
+
+    getter!(
+        /// The short name of the zone (ex: `poknowledge`).
+        short_name -> &str
+    );
+
+    getter!(
+        /// The id of the zone, or 0 if there's no local character yet to say
+        /// which zone they're in.
+        id -> u32
+    );
+
+    /// The type of the zone (ex: outdoors, or a city).
+    #[must_use]
+    pub fn zone_type(&self) -> ZoneType {
+        ZoneType::from(self.raw().zone_type())
+    }
+
+    fn raw(&self) -> &ffi::eqlib::EQZoneInfo {
+        crate::macros::debug_assert_main_thread!(
+            ::std::any::type_name::<Self>(),
+            "raw"
+        );
+
+        &self.0
+    }
+}
+
+impl AsRef<Zone> for ffi::eqlib::EQZoneInfo {
+    fn as_ref(&self) -> &Zone {
+        Zone::ref_cast(self)
+    }
+}
+
+impl fmt::Debug for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Zone")
+            .field("id", &self.id())
+            .field("short_name", &self.short_name())
+            .field("name", &self.name())
+            .field("zone_type", &self.zone_type())
+            .finish()
+    }
+}
+
+/// The type of a zone, which decides things like whether mounts can be used
+/// in it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, FromPrimitive, IntoPrimitive)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "i32", into = "i32")
+)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum ZoneType {
+    /// An indoor dungeon.
+    IndoorDungeon  = 0,
+    /// An outdoor zone.
+    Outdoor        = 1,
+    /// An outdoor city.
+    OutdoorCity    = 2,
+    /// A city within a dungeon.
+    DungeonCity    = 3,
+    /// An indoor city.
+    IndoorCity     = 4,
+    /// An outdoor dungeon.
+    OutdoorDungeon = 5,
+
+    /// A zone type unknown to this crate.
+    #[num_enum(catch_all)]
+    Unknown(i32),
+}
+
+impl ZoneType {
+    /// Every known zone type, in id order.
+    pub const ALL: [ZoneType; 6] = [
+        ZoneType::IndoorDungeon,
+        ZoneType::Outdoor,
+        ZoneType::OutdoorCity,
+        ZoneType::DungeonCity,
+        ZoneType::IndoorCity,
+        ZoneType::OutdoorDungeon,
+    ];
+
+    /// The raw value of the zone type, including for [`ZoneType::Unknown`].
+    #[must_use]
+    pub fn raw(&self) -> i32 {
+        i32::from(*self)
+    }
+
+    /// Whether the zone type is one known to this crate, rather than
+    /// [`ZoneType::Unknown`].
+    #[must_use]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, ZoneType::Unknown(_))
+    }
+
+    /// Whether the zone is outdoors.
+    #[must_use]
+    pub fn is_outdoor(&self) -> bool {
+        matches!(
+            self,
+            ZoneType::Outdoor | ZoneType::OutdoorCity | ZoneType::OutdoorDungeon
+        )
+    }
+
+    /// Whether the zone is a city.
+    #[must_use]
+    pub fn is_city(&self) -> bool {
+        matches!(
+            self,
+            ZoneType::OutdoorCity | ZoneType::DungeonCity | ZoneType::IndoorCity
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_is_complete() {
+        let known: Vec<ZoneType> = (-10..=300)
+            .map(ZoneType::from)
+            .filter(ZoneType::is_known)
+            .collect();
+
+        assert_eq!(known, ZoneType::ALL);
+    }
+
+    #[test]
+    fn test_raw() {
+        for zone_type in ZoneType::ALL {
+            assert_eq!(ZoneType::from(zone_type.raw()), zone_type);
+        }
+
+        assert_eq!(ZoneType::from(6), ZoneType::Unknown(6));
+        assert_eq!(ZoneType::Unknown(6).raw(), 6);
+    }
+
+    #[test]
+    fn test_is_outdoor() {
+        assert!(ZoneType::Outdoor.is_outdoor());
+        assert!(ZoneType::OutdoorCity.is_outdoor());
+        assert!(!ZoneType::IndoorDungeon.is_outdoor());
+        assert!(!ZoneType::DungeonCity.is_outdoor());
+        assert!(!ZoneType::Unknown(6).is_outdoor());
+    }
+
+    #[test]
+    fn test_is_city() {
+        assert!(ZoneType::OutdoorCity.is_city());
+        assert!(ZoneType::DungeonCity.is_city());
+        assert!(ZoneType::IndoorCity.is_city());
+        assert!(!ZoneType::Outdoor.is_city());
+        assert!(!ZoneType::OutdoorDungeon.is_city());
+    }
+}
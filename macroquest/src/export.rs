@@ -0,0 +1,334 @@
+//! Exporting snapshots of the local character for tools outside of the game.
+//!
+//! [`character_profile()`] takes a [`CharacterProfile`] of the local
+//! character (its name, class, level, alternate advancement points, equipped
+//! items, and coin), which serializes to JSON for tools such as guild
+//! management sites to read.
+//!
+//! ```no_run
+//! use macroquest::export;
+//!
+//! let profile = export::character_profile().unwrap();
+//! let path = profile.write_to(macroquest::mq::paths().config()).unwrap();
+//! ```
+//!
+//! # Schema
+//!
+//! Every profile records the [`SCHEMA_VERSION`] that it was written with, as
+//! its `schema_version` field. Adding a field doesn't change the version (so
+//! readers should ignore fields that they don't know about), while removing,
+//! renaming, or changing the meaning of a field does.
+//!
+//! # Cost
+//!
+//! A profile only reads the character's worn inventory slots, rather than
+//! walking its bags or bank, so taking one is a fixed (and small) amount of
+//! work that's done in a single call on the game's main thread, instead of
+//! being spread out across pulses.
+
+use std::path::{Path, PathBuf};
+use std::{error, fmt, fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::eq::character::EQUIPMENT_SLOTS;
+use crate::eq::{Character, Coin};
+use crate::macros::debug_assert_main_thread;
+use crate::mq;
+
+/// The version of the schema of the [`CharacterProfile`].
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A snapshot of the local character, for exporting to tools outside of the
+/// game.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CharacterProfile {
+    /// The [`SCHEMA_VERSION`] that the profile was written with.
+    pub schema_version:  u32,
+    /// The name of the character.
+    pub name:            String,
+    /// The name of the class of the character (ex: `Shadow Knight`).
+    pub class:           String,
+    /// The id of the class of the character.
+    pub class_id:        i32,
+    /// The level of the character.
+    pub level:           u32,
+    /// How many alternate advancement (AA) points the character has available
+    /// to spend.
+    pub aa_points:       u32,
+    /// How many alternate advancement (AA) points the character has spent.
+    pub aa_points_spent: u32,
+    /// The items that the character has equipped, in slot order, skipping any
+    /// empty slots.
+    pub equipment:       Vec<EquippedItem>,
+    /// The coin that the character has.
+    pub currencies:      Currencies,
+}
+
+impl CharacterProfile {
+    /// Take a profile of `character`.
+    fn from_character(character: &Character) -> CharacterProfile {
+        let class = character.class();
+        let equipment = character
+            .iter_equipped()
+            .map(|(slot, item)| EquippedItem::new(slot, item.id(), item.name()))
+            .collect();
+
+        CharacterProfile {
+            schema_version: SCHEMA_VERSION,
+            name: character.name().to_owned(),
+            class: class.name().to_owned(),
+            class_id: class.into(),
+            level: character.level(),
+            aa_points: character.aa_points(),
+            aa_points_spent: character.aa_points_spent(),
+            equipment,
+            currencies: Currencies {
+                carried: character.coin().into(),
+                bank:    character.bank_coin().into(),
+            },
+        }
+    }
+
+    /// The profile, as pretty printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile can't be serialized.
+    pub fn to_json(&self) -> Result<String, ExportError> {
+        serde_json::to_string_pretty(self).map_err(ExportError::Serialize)
+    }
+
+    /// The name of the file that [`CharacterProfile::write_to()`] writes the
+    /// profile to (ex: `Alice_profile.json`).
+    #[must_use]
+    pub fn file_name(&self) -> String {
+        // Character names are only ever letters, but the profile might not
+        // have come from the game.
+        let name: String = self
+            .name
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect();
+
+        format!("{name}_profile.json")
+    }
+
+    /// Write the profile, as JSON, into the directory `dir` (such as
+    /// MacroQuest's Config directory), replacing any profile of the same
+    /// character that's already there, and returning the path it was
+    /// written to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile can't be serialized, or the file can't
+    /// be written.
+    pub fn write_to(&self, dir: &Path) -> Result<PathBuf, ExportError> {
+        let json = self.to_json()?;
+        let path = dir.join(self.file_name());
+
+        fs::write(&path, json).map_err(|error| ExportError::Io {
+            path: path.clone(),
+            error,
+        })?;
+
+        Ok(path)
+    }
+}
+
+/// An item that's equipped by the character.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct EquippedItem {
+    /// The slot that the item is equipped in.
+    pub slot:      usize,
+    /// The name of the slot that the item is equipped in (ex: `Primary`).
+    pub slot_name: String,
+    /// The id of the item.
+    pub id:        u32,
+    /// The name of the item.
+    pub name:      String,
+}
+
+impl EquippedItem {
+    fn new(slot: usize, id: u32, name: &str) -> EquippedItem {
+        EquippedItem {
+            slot,
+            slot_name: EQUIPMENT_SLOTS.get(slot).copied().unwrap_or("").to_owned(),
+            id,
+            name: name.to_owned(),
+        }
+    }
+}
+
+/// The currencies that the character has.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Currencies {
+    /// The coin that the character is carrying.
+    pub carried: Coins,
+    /// The coin that the character has in the bank.
+    pub bank:    Coins,
+}
+
+/// An amount of coin, in each of the game's denominations.
+///
+/// This mirrors [`Coin`], so that changes to it don't change the schema.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Coins {
+    /// Platinum pieces.
+    pub platinum: u32,
+    /// Gold pieces.
+    pub gold:     u32,
+    /// Silver pieces.
+    pub silver:   u32,
+    /// Copper pieces.
+    pub copper:   u32,
+}
+
+impl From<Coin> for Coins {
+    fn from(coin: Coin) -> Self {
+        Coins {
+            platinum: coin.platinum,
+            gold:     coin.gold,
+            silver:   coin.silver,
+            copper:   coin.copper,
+        }
+    }
+}
+
+/// An error exporting a [`CharacterProfile`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ExportError {
+    /// There's no character in game, so there's nothing to export.
+    NotInGame,
+    /// The profile couldn't be serialized.
+    Serialize(serde_json::Error),
+    /// The profile couldn't be written to `path`.
+    Io {
+        /// The path that was being written to.
+        path:  PathBuf,
+        /// The error from writing it.
+        error: io::Error,
+    },
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::NotInGame => f.write_str("not in game"),
+            ExportError::Serialize(_) => f.write_str("could not serialize profile"),
+            ExportError::Io { path, .. } => {
+                write!(f, "could not write profile to {}", path.display())
+            }
+        }
+    }
+}
+
+impl error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ExportError::NotInGame => None,
+            ExportError::Serialize(error) => Some(error),
+            ExportError::Io { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Take a [`CharacterProfile`] of the local character.
+///
+/// This must be called on the game's main thread.
+///
+/// # Errors
+///
+/// Returns an error if there's no character in game.
+pub fn character_profile() -> Result<CharacterProfile, ExportError> {
+    debug_assert_main_thread!("macroquest::export::character_profile");
+
+    mq::character()
+        .map(CharacterProfile::from_character)
+        .ok_or(ExportError::NotInGame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> CharacterProfile {
+        CharacterProfile {
+            schema_version:  SCHEMA_VERSION,
+            name:            "Alice".into(),
+            class:           "Shadow Knight".into(),
+            class_id:        5,
+            level:           125,
+            aa_points:       12,
+            aa_points_spent: 9001,
+            equipment:       vec![
+                EquippedItem::new(2, 1001, "Cloth Cap"),
+                EquippedItem::new(13, 5019, "Rusty Long Sword"),
+            ],
+            currencies:      Currencies {
+                carried: Coins {
+                    platinum: 120,
+                    ..Coins::default()
+                },
+                bank:    Coins::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_equipped_item_slot_name() {
+        assert_eq!(EquippedItem::new(13, 1, "Sword").slot_name, "Primary");
+        assert_eq!(EquippedItem::new(22, 1, "Arrow").slot_name, "Ammo");
+        assert_eq!(EquippedItem::new(99, 1, "Nothing").slot_name, "");
+    }
+
+    #[test]
+    fn test_file_name() {
+        let mut profile = profile();
+        assert_eq!(profile.file_name(), "Alice_profile.json");
+
+        profile.name = "../Bob".into();
+        assert_eq!(profile.file_name(), "Bob_profile.json");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = profile().to_json().unwrap();
+
+        assert!(json.contains(r#""schema_version": 1"#));
+        assert!(json.contains(r#""slot_name": "Primary""#));
+        assert_eq!(
+            serde_json::from_str::<CharacterProfile>(&json).unwrap(),
+            profile()
+        );
+    }
+
+    #[test]
+    fn test_json_ignores_unknown_fields() {
+        let mut value = serde_json::to_value(profile()).unwrap();
+        value["added_later"] = serde_json::Value::Bool(true);
+
+        let profile: CharacterProfile = serde_json::from_value(value).unwrap();
+        assert_eq!(profile.name, "Alice");
+    }
+
+    #[test]
+    fn test_write_to() {
+        let dir = std::env::temp_dir()
+            .join(format!("macroquest-export-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = profile().write_to(&dir).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+
+        assert_eq!(path, dir.join("Alice_profile.json"));
+        assert_eq!(written, profile().to_json().unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use tracing::Level;
+
+use crate::imgui::Ui;
+use crate::log::{LevelFilter, LogBuffer, LogLine};
+
+const LEVELS: [(&str, LevelFilter); 5] = [
+    ("Error", LevelFilter::ERROR),
+    ("Warn", LevelFilter::WARN),
+    ("Info", LevelFilter::INFO),
+    ("Debug", LevelFilter::DEBUG),
+    ("Trace", LevelFilter::TRACE),
+];
+
+const ERROR: [f32; 4] = [1.0, 0.3, 0.3, 1.0];
+const WARN: [f32; 4] = [1.0, 0.8, 0.2, 1.0];
+
+/// Draw a console showing the lines in `buffer`, which is usually filled by an
+/// [`ImGuiLogger`](crate::log::ImGuiLogger).
+///
+/// The console can filter the lines by level and by text (ignoring case), copy
+/// the lines that it's showing to the clipboard, and clear the buffer. While
+/// it's scrolled to the bottom, it stays scrolled to the bottom as new lines
+/// are logged.
+///
+/// The console only copies the lines out of `buffer` when they have changed,
+/// so drawing it every frame is cheap.
+///
+/// # Examples
+///
+/// ```
+/// # use macroquest::plugin::{Hooks, Plugin};
+/// # macroquest::plugin::setup!(MyPlugin);
+/// use macroquest::imgui::{log_console, windows, WindowHandle};
+/// use macroquest::log::{
+///     ConsoleLogger,
+///     FileLogger,
+///     ImGuiLogger,
+///     LevelFilter,
+///     LogBuffer,
+///     Logger,
+/// };
+///
+/// struct MyPlugin {
+///     console: WindowHandle,
+/// }
+///
+/// impl Plugin for MyPlugin {
+///     fn new() -> Self {
+///         let buffer = LogBuffer::default();
+///
+///         Logger::builder()
+///             .console(ConsoleLogger::builder().level(LevelFilter::WARN).build())
+///             .file(
+///                 FileLogger::builder()
+///                     .filename("MyPlugin")
+///                     .level(LevelFilter::DEBUG)
+///                     .build(),
+///             )
+///             .imgui(
+///                 ImGuiLogger::builder()
+///                     .level(LevelFilter::DEBUG)
+///                     .buffer(buffer.clone())
+///                     .build(),
+///             )
+///             .build()
+///             .install();
+///
+///         let console = windows().register("My Plugin Log", move |ui| {
+///             log_console(ui, &buffer);
+///         });
+///
+///         MyPlugin { console }
+///     }
+/// }
+/// # impl Hooks for MyPlugin {}
+/// ```
+pub fn log_console(ui: &Ui, buffer: &LogBuffer) {
+    let mut view = buffer.console();
+
+    for (index, (label, level)) in LEVELS.into_iter().enumerate() {
+        if index > 0 {
+            ui.same_line();
+        }
+
+        if ui.radio_button(label, view.level == level) {
+            view.level = level;
+            view.filter();
+        }
+    }
+
+    if ui.input_text("Search", &mut view.search) {
+        view.filter();
+    }
+
+    ui.same_line();
+    if ui.button("Copy") {
+        ui.set_clipboard_text(&view.text());
+    }
+
+    ui.same_line();
+    if ui.button("Clear") {
+        buffer.clear();
+    }
+
+    view.refresh(buffer);
+
+    ui.child("log-console", |ui| {
+        for line in &view.visible {
+            match line.level() {
+                Level::ERROR => ui.text_colored(ERROR, line.text()),
+                Level::WARN => ui.text_colored(WARN, line.text()),
+                Level::INFO => ui.text(line.text()),
+                _ => ui.text_disabled(line.text()),
+            }
+        }
+
+        // Keep following new lines, unless the user has scrolled up.
+        if ui.scroll_y() >= ui.scroll_max_y() {
+            ui.set_scroll_here_y(1.0);
+        }
+    });
+}
+
+/// The state of a console showing a [`LogBuffer`], which is kept with the
+/// buffer so that the console doesn't need to be.
+#[derive(Debug)]
+pub(crate) struct View {
+    level:      LevelFilter,
+    search:     String,
+    // The generation of the buffer that the lines were copied from.
+    generation: Option<u64>,
+    lines:      Vec<Arc<LogLine>>,
+    visible:    Vec<Arc<LogLine>>,
+}
+
+impl View {
+    pub(crate) fn new() -> View {
+        View {
+            level:      LevelFilter::TRACE,
+            search:     String::new(),
+            generation: None,
+            lines:      vec![],
+            visible:    vec![],
+        }
+    }
+
+    /// Copy the lines out of `buffer`, if they've changed since they were last
+    /// copied.
+    fn refresh(&mut self, buffer: &LogBuffer) {
+        if self.generation == Some(buffer.generation()) {
+            return;
+        }
+
+        let (generation, lines) = buffer.snapshot();
+        self.generation = Some(generation);
+        self.lines = lines;
+        self.filter();
+    }
+
+    /// Work out which lines are shown, after the lines or filters change.
+    fn filter(&mut self) {
+        let search = self.search.to_lowercase();
+
+        self.visible = self
+            .lines
+            .iter()
+            .filter(|line| line.level() <= self.level)
+            .filter(|line| {
+                search.is_empty() || line.text().to_lowercase().contains(&search)
+            })
+            .cloned()
+            .collect();
+    }
+
+    /// The text of the lines that are shown.
+    fn text(&self) -> String {
+        self.visible
+            .iter()
+            .map(|line| line.text())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer() -> LogBuffer {
+        let buffer = LogBuffer::new(10);
+        buffer.push(Level::ERROR, "ERROR it broke");
+        buffer.push(Level::INFO, "INFO Hello World");
+        buffer.push(Level::DEBUG, "DEBUG hello again");
+        buffer
+    }
+
+    #[test]
+    fn test_view_refresh() {
+        let buffer = buffer();
+        let mut view = View::new();
+
+        view.refresh(&buffer);
+        assert_eq!(view.visible.len(), 3);
+
+        buffer.push(Level::INFO, "INFO one more");
+        view.refresh(&buffer);
+        assert_eq!(view.visible.len(), 4);
+
+        buffer.clear();
+        view.refresh(&buffer);
+        assert!(view.visible.is_empty());
+    }
+
+    #[test]
+    fn test_view_filter_level() {
+        let mut view = View::new();
+        view.level = LevelFilter::INFO;
+        view.refresh(&buffer());
+
+        assert_eq!(view.text(), "ERROR it broke\nINFO Hello World");
+    }
+
+    #[test]
+    fn test_view_filter_search() {
+        let mut view = View::new();
+        view.refresh(&buffer());
+
+        view.search = "HELLO".to_owned();
+        view.filter();
+        assert_eq!(view.text(), "INFO Hello World\nDEBUG hello again");
+
+        view.search.clear();
+        view.filter();
+        assert_eq!(view.visible.len(), 3);
+    }
+}
@@ -0,0 +1,388 @@
+//! Drawing ImGui widgets from within the ImGui overlay.
+//!
+//! MacroQuest renders its ImGui overlay once per frame, calling
+//! [`Hooks::update_imgui()`](crate::plugin::Hooks::update_imgui) on every
+//! plugin while it does so, and ImGui may only be used from within that hook.
+//! To enforce that, every ImGui function is a method on [`Ui`], which can only
+//! be obtained (via [`Ui::current()`]) while inside of that hook.
+//!
+//! Windows can also be registered with [`windows()`], which draws them for the
+//! plugin, adds a menu to toggle them, and remembers which are open.
+//!
+//! When the `logger` feature is enabled, [`log_console()`] draws a console
+//! showing the plugin's own log output.
+//!
+//! # Examples
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MyPlugin);
+//! # struct MyPlugin;
+//! # impl Plugin for MyPlugin {
+//! #     fn new() -> Self {
+//! #         MyPlugin
+//! #     }
+//! # }
+//! use macroquest::imgui::Ui;
+//!
+//! #[macroquest::plugin::hooks]
+//! impl Hooks for MyPlugin {
+//!     fn update_imgui(&self) {
+//!         let Some(ui) = Ui::current()
+//!         else {
+//!             return;
+//!         };
+//!
+//!         ui.window("My Plugin", |ui| {
+//!             ui.text("Hello from Rust!");
+//!         });
+//!     }
+//! }
+//! ```
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+use crate::ffi::imgui as imguilib;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "logger")))]
+#[cfg(feature = "logger")]
+pub use self::console::log_console;
+pub use self::queue::{DrawCommand, DrawQueue};
+pub(crate) use self::windows::state_path;
+pub use self::windows::{windows, WindowHandle, Windows};
+
+#[cfg(feature = "logger")]
+pub(crate) mod console;
+mod queue;
+mod windows;
+
+thread_local! {
+    static IN_FRAME: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A token that proves that we're currently within the ImGui overlay, which
+/// all of the ImGui functions are available on.
+///
+/// This isn't [`Send`], as ImGui may only be used from the game's main thread,
+/// and it should not be held onto past the end of the
+/// [`Hooks::update_imgui()`](crate::plugin::Hooks::update_imgui) call that it
+/// was obtained in.
+#[derive(Debug)]
+pub struct Ui {
+    _not_send: PhantomData<*const ()>,
+}
+
+// The methods on Ui don't use self, but requiring a Ui to call them is what
+// ensures that they're only called from within update_imgui.
+#[allow(clippy::unused_self)]
+impl Ui {
+    /// Get the [`Ui`] for the current ImGui frame, returning `None` if we're
+    /// not currently within one.
+    #[must_use]
+    pub fn current() -> Option<Ui> {
+        IN_FRAME.with(Cell::get).then_some(Ui {
+            _not_send: PhantomData,
+        })
+    }
+
+    /// Draw a window named `name`, calling `f` to draw its contents whenever
+    /// the window is visible (i.e. not collapsed or clipped).
+    ///
+    /// The name is used to identify the window, so it must be unique.
+    pub fn window<F>(&self, name: &str, f: F)
+    where
+        F: FnOnce(&Ui),
+    {
+        debug_assert_in_frame();
+
+        if imguilib::begin(name) {
+            f(self);
+        }
+
+        // End must always be called, no matter what Begin returned.
+        imguilib::end();
+    }
+
+    /// Draw a scrolling region identified by `id` that fills the rest of the
+    /// current window, calling `f` to draw its contents whenever the region is
+    /// visible.
+    pub fn child<F>(&self, id: &str, f: F)
+    where
+        F: FnOnce(&Ui),
+    {
+        debug_assert_in_frame();
+
+        if imguilib::begin_child(id) {
+            f(self);
+        }
+
+        // Like End, EndChild must always be called, no matter what BeginChild
+        // returned.
+        imguilib::end_child();
+    }
+
+    /// How far the current window (or child region) is scrolled down, in
+    /// pixels.
+    #[must_use]
+    pub fn scroll_y(&self) -> f32 {
+        debug_assert_in_frame();
+        imguilib::get_scroll_y()
+    }
+
+    /// How far the current window (or child region) can be scrolled down, in
+    /// pixels.
+    #[must_use]
+    pub fn scroll_max_y(&self) -> f32 {
+        debug_assert_in_frame();
+        imguilib::get_scroll_max_y()
+    }
+
+    /// Scroll the current window (or child region) so that the current
+    /// position is at `ratio` of the way down it (`0.0` is the top, `1.0` is
+    /// the bottom).
+    pub fn set_scroll_here_y(&self, ratio: f32) {
+        debug_assert_in_frame();
+        imguilib::set_scroll_here_y(ratio);
+    }
+
+    /// Draw a line of text.
+    pub fn text(&self, text: &str) {
+        debug_assert_in_frame();
+        imguilib::text(text);
+    }
+
+    /// Draw a line of text, using the disabled (dimmed) text color.
+    pub fn text_disabled(&self, text: &str) {
+        debug_assert_in_frame();
+        imguilib::text_disabled(text);
+    }
+
+    /// Draw a line of text in `color`, given as red, green, blue, and alpha
+    /// (each from `0.0` to `1.0`).
+    pub fn text_colored(&self, color: [f32; 4], text: &str) {
+        debug_assert_in_frame();
+        let [r, g, b, a] = color;
+        imguilib::text_colored(r, g, b, a, text);
+    }
+
+    /// Draw a button, returning whether it was clicked.
+    #[must_use]
+    pub fn button(&self, label: &str) -> bool {
+        debug_assert_in_frame();
+        imguilib::button(label)
+    }
+
+    /// Draw a radio button, shown as selected if `active`, returning whether
+    /// it was clicked.
+    #[must_use]
+    pub fn radio_button(&self, label: &str, active: bool) -> bool {
+        debug_assert_in_frame();
+        imguilib::radio_button(label, active)
+    }
+
+    /// Draw a single line text input editing `text`, returning whether `text`
+    /// was changed.
+    ///
+    /// The input is limited to 255 bytes.
+    #[must_use]
+    pub fn input_text(&self, label: &str, text: &mut String) -> bool {
+        debug_assert_in_frame();
+        imguilib::input_text(label, text)
+    }
+
+    /// Draw a checkbox editing `value`, returning whether it was changed.
+    #[must_use]
+    pub fn checkbox(&self, label: &str, value: &mut bool) -> bool {
+        debug_assert_in_frame();
+        imguilib::checkbox(label, value)
+    }
+
+    /// Draw a draggable integer editing `value`, limited to `range` if there
+    /// is one, returning whether it was changed.
+    #[must_use]
+    pub fn drag_int(
+        &self,
+        label: &str,
+        value: &mut i64,
+        range: Option<RangeInclusive<i64>>,
+    ) -> bool {
+        debug_assert_in_frame();
+
+        let (min, max) = range.map_or((0, 0), RangeInclusive::into_inner);
+        imguilib::drag_i64(label, value, min, max)
+    }
+
+    /// Draw a draggable number editing `value`, limited to `range` if there is
+    /// one, returning whether it was changed.
+    #[must_use]
+    pub fn drag_float(
+        &self,
+        label: &str,
+        value: &mut f64,
+        range: Option<RangeInclusive<f64>>,
+    ) -> bool {
+        debug_assert_in_frame();
+
+        let (min, max) = range.map_or((0.0, 0.0), RangeInclusive::into_inner);
+        imguilib::drag_f64(label, value, min, max)
+    }
+
+    /// Draw a table identified by `id`, with a column (and header) for each of
+    /// `headers`, calling `f` to draw its rows whenever the table is visible.
+    ///
+    /// Within `f`, each row is started with [`Ui::table_next_row()`], and each
+    /// cell within it with [`Ui::table_next_column()`].
+    pub fn table<F>(&self, id: &str, headers: &[&str], f: F)
+    where
+        F: FnOnce(&Ui),
+    {
+        debug_assert_in_frame();
+
+        // ImGui requires at least one column, and doesn't support more than a
+        // few hundred.
+        let Ok(columns @ 1..) = i32::try_from(headers.len())
+        else {
+            return;
+        };
+
+        // Unlike windows, EndTable must only be called if BeginTable returned
+        // true.
+        if imguilib::begin_table(id, columns) {
+            for header in headers {
+                imguilib::table_setup_column(header);
+            }
+            imguilib::table_headers_row();
+
+            f(self);
+
+            imguilib::end_table();
+        }
+    }
+
+    /// Start a new row in the current table.
+    pub fn table_next_row(&self) {
+        debug_assert_in_frame();
+        imguilib::table_next_row();
+    }
+
+    /// Move to the next cell in the current row of the current table.
+    pub fn table_next_column(&self) {
+        debug_assert_in_frame();
+        let _ = imguilib::table_next_column();
+    }
+
+    /// Draw a collapsing header labelled `label`, calling `f` to draw its
+    /// contents whenever it's open.
+    ///
+    /// The contents are drawn with `label` pushed onto the ID stack, so widgets
+    /// under different headers can share labels.
+    pub fn collapsing_header<F>(&self, label: &str, f: F)
+    where
+        F: FnOnce(&Ui),
+    {
+        debug_assert_in_frame();
+
+        if imguilib::collapsing_header(label) {
+            imguilib::push_id(label);
+            f(self);
+            imguilib::pop_id();
+        }
+    }
+
+    /// Draw a combo box labelled `label`, choosing between `items`, with
+    /// `current` the index of the chosen item. Returns whether a different
+    /// item was chosen.
+    #[must_use]
+    pub fn combo(&self, label: &str, items: &[&str], current: &mut usize) -> bool {
+        debug_assert_in_frame();
+
+        let preview = items.get(*current).copied().unwrap_or_default();
+        let mut changed = false;
+
+        // Like tables, EndCombo must only be called if BeginCombo returned
+        // true.
+        if imguilib::begin_combo(label, preview) {
+            for (index, item) in items.iter().enumerate() {
+                if imguilib::selectable(item, index == *current) && index != *current {
+                    *current = index;
+                    changed = true;
+                }
+            }
+
+            imguilib::end_combo();
+        }
+
+        changed
+    }
+
+    /// Draw a horizontal separator line.
+    pub fn separator(&self) {
+        debug_assert_in_frame();
+        imguilib::separator();
+    }
+
+    /// Draw the next widget on the same line as the previous one.
+    pub fn same_line(&self) {
+        debug_assert_in_frame();
+        imguilib::same_line();
+    }
+
+    /// Draw a progress bar, filled to `fraction` (from `0.0` to `1.0`), with
+    /// `overlay` drawn over top of it.
+    pub fn progress_bar(&self, fraction: f32, overlay: &str) {
+        debug_assert_in_frame();
+        imguilib::progress_bar(fraction, overlay);
+    }
+
+    /// Copy `text` to the clipboard.
+    pub fn set_clipboard_text(&self, text: &str) {
+        debug_assert_in_frame();
+        imguilib::set_clipboard_text(text);
+    }
+}
+
+fn debug_assert_in_frame() {
+    debug_assert!(
+        IN_FRAME.with(Cell::get),
+        "the ImGui Ui was used outside of update_imgui"
+    );
+}
+
+/// Run `f` as being within an ImGui frame.
+pub(crate) fn frame<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Reset(bool);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            IN_FRAME.with(|in_frame| in_frame.set(self.0));
+        }
+    }
+
+    let _reset = Reset(IN_FRAME.with(|in_frame| in_frame.replace(true)));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_only_within_frame() {
+        assert!(Ui::current().is_none());
+        assert!(frame(|| Ui::current().is_some()));
+        assert!(Ui::current().is_none());
+    }
+
+    #[test]
+    fn test_frame_resets_on_unwind() {
+        let result = std::panic::catch_unwind(|| frame(|| panic!("oh no")));
+
+        assert!(result.is_err());
+        assert!(Ui::current().is_none());
+    }
+}
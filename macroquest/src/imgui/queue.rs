@@ -0,0 +1,499 @@
+//! Display data produced on background threads, rendered from the ImGui
+//! overlay.
+//!
+//! Background threads can't use ImGui, but they're often the ones producing
+//! the data that a plugin wants to display. A [`DrawQueue`] lets them push
+//! lightweight [`DrawCommand`]s from any thread, which are then applied and
+//! rendered into a standard window by [`DrawQueue::render()`] from within
+//! [`Hooks::update_imgui()`](crate::plugin::Hooks::update_imgui).
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use parking_lot::{Mutex, MutexGuard};
+
+use super::Ui;
+
+// The maximum number of log lines that are kept for display.
+const MAX_LOG_LINES: usize = 256;
+
+/// A single command to update what a [`DrawQueue`] displays.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    /// Set the value of the row named `key`, adding it if it doesn't exist.
+    Row {
+        /// The name of the row.
+        key:   String,
+        /// The value displayed in the row.
+        value: String,
+    },
+
+    /// Set the progress bar named `key` to `fraction` (from `0.0` to `1.0`),
+    /// adding it if it doesn't exist.
+    Progress {
+        /// The name of the progress bar.
+        key:      String,
+        /// How full the progress bar is.
+        fraction: f32,
+    },
+
+    /// Append a line to the log.
+    Log(String),
+
+    /// Remove all rows, progress bars, and log lines.
+    Clear,
+}
+
+impl DrawCommand {
+    fn key(&self) -> Option<(&str, bool)> {
+        match self {
+            DrawCommand::Row { key, .. } => Some((key, false)),
+            DrawCommand::Progress { key, .. } => Some((key, true)),
+            DrawCommand::Log(_) | DrawCommand::Clear => None,
+        }
+    }
+}
+
+/// A bounded, thread safe, queue of [`DrawCommand`]s, which are rendered into
+/// an ImGui window.
+///
+/// Cloning a [`DrawQueue`] produces another handle to the same queue, so one
+/// clone can be given to a background thread to push commands into, while the
+/// plugin renders it from another.
+///
+/// When the queue is full, commands are coalesced rather than dropped: a row
+/// or progress bar that is already pending is updated in place, and the oldest
+/// pending log line is replaced with a count of skipped lines. Only when the
+/// queue is full of updates to distinct rows and progress bars is a command
+/// dropped, and the number of dropped commands is displayed in the window.
+///
+/// # Examples
+///
+/// ```
+/// # use macroquest::plugin::{Hooks, Plugin};
+/// # macroquest::plugin::setup!(MyPlugin);
+/// use macroquest::imgui::{DrawQueue, Ui};
+///
+/// struct MyPlugin {
+///     status: DrawQueue,
+/// }
+///
+/// impl Plugin for MyPlugin {
+///     fn new() -> Self {
+///         MyPlugin {
+///             status: DrawQueue::new(64),
+///         }
+///     }
+/// }
+///
+/// #[macroquest::plugin::hooks]
+/// impl Hooks for MyPlugin {
+///     fn initialize(&self) {
+///         let status = self.status.clone();
+///         std::thread::spawn(move || {
+///             status.set_row("State", "Working");
+///             status.set_progress("Download", 0.5);
+///             status.log("Started download");
+///         });
+///     }
+///
+///     fn update_imgui(&self) {
+///         if let Some(ui) = Ui::current() {
+///             self.status.render(&ui, "My Plugin Status");
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct DrawQueue {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    pending:  Mutex<Pending>,
+    view:     Mutex<View>,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct Pending {
+    commands: VecDeque<DrawCommand>,
+    skipped:  u64,
+    dropped:  u64,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct View {
+    rows:     Vec<(String, String)>,
+    progress: Vec<(String, f32)>,
+    log:      VecDeque<String>,
+    dropped:  u64,
+}
+
+impl DrawQueue {
+    /// Create a new [`DrawQueue`] that holds up to `capacity` pending commands
+    /// between renders.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "a draw queue must have a capacity of at least 1"
+        );
+
+        DrawQueue {
+            shared: Arc::new(Shared {
+                pending: Mutex::new(Pending::default()),
+                view: Mutex::new(View::default()),
+                capacity,
+            }),
+        }
+    }
+
+    /// Push a command onto the queue, coalescing it with any pending commands
+    /// if possible.
+    pub fn push(&self, command: DrawCommand) {
+        self.shared
+            .pending
+            .lock()
+            .push(command, self.shared.capacity);
+    }
+
+    /// Set the value of the row named `key`.
+    pub fn set_row<K, V>(&self, key: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.push(DrawCommand::Row {
+            key:   key.into(),
+            value: value.into(),
+        });
+    }
+
+    /// Set the progress bar named `key` to `fraction` (from `0.0` to `1.0`).
+    pub fn set_progress<K>(&self, key: K, fraction: f32)
+    where
+        K: Into<String>,
+    {
+        self.push(DrawCommand::Progress {
+            key: key.into(),
+            fraction,
+        });
+    }
+
+    /// Append a line to the log.
+    pub fn log<L>(&self, line: L)
+    where
+        L: Into<String>,
+    {
+        self.push(DrawCommand::Log(line.into()));
+    }
+
+    /// Remove all rows, progress bars, and log lines.
+    pub fn clear(&self) {
+        self.push(DrawCommand::Clear);
+    }
+
+    /// The total number of commands that have been dropped because the queue
+    /// was full and they could not be coalesced.
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.shared.pending.lock().dropped
+    }
+
+    /// Apply every pending command, and render the result into a window named
+    /// `title`.
+    pub fn render(&self, ui: &Ui, title: &str) {
+        let view = self.update();
+
+        ui.window(title, |ui| {
+            for (key, value) in &view.rows {
+                ui.text_disabled(key);
+                ui.same_line();
+                ui.text(value);
+            }
+
+            for (key, fraction) in &view.progress {
+                ui.progress_bar(*fraction, key);
+            }
+
+            if !view.log.is_empty() {
+                ui.separator();
+                for line in &view.log {
+                    ui.text(line);
+                }
+            }
+
+            if view.dropped > 0 {
+                ui.separator();
+                ui.text_disabled(&format!("{} updates dropped", view.dropped));
+            }
+        });
+    }
+
+    /// Apply every pending command to the view, returning it.
+    fn update(&self) -> MutexGuard<'_, View> {
+        let (commands, skipped, dropped) = {
+            let mut pending = self.shared.pending.lock();
+            (
+                std::mem::take(&mut pending.commands),
+                std::mem::take(&mut pending.skipped),
+                pending.dropped,
+            )
+        };
+
+        let mut view = self.shared.view.lock();
+        view.apply(commands, skipped);
+        view.dropped = dropped;
+
+        view
+    }
+}
+
+impl std::fmt::Debug for DrawQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DrawQueue")
+            .field("pending", &self.shared.pending.lock().commands.len())
+            .field("capacity", &self.shared.capacity)
+            .finish()
+    }
+}
+
+impl Pending {
+    fn push(&mut self, command: DrawCommand, capacity: usize) {
+        // Updates to a row or progress bar that's already pending just replace
+        // the pending update, as only the latest value will be displayed.
+        if let Some(key) = command.key() {
+            if let Some(existing) =
+                self.commands.iter_mut().find(|c| c.key() == Some(key))
+            {
+                *existing = command;
+                return;
+            }
+        }
+
+        // Nothing pending will be displayed after a clear.
+        if matches!(command, DrawCommand::Clear) {
+            self.commands.clear();
+            self.skipped = 0;
+        }
+
+        if self.commands.len() >= capacity {
+            let oldest_log = self
+                .commands
+                .iter()
+                .position(|c| matches!(c, DrawCommand::Log(_)));
+
+            if let Some(index) = oldest_log {
+                self.commands.remove(index);
+                self.skipped += 1;
+            }
+            else {
+                self.dropped += 1;
+                return;
+            }
+        }
+
+        self.commands.push_back(command);
+    }
+}
+
+impl View {
+    fn apply(&mut self, commands: VecDeque<DrawCommand>, mut skipped: u64) {
+        // The skipped lines were the oldest ones pending, so they come before
+        // any of the lines that we still have.
+        for command in commands {
+            match command {
+                DrawCommand::Row { key, value } => {
+                    match self.rows.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, v)) => *v = value,
+                        None => self.rows.push((key, value)),
+                    }
+                }
+                DrawCommand::Progress { key, fraction } => {
+                    let fraction = fraction.clamp(0.0, 1.0);
+                    match self.progress.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, f)) => *f = fraction,
+                        None => self.progress.push((key, fraction)),
+                    }
+                }
+                DrawCommand::Log(line) => {
+                    if skipped > 0 {
+                        self.push_log(format!("... {skipped} lines skipped"));
+                        skipped = 0;
+                    }
+                    self.push_log(line);
+                }
+                DrawCommand::Clear => {
+                    self.rows.clear();
+                    self.progress.clear();
+                    self.log.clear();
+                }
+            }
+        }
+
+        if skipped > 0 {
+            self.push_log(format!("... {skipped} lines skipped"));
+        }
+    }
+
+    fn push_log(&mut self, line: String) {
+        if self.log.len() >= MAX_LOG_LINES {
+            self.log.pop_front();
+        }
+        self.log.push_back(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(key: &str, value: &str) -> DrawCommand {
+        DrawCommand::Row {
+            key:   key.into(),
+            value: value.into(),
+        }
+    }
+
+    fn log(line: &str) -> DrawCommand {
+        DrawCommand::Log(line.into())
+    }
+
+    fn pending(queue: &DrawQueue) -> Vec<DrawCommand> {
+        queue
+            .shared
+            .pending
+            .lock()
+            .commands
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_rows_coalesce() {
+        let queue = DrawQueue::new(8);
+        queue.set_row("a", "1");
+        queue.set_row("b", "1");
+        queue.set_row("a", "2");
+
+        assert_eq!(pending(&queue), [row("a", "2"), row("b", "1")]);
+    }
+
+    #[test]
+    fn test_rows_and_progress_do_not_coalesce() {
+        let queue = DrawQueue::new(8);
+        queue.set_row("a", "1");
+        queue.set_progress("a", 0.5);
+
+        assert_eq!(pending(&queue).len(), 2);
+    }
+
+    #[test]
+    fn test_full_queue_skips_oldest_log_line() {
+        let queue = DrawQueue::new(3);
+        queue.log("one");
+        queue.set_row("a", "1");
+        queue.log("two");
+        queue.log("three");
+
+        assert_eq!(pending(&queue), [row("a", "1"), log("two"), log("three")]);
+        assert_eq!(queue.dropped(), 0);
+
+        let view = queue.update();
+        assert_eq!(view.log, ["... 1 lines skipped", "two", "three"]);
+    }
+
+    #[test]
+    fn test_full_queue_of_rows_drops() {
+        let queue = DrawQueue::new(2);
+        queue.set_row("a", "1");
+        queue.set_row("b", "1");
+        queue.set_row("c", "1");
+
+        // Existing rows can still be updated.
+        queue.set_row("a", "2");
+
+        assert_eq!(pending(&queue), [row("a", "2"), row("b", "1")]);
+        assert_eq!(queue.dropped(), 1);
+
+        assert_eq!(queue.update().dropped, 1);
+    }
+
+    #[test]
+    fn test_clear_discards_pending() {
+        let queue = DrawQueue::new(8);
+        queue.set_row("a", "1");
+        queue.log("one");
+        queue.clear();
+        queue.set_row("b", "1");
+
+        assert_eq!(pending(&queue), [DrawCommand::Clear, row("b", "1")]);
+    }
+
+    #[test]
+    fn test_update_applies_commands() {
+        let queue = DrawQueue::new(8);
+        queue.set_row("a", "1");
+        queue.set_progress("p", 2.0);
+        queue.log("one");
+        drop(queue.update());
+
+        queue.set_row("b", "1");
+        queue.set_row("a", "2");
+        queue.set_progress("p", 0.25);
+        let view = queue.update();
+
+        assert_eq!(
+            view.rows,
+            [("a".into(), "2".into()), ("b".into(), "1".into())]
+        );
+        assert_eq!(view.progress, [("p".into(), 0.25)]);
+        assert_eq!(view.log, ["one"]);
+        assert!(pending(&queue).is_empty());
+    }
+
+    #[test]
+    fn test_update_clear() {
+        let queue = DrawQueue::new(8);
+        queue.set_row("a", "1");
+        queue.log("one");
+        drop(queue.update());
+
+        queue.clear();
+        queue.log("two");
+        let view = queue.update();
+
+        assert!(view.rows.is_empty());
+        assert_eq!(view.log, ["two"]);
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        let queue = DrawQueue::new(MAX_LOG_LINES * 2);
+        for i in 0..=MAX_LOG_LINES {
+            queue.log(i.to_string());
+        }
+        let view = queue.update();
+
+        assert_eq!(view.log.len(), MAX_LOG_LINES);
+        assert_eq!(view.log.front().map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn test_clones_share_queue() {
+        let queue = DrawQueue::new(8);
+        let other = queue.clone();
+
+        std::thread::spawn(move || other.set_row("a", "1"))
+            .join()
+            .unwrap();
+
+        assert_eq!(pending(&queue), [row("a", "1")]);
+    }
+}
@@ -0,0 +1,497 @@
+//! A registry of ImGui windows, which are rendered for the plugin.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write as _;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use super::Ui;
+use crate::ffi::imgui as imguilib;
+use crate::log::error;
+
+static WINDOWS: Windows = Windows::new();
+
+// The section of the state file that the open windows are stored in.
+const SECTION: &str = "[Windows]";
+
+type DrawFn = Arc<Mutex<Box<dyn FnMut(&Ui) + Send>>>;
+
+/// The global registry of ImGui windows for this plugin.
+///
+/// Every registered window is drawn after each call to
+/// [`Hooks::update_imgui()`](crate::plugin::Hooks::update_imgui) while it is
+/// open, and can be opened and closed from a menu named after the plugin in
+/// the main menu bar, as well as closed from its own close button. Whether
+/// each window is open is remembered across reloads of the plugin, in
+/// `{plugin}.windows.ini` in the MacroQuest config directory.
+///
+/// # Examples
+///
+/// ```
+/// # use macroquest::plugin::{Hooks, Plugin};
+/// # macroquest::plugin::setup!(MyPlugin);
+/// use macroquest::imgui::{self, WindowHandle};
+///
+/// struct MyPlugin {
+///     window: WindowHandle,
+/// }
+///
+/// impl Plugin for MyPlugin {
+///     fn new() -> Self {
+///         MyPlugin {
+///             window: imgui::windows().register("My Plugin", |ui| {
+///                 ui.text("Hello from Rust!");
+///             }),
+///         }
+///     }
+/// }
+///
+/// impl Hooks for MyPlugin {}
+/// ```
+pub struct Windows {
+    registry: Mutex<Registry>,
+}
+
+struct Registry {
+    next_id: u64,
+    windows: Vec<Window>,
+    // Whether each window, by name, is open. This includes windows that
+    // aren't currently registered, so that their state isn't forgotten.
+    state:   BTreeMap<String, bool>,
+    path:    Option<PathBuf>,
+}
+
+struct Window {
+    id:   u64,
+    name: String,
+    draw: DrawFn,
+}
+
+/// Get the global [`Windows`] registry.
+#[must_use]
+pub fn windows() -> &'static Windows {
+    &WINDOWS
+}
+
+impl Windows {
+    const fn new() -> Self {
+        Windows {
+            registry: Mutex::new(Registry {
+                next_id: 0,
+                windows: Vec::new(),
+                state:   BTreeMap::new(),
+                path:    None,
+            }),
+        }
+    }
+
+    /// Register a window named `name`, calling `draw` to draw its contents
+    /// whenever it's open and visible.
+    ///
+    /// The window is open unless it was closed the last time that the plugin
+    /// was loaded, and is unregistered when the returned [`WindowHandle`] is
+    /// dropped, or when the plugin is shut down.
+    ///
+    /// The name is used to identify the window, so registering a second window
+    /// with the same name replaces the first one.
+    pub fn register<N, F>(&'static self, name: N, draw: F) -> WindowHandle
+    where
+        N: Into<String>,
+        F: FnMut(&Ui) + Send + 'static,
+    {
+        let name = name.into();
+        let mut registry = self.registry.lock();
+
+        let id = registry.next_id;
+        registry.next_id += 1;
+
+        registry.windows.retain(|window| window.name != name);
+        registry.state.entry(name.clone()).or_insert(true);
+        registry.windows.push(Window {
+            id,
+            name: name.clone(),
+            draw: Arc::new(Mutex::new(Box::new(draw))),
+        });
+
+        WindowHandle {
+            windows: self,
+            id,
+            name,
+        }
+    }
+
+    /// Whether the window named `name` is open, returning `None` if there is
+    /// no window with that name registered.
+    #[must_use]
+    pub fn is_open(&self, name: &str) -> Option<bool> {
+        let registry = self.registry.lock();
+        registry
+            .windows
+            .iter()
+            .any(|window| window.name == name)
+            .then(|| registry.is_open(name))
+    }
+
+    /// Open or close the window named `name`.
+    ///
+    /// This is remembered even if there is no window with that name currently
+    /// registered.
+    pub fn set_open(&self, name: &str, open: bool) {
+        let mut registry = self.registry.lock();
+        if registry.state.insert(name.to_owned(), open) != Some(open) {
+            registry.save();
+        }
+    }
+
+    /// Load whether each window is open from the state file at `path`, and save
+    /// to it whenever that changes.
+    pub(crate) fn load(&self, path: PathBuf) {
+        let mut registry = self.registry.lock();
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => registry.state.extend(parse_state(&contents)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => {
+                error!(%error, path = %path.display(), "could not load window state");
+            }
+        }
+
+        registry.path = Some(path);
+    }
+
+    /// Unregister every window, and forget all state.
+    pub(crate) fn clear(&self) {
+        let windows = {
+            let mut registry = self.registry.lock();
+            registry.state.clear();
+            registry.path = None;
+            std::mem::take(&mut registry.windows)
+        };
+
+        // The windows are dropped outside of the lock, as dropping a draw
+        // function may drop a WindowHandle, which needs the lock.
+        drop(windows);
+    }
+
+    /// Draw the menu for toggling the windows, listed under `menu`, and every
+    /// window that is open.
+    pub(crate) fn render(&self, ui: &Ui, menu: &str) {
+        // The windows are drawn outside of the lock, so that drawing a window
+        // may register or unregister windows.
+        let windows = {
+            let registry = self.registry.lock();
+            registry
+                .windows
+                .iter()
+                .map(|window| {
+                    (
+                        window.name.clone(),
+                        registry.is_open(&window.name),
+                        Arc::clone(&window.draw),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if windows.is_empty() {
+            return;
+        }
+
+        let mut toggled = None;
+        if imguilib::begin_main_menu_bar() {
+            if imguilib::begin_menu(menu) {
+                for (name, open, _) in &windows {
+                    if imguilib::menu_item(name, *open) {
+                        toggled = Some(name.as_str());
+                    }
+                }
+
+                imguilib::end_menu();
+            }
+
+            imguilib::end_main_menu_bar();
+        }
+
+        for (name, open, draw) in &windows {
+            let open = if toggled == Some(name.as_str()) {
+                self.set_open(name, !open);
+                !open
+            }
+            else {
+                *open
+            };
+
+            if !open {
+                continue;
+            }
+
+            let mut still_open = true;
+            if imguilib::begin_closable(name, &mut still_open) {
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| (draw.lock())(ui)));
+                if let Err(error) = result {
+                    error!(?error, window = %name, "caught an unwind drawing window");
+                }
+            }
+
+            // End must always be called, no matter what Begin returned, or
+            // whether drawing the window panicked.
+            imguilib::end();
+
+            if !still_open {
+                self.set_open(name, false);
+            }
+        }
+    }
+
+    fn unregister(&self, id: u64) {
+        let window = {
+            let mut registry = self.registry.lock();
+            let index = registry.windows.iter().position(|window| window.id == id);
+            index.map(|index| registry.windows.remove(index))
+        };
+
+        drop(window);
+    }
+}
+
+impl fmt::Debug for Windows {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registry = self.registry.lock();
+        f.debug_struct("Windows")
+            .field(
+                "windows",
+                &registry
+                    .windows
+                    .iter()
+                    .map(|window| (&window.name, registry.is_open(&window.name)))
+                    .collect::<Vec<_>>(),
+            )
+            .field("path", &registry.path)
+            .finish()
+    }
+}
+
+impl Registry {
+    fn is_open(&self, name: &str) -> bool {
+        self.state.get(name).copied().unwrap_or(true)
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path
+        else {
+            return;
+        };
+
+        if let Err(error) = std::fs::write(path, format_state(&self.state)) {
+            error!(%error, path = %path.display(), "could not save window state");
+        }
+    }
+}
+
+/// A handle to a window registered with [`Windows::register()`], which
+/// unregisters the window when dropped.
+pub struct WindowHandle {
+    windows: &'static Windows,
+    id:      u64,
+    name:    String,
+}
+
+impl WindowHandle {
+    /// The name of the window.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the window is open.
+    ///
+    /// This is `false` if the window has since been unregistered, such as by
+    /// registering another window with the same name.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        let registry = self.windows.registry.lock();
+        registry.windows.iter().any(|window| window.id == self.id)
+            && registry.is_open(&self.name)
+    }
+
+    /// Open or close the window.
+    pub fn set_open(&self, open: bool) {
+        self.windows.set_open(&self.name, open);
+    }
+}
+
+impl Drop for WindowHandle {
+    fn drop(&mut self) {
+        self.windows.unregister(self.id);
+    }
+}
+
+impl fmt::Debug for WindowHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowHandle")
+            .field("name", &self.name)
+            .field("open", &self.is_open())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The path to the state file for the plugin named `plugin`.
+pub(crate) fn state_path(plugin: &str) -> PathBuf {
+    state_path_in(crate::mq::paths().config(), plugin)
+}
+
+fn state_path_in(config: &Path, plugin: &str) -> PathBuf {
+    config.join(format!("{plugin}.windows.ini"))
+}
+
+fn parse_state(contents: &str) -> impl Iterator<Item = (String, bool)> + '_ {
+    contents
+        .lines()
+        .skip_while(|line| line.trim() != SECTION)
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .filter_map(|line| {
+            // Names may contain an "=", but the value never does.
+            let (name, value) = line.rsplit_once('=')?;
+            let open = match value.trim() {
+                "1" => true,
+                "0" => false,
+                _ => return None,
+            };
+
+            Some((name.trim().to_owned(), open))
+        })
+}
+
+fn format_state(state: &BTreeMap<String, bool>) -> String {
+    let mut contents = format!("{SECTION}\n");
+    for (name, open) in state {
+        // A name that can't be read back is not worth remembering.
+        if name.contains('\n') || name.trim() != name || name.starts_with('[') {
+            continue;
+        }
+
+        let _ = writeln!(contents, "{name}={}", u8::from(*open));
+    }
+
+    contents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> &'static Windows {
+        Box::leak(Box::new(Windows::new()))
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("macroquest-windows-{}-{name}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        state_path_in(&dir, "MyPlugin")
+    }
+
+    #[test]
+    fn test_register_and_drop() {
+        let windows = registry();
+
+        let handle = windows.register("One", |_| {});
+        assert_eq!(windows.is_open("One"), Some(true));
+        assert!(handle.is_open());
+
+        drop(handle);
+        assert_eq!(windows.is_open("One"), None);
+    }
+
+    #[test]
+    fn test_toggle_independently() {
+        let windows = registry();
+
+        let one = windows.register("One", |_| {});
+        let two = windows.register("Two", |_| {});
+
+        one.set_open(false);
+        assert!(!one.is_open());
+        assert!(two.is_open());
+
+        windows.set_open("Two", false);
+        one.set_open(true);
+        assert!(one.is_open());
+        assert!(!two.is_open());
+    }
+
+    #[test]
+    fn test_remembered_across_reloads() {
+        let path = temp_path("reload");
+        let _ = std::fs::remove_file(&path);
+
+        let windows = registry();
+        windows.load(path.clone());
+        let one = windows.register("One", |_| {});
+        let two = windows.register("Two", |_| {});
+        one.set_open(false);
+
+        // Unloading the plugin unregisters every window, and the handles are
+        // then dropped along with the plugin.
+        windows.clear();
+        assert!(!one.is_open());
+        drop((one, two));
+
+        windows.load(path.clone());
+        let one = windows.register("One", |_| {});
+        let two = windows.register("Two", |_| {});
+        assert!(!one.is_open());
+        assert!(two.is_open());
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "[Windows]\nOne=0\nTwo=1\n"
+        );
+    }
+
+    #[test]
+    fn test_same_name_replaces() {
+        let windows = registry();
+
+        let first = windows.register("One", |_| {});
+        let second = windows.register("One", |_| {});
+
+        assert!(!first.is_open());
+        assert!(second.is_open());
+
+        // Dropping the replaced handle doesn't unregister the new window.
+        drop(first);
+        assert_eq!(windows.is_open("One"), Some(true));
+    }
+
+    #[test]
+    fn test_parse_state() {
+        let contents = "[Other]\nOne=0\n\n[Windows]\nOne=0\nA = B=1\nbad\nTwo=yes\n[After]\nThree=0\n";
+
+        assert_eq!(
+            parse_state(contents).collect::<Vec<_>>(),
+            [("One".to_string(), false), ("A = B".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn test_format_state_skips_unreadable_names() {
+        let state = BTreeMap::from([
+            ("One".to_string(), true),
+            ("Two\nLines".to_string(), false),
+            ("[Section]".to_string(), false),
+        ]);
+
+        assert_eq!(format_state(&state), "[Windows]\nOne=1\n");
+        assert_eq!(
+            parse_state(&format_state(&state)).collect::<Vec<_>>(),
+            [("One".to_string(), true)]
+        );
+    }
+}
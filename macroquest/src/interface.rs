@@ -0,0 +1,516 @@
+//! Talking to other macroquest-rs plugins, through a stable interface.
+//!
+//! Rust doesn't have a stable ABI, so two plugins that were compiled separately
+//! can't safely share Rust types, even when they're both written with this
+//! crate. Instead, a plugin can export the small, stable interface declared by
+//! the `macroquest-interface` crate, which lets other plugins send it messages
+//! (as bytes, in whatever encoding the plugins agree on), and ask it for its
+//! capabilities (strings that describe what messages it understands).
+//!
+//! A plugin that wants to receive messages exports the interface with
+//! [`export!`], and then [serves](serve()) it once it's initialized:
+//!
+//! ```no_run
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MyPlugin);
+//! # struct MyPlugin;
+//! # impl Plugin for MyPlugin { fn new() -> Self { MyPlugin } }
+//! # impl Hooks for MyPlugin {}
+//! use macroquest::interface;
+//!
+//! interface::export!();
+//!
+//! let endpoint = interface::serve("MyPlugin", &["ping"], |message| {
+//!     if message.payload == b"ping" {
+//!         interface::send(&message.sender, b"pong").ok();
+//!     }
+//! })
+//! .unwrap();
+//! ```
+//!
+//! Sending a message to another plugin requires serving our own interface,
+//! so that the other plugin knows who it's from (and can reply).
+//!
+//! # Versions
+//!
+//! Before using another plugin's interface, we check that it's the same
+//! version as ours, failing with [`InterfaceError::IncompatibleVersion`] if it
+//! isn't, rather than guessing at how to talk to it.
+//!
+//! # Lifetimes
+//!
+//! The other plugin's interface is looked up each time that it's used, as it
+//! may have been unloaded (or reloaded) since the last time, so nothing here
+//! ever holds onto a pointer into another plugin. Our own interface stops
+//! accepting messages when its [`Endpoint`] is dropped, or when the plugin is
+//! unloaded.
+//!
+//! Everything in here must be called from the game's main thread.
+
+use std::ffi::c_void;
+use std::{error, fmt};
+
+use macroquest_interface::{self as raw, Bytes, ExportFn, Interface};
+use parking_lot::Mutex;
+
+use crate::ffi::mq as mqlib;
+use crate::log::error;
+use crate::macros::debug_assert_main_thread;
+use crate::mq;
+use crate::plugin::panic_message;
+
+pub use macroquest_interface::VERSION;
+
+type Handler = Box<dyn FnMut(Message) + Send>;
+
+static ENDPOINT: Mutex<Option<State>> = Mutex::new(None);
+
+// The interface that's exported by export!(), which stays the same for as long
+// as the plugin is loaded, while what it does depends on ENDPOINT.
+static INTERFACE: Interface = Interface {
+    version:      raw::VERSION,
+    send:         receive,
+    capabilities: visit_capabilities,
+};
+
+/// Export this plugin's interface, so that other plugins can find it.
+///
+/// This must be used exactly once, at the top level of the plugin's crate,
+/// alongside [`setup!`](crate::plugin::setup). The interface doesn't accept any
+/// messages until it's [served](serve()).
+#[doc(inline)]
+pub use crate::__interface_export as export;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __interface_export {
+    () => {
+        // The name of this function has to match EXPORT_SYMBOL from the
+        // macroquest-interface crate.
+        #[no_mangle]
+        pub extern "C" fn MQRustInterface(
+        ) -> *const ::macroquest::interface::__private::Interface {
+            ::macroquest::interface::__private::interface()
+        }
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use macroquest_interface::Interface;
+
+    #[must_use]
+    pub fn interface() -> *const Interface {
+        std::ptr::addr_of!(super::INTERFACE)
+    }
+}
+
+/// A message sent to us by another plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// The name of the plugin that sent the message.
+    pub sender:  String,
+    /// The message itself.
+    pub payload: Vec<u8>,
+}
+
+/// Serve this plugin's interface as `name` (which should be the name of the
+/// plugin), calling `handler` with every message that's sent to it, and
+/// advertising `capabilities` to other plugins.
+///
+/// The interface must also have been exported with [`export!`], otherwise
+/// other plugins can't find it. It stops accepting messages when the returned
+/// [`Endpoint`] is dropped, or when the plugin is unloaded, whichever comes
+/// first.
+///
+/// # Errors
+///
+/// Returns an error if the interface is already being served.
+pub fn serve<F>(
+    name: &str,
+    capabilities: &[&str],
+    handler: F,
+) -> Result<Endpoint, InterfaceError>
+where
+    F: FnMut(Message) + Send + 'static,
+{
+    debug_assert_main_thread!("macroquest::interface::serve");
+
+    let mut endpoint = ENDPOINT.lock();
+    if endpoint.is_some() {
+        return Err(InterfaceError::AlreadyServing);
+    }
+
+    *endpoint = Some(State::new(name, capabilities, Box::new(handler)));
+
+    Ok(Endpoint { _private: () })
+}
+
+/// This plugin's interface, while it's being served.
+///
+/// The interface stops accepting messages when this is dropped.
+#[derive(Debug)]
+pub struct Endpoint {
+    _private: (),
+}
+
+impl Drop for Endpoint {
+    fn drop(&mut self) {
+        debug_assert_main_thread!("macroquest::interface::Endpoint::drop");
+        clear();
+    }
+}
+
+/// Send `payload` to the plugin named `plugin`.
+///
+/// The message is delivered (and handled by the other plugin) before this
+/// returns.
+///
+/// # Errors
+///
+/// Returns an error if we aren't serving our own interface, if `plugin` isn't
+/// loaded, doesn't export an interface (or exports a different version of it),
+/// or doesn't accept the message.
+pub fn send(plugin: &str, payload: &[u8]) -> Result<(), InterfaceError> {
+    debug_assert_main_thread!("macroquest::interface::send");
+
+    // The lock isn't held while the message is sent, as the other plugin may
+    // send a reply to us before returning.
+    let sender = ENDPOINT
+        .lock()
+        .as_ref()
+        .map(|state| state.name.clone())
+        .ok_or(InterfaceError::NotServing)?;
+
+    let interface = resolve(plugin)?;
+
+    // SAFETY: The interface is the version that we expect, and the plugin
+    //         stays loaded until we return, as plugins are only unloaded from
+    //         the main thread.
+    let accepted =
+        unsafe { (interface.send)(Bytes::new(sender.as_bytes()), Bytes::new(payload)) };
+
+    if !accepted {
+        return Err(InterfaceError::Rejected {
+            plugin: plugin.to_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+/// The capabilities that the plugin named `plugin` advertises.
+///
+/// # Errors
+///
+/// Returns an error if `plugin` isn't loaded, or doesn't export an interface
+/// (or exports a different version of it).
+pub fn capabilities(plugin: &str) -> Result<Vec<String>, InterfaceError> {
+    debug_assert_main_thread!("macroquest::interface::capabilities");
+
+    let interface = resolve(plugin)?;
+    let mut capabilities: Vec<String> = Vec::new();
+
+    // SAFETY: See send(), and capabilities outlives the call.
+    unsafe {
+        (interface.capabilities)(
+            std::ptr::addr_of_mut!(capabilities).cast(),
+            collect_capability,
+        );
+    }
+
+    Ok(capabilities)
+}
+
+/// Whether the plugin named `plugin` advertises `capability`.
+///
+/// # Errors
+///
+/// Returns an error if `plugin` isn't loaded, or doesn't export an interface
+/// (or exports a different version of it).
+pub fn has_capability(plugin: &str, capability: &str) -> Result<bool, InterfaceError> {
+    Ok(capabilities(plugin)?.iter().any(|c| c == capability))
+}
+
+/// An error using another plugin's interface, or serving our own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InterfaceError {
+    /// Our interface is already being served.
+    AlreadyServing,
+    /// We aren't serving our interface, which is needed to send messages.
+    NotServing,
+    /// The plugin isn't loaded.
+    NotLoaded {
+        /// The name of the plugin.
+        plugin: String,
+    },
+    /// The plugin doesn't export an interface.
+    NotExported {
+        /// The name of the plugin.
+        plugin: String,
+    },
+    /// The plugin exports a different version of the interface than ours.
+    IncompatibleVersion {
+        /// The name of the plugin.
+        plugin:   String,
+        /// The version of our interface.
+        expected: u32,
+        /// The version of the plugin's interface.
+        found:    u32,
+    },
+    /// The plugin didn't accept the message, as it isn't serving its
+    /// interface.
+    Rejected {
+        /// The name of the plugin.
+        plugin: String,
+    },
+}
+
+impl fmt::Display for InterfaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterfaceError::AlreadyServing => {
+                f.write_str("the interface is already being served")
+            }
+            InterfaceError::NotServing => {
+                f.write_str("the interface is not being served")
+            }
+            InterfaceError::NotLoaded { plugin } => write!(f, "{plugin} is not loaded"),
+            InterfaceError::NotExported { plugin } => {
+                write!(f, "{plugin} does not export an interface")
+            }
+            InterfaceError::IncompatibleVersion {
+                plugin,
+                expected,
+                found,
+            } => {
+                write!(
+                    f,
+                    "incompatible interface version: {plugin} uses version {found}, \
+                     but we use version {expected}"
+                )
+            }
+            InterfaceError::Rejected { plugin } => {
+                write!(f, "{plugin} did not accept the message")
+            }
+        }
+    }
+}
+
+impl error::Error for InterfaceError {}
+
+/// Stop serving our interface, as the plugin is being unloaded.
+pub(crate) fn clear() {
+    ENDPOINT.lock().take();
+}
+
+/// Look up the interface that the plugin named `plugin` exports, checking that
+/// it's the same version as ours.
+fn resolve(plugin: &str) -> Result<Interface, InterfaceError> {
+    if !mq::is_plugin_loaded(plugin) {
+        return Err(InterfaceError::NotLoaded {
+            plugin: plugin.to_owned(),
+        });
+    }
+
+    let export = mqlib::get_plugin_proc(plugin, raw::EXPORT_SYMBOL);
+    if export == 0 {
+        return Err(InterfaceError::NotExported {
+            plugin: plugin.to_owned(),
+        });
+    }
+
+    // SAFETY: Anything exported as EXPORT_SYMBOL is an ExportFn, and the
+    //         plugin stays loaded until we return.
+    let interface = unsafe { std::mem::transmute::<usize, ExportFn>(export)() };
+    if interface.is_null() {
+        return Err(InterfaceError::NotExported {
+            plugin: plugin.to_owned(),
+        });
+    }
+
+    // SAFETY: The version is the first field of every version of the
+    //         interface, so it's the one thing that's safe to read before
+    //         we've checked it.
+    let found = unsafe { std::ptr::addr_of!((*interface).version).read() };
+    check_version(plugin, found)?;
+
+    // SAFETY: The interface is the version that we expect.
+    Ok(unsafe { *interface })
+}
+
+fn check_version(plugin: &str, found: u32) -> Result<(), InterfaceError> {
+    if found != VERSION {
+        return Err(InterfaceError::IncompatibleVersion {
+            plugin: plugin.to_owned(),
+            expected: VERSION,
+            found,
+        });
+    }
+
+    Ok(())
+}
+
+/// Called by other plugins to send us a message.
+unsafe extern "C" fn receive(sender: Bytes, payload: Bytes) -> bool {
+    // SAFETY: The other plugin guarantees that both are valid for this call.
+    let (sender, payload) = unsafe { (sender.as_slice(), payload.as_slice()) };
+    let Ok(sender) = std::str::from_utf8(sender)
+    else {
+        return false;
+    };
+
+    let message = Message {
+        sender:  sender.to_owned(),
+        payload: payload.to_vec(),
+    };
+
+    // The handler is taken out while it's called, so that it can send messages
+    // itself (including replies, which may call back into us) without
+    // deadlocking.
+    let Some(mut handler) = ENDPOINT.lock().as_mut().and_then(State::take)
+    else {
+        return false;
+    };
+
+    // A panic must never unwind into the other plugin.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        handler(message);
+    }));
+
+    if let Err(payload) = result {
+        error!(
+            sender,
+            error = panic_message(&*payload),
+            "interface handler panicked"
+        );
+    }
+
+    if let Some(state) = ENDPOINT.lock().as_mut() {
+        state.restore(handler);
+    }
+
+    true
+}
+
+/// Called by other plugins to list our capabilities.
+unsafe extern "C" fn visit_capabilities(context: *mut c_void, visit: raw::VisitFn) {
+    if let Some(state) = ENDPOINT.lock().as_ref() {
+        for capability in &state.capabilities {
+            // SAFETY: The other plugin guarantees that visit can be called
+            //         with the context that it gave us.
+            unsafe { visit(context, Bytes::new(capability.as_bytes())) };
+        }
+    }
+}
+
+/// Called by other plugins with each of their capabilities, collecting them
+/// into the Vec<String> that the context points to.
+unsafe extern "C" fn collect_capability(context: *mut c_void, capability: Bytes) {
+    // SAFETY: The context is always the Vec<String> given by capabilities(),
+    //         and the capability is valid for this call.
+    let (capabilities, capability) =
+        unsafe { (&mut *context.cast::<Vec<String>>(), capability.as_slice()) };
+
+    capabilities.push(String::from_utf8_lossy(capability).into_owned());
+}
+
+struct State {
+    name:         String,
+    capabilities: Vec<String>,
+    // The handler is None while it's being called.
+    handler:      Option<Handler>,
+}
+
+impl State {
+    fn new(name: &str, capabilities: &[&str], handler: Handler) -> State {
+        State {
+            name:         name.to_owned(),
+            capabilities: capabilities.iter().map(|&c| c.to_owned()).collect(),
+            handler:      Some(handler),
+        }
+    }
+
+    /// Take the handler out to call it.
+    fn take(&mut self) -> Option<Handler> {
+        self.handler.take()
+    }
+
+    /// Put the handler back after calling it.
+    fn restore(&mut self, handler: Handler) {
+        self.handler.get_or_insert(handler);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_symbol() {
+        // export!() exports a function with this name.
+        assert_eq!(raw::EXPORT_SYMBOL, "MQRustInterface");
+    }
+
+    #[test]
+    fn test_check_version() {
+        assert_eq!(check_version("MQRustPong", VERSION), Ok(()));
+        assert_eq!(
+            check_version("MQRustPong", VERSION + 1),
+            Err(InterfaceError::IncompatibleVersion {
+                plugin:   "MQRustPong".into(),
+                expected: VERSION,
+                found:    VERSION + 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_collect_capabilities() {
+        let mut capabilities: Vec<String> = Vec::new();
+        let context = std::ptr::addr_of_mut!(capabilities).cast();
+
+        // SAFETY: The context is a Vec<String>, and the bytes outlive the
+        // calls.
+        unsafe {
+            collect_capability(context, Bytes::new(b"ping"));
+            collect_capability(context, Bytes::new(b"pong"));
+        }
+
+        assert_eq!(capabilities, ["ping", "pong"]);
+    }
+
+    #[test]
+    fn test_state_take_and_restore() {
+        let mut state = State::new("MQRustPing", &["ping"], Box::new(|_| {}));
+        assert_eq!(state.capabilities, ["ping"]);
+
+        let handler = state.take().unwrap();
+        assert!(state.take().is_none());
+
+        state.restore(handler);
+        assert!(state.take().is_some());
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            InterfaceError::IncompatibleVersion {
+                plugin:   "MQRustPong".into(),
+                expected: 1,
+                found:    2,
+            }
+            .to_string(),
+            "incompatible interface version: MQRustPong uses version 2, but we use \
+             version 1"
+        );
+        assert_eq!(
+            InterfaceError::Rejected {
+                plugin: "MQRustPong".into(),
+            }
+            .to_string(),
+            "MQRustPong did not accept the message"
+        );
+    }
+}
@@ -0,0 +1,474 @@
+//! Messaging other characters with MQ2DanNet, or with MQ2EQBC when DanNet
+//! isn't loaded.
+//!
+//! Messages are sent with the same commands that a player would use (`/dtell`
+//! and `/dgtell` for DanNet, or `/bct` and `/bc` for EQBC), and peers are
+//! listed from their top level objects.
+//!
+//! Both plugins show the messages that they receive as lines of chat (ex:
+//! `[Alice] hello`), so receiving messages is built on the plugin's own
+//! [`Hooks::write_chat()`](crate::plugin::Hooks::write_chat), which has to
+//! hand each line to [`handle_chat()`] for any [observers](observe) to see it.
+//!
+//! ```no_run
+//! use macroquest::eq::ChatColor;
+//! use macroquest::interop::dannet::{self, Recipient};
+//! use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MyPlugin);
+//! # #[derive(Debug)]
+//! # struct MyPlugin;
+//! # impl Plugin for MyPlugin { fn new() -> Self { MyPlugin } }
+//!
+//! #[macroquest::plugin::hooks]
+//! impl Hooks for MyPlugin {
+//!     fn initialize(&self) {
+//!         dannet::observe("ping", |message| {
+//!             let to = Recipient::Peer(message.from.clone());
+//!             dannet::send(&to, "pong").ok();
+//!         });
+//!     }
+//!
+//!     fn write_chat(&self, line: &str, _color: ChatColor) {
+//!         dannet::handle_chat(line);
+//!     }
+//! }
+//! ```
+//!
+//! Sending and listing peers must be done from the game's main thread.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::{error, fmt};
+
+use parking_lot::Mutex;
+
+use crate::log::error;
+use crate::mq;
+use crate::plugin::panic_message;
+
+static OBSERVERS: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+/// The plugins that messages can be sent with.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Backend {
+    /// MQ2DanNet
+    DanNet,
+    /// MQ2EQBC
+    Eqbc,
+}
+
+impl Backend {
+    /// The name of the plugin.
+    #[must_use]
+    pub fn plugin(self) -> &'static str {
+        match self {
+            Backend::DanNet => "MQ2DanNet",
+            Backend::Eqbc => "MQ2EQBC",
+        }
+    }
+
+    /// The command that sends `message` to `to` with this plugin.
+    fn command(self, to: &Recipient, message: &str) -> String {
+        match (self, to) {
+            (Backend::DanNet, Recipient::Peer(peer)) => {
+                format!("/dtell {peer} {message}")
+            }
+            (Backend::DanNet, Recipient::Channel(channel)) => {
+                format!("/dgtell {channel} {message}")
+            }
+            (Backend::DanNet, Recipient::All) => format!("/dgtell all {message}"),
+            // EQBC sends to channels the same way that it sends to characters.
+            (Backend::Eqbc, Recipient::Peer(name) | Recipient::Channel(name)) => {
+                format!("/bct {name} {message}")
+            }
+            (Backend::Eqbc, Recipient::All) => format!("/bc {message}"),
+        }
+    }
+
+    /// The expression that lists the connected peers with this plugin.
+    fn peers_expression(self) -> &'static str {
+        match self {
+            Backend::DanNet => "${DanNet.Peers}",
+            Backend::Eqbc => "${EQBC.Names}",
+        }
+    }
+
+    /// Split the list of peers from [`Backend::peers_expression()`].
+    fn parse_peers(self, peers: &str) -> Vec<String> {
+        if peers == "NULL" {
+            return vec![];
+        }
+
+        let split: Box<dyn Iterator<Item = &str>> = match self {
+            Backend::DanNet => Box::new(peers.split('|')),
+            Backend::Eqbc => Box::new(peers.split_whitespace()),
+        };
+
+        split
+            .map(str::trim)
+            .filter(|peer| !peer.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+}
+
+/// Who to send a message to.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Recipient {
+    /// A single character (for DanNet, the peer name, which may include the
+    /// server, as in `server_name`).
+    Peer(String),
+    /// Every character in a channel (for DanNet, a group such as `group` or
+    /// `raid`).
+    Channel(String),
+    /// Every connected character.
+    All,
+}
+
+/// A message received from another character.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Message {
+    /// Who sent the message.
+    pub from: String,
+    /// The text of the message.
+    pub text: String,
+}
+
+impl Message {
+    /// Parse a message out of a line of chat (ex: `[Alice] hello`), ignoring
+    /// any color codes.
+    fn parse(line: &str) -> Option<Message> {
+        let line = strip_colors(line);
+        let rest = line.trim_start().strip_prefix('[')?;
+        let (from, text) = rest.split_once(']')?;
+
+        // Senders may be followed by where the message was sent, as in
+        // `Alice(group)`.
+        let from = from.split('(').next().unwrap_or(from).trim();
+        if from.is_empty() || from.contains(char::is_whitespace) {
+            return None;
+        }
+
+        Some(Message {
+            from: from.to_owned(),
+            text: text.trim().to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.from, self.text)
+    }
+}
+
+/// An error messaging other characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MessagingError {
+    /// Neither MQ2DanNet nor MQ2EQBC is loaded.
+    NotLoaded,
+    /// The plugin couldn't list its peers.
+    NoPeers,
+}
+
+impl fmt::Display for MessagingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessagingError::NotLoaded => {
+                f.write_str("neither MQ2DanNet nor MQ2EQBC is loaded")
+            }
+            MessagingError::NoPeers => f.write_str("could not list peers"),
+        }
+    }
+}
+
+impl error::Error for MessagingError {}
+
+/// The plugin that messages will be sent with, which is DanNet if it's loaded,
+/// then EQBC if it's loaded.
+#[must_use]
+pub fn backend() -> Option<Backend> {
+    [Backend::DanNet, Backend::Eqbc]
+        .into_iter()
+        .find(|backend| mq::is_plugin_loaded(backend.plugin()))
+}
+
+/// Send `message` to `to`.
+///
+/// # Errors
+///
+/// Returns an error, without sending anything, if neither plugin is loaded.
+pub fn send(to: &Recipient, message: &str) -> Result<(), MessagingError> {
+    let backend = backend().ok_or(MessagingError::NotLoaded)?;
+    mq::do_command(&backend.command(to, message));
+
+    Ok(())
+}
+
+/// The names of the other characters that are connected.
+///
+/// # Errors
+///
+/// Returns an error if neither plugin is loaded, or if the plugin couldn't
+/// list its peers.
+pub fn peers() -> Result<Vec<String>, MessagingError> {
+    let backend = backend().ok_or(MessagingError::NotLoaded)?;
+    let peers = mq::parse_macro_data(backend.peers_expression())
+        .ok_or(MessagingError::NoPeers)?;
+
+    Ok(backend.parse_peers(&peers))
+}
+
+/// A handle to an observer registered with [`observe()`], which can be used
+/// to cancel it.
+///
+/// Dropping the handle does *not* cancel the observer.
+#[derive(Clone)]
+pub struct Observer {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Observer {
+    /// Cancel the observer, so that it isn't given any more messages.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the observer has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observer")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+struct Entry {
+    prefix:    String,
+    cancelled: Arc<AtomicBool>,
+    f:         Box<dyn FnMut(&Message) + Send>,
+}
+
+/// Call `f` with every message received whose text starts with `prefix`.
+///
+/// Messages are only received while the plugin hands its chat to
+/// [`handle_chat()`].
+pub fn observe<F>(prefix: &str, f: F) -> Observer
+where
+    F: FnMut(&Message) + Send + 'static,
+{
+    let cancelled = Arc::new(AtomicBool::new(false));
+    OBSERVERS.lock().push(Entry {
+        prefix:    prefix.to_owned(),
+        cancelled: Arc::clone(&cancelled),
+        f:         Box::new(f),
+    });
+
+    Observer { cancelled }
+}
+
+/// Give a line of chat to the observers, returning whether it was a message
+/// that any of them were given.
+///
+/// This should be called with every line from the plugin's
+/// [`Hooks::write_chat()`](crate::plugin::Hooks::write_chat).
+pub fn handle_chat(line: &str) -> bool {
+    if OBSERVERS.lock().is_empty() {
+        return false;
+    }
+
+    let Some(message) = Message::parse(line)
+    else {
+        return false;
+    };
+
+    // The observers are run without holding the lock, so that they are able
+    // to observe (or cancel) other observers.
+    let mut observers = std::mem::take(&mut *OBSERVERS.lock());
+    let handled = dispatch(&mut observers, &message);
+
+    let mut current = OBSERVERS.lock();
+    observers.append(&mut current);
+    *current = observers;
+
+    handled
+}
+
+/// Drop all observers.
+pub(crate) fn clear() {
+    let observers = std::mem::take(&mut *OBSERVERS.lock());
+    drop(observers);
+}
+
+/// Give `message` to every observer whose prefix it matches, dropping any that
+/// have been cancelled (or that panic).
+fn dispatch(observers: &mut Vec<Entry>, message: &Message) -> bool {
+    let mut handled = false;
+
+    observers.retain_mut(|entry| {
+        if entry.cancelled.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        if !message.text.starts_with(&entry.prefix) {
+            return true;
+        }
+
+        handled = true;
+        match panic::catch_unwind(AssertUnwindSafe(|| (entry.f)(message))) {
+            Ok(()) => !entry.cancelled.load(Ordering::Relaxed),
+            Err(payload) => {
+                error!(
+                    error = panic_message(payload.as_ref()),
+                    "message observer panicked, it will not be run again"
+                );
+                false
+            }
+        }
+    });
+
+    handled
+}
+
+/// Remove the MacroQuest color codes (ex: `\ay`, `\a-r`, and `\a#ff0000`)
+/// from `line`.
+fn strip_colors(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' || chars.peek() != Some(&'a') {
+            stripped.push(c);
+            continue;
+        }
+
+        // Skip the `a`, and then the color, which is a single character
+        // unless it's dark (`-r`) or a hex color (`#ff0000`).
+        chars.next();
+        match chars.next() {
+            Some('-') => {
+                chars.next();
+            }
+            Some('#') => chars.by_ref().take(6).for_each(drop),
+            _ => {}
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    fn entry(prefix: &str, count: &Arc<AtomicUsize>) -> (Observer, Entry) {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let count = Arc::clone(count);
+        let entry = Entry {
+            prefix:    prefix.to_owned(),
+            cancelled: Arc::clone(&cancelled),
+            f:         Box::new(move |_| {
+                count.fetch_add(1, Ordering::SeqCst);
+            }),
+        };
+
+        (Observer { cancelled }, entry)
+    }
+
+    fn message(text: &str) -> Message {
+        Message {
+            from: "Alice".to_owned(),
+            text: text.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_commands() {
+        let peer = Recipient::Peer("Bob".to_owned());
+        let group = Recipient::Channel("group".to_owned());
+
+        assert_eq!(Backend::DanNet.command(&peer, "hi"), "/dtell Bob hi");
+        assert_eq!(Backend::DanNet.command(&group, "hi"), "/dgtell group hi");
+        assert_eq!(
+            Backend::DanNet.command(&Recipient::All, "hi"),
+            "/dgtell all hi"
+        );
+        assert_eq!(Backend::Eqbc.command(&peer, "hi"), "/bct Bob hi");
+        assert_eq!(Backend::Eqbc.command(&group, "hi"), "/bct group hi");
+        assert_eq!(Backend::Eqbc.command(&Recipient::All, "hi"), "/bc hi");
+    }
+
+    #[test]
+    fn test_parse_peers() {
+        assert_eq!(
+            Backend::DanNet.parse_peers("server_alice|server_bob|"),
+            ["server_alice", "server_bob"]
+        );
+        assert_eq!(Backend::Eqbc.parse_peers(" Alice Bob "), ["Alice", "Bob"]);
+        assert!(Backend::DanNet.parse_peers("").is_empty());
+        assert!(Backend::Eqbc.parse_peers("NULL").is_empty());
+    }
+
+    #[test]
+    fn test_strip_colors() {
+        assert_eq!(strip_colors(r"\ay[\axAlice\ay]\ax hi"), "[Alice] hi");
+        assert_eq!(strip_colors(r"\a-r[\a#ff0000Alice\ax]"), "[Alice]");
+        assert_eq!(strip_colors(r"C:\path\to"), r"C:\path\to");
+        assert_eq!(strip_colors(r"trailing\a"), "trailing");
+    }
+
+    #[test]
+    fn test_parse_message() {
+        assert_eq!(Message::parse("[Alice] ping 1"), Some(message("ping 1")));
+        assert_eq!(
+            Message::parse(r"\ao[ Alice ]\ax ping 1"),
+            Some(message("ping 1"))
+        );
+        assert_eq!(
+            Message::parse("[Alice(group)] ping 1"),
+            Some(message("ping 1"))
+        );
+        assert_eq!(Message::parse("You say, 'ping'"), None);
+        assert_eq!(Message::parse("[not a name] ping"), None);
+        assert_eq!(Message::parse("[] ping"), None);
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (_, matching) = entry("ping", &count);
+        let (cancelled, cancelled_entry) = entry("ping", &count);
+        let (_, not_matching) = entry("pong", &count);
+        let mut observers = vec![matching, cancelled_entry, not_matching];
+
+        cancelled.cancel();
+        assert!(dispatch(&mut observers, &message("ping 1")));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert_eq!(observers.len(), 2);
+
+        assert!(!dispatch(&mut observers, &message("hello")));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_dispatch_drops_panicking() {
+        let mut observers = vec![Entry {
+            prefix:    String::new(),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            f:         Box::new(|_| panic!("boom")),
+        }];
+
+        assert!(dispatch(&mut observers, &message("hello")));
+        assert!(observers.is_empty());
+    }
+}
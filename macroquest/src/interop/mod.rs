@@ -0,0 +1,10 @@
+//! Working with other MacroQuest plugins, from Rust.
+//!
+//! Each module wraps one plugin, which has to be loaded in MacroQuest for it
+//! to do anything. Every wrapper checks that its plugin is loaded (and that it
+//! exports what the wrapper expects) each time that it's used, and returns an
+//! error rather than crashing when it isn't, so plugins are free to be loaded
+//! and unloaded at any time.
+
+pub mod dannet;
+pub mod nav;
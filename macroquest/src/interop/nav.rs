@@ -0,0 +1,245 @@
+//! Pathing with MQ2Nav, the navigation mesh plugin.
+//!
+//! Queries (like [`path_length()`]) call the functions that MQ2Nav exports,
+//! which are looked up each time that they're used, as MQ2Nav may have been
+//! unloaded (or reloaded) since the last time. Navigating is done with the
+//! same `/nav` commands that a player would use.
+//!
+//! ```no_run
+//! use macroquest::interop::nav::{self, Destination};
+//!
+//! if nav::path_exists(&Destination::Target).unwrap_or(false) {
+//!     nav::navigate_to(&Destination::Target).unwrap();
+//! }
+//! ```
+//!
+//! Everything in here must be called from the game's main thread.
+
+use std::ffi::{c_char, CString};
+use std::{error, fmt};
+
+use crate::eq::Location;
+use crate::ffi::mq as mqlib;
+use crate::macros::debug_assert_main_thread;
+use crate::mq;
+
+/// The name of the MQ2Nav plugin.
+pub const PLUGIN: &str = "MQ2Nav";
+
+// The functions that MQ2Nav exports, which older versions may be missing.
+const MESH_LOADED: &str = "IsNavMeshLoaded";
+const PATH_ACTIVE: &str = "IsNavPathActive";
+const PATH_LENGTH: &str = "GetNavPathLength";
+
+type StatusFn = unsafe extern "C" fn() -> bool;
+type PathLengthFn = unsafe extern "C" fn(*const c_char) -> f32;
+
+/// Somewhere to navigate to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Destination {
+    /// A location in the current zone.
+    Location(Location),
+    /// The spawn with this spawn id.
+    Spawn(u32),
+    /// The current target.
+    Target,
+}
+
+/// Formats the destination as the arguments that `/nav` takes for it (ex:
+/// `locxyz 10 20 30`).
+impl fmt::Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Destination::Location(location) => {
+                write!(f, "locxyz {} {} {}", location.x, location.y, location.z)
+            }
+            Destination::Spawn(id) => write!(f, "id {id}"),
+            Destination::Target => f.write_str("target"),
+        }
+    }
+}
+
+impl From<Location> for Destination {
+    fn from(location: Location) -> Self {
+        Destination::Location(location)
+    }
+}
+
+/// An error using MQ2Nav.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NavError {
+    /// MQ2Nav isn't loaded.
+    NotLoaded,
+    /// MQ2Nav is loaded, but it doesn't export a function that we need, which
+    /// means that it's a version that we don't work with.
+    MissingExport {
+        /// The name of the missing function.
+        name: &'static str,
+    },
+    /// There's no navigation mesh loaded for the current zone.
+    NoMesh,
+    /// There's no path to the destination.
+    NoPath,
+}
+
+impl fmt::Display for NavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NavError::NotLoaded => write!(f, "{PLUGIN} is not loaded"),
+            NavError::MissingExport { name } => {
+                write!(f, "{PLUGIN} does not export {name} (is it out of date?)")
+            }
+            NavError::NoMesh => f.write_str("no navigation mesh is loaded"),
+            NavError::NoPath => f.write_str("no path to the destination"),
+        }
+    }
+}
+
+impl error::Error for NavError {}
+
+/// Whether MQ2Nav is loaded.
+#[must_use]
+pub fn is_loaded() -> bool {
+    mq::is_plugin_loaded(PLUGIN)
+}
+
+/// Whether MQ2Nav has a navigation mesh loaded for the current zone.
+///
+/// # Errors
+///
+/// Returns an error if MQ2Nav isn't loaded, or is a version that we don't work
+/// with.
+pub fn mesh_loaded() -> Result<bool, NavError> {
+    let mesh_loaded = resolve(MESH_LOADED)?;
+
+    // SAFETY: MQ2Nav exports this function with this signature, and it stays
+    //         loaded until we return, as plugins are only unloaded from the
+    //         main thread.
+    Ok(unsafe { std::mem::transmute::<usize, StatusFn>(mesh_loaded)() })
+}
+
+/// Whether MQ2Nav is currently navigating somewhere.
+///
+/// # Errors
+///
+/// Returns an error if MQ2Nav isn't loaded, or is a version that we don't work
+/// with.
+pub fn is_navigating() -> Result<bool, NavError> {
+    let path_active = resolve(PATH_ACTIVE)?;
+
+    // SAFETY: See mesh_loaded().
+    Ok(unsafe { std::mem::transmute::<usize, StatusFn>(path_active)() })
+}
+
+/// The length of the path to `destination`.
+///
+/// # Errors
+///
+/// Returns an error if MQ2Nav isn't loaded (or is a version that we don't work
+/// with), if there's no navigation mesh for the current zone, or if there's no
+/// path to `destination`.
+pub fn path_length(destination: &Destination) -> Result<f32, NavError> {
+    if !mesh_loaded()? {
+        return Err(NavError::NoMesh);
+    }
+
+    let path_length = resolve(PATH_LENGTH)?;
+    // A destination that can't be passed to MQ2Nav has no path to it, though
+    // formatting one never produces a nul.
+    let destination =
+        CString::new(destination.to_string()).map_err(|_| NavError::NoPath)?;
+
+    // SAFETY: See mesh_loaded(), and the destination outlives the call.
+    let length = unsafe {
+        std::mem::transmute::<usize, PathLengthFn>(path_length)(destination.as_ptr())
+    };
+
+    // MQ2Nav gives a negative length when there's no path.
+    if length < 0.0 {
+        return Err(NavError::NoPath);
+    }
+
+    Ok(length)
+}
+
+/// Whether there's a path to `destination`.
+///
+/// # Errors
+///
+/// Returns an error if MQ2Nav isn't loaded (or is a version that we don't work
+/// with), or if there's no navigation mesh for the current zone.
+pub fn path_exists(destination: &Destination) -> Result<bool, NavError> {
+    match path_length(destination) {
+        Ok(_) => Ok(true),
+        Err(NavError::NoPath) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Start navigating to `destination`, replacing wherever MQ2Nav was
+/// navigating to before.
+///
+/// # Errors
+///
+/// Returns an error, without navigating, if MQ2Nav isn't loaded (or is a
+/// version that we don't work with), if there's no navigation mesh for the
+/// current zone, or if there's no path to `destination`.
+pub fn navigate_to(destination: &Destination) -> Result<(), NavError> {
+    path_length(destination)?;
+    mq::do_command(&format!("/nav {destination}"));
+
+    Ok(())
+}
+
+/// Stop navigating.
+///
+/// # Errors
+///
+/// Returns an error if MQ2Nav isn't loaded.
+pub fn stop() -> Result<(), NavError> {
+    if !is_loaded() {
+        return Err(NavError::NotLoaded);
+    }
+
+    mq::do_command("/nav stop");
+
+    Ok(())
+}
+
+/// Look up the address of the function that MQ2Nav exports as `name`.
+fn resolve(name: &'static str) -> Result<usize, NavError> {
+    debug_assert_main_thread!("macroquest::interop::nav");
+
+    if !is_loaded() {
+        return Err(NavError::NotLoaded);
+    }
+
+    match mqlib::get_plugin_proc(PLUGIN, name) {
+        0 => Err(NavError::MissingExport { name }),
+        address => Ok(address),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destination_args() {
+        let location = Location::new(10.0, -20.5, 3.0);
+
+        assert_eq!(Destination::from(location).to_string(), "locxyz 10 -20.5 3");
+        assert_eq!(Destination::Spawn(1234).to_string(), "id 1234");
+        assert_eq!(Destination::Target.to_string(), "target");
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(NavError::NotLoaded.to_string(), "MQ2Nav is not loaded");
+        assert_eq!(
+            NavError::MissingExport { name: PATH_LENGTH }.to_string(),
+            "MQ2Nav does not export GetNavPathLength (is it out of date?)"
+        );
+    }
+}
@@ -17,65 +17,54 @@ pub use macroquest_sys as ffi;
 
 #[cfg(docsrs)]
 #[doc(hidden)]
-// I hate that we have to maintain this in order to get doc building to work
-// correctly on docs.rs, but such is life.
+// macroquest-sys can't be built on docs.rs, so build.rs generates a stub of it
+// from ffi_stub.txt instead (which tests/ffi_stub.rs checks is up to date).
 pub mod ffi {
-    pub mod eqlib {
-        pub struct PlayerClient;
-        pub struct EQGroundItem;
-    }
-
-    pub mod mq {
-        pub fn get_path_MQRoot() -> &'static str {
-            unimplemented!()
-        }
-
-        pub fn get_path_Config() -> &'static str {
-            unimplemented!()
-        }
-
-        pub fn get_path_MQini() -> &'static str {
-            unimplemented!()
-        }
-
-        pub fn get_path_Macros() -> &'static str {
-            unimplemented!()
-        }
-
-        pub fn get_path_Logs() -> &'static str {
-            unimplemented!()
-        }
-
-        pub fn get_path_CrashDumps() -> &'static str {
-            unimplemented!()
-        }
-
-        pub fn get_path_Plugins() -> &'static str {
-            unimplemented!()
-        }
-
-        pub fn get_path_Resources() -> &'static str {
-            unimplemented!()
-        }
-
-        pub fn get_path_EverQuest() -> &'static str {
-            unimplemented!()
-        }
+    include!(concat!(env!("OUT_DIR"), "/ffi_stub.rs"));
+}
 
-        pub fn write_chat_color(line: &str, color: i32) {
-            unimplemented!()
-        }
+// The derive macros generate paths rooted at ::macroquest, which lets them be
+// used within this crate too.
+extern crate self as macroquest;
 
-        pub struct MQPlugin;
-    }
-}
+#[doc(inline)]
+pub use macroquest_proc_macros::{ArgValue, CommandArgs, SettingsUi};
 
+pub mod actors;
+pub mod config;
 pub mod eq;
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+pub mod export;
+pub mod imgui;
+#[cfg_attr(docsrs, doc(cfg(feature = "interface")))]
+#[cfg(feature = "interface")]
+pub mod interface;
+#[cfg_attr(docsrs, doc(cfg(feature = "interop")))]
+#[cfg(feature = "interop")]
+pub mod interop;
 pub mod log;
 pub mod mq;
 pub mod plugin;
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+#[cfg(feature = "tokio")]
+pub mod rt;
 
 mod macros {
+    /// Asserts (in debug builds only) that we're being called from the game's
+    /// main thread, naming the function that was called when we're not.
+    ///
+    /// The function is named by the given path segments, which get joined with
+    /// `::`, and are only joined if the assertion fails.
+    macro_rules! debug_assert_main_thread {
+        ($($segment:expr),+ $(,)?) => {{
+            #[cfg(debug_assertions)]
+            $crate::mq::assert_main_thread(&[$($segment),+]);
+        }};
+    }
+
+    pub(crate) use debug_assert_main_thread;
+
     #[allow(missing_docs)]
     #[macro_export]
     macro_rules! println {
@@ -84,7 +73,7 @@ mod macros {
         ($($arg:tt)*) => {
             ::macroquest::mq::write_chat_color(
                 format!($($arg)*),
-                ::macroquest::eq::ChatColor::ChatChannel,
+                ::macroquest::eq::Channel::ChatChannel,
             );
         };
     }
@@ -108,6 +97,70 @@ pub const fn is_mq_next() -> bool {
 pub struct EQVersion([u8; 21]);
 
 impl EQVersion {
+    /// Create an [`EQVersion`] from the build date and time, formatted like
+    /// ``Jan 02 2006 15:04:05``, followed by a null byte.
+    ///
+    /// This is a `const fn`, so when it's used in a const context (such as in a
+    /// `const` or `static`), an invalid version fails the build.
+    ///
+    /// ```
+    /// use macroquest::EQVersion;
+    ///
+    /// const VERSION: EQVersion = EQVersion::from_bytes(*b"Jan 02 2006 15:04:05\0");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` doesn't end with a null byte, contains any other null
+    /// bytes, or isn't formatted like a build date and time.
+    #[must_use]
+    pub const fn from_bytes(bytes: [u8; 21]) -> EQVersion {
+        assert!(bytes[20] == 0, "EQVersion must end with a null byte");
+
+        let mut i = 0;
+        while i < 20 {
+            assert!(
+                bytes[i] != 0,
+                "EQVersion must only have a trailing null byte"
+            );
+            i += 1;
+        }
+
+        assert!(
+            bytes[3] == b' '
+                && bytes[6] == b' '
+                && bytes[11] == b' '
+                && bytes[14] == b':'
+                && bytes[17] == b':',
+            "EQVersion must be formatted like \"Jan 02 2006 15:04:05\""
+        );
+
+        EQVersion(bytes)
+    }
+
+    /// Whether this is the version `version`, formatted like
+    /// ``Jan 02 2006 15:04:05`` (without a null byte).
+    ///
+    /// This is a `const fn`, so that it can be used by
+    /// [`assert_eq_version!`].
+    #[must_use]
+    pub const fn matches(&self, version: &str) -> bool {
+        let version = version.as_bytes();
+        if version.len() != 20 {
+            return false;
+        }
+
+        let mut i = 0;
+        while i < 20 {
+            if self.0[i] != version[i] {
+                return false;
+            }
+            i += 1;
+        }
+
+        true
+    }
+
     /// Return the build date portion of the [`EQVersion`]
     ///
     /// # Panics
@@ -142,7 +195,32 @@ impl EQVersion {
 #[doc(alias = "EverQuestVersion")]
 #[must_use]
 pub const fn eq_version() -> EQVersion {
-    EQVersion(*ffi::EQ_VERSION)
+    EQVersion::from_bytes(*ffi::EQ_VERSION)
+}
+
+/// Assert, at compile time, that this crate was built against the version of
+/// EverQuest `version` (formatted like ``Jan 02 2006 15:04:05``).
+///
+/// The version that the crate is built against comes from the MacroQuest
+/// build that `MACROQUEST_DIR` points to, so this catches building against
+/// the wrong MacroQuest by failing the build, rather than by MacroQuest
+/// refusing to load the plugin in game.
+///
+/// ```compile_fail
+/// // EverQuest didn't exist yet, so this is never the version.
+/// macroquest::assert_eq_version!("Jan 01 1970 00:00:00");
+/// ```
+#[macro_export]
+macro_rules! assert_eq_version {
+    ($version:literal) => {
+        const _: () = ::std::assert!(
+            $crate::eq_version().matches($version),
+            ::std::concat!(
+                "macroquest was not built against EverQuest version ",
+                $version
+            )
+        );
+    };
 }
 
 #[cfg(test)]
@@ -157,6 +235,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_eq_version_from_bytes() {
+        const VERSION: EQVersion = EQVersion::from_bytes(*b"Jan 02 2006 15:04:05\0");
+
+        assert_eq!(VERSION.build_date(), "Jan 02 2006");
+        assert!(VERSION.matches("Jan 02 2006 15:04:05"));
+        assert!(!VERSION.matches("Jan 02 2006 15:04:06"));
+        assert!(!VERSION.matches("Jan 02 2006"));
+    }
+
+    #[test]
+    fn test_eq_version_from_bytes_invalid() {
+        for bytes in [
+            *b"Jan 02 2006 15:04:05 ",
+            *b"Jan 02 2006\x0015:04:05\0",
+            *b"Jan 02 2006T15:04:05\0",
+        ] {
+            assert!(std::panic::catch_unwind(|| EQVersion::from_bytes(bytes)).is_err());
+        }
+    }
+
     #[test]
     fn test_eq_version_build_time() {
         assert_eq!(
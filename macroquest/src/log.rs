@@ -14,7 +14,8 @@
 //! support for creating a [tracing-subscriber](https://crates.io/crates/tracing-subscriber)
 //! logging sink that integrates tracing with MacroQuest. It can emit logging
 //! events to the MacroQuest console, to files in the MacroQuest log directory,
-//! and to the "Debug Spew" log.
+//! to the "Debug Spew" log, and to a [`LogBuffer`] that can be shown in game
+//! with [`log_console()`](crate::imgui::log_console).
 //!
 //! # Examples
 //!
@@ -76,18 +77,60 @@
 //!     }
 //! }
 //! ```
+//!
+//! Or leave the settings of the logger up to the user, in the `[logging]`
+//! section of the plugin's config file (see [`Logger::from_config()`]).
+//!
+//! ```ini
+//! [logging]
+//! console_level = warn
+//! file_level = trace
+//! rotation = hourly
+//! ```
+//!
+//! ```
+//! # use macroquest::log::Logger;
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MQRustLogging);
+//! # #[derive(Debug)]
+//! # struct MQRustLogging {}
+//! # impl Plugin for MQRustLogging {
+//! #     fn new() -> Self {
+//! #         MQRustLogging { }
+//! #     }
+//! # }
+//! #[macroquest::plugin::hooks]
+//! impl Hooks for MQRustLogging {
+//!     fn initialize(&self) {
+//!         Logger::from_config("MQRustLogging").install();
+//!     }
+//! }
+//! ```
 
 pub use tracing::{debug, error, info, trace, warn};
 
 #[cfg_attr(docsrs, doc(cfg(feature = "logger")))]
 #[cfg(feature = "logger")]
 mod logger {
+    use std::collections::VecDeque;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+    use std::{error, fmt, fs, io};
+
+    use parking_lot::{Mutex, MutexGuard};
     pub use tracing::level_filters::LevelFilter;
-    use tracing_appender::rolling::{RollingFileAppender, Rotation};
+    use tracing::{Level, Metadata};
+    pub use tracing_appender::rolling::Rotation;
+    use tracing_appender::rolling::{RollingFileAppender, RollingWriter};
+    use tracing_subscriber::fmt::MakeWriter;
     use tracing_subscriber::prelude::*;
     use typed_builder::TypedBuilder;
 
+    use crate::imgui::console::View;
     use crate::mq;
+    use crate::mq::ini::{self, Line};
 
     /// Implements logging to the MacroQuest console.
     ///
@@ -103,9 +146,20 @@ mod logger {
 
     /// Implements logging to a rotating file.
     ///
-    /// This will log all events to a file in the MacroQuest logs directory,
-    /// using the given filename (as a prefix), and rotating on some set
-    /// cadence.
+    /// This will log all events to a file using the given filename (as a
+    /// prefix), and rotating on some set cadence.
+    ///
+    /// The file is written into the given directory if there is one, or the
+    /// MacroQuest logs directory if MacroQuest's paths are available, or
+    /// otherwise into a `macroquest-rs` directory within the temporary
+    /// directory (such as when running outside of the game). The directory
+    /// is chosen when the logger is installed, and the choice is logged.
+    ///
+    /// Old files can be removed by giving a `max_files` or `max_age`, which
+    /// is done when the logger is installed and each time that it rotates,
+    /// for files that are rotated by the minute, hour, or day. Only files
+    /// named exactly as this logger names them are ever removed, so the files
+    /// of other loggers in the same directory are left alone.
     #[allow(clippy::module_name_repetitions)]
     #[derive(TypedBuilder)]
     pub struct FileLogger {
@@ -116,6 +170,524 @@ mod logger {
         /// will use.
         #[builder(setter(into))]
         filename: String,
+
+        /// The (optional) directory to write the file into, rather than the
+        /// MacroQuest logs directory.
+        #[builder(default, setter(strip_option, into))]
+        directory: Option<PathBuf>,
+
+        /// How often to start a new file, which defaults to daily.
+        #[builder(default = Rotation::DAILY)]
+        rotation: Rotation,
+
+        /// The format that events are written in, which defaults to
+        /// [`LogFormat::Full`].
+        #[builder(default)]
+        format: LogFormat,
+
+        /// The (optional) maximum number of files to keep, including the
+        /// current one, removing the oldest files beyond it.
+        #[builder(default, setter(strip_option))]
+        max_files: Option<usize>,
+
+        /// The (optional) maximum age of the files to keep, from the start of
+        /// the period that each file covers, removing any files older than it.
+        #[builder(default, setter(strip_option))]
+        max_age: Option<Duration>,
+    }
+
+    /// The format that a [`FileLogger`] writes events in.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum LogFormat {
+        /// One line per event, with the time, level, spans, and fields.
+        #[default]
+        Full,
+        /// One line per event, with the fields of the spans after the event's
+        /// own.
+        Compact,
+        /// Several lines per event, which is easier to read but much longer.
+        Pretty,
+    }
+
+    /// Where a [`FileLogger`]'s directory came from.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum DirectorySource {
+        Configured,
+        MacroQuest,
+        Temporary,
+    }
+
+    impl fmt::Display for DirectorySource {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(match self {
+                DirectorySource::Configured => "configured",
+                DirectorySource::MacroQuest => "MacroQuest logs",
+                DirectorySource::Temporary => "temporary",
+            })
+        }
+    }
+
+    /// Choose the directory for a [`FileLogger`], from the `configured`
+    /// directory, then MacroQuest's `logs` directory, and then a directory
+    /// within `temp`.
+    fn log_directory(
+        configured: Option<&Path>,
+        logs: Option<&Path>,
+        temp: &Path,
+    ) -> (PathBuf, DirectorySource) {
+        if let Some(configured) = configured {
+            (configured.to_owned(), DirectorySource::Configured)
+        }
+        else if let Some(logs) = logs {
+            (logs.to_owned(), DirectorySource::MacroQuest)
+        }
+        else {
+            (temp.join("macroquest-rs"), DirectorySource::Temporary)
+        }
+    }
+
+    /// Removes the old files of a [`FileLogger`], beyond its `max_files` and
+    /// `max_age`.
+    ///
+    /// Only files named exactly as the logger names them are ever removed,
+    /// which is `{prefix}.{date}.log`, where the date is in the form that the
+    /// rotation gives it. Files are only removed when rotated by the minute,
+    /// hour, or day, as otherwise there either are no old files, or their
+    /// dates don't say which one is current.
+    struct Retention {
+        directory: PathBuf,
+        prefix:    String,
+        period:    Period,
+        max_files: Option<usize>,
+        max_age:   Option<Duration>,
+        // The time (in seconds since the Unix epoch) of the next rotation,
+        // after which the old files are removed again.
+        next:      AtomicU64,
+    }
+
+    /// How often a [`FileLogger`] starts a new file, and the length of the date
+    /// in each file's name.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Period {
+        seconds:  u64,
+        date_len: usize,
+    }
+
+    impl Period {
+        // The form of the dates in the file names, which are cut short for the
+        // longer periods.
+        const DATE: &'static str = "0000-00-00-00-00";
+
+        fn new(rotation: &Rotation) -> Option<Period> {
+            let (seconds, date_len) = if *rotation == Rotation::MINUTELY {
+                (60, 16)
+            }
+            else if *rotation == Rotation::HOURLY {
+                (60 * 60, 13)
+            }
+            else if *rotation == Rotation::DAILY {
+                (24 * 60 * 60, 10)
+            }
+            else {
+                return None;
+            };
+
+            Some(Period { seconds, date_len })
+        }
+
+        /// The start of the period that `time` is in.
+        fn start(self, time: u64) -> u64 {
+            time - time % self.seconds
+        }
+    }
+
+    impl Retention {
+        fn new(
+            directory: &Path,
+            prefix: &str,
+            rotation: &Rotation,
+            max_files: Option<usize>,
+            max_age: Option<Duration>,
+        ) -> Option<Retention> {
+            if max_files.is_none() && max_age.is_none() {
+                return None;
+            }
+
+            Some(Retention {
+                directory: directory.to_owned(),
+                prefix: prefix.to_owned(),
+                period: Period::new(rotation)?,
+                max_files,
+                max_age,
+                next: AtomicU64::new(0),
+            })
+        }
+
+        /// Remove the old files if the logger has rotated since they were last
+        /// removed.
+        fn prune_if_rotated(&self) {
+            let now = unix_time(SystemTime::now());
+            let next = self.next.load(Ordering::Relaxed);
+            if now < next {
+                return;
+            }
+
+            // Only one thread gets to remove the files for each rotation.
+            let following = self.period.start(now) + self.period.seconds;
+            let swapped = self.next.compare_exchange(
+                next,
+                following,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+            if swapped.is_ok() {
+                self.prune(now);
+            }
+        }
+
+        /// Remove the old files, as of `now` (in seconds since the Unix epoch).
+        fn prune(&self, now: u64) {
+            let Ok(entries) = fs::read_dir(&self.directory)
+            else {
+                return;
+            };
+
+            let files = entries.filter_map(|entry| {
+                let entry = entry.ok()?;
+                // The logger only ever creates files, so anything else (even a
+                // link to a file) is never ours to remove.
+                if !entry.file_type().ok()?.is_file() {
+                    return None;
+                }
+
+                let start = self.file_start(entry.file_name().to_str()?)?;
+                Some((entry.path(), start))
+            });
+
+            for path in self.expired(files.collect(), now) {
+                // A file that can't be removed now (such as one that's still
+                // open) is tried again after the next rotation.
+                let _ = fs::remove_file(path);
+            }
+        }
+
+        /// The start of the period that the file named `name` covers, if it's
+        /// named exactly as the logger would name it.
+        fn file_start(&self, name: &str) -> Option<u64> {
+            let date = name
+                .strip_prefix(self.prefix.as_str())?
+                .strip_prefix('.')?
+                .strip_suffix(".log")?;
+
+            let matches = date.len() == self.period.date_len
+                && date.bytes().zip(Period::DATE.bytes()).all(|(c, form)| {
+                    if form == b'-' {
+                        c == b'-'
+                    }
+                    else {
+                        c.is_ascii_digit()
+                    }
+                });
+            if !matches {
+                return None;
+            }
+
+            let part = |start: usize, len: usize| -> u64 {
+                date.get(start..start + len)
+                    .map_or(0, |part| part.parse().unwrap_or(0))
+            };
+            date_time(part(0, 4), part(5, 2), part(8, 2), part(11, 2), part(14, 2))
+        }
+
+        /// Which of `files` (with the start of the period that each covers)
+        /// to remove, as of `now`.
+        fn expired(&self, mut files: Vec<(PathBuf, u64)>, now: u64) -> Vec<PathBuf> {
+            let current = self.period.start(now);
+
+            // Newest first, with the current file always kept and always
+            // counted, as it's about to be created if it doesn't exist yet.
+            files.sort_by(|(_, a), (_, b)| b.cmp(a));
+            files.retain(|(_, start)| *start < current);
+
+            let mut expired = Vec::new();
+            for (index, (path, start)) in files.into_iter().enumerate() {
+                let too_many = self.max_files.is_some_and(|max| index + 1 >= max);
+                let too_old = self
+                    .max_age
+                    .is_some_and(|max| now.saturating_sub(start) > max.as_secs());
+
+                if too_many || too_old {
+                    expired.push(path);
+                }
+            }
+
+            expired
+        }
+    }
+
+    /// A [`RollingFileAppender`] that removes the old files each time that it
+    /// rotates.
+    struct RetainingAppender {
+        appender:  RollingFileAppender,
+        retention: Option<Retention>,
+    }
+
+    impl<'a> MakeWriter<'a> for RetainingAppender {
+        type Writer = RollingWriter<'a>;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            if let Some(retention) = &self.retention {
+                retention.prune_if_rotated();
+            }
+
+            self.appender.make_writer()
+        }
+    }
+
+    /// The number of seconds since the Unix epoch at `time`.
+    fn unix_time(time: SystemTime) -> u64 {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs())
+    }
+
+    /// The number of seconds since the Unix epoch at the given date and time
+    /// (in UTC), if it's a real date and time after the epoch.
+    fn date_time(
+        year: u64,
+        month: u64,
+        day: u64,
+        hour: u64,
+        minute: u64,
+    ) -> Option<u64> {
+        let leap = year.is_multiple_of(4)
+            && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days_in_month = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => return None,
+        };
+        if year < 1970
+            || !(1..=days_in_month).contains(&day)
+            || hour > 23
+            || minute > 59
+        {
+            return None;
+        }
+
+        // The days since 1970-01-01, counting years as starting in March so
+        // that the leap day is at the end of the year.
+        let (year, month) = if month > 2 {
+            (year, month - 3)
+        }
+        else {
+            (year - 1, month + 9)
+        };
+        let day_of_year = (153 * month + 2) / 5 + day - 1;
+        let days =
+            year * 365 + year / 4 - year / 100 + year / 400 + day_of_year - 719_468;
+
+        Some(((days * 24 + hour) * 60 + minute) * 60)
+    }
+
+    /// Implements logging to a [`LogBuffer`], which can be shown in game with
+    /// [`log_console()`](crate::imgui::log_console).
+    #[allow(clippy::module_name_repetitions)]
+    #[derive(TypedBuilder)]
+    pub struct ImGuiLogger {
+        /// The maximum level of event to log to the buffer.
+        level: LevelFilter,
+
+        /// The buffer to log events to, which should also be given to
+        /// [`log_console()`](crate::imgui::log_console).
+        buffer: LogBuffer,
+    }
+
+    /// A bounded buffer of the most recent log lines, which can be shown in
+    /// game with [`log_console()`](crate::imgui::log_console).
+    ///
+    /// Cloning a buffer is cheap, and the clone refers to the same lines.
+    /// Lines may be pushed from any thread, and the lock protecting them is
+    /// only ever held long enough to push or copy out the lines, so reading
+    /// them every frame doesn't hold up the threads that are logging.
+    #[allow(clippy::module_name_repetitions)]
+    #[derive(Clone)]
+    pub struct LogBuffer {
+        inner: Arc<Inner>,
+    }
+
+    struct Inner {
+        capacity:   usize,
+        lines:      Mutex<VecDeque<Arc<LogLine>>>,
+        // Incremented whenever the lines change, so that readers can tell
+        // whether they need to copy them out again.
+        generation: AtomicU64,
+        console:    Mutex<View>,
+    }
+
+    impl LogBuffer {
+        /// The number of lines that a buffer holds by default.
+        pub const DEFAULT_CAPACITY: usize = 1000;
+
+        /// Create a buffer holding at most `capacity` lines, discarding the
+        /// oldest lines once it's full.
+        #[must_use]
+        pub fn new(capacity: usize) -> LogBuffer {
+            let capacity = capacity.max(1);
+
+            LogBuffer {
+                inner: Arc::new(Inner {
+                    capacity,
+                    lines: Mutex::new(VecDeque::with_capacity(capacity)),
+                    generation: AtomicU64::new(0),
+                    console: Mutex::new(View::new()),
+                }),
+            }
+        }
+
+        /// The maximum number of lines that the buffer holds.
+        #[must_use]
+        pub fn capacity(&self) -> usize {
+            self.inner.capacity
+        }
+
+        /// Add a line to the buffer, discarding the oldest line if it's full.
+        pub fn push<T>(&self, level: Level, text: T)
+        where
+            T: Into<String>,
+        {
+            let line = Arc::new(LogLine {
+                level,
+                text: text.into(),
+            });
+
+            let mut lines = self.inner.lines.lock();
+            if lines.len() == self.inner.capacity {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+            self.inner.generation.fetch_add(1, Ordering::Release);
+        }
+
+        /// Remove every line from the buffer.
+        pub fn clear(&self) {
+            let mut lines = self.inner.lines.lock();
+            lines.clear();
+            self.inner.generation.fetch_add(1, Ordering::Release);
+        }
+
+        /// The lines currently in the buffer, from oldest to newest.
+        #[must_use]
+        pub fn lines(&self) -> Vec<Arc<LogLine>> {
+            self.snapshot().1
+        }
+
+        /// A number that changes whenever the lines in the buffer change.
+        pub(crate) fn generation(&self) -> u64 {
+            self.inner.generation.load(Ordering::Acquire)
+        }
+
+        /// The lines currently in the buffer, along with the generation that
+        /// they're from.
+        pub(crate) fn snapshot(&self) -> (u64, Vec<Arc<LogLine>>) {
+            let lines = self.inner.lines.lock();
+            (self.generation(), lines.iter().cloned().collect())
+        }
+
+        /// The state of the console showing this buffer.
+        pub(crate) fn console(&self) -> MutexGuard<'_, View> {
+            self.inner.console.lock()
+        }
+    }
+
+    impl Default for LogBuffer {
+        fn default() -> Self {
+            LogBuffer::new(LogBuffer::DEFAULT_CAPACITY)
+        }
+    }
+
+    impl std::fmt::Debug for LogBuffer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("LogBuffer")
+                .field("capacity", &self.inner.capacity)
+                .field("len", &self.inner.lines.lock().len())
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for LogBuffer {
+        type Writer = LineWriter;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            LineWriter::new(self.clone(), Level::INFO)
+        }
+
+        fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+            LineWriter::new(self.clone(), *meta.level())
+        }
+    }
+
+    /// A single line in a [`LogBuffer`].
+    #[allow(clippy::module_name_repetitions)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct LogLine {
+        level: Level,
+        text:  String,
+    }
+
+    impl LogLine {
+        /// The level of the event that the line was logged for.
+        #[must_use]
+        pub fn level(&self) -> Level {
+            self.level
+        }
+
+        /// The formatted text of the line.
+        #[must_use]
+        pub fn text(&self) -> &str {
+            &self.text
+        }
+    }
+
+    /// Collects everything written for a single event, and pushes it to a
+    /// [`LogBuffer`] as one line once the event has been written.
+    pub struct LineWriter {
+        buffer: LogBuffer,
+        level:  Level,
+        text:   Vec<u8>,
+    }
+
+    impl LineWriter {
+        fn new(buffer: LogBuffer, level: Level) -> LineWriter {
+            LineWriter {
+                buffer,
+                level,
+                text: vec![],
+            }
+        }
+    }
+
+    impl io::Write for LineWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.text.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Drop for LineWriter {
+        fn drop(&mut self) {
+            let text = String::from_utf8_lossy(&self.text);
+            let text = text.trim_end();
+
+            if !text.is_empty() {
+                self.buffer.push(self.level, text);
+            }
+        }
     }
 
     /// Implements logging to multiple locations using MacroQuest standard
@@ -130,13 +702,81 @@ mod logger {
         /// The (optional) file logger to log events to.
         #[builder(setter(strip_option))]
         file: Option<FileLogger>,
+
+        /// The (optional) ImGui logger to log events to.
+        #[builder(default, setter(strip_option))]
+        imgui: Option<ImGuiLogger>,
     }
 
     impl Logger {
+        /// Create a logger from the `[logging]` section of the config file of
+        /// the plugin named `plugin_name` (`{plugin_name}.ini` in the
+        /// MacroQuest config directory).
+        ///
+        /// The section may have any of these settings, which otherwise
+        /// default to the value given:
+        ///
+        /// - `console_level`: the maximum level logged to the console, one of
+        ///   `off`, `error`, `warn`, `info`, `debug`, or `trace` (`info`).
+        /// - `file_level`: the maximum level logged to a file, in the same form
+        ///   (`debug`).
+        /// - `file_name`: the name of the file, without any extension (the
+        ///   plugin's name).
+        /// - `rotation`: how often to start a new file, one of `minutely`,
+        ///   `hourly`, `daily`, or `never` (`daily`).
+        /// - `format`: the format of the file, one of `full`, `compact`, or
+        ///   `pretty` (`full`).
+        ///
+        /// A level of `off` leaves out that logger entirely. Any setting that
+        /// can't be used is written to the MacroQuest console as a warning and
+        /// its default used instead, as is any problem reading the file.
+        #[must_use]
+        pub fn from_config(plugin_name: &str) -> Logger {
+            let mut config = LoggingConfig::new(plugin_name);
+
+            if let Some(paths) = mq::try_paths() {
+                let path = config_path(paths.config(), plugin_name);
+                match fs::read_to_string(&path) {
+                    Ok(contents) => {
+                        let errors;
+                        (config, errors) = parse_config(&contents, plugin_name);
+                        for error in errors {
+                            mq::write_chat(format!("{}: {error}", path.display()));
+                        }
+                    }
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                    Err(error) => {
+                        mq::write_chat(format!(
+                            "could not read {}: {error}",
+                            path.display()
+                        ));
+                    }
+                }
+            }
+
+            config.logger()
+        }
+
         /// Install this logger as our global logger, consuming it in the
         /// process.
-        #[allow(clippy::missing_panics_doc)]
+        ///
+        /// If the logger can't be installed, the reason is written to the
+        /// MacroQuest console instead. Use [`Logger::try_install()`] to handle
+        /// it some other way.
         pub fn install(self) {
+            if let Err(error) = self.try_install() {
+                mq::write_chat(format!("could not install logger: {error}"));
+            }
+        }
+
+        /// Install this logger as our global logger, consuming it in the
+        /// process.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the file logger can't create its log file, or if
+        /// a global logger has already been installed.
+        pub fn try_install(self) -> Result<(), InstallError> {
             let console_layer = self.console.map(|console| {
                 tracing_subscriber::fmt::layer()
                     .with_writer(mq::console)
@@ -148,27 +788,858 @@ mod logger {
                     .with_filter(console.level)
             });
 
-            let file_layer = self.file.map(|file| {
+            let (file_layer, file_directory) = match self.file {
+                Some(file) => {
+                    let (directory, source) = log_directory(
+                        file.directory.as_deref(),
+                        mq::try_paths().map(mq::Paths::logs),
+                        &std::env::temp_dir(),
+                    );
+                    let retention = Retention::new(
+                        &directory,
+                        &file.filename,
+                        &file.rotation,
+                        file.max_files,
+                        file.max_age,
+                    );
+                    if let Some(retention) = &retention {
+                        retention.prune_if_rotated();
+                    }
+
+                    let appender = RollingFileAppender::builder()
+                        .rotation(file.rotation)
+                        .filename_prefix(file.filename)
+                        .filename_suffix("log")
+                        .build(&directory)
+                        .map_err(InstallError::File)?;
+                    let appender = RetainingAppender {
+                        appender,
+                        retention,
+                    };
+
+                    let layer = tracing_subscriber::fmt::layer().with_writer(appender);
+                    let format = tracing_subscriber::fmt::format().with_ansi(false);
+                    let layer = match file.format {
+                        LogFormat::Full => layer.event_format(format).boxed(),
+                        LogFormat::Compact => {
+                            layer.event_format(format.compact()).boxed()
+                        }
+                        LogFormat::Pretty => {
+                            layer.event_format(format.pretty()).boxed()
+                        }
+                    };
+
+                    (
+                        Some(layer.with_filter(file.level)),
+                        Some((directory, source)),
+                    )
+                }
+                None => (None, None),
+            };
+
+            let imgui_layer = self.imgui.map(|imgui| {
                 tracing_subscriber::fmt::layer()
-                    .with_writer(
-                        RollingFileAppender::builder()
-                            .rotation(Rotation::DAILY)
-                            .filename_prefix(file.filename)
-                            .filename_suffix("log")
-                            .build(mq::paths().logs())
-                            .expect("invalid file logger configuration"),
+                    .with_writer(imgui.buffer)
+                    .event_format(
+                        tracing_subscriber::fmt::format()
+                            .with_ansi(false)
+                            .without_time(),
                     )
-                    .event_format(tracing_subscriber::fmt::format().with_ansi(false))
-                    .with_filter(file.level)
+                    .with_filter(imgui.level)
             });
 
             tracing_subscriber::registry()
                 .with(console_layer)
                 .with(file_layer)
-                .init();
+                .with(imgui_layer)
+                .try_init()
+                .map_err(|_| InstallError::AlreadyInstalled)?;
+
+            if let Some((directory, source)) = file_directory {
+                tracing::info!(
+                    directory = %directory.display(),
+                    %source,
+                    "logging to file"
+                );
+            }
+
+            Ok(())
+        }
+    }
+
+    // The section of a plugin's config file that the logger is configured in.
+    const SECTION: &str = "logging";
+
+    /// The path to the config file of the plugin named `plugin_name`.
+    fn config_path(config: &Path, plugin_name: &str) -> PathBuf {
+        config.join(format!("{plugin_name}.ini"))
+    }
+
+    /// The settings of a [`Logger`] from a plugin's config file.
+    #[derive(Debug, Clone, PartialEq)]
+    struct LoggingConfig {
+        console_level: LevelFilter,
+        file_level:    LevelFilter,
+        file_name:     String,
+        rotation:      Rotation,
+        format:        LogFormat,
+    }
+
+    impl LoggingConfig {
+        fn new(plugin_name: &str) -> LoggingConfig {
+            LoggingConfig {
+                console_level: LevelFilter::INFO,
+                file_level:    LevelFilter::DEBUG,
+                file_name:     plugin_name.to_owned(),
+                rotation:      Rotation::DAILY,
+                format:        LogFormat::Full,
+            }
+        }
+
+        fn logger(self) -> Logger {
+            let console = ConsoleLogger {
+                level: self.console_level,
+            };
+            let file = FileLogger {
+                level:     self.file_level,
+                filename:  self.file_name,
+                directory: None,
+                rotation:  self.rotation,
+                format:    self.format,
+                max_files: None,
+                max_age:   None,
+            };
+
+            Logger {
+                console: (console.level != LevelFilter::OFF).then_some(console),
+                file:    (file.level != LevelFilter::OFF).then_some(file),
+                imgui:   None,
+            }
+        }
+    }
+
+    /// Parse the `[logging]` section of a plugin's config file, along with
+    /// every problem with it, each of which leaves that setting at its default.
+    ///
+    /// The file is read the same way as the plugin's settings are (see
+    /// [`mq::settings`]), so the section and setting names are
+    /// case-insensitive, and lines starting with `;` are comments.
+    fn parse_config(
+        contents: &str,
+        plugin_name: &str,
+    ) -> (LoggingConfig, Vec<ConfigError>) {
+        let mut config = LoggingConfig::new(plugin_name);
+        let mut errors = Vec::new();
+
+        for (_, line) in ini::section(contents, SECTION) {
+            let (key, value) = match line {
+                Line::Entry(key, value) => (key, value),
+                Line::Invalid(line) => {
+                    errors.push(ConfigError::InvalidLine(line.to_owned()));
+                    continue;
+                }
+                Line::Section(_) | Line::Blank => continue,
+            };
+
+            let result = match key.to_ascii_lowercase().as_str() {
+                "console_level" => parse_level(value).map(|v| config.console_level = v),
+                "file_level" => parse_level(value).map(|v| config.file_level = v),
+                "file_name" => parse_file_name(value).map(|v| config.file_name = v),
+                "rotation" => parse_rotation(value).map(|v| config.rotation = v),
+                "format" => parse_format(value).map(|v| config.format = v),
+                _ => {
+                    errors.push(ConfigError::UnknownSetting(key.to_owned()));
+                    continue;
+                }
+            };
+
+            if let Err(expected) = result {
+                errors.push(ConfigError::InvalidValue {
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                    expected,
+                });
+            }
+        }
+
+        (config, errors)
+    }
+
+    // Each of these parses the value of a setting, or returns what it expected
+    // the value to be.
+
+    fn parse_level(value: &str) -> Result<LevelFilter, &'static str> {
+        match value.to_ascii_lowercase().as_str() {
+            "off" => Ok(LevelFilter::OFF),
+            "error" => Ok(LevelFilter::ERROR),
+            "warn" => Ok(LevelFilter::WARN),
+            "info" => Ok(LevelFilter::INFO),
+            "debug" => Ok(LevelFilter::DEBUG),
+            "trace" => Ok(LevelFilter::TRACE),
+            _ => Err("off, error, warn, info, debug, or trace"),
+        }
+    }
+
+    fn parse_file_name(value: &str) -> Result<String, &'static str> {
+        if value.is_empty() || value.contains(['/', '\\']) {
+            Err("a file name, without a directory")
+        }
+        else {
+            Ok(value.to_owned())
+        }
+    }
+
+    fn parse_rotation(value: &str) -> Result<Rotation, &'static str> {
+        match value.to_ascii_lowercase().as_str() {
+            "minutely" => Ok(Rotation::MINUTELY),
+            "hourly" => Ok(Rotation::HOURLY),
+            "daily" => Ok(Rotation::DAILY),
+            "never" => Ok(Rotation::NEVER),
+            _ => Err("minutely, hourly, daily, or never"),
+        }
+    }
+
+    fn parse_format(value: &str) -> Result<LogFormat, &'static str> {
+        match value.to_ascii_lowercase().as_str() {
+            "full" => Ok(LogFormat::Full),
+            "compact" => Ok(LogFormat::Compact),
+            "pretty" => Ok(LogFormat::Pretty),
+            _ => Err("full, compact, or pretty"),
+        }
+    }
+
+    /// A problem with the `[logging]` section of a plugin's config file.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum ConfigError {
+        /// A setting has a value that it can't have.
+        InvalidValue {
+            key:      String,
+            value:    String,
+            expected: &'static str,
+        },
+        /// A setting that the logger doesn't have.
+        UnknownSetting(String),
+        /// A line that isn't a `key = value` setting.
+        InvalidLine(String),
+    }
+
+    impl fmt::Display for ConfigError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ConfigError::InvalidValue {
+                    key,
+                    value,
+                    expected,
+                } => {
+                    write!(
+                        f,
+                        "invalid {key} {value:?} in [{SECTION}] (expected {expected}), \
+                         using the default"
+                    )
+                }
+                ConfigError::UnknownSetting(key) => {
+                    write!(f, "ignoring unknown setting {key:?} in [{SECTION}]")
+                }
+                ConfigError::InvalidLine(line) => {
+                    write!(f, "ignoring {line:?} in [{SECTION}], which isn't a setting")
+                }
+            }
+        }
+    }
+
+    /// An error installing a [`Logger`].
+    #[derive(Debug)]
+    #[non_exhaustive]
+    pub enum InstallError {
+        /// The file logger couldn't create its log file.
+        File(tracing_appender::rolling::InitError),
+        /// A global logger has already been installed.
+        AlreadyInstalled,
+    }
+
+    impl fmt::Display for InstallError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                InstallError::File(error) => {
+                    write!(f, "could not create log file: {error}")
+                }
+                InstallError::AlreadyInstalled => {
+                    f.write_str("a logger has already been installed")
+                }
+            }
+        }
+    }
+
+    impl error::Error for InstallError {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            match self {
+                InstallError::File(error) => Some(error),
+                _ => None,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::io::Write;
+
+        use super::*;
+
+        fn texts(buffer: &LogBuffer) -> Vec<String> {
+            buffer
+                .lines()
+                .iter()
+                .map(|line| line.text().to_owned())
+                .collect()
+        }
+
+        #[test]
+        fn test_log_directory() {
+            let configured = Path::new(r"D:\logs");
+            let logs = Path::new(r"C:\MacroQuest\logs");
+            let temp = Path::new(r"C:\Temp");
+
+            assert_eq!(
+                log_directory(Some(configured), Some(logs), temp),
+                (configured.to_owned(), DirectorySource::Configured)
+            );
+            assert_eq!(
+                log_directory(None, Some(logs), temp),
+                (logs.to_owned(), DirectorySource::MacroQuest)
+            );
+            assert_eq!(
+                log_directory(None, None, temp),
+                (temp.join("macroquest-rs"), DirectorySource::Temporary)
+            );
+        }
+
+        fn invalid(key: &str, value: &str, expected: &'static str) -> ConfigError {
+            ConfigError::InvalidValue {
+                key: key.to_owned(),
+                value: value.to_owned(),
+                expected,
+            }
+        }
+
+        #[test]
+        fn test_parse_config() {
+            let contents = "\
+[General]
+console_level=trace
+
+[Logging]
+; Comments are skipped.
+Console_Level = WARN
+file_level=Trace
+file_name = MyPlugin-Debug
+rotation = Hourly
+FORMAT = compact
+[Other]
+format = pretty
+";
+            let (config, errors) = parse_config(contents, "MyPlugin");
+
+            assert_eq!(errors, []);
+            assert_eq!(
+                config,
+                LoggingConfig {
+                    console_level: LevelFilter::WARN,
+                    file_level:    LevelFilter::TRACE,
+                    file_name:     "MyPlugin-Debug".to_owned(),
+                    rotation:      Rotation::HOURLY,
+                    format:        LogFormat::Compact,
+                }
+            );
+        }
+
+        #[test]
+        fn test_parse_config_defaults() {
+            let contents = "[General]\nfile_level=off\n";
+            let (config, errors) = parse_config(contents, "MyPlugin");
+
+            assert_eq!(errors, []);
+            assert_eq!(config, LoggingConfig::new("MyPlugin"));
+            assert_eq!(config.file_name, "MyPlugin");
+        }
+
+        #[test]
+        fn test_parse_level() {
+            for (value, level) in [
+                ("off", LevelFilter::OFF),
+                ("Error", LevelFilter::ERROR),
+                ("WARN", LevelFilter::WARN),
+                ("info", LevelFilter::INFO),
+                ("dEbUg", LevelFilter::DEBUG),
+                ("trace", LevelFilter::TRACE),
+            ] {
+                assert_eq!(parse_level(value), Ok(level));
+            }
+
+            assert!(parse_level("warning").is_err());
+            assert!(parse_level("3").is_err());
+            assert!(parse_level("").is_err());
+        }
+
+        #[test]
+        fn test_parse_config_invalid_console_level() {
+            let contents = "[logging]\nconsole_level = loud\n";
+            let (config, errors) = parse_config(contents, "P");
+
+            assert_eq!(config.console_level, LevelFilter::INFO);
+            assert_eq!(
+                errors,
+                [invalid(
+                    "console_level",
+                    "loud",
+                    "off, error, warn, info, debug, or trace"
+                )]
+            );
+        }
+
+        #[test]
+        fn test_parse_config_invalid_file_level() {
+            let (config, errors) = parse_config("[logging]\nfile_level = 5\n", "P");
+
+            assert_eq!(config.file_level, LevelFilter::DEBUG);
+            assert_eq!(
+                errors,
+                [invalid(
+                    "file_level",
+                    "5",
+                    "off, error, warn, info, debug, or trace"
+                )]
+            );
+        }
+
+        #[test]
+        fn test_parse_config_invalid_file_name() {
+            let contents = "[logging]\nfile_name =\nfile_name = ..\\Other\n";
+            let (config, errors) = parse_config(contents, "P");
+
+            assert_eq!(config.file_name, "P");
+            assert_eq!(
+                errors,
+                [
+                    invalid("file_name", "", "a file name, without a directory"),
+                    invalid(
+                        "file_name",
+                        "..\\Other",
+                        "a file name, without a directory"
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_parse_config_invalid_rotation() {
+            let (config, errors) = parse_config("[logging]\nrotation = weekly\n", "P");
+
+            assert_eq!(config.rotation, Rotation::DAILY);
+            assert_eq!(
+                errors,
+                [invalid(
+                    "rotation",
+                    "weekly",
+                    "minutely, hourly, daily, or never"
+                )]
+            );
+        }
+
+        #[test]
+        fn test_parse_config_invalid_format() {
+            let (config, errors) = parse_config("[logging]\nformat = json\n", "P");
+
+            assert_eq!(config.format, LogFormat::Full);
+            assert_eq!(
+                errors,
+                [invalid("format", "json", "full, compact, or pretty")]
+            );
+        }
+
+        #[test]
+        fn test_parse_config_invalid_lines() {
+            let contents = "[logging]\nlevel = info\nverbose\nformat = pretty\n";
+            let (config, errors) = parse_config(contents, "P");
+
+            assert_eq!(config.format, LogFormat::Pretty);
+            assert_eq!(
+                errors,
+                [
+                    ConfigError::UnknownSetting("level".to_owned()),
+                    ConfigError::InvalidLine("verbose".to_owned()),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_parse_config_duplicate_section() {
+            // Only the first [logging] is read, the same as for the plugin's
+            // settings, and quoted values are unquoted.
+            let contents = "[logging]\nformat = \"compact\"\n[LOGGING]\nformat = pretty\nverbose\n";
+            let (config, errors) = parse_config(contents, "P");
+
+            assert_eq!(config.format, LogFormat::Compact);
+            assert_eq!(errors, []);
+        }
+
+        #[test]
+        fn test_config_error_display() {
+            assert_eq!(
+                invalid("rotation", "weekly", "daily or never").to_string(),
+                "invalid rotation \"weekly\" in [logging] (expected daily or never), \
+                 using the default"
+            );
+            assert_eq!(
+                ConfigError::UnknownSetting("level".to_owned()).to_string(),
+                "ignoring unknown setting \"level\" in [logging]"
+            );
+        }
+
+        #[test]
+        fn test_config_logger() {
+            let logger = LoggingConfig::new("MyPlugin").logger();
+            assert_eq!(logger.console.unwrap().level, LevelFilter::INFO);
+            let file = logger.file.unwrap();
+            assert_eq!(file.level, LevelFilter::DEBUG);
+            assert_eq!(file.filename, "MyPlugin");
+
+            let contents = "[logging]\nconsole_level=off\nfile_level=off";
+            let (config, _) = parse_config(contents, "P");
+            let logger = config.logger();
+            assert!(logger.console.is_none());
+            assert!(logger.file.is_none());
+        }
+
+        fn retention(max_files: Option<usize>, max_age: Option<Duration>) -> Retention {
+            Retention::new(
+                Path::new("logs"),
+                "MyPlugin",
+                &Rotation::DAILY,
+                max_files,
+                max_age,
+            )
+            .unwrap()
+        }
+
+        fn temp_dir(name: &str) -> PathBuf {
+            let dir = std::env::temp_dir()
+                .join(format!("macroquest-log-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            dir
+        }
+
+        fn file_names(dir: &Path) -> Vec<String> {
+            let mut names: Vec<String> = fs::read_dir(dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect();
+            names.sort();
+            names
+        }
+
+        const DAY: u64 = 24 * 60 * 60;
+
+        #[test]
+        fn test_date_time() {
+            assert_eq!(date_time(1970, 1, 1, 0, 0), Some(0));
+            assert_eq!(date_time(2024, 1, 1, 0, 0), Some(19_723 * DAY));
+            assert_eq!(date_time(2024, 2, 29, 13, 5), Some(19_782 * DAY + 47_100));
+            assert_eq!(date_time(2000, 3, 1, 0, 0), Some(11_017 * DAY));
+
+            assert_eq!(date_time(2023, 2, 29, 0, 0), None);
+            assert_eq!(date_time(1900, 2, 29, 0, 0), None);
+            assert_eq!(date_time(2024, 4, 31, 0, 0), None);
+            assert_eq!(date_time(2024, 13, 1, 0, 0), None);
+            assert_eq!(date_time(2024, 0, 1, 0, 0), None);
+            assert_eq!(date_time(2024, 1, 0, 0, 0), None);
+            assert_eq!(date_time(2024, 1, 1, 24, 0), None);
+            assert_eq!(date_time(2024, 1, 1, 0, 60), None);
+            assert_eq!(date_time(1969, 12, 31, 0, 0), None);
+        }
+
+        #[test]
+        fn test_period() {
+            assert_eq!(Period::new(&Rotation::NEVER), None);
+
+            let minutely = Period::new(&Rotation::MINUTELY).unwrap();
+            assert_eq!((minutely.seconds, minutely.date_len), (60, 16));
+            assert_eq!(minutely.start(DAY + 61), DAY + 60);
+
+            let daily = Period::new(&Rotation::DAILY).unwrap();
+            assert_eq!(daily.start(DAY + 61), DAY);
+        }
+
+        #[test]
+        fn test_retention_needs_limits() {
+            let directory = Path::new("logs");
+
+            let new = |rotation, max_files| {
+                Retention::new(directory, "P", &rotation, max_files, None)
+            };
+
+            assert!(new(Rotation::DAILY, None).is_none());
+            assert!(new(Rotation::NEVER, Some(5)).is_none());
+            assert!(new(Rotation::HOURLY, Some(5)).is_some());
+        }
+
+        #[test]
+        fn test_file_start() {
+            let daily = retention(Some(5), None);
+
+            assert_eq!(
+                daily.file_start("MyPlugin.2024-01-01.log"),
+                Some(19_723 * DAY)
+            );
+
+            for name in [
+                "MyPlugin.log",
+                "MyPlugin..log",
+                "MyPlugin.2024-01-01.log.bak",
+                "MyPlugin.2024-01-01.txt",
+                "MyPlugin.2024-01-01",
+                "MyPlugin.2024-01-01-05.log",
+                "MyPlugin.2024-1-01.log",
+                "MyPlugin.2024-01-1.log",
+                "MyPlugin.2024_01_01.log",
+                "MyPlugin.+024-01-01.log",
+                "MyPlugin.2024-13-01.log",
+                "MyPlugin.2023-02-29.log",
+                "MyPlugin.1969-12-31.log",
+                "MyPlugin-2024-01-01.log",
+                "MyPlugin.Debug.2024-01-01.log",
+                "MyPluginX.2024-01-01.log",
+                "myplugin.2024-01-01.log",
+                "MYPLUGIN.2024-01-01.log",
+                " MyPlugin.2024-01-01.log",
+                "MyPlugin.2024-01-01.log ",
+                "Other.2024-01-01.log",
+                "Other.MyPlugin.2024-01-01.log",
+                "2024-01-01.log",
+                "",
+            ] {
+                assert_eq!(daily.file_start(name), None, "{name:?}");
+            }
+
+            let hourly = Retention::new(
+                Path::new("logs"),
+                "MyPlugin",
+                &Rotation::HOURLY,
+                Some(5),
+                None,
+            )
+            .unwrap();
+            assert_eq!(
+                hourly.file_start("MyPlugin.2024-01-01-05.log"),
+                Some(19_723 * DAY + 5 * 60 * 60)
+            );
+            assert_eq!(hourly.file_start("MyPlugin.2024-01-01.log"), None);
+            assert_eq!(hourly.file_start("MyPlugin.2024-01-01-24.log"), None);
+        }
+
+        #[test]
+        fn test_expired_max_files() {
+            let retention = retention(Some(3), None);
+            let now = 10 * DAY + 5;
+            let files = (5..=10)
+                .map(|day| (PathBuf::from(day.to_string()), day * DAY))
+                .collect();
+
+            // The current file counts as one of the three, leaving two others.
+            assert_eq!(
+                retention.expired(files, now),
+                ["7", "6", "5"].map(PathBuf::from)
+            );
+
+            // Even if the current file doesn't exist yet.
+            let files = vec![
+                (PathBuf::from("9"), 9 * DAY),
+                (PathBuf::from("8"), 8 * DAY),
+                (PathBuf::from("7"), 7 * DAY),
+            ];
+            assert_eq!(retention.expired(files, now), [PathBuf::from("7")]);
+        }
+
+        #[test]
+        fn test_expired_max_files_keeps_current() {
+            let retention = retention(Some(0), None);
+            let now = 10 * DAY + 5;
+            let files = vec![
+                (PathBuf::from("10"), 10 * DAY),
+                (PathBuf::from("9"), 9 * DAY),
+            ];
+
+            assert_eq!(retention.expired(files, now), [PathBuf::from("9")]);
+        }
+
+        #[test]
+        fn test_expired_max_age() {
+            let retention = retention(None, Some(Duration::from_secs(2 * DAY)));
+            let now = 10 * DAY + 5;
+            let files = (5..=10)
+                .map(|day| (PathBuf::from(day.to_string()), day * DAY))
+                .collect();
+
+            // Day 8 started 2 days and 5 seconds ago.
+            assert_eq!(
+                retention.expired(files, now),
+                ["8", "7", "6", "5"].map(PathBuf::from)
+            );
+        }
+
+        #[test]
+        fn test_expired_both() {
+            let retention = retention(Some(5), Some(Duration::from_secs(3 * DAY)));
+            let now = 10 * DAY;
+            let files = (5..=10)
+                .map(|day| (PathBuf::from(day.to_string()), day * DAY))
+                .collect();
+
+            assert_eq!(retention.expired(files, now), ["6", "5"].map(PathBuf::from));
+        }
+
+        #[test]
+        fn test_prune_only_our_files() {
+            let dir = temp_dir("prune");
+            let ours = [
+                "MyPlugin.2024-01-01.log",
+                "MyPlugin.2024-01-02.log",
+                "MyPlugin.2024-01-03.log",
+                "MyPlugin.2024-01-04.log",
+            ];
+            let others = [
+                "MyPlugin.log",
+                "MyPlugin.2024-01-01.log.bak",
+                "MyPlugin.2024-01-01.txt",
+                "MyPlugin.2024-01-01-05.log",
+                "MyPlugin.2024-13-01.log",
+                "MyPlugin.Debug.2024-01-01.log",
+                "MyPluginX.2024-01-01.log",
+                "myplugin.2024-01-01.log",
+                "Other.2024-01-01.log",
+                "notes.txt",
+            ];
+            for name in ours.iter().chain(&others) {
+                fs::write(dir.join(name), "").unwrap();
+            }
+
+            // Neither directories, nor the files within them, are ours.
+            fs::create_dir(dir.join("MyPlugin.2023-01-01.log")).unwrap();
+            fs::create_dir(dir.join("old")).unwrap();
+            fs::write(dir.join("old").join("MyPlugin.2023-01-02.log"), "").unwrap();
+
+            let retention =
+                Retention::new(&dir, "MyPlugin", &Rotation::DAILY, Some(2), None)
+                    .unwrap();
+            retention.prune(date_time(2024, 1, 5, 12, 0).unwrap());
+
+            let mut expected: Vec<String> = others
+                .iter()
+                .chain(&["MyPlugin.2024-01-04.log", "MyPlugin.2023-01-01.log", "old"])
+                .map(|name| (*name).to_owned())
+                .collect();
+            expected.sort();
+
+            assert_eq!(file_names(&dir), expected);
+            assert_eq!(file_names(&dir.join("old")), ["MyPlugin.2023-01-02.log"]);
+
+            fs::remove_dir_all(&dir).unwrap();
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn test_prune_skips_links() {
+            let dir = temp_dir("links");
+            let target = dir.join("target.log");
+            fs::write(&target, "").unwrap();
+            std::os::unix::fs::symlink(&target, dir.join("MyPlugin.2024-01-01.log"))
+                .unwrap();
+
+            let retention =
+                Retention::new(&dir, "MyPlugin", &Rotation::DAILY, Some(1), None)
+                    .unwrap();
+            retention.prune(date_time(2024, 1, 5, 0, 0).unwrap());
+
+            assert_eq!(file_names(&dir), ["MyPlugin.2024-01-01.log", "target.log"]);
+
+            fs::remove_dir_all(&dir).unwrap();
+        }
+
+        #[test]
+        fn test_prune_missing_directory() {
+            let retention = Retention::new(
+                &std::env::temp_dir().join("macroquest-log-does-not-exist"),
+                "MyPlugin",
+                &Rotation::DAILY,
+                Some(1),
+                None,
+            )
+            .unwrap();
+
+            retention.prune(date_time(2024, 1, 5, 0, 0).unwrap());
+        }
+
+        #[test]
+        fn test_buffer_discards_oldest() {
+            let buffer = LogBuffer::new(2);
+            buffer.push(Level::INFO, "one");
+            buffer.push(Level::INFO, "two");
+            buffer.push(Level::WARN, "three");
+
+            assert_eq!(texts(&buffer), vec!["two", "three"]);
+            assert_eq!(buffer.lines()[1].level(), Level::WARN);
+        }
+
+        #[test]
+        fn test_buffer_generation() {
+            let buffer = LogBuffer::new(10);
+            let start = buffer.generation();
+
+            buffer.push(Level::INFO, "one");
+            let (generation, lines) = buffer.snapshot();
+            assert_ne!(generation, start);
+            assert_eq!(lines.len(), 1);
+
+            buffer.clear();
+            assert_ne!(buffer.generation(), generation);
+            assert!(buffer.lines().is_empty());
+        }
+
+        #[test]
+        fn test_buffer_clones_share_lines() {
+            let buffer = LogBuffer::new(10);
+            buffer.clone().push(Level::INFO, "shared");
+
+            assert_eq!(texts(&buffer), vec!["shared"]);
+        }
+
+        #[test]
+        fn test_line_writer() {
+            let buffer = LogBuffer::new(10);
+
+            let mut writer = buffer.make_writer();
+            write!(writer, "INFO hello ").unwrap();
+            writeln!(writer, "world").unwrap();
+            drop(writer);
+
+            // Nothing written means nothing pushed.
+            drop(buffer.make_writer());
+
+            assert_eq!(texts(&buffer), vec!["INFO hello world"]);
         }
     }
 }
 
 #[cfg(feature = "logger")]
-pub use logger::{ConsoleLogger, FileLogger, LevelFilter, Logger};
+pub use logger::{
+    ConsoleLogger,
+    FileLogger,
+    ImGuiLogger,
+    InstallError,
+    LevelFilter,
+    LogBuffer,
+    LogFormat,
+    LogLine,
+    Logger,
+    Rotation,
+};
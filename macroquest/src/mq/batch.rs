@@ -0,0 +1,425 @@
+//! Writing many lines of chat at once.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::num::NonZeroUsize;
+
+use parking_lot::Mutex;
+
+use super::write_chat_lines;
+use crate::eq::ChatColor;
+
+static STAGGERED: Mutex<Stagger> = Mutex::new(Stagger::new());
+
+/// A batch of lines of chat, which are written together when the batch is
+/// flushed (or dropped), rather than one at a time.
+///
+/// Each call to [`write_chat`](super::write_chat) crosses over to MacroQuest
+/// on its own, so writing a table of 100 lines that way crosses over 100
+/// times, while a batch of the same 100 lines (all in the same color) crosses
+/// over once. MacroQuest still handles each line on its own, so a batch
+/// doesn't make the lines any cheaper for MacroQuest, only for the plugin.
+///
+/// A batch that's large enough to take a noticeable part of a frame can be
+/// [staggered](ChatBatch::staggered), so that only some of its lines are
+/// written in each pulse.
+///
+/// Lines are written to the current [`ChatSink`](super::ChatSink) on the game's
+/// main thread, the same as [`write_chat`](super::write_chat).
+///
+/// # Examples
+///
+/// ```
+/// # use macroquest::mq::testing::CapturedChat;
+/// use std::fmt::Write;
+///
+/// use macroquest::mq::ChatBatch;
+///
+/// # let chat = CapturedChat::new();
+/// let mut batch = ChatBatch::new();
+/// batch.push("Zones:");
+/// for (name, players) in [("poknowledge", 12), ("guildlobby", 40)] {
+///     writeln!(batch, "  {name}: {players}").unwrap();
+/// }
+/// batch.flush();
+/// # assert_eq!(chat.lines(), ["Zones:", "  poknowledge: 12", "  guildlobby: 40"]);
+/// ```
+#[derive(Debug, Default)]
+pub struct ChatBatch {
+    lines:   Vec<(String, ChatColor)>,
+    // Text written with fmt::Write that hasn't been ended with a newline yet.
+    partial: String,
+    stagger: Option<NonZeroUsize>,
+}
+
+impl ChatBatch {
+    /// Create an empty batch, which writes all of its lines at once.
+    #[must_use]
+    pub fn new() -> ChatBatch {
+        ChatBatch::default()
+    }
+
+    /// Create an empty batch, which writes at most `lines_per_pulse` of its
+    /// lines at once, and the rest over the following pulses.
+    ///
+    /// Lines written to chat some other way in the meantime (including by
+    /// batches that aren't staggered) aren't held back, so they may come
+    /// before the rest of a staggered batch. Staggered batches are always
+    /// written in the order that they're flushed, though.
+    #[must_use]
+    pub fn staggered(lines_per_pulse: NonZeroUsize) -> ChatBatch {
+        ChatBatch {
+            lines:   Vec::new(),
+            partial: String::new(),
+            stagger: Some(lines_per_pulse),
+        }
+    }
+
+    /// Add a line using the default [`ChatColor`].
+    ///
+    /// A line that contains newlines is split into several lines.
+    pub fn push<S>(&mut self, line: S)
+    where
+        S: AsRef<str>,
+    {
+        self.push_color(line, ChatColor::default());
+    }
+
+    /// Add a line using the given [`ChatColor`], which may be given as a
+    /// [`Channel`](crate::eq::Channel).
+    ///
+    /// A line that contains newlines is split into several lines.
+    pub fn push_color<S, C>(&mut self, line: S, color: C)
+    where
+        S: AsRef<str>,
+        C: Into<ChatColor>,
+    {
+        let color = color.into();
+        self.end_partial();
+        self.lines.extend(
+            line.as_ref()
+                .split('\n')
+                .map(|line| (line.to_owned(), color)),
+        );
+    }
+
+    /// The number of lines in the batch that haven't been written yet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lines.len() + usize::from(!self.partial.is_empty())
+    }
+
+    /// Whether every line in the batch has been written.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Write every line in the batch (or for a staggered batch, start writing
+    /// them), leaving it empty.
+    pub fn flush(&mut self) {
+        self.end_partial();
+        if self.lines.is_empty() {
+            return;
+        }
+
+        let lines = std::mem::take(&mut self.lines);
+        let now = match self.stagger {
+            Some(per_pulse) => STAGGERED.lock().submit(lines, per_pulse),
+            None => Some(lines),
+        };
+
+        if let Some(now) = now {
+            write_chat_lines(&now);
+        }
+    }
+
+    // Text written with fmt::Write that doesn't end with a newline is still a
+    // line of its own once anything else is pushed, or the batch is flushed.
+    fn end_partial(&mut self) {
+        if !self.partial.is_empty() {
+            let line = std::mem::take(&mut self.partial);
+            self.lines.push((line, ChatColor::default()));
+        }
+    }
+}
+
+impl fmt::Write for ChatBatch {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        while let Some(newline) = rest.find('\n') {
+            self.partial.push_str(&rest[..newline]);
+            let line = std::mem::take(&mut self.partial);
+            self.lines.push((line, ChatColor::default()));
+            rest = &rest[newline + 1..];
+        }
+
+        self.partial.push_str(rest);
+        Ok(())
+    }
+}
+
+impl Drop for ChatBatch {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// Write the next part of any staggered batches.
+pub(crate) fn pulse() {
+    let next = STAGGERED.lock().next();
+    if let Some(next) = next {
+        write_chat_lines(&next);
+    }
+}
+
+/// Write the rest of any staggered batches at once, as the plugin is being
+/// unloaded and so there won't be another pulse for them.
+pub(crate) fn finish() {
+    let rest = STAGGERED.lock().take();
+    if !rest.is_empty() {
+        write_chat_lines(&rest);
+    }
+}
+
+/// The parts of staggered batches that are waiting for a later pulse.
+struct Stagger {
+    parts: VecDeque<Vec<(String, ChatColor)>>,
+}
+
+impl Stagger {
+    const fn new() -> Stagger {
+        Stagger {
+            parts: VecDeque::new(),
+        }
+    }
+
+    /// Split `lines` into parts of `per_pulse` lines, returning the part to
+    /// write now, if any.
+    ///
+    /// Nothing is written now while an earlier batch is still waiting, so that
+    /// staggered batches aren't interleaved.
+    fn submit(
+        &mut self,
+        mut lines: Vec<(String, ChatColor)>,
+        per_pulse: NonZeroUsize,
+    ) -> Option<Vec<(String, ChatColor)>> {
+        let waiting = !self.parts.is_empty();
+
+        while lines.len() > per_pulse.get() {
+            let rest = lines.split_off(per_pulse.get());
+            self.parts.push_back(lines);
+            lines = rest;
+        }
+        self.parts.push_back(lines);
+
+        if waiting {
+            None
+        }
+        else {
+            self.parts.pop_front()
+        }
+    }
+
+    /// The part to write in this pulse, if any.
+    fn next(&mut self) -> Option<Vec<(String, ChatColor)>> {
+        self.parts.pop_front()
+    }
+
+    /// Every part that's still waiting, as one.
+    fn take(&mut self) -> Vec<(String, ChatColor)> {
+        std::mem::take(&mut self.parts)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::eq::Channel;
+    use crate::mq::testing::CapturedChat;
+    use crate::mq::{set_chat_sink, ChatSink};
+
+    fn lines(texts: &[&str]) -> Vec<(String, ChatColor)> {
+        texts
+            .iter()
+            .map(|text| ((*text).to_owned(), ChatColor::default()))
+            .collect()
+    }
+
+    fn texts(lines: &[(String, ChatColor)]) -> Vec<&str> {
+        lines.iter().map(|(line, _)| line.as_str()).collect()
+    }
+
+    // Records each call that crosses over to the sink, as the number of lines
+    // that it wrote.
+    #[derive(Default)]
+    struct Crossings(parking_lot::Mutex<Vec<usize>>);
+
+    impl ChatSink for Crossings {
+        fn write_chat_color(&self, _line: &str, _color: ChatColor) {
+            self.0.lock().push(1);
+        }
+
+        fn write_chat_lines(&self, lines: &[(String, ChatColor)]) {
+            self.0.lock().push(lines.len());
+        }
+    }
+
+    #[test]
+    fn test_push_and_flush() {
+        let chat = CapturedChat::new();
+
+        let mut batch = ChatBatch::new();
+        batch.push("one");
+        batch.push_color("two", Channel::Tell);
+        assert_eq!(batch.len(), 2);
+        assert!(chat.lines().is_empty());
+
+        batch.flush();
+        assert!(batch.is_empty());
+        assert_eq!(
+            chat.lines_with_color(),
+            [
+                ("one".to_owned(), ChatColor::default()),
+                ("two".to_owned(), ChatColor::from(Channel::Tell)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_push_splits_lines() {
+        let chat = CapturedChat::new();
+
+        let mut batch = ChatBatch::new();
+        batch.push("one\ntwo\n\nthree");
+        batch.flush();
+
+        assert_eq!(chat.lines(), ["one", "two", "", "three"]);
+    }
+
+    #[test]
+    fn test_fmt_write() {
+        let chat = CapturedChat::new();
+
+        let mut batch = ChatBatch::new();
+        write!(batch, "one").unwrap();
+        writeln!(batch, " and {}", 1).unwrap();
+        write!(batch, "two\nthree").unwrap();
+        assert_eq!(batch.len(), 3);
+
+        // A partial line is ended by anything pushed after it.
+        batch.push("four");
+        write!(batch, "five").unwrap();
+        batch.flush();
+
+        assert_eq!(chat.lines(), ["one and 1", "two", "three", "four", "five"]);
+    }
+
+    #[test]
+    fn test_flushed_on_drop() {
+        let chat = CapturedChat::new();
+
+        {
+            let mut batch = ChatBatch::new();
+            batch.push("one");
+        }
+
+        assert_eq!(chat.lines(), ["one"]);
+    }
+
+    #[test]
+    fn test_one_crossing() {
+        let _chat = CapturedChat::new();
+        let crossings = Arc::new(Crossings::default());
+        let previous = set_chat_sink(Some(Arc::clone(&crossings) as Arc<dyn ChatSink>));
+
+        let mut batch = ChatBatch::new();
+        for i in 0..100 {
+            writeln!(batch, "line {i}").unwrap();
+        }
+        batch.flush();
+        batch.flush();
+
+        set_chat_sink(previous);
+        assert_eq!(*crossings.0.lock(), [100]);
+    }
+
+    #[test]
+    fn test_stagger_small_batch_is_written_now() {
+        let mut stagger = Stagger::new();
+        let per_pulse = NonZeroUsize::new(3).unwrap();
+
+        let now = stagger.submit(lines(&["a", "b", "c"]), per_pulse).unwrap();
+        assert_eq!(texts(&now), ["a", "b", "c"]);
+        assert!(stagger.next().is_none());
+    }
+
+    #[test]
+    fn test_stagger_splits_across_pulses() {
+        let mut stagger = Stagger::new();
+        let per_pulse = NonZeroUsize::new(2).unwrap();
+
+        let now = stagger
+            .submit(lines(&["a", "b", "c", "d", "e"]), per_pulse)
+            .unwrap();
+        assert_eq!(texts(&now), ["a", "b"]);
+        assert_eq!(texts(&stagger.next().unwrap()), ["c", "d"]);
+        assert_eq!(texts(&stagger.next().unwrap()), ["e"]);
+        assert!(stagger.next().is_none());
+    }
+
+    #[test]
+    fn test_stagger_batches_are_not_interleaved() {
+        let mut stagger = Stagger::new();
+        let per_pulse = NonZeroUsize::new(2).unwrap();
+
+        let now = stagger.submit(lines(&["a", "b", "c"]), per_pulse).unwrap();
+        assert_eq!(texts(&now), ["a", "b"]);
+
+        // A second batch waits for the rest of the first, even though it's
+        // small enough to be written at once.
+        assert!(stagger.submit(lines(&["x"]), per_pulse).is_none());
+        assert_eq!(texts(&stagger.next().unwrap()), ["c"]);
+        assert_eq!(texts(&stagger.next().unwrap()), ["x"]);
+        assert!(stagger.next().is_none());
+    }
+
+    #[test]
+    fn test_stagger_take() {
+        let mut stagger = Stagger::new();
+        let per_pulse = NonZeroUsize::new(1).unwrap();
+
+        stagger.submit(lines(&["a", "b", "c"]), per_pulse);
+        stagger.submit(lines(&["d"]), per_pulse);
+
+        assert_eq!(texts(&stagger.take()), ["b", "c", "d"]);
+        assert!(stagger.next().is_none());
+    }
+
+    #[test]
+    fn test_staggered_batch() {
+        let chat = CapturedChat::new();
+        finish();
+        chat.clear();
+
+        let mut batch = ChatBatch::staggered(NonZeroUsize::new(2).unwrap());
+        batch.push("a\nb\nc\nd\ne");
+        batch.flush();
+        assert_eq!(chat.lines(), ["a", "b"]);
+
+        pulse();
+        assert_eq!(chat.lines(), ["a", "b", "c", "d"]);
+
+        finish();
+        assert_eq!(chat.lines(), ["a", "b", "c", "d", "e"]);
+
+        pulse();
+        assert_eq!(chat.lines(), ["a", "b", "c", "d", "e"]);
+    }
+}
@@ -0,0 +1,214 @@
+//! Converting text to and from the game's character set.
+//!
+//! The game (and so MacroQuest) treats text as Windows-1252 rather than UTF-8,
+//! so any other characters in a line written to chat come out as mojibake. The
+//! characters that Windows-1252 has are encoded as such, a handful of common
+//! symbols that it doesn't have are replaced with an ASCII lookalike (`→`
+//! becomes `->`), and anything else is replaced with a `?`.
+//!
+//! The same goes for text that the game writes, such as its log files, which
+//! is decoded from Windows-1252.
+
+use std::borrow::Cow;
+
+// The character that anything which can't be encoded is replaced with.
+const REPLACEMENT: u8 = b'?';
+
+/// Encode `line` as Windows-1252, replacing anything that it can't represent.
+pub(crate) fn encode(line: &str) -> Cow<'_, [u8]> {
+    if line.is_ascii() {
+        return Cow::Borrowed(line.as_bytes());
+    }
+
+    let mut encoded = Vec::with_capacity(line.len());
+    for c in line.chars() {
+        if let Some(fallback) = fallback(c) {
+            encoded.extend_from_slice(fallback.as_bytes());
+        }
+        else {
+            encoded.push(to_cp1252(c).unwrap_or(REPLACEMENT));
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
+/// Decode `bytes` from Windows-1252.
+///
+/// The five bytes that Windows-1252 leaves undefined are decoded as the
+/// Unicode control characters with the same value, as browsers do.
+pub(crate) fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        if text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+    }
+
+    Cow::Owned(bytes.iter().map(|&byte| from_cp1252(byte)).collect())
+}
+
+/// The character for the Windows-1252 `byte`.
+fn from_cp1252(byte: u8) -> char {
+    match byte {
+        0x80 => '€',
+        0x82 => '‚',
+        0x83 => 'ƒ',
+        0x84 => '„',
+        0x85 => '…',
+        0x86 => '†',
+        0x87 => '‡',
+        0x88 => 'ˆ',
+        0x89 => '‰',
+        0x8A => 'Š',
+        0x8B => '‹',
+        0x8C => 'Œ',
+        0x8E => 'Ž',
+        0x91 => '\u{2018}',
+        0x92 => '\u{2019}',
+        0x93 => '\u{201C}',
+        0x94 => '\u{201D}',
+        0x95 => '•',
+        0x96 => '–',
+        0x97 => '—',
+        0x98 => '˜',
+        0x99 => '™',
+        0x9A => 'š',
+        0x9B => '›',
+        0x9C => 'œ',
+        0x9E => 'ž',
+        0x9F => 'Ÿ',
+        _ => char::from(byte),
+    }
+}
+
+/// The Windows-1252 byte for `c`, if it has one.
+fn to_cp1252(c: char) -> Option<u8> {
+    let byte = match c {
+        // Everything below 0x80, and from 0xA0 up, is the same as Latin-1 and
+        // so the same as the first 256 characters of Unicode.
+        '\0'..='\x7F' | '\u{A0}'..='\u{FF}' => return u8::try_from(c).ok(),
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8A,
+        '‹' => 0x8B,
+        'Œ' => 0x8C,
+        'Ž' => 0x8E,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201C}' => 0x93,
+        '\u{201D}' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9A,
+        '›' => 0x9B,
+        'œ' => 0x9C,
+        'ž' => 0x9E,
+        'Ÿ' => 0x9F,
+        _ => return None,
+    };
+
+    Some(byte)
+}
+
+/// The ASCII replacement for `c`, if it's a symbol that has one.
+fn fallback(c: char) -> Option<&'static str> {
+    let fallback = match c {
+        // Spelled out, like the rest of these, even though Windows-1252 has a
+        // single character ellipsis.
+        '…' => "...",
+        '→' | '⟶' => "->",
+        '←' | '⟵' => "<-",
+        '↔' | '⟷' => "<->",
+        '⇒' | '⟹' => "=>",
+        '⇐' | '≤' => "<=",
+        '↑' => "^",
+        '≥' => ">=",
+        '≠' => "!=",
+        '≈' => "~",
+        '−' | '‐' | '‑' | '‒' | '─' | '━' => "-",
+        '│' | '┃' => "|",
+        '├' | '└' | '┌' | '┐' | '┘' | '┤' | '┼' => "+",
+        '′' => "'",
+        '″' => "\"",
+        '↓' | '✓' | '✔' => "v",
+        '✗' | '✘' => "x",
+        // The other widths of space, and the invisible characters that don't
+        // take up any space at all.
+        '\u{2002}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => " ",
+        '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' => "",
+        _ => return None,
+    };
+
+    Some(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_is_borrowed() {
+        let encoded = encode("[MyPlugin] \x07gready");
+
+        assert!(matches!(encoded, Cow::Borrowed(_)));
+        assert_eq!(&*encoded, b"[MyPlugin] \x07gready");
+    }
+
+    #[test]
+    fn test_latin1() {
+        assert_eq!(&*encode("Café Ñoño ©"), b"Caf\xE9 \xD1o\xF1o \xA9");
+    }
+
+    #[test]
+    fn test_cp1252_specials() {
+        assert_eq!(
+            &*encode("€5 — “quoted” ‘single’ •"),
+            b"\x805 \x97 \x93quoted\x94 \x91single\x92 \x95"
+        );
+        assert_eq!(&*encode("Œuvre™"), b"\x8Cuvre\x99");
+    }
+
+    #[test]
+    fn test_fallbacks() {
+        assert_eq!(&*encode("North → South"), b"North -> South");
+        assert_eq!(&*encode("Loading…"), b"Loading...");
+        assert_eq!(&*encode("HP ≤ 50% ✓"), b"HP <= 50% v");
+        assert_eq!(&*encode("a\u{200B}b\u{2009}c"), b"ab c");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert!(matches!(
+            decode(b"You say, 'hi'"),
+            Cow::Borrowed("You say, 'hi'")
+        ));
+        assert_eq!(decode(b"Caf\xE9 \x93Bl\xE5\x94 \x85"), "Café “Blå” …");
+        assert_eq!(decode(b"\x81\x9D"), "\u{81}\u{9D}");
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        // Except for the ellipsis, which is spelled out, and the undefined
+        // bytes, which aren't characters that the game can show anyway.
+        let skipped = [0x81, 0x85, 0x8D, 0x8F, 0x90, 0x9D];
+        let bytes: Vec<u8> = (0x20..=0xFF).filter(|b| !skipped.contains(b)).collect();
+        assert_eq!(&*encode(&decode(&bytes)), bytes.as_slice());
+    }
+
+    #[test]
+    fn test_unrepresentable() {
+        assert_eq!(&*encode("Sword 剣"), b"Sword ?");
+        assert_eq!(&*encode("gg 🎉🎉"), b"gg ??");
+        // A combining accent is its own character, so only it is replaced.
+        assert_eq!(&*encode("e\u{301}"), b"e?");
+    }
+}
@@ -0,0 +1,930 @@
+//! Registering plugin commands, and parsing the arguments given to them.
+//!
+//! A plugin adds its own slash commands with [`add()`] (or [`add_with()`], to
+//! choose how MacroQuest treats the command), and removes them with
+//! [`remove()`]. Any commands that are still registered when the plugin is
+//! shut down are removed automatically, so that reloading the plugin can add
+//! them again.
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MyPlugin);
+//! use macroquest::mq::{self, commands};
+//!
+//! struct MyPlugin;
+//!
+//! impl Plugin for MyPlugin {
+//!     fn new() -> Self {
+//!         commands::add("/hello", |args| mq::write_chat(format!("hello {args}")))
+//!             .expect("/hello is already a command");
+//!
+//!         MyPlugin
+//!     }
+//! }
+//! # impl Hooks for MyPlugin {}
+//! ```
+//!
+//! A command can also be declared with the
+//! [`#[plugin::command]`](crate::plugin::command) attribute, which adds it when
+//! the plugin is initialized.
+//!
+//! MacroQuest hands a command its arguments as a single string, which it then
+//! splits into individual arguments with `GetArg`. [`split()`] splits them the
+//! same way, [`Args`] gives typed access to them by position, and the
+//! [`CommandArgs`] trait (which can be derived) parses them into a struct of
+//! typed fields.
+//!
+//! ```
+//! use macroquest::mq::commands::{ArgValue, CommandArgs};
+//!
+//! #[derive(Debug, PartialEq, ArgValue)]
+//! enum Speed {
+//!     Walk,
+//!     Run,
+//! }
+//!
+//! #[derive(Debug, CommandArgs)]
+//! struct FollowArgs {
+//!     target:   String,
+//!     distance: Option<u32>,
+//!     speed:    Option<Speed>,
+//! }
+//!
+//! let args = FollowArgs::from_args(r#""a guard" 20 run"#).unwrap();
+//!
+//! assert_eq!(args.target, "a guard");
+//! assert_eq!(args.distance, Some(20));
+//! assert_eq!(args.speed, Some(Speed::Run));
+//! assert_eq!(FollowArgs::usage(), "<target> [distance] [speed]");
+//! ```
+//!
+//! Fields are parsed from the arguments in the order that they're declared,
+//! and their kind is determined by their type:
+//!
+//! * `T` is a required argument.
+//! * `Option<T>` is an optional argument, which may only be followed by other
+//!   optional arguments.
+//! * `Vec<T>` consumes all of the remaining arguments, and must be last.
+//!
+//! Where `T` is any type that implements [`ArgValue`], which includes the
+//! integer and float types, [`String`], [`bool`] (from `on`, `off`, `true`, or
+//! `false`), and any enum of unit variants that derives [`ArgValue`] (from the
+//! name of the variant, in kebab-case).
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::{error, fmt};
+
+use parking_lot::Mutex;
+
+use crate::ffi::mq as mqlib;
+use crate::log::error;
+use crate::macros::debug_assert_main_thread;
+use crate::mq::{charset, write_chat};
+
+#[doc(inline)]
+pub use macroquest_proc_macros::{ArgValue, CommandArgs};
+
+type Handler = Arc<dyn Fn(&str) + Send + Sync>;
+type Declared = (&'static str, fn(&str));
+
+static COMMANDS: Mutex<BTreeMap<String, Handler>> = Mutex::new(BTreeMap::new());
+
+// The commands declared with #[plugin::command], which are added whenever the
+// plugin is initialized.
+static DECLARED: Mutex<Vec<Declared>> = Mutex::new(Vec::new());
+
+/// How MacroQuest treats a command added with [`add_with()`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CommandFlags {
+    /// Whether MacroQuest expands any macro data (ex: `${Me.Name}`) in the
+    /// arguments before they're given to the handler.
+    pub parse:   bool,
+    /// Whether the command is hidden until a character is in game, rather
+    /// than also being available at character select.
+    pub in_game: bool,
+}
+
+impl Default for CommandFlags {
+    /// The flags that [`add()`] uses, which parse the arguments and make the
+    /// command available everywhere, the same as MacroQuest's own defaults.
+    fn default() -> Self {
+        CommandFlags {
+            parse:   true,
+            in_game: false,
+        }
+    }
+}
+
+/// Add the command `name` (ex: `/hello`), calling `handler` with the
+/// arguments it's given whenever it's used.
+///
+/// The handler is called on the game's main thread. The command is removed by
+/// [`remove()`], or when the plugin is shut down.
+///
+/// # Errors
+///
+/// See [`add_with()`].
+pub fn add<F>(name: &str, handler: F) -> Result<(), CommandError>
+where
+    F: Fn(&str) + Send + Sync + 'static,
+{
+    add_with(name, CommandFlags::default(), handler)
+}
+
+/// Add the command `name` like [`add()`], but with `flags` choosing how
+/// MacroQuest treats it.
+///
+/// # Errors
+///
+/// Returns an error if `name` isn't a valid command name (a `/` followed by
+/// at least one character, without any whitespace), if there's already a
+/// command named `name` (whether from this plugin, another plugin, or the
+/// game), or if the plugin already has as many commands as it can add.
+pub fn add_with<F>(
+    name: &str,
+    flags: CommandFlags,
+    handler: F,
+) -> Result<(), CommandError>
+where
+    F: Fn(&str) + Send + Sync + 'static,
+{
+    debug_assert_main_thread!("macroquest::mq::commands::add_with");
+
+    if !is_valid_name(name) {
+        return Err(CommandError::InvalidName {
+            name: name.to_owned(),
+        });
+    }
+
+    let mut commands = COMMANDS.lock();
+    if commands
+        .keys()
+        .any(|command| command.eq_ignore_ascii_case(name))
+    {
+        return Err(CommandError::AlreadyExists {
+            name: name.to_owned(),
+        });
+    }
+
+    if commands.len() >= mqlib::max_commands() {
+        return Err(CommandError::TooMany {
+            max: mqlib::max_commands(),
+        });
+    }
+
+    if !mqlib::add_command(name, flags.parse, flags.in_game, dispatch) {
+        return Err(CommandError::AlreadyExists {
+            name: name.to_owned(),
+        });
+    }
+
+    commands.insert(name.to_owned(), Arc::new(handler));
+    Ok(())
+}
+
+/// Remove the command `name`, returning whether this plugin had added it.
+pub fn remove(name: &str) -> bool {
+    debug_assert_main_thread!("macroquest::mq::commands::remove");
+
+    let mut commands = COMMANDS.lock();
+    let Some(command) = commands
+        .keys()
+        .find(|command| command.eq_ignore_ascii_case(name))
+        .cloned()
+    else {
+        return false;
+    };
+
+    commands.remove(&command);
+    mqlib::remove_command(&command);
+    true
+}
+
+/// An error adding a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommandError {
+    /// The name isn't a valid command name.
+    InvalidName {
+        /// The name that was given.
+        name: String,
+    },
+    /// There's already a command with the same name.
+    AlreadyExists {
+        /// The name of the command.
+        name: String,
+    },
+    /// The plugin already has as many commands as it can add.
+    TooMany {
+        /// How many commands a plugin can add.
+        max: usize,
+    },
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::InvalidName { name } => {
+                write!(f, "invalid command name {name:?}")
+            }
+            CommandError::AlreadyExists { name } => {
+                write!(f, "a command named {name:?} already exists")
+            }
+            CommandError::TooMany { max } => {
+                write!(f, "a plugin can't add more than {max} commands")
+            }
+        }
+    }
+}
+
+impl error::Error for CommandError {}
+
+/// Declare a command to be added whenever the plugin is initialized.
+pub(crate) fn declare(name: &'static str, handler: fn(&str)) {
+    DECLARED.lock().push((name, handler));
+}
+
+/// Add every declared command, as the plugin is being initialized.
+pub(crate) fn add_declared() {
+    let declared = DECLARED.lock().clone();
+    for (name, handler) in declared {
+        if let Err(error) = add(name, handler) {
+            error!(command = name, %error, "could not add command");
+        }
+    }
+}
+
+/// Remove every command, as the plugin is being unloaded.
+pub(crate) fn clear() {
+    for name in std::mem::take(&mut *COMMANDS.lock()).into_keys() {
+        mqlib::remove_command(&name);
+    }
+}
+
+/// Called by MacroQuest whenever one of our commands is used.
+fn dispatch(name: &str, args: &[u8]) {
+    // The handler is cloned out of the registry while it's called, so that it
+    // can add or remove commands itself without deadlocking.
+    let Some(handler) = COMMANDS.lock().get(name).cloned()
+    else {
+        return;
+    };
+
+    let args = charset::decode(args);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        handler(&args);
+    }));
+
+    if result.is_err() {
+        error!(command = name, "command handler panicked");
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    name.strip_prefix('/')
+        .is_some_and(|rest| !rest.is_empty() && !rest.contains(char::is_whitespace))
+}
+
+/// Split a command's arguments the same way that MacroQuest's `GetArg` does.
+///
+/// Arguments are separated by any number of spaces or tabs, except where they
+/// are within double quotes. The quotes themselves are removed, and may
+/// appear anywhere within an argument, while an unterminated quote continues
+/// to the end of the arguments.
+///
+/// ```
+/// use macroquest::mq::commands::split;
+///
+/// assert_eq!(
+///     split(r#"say "hello world"  now"#),
+///     ["say", "hello world", "now"]
+/// );
+/// ```
+#[must_use]
+pub fn split(args: &str) -> Vec<String> {
+    let mut split = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quoted = false;
+
+    for c in args.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                // Even an empty pair of quotes is still an argument.
+                current.get_or_insert_with(String::new);
+            }
+            ' ' | '\t' if !quoted => split.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    split.extend(current);
+    split
+}
+
+/// A command's arguments, split the same way that MacroQuest's `GetArg` does
+/// (see [`split()`]).
+///
+/// ```
+/// use macroquest::mq::commands::Args;
+///
+/// let args = Args::new(r#"follow "a guard" 20"#);
+///
+/// assert_eq!(args.len(), 3);
+/// assert_eq!(args.arg(1), Some("a guard"));
+/// assert_eq!(args.get::<u32>(2), Some(20));
+/// assert_eq!(args.get::<u32>(1), None);
+/// assert_eq!(args.iter().last(), Some("20"));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    /// Split `args` into individual arguments.
+    #[must_use]
+    pub fn new(args: &str) -> Args {
+        Args { args: split(args) }
+    }
+
+    /// How many arguments there are.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Whether there aren't any arguments.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// The argument at `index` (counting from 0), or `None` if there aren't
+    /// that many arguments.
+    #[must_use]
+    pub fn arg(&self, index: usize) -> Option<&str> {
+        self.args.get(index).map(String::as_str)
+    }
+
+    /// Parse the argument at `index` (counting from 0), returning `None` if
+    /// there aren't that many arguments, or if it isn't a valid `T`.
+    #[must_use]
+    pub fn get<T>(&self, index: usize) -> Option<T>
+    where
+        T: ArgValue,
+    {
+        T::parse_arg(self.arg(index)?)
+    }
+
+    /// Iterate over the arguments.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.args.iter().map(String::as_str)
+    }
+
+    /// Parse all of the arguments into `T`, see [`CommandArgs`].
+    ///
+    /// # Errors
+    ///
+    /// See [`CommandArgs::parse()`].
+    pub fn parse<T>(&self) -> Result<T, ArgsError>
+    where
+        T: CommandArgs,
+    {
+        T::parse(&self.args)
+    }
+}
+
+impl From<&str> for Args {
+    fn from(args: &str) -> Self {
+        Args::new(args)
+    }
+}
+
+/// A type that can be parsed from a single command argument.
+///
+/// This can be derived for enums that only have unit variants, which are
+/// parsed (ignoring case) from the name of the variant in kebab-case.
+pub trait ArgValue: Sized {
+    /// Parse the value from `arg`, returning `None` if it isn't valid.
+    fn parse_arg(arg: &str) -> Option<Self>;
+
+    /// A description of the values that are valid, used in error messages
+    /// (ex: "an integer").
+    fn expected() -> Cow<'static, str>;
+}
+
+macro_rules! arg_value_from_str {
+    ($expected:literal: $($ty:ty),+) => {
+        $(
+            impl ArgValue for $ty {
+                fn parse_arg(arg: &str) -> Option<Self> {
+                    arg.parse().ok()
+                }
+
+                fn expected() -> Cow<'static, str> {
+                    Cow::Borrowed($expected)
+                }
+            }
+        )+
+    };
+}
+
+arg_value_from_str!("an integer": i8, i16, i32, i64, isize);
+arg_value_from_str!("a non-negative integer": u8, u16, u32, u64, usize);
+arg_value_from_str!("a number": f32, f64);
+
+impl ArgValue for bool {
+    fn parse_arg(arg: &str) -> Option<Self> {
+        if arg.eq_ignore_ascii_case("on") || arg.eq_ignore_ascii_case("true") {
+            Some(true)
+        }
+        else if arg.eq_ignore_ascii_case("off") || arg.eq_ignore_ascii_case("false") {
+            Some(false)
+        }
+        else {
+            None
+        }
+    }
+
+    fn expected() -> Cow<'static, str> {
+        Cow::Borrowed("on, off, true, or false")
+    }
+}
+
+impl ArgValue for String {
+    fn parse_arg(arg: &str) -> Option<Self> {
+        Some(arg.to_owned())
+    }
+
+    fn expected() -> Cow<'static, str> {
+        Cow::Borrowed("some text")
+    }
+}
+
+/// A type that a command's arguments can be parsed into.
+///
+/// This is normally derived, see the [module documentation](self).
+pub trait CommandArgs: Sized {
+    /// Parse from arguments that have already been split.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an argument is missing, isn't valid, or if there
+    /// are more arguments than expected.
+    fn parse(args: &[String]) -> Result<Self, ArgsError>;
+
+    /// The usage text for the arguments (ex: `<target> [distance]`), without
+    /// the name of the command.
+    fn usage() -> String;
+
+    /// Split `args`, see [`split()`], and parse them.
+    ///
+    /// # Errors
+    ///
+    /// See [`CommandArgs::parse()`].
+    fn from_args(args: &str) -> Result<Self, ArgsError> {
+        Self::parse(&split(args))
+    }
+}
+
+/// Parse the arguments given to `command`, reporting any error to chat along
+/// with the usage text for the command.
+///
+/// ```
+/// # use macroquest::mq::testing::CapturedChat;
+/// use macroquest::mq::commands::{self, CommandArgs};
+///
+/// #[derive(CommandArgs)]
+/// struct Args {
+///     count: u32,
+/// }
+///
+/// # let chat = CapturedChat::new();
+/// let args: Option<Args> = commands::parse_or_report("/count", "many");
+///
+/// assert!(args.is_none());
+/// # assert_eq!(
+/// #     chat.lines(),
+/// #     [
+/// #         r#"/count: invalid value "many" for <count>, expected a non-negative integer"#,
+/// #         "usage: /count <count>",
+/// #     ]
+/// # );
+/// ```
+#[must_use]
+pub fn parse_or_report<T>(command: &str, args: &str) -> Option<T>
+where
+    T: CommandArgs,
+{
+    match T::from_args(args) {
+        Ok(args) => Some(args),
+        Err(error) => {
+            write_chat(format!("{command}: {error}"));
+            write_chat(format!("usage: {command} {}", T::usage()).trim_end());
+            None
+        }
+    }
+}
+
+/// An error parsing a command's arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArgsError {
+    /// A required argument wasn't given.
+    Missing {
+        /// The name of the argument.
+        name: &'static str,
+    },
+    /// An argument wasn't a valid value.
+    Invalid {
+        /// The name of the argument.
+        name:     &'static str,
+        /// The value that was given.
+        value:    String,
+        /// A description of the values that are valid.
+        expected: Cow<'static, str>,
+    },
+    /// More arguments were given than expected.
+    Unexpected {
+        /// The first of the extra arguments.
+        value: String,
+    },
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgsError::Missing { name } => write!(f, "missing <{name}>"),
+            ArgsError::Invalid {
+                name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value {value:?} for <{name}>, expected {expected}"
+            ),
+            ArgsError::Unexpected { value } => {
+                write!(f, "unexpected argument {value:?}")
+            }
+        }
+    }
+}
+
+impl error::Error for ArgsError {}
+
+/// Parses split arguments into values one at a time, which is what the
+/// [`CommandArgs`] derive is implemented with.
+#[derive(Debug)]
+pub struct Parser<'a> {
+    args: &'a [String],
+    next: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Create a parser over the split arguments, `args`.
+    #[must_use]
+    pub fn new(args: &'a [String]) -> Parser<'a> {
+        Parser { args, next: 0 }
+    }
+
+    /// Parse the next argument, which is required.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no arguments left, or if the next one
+    /// isn't valid.
+    pub fn required<T>(&mut self, name: &'static str) -> Result<T, ArgsError>
+    where
+        T: ArgValue,
+    {
+        self.optional(name)?.ok_or(ArgsError::Missing { name })
+    }
+
+    /// Parse the next argument, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the next argument isn't valid.
+    pub fn optional<T>(&mut self, name: &'static str) -> Result<Option<T>, ArgsError>
+    where
+        T: ArgValue,
+    {
+        let Some(value) = self.args.get(self.next)
+        else {
+            return Ok(None);
+        };
+
+        self.next += 1;
+        T::parse_arg(value)
+            .map(Some)
+            .ok_or_else(|| ArgsError::Invalid {
+                name,
+                value: value.clone(),
+                expected: T::expected(),
+            })
+    }
+
+    /// Parse all of the remaining arguments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the remaining arguments aren't valid.
+    pub fn rest<T>(&mut self, name: &'static str) -> Result<Vec<T>, ArgsError>
+    where
+        T: ArgValue,
+    {
+        let mut rest = Vec::with_capacity(self.args.len().saturating_sub(self.next));
+
+        while let Some(value) = self.optional(name)? {
+            rest.push(value);
+        }
+
+        Ok(rest)
+    }
+
+    /// Finish parsing, checking that every argument was parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are arguments left over.
+    pub fn finish(self) -> Result<(), ArgsError> {
+        match self.args.get(self.next) {
+            Some(value) => Err(ArgsError::Unexpected {
+                value: value.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mq::testing::CapturedChat;
+
+    #[test]
+    fn test_valid_names() {
+        assert!(is_valid_name("/hello"));
+        assert!(is_valid_name("/h"));
+        assert!(!is_valid_name("hello"));
+        assert!(!is_valid_name("/"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("/hello world"));
+        assert!(!is_valid_name("//\t"));
+    }
+
+    #[test]
+    fn test_command_error_display() {
+        assert_eq!(
+            CommandError::InvalidName {
+                name: "hello".into(),
+            }
+            .to_string(),
+            "invalid command name \"hello\""
+        );
+        assert_eq!(
+            CommandError::AlreadyExists {
+                name: "/hello".into(),
+            }
+            .to_string(),
+            "a command named \"/hello\" already exists"
+        );
+        assert_eq!(
+            CommandError::TooMany { max: 64 }.to_string(),
+            "a plugin can't add more than 64 commands"
+        );
+    }
+
+    #[derive(Debug, PartialEq, ArgValue)]
+    enum Mode {
+        Fast,
+        SlowAndSteady,
+    }
+
+    #[derive(Debug, PartialEq, CommandArgs)]
+    struct Required {
+        count:   i32,
+        scale:   f32,
+        enabled: bool,
+        mode:    Mode,
+        name:    String,
+    }
+
+    #[derive(Debug, PartialEq, CommandArgs)]
+    struct Optional {
+        count: u32,
+        mode:  Option<Mode>,
+        rest:  Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, CommandArgs)]
+    struct Empty {}
+
+    #[test]
+    fn test_split() {
+        assert_eq!(split("one two"), ["one", "two"]);
+        assert_eq!(split("  one \t two  "), ["one", "two"]);
+        assert_eq!(split(r#""one two" three"#), ["one two", "three"]);
+        assert_eq!(split(r#"one" two "three"#), ["one two three"]);
+        assert_eq!(split(r#"one "" three"#), ["one", "", "three"]);
+        assert_eq!(split(r#"one "two three"#), ["one", "two three"]);
+        assert!(split("").is_empty());
+        assert!(split("   ").is_empty());
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Args::new(r#"  "a guard"  20 "" "#);
+        assert_eq!(args.len(), 3);
+        assert_eq!(args.arg(0), Some("a guard"));
+        assert_eq!(args.get::<u32>(1), Some(20));
+        assert_eq!(args.get::<String>(2), Some(String::new()));
+        assert_eq!(args.arg(3), None);
+        assert_eq!(args.get::<u32>(3), None);
+        assert_eq!(args.iter().collect::<Vec<_>>(), ["a guard", "20", ""]);
+    }
+
+    #[test]
+    fn test_args_empty() {
+        for input in ["", " \t "] {
+            let args = Args::new(input);
+            assert!(args.is_empty());
+            assert_eq!(args.iter().count(), 0);
+            assert_eq!(args.arg(0), None);
+        }
+    }
+
+    #[test]
+    fn test_args_trailing_whitespace() {
+        let args = Args::from("one two \t");
+        assert_eq!(args.iter().collect::<Vec<_>>(), ["one", "two"]);
+
+        // An unterminated quote keeps the whitespace after it.
+        let args = Args::from(r#"one "two "#);
+        assert_eq!(args.iter().collect::<Vec<_>>(), ["one", "two "]);
+    }
+
+    #[test]
+    fn test_args_parse() {
+        let args = Args::new("3 1.5 on fast bob");
+        assert_eq!(
+            args.parse::<Required>(),
+            Ok(Required {
+                count:   3,
+                scale:   1.5,
+                enabled: true,
+                mode:    Mode::Fast,
+                name:    "bob".into(),
+            })
+        );
+        assert_eq!(
+            Args::new("3").parse::<Required>(),
+            Err(ArgsError::Missing { name: "scale" })
+        );
+    }
+
+    #[test]
+    fn test_parse_ints() {
+        assert_eq!(i32::parse_arg("-12"), Some(-12));
+        assert_eq!(u8::parse_arg("255"), Some(255));
+        assert_eq!(u8::parse_arg("256"), None);
+        assert_eq!(u32::parse_arg("-1"), None);
+        assert_eq!(i64::parse_arg("1.5"), None);
+    }
+
+    #[test]
+    fn test_parse_floats() {
+        assert_eq!(f32::parse_arg("1.5"), Some(1.5));
+        assert_eq!(f64::parse_arg("-2"), Some(-2.0));
+        assert_eq!(f64::parse_arg("abc"), None);
+    }
+
+    #[test]
+    fn test_parse_bools() {
+        for arg in ["on", "ON", "true", "True"] {
+            assert_eq!(bool::parse_arg(arg), Some(true), "{arg}");
+        }
+        for arg in ["off", "Off", "false", "FALSE"] {
+            assert_eq!(bool::parse_arg(arg), Some(false), "{arg}");
+        }
+        for arg in ["yes", "1", ""] {
+            assert_eq!(bool::parse_arg(arg), None, "{arg}");
+        }
+    }
+
+    #[test]
+    fn test_parse_enums() {
+        assert_eq!(Mode::parse_arg("fast"), Some(Mode::Fast));
+        assert_eq!(Mode::parse_arg("FAST"), Some(Mode::Fast));
+        assert_eq!(
+            Mode::parse_arg("slow-and-steady"),
+            Some(Mode::SlowAndSteady)
+        );
+        assert_eq!(Mode::parse_arg("slowandsteady"), None);
+        assert_eq!(Mode::expected(), "one of fast, slow-and-steady");
+    }
+
+    #[test]
+    fn test_required() {
+        assert_eq!(
+            Required::from_args(r#"-3 0.5 on slow-and-steady "a name""#),
+            Ok(Required {
+                count:   -3,
+                scale:   0.5,
+                enabled: true,
+                mode:    Mode::SlowAndSteady,
+                name:    "a name".into(),
+            })
+        );
+        assert_eq!(
+            Required::from_args("-3 0.5 on"),
+            Err(ArgsError::Missing { name: "mode" })
+        );
+        assert_eq!(
+            Required::from_args("-3 0.5 maybe fast name"),
+            Err(ArgsError::Invalid {
+                name:     "enabled",
+                value:    "maybe".into(),
+                expected: "on, off, true, or false".into(),
+            })
+        );
+        assert_eq!(
+            Required::from_args("-3 0.5 on fast name extra"),
+            Err(ArgsError::Unexpected {
+                value: "extra".into(),
+            })
+        );
+        assert_eq!(Required::usage(), "<count> <scale> <enabled> <mode> <name>");
+    }
+
+    #[test]
+    fn test_optional_and_rest() {
+        assert_eq!(
+            Optional::from_args("5"),
+            Ok(Optional {
+                count: 5,
+                mode:  None,
+                rest:  vec![],
+            })
+        );
+        assert_eq!(
+            Optional::from_args("5 fast a \"b c\""),
+            Ok(Optional {
+                count: 5,
+                mode:  Some(Mode::Fast),
+                rest:  vec!["a".into(), "b c".into()],
+            })
+        );
+        assert_eq!(
+            Optional::from_args("5 medium"),
+            Err(ArgsError::Invalid {
+                name:     "mode",
+                value:    "medium".into(),
+                expected: "one of fast, slow-and-steady".into(),
+            })
+        );
+        assert_eq!(Optional::usage(), "<count> [mode] [rest...]");
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(Empty::from_args("  "), Ok(Empty {}));
+        assert_eq!(
+            Empty::from_args("extra"),
+            Err(ArgsError::Unexpected {
+                value: "extra".into(),
+            })
+        );
+        assert_eq!(Empty::usage(), "");
+    }
+
+    #[test]
+    fn test_parse_or_report() {
+        let chat = CapturedChat::new();
+
+        let args: Option<Optional> = parse_or_report("/thing", "5 fast");
+        assert!(args.is_some());
+        assert!(chat.lines().is_empty());
+
+        let args: Option<Optional> = parse_or_report("/thing", "");
+        assert!(args.is_none());
+        assert_eq!(
+            chat.lines(),
+            [
+                "/thing: missing <count>",
+                "usage: /thing <count> [mode] [rest...]"
+            ]
+        );
+
+        chat.clear();
+        let args: Option<Empty> = parse_or_report("/empty", "extra");
+        assert!(args.is_none());
+        assert_eq!(
+            chat.lines(),
+            [r#"/empty: unexpected argument "extra""#, "usage: /empty"]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! The conventional directories for a plugin's configuration files, within
+//! MacroQuest's Config directory.
+//!
+//! A plugin's own files go in `Config\<plugin>`, while those that belong to a
+//! server or a character go in `Config\<plugin>\<server>` and
+//! `Config\<plugin>\<server>.<character>` (ex: `Config\MyPlugin\firiona.vox`).
+//!
+//! The server and character are looked up when we enter the game, and then
+//! again whenever the local character changes. `SetGameState` is only hooked
+//! for plugins that implement [`Hooks::game_state`](crate::plugin::Hooks), so
+//! rather than relying on it, the local character is compared with the one
+//! that was last looked up whenever one of the directories is asked for.
+
+use std::borrow::Cow;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+
+use crate::eq::Character;
+use crate::mq;
+
+// The characters that Windows doesn't allow in a file name.
+const INVALID: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+// The device names that Windows doesn't allow as a file name, with or without
+// an extension.
+const RESERVED: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7",
+    "COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8",
+    "LPT9",
+];
+
+static CURRENT: Mutex<Current> = Mutex::new(Current {
+    character: 0,
+    identity:  None,
+});
+
+/// The server and name of the character that we're playing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Identity {
+    pub(crate) server:    String,
+    pub(crate) character: String,
+}
+
+struct Current {
+    // The address of the local character that the identity was looked up for,
+    // or 0 if it hasn't been.
+    character: usize,
+    identity:  Option<Identity>,
+}
+
+/// The identity of the character that we're playing, or `None` if we're not
+/// in game.
+pub(crate) fn identity() -> Option<Identity> {
+    let character = mq::character();
+    let address = character.map_or(0, |c| std::ptr::from_ref::<Character>(c) as usize);
+
+    let mut current = CURRENT.lock();
+    if current.character != address {
+        current.identity = character.and_then(|character| {
+            let server: String = mq::tlo("MacroQuest.Server").ok()?;
+
+            Some(Identity {
+                server,
+                character: character.name().to_owned(),
+            })
+        });
+
+        // Not remembered until the lookup succeeds, so that it's retried if the
+        // server isn't known yet.
+        if current.identity.is_some() || character.is_none() {
+            current.character = address;
+        }
+    }
+
+    current.identity.clone()
+}
+
+/// The directory for the files of the plugin named `plugin`.
+pub(crate) fn plugin_dir(config: &Path, plugin: &str) -> PathBuf {
+    config.join(&*sanitize(plugin))
+}
+
+/// The directory for the files of the plugin named `plugin` that belong to
+/// the server of `identity`.
+pub(crate) fn server_dir(config: &Path, plugin: &str, identity: &Identity) -> PathBuf {
+    plugin_dir(config, plugin).join(&*sanitize(&identity.server.to_lowercase()))
+}
+
+/// The directory for the files of the plugin named `plugin` that belong to
+/// the character of `identity`.
+pub(crate) fn character_dir(
+    config: &Path,
+    plugin: &str,
+    identity: &Identity,
+) -> PathBuf {
+    let name = format!(
+        "{}.{}",
+        identity.server.to_lowercase(),
+        identity.character.to_lowercase()
+    );
+
+    plugin_dir(config, plugin).join(&*sanitize(&name))
+}
+
+/// Create `dir` if it doesn't exist already, returning it.
+pub(crate) fn create(dir: PathBuf) -> io::Result<PathBuf> {
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Turn `name` into a valid path component, replacing any characters that
+/// Windows doesn't allow (or that would change the directory) with `_`.
+fn sanitize(name: &str) -> Cow<'_, str> {
+    let trimmed = name.trim_end_matches(['.', ' ']);
+    let stem = trimmed.split('.').next().unwrap_or(trimmed);
+
+    let is_valid = !trimmed.is_empty()
+        && !trimmed.contains(|c: char| c.is_control() || INVALID.contains(&c))
+        && !RESERVED
+            .iter()
+            .any(|reserved| stem.eq_ignore_ascii_case(reserved));
+
+    if is_valid && trimmed.len() == name.len() {
+        return Cow::Borrowed(name);
+    }
+
+    let mut sanitized: String = trimmed
+        .chars()
+        .map(|c| {
+            if c.is_control() || INVALID.contains(&c) {
+                '_'
+            }
+            else {
+                c
+            }
+        })
+        .collect();
+
+    if sanitized.is_empty()
+        || RESERVED
+            .iter()
+            .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        sanitized.insert(0, '_');
+    }
+
+    Cow::Owned(sanitized)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    // A directory of our own in the temp directory, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let path = std::env::temp_dir().join(format!(
+                "macroquest-config-dirs-{name}-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn identity(server: &str, character: &str) -> Identity {
+        Identity {
+            server:    server.to_owned(),
+            character: character.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_sanitize_valid() {
+        assert!(matches!(sanitize("MyPlugin"), Cow::Borrowed("MyPlugin")));
+        assert!(matches!(
+            sanitize("firiona.vox"),
+            Cow::Borrowed("firiona.vox")
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_invalid_characters() {
+        assert_eq!(sanitize("a/b\\c"), "a_b_c");
+        assert_eq!(sanitize("what?*"), "what__");
+        assert_eq!(sanitize("tab\there"), "tab_here");
+    }
+
+    #[test]
+    fn test_sanitize_changes_directory() {
+        assert_eq!(sanitize(".."), "_");
+        assert_eq!(sanitize("."), "_");
+        assert_eq!(sanitize(""), "_");
+        assert_eq!(sanitize("../up"), ".._up");
+    }
+
+    #[test]
+    fn test_sanitize_trailing() {
+        assert_eq!(sanitize("name. "), "name");
+        assert_eq!(sanitize("name..."), "name");
+    }
+
+    #[test]
+    fn test_sanitize_reserved() {
+        assert_eq!(sanitize("con"), "_con");
+        assert_eq!(sanitize("NUL.txt"), "_NUL.txt");
+        assert_eq!(sanitize("COM10"), "COM10");
+        assert_eq!(sanitize("console"), "console");
+    }
+
+    #[test]
+    fn test_dirs() {
+        let config = Path::new("/mq/config");
+        let vox = identity("Firiona", "Vox");
+
+        assert_eq!(
+            plugin_dir(config, "MyPlugin"),
+            Path::new("/mq/config/MyPlugin")
+        );
+        assert_eq!(
+            server_dir(config, "MyPlugin", &vox),
+            Path::new("/mq/config/MyPlugin/firiona")
+        );
+        assert_eq!(
+            character_dir(config, "MyPlugin", &vox),
+            Path::new("/mq/config/MyPlugin/firiona.vox")
+        );
+        assert_eq!(
+            character_dir(config, "../MyPlugin", &identity("a/b", "c")),
+            Path::new("/mq/config/.._MyPlugin/a_b.c")
+        );
+    }
+
+    #[test]
+    fn test_create() {
+        let temp = TempDir::new("create");
+        let vox = identity("firiona", "Vox");
+
+        let dir = create(character_dir(&temp.0, "MyPlugin", &vox)).unwrap();
+        assert_eq!(dir, temp.0.join("MyPlugin").join("firiona.vox"));
+        assert!(dir.is_dir());
+
+        // Creating it again is fine, and leaves what's in it alone.
+        fs::write(dir.join("settings.toml"), "x = 1").unwrap();
+        let again = create(character_dir(&temp.0, "MyPlugin", &vox)).unwrap();
+        assert_eq!(again, dir);
+        assert!(dir.join("settings.toml").is_file());
+    }
+
+    #[test]
+    fn test_create_under_a_file() {
+        let temp = TempDir::new("under-a-file");
+        fs::write(temp.0.join("MyPlugin"), "").unwrap();
+
+        assert!(create(plugin_dir(&temp.0, "MyPlugin").join("firiona")).is_err());
+    }
+}
@@ -0,0 +1,424 @@
+//! Sharing a plugin's data with macros and Lua, through a MacroQuest datatype.
+//!
+//! MacroQuest exposes data to macros and Lua scripts through top level objects
+//! (TLOs), such as `${Me}`, each of which returns a datatype with members, such
+//! as `${Me.PctHPs}`. Registering a [`DataType`] adds both a datatype and a TLO
+//! with the same name, whose members are looked up by calling into the plugin.
+//!
+//! Macros and Lua read datatypes the same way, so a datatype registered as
+//! `RustSample` with a `Counter` member can be read as `${RustSample.Counter}`
+//! from a macro and as `mq.TLO.RustSample.Counter()` from Lua, and both get the
+//! same value. The members are registered with MacroQuest by name, so they're
+//! also listed when a Lua script inspects the datatype.
+//!
+//! ```no_run
+//! use std::sync::atomic::{AtomicI64, Ordering};
+//! use std::sync::Arc;
+//!
+//! use macroquest::mq::datatypes::{self, DataType, Value};
+//!
+//! struct Sample {
+//!     counter: Arc<AtomicI64>,
+//! }
+//!
+//! impl DataType for Sample {
+//!     fn members(&self) -> &[&str] {
+//!         &["Counter"]
+//!     }
+//!
+//!     fn member(&self, member: &str, _index: Option<&str>) -> Value {
+//!         match member {
+//!             "Counter" => self.counter.load(Ordering::Relaxed).into(),
+//!             _ => Value::Null,
+//!         }
+//!     }
+//! }
+//!
+//! let counter = Arc::new(AtomicI64::new(0));
+//! let tlo = datatypes::register(
+//!     "RustSample",
+//!     Sample {
+//!         counter: Arc::clone(&counter),
+//!     },
+//! )
+//! .unwrap();
+//! ```
+//!
+//! # Lifetimes
+//!
+//! MacroQuest calls into the plugin whenever the datatype is used, so it must
+//! be removed before the plugin is unloaded. The datatype (and its TLO) is
+//! removed when its [`TopLevelObject`] is dropped, and every datatype that's
+//! still registered is removed when the plugin is unloaded, after the plugin
+//! itself has been dropped.
+//!
+//! Datatypes are only used from the game's main thread, and everything in here
+//! must be called from it too.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::{error, fmt};
+
+use parking_lot::Mutex;
+
+use crate::ffi::datatypes as dtlib;
+use crate::log::error;
+use crate::macros::debug_assert_main_thread;
+
+static DATATYPES: Mutex<Registry> = Mutex::new(Registry::new());
+
+/// The data behind a MacroQuest datatype.
+///
+/// The methods are called on the game's main thread, whenever a macro or Lua
+/// script reads the datatype.
+pub trait DataType: Send + Sync + 'static {
+    /// The names of the datatype's members.
+    ///
+    /// This is only called once, when the datatype is registered. MacroQuest
+    /// looks members up ignoring case, and [`DataType::member()`] is always
+    /// called with the name as it's given here.
+    fn members(&self) -> &[&str];
+
+    /// The value of the member named `member`, where `index` is what was
+    /// given in brackets after it, if anything (ex: `2` for
+    /// `${RustSample.Item[2]}`).
+    ///
+    /// Returning [`Value::Null`] makes the member `NULL`.
+    fn member(&self, member: &str, index: Option<&str>) -> Value;
+
+    /// The text of the datatype itself, such as `${RustSample}`.
+    ///
+    /// The default is the name of the datatype.
+    fn text(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The value of a member of a [`DataType`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// No value, which macros see as `NULL`, and Lua sees as `nil`.
+    Null,
+    /// An integer.
+    Int(i64),
+    /// A floating point number.
+    Float(f64),
+    /// `TRUE` or `FALSE`.
+    Bool(bool),
+    /// A string, which is truncated if it's longer than MacroQuest allows.
+    String(String),
+}
+
+impl Value {
+    fn to_raw(&self, raw: &mut dtlib::MemberValue) {
+        match self {
+            Value::Null => raw.kind = dtlib::MemberKind::Null,
+            Value::Int(value) => {
+                raw.kind = dtlib::MemberKind::Int;
+                raw.integer = *value;
+            }
+            Value::Float(value) => {
+                raw.kind = dtlib::MemberKind::Float;
+                raw.number = *value;
+            }
+            Value::Bool(value) => {
+                raw.kind = dtlib::MemberKind::Bool;
+                raw.boolean = *value;
+            }
+            Value::String(value) => {
+                raw.kind = dtlib::MemberKind::Text;
+                raw.text.clone_from(value);
+            }
+        }
+    }
+}
+
+macro_rules! value_from {
+    ($variant:ident($inner:ty): $($ty:ty),+) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    Value::$variant(<$inner>::from(value))
+                }
+            }
+        )+
+    };
+}
+
+value_from!(Int(i64): i8, i16, i32, i64, u8, u16, u32);
+value_from!(Float(f64): f32, f64);
+value_from!(Bool(bool): bool);
+value_from!(String(String): String, &str);
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+/// Register `datatype` as both a datatype and a TLO named `name`.
+///
+/// The datatype is removed when the returned [`TopLevelObject`] is dropped, or
+/// when the plugin is unloaded, whichever comes first.
+///
+/// # Errors
+///
+/// Returns an error if there's already a datatype or TLO named `name` (whether
+/// from this plugin, another plugin, or MacroQuest itself).
+pub fn register<T>(name: &str, datatype: T) -> Result<TopLevelObject, DataTypeError>
+where
+    T: DataType,
+{
+    debug_assert_main_thread!("macroquest::mq::datatypes::register");
+
+    let members: Vec<String> = datatype
+        .members()
+        .iter()
+        .map(|&member| member.to_owned())
+        .collect();
+
+    let mut datatypes = DATATYPES.lock();
+    if !datatypes.insert(name, Arc::new(datatype)) {
+        return Err(DataTypeError::AlreadyRegistered {
+            name: name.to_owned(),
+        });
+    }
+
+    if !dtlib::add_datatype(name, &members, get_member, to_string) {
+        datatypes.remove(name);
+        return Err(DataTypeError::AlreadyRegistered {
+            name: name.to_owned(),
+        });
+    }
+
+    Ok(TopLevelObject {
+        name: name.to_owned(),
+    })
+}
+
+/// A datatype, and the TLO that returns it, registered with MacroQuest.
+///
+/// The datatype and TLO are removed when this is dropped.
+#[derive(Debug)]
+pub struct TopLevelObject {
+    name: String,
+}
+
+impl TopLevelObject {
+    /// The name that the datatype and TLO were registered with.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for TopLevelObject {
+    fn drop(&mut self) {
+        debug_assert_main_thread!("macroquest::mq::datatypes::TopLevelObject::drop");
+
+        if DATATYPES.lock().remove(&self.name) {
+            dtlib::remove_datatype(&self.name);
+        }
+    }
+}
+
+/// An error registering a [`DataType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DataTypeError {
+    /// There's already a datatype or TLO with the same name.
+    AlreadyRegistered {
+        /// The name of the datatype.
+        name: String,
+    },
+}
+
+impl fmt::Display for DataTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataTypeError::AlreadyRegistered { name } => {
+                write!(f, "a datatype or TLO named {name:?} already exists")
+            }
+        }
+    }
+}
+
+impl error::Error for DataTypeError {}
+
+/// Remove every datatype, as the plugin is being unloaded.
+pub(crate) fn clear() {
+    for name in DATATYPES.lock().clear() {
+        dtlib::remove_datatype(&name);
+    }
+}
+
+/// Called by MacroQuest to look up a member of one of our datatypes.
+fn get_member(
+    datatype: &str,
+    member: &str,
+    index: &str,
+    raw: &mut dtlib::MemberValue,
+) -> bool {
+    let index = (!index.is_empty()).then_some(index);
+    let value = call(datatype, |datatype| datatype.member(member, index));
+
+    match value {
+        Some(value) => {
+            value.to_raw(raw);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Called by MacroQuest to get the text of one of our datatypes.
+fn to_string(datatype: &str, text: &mut String) -> bool {
+    match call(datatype, DataType::text).flatten() {
+        Some(value) => {
+            *text = value;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Call `f` with the datatype named `name`, returning `None` if there isn't
+/// one, or if it panicked.
+fn call<F, R>(name: &str, f: F) -> Option<R>
+where
+    F: FnOnce(&dyn DataType) -> R,
+{
+    // The datatype is cloned out of the registry while it's called, so that it
+    // can register or drop datatypes itself without deadlocking.
+    let datatype = DATATYPES.lock().get(name)?;
+
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(datatype.as_ref())));
+
+    if result.is_err() {
+        error!(datatype = name, "datatype panicked");
+    }
+
+    result.ok()
+}
+
+struct Registry {
+    datatypes: BTreeMap<String, Arc<dyn DataType>>,
+}
+
+impl Registry {
+    const fn new() -> Registry {
+        Registry {
+            datatypes: BTreeMap::new(),
+        }
+    }
+
+    /// Add a datatype, returning false if there's already one with that name.
+    fn insert(&mut self, name: &str, datatype: Arc<dyn DataType>) -> bool {
+        if self.datatypes.contains_key(name) {
+            return false;
+        }
+
+        self.datatypes.insert(name.to_owned(), datatype);
+        true
+    }
+
+    /// Remove a datatype, returning whether there was one with that name.
+    fn remove(&mut self, name: &str) -> bool {
+        self.datatypes.remove(name).is_some()
+    }
+
+    fn get(&self, name: &str) -> Option<Arc<dyn DataType>> {
+        self.datatypes.get(name).cloned()
+    }
+
+    /// Remove every datatype, returning their names.
+    fn clear(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.datatypes).into_keys().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sample;
+
+    impl DataType for Sample {
+        fn members(&self) -> &[&str] {
+            &["Counter", "Item"]
+        }
+
+        fn member(&self, member: &str, index: Option<&str>) -> Value {
+            match (member, index) {
+                ("Counter", _) => 42.into(),
+                ("Item", Some(index)) => format!("item {index}").into(),
+                _ => Value::Null,
+            }
+        }
+    }
+
+    fn raw() -> dtlib::MemberValue {
+        dtlib::MemberValue {
+            kind:    dtlib::MemberKind::Null,
+            integer: 0,
+            number:  0.0,
+            boolean: false,
+            text:    String::new(),
+        }
+    }
+
+    #[test]
+    fn test_value_from() {
+        assert_eq!(Value::from(5u32), Value::Int(5));
+        assert_eq!(Value::from(1.5f32), Value::Float(1.5));
+        assert_eq!(Value::from("text"), Value::String("text".into()));
+        assert_eq!(Value::from(Some(true)), Value::Bool(true));
+        assert_eq!(Value::from(None::<i32>), Value::Null);
+    }
+
+    #[test]
+    fn test_value_to_raw() {
+        let mut value = raw();
+
+        Value::Int(-3).to_raw(&mut value);
+        assert_eq!(value.kind, dtlib::MemberKind::Int);
+        assert_eq!(value.integer, -3);
+
+        Value::String("hello".into()).to_raw(&mut value);
+        assert_eq!(value.kind, dtlib::MemberKind::Text);
+        assert_eq!(value.text, "hello");
+
+        Value::Null.to_raw(&mut value);
+        assert_eq!(value.kind, dtlib::MemberKind::Null);
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+
+        assert!(registry.insert("RustSample", Arc::new(Sample)));
+        assert!(!registry.insert("RustSample", Arc::new(Sample)));
+
+        let sample = registry.get("RustSample").unwrap();
+        assert_eq!(sample.member("Counter", None), Value::Int(42));
+        assert_eq!(
+            sample.member("Item", Some("2")),
+            Value::String("item 2".into())
+        );
+        assert_eq!(sample.member("Item", None), Value::Null);
+        assert_eq!(sample.text(), None);
+
+        assert!(registry.remove("RustSample"));
+        assert!(registry.get("RustSample").is_none());
+        assert!(registry.clear().is_empty());
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            DataTypeError::AlreadyRegistered { name: "Me".into() }.to_string(),
+            "a datatype or TLO named \"Me\" already exists"
+        );
+    }
+}
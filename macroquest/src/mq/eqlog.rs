@@ -0,0 +1,753 @@
+//! Following the game's own log file.
+//!
+//! With logging turned on (`/log on`), the game writes everything shown in
+//! chat to `Logs/eqlog_<character>_<server>.txt` in the EverQuest directory,
+//! and some of it (such as loot rolls) never passes through any of the plugin
+//! hooks. A [`Tailer`] reads the lines that the game adds to its log, as it
+//! adds them, parsing the timestamp that the game starts each of them with.
+//!
+//! The log may not exist (if logging has never been turned on), may stop
+//! growing (if logging is turned off), or may be truncated or replaced (if
+//! it's cleared or archived while the game is running), none of which are
+//! errors: a [`Tailer`] reads whatever is added to the log from then on.
+//!
+//! # Examples
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MyPlugin);
+//! use macroquest::eq::GameState;
+//! use macroquest::log::info;
+//! use macroquest::mq::eqlog::{TailHandle, Tailer};
+//! use parking_lot::Mutex;
+//!
+//! struct MyPlugin {
+//!     log: Mutex<Option<TailHandle>>,
+//! }
+//!
+//! impl Plugin for MyPlugin {
+//!     fn new() -> Self {
+//!         MyPlugin {
+//!             log: Mutex::new(None),
+//!         }
+//!     }
+//! }
+//!
+//! #[macroquest::plugin::hooks]
+//! impl Hooks for MyPlugin {
+//!     fn game_state(&self, state: GameState) {
+//!         // The log is named after the character, so it's only known once
+//!         // we're in game, and changes with the character.
+//!         let tailer = match state {
+//!             GameState::InGame => Tailer::for_character(),
+//!             _ => None,
+//!         };
+//!
+//!         *self.log.lock() = tailer.map(|tailer| {
+//!             tailer.register(|line| {
+//!                 if line.text.contains("Magic Die is rolled") {
+//!                     info!(roll = %line.text, "loot roll");
+//!                 }
+//!             })
+//!         });
+//!     }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use super::charset;
+use crate::log::error;
+use crate::mq;
+use crate::plugin::panic_message;
+
+type Handler = Box<dyn FnMut(&LogLine) + Send>;
+
+static TAILERS: Mutex<Registry> = Mutex::new(Registry::new());
+
+// How often a registered tailer checks its log for new lines.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// The time that the game wrote a line to its log, in the local time of the
+/// computer that it's running on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    /// The year (ex: `2024`).
+    pub year:   u16,
+    /// The month, from `1` to `12`.
+    pub month:  u8,
+    /// The day of the month, from `1` to `31`.
+    pub day:    u8,
+    /// The hour, from `0` to `23`.
+    pub hour:   u8,
+    /// The minute, from `0` to `59`.
+    pub minute: u8,
+    /// The second, from `0` to `60` (for a leap second).
+    pub second: u8,
+}
+
+impl Timestamp {
+    /// Parse the timestamp that the game starts each line of its log with,
+    /// without the brackets around it (ex: `Mon Oct 14 20:15:02 2024`).
+    #[must_use]
+    pub fn parse(timestamp: &str) -> Option<Timestamp> {
+        let mut parts = timestamp.split_whitespace();
+
+        // The day of the week is implied by the date, so it's skipped.
+        let _weekday = parts.next()?;
+        let month = parts.next()?;
+        let month = MONTHS.iter().position(|name| *name == month)?;
+        let day = parts.next()?.parse().ok()?;
+        let time = parts.next()?;
+        let year = parts.next()?.parse().ok()?;
+
+        let mut time = time.split(':');
+        let hour = time.next()?.parse().ok()?;
+        let minute = time.next()?.parse().ok()?;
+        let second = time.next()?.parse().ok()?;
+
+        if parts.next().is_some() || time.next().is_some() {
+            return None;
+        }
+
+        let timestamp = Timestamp {
+            year,
+            month: u8::try_from(month + 1).ok()?,
+            day,
+            hour,
+            minute,
+            second,
+        };
+
+        timestamp.is_valid().then_some(timestamp)
+    }
+
+    fn is_valid(self) -> bool {
+        (1..=31).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second <= 60
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// A single line of the game's log.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LogLine {
+    /// When the game wrote the line, or `None` if it doesn't start with a
+    /// timestamp.
+    pub timestamp: Option<Timestamp>,
+    /// The text of the line, without the timestamp.
+    pub text:      String,
+}
+
+impl LogLine {
+    /// Parse a line of the game's log (ex: `[Mon Oct 14 20:15:02 2024] You
+    /// have entered The Plane of Knowledge.`), splitting off its timestamp.
+    ///
+    /// A line that doesn't start with a valid timestamp is kept whole, with no
+    /// timestamp.
+    #[must_use]
+    pub fn parse(line: &str) -> LogLine {
+        let parsed = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .and_then(|(timestamp, text)| {
+                let timestamp = Timestamp::parse(timestamp)?;
+                Some((timestamp, text.strip_prefix(' ').unwrap_or(text)))
+            });
+
+        match parsed {
+            Some((timestamp, text)) => LogLine {
+                timestamp: Some(timestamp),
+                text:      text.to_owned(),
+            },
+            None => LogLine {
+                timestamp: None,
+                text:      line.to_owned(),
+            },
+        }
+    }
+}
+
+/// The path of the log for the character named `character` on `server`,
+/// within the EverQuest directory `everquest`.
+#[must_use]
+pub fn log_path(everquest: &Path, character: &str, server: &str) -> PathBuf {
+    everquest
+        .join("Logs")
+        .join(format!("eqlog_{character}_{server}.txt"))
+}
+
+/// Reads the lines that the game adds to one of its logs.
+///
+/// Lines can be read by calling [`Tailer::poll()`], or the tailer can be
+/// [registered](Tailer::register) to have them passed to a handler as they're
+/// added.
+#[derive(Debug)]
+pub struct Tailer {
+    path:     PathBuf,
+    // How far into the log has been read, or None if it hasn't been read yet.
+    position: Option<u64>,
+    // The start of a line that the game hasn't finished writing yet.
+    partial:  Vec<u8>,
+}
+
+impl Tailer {
+    /// Tail the log of the character named `character` on `server`, which is
+    /// the short name of the server (ex: `vox`), as the log is named with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if MacroQuest hasn't set its paths yet, see
+    /// [`mq::paths()`](crate::mq::paths).
+    #[must_use]
+    pub fn new(character: &str, server: &str) -> Tailer {
+        Tailer::from_path(log_path(mq::paths().everquest(), character, server))
+    }
+
+    /// Tail the log of the character that we're playing, or `None` if we're
+    /// not playing a character.
+    #[must_use]
+    pub fn for_character() -> Option<Tailer> {
+        let character = mq::character()?;
+        let server: String = mq::tlo("MacroQuest.Server").ok()?;
+
+        Some(Tailer::new(character.name(), &server))
+    }
+
+    /// Tail the log at `path`.
+    pub fn from_path<P>(path: P) -> Tailer
+    where
+        P: Into<PathBuf>,
+    {
+        Tailer {
+            path:     path.into(),
+            position: None,
+            partial:  Vec::new(),
+        }
+    }
+
+    /// The path of the log that's being tailed.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the lines that have been added to the log since it was last
+    /// polled.
+    ///
+    /// The first poll skips over everything that's already in the log, so
+    /// only the lines added after it are read, unless the log doesn't exist
+    /// yet, in which case everything in it is read once it does. If the log
+    /// is shorter than it was (as it has been truncated or replaced), it's
+    /// read again from its start.
+    ///
+    /// A line that the game hasn't finished writing is held until it has.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log exists but couldn't be read.
+    pub fn poll(&mut self) -> io::Result<Vec<LogLine>> {
+        // The log is opened afresh every time, rather than held open, so that
+        // a log that has been replaced is noticed, and so that the log is
+        // never held open while the game (or the player) is moving it.
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                self.restart();
+                return Ok(Vec::new());
+            }
+            Err(error) => return Err(error),
+        };
+
+        let len = file.metadata()?.len();
+        let position = match self.position {
+            None => len,
+            Some(position) if len < position => {
+                self.restart();
+                0
+            }
+            Some(position) => position,
+        };
+
+        file.seek(SeekFrom::Start(position))?;
+        let read = file.take(len - position).read_to_end(&mut self.partial)?;
+        self.position = Some(position + read as u64);
+
+        Ok(take_lines(&mut self.partial))
+    }
+
+    /// Poll the log from every pulse, calling `handler` with each line that's
+    /// added to it, until the returned [`TailHandle`] is dropped.
+    ///
+    /// The handler is called on the game's main thread. Any error reading the
+    /// log is logged rather than stopping the tailer, as the log may become
+    /// readable again.
+    pub fn register<F>(self, handler: F) -> TailHandle
+    where
+        F: FnMut(&LogLine) + Send + 'static,
+    {
+        let id = TAILERS.lock().insert(Tailing {
+            tailer:  self,
+            handler: Box::new(handler),
+            polled:  None,
+            failed:  false,
+        });
+
+        TailHandle { id }
+    }
+
+    // Read the log from its start whenever it exists, as anything in it from
+    // then on is new.
+    fn restart(&mut self) {
+        self.position = Some(0);
+        self.partial.clear();
+    }
+}
+
+/// A handle to a [registered](Tailer::register) [`Tailer`], which stops
+/// tailing the log when it's dropped.
+#[derive(Debug)]
+pub struct TailHandle {
+    id: u64,
+}
+
+impl Drop for TailHandle {
+    fn drop(&mut self) {
+        TAILERS.lock().remove(self.id);
+    }
+}
+
+/// Poll every registered tailer that's due to be polled.
+pub(crate) fn pulse() {
+    let now = Instant::now();
+
+    for id in TAILERS.lock().due(now) {
+        // The tailer is taken out of the registry while its handler is called,
+        // so that it can register or drop tailers itself without deadlocking.
+        let Some(mut tailing) = TAILERS.lock().take(id)
+        else {
+            continue;
+        };
+
+        tailing.poll(now);
+        TAILERS.lock().restore(id, tailing);
+    }
+}
+
+/// Stop every registered tailer, as the plugin is being unloaded.
+pub(crate) fn clear() {
+    TAILERS.lock().clear();
+}
+
+/// Take the complete lines out of the start of `buffer`, leaving any partial
+/// line at its end.
+fn take_lines(buffer: &mut Vec<u8>) -> Vec<LogLine> {
+    let Some(end) = memchr::memrchr(b'\n', buffer)
+    else {
+        return Vec::new();
+    };
+
+    let partial = buffer.split_off(end + 1);
+    let complete = std::mem::replace(buffer, partial);
+
+    complete[..end]
+        .split(|&byte| byte == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .filter(|line| !line.is_empty())
+        .map(|line| LogLine::parse(&charset::decode(line)))
+        .collect()
+}
+
+struct Tailing {
+    tailer:  Tailer,
+    handler: Handler,
+    polled:  Option<Instant>,
+    // Whether the last poll failed, so that a log that can't be read is only
+    // logged once, rather than on every poll.
+    failed:  bool,
+}
+
+impl Tailing {
+    fn is_due(&self, now: Instant) -> bool {
+        self.polled
+            .is_none_or(|polled| now.duration_since(polled) >= POLL_INTERVAL)
+    }
+
+    fn poll(&mut self, now: Instant) {
+        self.polled = Some(now);
+
+        let lines = match self.tailer.poll() {
+            Ok(lines) => {
+                self.failed = false;
+                lines
+            }
+            Err(error) => {
+                if !self.failed {
+                    let path = self.tailer.path().display();
+                    error!(%path, %error, "could not read the EverQuest log");
+                }
+
+                self.failed = true;
+                return;
+            }
+        };
+
+        for line in &lines {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| (self.handler)(line)));
+
+            if let Err(payload) = result {
+                error!(
+                    panic = panic_message(payload.as_ref()),
+                    "EverQuest log handler panicked"
+                );
+            }
+        }
+    }
+}
+
+struct Registry {
+    next_id:  u64,
+    // A tailer is None while its handler is being called.
+    tailings: BTreeMap<u64, Option<Tailing>>,
+}
+
+impl Registry {
+    const fn new() -> Registry {
+        Registry {
+            next_id:  0,
+            tailings: BTreeMap::new(),
+        }
+    }
+
+    fn insert(&mut self, tailing: Tailing) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.tailings.insert(id, Some(tailing));
+        id
+    }
+
+    fn remove(&mut self, id: u64) {
+        self.tailings.remove(&id);
+    }
+
+    /// The ids of the tailers that are due to be polled at `now`.
+    fn due(&self, now: Instant) -> Vec<u64> {
+        self.tailings
+            .iter()
+            .filter(|(_, tailing)| tailing.as_ref().is_some_and(|t| t.is_due(now)))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Take a tailer out to poll it.
+    fn take(&mut self, id: u64) -> Option<Tailing> {
+        self.tailings.get_mut(&id)?.take()
+    }
+
+    /// Put a tailer back after polling it, unless it was removed meanwhile.
+    fn restore(&mut self, id: u64, tailing: Tailing) {
+        if let Some(slot @ None) = self.tailings.get_mut(&id) {
+            *slot = Some(tailing);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.tailings.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use super::*;
+
+    // Captured from a real log.
+    const EXCERPT: &str = "\
+[Mon Oct 14 20:15:02 2024] You have entered The Plane of Knowledge.\r
+[Mon Oct 14 20:15:40 2024] **A Magic Die is rolled by Alice. It could have been any \
+number from 0 to 1000, but this time it turned up a 612.\r
+[Mon Oct 14 20:16:03 2024] Bob tells the group, 'incoming [Spawn] #2'\r
+[Mon Oct 14 20:16:05 2024] You have slain a gnoll pup!\r
+[Mon Oct 14 20:16:05 2024] --You have looted a Gnoll Fang.--\r
+";
+
+    fn timestamp(day: u8, hour: u8, minute: u8, second: u8) -> Timestamp {
+        Timestamp {
+            year: 2024,
+            month: 10,
+            day,
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    fn texts(lines: &[LogLine]) -> Vec<&str> {
+        lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    struct TempLog(PathBuf);
+
+    impl TempLog {
+        fn new(name: &str) -> TempLog {
+            let path = std::env::temp_dir().join(format!(
+                "macroquest-eqlog-{name}-{}.txt",
+                std::process::id()
+            ));
+            let _ = std::fs::remove_file(&path);
+            TempLog(path)
+        }
+
+        fn append(&self, bytes: &[u8]) {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.0)
+                .unwrap()
+                .write_all(bytes)
+                .unwrap();
+        }
+    }
+
+    impl Drop for TempLog {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_parse_excerpt() {
+        let lines: Vec<LogLine> = EXCERPT.lines().map(LogLine::parse).collect();
+
+        assert_eq!(lines[0].timestamp, Some(timestamp(14, 20, 15, 2)));
+        assert_eq!(lines[0].text, "You have entered The Plane of Knowledge.");
+        assert!(lines[1]
+            .text
+            .starts_with("**A Magic Die is rolled by Alice."));
+        assert_eq!(lines[2].text, "Bob tells the group, 'incoming [Spawn] #2'");
+        assert_eq!(lines[4].timestamp, Some(timestamp(14, 20, 16, 5)));
+    }
+
+    #[test]
+    fn test_parse_without_timestamp() {
+        assert_eq!(
+            LogLine::parse("Logging to 'eqlog_Alice_vox.txt' is now *ON*."),
+            LogLine {
+                timestamp: None,
+                text:      "Logging to 'eqlog_Alice_vox.txt' is now *ON*.".into(),
+            }
+        );
+
+        // Brackets that don't hold a timestamp are part of the text.
+        assert_eq!(LogLine::parse("[Alice] hello").timestamp, None);
+        assert_eq!(LogLine::parse("[Alice] hello").text, "[Alice] hello");
+    }
+
+    #[test]
+    fn test_parse_empty_text() {
+        let line = LogLine::parse("[Tue Oct 01 00:00:00 2024]");
+
+        assert_eq!(line.timestamp, Some(timestamp(1, 0, 0, 0)));
+        assert_eq!(line.text, "");
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(
+            Timestamp::parse("Mon Oct 14 20:15:02 2024"),
+            Some(timestamp(14, 20, 15, 2))
+        );
+        assert_eq!(
+            Timestamp::parse("Thu Jan  2 03:04:05 2025").map(|t| (t.month, t.day)),
+            Some((1, 2))
+        );
+
+        assert_eq!(Timestamp::parse("Mon Foo 14 20:15:02 2024"), None);
+        assert_eq!(Timestamp::parse("Mon Oct 14 20:15 2024"), None);
+        assert_eq!(Timestamp::parse("Mon Oct 14 24:15:02 2024"), None);
+        assert_eq!(Timestamp::parse("Mon Oct 32 20:15:02 2024"), None);
+        assert_eq!(Timestamp::parse("Mon Oct 14 20:15:02 2024 extra"), None);
+    }
+
+    #[test]
+    fn test_timestamp_display() {
+        assert_eq!(timestamp(4, 5, 6, 7).to_string(), "2024-10-04 05:06:07");
+    }
+
+    #[test]
+    fn test_log_path() {
+        assert_eq!(
+            log_path(Path::new("EQ"), "Alice", "vox"),
+            Path::new("EQ").join("Logs").join("eqlog_Alice_vox.txt")
+        );
+    }
+
+    #[test]
+    fn test_take_lines() {
+        let mut buffer = b"[Mon Oct 14 20:15:02 2024] one\r\n\r\ntwo\nthr".to_vec();
+
+        let lines = take_lines(&mut buffer);
+        assert_eq!(texts(&lines), ["one", "two"]);
+        assert_eq!(buffer, b"thr");
+
+        assert!(take_lines(&mut buffer).is_empty());
+        assert_eq!(buffer, b"thr");
+    }
+
+    #[test]
+    fn test_take_lines_decodes_cp1252() {
+        let mut buffer = b"You say, 'Caf\xE9 \x85'\n".to_vec();
+        assert_eq!(texts(&take_lines(&mut buffer)), ["You say, 'Café …'"]);
+    }
+
+    #[test]
+    fn test_poll_skips_existing() {
+        let log = TempLog::new("existing");
+        log.append(EXCERPT.as_bytes());
+
+        let mut tailer = Tailer::from_path(&log.0);
+        assert!(tailer.poll().unwrap().is_empty());
+
+        log.append(b"[Mon Oct 14 20:17:00 2024] You feel better.\r\n");
+        assert_eq!(texts(&tailer.poll().unwrap()), ["You feel better."]);
+        assert!(tailer.poll().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_poll_partial_line() {
+        let log = TempLog::new("partial");
+        log.append(b"");
+
+        let mut tailer = Tailer::from_path(&log.0);
+        assert!(tailer.poll().unwrap().is_empty());
+
+        log.append(b"[Mon Oct 14 20:17:00 2024] You feel");
+        assert!(tailer.poll().unwrap().is_empty());
+
+        log.append(b" better.\r\n");
+        assert_eq!(texts(&tailer.poll().unwrap()), ["You feel better."]);
+    }
+
+    #[test]
+    fn test_poll_missing_then_created() {
+        let log = TempLog::new("missing");
+
+        // Logging has never been turned on, so there's no log yet.
+        let mut tailer = Tailer::from_path(&log.0);
+        assert!(tailer.poll().unwrap().is_empty());
+
+        log.append(b"one\r\ntwo\r\n");
+        assert_eq!(texts(&tailer.poll().unwrap()), ["one", "two"]);
+    }
+
+    #[test]
+    fn test_poll_truncated() {
+        let log = TempLog::new("truncated");
+        log.append(EXCERPT.as_bytes());
+
+        let mut tailer = Tailer::from_path(&log.0);
+        assert!(tailer.poll().unwrap().is_empty());
+
+        std::fs::write(&log.0, b"fresh\r\n").unwrap();
+        assert_eq!(texts(&tailer.poll().unwrap()), ["fresh"]);
+    }
+
+    #[test]
+    fn test_poll_deleted_then_recreated() {
+        let log = TempLog::new("deleted");
+        log.append(EXCERPT.as_bytes());
+
+        let mut tailer = Tailer::from_path(&log.0);
+        assert!(tailer.poll().unwrap().is_empty());
+
+        std::fs::remove_file(&log.0).unwrap();
+        assert!(tailer.poll().unwrap().is_empty());
+
+        log.append(EXCERPT.as_bytes());
+        assert_eq!(tailer.poll().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_registry_due() {
+        let start = Instant::now();
+        let now = start + POLL_INTERVAL;
+        let mut registry = Registry::new();
+        let tailing = |polled| Tailing {
+            tailer: Tailer::from_path("unused"),
+            handler: Box::new(|_| {}),
+            polled,
+            failed: false,
+        };
+
+        let never = registry.insert(tailing(None));
+        let recent = registry.insert(tailing(Some(now)));
+        let old = registry.insert(tailing(Some(start)));
+        assert_eq!(registry.due(now), [never, old]);
+
+        // A tailer that's being polled isn't due, and is only restored if it
+        // wasn't removed in the meantime.
+        let taken = registry.take(never).unwrap();
+        assert_eq!(registry.due(now), [old]);
+        registry.restore(never, taken);
+        assert_eq!(registry.due(now), [never, old]);
+
+        let taken = registry.take(old).unwrap();
+        registry.remove(old);
+        registry.restore(old, taken);
+        assert_eq!(registry.due(now + POLL_INTERVAL), [never, recent]);
+    }
+
+    #[test]
+    fn test_tailing_poll() {
+        let log = TempLog::new("tailing");
+        log.append(b"");
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut tailing = Tailing {
+            tailer:  Tailer::from_path(&log.0),
+            handler: {
+                let seen = Arc::clone(&seen);
+                Box::new(move |line: &LogLine| {
+                    assert_ne!(line.text, "boom");
+                    seen.lock().push(line.text.clone());
+                })
+            },
+            polled:  None,
+            failed:  false,
+        };
+
+        tailing.poll(Instant::now());
+        log.append(b"one\r\nboom\r\ntwo\r\n");
+        tailing.poll(Instant::now());
+
+        // A panicking handler doesn't stop the rest of the lines.
+        assert_eq!(*seen.lock(), ["one", "two"]);
+        assert!(!tailing.failed);
+    }
+}
@@ -0,0 +1,135 @@
+//! Drawing text on the game's Heads Up Display (HUD), the same way that MQ2HUD
+//! does.
+//!
+//! The HUD can only be drawn on while the game is drawing it, so drawing
+//! requires a [`HudFrame`], which is only given out to
+//! [`Hooks::draw_hud_frame()`](crate::plugin::Hooks::draw_hud_frame).
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # #[derive(Debug)]
+//! # struct MyPlugin;
+//! # impl Plugin for MyPlugin {
+//! #     fn new() -> Self {
+//! #         MyPlugin
+//! #     }
+//! # }
+//! use macroquest::mq::hud::{Color, HudFrame};
+//!
+//! impl Hooks for MyPlugin {
+//!     fn draw_hud_frame(&self, frame: &HudFrame) {
+//!         frame.draw_text(10, 10, Color::WHITE, "Hello World");
+//!     }
+//! }
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::ffi::mq as mqlib;
+
+/// A color to draw on the HUD with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(u32);
+
+impl Color {
+    /// Opaque white.
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    /// Opaque black.
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+
+    /// Opaque red.
+    pub const RED: Color = Color::rgb(255, 0, 0);
+
+    /// Opaque green.
+    pub const GREEN: Color = Color::rgb(0, 255, 0);
+
+    /// Opaque blue.
+    pub const BLUE: Color = Color::rgb(0, 0, 255);
+
+    /// Opaque yellow.
+    pub const YELLOW: Color = Color::rgb(255, 255, 0);
+
+    /// An opaque color from its red, green, and blue components.
+    #[must_use]
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Color {
+        Color::argb(255, red, green, blue)
+    }
+
+    /// A color from its alpha, red, green, and blue components.
+    #[must_use]
+    pub const fn argb(alpha: u8, red: u8, green: u8, blue: u8) -> Color {
+        Color(
+            (alpha as u32) << 24
+                | (red as u32) << 16
+                | (green as u32) << 8
+                | blue as u32,
+        )
+    }
+
+    /// The color packed as `0xAARRGGBB`.
+    #[must_use]
+    pub const fn to_argb(self) -> u32 {
+        self.0
+    }
+}
+
+/// A token that proves that the game is currently drawing the HUD, which
+/// the HUD drawing functions are available on.
+///
+/// This isn't [`Send`], as the HUD may only be drawn on from the game's main
+/// thread, and it can't be held onto past the end of the
+/// [`Hooks::draw_hud_frame()`](crate::plugin::Hooks::draw_hud_frame) call that
+/// it was given to.
+#[derive(Debug)]
+pub struct HudFrame {
+    // Frames given to a hook that's being replayed, rather than called by the
+    // game, discard everything drawn on them.
+    discard:   bool,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl HudFrame {
+    pub(crate) fn new() -> HudFrame {
+        HudFrame {
+            discard:   false,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// A frame that discards everything drawn on it, for replaying the
+    /// `draw_hud` hook outside of the game actually drawing the HUD.
+    pub(crate) fn discarding() -> HudFrame {
+        HudFrame {
+            discard:   true,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Draw `text` with its top left corner at `x`, `y` (in pixels, from the
+    /// top left of the screen), in `color`.
+    pub fn draw_text(&self, x: i32, y: i32, color: Color, text: &str) {
+        if !self.discard {
+            mqlib::draw_hud_text(text, x, y, color.to_argb());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_packing() {
+        assert_eq!(Color::rgb(0x12, 0x34, 0x56).to_argb(), 0xff12_3456);
+        assert_eq!(Color::argb(0x80, 0x12, 0x34, 0x56).to_argb(), 0x8012_3456);
+        assert_eq!(Color::WHITE.to_argb(), 0xffff_ffff);
+        assert_eq!(Color::BLACK.to_argb(), 0xff00_0000);
+    }
+
+    #[test]
+    fn test_discarding_frame_draws_nothing() {
+        // This would call into MacroQuest if it didn't discard the text.
+        HudFrame::discarding().draw_text(0, 0, Color::WHITE, "discarded");
+    }
+}
@@ -0,0 +1,201 @@
+//! A token that proves that we're in game.
+
+use std::marker::PhantomData;
+
+use super::{
+    character,
+    ground_items,
+    me,
+    nearest_ground_item,
+    spawn_by_id,
+    spawns,
+    target,
+    zone,
+    zone_name,
+};
+use crate::eq::{Character, GameState, GroundItem, Spawn, SpawnId, Zone};
+use crate::ffi::mq as mqlib;
+
+/// A token that proves that we're in game, playing a character in a zone,
+/// which is what the accessors for the character, the target, the zone, and
+/// the things in it need.
+///
+/// The token is obtained with [`mq::in_game()`](super::in_game), which checks
+/// the live game state. Accessing the character while we're not in game (such
+/// as at character select, or while zoning) is the most common way for a
+/// plugin to crash the game, so the accessors on this token are the ones to
+/// reach for, as they can't be called without that check having been made.
+///
+/// This isn't [`Send`], as the things that it gives access to may only be used
+/// from the game's main thread, and it should not be held onto past the end of
+/// the hook that it was obtained in, as we may leave the game between hooks.
+///
+/// # Examples
+///
+/// ```
+/// # use macroquest::plugin::{Hooks, Plugin};
+/// # macroquest::plugin::setup!(MyPlugin);
+/// # struct MyPlugin;
+/// # impl Plugin for MyPlugin { fn new() -> Self { MyPlugin } }
+/// use macroquest::mq;
+///
+/// #[macroquest::plugin::hooks]
+/// impl Hooks for MyPlugin {
+///     fn pulse(&self) {
+///         let Some(game) = mq::in_game()
+///         else {
+///             return;
+///         };
+///
+///         if let Some(target) = game.target() {
+///             let name = game.character().name();
+///             mq::write_chat(format!("{name} is targeting {}", target.name()));
+///         }
+///     }
+/// }
+/// ```
+///
+/// The token can only be obtained by checking, so it can't be made up instead.
+///
+/// ```compile_fail
+/// let game = macroquest::mq::InGame {};
+/// ```
+///
+/// Nor can it be sent to another thread, where the game may have moved on.
+///
+/// ```compile_fail
+/// let game = macroquest::mq::in_game().unwrap();
+/// std::thread::spawn(move || game.character().name().to_owned());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct InGame {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl InGame {
+    /// Check the live game state, returning a token if we're in game.
+    pub(super) fn check() -> Option<InGame> {
+        let state = GameState::from(mqlib::get_game_state());
+
+        is_in_game(state, character().is_some()).then_some(InGame {
+            _not_send: PhantomData,
+        })
+    }
+
+    /// The local character, which is the one that we're playing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the character has gone away since the token was obtained,
+    /// which can only happen if the token was held onto past the hook that it
+    /// was obtained in.
+    #[must_use]
+    pub fn character(&self) -> &Character {
+        character().expect("the InGame token was held onto after leaving the game")
+    }
+
+    /// The local character's spawn, which is where they are in the zone, or
+    /// `None` in the moment while zoning that the character has no spawn.
+    #[must_use]
+    pub fn me(&self) -> Option<&Spawn> {
+        me()
+    }
+
+    /// The spawn that we have targeted, or `None` if nothing is targeted.
+    ///
+    /// See [`mq::target()`](super::target) for how long the target may be held
+    /// onto.
+    #[must_use]
+    pub fn target(&self) -> Option<&Spawn> {
+        target()
+    }
+
+    /// The zone that we're in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the zone has gone away since the token was obtained, which
+    /// can only happen if the token was held onto past the hook that it was
+    /// obtained in.
+    #[must_use]
+    pub fn zone(&self) -> &Zone {
+        zone().expect("the InGame token was held onto after leaving the zone")
+    }
+
+    /// The long name of the zone that we're in.
+    ///
+    /// The name is empty in the unlikely case that the zone's information
+    /// hasn't been loaded yet.
+    #[must_use]
+    pub fn zone_name(&self) -> String {
+        zone_name().unwrap_or_default()
+    }
+
+    /// Iterate over every spawn in the zone, including the local character's
+    /// own spawn.
+    ///
+    /// See [`mq::spawns()`](super::spawns) for how long the spawns may be held
+    /// onto.
+    pub fn spawns(&self) -> impl Iterator<Item = &'static Spawn> {
+        spawns()
+    }
+
+    /// The spawn in the zone with the id `id`, or `None` if there isn't one
+    /// (such as when it has despawned).
+    #[must_use]
+    pub fn spawn_by_id(&self, id: SpawnId) -> Option<&Spawn> {
+        spawn_by_id(id)
+    }
+
+    /// Iterate over the items lying on the ground in the zone.
+    ///
+    /// See [`mq::ground_items()`](super::ground_items) for how long the items
+    /// may be held onto.
+    pub fn ground_items(&self) -> impl Iterator<Item = &'static GroundItem> {
+        ground_items()
+    }
+
+    /// The item lying on the ground that's closest to the local character, or
+    /// `None` if there aren't any.
+    #[must_use]
+    pub fn nearest_ground_item(&self) -> Option<&GroundItem> {
+        nearest_ground_item()
+    }
+}
+
+/// Whether we're in game, given the game state and whether there's a local
+/// character.
+///
+/// The game state becomes [`GameState::InGame`] slightly before the local
+/// character is loaded (and stays so until slightly after it's unloaded when
+/// camping), so both are checked.
+fn is_in_game(state: GameState, has_character: bool) -> bool {
+    state == GameState::InGame && has_character
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_zero_sized() {
+        assert_eq!(std::mem::size_of::<InGame>(), 0);
+    }
+
+    #[test]
+    fn test_is_in_game() {
+        assert!(is_in_game(GameState::InGame, true));
+
+        // Entering the world, before the character is loaded.
+        assert!(!is_in_game(GameState::InGame, false));
+
+        // Camping out to character select, before the character is unloaded.
+        assert!(!is_in_game(GameState::CharacterSelect, true));
+
+        for state in GameState::ALL {
+            if state != GameState::InGame {
+                assert!(!is_in_game(state, false));
+            }
+        }
+    }
+}
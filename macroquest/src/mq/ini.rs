@@ -0,0 +1,165 @@
+//! Reading ini files the way that MacroQuest's config files are read.
+//!
+//! Plugin settings ([`settings`](crate::mq::settings)) and the logging config
+//! ([`log`](crate::log)) both read their plugin's ini file through this, the
+//! same way that the Windows `GetPrivateProfileString` function does:
+//!
+//! * Lines are trimmed, and lines starting with `;` are comments.
+//! * Sections and keys are matched ignoring case.
+//! * Values are trimmed, and a value that's wrapped in matching quotes has them
+//!   removed.
+//! * Only the first copy of a section that appears more than once is read.
+
+/// A line of an ini file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Line<'a> {
+    /// A section header, with its name.
+    Section(&'a str),
+    /// A key and its value.
+    Entry(&'a str, &'a str),
+    /// An empty line, or a comment.
+    Blank,
+    /// Anything else, which is ignored.
+    Invalid(&'a str),
+}
+
+impl<'a> Line<'a> {
+    /// Parse `line`.
+    pub(crate) fn parse(line: &'a str) -> Line<'a> {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with(';') {
+            Line::Blank
+        }
+        else if let Some(name) = line.strip_prefix('[') {
+            Line::Section(name.split_once(']').map_or(name, |(name, _)| name).trim())
+        }
+        else if let Some((key, value)) = line.split_once('=') {
+            Line::Entry(key.trim(), unquote(value.trim()))
+        }
+        else {
+            Line::Invalid(line)
+        }
+    }
+}
+
+/// The lines of `contents` in the section named `name`, along with the index
+/// of each of them in `contents`, starting with its header.
+///
+/// Returns nothing if there's no such section.
+pub(crate) fn section<'a>(
+    contents: &'a str,
+    name: &str,
+) -> impl Iterator<Item = (usize, Line<'a>)> + 'a {
+    let mut lines = contents.lines().map(Line::parse).enumerate();
+    let header = lines.by_ref().find(|(_, line)| {
+        matches!(line, Line::Section(section) if section.eq_ignore_ascii_case(name))
+    });
+
+    header
+        .into_iter()
+        .chain(lines.take_while(|(_, line)| !matches!(line, Line::Section(_))))
+}
+
+/// The value of `key` in the section named `section` of `contents`.
+pub(crate) fn lookup<'a>(
+    contents: &'a str,
+    section: &str,
+    key: &str,
+) -> Option<&'a str> {
+    self::section(contents, section).find_map(|(_, line)| match line {
+        Line::Entry(name, value) if name.eq_ignore_ascii_case(key) => Some(value),
+        _ => None,
+    })
+}
+
+/// Remove the quotes from a value that's wrapped in matching quotes.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &str = "\
+; A comment
+[General]
+Radius = 120
+Name=\"Alice \"
+Quoted='single'
+verbose
+
+[firiona.Vox]
+Enabled=no
+[general]
+Radius=80
+Color=red
+";
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Line::parse("  [General] "), Line::Section("General"));
+        assert_eq!(
+            Line::parse("[ firiona.Vox ] ; c"),
+            Line::Section("firiona.Vox")
+        );
+        assert_eq!(Line::parse("Radius = 120"), Line::Entry("Radius", "120"));
+        assert_eq!(
+            Line::parse("Name=\"Alice \""),
+            Line::Entry("Name", "Alice ")
+        );
+        assert_eq!(
+            Line::parse("Quoted='single\""),
+            Line::Entry("Quoted", "'single\"")
+        );
+        assert_eq!(Line::parse("Empty="), Line::Entry("Empty", ""));
+        assert_eq!(Line::parse(""), Line::Blank);
+        assert_eq!(Line::parse("; Radius=1"), Line::Blank);
+        assert_eq!(Line::parse(" verbose "), Line::Invalid("verbose"));
+    }
+
+    #[test]
+    fn test_section() {
+        let lines: Vec<_> = section(CONTENTS, "FIRIONA.VOX").collect();
+        assert_eq!(
+            lines,
+            [
+                (7, Line::Section("firiona.Vox")),
+                (8, Line::Entry("Enabled", "no")),
+            ]
+        );
+
+        assert_eq!(section(CONTENTS, "Missing").count(), 0);
+        assert_eq!(section("", "General").count(), 0);
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup(CONTENTS, "General", "Radius"), Some("120"));
+        assert_eq!(lookup(CONTENTS, "general", "RADIUS"), Some("120"));
+        assert_eq!(lookup(CONTENTS, "General", "Name"), Some("Alice "));
+        assert_eq!(lookup(CONTENTS, "General", "Quoted"), Some("single"));
+        assert_eq!(lookup(CONTENTS, "firiona.Vox", "Enabled"), Some("no"));
+
+        assert_eq!(lookup(CONTENTS, "General", "verbose"), None);
+        assert_eq!(lookup(CONTENTS, "General", "; A comment"), None);
+        assert_eq!(lookup(CONTENTS, "Missing", "Radius"), None);
+    }
+
+    #[test]
+    fn test_duplicate_section() {
+        // Only the first copy of [General] is read.
+        assert_eq!(lookup(CONTENTS, "General", "Radius"), Some("120"));
+        assert_eq!(lookup(CONTENTS, "General", "Color"), None);
+    }
+}
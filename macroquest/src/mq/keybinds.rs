@@ -0,0 +1,324 @@
+//! Reacting to keys being pressed, through MacroQuest's keybinds.
+//!
+//! MacroQuest doesn't give plugins a hook for raw keyboard or mouse input, so
+//! there's no `Hooks` method for it. Instead, the way for a plugin to react to
+//! a key is to register a [`KeyBind`], which MacroQuest calls whenever its key
+//! combination is pressed and released.
+//!
+//! # Interaction with the game's keybinds
+//!
+//! MacroQuest checks its own keybinds before the game checks the game's
+//! keybinds, and a key combination that's bound to a MacroQuest keybind is
+//! swallowed, so the game never sees it. That means that binding a key that's
+//! also bound in the game (such as a movement key) will stop it from working
+//! in the game for as long as the [`KeyBind`] exists, so keybinds should use
+//! key combinations that the game doesn't.
+//!
+//! Keys typed into a text input (such as the chat window) aren't keybinds, so
+//! they're never passed to a [`KeyBind`].
+//!
+//! The user can change the key combination of a keybind with MacroQuest's
+//! `/bind` command, using the name that the keybind was registered with.
+
+use std::collections::BTreeMap;
+use std::{error, fmt};
+
+use parking_lot::Mutex;
+
+use crate::ffi::mq as mqlib;
+use crate::log::error;
+use crate::macros::debug_assert_main_thread;
+
+type Handler = Box<dyn FnMut(bool) + Send>;
+
+static KEYBINDS: Mutex<Registry> = Mutex::new(Registry::new());
+
+/// A MacroQuest keybind, calling a handler whenever its key combination is
+/// pressed or released.
+///
+/// The keybind is removed when this is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use macroquest::plugin::{Hooks, Plugin};
+/// # macroquest::plugin::setup!(MyPlugin);
+/// use macroquest::mq::keybinds::KeyBind;
+///
+/// struct MyPlugin {
+///     toggle: Option<KeyBind>,
+/// }
+///
+/// impl Plugin for MyPlugin {
+///     fn new() -> Self {
+///         let toggle = KeyBind::register("MyPluginToggle", "ctrl+shift+t", |down| {
+///             if down {
+///                 macroquest::log::info!("toggled");
+///             }
+///         });
+///
+///         MyPlugin {
+///             toggle: toggle.ok(),
+///         }
+///     }
+/// }
+/// # impl Hooks for MyPlugin {}
+/// ```
+#[derive(Debug)]
+pub struct KeyBind {
+    name: String,
+}
+
+impl KeyBind {
+    /// Register a keybind named `name`, bound to the key combination `keys`
+    /// (ex: `ctrl+shift+t`), calling `handler` with `true` when the keys are
+    /// pressed, and with `false` when they are released.
+    ///
+    /// The handler is called on the game's main thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `keys` isn't a key combination that MacroQuest
+    /// understands, or if there's already a keybind named `name` (whether
+    /// from this plugin or another).
+    pub fn register<F>(
+        name: &str,
+        keys: &str,
+        handler: F,
+    ) -> Result<KeyBind, KeyBindError>
+    where
+        F: FnMut(bool) + Send + 'static,
+    {
+        debug_assert_main_thread!("macroquest::mq::keybinds::KeyBind::register");
+
+        if !mqlib::is_valid_key_combo(keys) {
+            return Err(KeyBindError::InvalidKeys {
+                keys: keys.to_owned(),
+            });
+        }
+
+        let mut keybinds = KEYBINDS.lock();
+        if !keybinds.insert(name, Box::new(handler)) {
+            return Err(KeyBindError::AlreadyBound {
+                name: name.to_owned(),
+            });
+        }
+
+        if !mqlib::add_keybind(name, keys, dispatch) {
+            keybinds.remove(name);
+            return Err(KeyBindError::AlreadyBound {
+                name: name.to_owned(),
+            });
+        }
+
+        Ok(KeyBind {
+            name: name.to_owned(),
+        })
+    }
+
+    /// The name that the keybind was registered with.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for KeyBind {
+    fn drop(&mut self) {
+        debug_assert_main_thread!("macroquest::mq::keybinds::KeyBind::drop");
+
+        if KEYBINDS.lock().remove(&self.name) {
+            mqlib::remove_keybind(&self.name);
+        }
+    }
+}
+
+/// An error registering a [`KeyBind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KeyBindError {
+    /// The key combination isn't one that MacroQuest understands.
+    InvalidKeys {
+        /// The key combination that was given.
+        keys: String,
+    },
+    /// There's already a keybind with the same name.
+    AlreadyBound {
+        /// The name of the keybind.
+        name: String,
+    },
+}
+
+impl fmt::Display for KeyBindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyBindError::InvalidKeys { keys } => {
+                write!(f, "invalid key combination {keys:?}")
+            }
+            KeyBindError::AlreadyBound { name } => {
+                write!(f, "a keybind named {name:?} already exists")
+            }
+        }
+    }
+}
+
+impl error::Error for KeyBindError {}
+
+/// Remove every keybind, as the plugin is being unloaded.
+pub(crate) fn clear() {
+    for name in KEYBINDS.lock().clear() {
+        mqlib::remove_keybind(&name);
+    }
+}
+
+/// Called by MacroQuest whenever the key combination of one of our keybinds is
+/// pressed or released.
+fn dispatch(name: &str, down: bool) {
+    // The handler is taken out of the registry while it's called, so that it
+    // can register or drop keybinds itself without deadlocking.
+    let Some(mut handler) = KEYBINDS.lock().take(name)
+    else {
+        return;
+    };
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        handler(down);
+    }));
+
+    if result.is_err() {
+        error!(keybind = name, "keybind handler panicked");
+    }
+
+    KEYBINDS.lock().restore(name, handler);
+}
+
+struct Registry {
+    // A handler is None while it's being called.
+    handlers: BTreeMap<String, Option<Handler>>,
+}
+
+impl Registry {
+    const fn new() -> Registry {
+        Registry {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    /// Add a handler, returning false if there's already one with that name.
+    fn insert(&mut self, name: &str, handler: Handler) -> bool {
+        if self.handlers.contains_key(name) {
+            return false;
+        }
+
+        self.handlers.insert(name.to_owned(), Some(handler));
+        true
+    }
+
+    /// Remove a handler, returning whether there was one with that name.
+    fn remove(&mut self, name: &str) -> bool {
+        self.handlers.remove(name).is_some()
+    }
+
+    /// Take a handler out to call it.
+    fn take(&mut self, name: &str) -> Option<Handler> {
+        self.handlers.get_mut(name)?.take()
+    }
+
+    /// Put a handler back after calling it, unless it was removed meanwhile.
+    fn restore(&mut self, name: &str, handler: Handler) {
+        if let Some(slot @ None) = self.handlers.get_mut(name) {
+            *slot = Some(handler);
+        }
+    }
+
+    /// Remove every handler, returning their names.
+    fn clear(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.handlers).into_keys().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn counter() -> (Arc<AtomicUsize>, Handler) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let handler = {
+            let count = Arc::clone(&count);
+            Box::new(move |_| {
+                count.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        (count, handler)
+    }
+
+    #[test]
+    fn test_registry_insert() {
+        let mut registry = Registry::new();
+
+        assert!(registry.insert("one", counter().1));
+        assert!(!registry.insert("one", counter().1));
+        assert!(registry.insert("two", counter().1));
+    }
+
+    #[test]
+    fn test_registry_take_and_restore() {
+        let mut registry = Registry::new();
+        let (count, handler) = counter();
+        registry.insert("one", handler);
+
+        let mut handler = registry.take("one").unwrap();
+        assert!(registry.take("one").is_none());
+
+        handler(true);
+        registry.restore("one", handler);
+
+        registry.take("one").unwrap()(false);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_registry_removed_while_taken() {
+        let mut registry = Registry::new();
+        registry.insert("one", counter().1);
+
+        let handler = registry.take("one").unwrap();
+        assert!(registry.remove("one"));
+        registry.restore("one", handler);
+
+        assert!(registry.take("one").is_none());
+        assert!(!registry.remove("one"));
+    }
+
+    #[test]
+    fn test_registry_clear() {
+        let mut registry = Registry::new();
+        registry.insert("b", counter().1);
+        registry.insert("a", counter().1);
+
+        assert_eq!(registry.clear(), vec!["a", "b"]);
+        assert!(registry.take("a").is_none());
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            KeyBindError::InvalidKeys {
+                keys: "ctrl+nope".into(),
+            }
+            .to_string(),
+            "invalid key combination \"ctrl+nope\""
+        );
+        assert_eq!(
+            KeyBindError::AlreadyBound {
+                name: "Toggle".into(),
+            }
+            .to_string(),
+            "a keybind named \"Toggle\" already exists"
+        );
+    }
+}
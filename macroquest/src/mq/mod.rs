@@ -0,0 +1,2275 @@
+//!
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread::{self, Thread, ThreadId};
+use std::time::Instant;
+use std::{error, fmt, io};
+
+use cansi::{Color, Intensity};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::{Mutex, RwLock};
+
+use crate::eq::buff::TICK;
+use crate::eq::{
+    Character,
+    ChatColor,
+    DynamicZone,
+    Fellowship,
+    GameState,
+    GameTime,
+    GroundItem,
+    Group,
+    Location,
+    Lockout,
+    Raid,
+    Spawn,
+    SpawnId,
+    Spell,
+    Switch,
+    Task,
+    Zone,
+};
+use crate::ffi::{eqlib, mq as mqlib};
+use crate::macros::debug_assert_main_thread;
+
+pub mod commands;
+pub mod datatypes;
+pub mod eqlog;
+pub mod hud;
+pub mod keybinds;
+pub mod settings;
+pub mod targeting;
+pub mod testing;
+pub mod tlo;
+pub mod ui;
+pub mod zones;
+
+mod batch;
+mod charset;
+mod config_dirs;
+mod in_game;
+pub(crate) mod ini;
+
+pub use self::batch::ChatBatch;
+pub use self::in_game::InGame;
+#[doc(inline)]
+pub use crate::__mq_command as command;
+
+static MAIN_THREAD: MainThread = MainThread::new();
+
+// The maximum number of lines of chat, written from other threads or before
+// MacroQuest was ready for them, that we'll hold onto while waiting for the
+// next pulse.
+const MAX_PENDING_CONSOLE_LINES: usize = 1024;
+
+// The maximum length, in bytes, of a single line of console output. This
+// matches the size of the buffers that MacroQuest uses for a line of chat, and
+// longer lines are split into multiple lines.
+const MAX_CONSOLE_LINE_LENGTH: usize = 2048;
+
+static PENDING_CONSOLE_LINES: ChatQueue = ChatQueue::new(MAX_PENDING_CONSOLE_LINES);
+
+// The sink that chat is written to, where None is the default MacroQuestChat.
+static CHAT_SINK: RwLock<Option<Arc<dyn ChatSink>>> = RwLock::new(None);
+
+// Only set once MacroQuest's paths have been checked, so that asking for them
+// too early (such as from DllMain) doesn't cache empty or garbage paths.
+static PATHS: OnceCell<Paths<'static>> = OnceCell::new();
+
+/// The standard MacroQuest paths for the locations of various directories.
+///
+/// These paths should be used whenever interacting with the filesystem for a
+/// given type of file, rather than trying to compute these values directly.
+pub struct Paths<'a> {
+    root:        &'a Path,
+    config:      &'a Path,
+    ini:         &'a Path,
+    macros:      &'a Path,
+    logs:        &'a Path,
+    crash_dumps: &'a Path,
+    plugins:     &'a Path,
+    resources:   &'a Path,
+    everquest:   &'a Path,
+}
+
+impl<'a> Paths<'a> {
+    /// Create the [`Paths`] from MacroQuest's paths, given in the same order as
+    /// the fields, returning `None` if any of them isn't a plausible path.
+    fn new(raw: [&'a str; 9]) -> Option<Paths<'a>> {
+        // MacroQuest always gives absolute paths once it has set them, while
+        // before then they're empty (or whatever happens to be in memory).
+        if !raw
+            .iter()
+            .all(|path| !path.contains('\0') && Path::new(path).is_absolute())
+        {
+            return None;
+        }
+
+        let [root, config, ini, macros, logs, crash_dumps, plugins, resources, everquest] =
+            raw.map(Path::new);
+
+        Some(Paths {
+            root,
+            config,
+            ini,
+            macros,
+            logs,
+            crash_dumps,
+            plugins,
+            resources,
+            everquest,
+        })
+    }
+
+    /// The MacroQuest root path (i.e. where MacroQuest itself resides).
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        self.root
+    }
+
+    /// The directory for storing all MacroQuest related configuration files.
+    #[must_use]
+    pub fn config(&self) -> &Path {
+        self.config
+    }
+
+    /// The path to the `MacroQuest.ini` file.
+    #[must_use]
+    pub fn ini(&self) -> &Path {
+        self.ini
+    }
+
+    /// The directory for MacroQuest macros.
+    #[must_use]
+    pub fn macros(&self) -> &Path {
+        self.macros
+    }
+
+    /// The directory for writing log files to.
+    #[must_use]
+    pub fn logs(&self) -> &Path {
+        self.logs
+    }
+
+    /// The directory for writing crash dumps to.
+    #[must_use]
+    pub fn crash_dumps(&self) -> &Path {
+        self.crash_dumps
+    }
+
+    /// The directory for MacroQuest plugins.
+    #[must_use]
+    pub fn plugins(&self) -> &Path {
+        self.plugins
+    }
+
+    /// The directory for storing related resources (additional runtime files
+    /// for a plugin, script, macro, or other item that doesn't fit in another
+    /// category).
+    #[must_use]
+    pub fn resources(&self) -> &Path {
+        self.resources
+    }
+
+    /// The base directory for the current EverQuest installation.
+    #[must_use]
+    pub fn everquest(&self) -> &Path {
+        self.everquest
+    }
+
+    /// The directory for the configuration files of the plugin named `plugin`
+    /// (ex: `Config\MyPlugin`), which is created if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory doesn't exist and can't be created.
+    pub fn plugin_config(&self, plugin: &str) -> io::Result<PathBuf> {
+        config_dirs::create(config_dirs::plugin_dir(self.config, plugin))
+    }
+
+    /// The directory for the configuration files of the plugin named `plugin`
+    /// that belong to the server that we're playing on (ex:
+    /// `Config\MyPlugin\firiona`), which is created if it doesn't exist.
+    ///
+    /// Returns `Ok(None)` if we're not in game.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory doesn't exist and can't be created.
+    pub fn server_config(&self, plugin: &str) -> io::Result<Option<PathBuf>> {
+        debug_assert_main_thread!("macroquest::mq::Paths::server_config");
+
+        config_dirs::identity()
+            .map(|identity| {
+                config_dirs::create(config_dirs::server_dir(
+                    self.config,
+                    plugin,
+                    &identity,
+                ))
+            })
+            .transpose()
+    }
+
+    /// The directory for the configuration files of the plugin named `plugin`
+    /// that belong to the character that we're playing (ex:
+    /// `Config\MyPlugin\firiona.vox`), which is created if it doesn't exist.
+    ///
+    /// The directory changes along with the character, so it should be asked
+    /// for again whenever the files in it are used, rather than held onto.
+    /// Returns `Ok(None)` if we're not in game.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory doesn't exist and can't be created.
+    pub fn character_config(&self, plugin: &str) -> io::Result<Option<PathBuf>> {
+        debug_assert_main_thread!("macroquest::mq::Paths::character_config");
+
+        config_dirs::identity()
+            .map(|identity| {
+                config_dirs::create(config_dirs::character_dir(
+                    self.config,
+                    plugin,
+                    &identity,
+                ))
+            })
+            .transpose()
+    }
+}
+
+/// Gets the [`Paths`] for the current MacroQuest process.
+///
+/// # Panics
+///
+/// Panics if MacroQuest hasn't set its paths yet, which only happens when
+/// called very early (such as while the plugin's DLL is still being loaded).
+/// Use [`try_paths()`] where that's possible.
+#[must_use]
+pub fn paths() -> &'static Paths<'static> {
+    try_paths().expect("MacroQuest's paths are not available yet")
+}
+
+/// Gets the [`Paths`] for the current MacroQuest process, or `None` if
+/// MacroQuest hasn't set its paths yet.
+///
+/// The paths are only cached once they're available, so calling this too
+/// early doesn't stop later calls from succeeding.
+#[must_use]
+pub fn try_paths() -> Option<&'static Paths<'static>> {
+    if let Some(paths) = PATHS.get() {
+        return Some(paths);
+    }
+
+    let paths = Paths::new([
+        mqlib::get_path_MQRoot(),
+        mqlib::get_path_Config(),
+        mqlib::get_path_MQini(),
+        mqlib::get_path_Macros(),
+        mqlib::get_path_Logs(),
+        mqlib::get_path_CrashDumps(),
+        mqlib::get_path_Plugins(),
+        mqlib::get_path_Resources(),
+        mqlib::get_path_EverQuest(),
+    ])?;
+
+    Some(PATHS.get_or_init(|| paths))
+}
+
+struct MainThread(OnceCell<ThreadId>);
+
+impl MainThread {
+    const fn new() -> Self {
+        MainThread(OnceCell::new())
+    }
+
+    fn record(&self) {
+        let _ = self.0.set(thread::current().id());
+    }
+
+    fn is_recorded(&self) -> bool {
+        self.0.get().is_some()
+    }
+
+    fn is_current(&self) -> bool {
+        self.0.get() == Some(&thread::current().id())
+    }
+
+    fn is_other(&self) -> bool {
+        self.0
+            .get()
+            .is_some_and(|main| *main != thread::current().id())
+    }
+
+    #[cfg_attr(not(debug_assertions), allow(dead_code))]
+    #[track_caller]
+    fn check(&self, path: &[&str]) {
+        if let Some(main) = self.0.get() {
+            let current = thread::current();
+
+            assert!(
+                *main == current.id(),
+                "{} must only be called from the game's main thread, but was called \
+                 from {}",
+                path.join("::"),
+                describe_thread(&current),
+            );
+        }
+    }
+}
+
+fn describe_thread(thread: &Thread) -> String {
+    match thread.name() {
+        Some(name) => format!("thread {name:?} ({:?})", thread.id()),
+        None => format!("thread {:?}", thread.id()),
+    }
+}
+
+/// Whether the current thread is the game's main thread.
+///
+/// Almost everything that interacts with the game (writing to chat, reading
+/// spawns, etc) is only safe to do from the game's main thread, which is the
+/// thread that all of the [`Hooks`](crate::plugin::Hooks) are called on.
+///
+/// The main thread is recorded when the plugin is initialized, so this will
+/// always return `false` prior to that.
+#[must_use]
+pub fn is_main_thread() -> bool {
+    MAIN_THREAD.is_current()
+}
+
+/// Record the current thread as the game's main thread.
+pub(crate) fn record_main_thread() {
+    MAIN_THREAD.record();
+}
+
+/// Panics if called from a thread other than the game's main thread, naming
+/// the function (given as a list of path segments) that was called.
+///
+/// Nothing is checked if the main thread hasn't been recorded yet.
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+#[track_caller]
+pub(crate) fn assert_main_thread(path: &[&str]) {
+    MAIN_THREAD.check(path);
+}
+
+/// Write out any chat that was written from other threads, or before
+/// MacroQuest was ready for it, followed by the next part of any staggered
+/// [`ChatBatch`].
+pub(crate) fn flush_pending_console() {
+    for (line, color) in PENDING_CONSOLE_LINES.take() {
+        write_chat_color(line, color);
+    }
+
+    batch::pulse();
+}
+
+/// Write out every remaining part of any staggered [`ChatBatch`], as there
+/// won't be any more pulses to write them on.
+pub(crate) fn finish_pending_console() {
+    batch::finish();
+}
+
+/// The current date and time on the in-game (Norrathian) clock.
+///
+/// Returns `None` when the game clock isn't available, such as at character
+/// select. See [`eq::time`](crate::eq::time) for converting between game time
+/// and real time.
+#[must_use]
+pub fn game_time() -> Option<GameTime> {
+    debug_assert_main_thread!("macroquest::mq::game_time");
+
+    let mut time = eqlib::WorldTime::default();
+    if !eqlib::get_world_time(&mut time) {
+        return None;
+    }
+
+    GameTime::new(
+        u16::try_from(time.year).ok()?,
+        time.month,
+        time.day,
+        time.hour,
+        time.minute,
+    )
+}
+
+/// Whether it's currently night on the in-game clock.
+///
+/// Returns `None` when the game clock isn't available, such as at character
+/// select. See [`GameTime::is_night`] for what the game considers night.
+#[must_use]
+pub fn is_night() -> Option<bool> {
+    game_time().map(GameTime::is_night)
+}
+
+/// The long name of the zone that we're currently in.
+///
+/// Returns `None` when the zone isn't available, such as before the game has
+/// finished loading. This doesn't check that we're in game; see
+/// [`InGame::zone_name`] for the checked version.
+#[must_use]
+pub fn zone_name() -> Option<String> {
+    debug_assert_main_thread!("macroquest::mq::zone_name");
+
+    let mut name = String::new();
+    eqlib::get_zone_name(&mut name).then_some(name)
+}
+
+/// The zone that we're currently in.
+///
+/// Returns `None` when we're not in a zone, such as at character select. The
+/// zone is replaced when we zone, so it must not be held onto past zoning, but
+/// it's available again by the time that
+/// [`Hooks::zoned`](crate::plugin::Hooks::zoned) is called. This doesn't check
+/// that we're in game; see [`InGame::zone`] for the checked version.
+#[must_use]
+pub fn zone() -> Option<&'static Zone> {
+    debug_assert_main_thread!("macroquest::mq::zone");
+
+    // SAFETY: The pointer is either null or points to the current zone's
+    //         information, which lives until we zone.
+    unsafe { eqlib::get_current_zone().as_ref() }.map(AsRef::as_ref)
+}
+
+/// Run `command` the same way as if it had been typed into chat (ex:
+/// `/nav stop`).
+///
+/// The command is run straight away, through MacroQuest's `EzCommand`, so this
+/// must only be called from the game's main thread, such as from within one of
+/// the [`Hooks`](crate::plugin::Hooks). See [`command!`](crate::mq::command)
+/// to build the command with [`format!`] syntax.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::mq;
+///
+/// fn assist(name: &str) {
+///     mq::do_command(&format!("/target {name}"));
+///     mq::do_command("/assist");
+/// }
+/// ```
+pub fn do_command(command: &str) {
+    debug_assert_main_thread!("macroquest::mq::do_command");
+
+    mqlib::do_command(command);
+}
+
+/// Run a command built with [`format!`] syntax, the same way as
+/// [`do_command`].
+///
+/// Like [`do_command`], this must only be called from the game's main thread.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::mq;
+///
+/// fn cast(gem: u8, target: &str) {
+///     mq::command!("/target {target}");
+///     mq::command!("/cast {gem}");
+/// }
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mq_command {
+    ($($arg:tt)+) => {
+        $crate::mq::do_command(&::std::format!($($arg)+))
+    };
+}
+
+/// Evaluate the MacroQuest data expression `expression` (ex:
+/// `${Me.PctHPs}`), the same way that a macro would, returning the result.
+///
+/// Returns `None` if MacroQuest couldn't parse the expression, while anything
+/// that it could parse, but that doesn't exist, evaluates to `NULL`.
+#[must_use]
+pub fn parse_macro_data(expression: &str) -> Option<String> {
+    debug_assert_main_thread!("macroquest::mq::parse_macro_data");
+
+    let mut result = String::new();
+    mqlib::parse_macro_data(expression, &mut result).then_some(result)
+}
+
+/// Evaluate the MacroQuest data expression `expression` (ex:
+/// `${Me.PctHPs}`), the same way that a macro would, returning the result.
+///
+/// Unlike [`parse_macro_data()`], anything that MacroQuest couldn't evaluate
+/// is an error, rather than being returned as text.
+///
+/// # Errors
+///
+/// Returns an error if MacroQuest couldn't parse the expression, if it
+/// evaluated to `NULL` (such as for a TLO or member that doesn't exist), or if
+/// any of it was left unevaluated.
+///
+/// # Examples
+///
+/// ```no_run
+/// use macroquest::mq;
+///
+/// let status = mq::eval("${Me.Name} is at ${Me.PctHPs}%").unwrap();
+/// ```
+pub fn eval(expression: &str) -> Result<String, tlo::TloError> {
+    debug_assert_main_thread!("macroquest::mq::eval");
+
+    tlo::eval(expression, parse_macro_data)
+}
+
+/// Evaluate `expression` like [`eval()`], and parse the result as a `bool`
+/// (from `TRUE` or `FALSE`, ignoring case).
+///
+/// # Errors
+///
+/// See [`eval()`], and returns an error if the result isn't a `bool`.
+pub fn eval_bool(expression: &str) -> Result<bool, tlo::TloError> {
+    debug_assert_main_thread!("macroquest::mq::eval_bool");
+
+    tlo::eval_as(expression, parse_macro_data)
+}
+
+/// Evaluate `expression` like [`eval()`], and parse the result as an `i64`.
+///
+/// # Errors
+///
+/// See [`eval()`], and returns an error if the result isn't an integer.
+pub fn eval_i64(expression: &str) -> Result<i64, tlo::TloError> {
+    debug_assert_main_thread!("macroquest::mq::eval_i64");
+
+    tlo::eval_as(expression, parse_macro_data)
+}
+
+/// Evaluate `expression` like [`eval()`], and parse the result as an `f64`.
+///
+/// # Errors
+///
+/// See [`eval()`], and returns an error if the result isn't a number.
+pub fn eval_f64(expression: &str) -> Result<f64, tlo::TloError> {
+    debug_assert_main_thread!("macroquest::mq::eval_f64");
+
+    tlo::eval_as(expression, parse_macro_data)
+}
+
+/// Evaluate the TLO expression `expression` (ex: `Me.PctHPs`), the same way
+/// that a macro would evaluate `${Me.PctHPs}`, and parse the result as a `T`.
+///
+/// See [`tlo`] for the details, and for functions that read the most used
+/// expressions.
+///
+/// # Errors
+///
+/// Returns an error if there's no character in game, if MacroQuest couldn't
+/// parse the expression (or left any of it unevaluated), or if the result is
+/// `NULL` (and `T` isn't an [`Option`]) or isn't a `T`.
+pub fn tlo<T: tlo::FromMacroData>(expression: &str) -> Result<T, tlo::TloError> {
+    debug_assert_main_thread!("macroquest::mq::tlo");
+
+    tlo::read(expression, character().is_some(), parse_macro_data)
+}
+
+/// Whether the plugin named `name` (ex: `MQ2Nav`) is loaded, ignoring case.
+///
+/// A plugin is already loaded by the time that
+/// [`Hooks::plugin_load`](crate::plugin::Hooks::plugin_load) is called for it,
+/// and is still loaded while
+/// [`Hooks::plugin_unload`](crate::plugin::Hooks::plugin_unload) is called for
+/// it.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::mq;
+///
+/// fn can_navigate() -> bool {
+///     mq::is_plugin_loaded("MQ2Nav")
+/// }
+/// ```
+#[must_use]
+pub fn is_plugin_loaded(name: &str) -> bool {
+    debug_assert_main_thread!("macroquest::mq::is_plugin_loaded");
+
+    mqlib::is_plugin_loaded(name)
+}
+
+/// The names of the loaded plugins (ex: `MQ2Nav`), including this one, with
+/// the most recently loaded first.
+#[must_use]
+pub fn plugins() -> Vec<String> {
+    debug_assert_main_thread!("macroquest::mq::plugins");
+
+    // SAFETY: The plugins can only be unloaded on the main thread, so they all
+    //         outlive this call, and the pointer is null if there are none.
+    let first = unsafe { mqlib::first_plugin().as_ref() };
+
+    std::iter::successors(first, |plugin| {
+        // SAFETY: Each pointer is either null or points to the next plugin,
+        //         which outlives this call just as the first one does.
+        unsafe { plugin.next().as_ref() }
+    })
+    .map(|plugin| plugin.plugin_name().to_owned())
+    .collect()
+}
+
+/// A monotonic count of 6 second ticks, which is what [`Buff`] durations are
+/// counted against.
+///
+/// This is a clock local to the plugin, not the game's own tick, so it starts
+/// from the first time that this is called and doesn't line up with when the
+/// game's ticks happen. It's only useful for comparing against other ticks
+/// from it.
+///
+/// [`Buff`]: crate::eq::Buff
+#[must_use]
+pub fn local_tick() -> u64 {
+    static START: Lazy<Instant> = Lazy::new(Instant::now);
+
+    START.elapsed().as_secs() / TICK.as_secs()
+}
+
+/// Look up the spell with the id `id` in the game's spell database.
+///
+/// Returns `None` when there's no such spell, or when the spell database isn't
+/// available, such as before the game has finished loading.
+#[must_use]
+pub fn spell_by_id(id: u32) -> Option<&'static Spell> {
+    debug_assert_main_thread!("macroquest::mq::spell_by_id");
+
+    // SAFETY: The spell database lives for as long as the game does, and the
+    //         pointer is either null or points to a spell within it.
+    unsafe { eqlib::get_spell_by_id(id).as_ref() }.map(AsRef::as_ref)
+}
+
+/// Look up the spell named `name` (ignoring case) in the game's spell
+/// database.
+///
+/// Spell names aren't unique (ex: there are several spells named `Complete
+/// Heal`), in which case this finds the same one that MacroQuest's
+/// `${Spell[name]}` does. Returns `None` when there's no such spell, or when
+/// the spell database isn't available.
+#[must_use]
+pub fn spell_by_name(name: &str) -> Option<&'static Spell> {
+    debug_assert_main_thread!("macroquest::mq::spell_by_name");
+
+    // SAFETY: The spell database lives for as long as the game does, and the
+    //         pointer is either null or points to a spell within it.
+    unsafe { eqlib::get_spell_by_name(name).as_ref() }.map(AsRef::as_ref)
+}
+
+/// The local character, which is the one that we're playing.
+///
+/// Returns `None` when we're not playing a character, such as at character
+/// select. This is unchecked, in that the character may be returned while the
+/// game is still entering the world or camping out of it, so prefer
+/// [`InGame::character`], which can only be called once [`in_game`] has
+/// checked.
+///
+/// The character is the character's own data, rather than their spawn in the
+/// zone, which is [`me`].
+#[must_use]
+pub fn character() -> Option<&'static Character> {
+    debug_assert_main_thread!("macroquest::mq::character");
+
+    // SAFETY: The pointer is either null or points to the local character,
+    //         which lives until we stop playing it.
+    unsafe { eqlib::get_local_pc().as_ref() }.map(AsRef::as_ref)
+}
+
+/// The local character's spawn, which is where they are in the zone.
+///
+/// Returns `None` when there's no local spawn, such as while zoning. The spawn
+/// is only safe to use while the game is [`GameState::InGame`], as at
+/// character select it's the model of the character being shown rather than a
+/// spawn in a zone (and a warning is logged in debug builds if it's called
+/// then). The spawn belongs to the zone, so it must not be held onto past
+/// zoning.
+///
+/// This doesn't check that we're in game; see [`InGame::me`] for the checked
+/// version.
+#[must_use]
+pub fn me() -> Option<&'static Spawn> {
+    debug_assert_main_thread!("macroquest::mq::me");
+
+    #[cfg(debug_assertions)]
+    {
+        let state = game_state();
+        if state != GameState::InGame {
+            tracing::warn!(?state, "mq::me() was called while not in game");
+        }
+    }
+
+    // SAFETY: The pointer is either null or points to the local character's
+    //         spawn, which lives until we zone.
+    unsafe { eqlib::get_local_player().as_ref() }.map(AsRef::as_ref)
+}
+
+/// The state that the game is in (ex: at character select, or in game).
+#[must_use]
+pub fn game_state() -> GameState {
+    debug_assert_main_thread!("macroquest::mq::game_state");
+
+    GameState::from(mqlib::get_game_state())
+}
+
+/// Check whether we're in game, playing a character in a zone, returning an
+/// [`InGame`] token that gives access to the character, the target, and the
+/// zone if we are.
+///
+/// This checks the live game state every time that it's called, so the token
+/// should be obtained in each hook that uses it, rather than held onto.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::mq;
+///
+/// fn report_target() {
+///     let Some(game) = mq::in_game()
+///     else {
+///         mq::write_chat("Not in game");
+///         return;
+///     };
+///
+///     match game.target() {
+///         Some(target) => mq::write_chat(format!("Targeting {}", target.name())),
+///         None => mq::write_chat(format!("Nothing targeted in {}", game.zone_name())),
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn in_game() -> Option<InGame> {
+    debug_assert_main_thread!("macroquest::mq::in_game");
+
+    InGame::check()
+}
+
+/// Take a snapshot of the dynamic zone (expedition) that the local character
+/// is in.
+///
+/// Returns `None` when the character isn't in a dynamic zone.
+#[must_use]
+pub fn dynamic_zone() -> Option<DynamicZone> {
+    debug_assert_main_thread!("macroquest::mq::dynamic_zone");
+
+    DynamicZone::load()
+}
+
+/// The local character's dynamic zone lockout timers, whether or not they're
+/// in a dynamic zone.
+#[must_use]
+pub fn lockouts() -> Vec<Lockout> {
+    debug_assert_main_thread!("macroquest::mq::lockouts");
+
+    Lockout::load()
+}
+
+/// Take a snapshot of every task (quest or shared task) in the local
+/// character's task journal.
+#[must_use]
+pub fn tasks() -> Vec<Task> {
+    debug_assert_main_thread!("macroquest::mq::tasks");
+
+    Task::load()
+}
+
+/// Take a snapshot of the local character's fellowship.
+///
+/// Returns `None` when we're not playing a character, or when the character
+/// isn't in a fellowship.
+#[must_use]
+pub fn fellowship() -> Option<Fellowship> {
+    character()?.fellowship()
+}
+
+/// Take a snapshot of the local character's group.
+///
+/// Returns `None` when we're not playing a character, or when the character
+/// isn't in a group.
+#[must_use]
+pub fn group() -> Option<Group> {
+    character()?.group()
+}
+
+/// Take a snapshot of the local character's raid.
+///
+/// Returns `None` when the character isn't in a raid.
+#[must_use]
+pub fn raid() -> Option<Raid> {
+    debug_assert_main_thread!("macroquest::mq::raid");
+
+    Raid::load()
+}
+
+/// The corpses in the current zone that belong to the player named
+/// `player_name` (ignoring case).
+///
+/// The corpses belong to the zone, so they must not be held onto past zoning.
+#[must_use]
+pub fn spawns_corpses_of(player_name: &str) -> Vec<&'static Spawn> {
+    debug_assert_main_thread!("macroquest::mq::spawns_corpses_of");
+
+    spawns()
+        .filter(|spawn| spawn.is_player_corpse())
+        .filter(|spawn| {
+            spawn
+                .corpse_owner_name()
+                .is_some_and(|owner| owner.eq_ignore_ascii_case(player_name))
+        })
+        .collect()
+}
+
+/// The spawn that we have targeted, or `None` if nothing is targeted.
+///
+/// The target belongs to the zone, so it must not be held onto past zoning,
+/// and may despawn at any time (which clears the target), so it also must not
+/// be held onto past the current hook. See [`targeting`] to be told when the
+/// target changes.
+///
+/// This doesn't check that we're in game; see [`InGame::target`] for the
+/// checked version, which also ties the target to the [`InGame`] token, so that
+/// it can't be held onto past the hook without the compiler noticing.
+///
+/// # Examples
+///
+/// Announcing when the target drops below half of its hit points, by
+/// reading the target afresh on every pulse, and keeping only its id
+/// between pulses.
+///
+/// ```
+/// # use macroquest::plugin::{Hooks, Plugin};
+/// # macroquest::plugin::setup!(MyPlugin);
+/// # impl Plugin for MyPlugin {
+/// #     fn new() -> Self {
+/// #         MyPlugin { announced: Mutex::new(None) }
+/// #     }
+/// # }
+/// use std::sync::Mutex;
+///
+/// use macroquest::eq::SpawnId;
+/// use macroquest::mq;
+///
+/// struct MyPlugin {
+///     announced: Mutex<Option<SpawnId>>,
+/// }
+///
+/// #[macroquest::plugin::hooks]
+/// impl Hooks for MyPlugin {
+///     fn pulse(&self) {
+///         let Some(target) = mq::target()
+///         else {
+///             return;
+///         };
+///
+///         let mut announced = self.announced.lock().unwrap();
+///         if target.hp_percent() < 50.0 && *announced != Some(target.id()) {
+///             mq::write_chat(format!("{} is below half", target.name()));
+///             *announced = Some(target.id());
+///         }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn target() -> Option<&'static Spawn> {
+    debug_assert_main_thread!("macroquest::mq::target");
+
+    // SAFETY: The pointer is either null or points to the targeted spawn, which
+    //         lives for at least as long as it's targeted.
+    unsafe { eqlib::get_target().as_ref() }.map(AsRef::as_ref)
+}
+
+/// Target `spawn`, the same as clicking on it would.
+///
+/// # Errors
+///
+/// Returns an error if we're not in game, or if the spawn no longer exists.
+pub fn set_target(spawn: &Spawn) -> Result<(), TargetError> {
+    set_target_id(spawn.id())
+}
+
+/// Target the spawn with the id `id`, the same as clicking on it would.
+///
+/// # Errors
+///
+/// Returns an error if we're not in game, or if there's no spawn with that id
+/// in the zone.
+pub fn set_target_id(id: SpawnId) -> Result<(), TargetError> {
+    debug_assert_main_thread!("macroquest::mq::set_target_id");
+
+    if character().is_none() {
+        return Err(TargetError::NotInGame);
+    }
+
+    if eqlib::set_target(id.0) {
+        Ok(())
+    }
+    else {
+        Err(TargetError::NoSuchSpawn { id })
+    }
+}
+
+/// Clear the target, if anything is targeted.
+pub fn clear_target() {
+    debug_assert_main_thread!("macroquest::mq::clear_target");
+
+    eqlib::clear_target();
+}
+
+/// An error setting the target, or the item target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TargetError {
+    /// We're not in game, so there's nothing to target.
+    NotInGame,
+    /// There's no spawn in the zone with the given id.
+    NoSuchSpawn {
+        /// The id of the spawn.
+        id: SpawnId,
+    },
+    /// There's no item on the ground in the zone with the given drop id.
+    NoSuchGroundItem {
+        /// The [drop id](GroundItem::drop_id) of the ground item.
+        id: u32,
+    },
+}
+
+impl fmt::Display for TargetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetError::NotInGame => f.write_str("not in game"),
+            TargetError::NoSuchSpawn { id } => {
+                write!(f, "there is no spawn with id {id}")
+            }
+            TargetError::NoSuchGroundItem { id } => {
+                write!(f, "there is no ground item with id {id}")
+            }
+        }
+    }
+}
+
+impl error::Error for TargetError {}
+
+/// The spawn in the current zone with the id `id`, or `None` if there isn't
+/// one (such as when it has despawned).
+///
+/// The spawn belongs to the zone, so it must not be held onto past zoning.
+/// This doesn't check that we're in game; see [`InGame::spawn_by_id`] for the
+/// checked version.
+#[must_use]
+pub fn spawn_by_id(id: SpawnId) -> Option<&'static Spawn> {
+    debug_assert_main_thread!("macroquest::mq::spawn_by_id");
+
+    spawns().find(|spawn| spawn.id() == id)
+}
+
+/// Iterate over every spawn in the current zone, including the local
+/// character's own spawn.
+///
+/// There are no spawns while we're not in a zone, such as at character select.
+/// The spawns belong to the zone, and may despawn between hooks, so the
+/// iterator (and the spawns that it yields) must be used up within the hook
+/// that it was created in, rather than held onto.
+///
+/// This doesn't check that we're in game; see [`InGame::spawns`] for the
+/// checked version.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::eq::SpawnType;
+/// use macroquest::mq;
+///
+/// fn count_npcs() {
+///     let count = mq::spawns()
+///         .filter(|spawn| spawn.spawn_type() == SpawnType::Npc)
+///         .count();
+///
+///     mq::write_chat(format!("There are {count} NPCs in the zone"));
+/// }
+/// ```
+pub fn spawns() -> impl Iterator<Item = &'static Spawn> {
+    debug_assert_main_thread!("macroquest::mq::spawns");
+
+    // SAFETY: The spawns live for as long as the zone does, and each pointer is
+    //         either null or points to the next one of them.
+    let first = unsafe { eqlib::first_spawn().as_ref() };
+
+    std::iter::successors(first, |spawn| unsafe { spawn.next().as_ref() })
+        .map(AsRef::as_ref)
+}
+
+/// Iterate over the switches (doors, levers, and other clickable objects) in
+/// the current zone.
+///
+/// There are no switches while we're not in a zone, such as at character
+/// select. The switches belong to the zone, so they must not be held onto past
+/// zoning.
+pub fn switches() -> impl Iterator<Item = &'static Switch> {
+    debug_assert_main_thread!("macroquest::mq::switches");
+
+    // SAFETY: The switches live for as long as the zone does, and each pointer
+    //         is either null or points to one of them.
+    (0..eqlib::switch_count())
+        .filter_map(|index| unsafe { eqlib::get_switch(index).as_ref() })
+        .map(AsRef::as_ref)
+}
+
+/// The switch in the current zone with the id `id`, or `None` if there isn't
+/// one.
+///
+/// The switch belongs to the zone, so it must not be held onto past zoning.
+#[must_use]
+pub fn switch_by_id(id: u32) -> Option<&'static Switch> {
+    debug_assert_main_thread!("macroquest::mq::switch_by_id");
+
+    switches().find(|switch| switch.id() == id)
+}
+
+/// The switch that's closest to the local character, or `None` if we're not
+/// in game or there aren't any.
+///
+/// The closest switch isn't necessarily close enough to click; see
+/// [`INTERACT_RANGE`](crate::eq::switch::INTERACT_RANGE).
+#[must_use]
+pub fn nearest_switch() -> Option<&'static Switch> {
+    let mut player = eqlib::Location::default();
+    if !eqlib::get_player_location(&mut player) {
+        return None;
+    }
+
+    let player = Location::from(player);
+    switches().min_by(|a, b| {
+        let a = a.location().distance(&player);
+        let b = b.location().distance(&player);
+        a.total_cmp(&b)
+    })
+}
+
+/// Iterate over the items lying on the ground in the current zone.
+///
+/// There are no ground items while we're not in game, such as at character
+/// select, where the game may still have the list of ground items from the
+/// zone that we camped out of. The ground items belong to the zone, so they
+/// must not be held onto past zoning, and may be picked up at any time, so
+/// they also must not be held onto past the current hook.
+///
+/// See [`InGame::ground_items`] for the version that can only be called once
+/// [`in_game`] has checked.
+pub fn ground_items() -> impl Iterator<Item = &'static GroundItem> {
+    debug_assert_main_thread!("macroquest::mq::ground_items");
+
+    // SAFETY: The ground items live for as long as the zone does, and each
+    //         pointer is either null or points to the next one of them.
+    let first = match game_state() {
+        GameState::InGame => unsafe { eqlib::first_ground_item().as_ref() },
+        _ => None,
+    };
+
+    std::iter::successors(first, |item| unsafe { item.next().as_ref() })
+        .map(AsRef::as_ref)
+}
+
+/// The item lying on the ground that's closest to the local character, or
+/// `None` if we're not in game or there aren't any.
+///
+/// See [`InGame::nearest_ground_item`] for the checked version.
+#[must_use]
+pub fn nearest_ground_item() -> Option<&'static GroundItem> {
+    let mut player = eqlib::Location::default();
+    if !eqlib::get_player_location(&mut player) {
+        return None;
+    }
+
+    let player = Location::from(player);
+    ground_items().min_by(|a, b| {
+        let a = a.location().distance(&player);
+        let b = b.location().distance(&player);
+        a.total_cmp(&b)
+    })
+}
+
+/// Target `item`, the same as `/itemtarget` does, which is what
+/// `${ItemTarget}` and `/click left item` use.
+///
+/// # Errors
+///
+/// Returns an error if we're not in game, or if the item is no longer on the
+/// ground.
+pub fn item_target(item: &GroundItem) -> Result<(), TargetError> {
+    debug_assert_main_thread!("macroquest::mq::item_target");
+
+    if character().is_none() {
+        return Err(TargetError::NotInGame);
+    }
+
+    let id = item.drop_id();
+    if mqlib::set_ground_target(id) {
+        Ok(())
+    }
+    else {
+        Err(TargetError::NoSuchGroundItem { id })
+    }
+}
+
+/// A destination for the lines of chat written with [`write_chat`] and
+/// [`write_chat_color`].
+///
+/// By default, chat is written to the MacroQuest console by [`MacroQuestChat`],
+/// but a different sink can be installed with [`set_chat_sink`], which is most
+/// useful to capture the chat written by code under test (see
+/// [`testing::CapturedChat`]).
+pub trait ChatSink: Send + Sync {
+    /// Write a line of text using the given [`ChatColor`].
+    fn write_chat_color(&self, line: &str, color: ChatColor);
+
+    /// Write a line of text using the default [`ChatColor`].
+    fn write_chat(&self, line: &str) {
+        self.write_chat_color(line, ChatColor::default());
+    }
+
+    /// Write several lines of text, each using its own [`ChatColor`], as
+    /// written by a [`ChatBatch`].
+    ///
+    /// By default, each line is written with
+    /// [`write_chat_color`](ChatSink::write_chat_color) in turn.
+    fn write_chat_lines(&self, lines: &[(String, ChatColor)]) {
+        for (line, color) in lines {
+            self.write_chat_color(line, *color);
+        }
+    }
+}
+
+/// The default [`ChatSink`], which writes to the MacroQuest console.
+///
+/// Any of the standard 8 ANSI color codes in the line are converted into the
+/// equivalent MacroQuest color codes, and any other escape sequences (such as
+/// hyperlinks or cursor movement) and control characters are removed.
+///
+/// The line is then encoded in the game's character set (Windows-1252), where
+/// any characters that it doesn't have are replaced with an ASCII lookalike
+/// for a few common symbols (such as `->` for `→`), or otherwise with a `?`.
+/// See [`write_chat_raw`] to write a line that's already encoded.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MacroQuestChat;
+
+impl ChatSink for MacroQuestChat {
+    fn write_chat_color(&self, line: &str, color: ChatColor) {
+        debug_assert_main_thread!("macroquest::mq::write_chat_color");
+
+        mqlib::write_chat_color(&charset::encode(&colorize_line(line)), color.raw());
+    }
+
+    fn write_chat_lines(&self, lines: &[(String, ChatColor)]) {
+        debug_assert_main_thread!("macroquest::mq::write_chat_lines");
+
+        // Each run of lines with the same color crosses over to MacroQuest at
+        // once, with every line ended by a newline (which colorizing removes
+        // from within a line).
+        for run in lines.chunk_by(|(_, a), (_, b)| a == b) {
+            let mut buffer = Vec::new();
+            for (line, _) in run {
+                let line = colorize_line(line.as_str());
+                buffer.extend_from_slice(&charset::encode(&line));
+                buffer.push(b'\n');
+            }
+
+            mqlib::write_chat_lines(&buffer, run[0].1.raw());
+        }
+    }
+}
+
+/// Replace the global [`ChatSink`] that [`write_chat`] and
+/// [`write_chat_color`] write to, returning the previous one.
+///
+/// Passing `None` restores the default [`MacroQuestChat`] sink, and `None` is
+/// returned if that was the previous sink.
+pub fn set_chat_sink(sink: Option<Arc<dyn ChatSink>>) -> Option<Arc<dyn ChatSink>> {
+    std::mem::replace(&mut *CHAT_SINK.write(), sink)
+}
+
+/// Write a line of text into the MacroQuest console
+///
+/// This text will show up in the MacroQuest console (`ctrl \`), or in MQ2Chat
+/// or MQ2ChatWnd depending on which plugins you have loaded. It supports any of
+/// the MacroQuest colors (see
+/// [Color Codes](https://docs.macroquest.org/reference/commands/echo/?h=#color-codes))
+/// or any of the standard 8 ANSI color codes (as well as the "Faint" intensity
+/// modifier for dimmed or darker text).
+///
+/// This will use the the default [`ChatColor`], if you want to set a specific
+/// [`ChatColor`], see [`write_chat_color`].
+///
+/// The line is written to the current [`ChatSink`], see [`set_chat_sink`].
+///
+/// Lines written before MacroQuest is ready for chat (see [`is_chat_ready`])
+/// are held, and written in order (ahead of any later lines) on the first
+/// pulse after it is. Only the most recent 1024 lines are held, see
+/// [`try_write_chat`] to handle it some other way.
+pub fn write_chat<'a, S>(line: S)
+where
+    S: Into<Cow<'a, str>>,
+{
+    write_chat_color(line, ChatColor::default());
+}
+
+/// Write a line of text into the MacroQuest console
+///
+/// This text will show up in the MacroQuest console (`ctrl \`), or in MQ2Chat
+/// or MQ2ChatWnd depending on which plugins you have loaded. It supports any of
+/// the MacroQuest colors (see
+/// [Color Codes](https://docs.macroquest.org/reference/commands/echo/?h=#color-codes))
+/// or any of the standard 8 ANSI color codes (as well as the "Faint" intensity
+/// modifier for dimmed or darker text).
+///
+/// You must specify which [`ChatColor`] the line of text should use, which may
+/// be given as a [`Channel`](crate::eq::Channel), if you want to just use the
+/// default, see [`write_chat`].
+///
+/// The line is written to the current [`ChatSink`], see [`set_chat_sink`].
+///
+/// Lines written before MacroQuest is ready for chat are held until it is,
+/// see [`write_chat`].
+pub fn write_chat_color<'a, S, C>(line: S, color: C)
+where
+    S: Into<Cow<'a, str>>,
+    C: Into<ChatColor>,
+{
+    let line = line.into();
+    let color = color.into();
+
+    // The sink is cloned out of the lock, so that a sink is free to swap the
+    // sink, or write chat itself, without deadlocking.
+    let sink = CHAT_SINK.read().clone();
+    match sink {
+        Some(sink) => sink.write_chat_color(&line, color),
+        None => write_when_ready(
+            &MacroQuestChat,
+            is_chat_ready(),
+            &PENDING_CONSOLE_LINES,
+            &line,
+            color,
+        ),
+    }
+}
+
+/// Write a line into the MacroQuest console exactly as given, using the given
+/// [`ChatColor`].
+///
+/// Unlike [`write_chat_color`], the line isn't converted in any way, so it
+/// must already be in the game's character set (Windows-1252) and use
+/// MacroQuest's color codes rather than ANSI ones. It's written straight to
+/// MacroQuest, rather than to the current [`ChatSink`], so it must only be
+/// called from the main thread once MacroQuest is ready for chat (see
+/// [`is_chat_ready`]).
+pub fn write_chat_raw<C>(line: &[u8], color: C)
+where
+    C: Into<ChatColor>,
+{
+    debug_assert_main_thread!("macroquest::mq::write_chat_raw");
+
+    mqlib::write_chat_color(line, color.into().raw());
+}
+
+/// Write a line of text into the MacroQuest console, like [`write_chat`],
+/// unless MacroQuest isn't ready for chat yet.
+///
+/// # Errors
+///
+/// Returns an error, without holding onto the line, if MacroQuest isn't ready
+/// for chat yet.
+pub fn try_write_chat<'a, S>(line: S) -> Result<(), ChatError>
+where
+    S: Into<Cow<'a, str>>,
+{
+    try_write_chat_color(line, ChatColor::default())
+}
+
+/// Write a line of text into the MacroQuest console, like
+/// [`write_chat_color`], unless MacroQuest isn't ready for chat yet.
+///
+/// # Errors
+///
+/// Returns an error, without holding onto the line, if MacroQuest isn't ready
+/// for chat yet.
+pub fn try_write_chat_color<'a, S, C>(line: S, color: C) -> Result<(), ChatError>
+where
+    S: Into<Cow<'a, str>>,
+    C: Into<ChatColor>,
+{
+    // Installed sinks (such as when capturing chat in tests) don't depend on
+    // MacroQuest, so they're always ready.
+    if CHAT_SINK.read().is_none() && !is_chat_ready() {
+        return Err(ChatError::NotReady);
+    }
+
+    write_chat_color(line, color);
+    Ok(())
+}
+
+/// Whether MacroQuest is ready for chat to be written to it.
+///
+/// MacroQuest is ready once it has started initializing the plugin and the
+/// game has gotten as far as character select, as there's nowhere for chat to
+/// be shown until the game has loaded its UI. Chat written any earlier than
+/// that (such as while the plugin's DLL is still being loaded, or while the
+/// game is starting up) isn't ready.
+#[must_use]
+pub fn is_chat_ready() -> bool {
+    // The game state is read straight from MacroQuest, rather than through
+    // game_state(), as this may be called from any thread.
+    MAIN_THREAD.is_recorded()
+        && GameState::from(mqlib::get_game_state()) != GameState::PreCharacterSelect
+}
+
+/// An error writing chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChatError {
+    /// MacroQuest isn't ready for chat yet.
+    NotReady,
+}
+
+impl fmt::Display for ChatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChatError::NotReady => f.write_str("MacroQuest is not ready for chat yet"),
+        }
+    }
+}
+
+impl error::Error for ChatError {}
+
+/// Write `lines` to the current [`ChatSink`], the same as [`write_chat_color`]
+/// does for a single line.
+fn write_chat_lines(lines: &[(String, ChatColor)]) {
+    let sink = CHAT_SINK.read().clone();
+    match sink {
+        Some(sink) => sink.write_chat_lines(lines),
+        None => write_lines_when_ready(
+            &MacroQuestChat,
+            is_chat_ready(),
+            &PENDING_CONSOLE_LINES,
+            lines,
+        ),
+    }
+}
+
+/// Write `lines` to `sink` if MacroQuest is `ready` for them, after first
+/// writing anything that was held in `pending`, and otherwise hold them in
+/// `pending`.
+fn write_lines_when_ready(
+    sink: &dyn ChatSink,
+    ready: bool,
+    pending: &ChatQueue,
+    lines: &[(String, ChatColor)],
+) {
+    if !ready {
+        for (line, color) in lines {
+            pending.push(line, *color);
+        }
+        return;
+    }
+
+    for (pending, color) in pending.take() {
+        sink.write_chat_color(&pending, color);
+    }
+
+    sink.write_chat_lines(lines);
+}
+
+/// Write `line` to `sink` if MacroQuest is `ready` for it, after first writing
+/// anything that was held in `pending`, and otherwise hold it in `pending`.
+fn write_when_ready(
+    sink: &dyn ChatSink,
+    ready: bool,
+    pending: &ChatQueue,
+    line: &str,
+    color: ChatColor,
+) {
+    if !ready {
+        pending.push(line, color);
+        return;
+    }
+
+    for (pending, color) in pending.take() {
+        sink.write_chat_color(&pending, color);
+    }
+
+    sink.write_chat_color(line, color);
+}
+
+/// A bounded queue of lines of chat that are waiting to be written, which
+/// discards the oldest lines once it's full.
+struct ChatQueue {
+    capacity: usize,
+    lines:    Mutex<VecDeque<(String, ChatColor)>>,
+}
+
+impl ChatQueue {
+    const fn new(capacity: usize) -> ChatQueue {
+        ChatQueue {
+            capacity,
+            lines: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn push(&self, line: &str, color: ChatColor) {
+        let mut lines = self.lines.lock();
+        if lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back((line.to_owned(), color));
+    }
+
+    fn take(&self) -> VecDeque<(String, ChatColor)> {
+        std::mem::take(&mut *self.lines.lock())
+    }
+}
+
+/// Convert the standard 8 ANSI color codes into MacroQuest color codes
+///
+/// While MacroQuest has it's own color codes, the ANSI codes are far more
+/// standard and will have crates already available to make working with them
+/// easy.
+///
+/// Anything else that the MacroQuest console can't display, such as other
+/// escape sequences and control characters, is removed (see
+/// [`sanitize_line`]).
+fn colorize_line<'a, S>(line: S) -> Cow<'a, str>
+where
+    S: Into<Cow<'a, str>>,
+{
+    let line = sanitize_line(line.into());
+    match memchr::memchr(b'\x1b', line.as_bytes()) {
+        Some(_) => {
+            cansi::v3::categorise_text(&line)
+                .iter()
+                .flat_map(|m| {
+                    // We ignore most of the ANSI codes, because WriteChatColor
+                    // doesn't support them in any meaningful way, but we can
+                    // support foreground colors.
+                    match m.fg {
+                        None => ["", "", "", m.text, ""],
+                        Some(fg) => [
+                            // Control character that signifies a color code
+                            "\x07",
+                            // If we have a "Faint" color, then we'll use the darker
+                            // variant of our colors.
+                            match m.intensity.unwrap_or(Intensity::Normal) {
+                                Intensity::Faint => "-",
+                                _ => "",
+                            },
+                            // Map the ANSI colors to the MacroQuest color codes.
+                            //
+                            // MacroQuest has a code for each of the 8 standard
+                            // ANSI colors (and Orange and Purple, which ANSI
+                            // doesn't have), so the bright variants share the
+                            // code of their normal color.
+                            match fg {
+                                Color::Black | Color::BrightBlack => "b",
+                                Color::Green | Color::BrightGreen => "g",
+                                Color::Magenta | Color::BrightMagenta => "m",
+                                Color::Red | Color::BrightRed => "r",
+                                Color::Cyan | Color::BrightCyan => "t",
+                                Color::Blue | Color::BrightBlue => "u",
+                                Color::White | Color::BrightWhite => "w",
+                                Color::Yellow | Color::BrightYellow => "y",
+                            },
+                            // The actual text wrapped by this ANSI color code.
+                            m.text,
+                            // The "reset back to the normal color" code.
+                            "\x07x",
+                        ],
+                    }
+                })
+                .collect()
+        }
+        None => line,
+    }
+}
+
+/// Remove everything from `line` that the MacroQuest console can't display,
+/// other than SGR (color) escape sequences, which [`colorize_line`] converts.
+///
+/// That's every other escape sequence, such as OSC sequences (which are used
+/// for hyperlinks and window titles) and CSI sequences that move the cursor,
+/// along with any control characters other than tabs and the `\x07` that
+/// starts MacroQuest's own color codes. A sequence that's cut off by the end
+/// of the line is removed up to the end of the line.
+fn sanitize_line(line: Cow<'_, str>) -> Cow<'_, str> {
+    if !line.bytes().any(is_control_start) {
+        return line;
+    }
+
+    let mut sanitized = String::with_capacity(line.len());
+    let mut rest = &*line;
+    while let Some(pos) = rest.bytes().position(is_control_start) {
+        sanitized.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        let len = match control_len(rest.as_bytes()) {
+            Some((len, true)) => {
+                sanitized.push_str(&rest[..len]);
+                len
+            }
+            Some((len, false)) => len,
+            // Not a control at all, but a character (such as `©`) that starts
+            // with the same byte as the C1 controls do.
+            None => {
+                let len = rest.chars().next().map_or(1, char::len_utf8);
+                sanitized.push_str(&rest[..len]);
+                len
+            }
+        };
+
+        rest = &rest[len..];
+    }
+    sanitized.push_str(rest);
+
+    Cow::Owned(sanitized)
+}
+
+/// Whether `byte` might start a control character or escape sequence.
+fn is_control_start(byte: u8) -> bool {
+    // 0xc2 is the first byte of each of the C1 controls in UTF-8 (along with
+    // some printable characters).
+    (byte < 0x20 && byte != b'\t' && byte != b'\x07') || byte == 0x7f || byte == 0xc2
+}
+
+/// The length of the control character or escape sequence at the start of
+/// `bytes`, and whether it's an SGR sequence that should be kept, or `None` if
+/// `bytes` doesn't start with one.
+fn control_len(bytes: &[u8]) -> Option<(usize, bool)> {
+    // The C1 controls can either be written as ESC followed by a byte in
+    // 0x40..=0x5f, or as their own character (0x80..=0x9f).
+    let (introducer, body) = match bytes {
+        [0x1b, c @ 0x40..=0x5f, body @ ..] => (*c, body),
+        [0xc2, c @ 0x80..=0x9f, body @ ..] => (c - 0x40, body),
+        [0xc2, ..] => return None,
+        // Any other escape sequence is ESC, then any number of intermediate
+        // bytes, then a final byte.
+        [0x1b, body @ ..] => {
+            let intermediates =
+                body.iter().take_while(|&&b| is_intermediate(b)).count();
+            let last = body
+                .get(intermediates)
+                .filter(|b| (0x30..=0x7e).contains(*b));
+
+            return Some((1 + intermediates + usize::from(last.is_some()), false));
+        }
+        _ => return Some((1, false)),
+    };
+
+    let start = bytes.len() - body.len();
+    let len = match introducer {
+        // CSI, which is parameter bytes, then intermediate bytes, then a final
+        // byte. A sequence that's interrupted (or cut off) ends where it stops
+        // being valid.
+        b'[' => {
+            let params = body
+                .iter()
+                .take_while(|b| (0x30..=0x3f).contains(*b))
+                .count();
+            let intermediates = body[params..]
+                .iter()
+                .take_while(|&&b| is_intermediate(b))
+                .count();
+
+            match body.get(params + intermediates) {
+                Some(b'm') if intermediates == 0 => {
+                    return Some((start + params + 1, true));
+                }
+                Some(0x40..=0x7e) => params + intermediates + 1,
+                _ => params + intermediates,
+            }
+        }
+        // OSC, DCS, SOS, PM, and APC are strings, which run until a string
+        // terminator (ST), or a BEL (which is commonly used to end an OSC).
+        b']' | b'P' | b'X' | b'^' | b'_' => string_len(body),
+        // Any other C1 control is just the one character.
+        _ => 0,
+    };
+
+    Some((start + len, false))
+}
+
+/// The length of a control string (including its terminator) at the start of
+/// `body`, which is the whole of `body` if it's never terminated.
+fn string_len(body: &[u8]) -> usize {
+    for (i, byte) in body.iter().enumerate() {
+        match (*byte, body.get(i + 1)) {
+            (0x07, _) => return i + 1,
+            (0x1b, Some(b'\\')) | (0xc2, Some(0x9c)) => return i + 2,
+            // Any other escape sequence ends the string, and is handled on its
+            // own.
+            (0x1b, _) => return i,
+            _ => {}
+        }
+    }
+
+    body.len()
+}
+
+fn is_intermediate(byte: u8) -> bool {
+    (0x20..=0x2f).contains(&byte)
+}
+
+struct DeferredChat;
+
+impl ChatSink for DeferredChat {
+    fn write_chat_color(&self, line: &str, color: ChatColor) {
+        // Logging can happen from any thread, but writing to chat can only
+        // happen on the main thread, so anything logged from another thread
+        // is held until the next pulse.
+        if MAIN_THREAD.is_other() {
+            PENDING_CONSOLE_LINES.push(line, color);
+        }
+        else {
+            write_chat_color(line, color);
+        }
+    }
+}
+
+struct InternalConsoleWriter<W: ChatSink> {
+    writer:     W,
+    buffer:     Vec<u8>,
+    // The number of bytes at the end of the buffer that are the start of a
+    // character that hasn't been completely written yet.
+    incomplete: usize,
+}
+
+impl<W: ChatSink> InternalConsoleWriter<W> {
+    #[must_use]
+    fn new(writer: W) -> Self {
+        InternalConsoleWriter {
+            writer,
+            buffer: Vec::new(),
+            incomplete: 0,
+        }
+    }
+}
+
+impl<W: ChatSink> io::Write for InternalConsoleWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.buffer.len();
+        let start = len - self.incomplete;
+
+        // Add our incoming data to the end of our buffer, and validate that
+        // everything we haven't already validated is valid utf8. A character
+        // can be split across calls to write, so an incomplete character at
+        // the end of the buffer is allowed, and will be validated again once
+        // the rest of it has been written.
+        self.buffer.extend_from_slice(buf);
+        match std::str::from_utf8(&self.buffer[start..]) {
+            Ok(_) => self.incomplete = 0,
+            Err(e) if e.error_len().is_none() => {
+                self.incomplete = self.buffer.len() - start - e.valid_up_to();
+            }
+            Err(e) => {
+                self.buffer.truncate(len);
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, e.to_string()));
+            }
+        }
+
+        // Iterate over newline positions in our buffer, printing out each new
+        // line using `write_chat`.
+        let mut written = 0;
+        for pos in memchr::memchr_iter(b'\n', &self.buffer) {
+            // We need to turn our bytes into a &str so we can pass them into
+            // write_chat. It should not be possible for this to _not_ be valid
+            // utf8, as we've only added valid utf8 to our buffer, and an
+            // incomplete character can only be at the very end of it.
+            let line = std::str::from_utf8(&self.buffer[written..pos])
+                .expect("invalid utf8 in buffer");
+            written = pos + 1;
+
+            // Actually write our line of chat out.
+            write_console_line(&self.writer, line);
+        }
+
+        // A line without a newline is held until the newline is written, but
+        // we don't want a runaway line to grow without limit, so once it's
+        // longer than a line can be, we write out as much of it as we can.
+        let complete = self.buffer.len() - self.incomplete;
+        let mut partial = std::str::from_utf8(&self.buffer[written..complete])
+            .expect("invalid utf8 in buffer");
+        while partial.len() > MAX_CONSOLE_LINE_LENGTH {
+            let (line, rest) = split_console_line(partial);
+            written += line.len();
+            partial = rest;
+
+            self.writer.write_chat(line);
+        }
+
+        // Remove our written bytes from our buffer
+        self.buffer.drain(..written);
+
+        // Everything we were given has either been written, or is buffered.
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Write a single line of console output to `writer`, splitting it into
+/// multiple lines if it is longer than [`MAX_CONSOLE_LINE_LENGTH`].
+fn write_console_line<W: ChatSink>(writer: &W, mut line: &str) {
+    while line.len() > MAX_CONSOLE_LINE_LENGTH {
+        let (head, rest) = split_console_line(line);
+        writer.write_chat(head);
+        line = rest;
+    }
+
+    writer.write_chat(line);
+}
+
+/// Split `line` at the last character boundary that is at most
+/// [`MAX_CONSOLE_LINE_LENGTH`] bytes in.
+fn split_console_line(line: &str) -> (&str, &str) {
+    let mut end = MAX_CONSOLE_LINE_LENGTH.min(line.len());
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    line.split_at(end)
+}
+
+static CONSOLE: Lazy<Mutex<InternalConsoleWriter<DeferredChat>>> =
+    Lazy::new(|| Mutex::new(InternalConsoleWriter::new(DeferredChat)));
+
+/// A handle to the global console stream of the current MacroQuest process.
+///
+/// Each handle shares a global buffer of data to be written to the standard
+/// output stream.
+///
+/// Created by the [`console`] method.
+///
+/// # Note
+///
+/// The MacroQuest console *only* supports emitting whole lines at a time, and
+/// as such you must write at least whole lines to the console or the console
+/// writer will buffer until it receives a new line character.
+pub struct Console {}
+
+impl io::Write for Console {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        CONSOLE.lock().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        CONSOLE.lock().flush()
+    }
+}
+
+/// Constructs a new handle to the console stream of the current MacroQuest
+/// process.
+///
+/// Each handle returned is a reference to a shared, global buffer whose access
+/// is synchronized via a mutex.
+///
+/// # Note
+///
+/// The MacroQuest console *only* supports emitting whole lines at a time, and
+/// as such you must write at least whole lines to the console or the console
+/// writer will buffer until it receives a new line character.
+#[must_use]
+pub fn console() -> Console {
+    Console {}
+}
+
+/// Entry points for the fuzz targets in `macroquest/fuzz`, which exercise the
+/// text handling that consumes chat from other players.
+#[cfg(fuzzing)]
+#[doc(hidden)]
+pub mod fuzzing {
+    use std::borrow::Cow;
+    use std::io::Write;
+
+    use parking_lot::Mutex;
+
+    use super::{ChatSink, InternalConsoleWriter};
+    use crate::eq::ChatColor;
+
+    pub const MAX_CONSOLE_LINE_LENGTH: usize = super::MAX_CONSOLE_LINE_LENGTH;
+
+    #[must_use]
+    pub fn colorize_line(line: &str) -> Cow<'_, str> {
+        super::colorize_line(line)
+    }
+
+    /// Write each of `writes` to a console writer, returning the lines that
+    /// it wrote, along with whether each write succeeded.
+    pub fn console_lines<'a, I>(writes: I) -> (Vec<String>, Vec<bool>)
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        #[derive(Default)]
+        struct Lines(Mutex<Vec<String>>);
+
+        impl ChatSink for Lines {
+            fn write_chat_color(&self, line: &str, _color: ChatColor) {
+                self.0.lock().push(line.to_owned());
+            }
+        }
+
+        let mut console = InternalConsoleWriter::new(Lines::default());
+        let results = writes
+            .into_iter()
+            .map(|buf| console.write(buf).is_ok())
+            .collect();
+
+        (console.writer.0.into_inner(), results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use colored::Colorize;
+
+    use super::*;
+    use crate::eq::Channel;
+
+    const MQ_PATHS: [&str; 9] = [
+        r"C:\MacroQuest",
+        r"C:\MacroQuest\config",
+        r"C:\MacroQuest\config\MacroQuest.ini",
+        r"C:\MacroQuest\macros",
+        r"C:\MacroQuest\logs",
+        r"C:\MacroQuest\CrashDumps",
+        r"C:\MacroQuest\plugins",
+        r"C:\MacroQuest\resources",
+        r"C:\EverQuest",
+    ];
+
+    #[test]
+    fn test_paths() {
+        let paths = Paths::new(MQ_PATHS).unwrap();
+
+        assert_eq!(paths.root(), Path::new(r"C:\MacroQuest"));
+        assert_eq!(paths.logs(), Path::new(r"C:\MacroQuest\logs"));
+        assert_eq!(paths.everquest(), Path::new(r"C:\EverQuest"));
+    }
+
+    #[test]
+    fn test_paths_not_set_yet() {
+        // Before MacroQuest sets its paths, they're empty, or garbage.
+        for garbage in ["", "\0\0\0", "config"] {
+            let mut raw = MQ_PATHS;
+            raw[4] = garbage;
+
+            assert!(Paths::new(raw).is_none(), "{garbage:?}");
+        }
+
+        assert!(Paths::new([""; 9]).is_none());
+    }
+
+    #[test]
+    fn test_colorize_returns_borrowed_when_no_color() {
+        assert!(matches!(
+            colorize_line("this is a line with no formatting"),
+            Cow::Borrowed(..)
+        ));
+    }
+
+    #[test]
+    fn test_colorize_returns_same_when_no_color() {
+        assert_eq!(
+            colorize_line("this is a line with no formatting"),
+            "this is a line with no formatting"
+        );
+    }
+
+    #[test]
+    fn test_colorize_leaves_mq_colors_alone() {
+        assert_eq!(
+            colorize_line(
+                "\x07b\x07-b\x07g\x07-g\x07m\x07-m\x07o\x07-o\x07p\x07-p\x07r\x07-r\x07t\x07-t\x07u\x07-u\x07w\x07-w\x07y\x07-ysome text\x07x"
+            ),
+            "\x07b\x07-b\x07g\x07-g\x07m\x07-m\x07o\x07-o\x07p\x07-p\x07r\x07-r\x07t\x07-t\x07u\x07-u\x07w\x07-w\x07y\x07-ysome text\x07x"
+        );
+    }
+
+    #[test]
+    fn test_colorize_converts_ansi() {
+        use super::colorize_line as c;
+
+        colored::control::set_override(true);
+
+        assert_eq!(c("black".black().to_string()), "\x07bblack\x07x");
+        assert_eq!(c("black".black().dimmed().to_string()), "\x07-bblack\x07x");
+
+        assert_eq!(c("green".green().to_string()), "\x07ggreen\x07x");
+        assert_eq!(c("green".green().dimmed().to_string()), "\x07-ggreen\x07x");
+
+        assert_eq!(c("magenta".magenta().to_string()), "\x07mmagenta\x07x");
+        assert_eq!(
+            c("magenta".magenta().dimmed().to_string()),
+            "\x07-mmagenta\x07x"
+        );
+
+        assert_eq!(c("red".red().to_string()), "\x07rred\x07x");
+        assert_eq!(c("red".red().dimmed().to_string()), "\x07-rred\x07x");
+
+        assert_eq!(c("cyan".cyan().to_string()), "\x07tcyan\x07x");
+        assert_eq!(c("cyan".cyan().dimmed().to_string()), "\x07-tcyan\x07x");
+
+        assert_eq!(c("blue".blue().to_string()), "\x07ublue\x07x");
+        assert_eq!(c("blue".blue().dimmed().to_string()), "\x07-ublue\x07x");
+
+        assert_eq!(c("white".white().to_string()), "\x07wwhite\x07x");
+        assert_eq!(c("white".white().dimmed().to_string()), "\x07-wwhite\x07x");
+
+        assert_eq!(c("yellow".yellow().to_string()), "\x07yyellow\x07x");
+        assert_eq!(
+            c("yellow".yellow().dimmed().to_string()),
+            "\x07-yyellow\x07x"
+        );
+
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_main_thread_unrecorded() {
+        let main = MainThread::new();
+
+        assert!(!main.is_current());
+        assert!(!main.is_other());
+
+        // Without a recorded main thread, there is nothing to check against.
+        main.check(&["test"]);
+    }
+
+    #[test]
+    fn test_main_thread_recorded() {
+        let main = MainThread::new();
+        main.record();
+
+        assert!(main.is_current());
+        assert!(!main.is_other());
+        main.check(&["test"]);
+
+        // Only the first thread to record is the main thread.
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                main.record();
+                assert!(!main.is_current());
+                assert!(main.is_other());
+            });
+        });
+        assert!(main.is_current());
+    }
+
+    #[test]
+    fn test_main_thread_assertion_from_other_thread() {
+        let main = MainThread::new();
+        main.record();
+
+        let error = std::thread::Builder::new()
+            .name("worker".into())
+            .spawn(move || main.check(&["macroquest", "mq", "write_chat"]))
+            .unwrap()
+            .join()
+            .unwrap_err();
+
+        let message = error.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with(
+            "macroquest::mq::write_chat must only be called from the game's main \
+             thread, but was called from thread \"worker\""
+        ));
+    }
+
+    struct TestChatWriter {
+        lines: Mutex<Vec<String>>,
+    }
+
+    impl ChatSink for TestChatWriter {
+        fn write_chat_color(&self, line: &str, _color: ChatColor) {
+            self.lines.lock().push(line.to_string());
+        }
+    }
+
+    fn test_console() -> InternalConsoleWriter<TestChatWriter> {
+        InternalConsoleWriter::new(test_sink())
+    }
+
+    fn test_sink() -> TestChatWriter {
+        TestChatWriter {
+            lines: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[test]
+    fn test_chat_held_until_ready() {
+        let sink = test_sink();
+        let pending = ChatQueue::new(10);
+
+        write_when_ready(&sink, false, &pending, "one", Channel::Default.into());
+        write_when_ready(&sink, false, &pending, "two", Channel::Say.into());
+        assert!(sink.lines.lock().is_empty());
+
+        // The held lines are written first, in the order that they were
+        // written, and only once.
+        write_when_ready(&sink, true, &pending, "three", Channel::Default.into());
+        write_when_ready(&sink, true, &pending, "four", Channel::Default.into());
+
+        assert_eq!(*sink.lines.lock(), &["one", "two", "three", "four"]);
+        assert!(pending.take().is_empty());
+    }
+
+    #[test]
+    fn test_chat_queue_discards_oldest() {
+        let pending = ChatQueue::new(2);
+        pending.push("one", Channel::Default.into());
+        pending.push("two", Channel::Default.into());
+        pending.push("three", Channel::Tell.into());
+
+        let lines: Vec<_> = pending.take().into_iter().collect();
+        assert_eq!(
+            lines,
+            [
+                ("two".to_owned(), ChatColor::from(Channel::Default)),
+                ("three".to_owned(), ChatColor::from(Channel::Tell))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chat_error_display() {
+        assert_eq!(
+            ChatError::NotReady.to_string(),
+            "MacroQuest is not ready for chat yet"
+        );
+    }
+
+    #[allow(clippy::unused_io_amount)]
+    #[test]
+    fn test_console_writer_writes_line() {
+        let mut console = test_console();
+
+        console
+            .write_all(b"this is one line\nand this is another\n")
+            .unwrap();
+
+        console.write(b"this is a line without a new line").unwrap();
+
+        assert_eq!(
+            *console.writer.lines.lock(),
+            &["this is one line", "and this is another"]
+        );
+
+        console.write(b"\n").unwrap();
+
+        assert_eq!(
+            *console.writer.lines.lock(),
+            &[
+                "this is one line",
+                "and this is another",
+                "this is a line without a new line"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_console_writer_returns_bytes_consumed() {
+        let mut console = test_console();
+
+        // A partial line is buffered, but has still been consumed.
+        assert_eq!(console.write(b"one").unwrap(), 3);
+        assert_eq!(console.write(b"\n").unwrap(), 1);
+
+        // Formatting writes each piece separately, which previously failed
+        // with WriteZero as soon as a piece didn't end in a newline.
+        let two = "two";
+        writeln!(console, "{two} {}", 3).unwrap();
+
+        assert_eq!(*console.writer.lines.lock(), &["one", "two 3"]);
+    }
+
+    #[test]
+    fn test_console_writer_write_all_without_newlines() {
+        let mut console = test_console();
+
+        // Chunks without a newline are buffered, and must not be appended to
+        // the buffer again by write_all retrying them.
+        for chunk in ["first", " line", "\nsecond", " line", "\n"] {
+            console.write_all(chunk.as_bytes()).unwrap();
+        }
+        console.write_all(b"third line").unwrap();
+        console.write_all(b"\n").unwrap();
+
+        assert_eq!(
+            *console.writer.lines.lock(),
+            &["first line", "second line", "third line"]
+        );
+        assert!(console.buffer.is_empty());
+    }
+
+    #[test]
+    fn test_console_writer_character_split_across_writes() {
+        let mut console = test_console();
+        let text = "caf\u{e9} \u{1f600}\n".as_bytes();
+
+        for byte in text {
+            console.write_all(std::slice::from_ref(byte)).unwrap();
+        }
+
+        assert_eq!(*console.writer.lines.lock(), &["caf\u{e9} \u{1f600}"]);
+    }
+
+    #[test]
+    fn test_console_writer_rejects_invalid_utf8() {
+        let mut console = test_console();
+        console.write_all(b"ok ").unwrap();
+
+        // A lone continuation byte, an encoded surrogate, and an incomplete
+        // character that is followed by something other than its remainder.
+        for invalid in [&b"\x80"[..], b"\xed\xa0\x80", b"\xc3\n"] {
+            let error = console.write(invalid).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+
+        // Nothing from a rejected write is kept.
+        console.write_all(b"still ok\n").unwrap();
+        assert_eq!(*console.writer.lines.lock(), &["ok still ok"]);
+    }
+
+    #[test]
+    fn test_console_writer_splits_long_lines() {
+        let mut console = test_console();
+
+        // The multibyte character straddles the split point, and must not be
+        // split itself.
+        let line = format!(
+            "{}\u{e9}{}",
+            "a".repeat(MAX_CONSOLE_LINE_LENGTH - 1),
+            "b".repeat(10)
+        );
+        writeln!(console, "{line}").unwrap();
+
+        let lines = console.writer.lines.lock();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "a".repeat(MAX_CONSOLE_LINE_LENGTH - 1));
+        assert_eq!(lines[1], format!("\u{e9}{}", "b".repeat(10)));
+    }
+
+    #[test]
+    fn test_console_writer_bounds_runaway_line() {
+        let mut console = test_console();
+        let chunk = "x".repeat(1000);
+
+        for _ in 0..1000 {
+            console.write_all(chunk.as_bytes()).unwrap();
+            assert!(console.buffer.len() <= MAX_CONSOLE_LINE_LENGTH);
+        }
+
+        let lines = console.writer.lines.lock();
+        assert!(!lines.is_empty());
+        assert!(lines.iter().all(|l| l.len() <= MAX_CONSOLE_LINE_LENGTH));
+    }
+
+    #[test]
+    fn test_colorize_truncated_escapes() {
+        for line in [
+            "\x1b",
+            "\x1b[",
+            "\x1b[31",
+            "\x1b[31;",
+            "text\x1b[3",
+            "\x1b[\x1b[31mnested",
+            "\x1b[31m\x1b[",
+            "\x1b[38;5;\u{e9}m",
+            "\x1b]8;;https://",
+            "\x1b]8;;\x1b",
+            "\x1bP",
+            "\x1b(",
+            "\u{9d}",
+        ] {
+            // These have their sequences dropped, but must never panic.
+            let colorized = colorize_line(line);
+            assert!(colorized.len() <= line.len() + 5, "{line:?}");
+            assert!(!colorized.contains('\x1b'), "{line:?}");
+        }
+
+        assert_eq!(colorize_line("\x1b[31"), "");
+        assert_eq!(colorize_line("text\x1b[3"), "text");
+
+        // The second sequence's ESC interrupts the first sequence, which is
+        // dropped, leaving the second sequence to color the text.
+        assert_eq!(colorize_line("\x1b[\x1b[31mred"), "\x07rred\x07x");
+    }
+
+    #[test]
+    fn test_colorize_strips_osc_hyperlinks() {
+        // OSC 8 hyperlinks, terminated by ST, and by BEL.
+        assert_eq!(
+            colorize_line("see \x1b]8;;http://x.io\x1b\\the docs\x1b]8;;\x1b\\"),
+            "see the docs"
+        );
+        assert_eq!(
+            colorize_line("\x1b]8;;http://x.io\x07link\x1b]8;;\x07"),
+            "link"
+        );
+        assert_eq!(colorize_line("\x1b]0;window title\x07text"), "text");
+
+        // A hyperlink within a color keeps its color.
+        assert_eq!(
+            colorize_line("\x1b[31m\x1b]8;;http://x.io\x1b\\red\x1b]8;;\x1b\\\x1b[0m"),
+            "\x07rred\x07x"
+        );
+    }
+
+    #[test]
+    fn test_colorize_strips_cursor_movement() {
+        // Progress bars redraw themselves by moving the cursor up a line, and
+        // erasing it.
+        assert_eq!(
+            colorize_line("\x1b[1A\x1b[2K\r[=====>    ] 50%"),
+            "[=====>    ] 50%"
+        );
+        assert_eq!(colorize_line("\x1b[?25lno cursor\x1b[?25h"), "no cursor");
+        assert_eq!(colorize_line("\x1b7saved\x1b8"), "saved");
+        assert_eq!(colorize_line("charset\x1b(B"), "charset");
+    }
+
+    #[test]
+    fn test_colorize_strips_control_characters() {
+        assert_eq!(colorize_line("tab\there\r"), "tab\there");
+        assert_eq!(colorize_line("back\x08space\x7f"), "backspace");
+
+        // C1 controls, including the single character forms of CSI and OSC.
+        assert_eq!(
+            colorize_line("\u{9b}1Aup \u{9d}0;title\u{9c}\u{85}done"),
+            "up done"
+        );
+
+        // Printable characters that start with the same byte as C1 controls
+        // are left alone.
+        assert_eq!(
+            colorize_line("\u{a9} caf\u{e9}\u{b0}\r"),
+            "\u{a9} caf\u{e9}\u{b0}"
+        );
+    }
+
+    #[test]
+    fn test_colorize_truncated_sequence_ends_with_line() {
+        let mut console = test_console();
+
+        console.write_all(b"one\x1b]8;;https://exa").unwrap();
+        console
+            .write_all(b"\ntwo \x1b[3\nthree\x1b\nfour\n")
+            .unwrap();
+
+        let lines: Vec<String> = console
+            .writer
+            .lines
+            .lock()
+            .iter()
+            .map(|line| colorize_line(line.as_str()).into_owned())
+            .collect();
+
+        assert_eq!(lines, ["one", "two ", "three", "four"]);
+    }
+}
@@ -0,0 +1,349 @@
+//! Reading and writing a plugin's settings in an ini file.
+//!
+//! Every MacroQuest plugin keeps its settings in `Config\<plugin>.ini`, which
+//! [`Settings::open()`] reads and writes the same way that the Windows
+//! `GetPrivateProfileString` and `WritePrivateProfileString` functions do that
+//! MacroQuest's own plugins use:
+//!
+//! * Sections and keys are matched ignoring case.
+//! * Values are trimmed, and a value that's wrapped in matching quotes has them
+//!   removed.
+//! * A missing file, section, or key is the same as a missing value.
+//! * Writing a value replaces the key in place, or adds it to the end of its
+//!   section, creating the section (and the file) if needed.
+//!
+//! Settings that belong to a single character conventionally go in a section
+//! named after their server and name (ex: `[firiona.Vox]`), which
+//! [`section_for_character()`] builds.
+//!
+//! ```no_run
+//! use macroquest::mq::settings::{self, Settings};
+//!
+//! let settings = Settings::open("MyPlugin");
+//!
+//! let radius = settings.get::<u32>("General", "Radius").unwrap_or(100);
+//! settings.set("General", "Radius", radius).unwrap();
+//!
+//! if let Some(section) = settings::section_for_character() {
+//!     let enabled = settings.get(&section, "Enabled").unwrap_or(true);
+//!     settings.set(&section, "Enabled", enabled).unwrap();
+//! }
+//! ```
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::macros::debug_assert_main_thread;
+use crate::mq::ini::{self, Line};
+use crate::mq::{self, config_dirs};
+
+/// A plugin's ini file of settings.
+///
+/// The file is read every time that a setting is read, and written every time
+/// that one is written, so it always reflects any changes made to the file
+/// outside of the plugin.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Settings {
+    path: PathBuf,
+}
+
+impl Settings {
+    /// The settings of the plugin named `plugin` (`Config\<plugin>.ini`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if MacroQuest hasn't set its paths yet, see [`mq::paths()`].
+    #[must_use]
+    pub fn open(plugin: &str) -> Settings {
+        Settings::from_path(mq::paths().config().join(format!("{plugin}.ini")))
+    }
+
+    /// The settings in the ini file at `path`.
+    #[must_use]
+    pub fn from_path(path: impl Into<PathBuf>) -> Settings {
+        Settings { path: path.into() }
+    }
+
+    /// The path to the ini file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the value of `key` in `section`, returning `None` if there's no
+    /// such value, or if it isn't a valid `T`.
+    ///
+    /// A file that can't be read is treated as if it had no values, the same
+    /// as `GetPrivateProfileString` does.
+    #[must_use]
+    pub fn get<T>(&self, section: &str, key: &str) -> Option<T>
+    where
+        T: IniValue,
+    {
+        let contents = std::fs::read_to_string(&self.path).ok()?;
+        T::from_ini(ini::lookup(&contents, section, key)?)
+    }
+
+    /// Write `value` as the value of `key` in `section`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or written.
+    pub fn set<T>(&self, section: &str, key: &str, value: T) -> io::Result<()>
+    where
+        T: IniValue,
+    {
+        let contents = self.read()?;
+        std::fs::write(
+            &self.path,
+            update(&contents, section, key, Some(&value.into_ini())),
+        )
+    }
+
+    /// Remove `key` from `section`, returning whether it was there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or written.
+    pub fn remove(&self, section: &str, key: &str) -> io::Result<bool> {
+        let contents = self.read()?;
+        if ini::lookup(&contents, section, key).is_none() {
+            return Ok(false);
+        }
+
+        std::fs::write(&self.path, update(&contents, section, key, None))?;
+        Ok(true)
+    }
+
+    // The contents of the file, which are empty if it doesn't exist yet.
+    fn read(&self) -> io::Result<String> {
+        match std::fs::read_to_string(&self.path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+            result => result,
+        }
+    }
+}
+
+/// The conventional section for the settings of the character that we're
+/// playing (ex: `firiona.Vox`), or `None` if we're not in game.
+#[must_use]
+pub fn section_for_character() -> Option<String> {
+    debug_assert_main_thread!("macroquest::mq::settings::section_for_character");
+
+    config_dirs::identity()
+        .map(|identity| character_section(&identity.server, &identity.character))
+}
+
+/// A type that can be read from, and written as, an ini value.
+pub trait IniValue: Sized {
+    /// Parse the value from `value`, returning `None` if it isn't valid.
+    fn from_ini(value: &str) -> Option<Self>;
+
+    /// The value, as it's written to the file.
+    fn into_ini(self) -> String;
+}
+
+macro_rules! ini_value_from_str {
+    ($($ty:ty),+) => {
+        $(
+            impl IniValue for $ty {
+                fn from_ini(value: &str) -> Option<Self> {
+                    value.parse().ok()
+                }
+
+                fn into_ini(self) -> String {
+                    self.to_string()
+                }
+            }
+        )+
+    };
+}
+
+ini_value_from_str!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+impl IniValue for bool {
+    /// Parsed from `true`, `on`, `yes`, or `1` and `false`, `off`, `no`, or `0`
+    /// (ignoring case), and written as `true` or `false`.
+    fn from_ini(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "true" | "on" | "yes" | "1" => Some(true),
+            "false" | "off" | "no" | "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn into_ini(self) -> String {
+        self.to_string()
+    }
+}
+
+impl IniValue for String {
+    fn from_ini(value: &str) -> Option<Self> {
+        Some(value.to_owned())
+    }
+
+    fn into_ini(self) -> String {
+        self
+    }
+}
+
+fn character_section(server: &str, character: &str) -> String {
+    format!("{server}.{character}")
+}
+
+/// The ini `contents` with the value of `key` in `section` replaced with
+/// `value`, or removed if `value` is `None`.
+fn update(contents: &str, section: &str, key: &str, value: Option<&str>) -> String {
+    let mut lines: Vec<String> = contents.lines().map(str::to_owned).collect();
+    let entry = value.map(|value| format!("{key}={value}"));
+
+    // Where the section's last value (or its header) is, and where the key is.
+    let mut last = None;
+    let mut found = None;
+
+    for (index, line) in ini::section(contents, section) {
+        match line {
+            Line::Section(_) => last = Some(index),
+            Line::Entry(name, _) => {
+                last = Some(index);
+                if name.eq_ignore_ascii_case(key) {
+                    found = Some(index);
+                    break;
+                }
+            }
+            Line::Blank | Line::Invalid(_) => {}
+        }
+    }
+
+    match (found, last, entry) {
+        (Some(index), _, Some(entry)) => lines[index] = entry,
+        (Some(index), _, None) => {
+            lines.remove(index);
+        }
+        (None, Some(index), Some(entry)) => lines.insert(index + 1, entry),
+        (None, None, Some(entry)) => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{section}]"));
+            lines.push(entry);
+        }
+        (None, _, None) => {}
+    }
+
+    let mut contents = lines.join("\r\n");
+    contents.push_str("\r\n");
+    contents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &str = "\
+; A comment
+[General]
+Radius = 120
+Name=\"Alice \"
+Quoted='single'
+Enabled=on
+Scale=1.5
+
+[firiona.Vox]
+Enabled=no
+";
+
+    fn temp_settings(name: &str) -> Settings {
+        let dir = std::env::temp_dir()
+            .join(format!("macroquest-settings-{}-{name}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let settings = Settings::from_path(dir.join("MyPlugin.ini"));
+        let _ = std::fs::remove_file(settings.path());
+        settings
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(u32::from_ini("120"), Some(120));
+        assert_eq!(u32::from_ini("-1"), None);
+        assert_eq!(f64::from_ini("1.5"), Some(1.5));
+        assert_eq!(bool::from_ini("ON"), Some(true));
+        assert_eq!(bool::from_ini("0"), Some(false));
+        assert_eq!(bool::from_ini("maybe"), None);
+        assert_eq!(true.into_ini(), "true");
+        assert_eq!(1.5_f32.into_ini(), "1.5");
+    }
+
+    #[test]
+    fn test_update() {
+        let updated = update(CONTENTS, "general", "radius", Some("200"));
+        assert_eq!(ini::lookup(&updated, "General", "Radius"), Some("200"));
+        assert!(updated.contains("\r\nradius=200\r\n"));
+        assert!(!updated.contains("Radius = 120"));
+
+        let updated = update(CONTENTS, "General", "Color", Some("red"));
+        assert_eq!(ini::lookup(&updated, "General", "Color"), Some("red"));
+        assert!(updated.contains("Scale=1.5\r\nColor=red\r\n"));
+
+        let updated = update(CONTENTS, "New", "Key", Some("value"));
+        assert!(updated.ends_with("Enabled=no\r\n\r\n[New]\r\nKey=value\r\n"));
+
+        let updated = update(CONTENTS, "General", "Radius", None);
+        assert_eq!(ini::lookup(&updated, "General", "Radius"), None);
+        assert_eq!(ini::lookup(&updated, "General", "Scale"), Some("1.5"));
+    }
+
+    #[test]
+    fn test_duplicate_section() {
+        let contents = "\
+[General]
+Radius=120
+[Other]
+[general]
+Radius=80
+Color=red
+";
+
+        assert_eq!(ini::lookup(contents, "General", "Radius"), Some("120"));
+        assert_eq!(ini::lookup(contents, "General", "Color"), None);
+
+        let updated = update(contents, "General", "Radius", Some("200"));
+        assert_eq!(ini::lookup(&updated, "General", "Radius"), Some("200"));
+        assert!(updated.contains("\r\n[general]\r\nRadius=80\r\n"));
+
+        let updated = update(contents, "General", "Color", Some("blue"));
+        assert_eq!(ini::lookup(&updated, "General", "Color"), Some("blue"));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let settings = temp_settings("missing");
+
+        assert_eq!(settings.get::<u32>("General", "Radius"), None);
+        assert!(!settings.remove("General", "Radius").unwrap());
+        assert!(!settings.path().exists());
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let settings = temp_settings("get-and-set");
+
+        settings.set("General", "Radius", 120_u32).unwrap();
+        settings.set("General", "Name", "Alice".to_owned()).unwrap();
+        settings.set("General", "Enabled", true).unwrap();
+        settings
+            .set(&character_section("firiona", "Vox"), "Scale", 1.5)
+            .unwrap();
+
+        assert_eq!(settings.get("General", "Radius"), Some(120_u32));
+        assert_eq!(settings.get("general", "name"), Some("Alice".to_owned()));
+        assert_eq!(settings.get("General", "Enabled"), Some(true));
+        assert_eq!(settings.get("firiona.Vox", "Scale"), Some(1.5));
+        assert_eq!(settings.get::<u32>("General", "Name"), None);
+        assert_eq!(settings.get::<u32>("General", "Missing"), None);
+
+        assert!(settings.remove("General", "Radius").unwrap());
+        assert_eq!(settings.get::<u32>("General", "Radius"), None);
+    }
+}
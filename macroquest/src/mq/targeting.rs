@@ -0,0 +1,302 @@
+//! Being told when the target changes.
+//!
+//! MacroQuest doesn't give plugins a hook for the target changing, so there's
+//! no `Hooks` method for it. Instead, the target is compared on every pulse
+//! (before [`Hooks::pulse`](crate::plugin::Hooks::pulse) is called), and the
+//! handlers registered with [`on_target_changed`] are called whenever it's
+//! different from the last pulse.
+//!
+//! Since the target is only compared once per pulse, targeting a spawn and
+//! then another within the same pulse is only seen as the second change.
+//!
+//! # Examples
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MyPlugin);
+//! use macroquest::log::info;
+//! use macroquest::mq::targeting::{self, TargetWatch};
+//!
+//! struct MyPlugin {
+//!     target: TargetWatch,
+//! }
+//!
+//! impl Plugin for MyPlugin {
+//!     fn new() -> Self {
+//!         let target = targeting::on_target_changed(|_, new| match new {
+//!             Some(target) => info!(name = target.name, "targeted"),
+//!             None => info!("cleared the target"),
+//!         });
+//!
+//!         MyPlugin { target }
+//!     }
+//! }
+//! # impl Hooks for MyPlugin {}
+//! ```
+
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+
+use parking_lot::Mutex;
+
+use crate::eq::{Spawn, SpawnId};
+use crate::log::error;
+use crate::mq;
+use crate::plugin::panic_message;
+
+type Handler = Box<dyn FnMut(Option<&TargetSnapshot>, Option<&TargetSnapshot>) + Send>;
+
+static WATCHES: Mutex<Registry> = Mutex::new(Registry::new());
+
+/// A snapshot of the spawn that was targeted.
+///
+/// This is a copy rather than a [`Spawn`], as the previous target may have
+/// despawned by the time the handler is called.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetSnapshot {
+    /// The id of the spawn.
+    pub id:   SpawnId,
+    /// The name of the spawn.
+    pub name: String,
+}
+
+impl From<&Spawn> for TargetSnapshot {
+    fn from(spawn: &Spawn) -> Self {
+        TargetSnapshot {
+            id:   spawn.id(),
+            name: spawn.name().to_owned(),
+        }
+    }
+}
+
+/// Call `handler` with the previous and the new target whenever the target
+/// changes, until the returned [`TargetWatch`] is dropped.
+///
+/// Either target is `None` if nothing was targeted. The handler is called on
+/// the game's main thread, and isn't called for whatever is targeted when it's
+/// registered.
+pub fn on_target_changed<F>(handler: F) -> TargetWatch
+where
+    F: FnMut(Option<&TargetSnapshot>, Option<&TargetSnapshot>) + Send + 'static,
+{
+    let id = WATCHES.lock().insert(Watch {
+        handler: Box::new(handler),
+        last:    None,
+        primed:  false,
+    });
+
+    TargetWatch { id }
+}
+
+/// A handle to a handler registered with [`on_target_changed`], which stops
+/// calling it when it's dropped.
+#[derive(Debug)]
+pub struct TargetWatch {
+    id: u64,
+}
+
+impl Drop for TargetWatch {
+    fn drop(&mut self) {
+        WATCHES.lock().remove(self.id);
+    }
+}
+
+/// Compare the target with the last pulse, calling every registered handler if
+/// it has changed.
+pub(crate) fn pulse() {
+    let ids = WATCHES.lock().ids();
+    if ids.is_empty() {
+        return;
+    }
+
+    let current = mq::target().map(TargetSnapshot::from);
+
+    for id in ids {
+        // The watch is taken out of the registry while its handler is called,
+        // so that it can register or drop watches itself without deadlocking.
+        let Some(mut watch) = WATCHES.lock().take(id)
+        else {
+            continue;
+        };
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            watch.update(current.as_ref());
+        }));
+
+        if let Err(payload) = result {
+            error!(
+                panic = panic_message(payload.as_ref()),
+                "target changed handler panicked"
+            );
+        }
+
+        WATCHES.lock().restore(id, watch);
+    }
+}
+
+/// Remove every registered handler, as the plugin is being unloaded.
+pub(crate) fn clear() {
+    WATCHES.lock().clear();
+}
+
+struct Watch {
+    handler: Handler,
+    // The target as of the last pulse.
+    last:    Option<TargetSnapshot>,
+    // Whether there has been a pulse yet, as there's nothing to compare the
+    // first pulse's target with.
+    primed:  bool,
+}
+
+impl Watch {
+    fn update(&mut self, current: Option<&TargetSnapshot>) {
+        let last = std::mem::replace(&mut self.last, current.cloned());
+        if !std::mem::replace(&mut self.primed, true) {
+            return;
+        }
+
+        let last_id = last.as_ref().map(|target| target.id);
+        let current_id = current.map(|target| target.id);
+
+        if last_id != current_id {
+            (self.handler)(last.as_ref(), current);
+        }
+    }
+}
+
+struct Registry {
+    next_id: u64,
+    // A watch is None while its handler is being called.
+    watches: BTreeMap<u64, Option<Watch>>,
+}
+
+impl Registry {
+    const fn new() -> Registry {
+        Registry {
+            next_id: 0,
+            watches: BTreeMap::new(),
+        }
+    }
+
+    fn insert(&mut self, watch: Watch) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.watches.insert(id, Some(watch));
+        id
+    }
+
+    fn remove(&mut self, id: u64) {
+        self.watches.remove(&id);
+    }
+
+    fn ids(&self) -> Vec<u64> {
+        self.watches.keys().copied().collect()
+    }
+
+    /// Take a watch out to call its handler.
+    fn take(&mut self, id: u64) -> Option<Watch> {
+        self.watches.get_mut(&id)?.take()
+    }
+
+    /// Put a watch back after calling its handler, unless it was removed
+    /// meanwhile.
+    fn restore(&mut self, id: u64, watch: Watch) {
+        if let Some(slot @ None) = self.watches.get_mut(&id) {
+            *slot = Some(watch);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.watches.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    type Changes = Arc<Mutex<Vec<(Option<u32>, Option<u32>)>>>;
+
+    fn target(id: u32, name: &str) -> TargetSnapshot {
+        TargetSnapshot {
+            id:   SpawnId(id),
+            name: name.to_owned(),
+        }
+    }
+
+    fn recording() -> (Changes, Watch) {
+        let changes = Changes::default();
+        let watch = Watch {
+            handler: {
+                let changes = Arc::clone(&changes);
+                Box::new(move |old, new| {
+                    let id = |target: Option<&TargetSnapshot>| target.map(|t| t.id.0);
+                    changes.lock().push((id(old), id(new)));
+                })
+            },
+            last:    None,
+            primed:  false,
+        };
+
+        (changes, watch)
+    }
+
+    #[test]
+    fn test_first_pulse_is_not_a_change() {
+        let (changes, mut watch) = recording();
+
+        watch.update(Some(&target(1, "a_rat")));
+        watch.update(Some(&target(1, "a_rat")));
+
+        assert!(changes.lock().is_empty());
+    }
+
+    #[test]
+    fn test_changes() {
+        let (changes, mut watch) = recording();
+
+        watch.update(None);
+        watch.update(Some(&target(1, "a_rat")));
+        watch.update(Some(&target(2, "a_bat")));
+        watch.update(Some(&target(2, "a_bat")));
+        watch.update(None);
+        watch.update(None);
+
+        assert_eq!(
+            *changes.lock(),
+            vec![(None, Some(1)), (Some(1), Some(2)), (Some(2), None)]
+        );
+    }
+
+    #[test]
+    fn test_same_id_is_not_a_change() {
+        // A spawn's name changes when it dies (a_rat becomes a_rat's corpse),
+        // but it's still the same target.
+        let (changes, mut watch) = recording();
+
+        watch.update(Some(&target(1, "a_rat")));
+        watch.update(Some(&target(1, "a_rat's corpse")));
+
+        assert!(changes.lock().is_empty());
+    }
+
+    #[test]
+    fn test_registry_removed_while_taken() {
+        let mut registry = Registry::new();
+        let one = registry.insert(recording().1);
+        let two = registry.insert(recording().1);
+        assert_eq!(registry.ids(), vec![one, two]);
+
+        let watch = registry.take(one).unwrap();
+        assert!(registry.take(one).is_none());
+        registry.remove(one);
+        registry.restore(one, watch);
+
+        assert_eq!(registry.ids(), vec![two]);
+
+        registry.clear();
+        assert!(registry.ids().is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Helpers for testing plugin code that interacts with MacroQuest.
+
+use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::{Mutex, ReentrantMutex, ReentrantMutexGuard};
+
+use super::{set_chat_sink, ChatSink};
+use crate::eq::ChatColor;
+
+// Tests are run in parallel, but the chat sink is global, so only one thread
+// may be capturing chat at a time. This is reentrant so that a single test may
+// nest captures.
+static CAPTURE_LOCK: ReentrantMutex<()> = ReentrantMutex::new(());
+
+#[derive(Default)]
+struct Capture {
+    lines: Mutex<Vec<(String, ChatColor)>>,
+}
+
+impl ChatSink for Capture {
+    fn write_chat_color(&self, line: &str, color: ChatColor) {
+        self.lines.lock().push((line.to_owned(), color));
+    }
+}
+
+/// Captures every line of chat written with
+/// [`write_chat`](crate::mq::write_chat) and
+/// [`write_chat_color`](crate::mq::write_chat_color), for as long as it's
+/// alive.
+///
+/// Creating a [`CapturedChat`] installs it as the global
+/// [`ChatSink`], and dropping it restores whichever sink was installed before
+/// it. As the sink is global, chat written from any thread is captured, and
+/// creating a [`CapturedChat`] blocks while another thread has one alive, so
+/// that tests running in parallel don't capture each other's chat.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::mq::testing::CapturedChat;
+///
+/// let chat = CapturedChat::new();
+/// macroquest::mq::write_chat("Hello World");
+///
+/// assert_eq!(chat.lines(), ["Hello World"]);
+/// ```
+pub struct CapturedChat {
+    capture:  Arc<Capture>,
+    previous: Option<Arc<dyn ChatSink>>,
+    _lock:    ReentrantMutexGuard<'static, ()>,
+}
+
+impl CapturedChat {
+    /// Start capturing chat.
+    #[must_use]
+    pub fn new() -> Self {
+        let lock = CAPTURE_LOCK.lock();
+        let capture = Arc::new(Capture::default());
+        let previous = set_chat_sink(Some(Arc::clone(&capture) as Arc<dyn ChatSink>));
+
+        CapturedChat {
+            capture,
+            previous,
+            _lock: lock,
+        }
+    }
+
+    /// The lines of chat that have been captured so far.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        self.capture
+            .lines
+            .lock()
+            .iter()
+            .map(|(line, _)| line.clone())
+            .collect()
+    }
+
+    /// The lines of chat that have been captured so far, along with the
+    /// [`ChatColor`] they were written with.
+    #[must_use]
+    pub fn lines_with_color(&self) -> Vec<(String, ChatColor)> {
+        self.capture.lines.lock().clone()
+    }
+
+    /// Discard all of the lines of chat that have been captured so far.
+    pub fn clear(&self) {
+        self.capture.lines.lock().clear();
+    }
+}
+
+impl Default for CapturedChat {
+    fn default() -> Self {
+        CapturedChat::new()
+    }
+}
+
+impl Drop for CapturedChat {
+    fn drop(&mut self) {
+        set_chat_sink(self.previous.take());
+    }
+}
+
+impl fmt::Debug for CapturedChat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapturedChat")
+            .field("lines", &self.lines())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::eq::Channel;
+    use crate::mq::{console, write_chat, write_chat_color};
+
+    #[test]
+    fn test_captures_chat() {
+        let chat = CapturedChat::new();
+
+        write_chat("one");
+        write_chat_color("two", Channel::Tell);
+
+        assert_eq!(chat.lines(), ["one", "two"]);
+        assert_eq!(
+            chat.lines_with_color(),
+            [
+                ("one".to_string(), ChatColor::default()),
+                ("two".to_string(), ChatColor::from(Channel::Tell))
+            ]
+        );
+
+        chat.clear();
+        assert!(chat.lines().is_empty());
+    }
+
+    #[test]
+    fn test_nested_captures_restore_previous() {
+        let outer = CapturedChat::new();
+        write_chat("outer");
+
+        {
+            let inner = CapturedChat::new();
+            write_chat("inner");
+            assert_eq!(inner.lines(), ["inner"]);
+        }
+
+        write_chat("outer again");
+        assert_eq!(outer.lines(), ["outer", "outer again"]);
+    }
+
+    #[test]
+    fn test_captures_from_other_threads() {
+        let chat = CapturedChat::new();
+
+        std::thread::spawn(|| write_chat("from a thread"))
+            .join()
+            .unwrap();
+
+        assert_eq!(chat.lines(), ["from a thread"]);
+    }
+
+    #[test]
+    fn test_captures_console() {
+        let chat = CapturedChat::new();
+
+        writeln!(console(), "from the console").unwrap();
+
+        assert_eq!(chat.lines(), ["from the console"]);
+    }
+}
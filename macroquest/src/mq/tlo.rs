@@ -0,0 +1,367 @@
+//! Reading MacroQuest's top level objects (TLOs) as typed values.
+//!
+//! [`mq::tlo()`](crate::mq::tlo()) evaluates a TLO expression, such as
+//! `Me.PctHPs`, the same way that a macro would evaluate `${Me.PctHPs}`, and
+//! parses the result into any type that implements [`FromMacroData`].
+//!
+//! ```no_run
+//! use macroquest::mq;
+//!
+//! let hps: i64 = mq::tlo("Me.PctHPs").unwrap();
+//! let target: Option<String> = mq::tlo("Target.CleanName").unwrap();
+//! ```
+//!
+//! MacroQuest evaluates anything that doesn't exist (such as `Target.ID` with
+//! nothing targeted) to `NULL`, which is [`None`] for an [`Option`], and an
+//! error for anything else. The most used queries also have functions of their
+//! own in here, such as [`target_id()`].
+
+use std::{error, fmt};
+
+use crate::mq;
+
+/// The id of the current target.
+pub const TARGET_ID: &str = "Target.ID";
+
+/// The current hit points of the character, as a percentage.
+pub const ME_PCT_HPS: &str = "Me.PctHPs";
+
+/// A type that can be parsed from the result of a TLO expression.
+pub trait FromMacroData: Sized {
+    /// Parse the value from `data`, returning `None` if it isn't valid.
+    fn from_macro_data(data: &str) -> Option<Self>;
+
+    /// The value to use when the expression is `NULL`, if there is one.
+    ///
+    /// The default is `None`, which makes `NULL` an error.
+    #[must_use]
+    fn from_null() -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! from_macro_data_from_str {
+    ($($ty:ty),+) => {
+        $(
+            impl FromMacroData for $ty {
+                fn from_macro_data(data: &str) -> Option<Self> {
+                    data.trim().parse().ok()
+                }
+            }
+        )+
+    };
+}
+
+from_macro_data_from_str!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl FromMacroData for bool {
+    fn from_macro_data(data: &str) -> Option<Self> {
+        let data = data.trim();
+
+        if data.eq_ignore_ascii_case("true") {
+            Some(true)
+        }
+        else if data.eq_ignore_ascii_case("false") {
+            Some(false)
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl FromMacroData for String {
+    fn from_macro_data(data: &str) -> Option<Self> {
+        Some(data.to_owned())
+    }
+}
+
+impl<T: FromMacroData> FromMacroData for Option<T> {
+    fn from_macro_data(data: &str) -> Option<Self> {
+        T::from_macro_data(data).map(Some)
+    }
+
+    fn from_null() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// An error reading a TLO with [`mq::tlo()`](crate::mq::tlo()), or evaluating
+/// a macro data expression with [`mq::eval()`](crate::mq::eval()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TloError {
+    /// There's no character in game, so there's nothing to read.
+    NotInGame,
+    /// MacroQuest couldn't parse the expression.
+    Malformed {
+        /// The expression, as it was given.
+        expression: String,
+    },
+    /// Part of the expression was left unevaluated, such as a `${}` that
+    /// MacroQuest didn't recognize.
+    Unresolved {
+        /// The expression, as it was given.
+        expression: String,
+        /// What the expression evaluated to.
+        value:      String,
+    },
+    /// The expression evaluated to `NULL`, such as for a TLO or member that
+    /// doesn't exist.
+    Null {
+        /// The expression, as it was given.
+        expression: String,
+    },
+    /// The expression evaluated to something that isn't the type it was read
+    /// as.
+    Invalid {
+        /// The expression, as it was given.
+        expression: String,
+        /// What the expression evaluated to.
+        value:      String,
+    },
+}
+
+impl fmt::Display for TloError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TloError::NotInGame => f.write_str("not in game"),
+            TloError::Malformed { expression } => {
+                write!(f, "malformed expression {expression:?}")
+            }
+            TloError::Unresolved { expression, value } => {
+                write!(f, "{expression} was left unresolved as {value:?}")
+            }
+            TloError::Null { expression } => write!(f, "{expression} is NULL"),
+            TloError::Invalid { expression, value } => {
+                write!(f, "{expression} has an unexpected value {value:?}")
+            }
+        }
+    }
+}
+
+impl error::Error for TloError {}
+
+/// The id of the current target, or `None` if nothing is targeted.
+///
+/// # Errors
+///
+/// Returns an error if there's no character in game.
+pub fn target_id() -> Result<Option<u32>, TloError> {
+    mq::tlo(TARGET_ID)
+}
+
+/// The current hit points of the character, as a percentage.
+///
+/// # Errors
+///
+/// Returns an error if there's no character in game.
+pub fn me_pct_hps() -> Result<i64, TloError> {
+    mq::tlo(ME_PCT_HPS)
+}
+
+/// Evaluate the TLO `expression` with `parse` (which is given the full `${}`
+/// macro data), and parse the result as a `T`.
+pub(crate) fn read<T, P>(
+    expression: &str,
+    in_game: bool,
+    parse: P,
+) -> Result<T, TloError>
+where
+    T: FromMacroData,
+    P: FnOnce(&str) -> Option<String>,
+{
+    if !in_game {
+        return Err(TloError::NotInGame);
+    }
+
+    evaluate(expression, &format!("${{{expression}}}"), parse)
+}
+
+/// Evaluate the macro data `expression` with `parse`, turning anything that
+/// MacroQuest couldn't evaluate into an error.
+pub(crate) fn eval<P>(expression: &str, parse: P) -> Result<String, TloError>
+where
+    P: FnOnce(&str) -> Option<String>,
+{
+    eval_as(expression, parse)
+}
+
+/// Evaluate the macro data `expression` with `parse`, and parse the result as
+/// a `T`.
+pub(crate) fn eval_as<T, P>(expression: &str, parse: P) -> Result<T, TloError>
+where
+    T: FromMacroData,
+    P: FnOnce(&str) -> Option<String>,
+{
+    evaluate(expression, expression, parse)
+}
+
+// Evaluate the macro `data` with `parse`, and parse the result as a `T`, with
+// any error naming `expression` (which `data` was built from).
+fn evaluate<T, P>(expression: &str, data: &str, parse: P) -> Result<T, TloError>
+where
+    T: FromMacroData,
+    P: FnOnce(&str) -> Option<String>,
+{
+    let Some(value) = parse(data)
+    else {
+        return Err(TloError::Malformed {
+            expression: expression.to_owned(),
+        });
+    };
+
+    if value == "NULL" {
+        T::from_null().ok_or_else(|| TloError::Null {
+            expression: expression.to_owned(),
+        })
+    }
+    else if value.contains("${") {
+        Err(TloError::Unresolved {
+            expression: expression.to_owned(),
+            value,
+        })
+    }
+    else {
+        T::from_macro_data(&value).ok_or_else(|| TloError::Invalid {
+            expression: expression.to_owned(),
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Evaluates expressions like MacroQuest would, for a character with
+    // nothing targeted.
+    fn parse(data: &str) -> Option<String> {
+        let value = match data {
+            "${Me.PctHPs}" => "87",
+            "${Me.Height}" => "5.75",
+            "${Me.Combat}" => "FALSE",
+            "${Me.Name}" => "Alice",
+            "${Target.ID}" | "${Target.Name}" | "${Me.Nope}" => "NULL",
+            "${Me.Name} is ${Me.PctHPs}%" => "Alice is 87%",
+            "${Nope.Name}" => "${Nope.Name}",
+            _ => return None,
+        };
+
+        Some(value.to_owned())
+    }
+
+    fn tlo<T: FromMacroData>(expression: &str) -> Result<T, TloError> {
+        read(expression, true, parse)
+    }
+
+    #[test]
+    fn test_read_values() {
+        assert_eq!(tlo::<i64>("Me.PctHPs"), Ok(87));
+        assert_eq!(tlo::<f64>("Me.Height"), Ok(5.75));
+        assert_eq!(tlo::<bool>("Me.Combat"), Ok(false));
+        assert_eq!(tlo::<String>("Me.Name"), Ok("Alice".into()));
+        assert_eq!(tlo::<Option<i64>>("Me.PctHPs"), Ok(Some(87)));
+    }
+
+    #[test]
+    fn test_read_null() {
+        assert_eq!(tlo::<Option<u32>>("Target.ID"), Ok(None));
+        assert_eq!(tlo::<Option<String>>("Target.Name"), Ok(None));
+        assert_eq!(
+            tlo::<u32>("Target.ID"),
+            Err(TloError::Null {
+                expression: "Target.ID".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_errors() {
+        assert_eq!(
+            tlo::<i64>("Me.PctHPs["),
+            Err(TloError::Malformed {
+                expression: "Me.PctHPs[".into(),
+            })
+        );
+        assert_eq!(
+            tlo::<i64>("Me.Name"),
+            Err(TloError::Invalid {
+                expression: "Me.Name".into(),
+                value:      "Alice".into(),
+            })
+        );
+        assert_eq!(
+            tlo::<Option<bool>>("Me.Height"),
+            Err(TloError::Invalid {
+                expression: "Me.Height".into(),
+                value:      "5.75".into(),
+            })
+        );
+        assert_eq!(
+            tlo::<String>("Nope.Name"),
+            Err(TloError::Unresolved {
+                expression: "Nope.Name".into(),
+                value:      "${Nope.Name}".into(),
+            })
+        );
+        assert_eq!(
+            read::<i64, _>("Me.PctHPs", false, parse),
+            Err(TloError::NotInGame)
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(TloError::NotInGame.to_string(), "not in game");
+        assert_eq!(
+            TloError::Null {
+                expression: "Target.ID".into(),
+            }
+            .to_string(),
+            "Target.ID is NULL"
+        );
+    }
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(eval("${Me.Name}", parse), Ok("Alice".into()));
+        assert_eq!(
+            eval("${Me.Name} is ${Me.PctHPs}%", parse),
+            Ok("Alice is 87%".into())
+        );
+        assert_eq!(eval_as::<i64, _>("${Me.PctHPs}", parse), Ok(87));
+        assert_eq!(eval_as::<f64, _>("${Me.Height}", parse), Ok(5.75));
+        assert_eq!(eval_as::<bool, _>("${Me.Combat}", parse), Ok(false));
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        assert_eq!(
+            eval("${Me.PctHPs[", parse),
+            Err(TloError::Malformed {
+                expression: "${Me.PctHPs[".into(),
+            })
+        );
+        assert_eq!(
+            eval("${Me.Nope}", parse),
+            Err(TloError::Null {
+                expression: "${Me.Nope}".into(),
+            })
+        );
+        assert_eq!(
+            eval("${Nope.Name}", parse),
+            Err(TloError::Unresolved {
+                expression: "${Nope.Name}".into(),
+                value:      "${Nope.Name}".into(),
+            })
+        );
+        assert_eq!(
+            eval_as::<i64, _>("${Me.Name}", parse),
+            Err(TloError::Invalid {
+                expression: "${Me.Name}".into(),
+                value:      "Alice".into(),
+            })
+        );
+    }
+}
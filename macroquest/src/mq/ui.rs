@@ -0,0 +1,356 @@
+//! Native EverQuest windows, created from the game's XML UI definitions.
+//!
+//! Unlike ImGui windows, these are real EverQuest windows. They're drawn by
+//! the game, follow the user's UI skin, and are defined by a screen in an XML
+//! file rather than in code.
+//!
+//! The game destroys its UI whenever it's cleaned up (when camping to
+//! character select, or before reloading the UI), so the windows created here
+//! are destroyed along with it and created again once the UI has been
+//! reloaded. Each [`Window`] remembers whether it's visible and the text of
+//! its children, and restores them whenever it's created again.
+
+use std::collections::BTreeMap;
+
+use parking_lot::Mutex;
+
+use crate::ffi::ui as uilib;
+use crate::log::error;
+use crate::macros::debug_assert_main_thread;
+
+static WINDOWS: Mutex<Registry> = Mutex::new(Registry::new());
+
+/// A native EverQuest window, created from a screen defined in an XML file.
+///
+/// The window is destroyed when this is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use macroquest::plugin::{Hooks, Plugin};
+/// # macroquest::plugin::setup!(MyPlugin);
+/// use macroquest::mq::ui::Window;
+///
+/// struct MyPlugin {
+///     window: Window,
+/// }
+///
+/// impl Plugin for MyPlugin {
+///     fn new() -> Self {
+///         let window = Window::create("MQUI_MyPlugin.xml", "MyPluginWnd");
+///         window.set_text("TitleLabel", "Hello from Rust");
+///         window.show();
+///
+///         MyPlugin { window }
+///     }
+/// }
+/// # impl Hooks for MyPlugin {}
+/// ```
+#[derive(Debug)]
+pub struct Window {
+    id: u64,
+}
+
+impl Window {
+    /// Create a window from the screen named `screen`, which is defined in the
+    /// XML file named `xml`.
+    ///
+    /// The XML file is added to the files that MacroQuest loads along with the
+    /// game's UI, which it looks for in its `uifiles` resources directory (see
+    /// [`Paths::resources()`](crate::mq::Paths::resources)). If the UI isn't
+    /// currently loaded, or was loaded before the XML file was added, then the
+    /// window is created the next time the UI is reloaded.
+    ///
+    /// The window starts out hidden, see [`Window::show()`].
+    #[must_use]
+    pub fn create(xml: &str, screen: &str) -> Window {
+        debug_assert_main_thread!("macroquest::mq::ui::Window::create");
+
+        let mut windows = WINDOWS.lock();
+        let (id, first) = windows.insert(State::new(xml, screen));
+
+        if first {
+            uilib::add_xml_file(xml);
+        }
+
+        if let Some(state) = windows.get_mut(id) {
+            state.build();
+        }
+
+        Window { id }
+    }
+
+    /// Show the window.
+    pub fn show(&self) {
+        debug_assert_main_thread!("macroquest::mq::ui::Window::show");
+        self.set_visible(true);
+    }
+
+    /// Hide the window.
+    pub fn hide(&self) {
+        debug_assert_main_thread!("macroquest::mq::ui::Window::hide");
+        self.set_visible(false);
+    }
+
+    /// Whether the window is shown.
+    ///
+    /// This is whether the window should be shown, which is remembered even
+    /// while the window doesn't exist because the UI isn't loaded.
+    #[must_use]
+    pub fn is_visible(&self) -> bool {
+        self.with(|state| state.visible).unwrap_or(false)
+    }
+
+    /// Whether the window currently exists in the game's UI.
+    #[must_use]
+    pub fn is_created(&self) -> bool {
+        self.with(|state| state.native.is_some()).unwrap_or(false)
+    }
+
+    /// Set the text of the child element named `child`, such as a label.
+    ///
+    /// The text is remembered, and set again whenever the window is created
+    /// again after the UI has been reloaded.
+    pub fn set_text(&self, child: &str, text: &str) {
+        debug_assert_main_thread!("macroquest::mq::ui::Window::set_text");
+
+        self.with(|state| {
+            state.text.insert(child.to_owned(), text.to_owned());
+
+            if let Some(native) = state.native {
+                set_child_text(&state.screen, native, child, text);
+            }
+        });
+    }
+
+    fn set_visible(&self, visible: bool) {
+        self.with(|state| {
+            state.visible = visible;
+
+            if let Some(native) = state.native {
+                uilib::set_window_visible(native, visible);
+            }
+        });
+    }
+
+    fn with<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut State) -> R,
+    {
+        WINDOWS.lock().get_mut(self.id).map(f)
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        debug_assert_main_thread!("macroquest::mq::ui::Window::drop");
+
+        let mut windows = WINDOWS.lock();
+        if let Some((mut state, last)) = windows.remove(self.id) {
+            state.destroy();
+
+            if last {
+                uilib::remove_xml_file(&state.xml);
+            }
+        }
+    }
+}
+
+/// Destroy every window, as the game is about to destroy its UI.
+pub(crate) fn clean_ui() {
+    for state in WINDOWS.lock().states_mut() {
+        state.destroy();
+    }
+}
+
+/// Create every window again, now that the game has reloaded its UI.
+pub(crate) fn reload_ui() {
+    for state in WINDOWS.lock().states_mut() {
+        state.build();
+    }
+}
+
+/// Destroy every window and remove their XML files, as the plugin is being
+/// unloaded.
+pub(crate) fn clear() {
+    let mut windows = WINDOWS.lock();
+
+    for state in windows.states_mut() {
+        state.destroy();
+    }
+
+    for xml in windows.clear() {
+        uilib::remove_xml_file(&xml);
+    }
+}
+
+fn set_child_text(screen: &str, native: u64, child: &str, text: &str) {
+    if !uilib::set_window_child_text(native, child, text) {
+        error!(screen, child, "window has no child with that name");
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    xml:     String,
+    screen:  String,
+    // The id of the window on the C++ side, if it currently exists.
+    native:  Option<u64>,
+    visible: bool,
+    text:    BTreeMap<String, String>,
+}
+
+impl State {
+    fn new(xml: &str, screen: &str) -> State {
+        State {
+            xml:     xml.to_owned(),
+            screen:  screen.to_owned(),
+            native:  None,
+            visible: false,
+            text:    BTreeMap::new(),
+        }
+    }
+
+    fn build(&mut self) {
+        if self.native.is_some() {
+            return;
+        }
+
+        let native = uilib::create_window(&self.screen);
+        if native == 0 {
+            return;
+        }
+
+        for (child, text) in &self.text {
+            set_child_text(&self.screen, native, child, text);
+        }
+
+        uilib::set_window_visible(native, self.visible);
+        self.native = Some(native);
+    }
+
+    fn destroy(&mut self) {
+        if let Some(native) = self.native.take() {
+            uilib::destroy_window(native);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Registry {
+    next_id: u64,
+    windows: BTreeMap<u64, State>,
+}
+
+impl Registry {
+    const fn new() -> Registry {
+        Registry {
+            next_id: 1,
+            windows: BTreeMap::new(),
+        }
+    }
+
+    /// Add a window, returning its id and whether it's the only window using
+    /// its XML file.
+    fn insert(&mut self, state: State) -> (u64, bool) {
+        let first = !self.uses_xml(&state.xml);
+        let id = self.next_id;
+
+        self.next_id += 1;
+        self.windows.insert(id, state);
+
+        (id, first)
+    }
+
+    /// Remove a window, returning it and whether it was the last window using
+    /// its XML file.
+    fn remove(&mut self, id: u64) -> Option<(State, bool)> {
+        let state = self.windows.remove(&id)?;
+        let last = !self.uses_xml(&state.xml);
+
+        Some((state, last))
+    }
+
+    /// Remove every window, returning the XML files that they used.
+    fn clear(&mut self) -> Vec<String> {
+        let mut xml: Vec<String> = std::mem::take(&mut self.windows)
+            .into_values()
+            .map(|state| state.xml)
+            .collect();
+
+        xml.sort();
+        xml.dedup();
+        xml
+    }
+
+    fn get_mut(&mut self, id: u64) -> Option<&mut State> {
+        self.windows.get_mut(&id)
+    }
+
+    fn states_mut(&mut self) -> impl Iterator<Item = &mut State> {
+        self.windows.values_mut()
+    }
+
+    fn uses_xml(&self, xml: &str) -> bool {
+        self.windows.values().any(|state| state.xml == xml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_insert() {
+        let mut registry = Registry::new();
+
+        let (first, added) = registry.insert(State::new("a.xml", "One"));
+        assert!(added);
+
+        let (second, added) = registry.insert(State::new("a.xml", "Two"));
+        assert!(!added);
+        assert_ne!(first, second);
+
+        let (_, added) = registry.insert(State::new("b.xml", "Three"));
+        assert!(added);
+    }
+
+    #[test]
+    fn test_registry_remove() {
+        let mut registry = Registry::new();
+        let (first, _) = registry.insert(State::new("a.xml", "One"));
+        let (second, _) = registry.insert(State::new("a.xml", "Two"));
+
+        let (state, last) = registry.remove(first).unwrap();
+        assert_eq!(state.screen, "One");
+        assert!(!last);
+
+        let (state, last) = registry.remove(second).unwrap();
+        assert_eq!(state.screen, "Two");
+        assert!(last);
+
+        assert!(registry.remove(second).is_none());
+    }
+
+    #[test]
+    fn test_registry_clear() {
+        let mut registry = Registry::new();
+        registry.insert(State::new("b.xml", "One"));
+        registry.insert(State::new("a.xml", "Two"));
+        let (id, _) = registry.insert(State::new("b.xml", "Three"));
+
+        assert_eq!(registry.clear(), vec!["a.xml", "b.xml"]);
+        assert!(registry.get_mut(id).is_none());
+        assert_eq!(registry.states_mut().count(), 0);
+    }
+
+    #[test]
+    fn test_registry_ids_not_reused() {
+        let mut registry = Registry::new();
+        let (first, _) = registry.insert(State::new("a.xml", "One"));
+        registry.remove(first);
+
+        let (second, _) = registry.insert(State::new("a.xml", "One"));
+        assert_ne!(first, second);
+    }
+}
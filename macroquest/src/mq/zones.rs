@@ -0,0 +1,156 @@
+//! Looking up zones in the game's zone table, by id or by short name.
+//!
+//! The zone table covers every zone in the game, not just the one that we're
+//! in, so this can resolve a short name like `poknowledge` to its id (202) and
+//! its long name (`The Plane of Knowledge`) from anywhere.
+//!
+//! The table doesn't change while the game is running, so it's copied out of
+//! the game the first time that it's used, which must be on the game's main
+//! thread. After that, it can be used from any thread. The table isn't
+//! available until the game has loaded its world data, and until then every
+//! lookup finds nothing.
+
+use once_cell::sync::OnceCell;
+
+use crate::ffi::eqlib;
+use crate::macros::debug_assert_main_thread;
+
+static TABLE: OnceCell<Table> = OnceCell::new();
+
+/// An entry in the zone table.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ZoneEntry {
+    /// The id of the zone.
+    pub id:         u32,
+    /// The short name of the zone (ex: `poknowledge`).
+    pub short_name: String,
+    /// The long name of the zone (ex: `The Plane of Knowledge`).
+    pub long_name:  String,
+}
+
+/// Look up the zone with the id `id`.
+#[must_use]
+pub fn by_id(id: u32) -> Option<&'static ZoneEntry> {
+    table()?.by_id(id)
+}
+
+/// Look up the zone with the short name `short_name`, ignoring case.
+#[must_use]
+pub fn by_short_name(short_name: &str) -> Option<&'static ZoneEntry> {
+    table()?.by_short_name(short_name)
+}
+
+/// The short name of the zone with the id `id`.
+#[must_use]
+pub fn zone_id_to_short_name(id: u32) -> Option<&'static str> {
+    by_id(id).map(|zone| zone.short_name.as_str())
+}
+
+/// Iterate over every zone in the zone table, in id order.
+pub fn iter() -> impl Iterator<Item = &'static ZoneEntry> {
+    table().into_iter().flat_map(|table| table.zones.iter())
+}
+
+fn table() -> Option<&'static Table> {
+    TABLE
+        .get_or_try_init(|| {
+            debug_assert_main_thread!("macroquest::mq::zones");
+
+            Table::load().ok_or(())
+        })
+        .ok()
+}
+
+struct Table {
+    // Sorted by id.
+    zones: Vec<ZoneEntry>,
+}
+
+impl Table {
+    fn new(mut zones: Vec<ZoneEntry>) -> Table {
+        zones.sort_by_key(|zone| zone.id);
+        zones.dedup_by_key(|zone| zone.id);
+
+        Table { zones }
+    }
+
+    /// Copy the zone table out of the game, returning `None` if it isn't
+    /// available yet.
+    fn load() -> Option<Table> {
+        let mut zones = vec![];
+        for index in 0..eqlib::zone_count() {
+            let mut info = eqlib::ZoneInfo::default();
+            if eqlib::get_zone_info(index, &mut info) {
+                zones.push(ZoneEntry {
+                    id:         info.id,
+                    short_name: info.short_name,
+                    long_name:  info.long_name,
+                });
+            }
+        }
+
+        (!zones.is_empty()).then(|| Table::new(zones))
+    }
+
+    fn by_id(&self, id: u32) -> Option<&ZoneEntry> {
+        self.zones
+            .binary_search_by_key(&id, |zone| zone.id)
+            .ok()
+            .map(|index| &self.zones[index])
+    }
+
+    fn by_short_name(&self, short_name: &str) -> Option<&ZoneEntry> {
+        self.zones
+            .iter()
+            .find(|zone| zone.short_name.eq_ignore_ascii_case(short_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(id: u32, short_name: &str, long_name: &str) -> ZoneEntry {
+        ZoneEntry {
+            id,
+            short_name: short_name.to_owned(),
+            long_name: long_name.to_owned(),
+        }
+    }
+
+    fn table() -> Table {
+        Table::new(vec![
+            zone(202, "poknowledge", "The Plane of Knowledge"),
+            zone(1, "qeynos", "South Qeynos"),
+            zone(344, "guildlobby", "The Guild Lobby"),
+            zone(1, "qeynos", "South Qeynos"),
+        ])
+    }
+
+    #[test]
+    fn test_by_id() {
+        let table = table();
+
+        assert_eq!(
+            table.by_id(202).unwrap().long_name,
+            "The Plane of Knowledge"
+        );
+        assert_eq!(table.by_id(1).unwrap().short_name, "qeynos");
+        assert_eq!(table.by_id(2), None);
+    }
+
+    #[test]
+    fn test_by_short_name() {
+        let table = table();
+
+        assert_eq!(table.by_short_name("poknowledge").unwrap().id, 202);
+        assert_eq!(table.by_short_name("PoKnowledge").unwrap().id, 202);
+        assert_eq!(table.by_short_name("pok"), None);
+    }
+
+    #[test]
+    fn test_sorted() {
+        let ids: Vec<u32> = table().zones.iter().map(|zone| zone.id).collect();
+        assert_eq!(ids, vec![1, 202, 344]);
+    }
+}
@@ -0,0 +1,60 @@
+//! Reporting the time spent in the plugin's hooks to MacroQuest.
+//!
+//! With the `benchmark` feature enabled, the plugin registers a benchmark with
+//! MacroQuest named after itself, which the time spent in its pulse and ImGui
+//! hooks is recorded through, so that the `/benchmark` command shows it along
+//! with every other plugin.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::ffi::mq as mqlib;
+
+// MacroQuest's handle for the plugin's benchmark, while it's registered.
+static BENCHMARK: AtomicU32 = AtomicU32::new(NONE);
+
+// MacroQuest's handles are indexes, so this is used for no handle instead of 0.
+const NONE: u32 = u32::MAX;
+
+/// Register the plugin's benchmark, named `name`.
+pub(crate) fn register(name: &str) {
+    let id = mqlib::add_benchmark(name);
+    let previous = BENCHMARK.swap(id, Ordering::Relaxed);
+
+    if previous != NONE {
+        mqlib::remove_benchmark(previous);
+    }
+}
+
+/// Remove the plugin's benchmark, if it was registered.
+pub(crate) fn unregister() {
+    let id = BENCHMARK.swap(NONE, Ordering::Relaxed);
+
+    if id != NONE {
+        mqlib::remove_benchmark(id);
+    }
+}
+
+/// Call `f`, recording the time it took in the plugin's benchmark.
+pub(crate) fn measure<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    // The benchmark is left even if `f` panics, as MacroQuest would otherwise
+    // count every frame from then on as time spent in the plugin.
+    struct Exit(u32);
+
+    impl Drop for Exit {
+        fn drop(&mut self) {
+            mqlib::exit_benchmark(self.0);
+        }
+    }
+
+    let id = BENCHMARK.load(Ordering::Relaxed);
+    if id == NONE {
+        return f();
+    }
+
+    mqlib::enter_benchmark(id);
+    let _exit = Exit(id);
+    f()
+}
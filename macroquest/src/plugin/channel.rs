@@ -0,0 +1,550 @@
+//! A bounded channel for handing work off from hooks to worker threads.
+//!
+//! Hooks are called on the game's main thread, and any time spent inside of
+//! them is time that the game isn't rendering a frame, so plugins that need to
+//! do any real work will typically want to hand that work off to a worker
+//! thread. The naive approach of using an unbounded channel means that if the
+//! worker ever stalls, the channel will grow without limit, so instead this
+//! module provides a bounded channel whose sending half never blocks, and which
+//! applies an [`OverflowPolicy`] whenever the channel is full.
+//!
+//! The sending half of every channel created by [`worker_channel`] is
+//! automatically disconnected when the plugin begins shutting down (prior to
+//! [`Hooks::shutdown()`](crate::plugin::Hooks::shutdown) being called), so any
+//! worker looping over the [`Receiver`] will exit once it has drained the
+//! remaining items.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use parking_lot::{Condvar, Mutex};
+
+static CHANNELS: Registry = Registry::new();
+
+/// What to do with an item sent into a full [`worker_channel`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OverflowPolicy {
+    /// Silently discard the item that is being sent.
+    DropNewest,
+    /// Silently discard the oldest item in the channel to make room for the
+    /// item that is being sent.
+    DropOldest,
+    /// Discard the item that is being sent, incrementing a counter that can
+    /// be read with [`HookSender::dropped()`].
+    CountAndDrop,
+}
+
+/// Creates a new bounded channel, returning the sending and receiving halves.
+///
+/// The [`HookSender`] will never block, when the channel already contains
+/// `capacity` items, the given [`OverflowPolicy`] decides which item gets
+/// dropped.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use macroquest::plugin::{worker_channel, OverflowPolicy};
+///
+/// let (tx, rx) = worker_channel::<String>(128, OverflowPolicy::CountAndDrop);
+///
+/// std::thread::spawn(move || {
+///     for line in &rx {
+///         // Do something slow with line.
+///     }
+/// });
+///
+/// tx.send("some work".to_string()).unwrap();
+/// ```
+#[must_use]
+pub fn worker_channel<T>(
+    capacity: usize,
+    policy: OverflowPolicy,
+) -> (HookSender<T>, Receiver<T>)
+where
+    T: Send + 'static,
+{
+    channel_in(&CHANNELS, capacity, policy)
+}
+
+/// Disconnect every channel created by [`worker_channel`].
+pub(crate) fn disconnect_all() {
+    CHANNELS.disconnect_all();
+}
+
+fn channel_in<T>(
+    registry: &Registry,
+    capacity: usize,
+    policy: OverflowPolicy,
+) -> (HookSender<T>, Receiver<T>)
+where
+    T: Send + 'static,
+{
+    assert!(capacity > 0, "worker_channel capacity must be non-zero");
+
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue:        VecDeque::with_capacity(capacity),
+            senders:      1,
+            receiver:     true,
+            disconnected: false,
+        }),
+        available: Condvar::new(),
+        dropped: AtomicU64::new(0),
+        capacity,
+        policy,
+    });
+
+    registry.register(Arc::downgrade(&shared) as Weak<dyn Disconnect>);
+
+    (
+        HookSender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+trait Disconnect: Send + Sync {
+    fn disconnect(&self);
+}
+
+struct Registry(Mutex<Vec<Weak<dyn Disconnect>>>);
+
+impl Registry {
+    const fn new() -> Self {
+        Registry(Mutex::new(Vec::new()))
+    }
+
+    fn register(&self, channel: Weak<dyn Disconnect>) {
+        let mut channels = self.0.lock();
+
+        // Prune any channels that have been completely dropped, so that we
+        // don't accumulate dead entries in a long running plugin.
+        channels.retain(|c| c.strong_count() > 0);
+        channels.push(channel);
+    }
+
+    fn disconnect_all(&self) {
+        for channel in self.0.lock().drain(..) {
+            if let Some(channel) = channel.upgrade() {
+                channel.disconnect();
+            }
+        }
+    }
+}
+
+struct State<T> {
+    queue:        VecDeque<T>,
+    senders:      usize,
+    receiver:     bool,
+    disconnected: bool,
+}
+
+impl<T> State<T> {
+    fn is_closed(&self) -> bool {
+        self.disconnected || self.senders == 0
+    }
+}
+
+struct Shared<T> {
+    state:     Mutex<State<T>>,
+    available: Condvar,
+    dropped:   AtomicU64,
+    capacity:  usize,
+    policy:    OverflowPolicy,
+}
+
+impl<T: Send> Disconnect for Shared<T> {
+    fn disconnect(&self) {
+        self.state.lock().disconnected = true;
+        self.available.notify_all();
+    }
+}
+
+/// The sending half of a [`worker_channel`].
+///
+/// Sending never blocks, which makes this safe to use from within a hook. It
+/// may be cloned to send from multiple places.
+pub struct HookSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> HookSender<T> {
+    /// Send an item into the channel, applying the channel's
+    /// [`OverflowPolicy`] if it is already full.
+    ///
+    /// # Errors
+    ///
+    /// Returns the item back inside of a [`SendError`] if the channel has
+    /// been disconnected, either because the [`Receiver`] was dropped or the
+    /// plugin is shutting down.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.state.lock();
+
+        if state.disconnected || !state.receiver {
+            return Err(SendError(value));
+        }
+
+        let mut evicted = None;
+        if state.queue.len() >= self.shared.capacity {
+            match self.shared.policy {
+                OverflowPolicy::DropNewest => return Ok(()),
+                OverflowPolicy::DropOldest => evicted = state.queue.pop_front(),
+                OverflowPolicy::CountAndDrop => {
+                    self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+            }
+        }
+
+        state.queue.push_back(value);
+        drop(state);
+
+        // We don't want to run the destructor for an evicted item while we're
+        // holding the lock.
+        drop(evicted);
+
+        self.shared.available.notify_one();
+
+        Ok(())
+    }
+
+    /// The number of items that have been dropped because the channel was
+    /// full.
+    ///
+    /// This is only counted for [`OverflowPolicy::CountAndDrop`], and will
+    /// always be zero for any other policy.
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// The maximum number of items that the channel can hold.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Whether the channel has been disconnected, and sending will fail.
+    #[must_use]
+    pub fn is_disconnected(&self) -> bool {
+        let state = self.shared.state.lock();
+        state.disconnected || !state.receiver
+    }
+}
+
+impl<T> Clone for HookSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().senders += 1;
+
+        HookSender {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for HookSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock();
+        state.senders -= 1;
+
+        if state.senders == 0 {
+            drop(state);
+            self.shared.available.notify_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for HookSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HookSender")
+            .field("capacity", &self.shared.capacity)
+            .field("policy", &self.shared.policy)
+            .field("dropped", &self.dropped())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of a [`worker_channel`].
+///
+/// Any items remaining in the channel once it has been disconnected are still
+/// delivered, and only then will receiving return an error.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Block until an item is available, returning it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RecvError`] once the channel is both disconnected and
+    /// empty.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.shared.state.lock();
+
+        loop {
+            if let Some(value) = state.queue.pop_front() {
+                return Ok(value);
+            }
+
+            if state.is_closed() {
+                return Err(RecvError);
+            }
+
+            self.shared.available.wait(&mut state);
+        }
+    }
+
+    /// Block until an item is available or the timeout has elapsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvTimeoutError::Timeout`] if no item became available in
+    /// time, or [`RecvTimeoutError::Disconnected`] once the channel is both
+    /// disconnected and empty.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.shared.state.lock();
+
+        loop {
+            if let Some(value) = state.queue.pop_front() {
+                return Ok(value);
+            }
+
+            if state.is_closed() {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+
+            if self
+                .shared
+                .available
+                .wait_until(&mut state, deadline)
+                .timed_out()
+            {
+                return state.queue.pop_front().ok_or(RecvTimeoutError::Timeout);
+            }
+        }
+    }
+
+    /// Return an item if one is available, without blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryRecvError::Empty`] if there is no item available, or
+    /// [`TryRecvError::Disconnected`] once the channel is both disconnected
+    /// and empty.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.state.lock();
+
+        match state.queue.pop_front() {
+            Some(value) => Ok(value),
+            None if state.is_closed() => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Returns an iterator that blocks waiting for items, ending once the
+    /// channel is disconnected and empty.
+    #[must_use]
+    pub fn iter(&self) -> RecvIter<'_, T> {
+        RecvIter { receiver: self }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock();
+        state.receiver = false;
+
+        let remaining = std::mem::take(&mut state.queue);
+        drop(state);
+        drop(remaining);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("capacity", &self.shared.capacity)
+            .field("policy", &self.shared.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type IntoIter = RecvIter<'a, T>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A blocking iterator over the items of a [`Receiver`].
+///
+/// Created by [`Receiver::iter()`].
+#[derive(Debug)]
+pub struct RecvIter<'a, T> {
+    receiver: &'a Receiver<T>,
+}
+
+impl<T> Iterator for RecvIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    fn channel<T: Send + 'static>(
+        capacity: usize,
+        policy: OverflowPolicy,
+    ) -> (Registry, HookSender<T>, Receiver<T>) {
+        let registry = Registry::new();
+        let (tx, rx) = channel_in(&registry, capacity, policy);
+
+        (registry, tx, rx)
+    }
+
+    fn drain<T>(rx: &Receiver<T>) -> Vec<T> {
+        std::iter::from_fn(|| rx.try_recv().ok()).collect()
+    }
+
+    #[test]
+    fn test_drop_newest() {
+        let (_registry, tx, rx) = channel(2, OverflowPolicy::DropNewest);
+
+        for i in 0..5 {
+            tx.send(i).unwrap();
+        }
+
+        assert_eq!(drain(&rx), [0, 1]);
+        assert_eq!(tx.dropped(), 0);
+    }
+
+    #[test]
+    fn test_drop_oldest() {
+        let (_registry, tx, rx) = channel(2, OverflowPolicy::DropOldest);
+
+        for i in 0..5 {
+            tx.send(i).unwrap();
+        }
+
+        assert_eq!(drain(&rx), [3, 4]);
+        assert_eq!(tx.dropped(), 0);
+    }
+
+    #[test]
+    fn test_count_and_drop() {
+        let (_registry, tx, rx) = channel(2, OverflowPolicy::CountAndDrop);
+
+        for i in 0..5 {
+            tx.send(i).unwrap();
+        }
+
+        assert_eq!(drain(&rx), [0, 1]);
+        assert_eq!(tx.dropped(), 3);
+
+        // Once there is space again, items are accepted again.
+        tx.send(5).unwrap();
+        assert_eq!(drain(&rx), [5]);
+        assert_eq!(tx.dropped(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-zero")]
+    fn test_zero_capacity() {
+        let _ = channel::<i32>(0, OverflowPolicy::DropNewest);
+    }
+
+    #[test]
+    fn test_recv_timeout() {
+        let (_registry, tx, rx) = channel::<i32>(1, OverflowPolicy::DropNewest);
+
+        assert_eq!(
+            rx.recv_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Timeout)
+        );
+
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Ok(1));
+    }
+
+    #[test]
+    fn test_dropping_receiver_fails_send() {
+        let (_registry, tx, rx) = channel(1, OverflowPolicy::DropNewest);
+
+        drop(rx);
+
+        assert!(tx.is_disconnected());
+        assert_eq!(tx.send(1), Err(SendError(1)));
+    }
+
+    #[test]
+    fn test_dropping_senders_ends_iteration() {
+        let (_registry, tx, rx) = channel(4, OverflowPolicy::DropNewest);
+        let tx2 = tx.clone();
+
+        tx.send(1).unwrap();
+        tx2.send(2).unwrap();
+        drop(tx);
+        drop(tx2);
+
+        assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn test_shutdown_disconnects_senders() {
+        let (registry, tx, rx) = channel(4, OverflowPolicy::DropNewest);
+
+        tx.send(1).unwrap();
+
+        let worker = thread::spawn(move || rx.iter().collect::<Vec<_>>());
+
+        registry.disconnect_all();
+
+        // The worker still gets any items that were queued prior to shutdown,
+        // but exits rather than blocking forever.
+        assert_eq!(worker.join().unwrap(), [1]);
+        assert!(tx.is_disconnected());
+        assert_eq!(tx.send(2), Err(SendError(2)));
+    }
+
+    #[test]
+    fn test_shutdown_wakes_blocked_receiver() {
+        let (registry, tx, rx) = channel::<i32>(4, OverflowPolicy::DropNewest);
+
+        let worker = thread::spawn(move || rx.recv());
+
+        thread::sleep(Duration::from_millis(20));
+        registry.disconnect_all();
+
+        assert_eq!(worker.join().unwrap(), Err(RecvError));
+        drop(tx);
+    }
+
+    #[test]
+    fn test_registry_prunes_dropped_channels() {
+        let registry = Registry::new();
+
+        drop(channel_in::<i32>(&registry, 1, OverflowPolicy::DropNewest));
+        let _live = channel_in::<i32>(&registry, 1, OverflowPolicy::DropNewest);
+
+        assert_eq!(registry.0.lock().len(), 1);
+    }
+}
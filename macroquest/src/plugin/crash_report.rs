@@ -0,0 +1,466 @@
+//! Writing a report to MacroQuest's crash dumps directory whenever the plugin
+//! panics.
+//!
+//! Once [installed](install()), every panic in the plugin (on any thread)
+//! writes a report to the crash dumps directory (see
+//! [`Paths::crash_dumps()`](crate::mq::Paths::crash_dumps)), and tells the
+//! user where to find it in chat. Each report includes the plugin's name and
+//! version, the version of EverQuest that it was built for, the panic message
+//! and where it happened, a backtrace, and (with the `logger` feature) the most
+//! recent lines from a [`LogBuffer`](crate::log::LogBuffer).
+//!
+//! ```no_run
+//! use macroquest::plugin::crash_report::{self, CrashReporter};
+//!
+//! crash_report::install(
+//!     CrashReporter::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")).keep(5),
+//! );
+//! ```
+//!
+//! Writing a report is best effort, and anything that goes wrong while writing
+//! it is ignored, so that reporting a panic never causes another one. Only the
+//! most recent reports for each plugin are kept, and older ones are removed
+//! whenever a new one is written.
+//!
+//! Only panics are reported. Crashes that aren't panics (such as an access
+//! violation) are left to MacroQuest's own crash handler.
+
+use std::backtrace::Backtrace;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fmt, fs, io};
+
+#[cfg(feature = "logger")]
+use crate::log::LogBuffer;
+use crate::mq;
+use crate::plugin::panic_message;
+
+// The extension of every report, which is also how they're told apart from
+// the other files in the crash dumps directory when rotating them.
+const EXTENSION: &str = "panic.txt";
+
+// The length of the timestamp in the name of a report (ex:
+// 20060102-150405-000).
+const TIMESTAMP_LEN: usize = 19;
+
+/// How crash reports are written, given to [`install()`].
+#[derive(Debug, Clone)]
+pub struct CrashReporter {
+    plugin:    String,
+    version:   String,
+    directory: Option<PathBuf>,
+    keep:      usize,
+    #[cfg(feature = "logger")]
+    log:       Option<LogBuffer>,
+}
+
+impl CrashReporter {
+    /// The number of reports that are kept for each plugin by default.
+    pub const DEFAULT_KEEP: usize = 10;
+
+    /// Write reports for the plugin named `plugin`, at version `version`.
+    ///
+    /// These are normally `env!("CARGO_PKG_NAME")` and
+    /// `env!("CARGO_PKG_VERSION")`, from the plugin's own crate.
+    #[must_use]
+    pub fn new(plugin: &str, version: &str) -> CrashReporter {
+        CrashReporter {
+            plugin: plugin.to_owned(),
+            version: version.to_owned(),
+            directory: None,
+            keep: CrashReporter::DEFAULT_KEEP,
+            #[cfg(feature = "logger")]
+            log: None,
+        }
+    }
+
+    /// Keep at most `keep` reports for this plugin, removing the oldest ones.
+    #[must_use]
+    pub fn keep(mut self, keep: usize) -> CrashReporter {
+        self.keep = keep.max(1);
+        self
+    }
+
+    /// Write reports to `directory`, rather than the crash dumps directory.
+    #[must_use]
+    pub fn directory<P: Into<PathBuf>>(mut self, directory: P) -> CrashReporter {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Include the lines in `buffer` in each report.
+    #[cfg_attr(docsrs, doc(cfg(feature = "logger")))]
+    #[cfg(feature = "logger")]
+    #[must_use]
+    pub fn log_buffer(mut self, buffer: LogBuffer) -> CrashReporter {
+        self.log = Some(buffer);
+        self
+    }
+
+    fn report(&self, info: &PanicHookInfo<'_>) -> Report {
+        let eq_version = crate::eq_version();
+        let thread = std::thread::current();
+
+        Report {
+            plugin:     self.plugin.clone(),
+            version:    self.version.clone(),
+            eq_version: format!(
+                "{} {}",
+                eq_version.build_date(),
+                eq_version.build_time()
+            ),
+            time:       SystemTime::now(),
+            thread:     thread.name().unwrap_or("<unnamed>").to_owned(),
+            location:   info.location().map(ToString::to_string),
+            message:    panic_message(info.payload()).to_owned(),
+            backtrace:  Backtrace::force_capture().to_string(),
+            log:        self.recent_log(),
+        }
+    }
+
+    #[cfg(feature = "logger")]
+    fn recent_log(&self) -> Vec<String> {
+        self.log.as_ref().map_or_else(Vec::new, |buffer| {
+            buffer
+                .lines()
+                .iter()
+                .map(|line| format!("{} {}", line.level(), line.text()))
+                .collect()
+        })
+    }
+
+    #[cfg(not(feature = "logger"))]
+    #[allow(clippy::unused_self)]
+    fn recent_log(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Write a crash report whenever the plugin panics, as configured by
+/// `reporter`.
+///
+/// The panic hook that was installed before (such as the default one, which
+/// prints the panic to stderr) is still called after the report is written.
+/// Installing again replaces the previous reporter.
+///
+/// This should be called once the plugin has been initialized, as the crash
+/// dumps directory isn't known before then.
+pub fn install(reporter: CrashReporter) {
+    let directory = reporter
+        .directory
+        .clone()
+        .unwrap_or_else(|| mq::paths().crash_dumps().to_owned());
+
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // Nothing in here is allowed to panic, as a panic while panicking
+        // aborts the game.
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+            let report = reporter.report(info);
+            if let Ok(path) = write(&directory, &report, reporter.keep) {
+                mq::write_chat(format!(
+                    "[{}] panicked, a crash report was written to {}",
+                    report.plugin,
+                    path.display()
+                ));
+            }
+        }));
+
+        previous(info);
+    }));
+}
+
+/// A single crash report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Report {
+    plugin:     String,
+    version:    String,
+    eq_version: String,
+    time:       SystemTime,
+    thread:     String,
+    location:   Option<String>,
+    message:    String,
+    backtrace:  String,
+    log:        Vec<String>,
+}
+
+impl Report {
+    /// The name of the file that the report is written to, which sorts in the
+    /// order that reports were written.
+    fn file_name(&self) -> String {
+        let time = Timestamp::from(self.time);
+
+        format!(
+            "{}-{:04}{:02}{:02}-{:02}{:02}{:02}-{:03}.{EXTENSION}",
+            self.plugin,
+            time.year,
+            time.month,
+            time.day,
+            time.hour,
+            time.minute,
+            time.second,
+            time.millis
+        )
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Plugin:     {} {}", self.plugin, self.version)?;
+        writeln!(f, "EQVersion:  {}", self.eq_version)?;
+        writeln!(f, "Time:       {}", Timestamp::from(self.time))?;
+        writeln!(f, "Thread:     {}", self.thread)?;
+        writeln!(
+            f,
+            "Location:   {}",
+            self.location.as_deref().unwrap_or("<unknown>")
+        )?;
+        writeln!(f, "Message:    {}", self.message)?;
+
+        writeln!(f)?;
+        writeln!(f, "Backtrace:")?;
+        writeln!(f, "{}", self.backtrace.trim_end())?;
+
+        if !self.log.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Recent log:")?;
+            for line in &self.log {
+                writeln!(f, "{line}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Write `report` to `directory`, and then remove all but the `keep` most
+/// recent reports for the same plugin, returning the path it was written to.
+fn write(directory: &Path, report: &Report, keep: usize) -> io::Result<PathBuf> {
+    fs::create_dir_all(directory)?;
+
+    let path = directory.join(report.file_name());
+    fs::write(&path, report.to_string())?;
+
+    // The report has been written even if the old ones can't be removed.
+    let _ = rotate(directory, &report.plugin, keep);
+
+    Ok(path)
+}
+
+/// Remove all but the `keep` most recent reports for `plugin` in `directory`.
+fn rotate(directory: &Path, plugin: &str, keep: usize) -> io::Result<()> {
+    let prefix = format!("{plugin}-");
+    let suffix = format!(".{EXTENSION}");
+
+    let mut reports: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.len() == prefix.len() + TIMESTAMP_LEN + suffix.len()
+                        && name.starts_with(&prefix)
+                        && name.ends_with(&suffix)
+                })
+        })
+        .collect();
+
+    // The names sort in the order that the reports were written, so the
+    // oldest ones come first.
+    reports.sort();
+
+    let excess = reports.len().saturating_sub(keep);
+    for path in &reports[..excess] {
+        let _ = fs::remove_file(path);
+    }
+
+    Ok(())
+}
+
+/// A time in UTC, broken down into its parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Timestamp {
+    year:   i64,
+    month:  u32,
+    day:    u32,
+    hour:   u32,
+    minute: u32,
+    second: u32,
+    millis: u32,
+}
+
+impl From<SystemTime> for Timestamp {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn from(time: SystemTime) -> Self {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let secs = since_epoch.as_secs();
+        let days = (secs / 86400) as i64;
+        let secs_of_day = (secs % 86400) as u32;
+
+        // Converts days since the epoch into a civil date, from Howard
+        // Hinnant's days_from_civil algorithm (run in reverse).
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = u32::try_from(doy - (153 * mp + 2) / 5 + 1).unwrap_or(1);
+        let month = u32::try_from(if mp < 10 { mp + 3 } else { mp - 9 }).unwrap_or(1);
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Timestamp {
+            year,
+            month,
+            day,
+            hour: secs_of_day / 3600,
+            minute: secs_of_day % 3600 / 60,
+            second: secs_of_day % 60,
+            millis: since_epoch.subsec_millis(),
+        }
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03} UTC",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            self.millis
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn report(plugin: &str, secs: u64) -> Report {
+        Report {
+            plugin:     plugin.to_owned(),
+            version:    "0.1.7".to_owned(),
+            eq_version: "Jan 02 2006 15:04:05".to_owned(),
+            time:       UNIX_EPOCH + Duration::from_secs(secs),
+            thread:     "main".to_owned(),
+            location:   Some("src/lib.rs:10:5".to_owned()),
+            message:    "oh no".to_owned(),
+            backtrace:  "   0: MQRustBasic::pulse\n".to_owned(),
+            log:        vec!["INFO pulsing".to_owned()],
+        }
+    }
+
+    // A directory of our own in the temp directory, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let path = std::env::temp_dir().join(format!(
+                "macroquest-crash-report-{name}-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn file_names(directory: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_136_214_245_123);
+
+        assert_eq!(
+            Timestamp::from(time).to_string(),
+            "2006-01-02 15:04:05.123 UTC"
+        );
+        assert_eq!(
+            Timestamp::from(UNIX_EPOCH + Duration::from_secs(951_782_400)).to_string(),
+            "2000-02-29 00:00:00.000 UTC"
+        );
+    }
+
+    #[test]
+    fn test_report_format() {
+        let report = report("MQRustBasic", 1_136_214_245);
+
+        assert_eq!(
+            report.file_name(),
+            "MQRustBasic-20060102-150405-000.panic.txt"
+        );
+        assert_eq!(
+            report.to_string(),
+            "Plugin:     MQRustBasic 0.1.7\n\
+             EQVersion:  Jan 02 2006 15:04:05\n\
+             Time:       2006-01-02 15:04:05.000 UTC\n\
+             Thread:     main\n\
+             Location:   src/lib.rs:10:5\n\
+             Message:    oh no\n\
+             \n\
+             Backtrace:\n   0: MQRustBasic::pulse\n\
+             \n\
+             Recent log:\n\
+             INFO pulsing\n"
+        );
+    }
+
+    #[test]
+    fn test_report_format_without_log() {
+        let mut report = report("MQRustBasic", 0);
+        report.location = None;
+        report.log.clear();
+
+        let text = report.to_string();
+        assert!(text.contains("Location:   <unknown>\n"));
+        assert!(!text.contains("Recent log:"));
+    }
+
+    #[test]
+    fn test_write_and_rotate() {
+        let directory = TempDir::new("rotate");
+        fs::write(directory.0.join("unrelated.dmp"), "").unwrap();
+        fs::write(directory.0.join("MQRustBasic-notes.panic.txt"), "").unwrap();
+
+        for secs in 0..4 {
+            write(&directory.0, &report("MQRustBasic", secs), 2).unwrap();
+        }
+        let path = write(&directory.0, &report("MQRustHud", 0), 2).unwrap();
+
+        assert!(fs::read_to_string(path).unwrap().contains("MQRustHud"));
+        assert_eq!(
+            file_names(&directory.0),
+            [
+                "MQRustBasic-19700101-000002-000.panic.txt",
+                "MQRustBasic-19700101-000003-000.panic.txt",
+                "MQRustBasic-notes.panic.txt",
+                "MQRustHud-19700101-000000-000.panic.txt",
+                "unrelated.dmp",
+            ]
+        );
+    }
+}
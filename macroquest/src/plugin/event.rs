@@ -0,0 +1,129 @@
+//! The hooks that MacroQuest calls, as data.
+//!
+//! An [`Event`] represents a single call to one of the [`Hooks`] methods,
+//! along with its arguments. This allows a sequence of hooks to be scripted
+//! (see [`scenario`](super::scenario)) or recorded (with the `recorder`
+//! feature), and then dispatched to any [`Hooks`] implementation.
+
+#[cfg(feature = "recorder")]
+use serde::{Deserialize, Serialize};
+
+use super::Hooks;
+use crate::eq;
+use crate::mq::hud::HudFrame;
+
+/// A snapshot of the data of an [`eq::Spawn`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "recorder", derive(Serialize, Deserialize))]
+pub struct SpawnSnapshot {
+    /// The name of the spawn.
+    pub name: String,
+}
+
+impl From<&eq::Spawn> for SpawnSnapshot {
+    fn from(spawn: &eq::Spawn) -> Self {
+        SpawnSnapshot {
+            name: spawn.name().to_owned(),
+        }
+    }
+}
+
+/// A snapshot of the data of an [`eq::GroundItem`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "recorder", derive(Serialize, Deserialize))]
+pub struct GroundItemSnapshot {
+    /// The name of the ground item.
+    pub name: String,
+}
+
+impl From<&eq::GroundItem> for GroundItemSnapshot {
+    fn from(item: &eq::GroundItem) -> Self {
+        GroundItemSnapshot {
+            name: item.name().to_owned(),
+        }
+    }
+}
+
+/// A single hook call, along with its arguments.
+///
+/// [`eq::GameState`] and [`eq::ChatColor`] are stored as their numeric
+/// values, so that values unknown to this crate survive a round trip.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "recorder", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "recorder", serde(tag = "hook", rename_all = "snake_case"))]
+pub enum Event {
+    Initialize,
+    Shutdown,
+    CleanUi,
+    ReloadUi,
+    DrawHud,
+    GameState { state: i32 },
+    Pulse,
+    WriteChat { line: String, color: i32 },
+    IncomingChat { line: String, color: i32 },
+    AddSpawn { spawn: SpawnSnapshot },
+    RemoveSpawn { spawn: SpawnSnapshot },
+    AddGroundItem { item: GroundItemSnapshot },
+    RemoveGroundItem { item: GroundItemSnapshot },
+    BeginZone,
+    EndZone,
+    Zoned,
+    UpdateImgui,
+    MacroStart { name: String },
+    MacroStop { name: String },
+    PluginLoad { name: String },
+    PluginUnload { name: String },
+}
+
+impl Event {
+    /// Call the hook that this event represents on `hooks`.
+    ///
+    /// Returns `false` without calling anything if the hook can't be replayed,
+    /// which is the case for the spawn and ground item hooks. The HUD hooks are
+    /// given a [`HudFrame`] that discards anything drawn on it.
+    #[allow(clippy::must_use_candidate)]
+    pub fn dispatch<H>(&self, hooks: &H) -> bool
+    where
+        H: Hooks + ?Sized,
+    {
+        match self {
+            Event::Initialize => hooks.initialize(),
+            Event::Shutdown => hooks.shutdown(),
+            Event::CleanUi => hooks.clean_ui(),
+            Event::ReloadUi => hooks.reload_ui(),
+            Event::DrawHud => {
+                hooks.draw_hud();
+                hooks.draw_hud_frame(&HudFrame::discarding());
+            }
+            Event::GameState { state } => hooks.game_state(eq::GameState::from(*state)),
+            Event::Pulse => hooks.pulse(),
+            Event::WriteChat { line, color } => {
+                hooks.write_chat(line, eq::ChatColor::from(*color));
+            }
+            Event::IncomingChat { line, color } => {
+                hooks.incoming_chat(line, eq::ChatColor::from(*color));
+            }
+            Event::AddSpawn { .. }
+            | Event::RemoveSpawn { .. }
+            | Event::AddGroundItem { .. }
+            | Event::RemoveGroundItem { .. } => return false,
+            Event::BeginZone => hooks.begin_zone(),
+            Event::EndZone => hooks.end_zone(),
+            Event::Zoned => hooks.zoned(),
+            Event::UpdateImgui => hooks.update_imgui(),
+            Event::MacroStart { name } => hooks.macro_start(name),
+            Event::MacroStop { name } => hooks.macro_stop(name),
+            Event::PluginLoad { name } => hooks.plugin_load(name),
+            Event::PluginUnload { name } => hooks.plugin_unload(name),
+        }
+
+        true
+    }
+
+    /// Whether this is one of the hooks that is called every frame.
+    #[cfg(feature = "recorder")]
+    pub(crate) fn is_per_frame(&self) -> bool {
+        matches!(self, Event::Pulse | Event::DrawHud | Event::UpdateImgui)
+    }
+}
@@ -0,0 +1,419 @@
+//! Timings of each of the plugin's hooks.
+//!
+//! With the `metrics` feature enabled, every call that MacroQuest makes to one
+//! of the plugin's hooks is timed, which makes it possible to tell which hooks
+//! (if any) are causing the game to hitch. The timings can be read with
+//! [`snapshot()`], or shown in game with [`metrics_window()`].
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MyPlugin);
+//! use std::time::Duration;
+//!
+//! use macroquest::imgui::WindowHandle;
+//!
+//! struct MyPlugin {
+//!     timings: WindowHandle,
+//! }
+//!
+//! impl Plugin for MyPlugin {
+//!     fn new() -> Self {
+//!         MyPlugin {
+//!             timings: macroquest::plugin::metrics_window()
+//!                 .budget(Duration::from_micros(500))
+//!                 .register(),
+//!         }
+//!     }
+//! }
+//!
+//! impl Hooks for MyPlugin {}
+//! ```
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::imgui::{windows, Ui, WindowHandle};
+
+static METRICS: Mutex<Registry> = Mutex::new(Registry::new());
+
+// How long the calls to a hook are counted for, to calculate its call rate.
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+// How often the metrics window refreshes what it displays, which saves it from
+// formatting every row on every frame.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+// The color that hooks over budget are highlighted with.
+const OVER_BUDGET: [f32; 4] = [1.0, 0.3, 0.3, 1.0];
+
+const HEADERS: [&str; 5] = ["Hook", "Calls/s", "Worst", "Average", "Calls"];
+
+/// The timings of a single hook.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct HookMetrics {
+    /// The name of the hook (ex: `OnPulse`).
+    pub hook:  &'static str,
+    /// The number of times the hook has been called.
+    pub calls: u64,
+    /// The number of times per second that the hook was called, over the last
+    /// second.
+    pub rate:  f64,
+    /// The longest that a single call to the hook has taken.
+    pub worst: Duration,
+    /// The total time spent in the hook.
+    pub total: Duration,
+}
+
+impl HookMetrics {
+    /// The average time that a call to the hook has taken.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average(&self) -> Duration {
+        match u32::try_from(self.calls) {
+            Ok(0) => Duration::ZERO,
+            Ok(calls) => self.total / calls,
+            Err(_) => self.total.div_f64(self.calls as f64),
+        }
+    }
+}
+
+/// The timings of every hook that has been called since the metrics were
+/// last [`reset()`], sorted by the name of the hook.
+#[must_use]
+pub fn snapshot() -> Vec<HookMetrics> {
+    METRICS.lock().snapshot(Instant::now())
+}
+
+/// Forget all of the timings recorded so far.
+pub fn reset() {
+    METRICS.lock().reset();
+}
+
+/// Call `f`, recording how long it took as a call to `hook`.
+pub(crate) fn measure<F, R>(hook: &'static str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let start = Instant::now();
+    let result = f();
+    let end = Instant::now();
+
+    METRICS.lock().record(hook, end - start, end);
+    result
+}
+
+/// Create a window that shows the timings of each hook, which is shown once
+/// it has been [registered](MetricsWindow::register).
+pub fn metrics_window() -> MetricsWindow {
+    MetricsWindow {
+        name:   "Hook Timings".into(),
+        budget: Duration::from_millis(1),
+    }
+}
+
+/// An ImGui window showing a live table of the timings of each hook, created
+/// with [`metrics_window()`].
+///
+/// Any hook that has taken longer than the frame budget (1ms by default) in a
+/// single call is highlighted in red, and the timings can be reset from the
+/// window.
+#[derive(Debug, Clone)]
+#[must_use = "the window isn't shown until it has been registered"]
+pub struct MetricsWindow {
+    name:   String,
+    budget: Duration,
+}
+
+impl MetricsWindow {
+    /// Set the name of the window, which defaults to "Hook Timings".
+    ///
+    /// If more than one plugin shows this window, they must each use a
+    /// different name.
+    pub fn name<N>(mut self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.name = name.into();
+        self
+    }
+
+    /// Set how long a single call to a hook may take before it's highlighted.
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Register the window with the [`windows()`] registry, which shows it
+    /// until the returned handle is dropped.
+    #[must_use]
+    pub fn register(self) -> WindowHandle {
+        let mut overlay = Overlay::new(self.budget);
+        windows().register(self.name, move |ui| overlay.draw(ui))
+    }
+}
+
+struct Overlay {
+    budget:    Duration,
+    refreshed: Option<Instant>,
+    rows:      Vec<Row>,
+}
+
+struct Row {
+    cells:       [String; 5],
+    over_budget: bool,
+}
+
+impl Overlay {
+    fn new(budget: Duration) -> Overlay {
+        Overlay {
+            budget,
+            refreshed: None,
+            rows: vec![],
+        }
+    }
+
+    fn draw(&mut self, ui: &Ui) {
+        let now = Instant::now();
+        if self
+            .refreshed
+            .is_none_or(|refreshed| now - refreshed >= REFRESH_INTERVAL)
+        {
+            self.refresh(&snapshot(), now);
+        }
+
+        ui.text(&format!("Frame budget: {}", format_duration(self.budget)));
+        ui.same_line();
+        if ui.button("Reset") {
+            reset();
+            self.refresh(&[], now);
+        }
+
+        ui.table("hook-timings", &HEADERS, |ui| {
+            for row in &self.rows {
+                ui.table_next_row();
+
+                for cell in &row.cells {
+                    ui.table_next_column();
+
+                    if row.over_budget {
+                        ui.text_colored(OVER_BUDGET, cell);
+                    }
+                    else {
+                        ui.text(cell);
+                    }
+                }
+            }
+        });
+    }
+
+    fn refresh(&mut self, metrics: &[HookMetrics], now: Instant) {
+        self.refreshed = Some(now);
+        self.rows = metrics
+            .iter()
+            .map(|metrics| Row {
+                cells:       [
+                    metrics.hook.to_owned(),
+                    format!("{:.1}", metrics.rate),
+                    format_duration(metrics.worst),
+                    format_duration(metrics.average()),
+                    metrics.calls.to_string(),
+                ],
+                over_budget: metrics.worst > self.budget,
+            })
+            .collect();
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+struct Entry {
+    metrics:      HookMetrics,
+    window_start: Instant,
+    window_calls: u64,
+}
+
+impl Entry {
+    /// Recalculate the call rate, if the current window has ended.
+    #[allow(clippy::cast_precision_loss)]
+    fn roll(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.window_start);
+
+        if elapsed >= RATE_WINDOW {
+            self.metrics.rate = self.window_calls as f64 / elapsed.as_secs_f64();
+            self.window_start = now;
+            self.window_calls = 0;
+        }
+    }
+}
+
+struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    const fn new() -> Registry {
+        Registry { entries: vec![] }
+    }
+
+    fn record(&mut self, hook: &'static str, elapsed: Duration, now: Instant) {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.metrics.hook == hook);
+        let entry = if let Some(index) = index {
+            &mut self.entries[index]
+        }
+        else {
+            self.entries.push(Entry {
+                metrics:      HookMetrics {
+                    hook,
+                    calls: 0,
+                    rate: 0.0,
+                    worst: Duration::ZERO,
+                    total: Duration::ZERO,
+                },
+                window_start: now,
+                window_calls: 0,
+            });
+            self.entries.last_mut().expect("an entry was just pushed")
+        };
+
+        entry.roll(now);
+        entry.window_calls += 1;
+        entry.metrics.calls += 1;
+        entry.metrics.total += elapsed;
+        entry.metrics.worst = entry.metrics.worst.max(elapsed);
+    }
+
+    fn snapshot(&mut self, now: Instant) -> Vec<HookMetrics> {
+        let mut metrics: Vec<HookMetrics> = self
+            .entries
+            .iter_mut()
+            .map(|entry| {
+                entry.roll(now);
+                entry.metrics.clone()
+            })
+            .collect();
+
+        metrics.sort_by_key(|metrics| metrics.hook);
+        metrics
+    }
+
+    fn reset(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_record() {
+        let start = Instant::now();
+        let mut registry = Registry::new();
+
+        registry.record("OnPulse", ms(2), start);
+        registry.record("OnPulse", ms(4), start);
+        registry.record("OnDrawHUD", ms(1), start);
+
+        let metrics = registry.snapshot(start);
+        assert_eq!(metrics.len(), 2);
+
+        assert_eq!(metrics[0].hook, "OnDrawHUD");
+        assert_eq!(metrics[0].calls, 1);
+
+        assert_eq!(metrics[1].hook, "OnPulse");
+        assert_eq!(metrics[1].calls, 2);
+        assert_eq!(metrics[1].worst, ms(4));
+        assert_eq!(metrics[1].total, ms(6));
+        assert_eq!(metrics[1].average(), ms(3));
+    }
+
+    #[test]
+    fn test_rate() {
+        let start = Instant::now();
+        let mut registry = Registry::new();
+
+        for i in 0..10 {
+            registry.record("OnPulse", ms(1), start + ms(i * 100));
+        }
+
+        // The rate isn't known until the first window has ended.
+        let rate = registry.snapshot(start + ms(900))[0].rate;
+        assert!(rate.abs() < f64::EPSILON, "{rate}");
+
+        let rate = registry.snapshot(start + ms(2000))[0].rate;
+        assert!((rate - 5.0).abs() < f64::EPSILON, "{rate}");
+
+        // No calls in the next window means a rate of zero.
+        let rate = registry.snapshot(start + ms(3000))[0].rate;
+        assert!(rate.abs() < f64::EPSILON, "{rate}");
+    }
+
+    #[test]
+    fn test_reset() {
+        let start = Instant::now();
+        let mut registry = Registry::new();
+
+        registry.record("OnPulse", ms(1), start);
+        registry.reset();
+
+        assert!(registry.snapshot(start).is_empty());
+    }
+
+    #[test]
+    fn test_average_without_calls() {
+        let metrics = HookMetrics {
+            hook:  "OnPulse",
+            calls: 0,
+            rate:  0.0,
+            worst: Duration::ZERO,
+            total: Duration::ZERO,
+        };
+
+        assert_eq!(metrics.average(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_overlay_rows() {
+        let mut overlay = Overlay::new(ms(1));
+        let metrics = [
+            HookMetrics {
+                hook:  "OnPulse",
+                calls: 4,
+                rate:  60.0,
+                worst: Duration::from_micros(2500),
+                total: ms(4),
+            },
+            HookMetrics {
+                hook:  "OnZoned",
+                calls: 1,
+                rate:  0.0,
+                worst: Duration::from_micros(500),
+                total: Duration::from_micros(500),
+            },
+        ];
+
+        overlay.refresh(&metrics, Instant::now());
+
+        assert_eq!(
+            overlay.rows[0].cells,
+            ["OnPulse", "60.0", "2.500ms", "1.000ms", "4"]
+        );
+        assert!(overlay.rows[0].over_budget);
+        assert_eq!(
+            overlay.rows[1].cells,
+            ["OnZoned", "0.0", "0.500ms", "0.500ms", "1"]
+        );
+        assert!(!overlay.rows[1].over_budget);
+    }
+}
@@ -0,0 +1,1837 @@
+//! The MacroQuest Plugin API and related types and functions.
+//!
+//! The plugin API exposed by MacroQuest is a series of module level functions
+//! with specific, well known names and type signatures that it will call into
+//! whenever certain events occur. Plugins may implement any number of these
+//! functions to implement their functionality.
+//!
+//! Defining a MacroQuest plugin will primarily use three items: The [`setup`]
+//! macro, the [`Hooks`] trait, and the [`hooks`] macro.
+//!
+//! The [`setup`] macro is responsible for setting up a given type to be used
+//! as a MacroQuest plugin, exporting all the required symbols and setting up
+//! all of our own internal state.
+//!
+//! It has one form:
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # #[derive(Debug)]
+//! # struct MyPlugin;
+//! # impl Plugin for MyPlugin {
+//! #     fn new() -> Self {
+//! #         MyPlugin
+//! #     }
+//! # }
+//! # impl Hooks for MyPlugin {}
+//! macroquest::plugin::setup!(MyPlugin);
+//! ```
+//!
+//! This takes a given type (`MyPlugin` in this case), which must implement
+//! [`Plugin`] and [`Hooks`], and generates all of the required structure for
+//! this plugin to be loaded as a MacroQuest plugin. A plugin whose [`Hooks`]
+//! implementation is chosen when it's created can instead be set up as a
+//! [`DynPlugin`].
+//!
+//! The [`Hooks`] trait is how a plugin implementation defines which MacroQuest
+//! hooks their plugin wants to implement. This trait has methods for each
+//! MacroQuest hook, which can be implemented to implement the actual desired
+//! functionality of the MacroQuest plugin.
+//!
+//! While the [`Hooks`] trait has methods available for every MacroQuest hook,
+//! only the hooks that are needed should be implemented (the rest have empty
+//! default implementations), and the unimplemented ones will not be exported
+//! by the [`hooks`] macro to prevent any runtime overhead for unused hooks. The
+//! exceptions to this are [`Hooks::initialize()`], [`Hooks::shutdown()`],
+//! [`Hooks::pulse()`], [`Hooks::update_imgui()`], [`Hooks::clean_ui()`], and
+//! [`Hooks::reload_ui()`], which are always exported by the [`setup`] macro as
+//! this crate has work of its own that it needs to do in them.
+//!
+//! The [`hooks`] macro is used to decorate the `impl Hooks` block for this
+//! plugin, and it exports all of the required symbols and boilerplate to have
+//! MacroQuest ultimately call the hook method on [`Hooks`] for the given hook.
+//!
+//!
+//! # Examples
+//!
+//! Putting this all together, to create a basic, but useless, plugin.
+//!
+//! ```
+//! # use macroquest::log::trace;
+//! # use macroquest::eq::ChatColor;
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # use std::sync::Mutex;
+//! macroquest::plugin::setup!(MyPlugin);
+//!
+//! #[derive(Debug)]
+//! struct MyPlugin {
+//!     last: Mutex<Option<String>>,
+//! }
+//!
+//! impl Plugin for MyPlugin {
+//!     fn new() -> Self {
+//!         MyPlugin {
+//!             last: Mutex::new(None),
+//!         }
+//!     }
+//! }
+//!
+//! #[macroquest::plugin::hooks]
+//! impl Hooks for MyPlugin {
+//!     fn incoming_chat(&self, line: &str, color: ChatColor) -> bool {
+//!         let mut l = self.last.lock().unwrap();
+//!         *l = Some(line.to_string());
+//!
+//!         false
+//!     }
+//! }
+//! ```
+
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::{mem, ptr};
+
+use arc_swap::ArcSwapOption;
+
+#[doc(inline)]
+pub use macroquest_proc_macros::plugin_command as command;
+#[doc(inline)]
+pub use macroquest_proc_macros::plugin_hooks as hooks;
+
+pub use self::channel::{
+    worker_channel,
+    HookSender,
+    OverflowPolicy,
+    Receiver,
+    RecvIter,
+};
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+#[cfg(feature = "metrics")]
+pub use self::metrics::{metrics_window, MetricsWindow};
+pub(crate) use self::workers::panic_message;
+pub use self::workers::{StopToken, Workers};
+
+#[doc(hidden)]
+pub use crate::__plugin_hook as hook;
+#[doc(inline)]
+pub use crate::__plugin_setup as setup;
+
+use crate::eq;
+use crate::mq::hud::HudFrame;
+
+pub mod crash_report;
+pub mod event;
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg_attr(docsrs, doc(cfg(feature = "recorder")))]
+#[cfg(feature = "recorder")]
+pub mod recorder;
+pub mod scenario;
+pub mod schedule;
+
+#[cfg(feature = "benchmark")]
+mod benchmark;
+mod channel;
+mod workers;
+
+/// Implements a MacroQuest plugin.
+///
+/// This trait implements the basic requirements of making a Plugin, but it does
+/// not expose any of the hooks that MacroQuest has that plugins can implement,
+/// for that see [`Hooks`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` must implement `Plugin` to be a MacroQuest plugin",
+    label = "not a MacroQuest plugin",
+    note = "implement `macroquest::plugin::Plugin` for `{Self}`, with a `new()` \
+            that creates the plugin"
+)]
+pub trait Plugin: Hooks {
+    /// Creates an instance of the plugin type.
+    ///
+    /// Typically this will only be called once, early on in the plugin
+    /// lifecycle prior to any of the hooks in [`Hooks`] being called.
+    fn new() -> Self;
+}
+
+/// The Hooks trait implements the protocol that a MacroQuest plugin must
+/// implement.
+///
+/// For each process, there is one global plugin instance, created using the
+/// [`Plugin::new()`] function, and the MacroQuest plugin hooks will get
+/// dispatched to the instance methods of that plugin instance.
+///
+/// All MacroQuest plugin hooks have a default, no-op implementation, allowing
+/// a Hooks implementation to implement only the ones that they actually care
+/// about, while leaving the no-op implementations to cover any other hook.
+///
+/// The orderings that are documented here are also available as scripted
+/// [`Scenario`](scenario::Scenario)s, for testing a plugin against them.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` must implement `Hooks` to be a MacroQuest plugin",
+    label = "not a MacroQuest plugin",
+    note = "implement `macroquest::plugin::Hooks` for `{Self}`, where an empty \
+            `impl Hooks for {Self} {{}}` doesn't implement any of the hooks"
+)]
+#[allow(unused_variables)]
+pub trait Hooks {
+    /// This is called once on plugin initialization and can be considered the
+    /// startup routine for the plugin.
+    #[doc(alias = "InitializePlugin")]
+    fn initialize(&self) {}
+
+    /// This is called once when the plugin has been asked to shutdown. The
+    /// plugin has not actually shut down until this completes.
+    #[doc(alias = "ShutdownPlugin")]
+    fn shutdown(&self) {}
+
+    /// This is called once just before the shutdown of the UI system and each
+    /// time the game requests that the UI be cleaned. Most commonly this
+    /// happens when a `/loadskin` command is issued, but it also occurs when
+    /// reaching the character select screen and when first entering the game.
+    ///
+    /// One purpose of this function is to allow you to destroy any custom
+    /// windows that you have created and cleanup any UI items that need to be
+    /// removed. Windows created with [`Window`](crate::mq::ui::Window) are
+    /// destroyed automatically, just after this is called.
+    #[doc(alias = "OnCleanUI")]
+    fn clean_ui(&self) {}
+
+    /// This is called once just after the UI system is loaded. Most commonly
+    /// this happens when a `/loadskin` command is issued, but it also occurs
+    /// when first entering the game.
+    ///
+    /// One purpose of this function is to allow you to recreate any custom
+    /// windows that you have setup. Windows created with
+    /// [`Window`](crate::mq::ui::Window) are recreated automatically, just
+    /// before this is called.
+    #[doc(alias = "OnReloadUI")]
+    fn reload_ui(&self) {}
+
+    /// This is called each time the Heads Up Display (HUD) is drawn. The HUD is
+    /// responsible for the net status and packet loss bar.
+    ///
+    /// Note that this is not called at all if the HUD is not shown (default F11
+    /// to toggle).
+    ///
+    /// Because the net status is updated frequently, it is recommended to have
+    /// a timer or counter at the start of this call to limit the amount of
+    /// times the code in this section is executed.
+    ///
+    /// To draw on the HUD, implement [`Hooks::draw_hud_frame()`] instead,
+    /// which is called at the same time.
+    #[doc(alias = "OnDrawHUD")]
+    fn draw_hud(&self) {}
+
+    /// This is called each time the Heads Up Display (HUD) is drawn, just after
+    /// [`Hooks::draw_hud()`], with a [`HudFrame`]
+    /// that can be used to draw on the HUD.
+    ///
+    /// Note that this is not called at all if the HUD is not shown (default F11
+    /// to toggle).
+    #[doc(alias = "OnDrawHUD")]
+    fn draw_hud_frame(&self, frame: &HudFrame) {}
+
+    /// This is called when the [`crate::eq::GameState`] changes. It is
+    /// also called once after the plugin is initialized.
+    ///
+    /// For a list of known [`crate::eq::GameState`] values, see the
+    /// [`crate::eq::GameState`] enum. The most commonly used of these is
+    /// [`crate::eq::GameState::InGame`].
+    ///
+    /// When zoning, this is called once after [`Hooks::begin_zone()`],
+    /// [`Hooks::remove_spawn()`], and [`Hooks::remove_ground_item()`]
+    /// are all done, and then called once again after [`Hooks::end_zone()`]
+    /// and [`Hooks::add_spawn()`] are done but prior to
+    /// [`Hooks::add_ground_item()`] and [`Hooks::zoned()`].
+    #[doc(alias = "SetGameState")]
+    fn game_state(&self, state: eq::GameState) {}
+
+    /// This is called each time MQ2 goes through its heartbeat (pulse)
+    /// function.
+    ///
+    /// Because this happens very frequently, it is recommended to have a timer
+    /// or counter at the start of this call to limit the amount of times the
+    /// code in this section is executed.
+    #[doc(alias = "OnPulse")]
+    fn pulse(&self) {}
+
+    /// This is called each time `WriteChatColor` is called (whether by
+    /// MQ2Main or by any plugin). This can be considered the "when outputting
+    /// text from MQ" callback.
+    ///
+    /// This ignores filters on display, so if they are needed either implement
+    /// them in this section or see [`Hooks::incoming_chat()`] where filters
+    /// are already handled.
+    ///
+    /// If `CEverQuest::dsp_chat` is not called, and events are required,
+    /// they'll need to be implemented here as well. Otherwise, see
+    /// [`Hooks::incoming_chat()`] where that is already handled.
+    ///
+    /// The color is usually one of the chat channels, see
+    /// [`ChatColor::channel()`](crate::eq::ChatColor::channel).
+    #[doc(alias = "OnWriteChatColor")]
+    fn write_chat(&self, line: &str, color: eq::ChatColor) {}
+
+    /// This is called each time a line of chat is shown. It occurs after MQ
+    /// filters and chat events have been handled.  If you need to know when
+    /// MQ2 has sent chat, consider using [`Hooks::write_chat()`]
+    /// instead.
+    ///
+    /// The color is usually one of the chat channels, see
+    /// [`ChatColor::channel()`](crate::eq::ChatColor::channel).
+    #[doc(alias = "OnIncomingChat")]
+    fn incoming_chat(&self, line: &str, color: eq::ChatColor) -> bool {
+        false
+    }
+
+    /// This is called each time a spawn is added to a zone (ie, something
+    /// spawns). It is also called for each existing spawn when a plugin first
+    /// initializes.
+    ///
+    /// When zoning, this is called for all spawns in the zone after
+    /// [`Hooks::end_zone()`] is called and before [`Hooks::zoned()`] is
+    /// called.
+    #[doc(alias = "OnAddSpawn")]
+    fn add_spawn(&self, spawn: &eq::Spawn) {}
+
+    /// This is called each time a spawn is removed from a zone (ie, something
+    /// despawns or is killed). It is NOT called when a plugin shuts down.
+    ///
+    /// When zoning, this is called for all spawns in the zone after
+    /// [`Hooks::begin_zone()`] is called.
+    #[doc(alias = "OnRemoveSpawn")]
+    fn remove_spawn(&self, spawn: &eq::Spawn) {}
+
+    /// This is called each time a ground item is added to a zone (ie, something
+    /// spawns). It is also called for each existing ground item when a plugin
+    /// first initializes.
+    ///
+    /// When zoning, this is called for all ground items in the zone after
+    /// [`Hooks::end_zone()`] is called and before [`Hooks::zoned()`] is
+    /// called.
+    #[doc(alias = "OnAddGroundItem")]
+    fn add_ground_item(&self, item: &eq::GroundItem) {}
+
+    /// This is called each time a ground item is removed from a zone (ie,
+    /// something despawns or is picked up). It is NOT called when a plugin
+    /// shuts down.
+    ///
+    /// When zoning, this is called for all ground items in the zone after
+    /// [`Hooks::begin_zone()`] is called.
+    #[doc(alias = "OnRemoveGroundItem")]
+    fn remove_ground_item(&self, item: &eq::GroundItem) {}
+
+    /// This is called just after entering a zone line and as the loading screen
+    /// appears.
+    #[doc(alias = "OnBeginZone")]
+    fn begin_zone(&self) {}
+
+    /// This is called just after the loading screen, but prior to the zone
+    /// being fully loaded.
+    ///
+    /// This should occur before [`Hooks::add_spawn()`] and
+    /// [`Hooks::add_ground_item()`] are called. It always occurs before
+    /// [`Hooks::zoned()`] is called.
+    #[doc(alias = "OnEndZone")]
+    fn end_zone(&self) {}
+
+    /// This is called after entering a new zone and the zone is considered
+    /// "loaded."
+    ///
+    /// It occurs after [`Hooks::end_zone()`], [`Hooks::add_spawn()`],
+    /// and [`Hooks::add_ground_item()`] have been called.
+    #[doc(alias = "OnZoned")]
+    fn zoned(&self) {}
+
+    /// This is called each time that the ImGui overlay is rendered. Use this to
+    /// render and update plugin specific widgets, using the
+    /// [`Ui`](crate::imgui::Ui) from
+    /// [`Ui::current()`](crate::imgui::Ui::current).
+    ///
+    /// Because this happens extremely frequently, it is recommended to move any
+    /// actual work to a separate call and use this only for updating the
+    /// display.
+    #[doc(alias = "OnUpdateImGui")]
+    fn update_imgui(&self) {}
+
+    /// This is called each time a macro starts (ex: `/mac somemacro.mac`),
+    /// prior to launching the macro.
+    #[doc(alias = "OnMacroStart")]
+    fn macro_start(&self, name: &str) {}
+
+    /// This is called each time a macro stops (ex: `/endmac`), after the
+    /// macro has ended.
+    #[doc(alias = "OnMacroStop")]
+    fn macro_stop(&self, name: &str) {}
+
+    /// This is called each time a plugin is loaded
+    /// (ex: `/plugin someplugin`), after the plugin has been loaded and any
+    /// associated `-AutoExec.cfg` file have been launched.
+    ///
+    /// This means it will be executed after the [`Hooks::initialize()`]
+    /// callback.
+    ///
+    /// This is also called when THIS plugin is loaded, but initialization tasks
+    /// should still be done in [`Hooks::initialize()`].
+    #[doc(alias = "OnLoadPlugin")]
+    fn plugin_load(&self, name: &str) {}
+
+    /// This is called each time a plugin is unloaded
+    /// (ex: `/plugin someplugin unload`), just prior to the plugin unloading.
+    /// This means it will be executed prior to the [`Hooks::shutdown()`]
+    /// callback.
+    ///
+    /// This is also called when THIS plugin is unloaded, but shutdown tasks
+    /// should still be done in [`Hooks::shutdown()`].
+    #[doc(alias = "OnUnloadPlugin")]
+    fn plugin_unload(&self, name: &str) {}
+}
+
+impl<T: Hooks + ?Sized> Hooks for Box<T> {
+    fn initialize(&self) {
+        (**self).initialize();
+    }
+
+    fn shutdown(&self) {
+        (**self).shutdown();
+    }
+
+    fn clean_ui(&self) {
+        (**self).clean_ui();
+    }
+
+    fn reload_ui(&self) {
+        (**self).reload_ui();
+    }
+
+    fn draw_hud(&self) {
+        (**self).draw_hud();
+    }
+
+    fn draw_hud_frame(&self, frame: &HudFrame) {
+        (**self).draw_hud_frame(frame);
+    }
+
+    fn game_state(&self, state: eq::GameState) {
+        (**self).game_state(state);
+    }
+
+    fn pulse(&self) {
+        (**self).pulse();
+    }
+
+    fn write_chat(&self, line: &str, color: eq::ChatColor) {
+        (**self).write_chat(line, color);
+    }
+
+    fn incoming_chat(&self, line: &str, color: eq::ChatColor) -> bool {
+        (**self).incoming_chat(line, color)
+    }
+
+    fn add_spawn(&self, spawn: &eq::Spawn) {
+        (**self).add_spawn(spawn);
+    }
+
+    fn remove_spawn(&self, spawn: &eq::Spawn) {
+        (**self).remove_spawn(spawn);
+    }
+
+    fn add_ground_item(&self, item: &eq::GroundItem) {
+        (**self).add_ground_item(item);
+    }
+
+    fn remove_ground_item(&self, item: &eq::GroundItem) {
+        (**self).remove_ground_item(item);
+    }
+
+    fn begin_zone(&self) {
+        (**self).begin_zone();
+    }
+
+    fn end_zone(&self) {
+        (**self).end_zone();
+    }
+
+    fn zoned(&self) {
+        (**self).zoned();
+    }
+
+    fn update_imgui(&self) {
+        (**self).update_imgui();
+    }
+
+    fn macro_start(&self, name: &str) {
+        (**self).macro_start(name);
+    }
+
+    fn macro_stop(&self, name: &str) {
+        (**self).macro_stop(name);
+    }
+
+    fn plugin_load(&self, name: &str) {
+        (**self).plugin_load(name);
+    }
+
+    fn plugin_unload(&self, name: &str) {
+        (**self).plugin_unload(name);
+    }
+}
+
+impl<T: Hooks + ?Sized> Hooks for Arc<T> {
+    fn initialize(&self) {
+        (**self).initialize();
+    }
+
+    fn shutdown(&self) {
+        (**self).shutdown();
+    }
+
+    fn clean_ui(&self) {
+        (**self).clean_ui();
+    }
+
+    fn reload_ui(&self) {
+        (**self).reload_ui();
+    }
+
+    fn draw_hud(&self) {
+        (**self).draw_hud();
+    }
+
+    fn draw_hud_frame(&self, frame: &HudFrame) {
+        (**self).draw_hud_frame(frame);
+    }
+
+    fn game_state(&self, state: eq::GameState) {
+        (**self).game_state(state);
+    }
+
+    fn pulse(&self) {
+        (**self).pulse();
+    }
+
+    fn write_chat(&self, line: &str, color: eq::ChatColor) {
+        (**self).write_chat(line, color);
+    }
+
+    fn incoming_chat(&self, line: &str, color: eq::ChatColor) -> bool {
+        (**self).incoming_chat(line, color)
+    }
+
+    fn add_spawn(&self, spawn: &eq::Spawn) {
+        (**self).add_spawn(spawn);
+    }
+
+    fn remove_spawn(&self, spawn: &eq::Spawn) {
+        (**self).remove_spawn(spawn);
+    }
+
+    fn add_ground_item(&self, item: &eq::GroundItem) {
+        (**self).add_ground_item(item);
+    }
+
+    fn remove_ground_item(&self, item: &eq::GroundItem) {
+        (**self).remove_ground_item(item);
+    }
+
+    fn begin_zone(&self) {
+        (**self).begin_zone();
+    }
+
+    fn end_zone(&self) {
+        (**self).end_zone();
+    }
+
+    fn zoned(&self) {
+        (**self).zoned();
+    }
+
+    fn update_imgui(&self) {
+        (**self).update_imgui();
+    }
+
+    fn macro_start(&self, name: &str) {
+        (**self).macro_start(name);
+    }
+
+    fn macro_stop(&self, name: &str) {
+        (**self).macro_stop(name);
+    }
+
+    fn plugin_load(&self, name: &str) {
+        (**self).plugin_load(name);
+    }
+
+    fn plugin_unload(&self, name: &str) {
+        (**self).plugin_unload(name);
+    }
+}
+
+/// A plugin whose [`Hooks`] implementation is chosen when it's created, such
+/// as from its config, which is set up with the `dyn Hooks` form of [`setup`].
+///
+/// # Examples
+///
+/// Choosing between two modes, each of which implements the hooks that it
+/// needs. The implementations don't use the [`hooks`] macro, as every hook is
+/// exported for a `dyn Hooks` plugin.
+///
+/// ```
+/// use macroquest::eq::ChatColor;
+/// use macroquest::plugin::{DynPlugin, Hooks};
+///
+/// struct Healer;
+///
+/// impl Hooks for Healer {
+///     fn pulse(&self) {
+///         // Keep everyone alive.
+///     }
+/// }
+///
+/// struct Damage;
+///
+/// impl Hooks for Damage {
+///     fn incoming_chat(&self, line: &str, color: ChatColor) -> bool {
+///         // Watch for the next target.
+///         false
+///     }
+/// }
+///
+/// fn choose_mode() -> DynPlugin {
+///     let healer = std::env::var("MY_PLUGIN_MODE").is_ok_and(|mode| mode == "healer");
+///
+///     if healer {
+///         Box::new(Healer)
+///     }
+///     else {
+///         Box::new(Damage)
+///     }
+/// }
+///
+/// macroquest::plugin::setup!(dyn Hooks = choose_mode);
+/// ```
+///
+/// Where the choices are known ahead of time, an `enum` with a variant for
+/// each of them, whose `impl Hooks` matches on itself to call the hooks of
+/// each variant, works with the usual form of [`setup`] instead, and only
+/// exports the hooks that it implements.
+pub type DynPlugin = Box<dyn Hooks + Send + Sync>;
+
+#[doc(hidden)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ArcPluginOption<T> {
+    plugin:  ArcSwapOption<T>,
+    // The plugin in `plugin` (or null), which the hooks get at it through
+    // without touching its reference count (see with()).
+    current: AtomicPtr<T>,
+    // How many calls to with() are in progress, and the plugins replaced while
+    // any were, which are kept alive until they've all returned.
+    depth:   AtomicUsize,
+    retired: Mutex<Vec<Arc<T>>>,
+    pending: AtomicBool,
+    // Creates the plugin, and names it (for its window state and hook spans).
+    new:     fn() -> T,
+    name:    fn() -> &'static str,
+}
+
+impl<T: Plugin> ArcPluginOption<T> {
+    // Only ever built in a static by setup!, which needs a const fn that
+    // Default can't provide.
+    #[allow(clippy::new_without_default)]
+    #[must_use]
+    pub const fn new() -> Self {
+        ArcPluginOption::with_constructor(plugin_name::<T>, T::new)
+    }
+}
+
+impl<T: Hooks> ArcPluginOption<T> {
+    /// The state of a plugin named by `name`, and created by `new` rather than
+    /// by [`Plugin::new()`].
+    #[must_use]
+    pub const fn with_constructor(name: fn() -> &'static str, new: fn() -> T) -> Self {
+        ArcPluginOption {
+            plugin: ArcSwapOption::const_empty(),
+            current: AtomicPtr::new(ptr::null_mut()),
+            depth: AtomicUsize::new(0),
+            retired: Mutex::new(Vec::new()),
+            pending: AtomicBool::new(false),
+            new,
+            name,
+        }
+    }
+
+    pub fn set(&self) {
+        crate::mq::record_main_thread();
+        crate::imgui::windows().load(crate::imgui::state_path((self.name)()));
+
+        #[cfg(feature = "benchmark")]
+        benchmark::register((self.name)());
+
+        self.replace(Some(Arc::new((self.new)())));
+        crate::mq::commands::add_declared();
+    }
+
+    pub fn unset(&self) {
+        self.replace(None);
+        crate::mq::finish_pending_console();
+
+        #[cfg(feature = "benchmark")]
+        benchmark::unregister();
+
+        schedule::clear();
+        crate::imgui::windows().clear();
+        crate::mq::ui::clear();
+        crate::mq::keybinds::clear();
+        crate::mq::commands::clear();
+        crate::mq::eqlog::clear();
+        crate::mq::targeting::clear();
+        crate::mq::datatypes::clear();
+        crate::actors::clear();
+
+        #[cfg(feature = "interface")]
+        crate::interface::clear();
+
+        #[cfg(feature = "interop")]
+        crate::interop::dannet::clear();
+
+        #[cfg(feature = "tokio")]
+        crate::rt::shutdown();
+    }
+
+    pub fn pulse(&self) {
+        crate::mq::flush_pending_console();
+        schedule::pulse();
+        crate::mq::eqlog::pulse();
+        crate::mq::targeting::pulse();
+
+        #[cfg(feature = "tokio")]
+        crate::rt::pulse();
+    }
+
+    pub fn imgui_frame<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        self.benchmark(|| {
+            crate::imgui::frame(|| {
+                let result = f();
+
+                // The registered windows are drawn after the plugin's own
+                // update_imgui, so that they're drawn on top of anything it
+                // draws.
+                if let Some(ui) = crate::imgui::Ui::current() {
+                    crate::imgui::windows().render(&ui, (self.name)());
+                }
+
+                result
+            })
+        })
+    }
+
+    pub fn hud_frame<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&HudFrame) -> R,
+    {
+        f(&HudFrame::new())
+    }
+
+    pub fn clean_ui<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        // The windows are destroyed after the plugin's own clean_ui, so that it
+        // can still use them, and even if it panics, as the game is about to
+        // destroy the UI that they belong to either way.
+        struct Destroy;
+
+        impl Drop for Destroy {
+            fn drop(&mut self) {
+                crate::mq::ui::clean_ui();
+            }
+        }
+
+        let _destroy = Destroy;
+        f()
+    }
+
+    pub fn reload_ui<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        // The windows are recreated before the plugin's own reload_ui, so that
+        // it can use them.
+        crate::mq::ui::reload_ui();
+        f()
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn measure<F, R>(&self, hook: &'static str, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        metrics::measure(hook, f)
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    pub fn measure<F, R>(&self, _hook: &'static str, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        f()
+    }
+
+    /// Call `f` within the plugin's MacroQuest benchmark, so that the time it
+    /// takes is shown by `/benchmark`.
+    #[cfg(feature = "benchmark")]
+    pub fn benchmark<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        benchmark::measure(f)
+    }
+
+    #[cfg(not(feature = "benchmark"))]
+    pub fn benchmark<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        f()
+    }
+
+    /// Call `f` within a `TRACE` span of the hook it's calling and the plugin,
+    /// so that anything logged by the plugin records which hook it came from.
+    #[cfg(feature = "hook-spans")]
+    pub fn span<F, R>(&self, hook: &'static str, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        tracing::trace_span!("hook", hook, plugin = (self.name)()).in_scope(f)
+    }
+
+    #[cfg(not(feature = "hook-spans"))]
+    pub fn span<F, R>(&self, _hook: &'static str, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        f()
+    }
+
+    pub fn begin_shutdown(&self) {
+        channel::disconnect_all();
+    }
+
+    // Replace the plugin, keeping the one it replaces alive if a hook is still
+    // using it (such as a hook that unloads its own plugin).
+    fn replace(&self, plugin: Option<Arc<T>>) {
+        let current = plugin
+            .as_ref()
+            .map_or(ptr::null_mut(), |plugin| Arc::as_ptr(plugin).cast_mut());
+        self.current.store(current, Ordering::Relaxed);
+
+        if let Some(previous) = self.plugin.swap(plugin) {
+            if self.depth.load(Ordering::Relaxed) > 0 {
+                self.retired
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(previous);
+                self.pending.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Call `f` with the plugin, which is borrowed through a pointer cached
+    /// when it's set, rather than loaded from the `Arc` that owns it, as this
+    /// is called by every hook (several of which are called every frame).
+    ///
+    /// # Safety
+    ///
+    /// Must only be called on the thread that the plugin is set and unset on,
+    /// which holds for the hooks, as MacroQuest calls all of them (along with
+    /// `InitializePlugin` and `ShutdownPlugin`) on the game's main thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin hasn't been initialized, or has been shut down,
+    /// which the hooks catch and log like any other panic.
+    pub unsafe fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        // Leaves the call, dropping anything replaced during it once the
+        // outermost call returns (or unwinds).
+        struct Exit<'a, T>(&'a ArcPluginOption<T>);
+
+        impl<T> Drop for Exit<'_, T> {
+            fn drop(&mut self) {
+                let depth = self.0.depth.load(Ordering::Relaxed) - 1;
+                self.0.depth.store(depth, Ordering::Relaxed);
+
+                if depth == 0 && self.0.pending.load(Ordering::Relaxed) {
+                    self.0.pending.store(false, Ordering::Relaxed);
+
+                    let retired = mem::take(
+                        &mut *self
+                            .0
+                            .retired
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner),
+                    );
+                    drop(retired);
+                }
+            }
+        }
+
+        let plugin = self.current.load(Ordering::Relaxed);
+        assert!(!plugin.is_null(), "hook called without plugin initialized");
+
+        // This is only ever called on one thread (as is replace()), so it
+        // doesn't need the locked increment of fetch_add.
+        self.depth
+            .store(self.depth.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
+        let _exit = Exit(self);
+
+        // SAFETY: `current` only points to a plugin while it's in `plugin`,
+        // and once it's replaced, it's retired until `depth` drops back to 0,
+        // which it can't until this call returns. Neither can be changed by
+        // another thread, per this function's contract.
+        f(unsafe { &*plugin })
+    }
+}
+
+/// Used by [`setup`] to check that `T` implements [`Plugin`] (and [`Hooks`]).
+#[doc(hidden)]
+pub const fn assert_plugin<T: Plugin>() {}
+
+/// Used by [`command`] to declare a command, which is added when the plugin is
+/// initialized.
+#[doc(hidden)]
+pub fn declare_command(name: &'static str, handler: fn(&str)) {
+    crate::mq::commands::declare(name, handler);
+}
+
+/// The name of the plugin type `T`, without its path or generic parameters.
+pub(crate) fn plugin_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Setup the Plugin type to be exported as an actual MacroQuest Plugin.
+///
+/// This performs all of the required setup to expose the plugin implementation
+/// from this crate in a way that MacroQuest will be able to understand and use
+/// it.
+///
+/// It has one form:
+///
+/// ```
+/// # #[derive(Debug)]
+/// # struct MyPlugin;
+/// # use macroquest::plugin::{Plugin, Hooks};
+/// # impl Plugin for MyPlugin {
+/// #     fn new() -> Self {
+/// #         MyPlugin
+/// #     }
+/// # }
+/// # impl Hooks for MyPlugin {}
+/// macroquest::plugin::setup!(MyPlugin);
+/// ```
+///
+/// Which registers the given type as a MacroQuest plugin, exporting all of the
+/// required symbols in the resulting DLL, setups up our own internal state
+/// required to execute the plugin hooks, etc.
+///
+/// Or a second form, for a [`DynPlugin`]:
+///
+/// ```
+/// # use macroquest::plugin::{DynPlugin, Hooks};
+/// # struct MyPlugin;
+/// # impl Hooks for MyPlugin {}
+/// fn new_plugin() -> DynPlugin {
+///     Box::new(MyPlugin)
+/// }
+///
+/// macroquest::plugin::setup!(dyn Hooks = new_plugin);
+/// ```
+///
+/// Which registers the plugin created by the given function, named after the
+/// crate rather than a type. As which hooks the plugin implements isn't known
+/// until then, every hook is exported, so the [`hooks`] macro isn't used.
+///
+/// Every path in the generated code is fully qualified, and its internal state
+/// is kept in a `__MACROQUEST_PLUGIN` static, so the plugin's crate is free to
+/// define (or import) items with the same names as anything it uses.
+///
+/// ```
+/// # #[derive(Debug)]
+/// # struct MyPlugin;
+/// # use macroquest::plugin::{Plugin, Hooks};
+/// # impl Plugin for MyPlugin {
+/// #     fn new() -> Self {
+/// #         MyPlugin
+/// #     }
+/// # }
+/// static PLUGIN: &str = "MyPlugin";
+///
+/// struct Option;
+/// struct ArcPluginOption;
+/// #[allow(non_camel_case_types)]
+/// struct bool;
+/// mod std {}
+///
+/// #[macroquest::plugin::hooks]
+/// impl Hooks for MyPlugin {
+///     fn begin_zone(&self) {}
+/// }
+///
+/// macroquest::plugin::setup!(MyPlugin);
+/// ```
+///
+/// A type that doesn't implement [`Plugin`] and [`Hooks`] fails to compile,
+/// with an error that says it must implement them.
+///
+/// ```compile_fail
+/// struct NotAPlugin;
+///
+/// macroquest::plugin::setup!(NotAPlugin);
+/// ```
+#[doc(hidden)]
+#[allow(clippy::module_name_repetitions)]
+#[macro_export]
+macro_rules! __plugin_setup {
+    ($plugin_type:ident) => {
+        // Checked on its own first, so that a type that isn't a plugin gets an
+        // error saying so, rather than one from deep within the code below.
+        const _: () = ::macroquest::plugin::assert_plugin::<$plugin_type>();
+
+        $crate::__plugin_setup!(
+            impl $plugin_type,
+            ::macroquest::plugin::ArcPluginOption::new()
+        );
+    };
+
+    (dyn Hooks = $new:path) => {
+        $crate::__plugin_setup!(
+            impl ::macroquest::plugin::DynPlugin,
+            ::macroquest::plugin::ArcPluginOption::with_constructor(
+                || ::std::env!("CARGO_CRATE_NAME"),
+                $new,
+            )
+        );
+
+        // Which hooks the plugin implements isn't known until it's created, so
+        // every other hook is exported too.
+        ::macroquest::plugin::hook!(OnDrawHUD(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(SetGameState(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnWriteChatColor(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnIncomingChat(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnAddSpawn(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnRemoveSpawn(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnAddGroundItem(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnRemoveGroundItem(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnBeginZone(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnEndZone(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnZoned(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnMacroStart(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnMacroStop(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnLoadPlugin(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnUnloadPlugin(__MACROQUEST_PLUGIN));
+    };
+
+    (impl $plugin_type:ty, $global:expr) => {
+        // MacroQuest requires a symbol exported named this to validate that a plugin
+        // was compiled for "MQNext", which is the only MacroQuest at this point in
+        // time.
+        #[no_mangle]
+        pub static IsBuiltForNext: ::std::primitive::bool = ::macroquest::is_mq_next();
+
+        // MacroQuest requires a symbol exported named this, that is used a stand in for
+        // "version" of the EverQuest binary, which is compromised of the build date and
+        // build time of the eqgame.exe.
+        #[no_mangle]
+        pub static EverQuestVersion: ::macroquest::EQVersion =
+            ::macroquest::eq_version();
+
+        // MacroQuest will look for this symbol, which is a pointer to a mq::MQPlugin,
+        // and if it exists, will set the value of that pointer to the mq::MQPlugin
+        // instance that it has created for the given plugin.
+        #[no_mangle]
+        pub static mut ThisPlugin: ::std::option::Option<
+            &::macroquest::ffi::mq::MQPlugin,
+        > = ::std::option::Option::None;
+
+        // We need to store our plugin instance somewhere so that our hook methods
+        // can access it to call the implemented hook method on that plugin, so
+        // we'll use this global to do that. It's named so that it's unlikely to
+        // collide with anything in the plugin's own crate, and the
+        // macroquest::plugin::hooks proc macro refers to it by this name.
+        static __MACROQUEST_PLUGIN: ::macroquest::plugin::ArcPluginOption<
+            $plugin_type,
+        > = $global;
+
+        // We always setup hooks for InitializePlugin, ShutdownPlugin, OnPulse,
+        // OnUpdateImGui, OnCleanUI, and OnReloadUI as we have our own logic that
+        // needs to happen during those hooks, regardless of whether the plugin
+        // itself has any logic there.
+        //
+        // If the plugin hasn't implemented these, then the default no-op
+        // implementations will be used (and should be optimized out completely).
+        ::macroquest::plugin::hook!(InitializePlugin(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(ShutdownPlugin(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnPulse(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnUpdateImGui(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnCleanUI(__MACROQUEST_PLUGIN));
+        ::macroquest::plugin::hook!(OnReloadUI(__MACROQUEST_PLUGIN));
+    };
+}
+
+// This is an internal macro, but it has to be exported and made public so that
+// the macroquest::plugin::hooks proc macro can generate code in our user's
+// crate that calls this, so we'll leave it undocumented at least so people
+// don't see it in the docs.
+//
+// Every hook calls the plugin's with() in an unsafe block, which is sound as
+// MacroQuest calls all of them on the game's main thread, which is also where
+// InitializePlugin and ShutdownPlugin set and unset the plugin.
+#[doc(hidden)]
+#[allow(clippy::module_name_repetitions)]
+#[macro_export]
+macro_rules! __plugin_hook {
+    (InitializePlugin($global:ident)) => {
+        $crate::__plugin_hook!(impl init $global InitializePlugin initialize);
+    };
+
+    (ShutdownPlugin($global:ident)) => {
+        $crate::__plugin_hook!(impl shutdown $global ShutdownPlugin shutdown);
+    };
+
+    (OnCleanUI($global:ident)) => {
+        $crate::__plugin_hook!(impl wrapped $global clean_ui OnCleanUI clean_ui);
+    };
+
+    (OnReloadUI($global:ident)) => {
+        $crate::__plugin_hook!(impl wrapped $global reload_ui OnReloadUI reload_ui);
+    };
+
+    (OnDrawHUD($global:ident)) => {
+        $crate::__plugin_hook!(impl hud $global OnDrawHUD draw_hud draw_hud_frame);
+    };
+
+    (OnPulse($global:ident)) => {
+        $crate::__plugin_hook!(impl pulse $global OnPulse pulse);
+    };
+
+    (OnBeginZone($global:ident)) => {
+        $crate::__plugin_hook!(impl simple $global OnBeginZone begin_zone);
+    };
+
+    (OnEndZone($global:ident)) => {
+        $crate::__plugin_hook!(impl simple $global OnEndZone end_zone);
+    };
+
+    (OnZoned($global:ident)) => {
+        $crate::__plugin_hook!(impl simple $global OnZoned zoned);
+    };
+
+    (OnUpdateImGui($global:ident)) => {
+        $crate::__plugin_hook!(impl wrapped $global imgui_frame OnUpdateImGui update_imgui);
+    };
+
+    (SetGameState($global:ident)) => {
+        $crate::__plugin_hook!(impl gamestate $global SetGameState game_state);
+    };
+
+    (OnWriteChatColor($global:ident)) => {
+        $crate::__plugin_hook!(impl chat $global OnWriteChatColor write_chat () = ());
+    };
+
+    (OnIncomingChat($global:ident)) => {
+        $crate::__plugin_hook!(impl chat $global OnIncomingChat incoming_chat ::std::primitive::bool = false);
+    };
+
+    (OnAddSpawn($global:ident)) => {
+        $crate::__plugin_hook!(impl spawn $global OnAddSpawn add_spawn);
+    };
+
+    (OnRemoveSpawn($global:ident)) => {
+        $crate::__plugin_hook!(impl spawn $global OnRemoveSpawn remove_spawn);
+    };
+
+    (OnAddGroundItem($global:ident)) => {
+        $crate::__plugin_hook!(impl ground $global OnAddGroundItem add_ground_item);
+    };
+
+    (OnRemoveGroundItem($global:ident)) => {
+        $crate::__plugin_hook!(impl ground $global OnRemoveGroundItem remove_ground_item);
+    };
+
+    (OnMacroStart($global:ident)) => {
+        $crate::__plugin_hook!(impl string $global OnMacroStart macro_start);
+    };
+
+    (OnMacroStop($global:ident)) => {
+        $crate::__plugin_hook!(impl string $global OnMacroStop macro_stop);
+    };
+
+    (OnLoadPlugin($global:ident)) => {
+        $crate::__plugin_hook!(impl string $global OnLoadPlugin plugin_load);
+    };
+
+    (OnUnloadPlugin($global:ident)) => {
+        $crate::__plugin_hook!(impl string $global OnUnloadPlugin plugin_unload);
+    };
+
+    (impl init $global:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook() {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                $global.set();
+                $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin)) })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl shutdown $global:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook() {
+            let results = [
+                $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                    $global.begin_shutdown();
+                    $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin)) });
+                })),
+                // This is kept out of the plugin's shutdown, so that everything
+                // the plugin registered is still removed if that panics.
+                ::std::panic::catch_unwind(|| $global.unset()),
+            ];
+
+            for result in results {
+                if let ::std::result::Result::Err(error) = result {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl pulse $global:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook() {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                $global.benchmark(|| {
+                    $global.pulse();
+                    $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin)) })
+                })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl wrapped $global:ident $wrapper:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook() {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                $global.$wrapper(|| {
+                    $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin)) })
+                })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl hud $global:ident $macroquest_hook:ident $plugin_hook:ident $frame_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook() {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                $global.hud_frame(|frame| {
+                    $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| {
+                        ::macroquest::plugin::Hooks::$plugin_hook(plugin);
+                        ::macroquest::plugin::Hooks::$frame_hook(plugin, frame)
+                    }) })
+                })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl simple $global:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook() {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin)) })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl gamestate $global:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook(c_state: ::std::ffi::c_int) {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin, ::macroquest::eq::GameState::from(c_state))) })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl chat $global:ident $macroquest_hook:ident $plugin_hook:ident $rtype:ty = $rvalue:expr) => {
+        #[no_mangle]
+        pub unsafe extern "C" fn $macroquest_hook(
+            ptr: *const ::std::os::raw::c_char,
+            color: ::std::ffi::c_ulong,
+        ) -> $rtype {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                let c_str = ::std::ffi::CStr::from_ptr(ptr);
+                let r_str = c_str.to_string_lossy();
+
+                let color = ::std::primitive::i32::try_from(color)
+                    .expect("color parameter couldn't convert to i32 from u32");
+
+                $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin, r_str.as_ref(), ::macroquest::eq::ChatColor::from(color))) })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                    $rvalue
+                }
+            }
+        }
+    };
+
+    (impl spawn $global:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook(pc: &::macroquest::ffi::eqlib::PlayerClient) {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                let spawn = ::std::convert::AsRef::<::macroquest::eq::Spawn>::as_ref(pc);
+
+                $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin, spawn)) })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl ground $global:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $macroquest_hook(eq_item: &::macroquest::ffi::eqlib::EQGroundItem) {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                let item = ::std::convert::AsRef::<::macroquest::eq::GroundItem>::as_ref(eq_item);
+
+                $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin, item)) })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+
+    (impl string $global:ident $macroquest_hook:ident $plugin_hook:ident) => {
+        #[no_mangle]
+        pub unsafe extern "C" fn $macroquest_hook(ptr: *const ::std::os::raw::c_char) {
+            let result = $global.measure(::std::stringify!($macroquest_hook), || ::std::panic::catch_unwind(|| {
+                let c_str = ::std::ffi::CStr::from_ptr(ptr);
+                let r_str = c_str.to_string_lossy();
+
+                $global.span(::std::stringify!($macroquest_hook), || unsafe { $global.with(|plugin| ::macroquest::plugin::Hooks::$plugin_hook(plugin, r_str.as_ref())) })
+            }));
+
+            match result {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err(error) => {
+                    ::macroquest::log::error!(?error, hook = ::std::stringify!($plugin_hook), "caught an unwind");
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::hint::black_box;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestPlugin {
+        value: u32,
+    }
+
+    impl Plugin for TestPlugin {
+        fn new() -> Self {
+            TestPlugin { value: 42 }
+        }
+    }
+
+    impl Hooks for TestPlugin {}
+
+    // Stores the plugin directly, as set() also sets up state that needs the
+    // game.
+    fn initialized() -> ArcPluginOption<TestPlugin> {
+        let plugin = ArcPluginOption::new();
+        plugin.replace(Some(Arc::new(TestPlugin::new())));
+        plugin
+    }
+
+    // Each test only uses its plugin on its own thread, which is all that
+    // with() needs to be sound.
+    fn with<T: Hooks, R>(plugin: &ArcPluginOption<T>, f: impl FnOnce(&T) -> R) -> R {
+        // SAFETY: See above.
+        unsafe { plugin.with(f) }
+    }
+
+    fn with_panic_message(plugin: &ArcPluginOption<TestPlugin>) -> Option<String> {
+        let result =
+            panic::catch_unwind(AssertUnwindSafe(|| with(plugin, |p| p.value)));
+        result.err().map(|error| panic_message(&*error).to_owned())
+    }
+
+    #[test]
+    fn test_with_initialized() {
+        let plugin = initialized();
+
+        assert_eq!(with(&plugin, |p| p.value), 42);
+        assert_eq!(with_panic_message(&plugin), None);
+    }
+
+    #[test]
+    fn test_with_uninitialized() {
+        let plugin = ArcPluginOption::<TestPlugin>::new();
+
+        assert_eq!(
+            with_panic_message(&plugin).as_deref(),
+            Some("hook called without plugin initialized")
+        );
+    }
+
+    #[test]
+    fn test_with_after_shutdown() {
+        let plugin = initialized();
+        plugin.replace(None);
+
+        assert_eq!(
+            with_panic_message(&plugin).as_deref(),
+            Some("hook called without plugin initialized")
+        );
+    }
+
+    #[test]
+    fn test_with_keeps_plugin_alive() {
+        let plugin = initialized();
+
+        // Shutting down while a hook is still running (such as a hook that
+        // unloads its own plugin) must not free the plugin out from under it.
+        let value = with(&plugin, |p| {
+            plugin.replace(None);
+            p.value
+        });
+
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn test_with_drops_plugin_after_returning() {
+        let plugin = initialized();
+        let weak = Arc::downgrade(&plugin.plugin.load_full().unwrap());
+
+        // The plugin that was kept alive for the hook is dropped once it
+        // returns, rather than being leaked.
+        with(&plugin, |_| {
+            plugin.replace(None);
+            assert!(weak.upgrade().is_some());
+        });
+
+        assert!(weak.upgrade().is_none());
+    }
+
+    // Getting at the plugin through with() has to beat loading it from the
+    // ArcSwapOption that owns it (which the hooks used to do), or it isn't
+    // worth its unsafe.
+    #[test]
+    fn test_with_faster_than_load() {
+        const CALLS: u32 = 100_000;
+
+        // The fastest of several runs, which is the least disturbed by
+        // anything else running at the same time.
+        fn fastest(mut f: impl FnMut()) -> Duration {
+            (0..10)
+                .map(|_| {
+                    let start = Instant::now();
+                    for _ in 0..CALLS {
+                        f();
+                    }
+                    start.elapsed()
+                })
+                .min()
+                .unwrap()
+        }
+
+        let plugin = initialized();
+
+        let loaded = fastest(|| {
+            black_box(plugin.plugin.load().as_deref().map(|p| p.value));
+        });
+        let borrowed = fastest(|| {
+            black_box(with(&plugin, |p| p.value));
+        });
+
+        assert!(
+            borrowed < loaded,
+            "{CALLS} calls took {borrowed:?} with with(), and {loaded:?} loading the plugin"
+        );
+    }
+
+    // Records the name of every hook that is called on it.
+    #[derive(Default)]
+    struct Calls(Mutex<Vec<&'static str>>);
+
+    impl Calls {
+        fn push(&self, hook: &'static str) {
+            self.0.lock().unwrap().push(hook);
+        }
+    }
+
+    impl Hooks for Calls {
+        fn initialize(&self) {
+            self.push("initialize");
+        }
+
+        fn shutdown(&self) {
+            self.push("shutdown");
+        }
+
+        fn clean_ui(&self) {
+            self.push("clean_ui");
+        }
+
+        fn reload_ui(&self) {
+            self.push("reload_ui");
+        }
+
+        fn draw_hud(&self) {
+            self.push("draw_hud");
+        }
+
+        fn draw_hud_frame(&self, _frame: &HudFrame) {
+            self.push("draw_hud_frame");
+        }
+
+        fn game_state(&self, _state: eq::GameState) {
+            self.push("game_state");
+        }
+
+        fn pulse(&self) {
+            self.push("pulse");
+        }
+
+        fn write_chat(&self, _line: &str, _color: eq::ChatColor) {
+            self.push("write_chat");
+        }
+
+        fn incoming_chat(&self, _line: &str, _color: eq::ChatColor) -> bool {
+            self.push("incoming_chat");
+            true
+        }
+
+        fn begin_zone(&self) {
+            self.push("begin_zone");
+        }
+
+        fn end_zone(&self) {
+            self.push("end_zone");
+        }
+
+        fn zoned(&self) {
+            self.push("zoned");
+        }
+
+        fn update_imgui(&self) {
+            self.push("update_imgui");
+        }
+
+        fn macro_start(&self, _name: &str) {
+            self.push("macro_start");
+        }
+
+        fn macro_stop(&self, _name: &str) {
+            self.push("macro_stop");
+        }
+
+        fn plugin_load(&self, _name: &str) {
+            self.push("plugin_load");
+        }
+
+        fn plugin_unload(&self, _name: &str) {
+            self.push("plugin_unload");
+        }
+    }
+
+    // Every hook, other than those taking a spawn or ground item, which only
+    // the game can create.
+    fn every_event() -> Vec<event::Event> {
+        use event::Event;
+
+        let name = || "name".to_owned();
+        vec![
+            Event::Initialize,
+            Event::Shutdown,
+            Event::CleanUi,
+            Event::ReloadUi,
+            Event::DrawHud,
+            Event::GameState { state: 5 },
+            Event::Pulse,
+            Event::WriteChat {
+                line:  name(),
+                color: 273,
+            },
+            Event::IncomingChat {
+                line:  name(),
+                color: 273,
+            },
+            Event::BeginZone,
+            Event::EndZone,
+            Event::Zoned,
+            Event::UpdateImgui,
+            Event::MacroStart { name: name() },
+            Event::MacroStop { name: name() },
+            Event::PluginLoad { name: name() },
+            Event::PluginUnload { name: name() },
+        ]
+    }
+
+    fn dispatch_every_event<H: Hooks>(hooks: &H) {
+        for event in every_event() {
+            event.dispatch(hooks);
+        }
+    }
+
+    #[test]
+    fn test_box_forwards_hooks() {
+        let expected = Calls::default();
+        dispatch_every_event(&expected);
+
+        let calls = Arc::new(Calls::default());
+        let plugin: DynPlugin = Box::new(Arc::clone(&calls));
+        dispatch_every_event(&plugin);
+
+        assert_eq!(*calls.0.lock().unwrap(), *expected.0.lock().unwrap());
+        assert!(plugin.incoming_chat("hello", eq::ChatColor::from(273)));
+    }
+
+    #[test]
+    fn test_arc_forwards_hooks() {
+        let expected = Calls::default();
+        dispatch_every_event(&expected);
+
+        let calls = Arc::new(Calls::default());
+        dispatch_every_event(&Arc::clone(&calls));
+
+        assert_eq!(*calls.0.lock().unwrap(), *expected.0.lock().unwrap());
+        assert_eq!(calls.0.lock().unwrap().len(), every_event().len() + 1);
+    }
+
+    #[test]
+    fn test_with_constructor() {
+        fn new_plugin() -> DynPlugin {
+            Box::new(TestPlugin::new())
+        }
+
+        let plugin = ArcPluginOption::with_constructor(|| "Modes", new_plugin);
+        plugin.replace(Some(Arc::new((plugin.new)())));
+
+        assert_eq!((plugin.name)(), "Modes");
+        let handled = with(&plugin, |p| {
+            p.incoming_chat("hello", eq::ChatColor::from(273))
+        });
+        assert!(!handled);
+    }
+
+    #[test]
+    fn test_new_names_after_type() {
+        let plugin = ArcPluginOption::<TestPlugin>::new();
+
+        assert_eq!((plugin.name)(), "TestPlugin");
+        assert_eq!((plugin.new)().value, 42);
+    }
+
+    // A subscriber that records the fields of every span, and how many times
+    // they've been entered and exited.
+    #[derive(Default)]
+    struct Capture {
+        spans: Mutex<Vec<CapturedSpan>>,
+    }
+
+    impl Capture {
+        fn update<F: FnOnce(&mut CapturedSpan)>(&self, span: &Id, f: F) {
+            let index = usize::try_from(span.into_u64() - 1).unwrap();
+            f(&mut self.spans.lock().unwrap()[index]);
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct CapturedSpan {
+        name:    &'static str,
+        fields:  Vec<(&'static str, String)>,
+        entered: u32,
+        exited:  u32,
+    }
+
+    impl Visit for CapturedSpan {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields.push((field.name(), value.to_owned()));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.fields.push((field.name(), format!("{value:?}")));
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+            let mut span = CapturedSpan {
+                name: attrs.metadata().name(),
+                ..CapturedSpan::default()
+            };
+            attrs.record(&mut span);
+
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span);
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, span: &Id) {
+            self.update(span, |span| span.entered += 1);
+        }
+
+        fn exit(&self, span: &Id) {
+            self.update(span, |span| span.exited += 1);
+        }
+    }
+
+    fn captured<F: FnOnce()>(f: F) -> Vec<CapturedSpan> {
+        let capture = Arc::new(Capture::default());
+        tracing::subscriber::with_default(Arc::clone(&capture), f);
+
+        let spans = capture.spans.lock().unwrap();
+        spans.clone()
+    }
+
+    #[cfg(feature = "hook-spans")]
+    #[test]
+    fn test_span_fields() {
+        let plugin = initialized();
+
+        let mut value = 0;
+        let spans = captured(|| {
+            value = plugin.span("OnPulse", || with(&plugin, |p| p.value));
+        });
+
+        assert_eq!(value, 42);
+        assert_eq!(
+            spans,
+            [CapturedSpan {
+                name:    "hook",
+                fields:  vec![
+                    ("hook", "OnPulse".to_owned()),
+                    ("plugin", "TestPlugin".to_owned()),
+                ],
+                entered: 1,
+                exited:  1,
+            }]
+        );
+    }
+
+    #[cfg(feature = "hook-spans")]
+    #[test]
+    fn test_span_closed_on_panic() {
+        let plugin = ArcPluginOption::<TestPlugin>::new();
+
+        // The hooks enter the span inside of catch_unwind, so it's exited as
+        // the panic unwinds out of it.
+        let spans = captured(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                plugin.span("OnPulse", || with(&plugin, |p| p.value))
+            }));
+            assert!(result.is_err());
+        });
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!((spans[0].entered, spans[0].exited), (1, 1));
+    }
+
+    #[cfg(not(feature = "hook-spans"))]
+    #[test]
+    fn test_span_disabled() {
+        let plugin = initialized();
+
+        let spans = captured(|| {
+            assert_eq!(plugin.span("OnPulse", || with(&plugin, |p| p.value)), 42);
+        });
+
+        assert!(spans.is_empty());
+    }
+}
@@ -0,0 +1,694 @@
+//! Recording and replaying the sequence of hooks that MacroQuest calls.
+//!
+//! Logic that depends on the exact order that MacroQuest calls hooks in (most
+//! notably around zoning) can be hard to reproduce. A [`Recorder`] wraps a
+//! [`Hooks`] implementation, and writes every hook that is called, along with
+//! a snapshot of its arguments, as a line of JSON. A [`Replayer`] reads those
+//! lines back, and [`replay()`] drives any [`Hooks`] implementation with the
+//! same sequence, whether that's in a test or in game.
+//!
+//! Each line is a JSON object with a `"v"` key holding the format version
+//! ([`FORMAT_VERSION`]), and a `"hook"` key holding the name of the hook (the
+//! same as the name of the [`Hooks`] method), along with a key for each of the
+//! hook's arguments. For example:
+//!
+//! ```json
+//! {"v":1,"hook":"game_state","state":5}
+//! {"v":1,"hook":"write_chat","line":"Hello World","color":273}
+//! ```
+//!
+//! Spawns and ground items are recorded as snapshots of their data, however
+//! the hooks that take them can't be replayed, as the [`eq::Spawn`] and
+//! [`eq::GroundItem`] types can only be created by the game itself. These
+//! events are still produced by the [`Replayer`], so they can be inspected or
+//! handled by hand, but [`replay()`] skips them.
+//!
+//! # Examples
+//!
+//! Recording every hook for a plugin into `MyPlugin.hooks.jsonl` in the
+//! MacroQuest logs directory.
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! use macroquest::plugin::recorder::Recorder;
+//!
+//! struct MyPlugin;
+//!
+//! impl Plugin for MyPlugin {
+//!     fn new() -> Self {
+//!         MyPlugin
+//!     }
+//! }
+//!
+//! #[macroquest::plugin::hooks]
+//! impl Hooks for MyPlugin {
+//!     fn zoned(&self) {}
+//! }
+//!
+//! type RecordedPlugin = Recorder<MyPlugin>;
+//! macroquest::plugin::setup!(RecordedPlugin);
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::{error, fmt};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+pub use super::event::{Event, GroundItemSnapshot, SpawnSnapshot};
+use super::{Hooks, Plugin};
+use crate::eq;
+use crate::log::error;
+use crate::mq::hud::HudFrame;
+
+/// The version of the format that each line is written in.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    v:     u32,
+    #[serde(flatten)]
+    event: Event,
+}
+
+/// A [`Hooks`] implementation that records every hook that is called, before
+/// passing it through to the wrapped implementation.
+///
+/// Only the hooks that are exported by the wrapped plugin are ever called, so
+/// only those will be recorded. The hooks that are called every frame
+/// ([`Hooks::pulse()`], [`Hooks::draw_hud()`], and [`Hooks::update_imgui()`])
+/// would drown out everything else, so they aren't recorded unless enabled
+/// with [`Recorder::record_per_frame()`].
+///
+/// If writing a line fails, the error is logged, and nothing further will be
+/// recorded.
+pub struct Recorder<H> {
+    inner:     H,
+    output:    Mutex<Option<Box<dyn Write + Send>>>,
+    per_frame: bool,
+}
+
+impl<H: Hooks> Recorder<H> {
+    /// Record the hooks called on `inner` into `output`.
+    #[must_use]
+    pub fn new<W>(inner: H, output: W) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        Recorder {
+            inner,
+            output: Mutex::new(Some(Box::new(output))),
+            per_frame: false,
+        }
+    }
+
+    /// Record the hooks called on `inner` into a file named `file_name` in
+    /// the MacroQuest logs directory, replacing it if it already exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be created.
+    pub fn to_logs(inner: H, file_name: &str) -> io::Result<Self> {
+        let file = File::create(crate::mq::paths().logs().join(file_name))?;
+        Ok(Recorder::new(inner, BufWriter::new(file)))
+    }
+
+    /// Set whether the hooks that are called every frame are recorded.
+    #[must_use]
+    pub fn record_per_frame(mut self, enabled: bool) -> Self {
+        self.per_frame = enabled;
+        self
+    }
+
+    /// The wrapped [`Hooks`] implementation.
+    #[must_use]
+    pub fn inner(&self) -> &H {
+        &self.inner
+    }
+
+    fn record(&self, event: Event) {
+        if event.is_per_frame() && !self.per_frame {
+            return;
+        }
+
+        let mut output = self.output.lock();
+        let Some(writer) = output.as_mut()
+        else {
+            return;
+        };
+
+        // Each line is flushed as it's written, so that the recording is
+        // complete even if the game crashes.
+        let record = Record {
+            v: FORMAT_VERSION,
+            event,
+        };
+        let result = serde_json::to_writer(&mut *writer, &record)
+            .map_err(io::Error::from)
+            .and_then(|()| writer.write_all(b"\n"))
+            .and_then(|()| writer.flush());
+
+        if let Err(error) = result {
+            error!(%error, "could not record hook, recording has been stopped");
+            output.take();
+        }
+    }
+}
+
+impl<H: Plugin> Plugin for Recorder<H> {
+    /// Records into `{type name}.hooks.jsonl` in the MacroQuest logs directory.
+    fn new() -> Self {
+        let file_name = format!("{}.hooks.jsonl", super::plugin_name::<H>());
+
+        let inner = H::new();
+        match File::create(crate::mq::paths().logs().join(&file_name)) {
+            Ok(file) => Recorder::new(inner, BufWriter::new(file)),
+            Err(error) => {
+                error!(%error, %file_name, "could not create hook recording");
+                Recorder {
+                    inner,
+                    output: Mutex::new(None),
+                    per_frame: false,
+                }
+            }
+        }
+    }
+}
+
+impl<H: Hooks> Hooks for Recorder<H> {
+    fn initialize(&self) {
+        self.record(Event::Initialize);
+        self.inner.initialize();
+    }
+
+    fn shutdown(&self) {
+        self.record(Event::Shutdown);
+        self.inner.shutdown();
+    }
+
+    fn clean_ui(&self) {
+        self.record(Event::CleanUi);
+        self.inner.clean_ui();
+    }
+
+    fn reload_ui(&self) {
+        self.record(Event::ReloadUi);
+        self.inner.reload_ui();
+    }
+
+    fn draw_hud(&self) {
+        self.record(Event::DrawHud);
+        self.inner.draw_hud();
+    }
+
+    fn draw_hud_frame(&self, frame: &HudFrame) {
+        // This is recorded as part of draw_hud, which is always called with it.
+        self.inner.draw_hud_frame(frame);
+    }
+
+    fn game_state(&self, state: eq::GameState) {
+        self.record(Event::GameState {
+            state: state.into(),
+        });
+        self.inner.game_state(state);
+    }
+
+    fn pulse(&self) {
+        self.record(Event::Pulse);
+        self.inner.pulse();
+    }
+
+    fn write_chat(&self, line: &str, color: eq::ChatColor) {
+        self.record(Event::WriteChat {
+            line:  line.to_owned(),
+            color: color.into(),
+        });
+        self.inner.write_chat(line, color);
+    }
+
+    fn incoming_chat(&self, line: &str, color: eq::ChatColor) -> bool {
+        self.record(Event::IncomingChat {
+            line:  line.to_owned(),
+            color: color.into(),
+        });
+        self.inner.incoming_chat(line, color)
+    }
+
+    fn add_spawn(&self, spawn: &eq::Spawn) {
+        self.record(Event::AddSpawn {
+            spawn: spawn.into(),
+        });
+        self.inner.add_spawn(spawn);
+    }
+
+    fn remove_spawn(&self, spawn: &eq::Spawn) {
+        self.record(Event::RemoveSpawn {
+            spawn: spawn.into(),
+        });
+        self.inner.remove_spawn(spawn);
+    }
+
+    fn add_ground_item(&self, item: &eq::GroundItem) {
+        self.record(Event::AddGroundItem { item: item.into() });
+        self.inner.add_ground_item(item);
+    }
+
+    fn remove_ground_item(&self, item: &eq::GroundItem) {
+        self.record(Event::RemoveGroundItem { item: item.into() });
+        self.inner.remove_ground_item(item);
+    }
+
+    fn begin_zone(&self) {
+        self.record(Event::BeginZone);
+        self.inner.begin_zone();
+    }
+
+    fn end_zone(&self) {
+        self.record(Event::EndZone);
+        self.inner.end_zone();
+    }
+
+    fn zoned(&self) {
+        self.record(Event::Zoned);
+        self.inner.zoned();
+    }
+
+    fn update_imgui(&self) {
+        self.record(Event::UpdateImgui);
+        self.inner.update_imgui();
+    }
+
+    fn macro_start(&self, name: &str) {
+        self.record(Event::MacroStart {
+            name: name.to_owned(),
+        });
+        self.inner.macro_start(name);
+    }
+
+    fn macro_stop(&self, name: &str) {
+        self.record(Event::MacroStop {
+            name: name.to_owned(),
+        });
+        self.inner.macro_stop(name);
+    }
+
+    fn plugin_load(&self, name: &str) {
+        self.record(Event::PluginLoad {
+            name: name.to_owned(),
+        });
+        self.inner.plugin_load(name);
+    }
+
+    fn plugin_unload(&self, name: &str) {
+        self.record(Event::PluginUnload {
+            name: name.to_owned(),
+        });
+        self.inner.plugin_unload(name);
+    }
+}
+
+impl<H: fmt::Debug> fmt::Debug for Recorder<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("inner", &self.inner)
+            .field("recording", &self.output.lock().is_some())
+            .field("per_frame", &self.per_frame)
+            .finish()
+    }
+}
+
+/// An error reading a recording.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// Reading from the recording failed.
+    Io(io::Error),
+
+    /// A line of the recording could not be parsed.
+    Parse {
+        /// The line number (starting at 1) that could not be parsed.
+        line:  usize,
+        /// The underlying parse error.
+        error: serde_json::Error,
+    },
+
+    /// A line of the recording was written in an unsupported format version.
+    UnsupportedVersion {
+        /// The line number (starting at 1) with the unsupported version.
+        line:    usize,
+        /// The version that the line was written in.
+        version: u32,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "could not read recording: {error}"),
+            ReplayError::Parse { line, error } => {
+                write!(f, "could not parse line {line} of recording: {error}")
+            }
+            ReplayError::UnsupportedVersion { line, version } => write!(
+                f,
+                "line {line} of recording has unsupported version {version} (expected \
+                 {FORMAT_VERSION})"
+            ),
+        }
+    }
+}
+
+impl error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ReplayError::Io(error) => Some(error),
+            ReplayError::Parse { error, .. } => Some(error),
+            ReplayError::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+/// An iterator over the [`Event`]s in a recording.
+///
+/// Blank lines are ignored.
+#[derive(Debug)]
+pub struct Replayer<R> {
+    lines: io::Lines<R>,
+    line:  usize,
+}
+
+impl<R: BufRead> Replayer<R> {
+    /// Read a recording from `reader`.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Replayer {
+            lines: reader.lines(),
+            line:  0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Replayer<R> {
+    type Item = Result<Event, ReplayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => return Some(Err(ReplayError::Io(error))),
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            return Some(parse(&line, self.line));
+        }
+    }
+}
+
+fn parse(text: &str, line: usize) -> Result<Event, ReplayError> {
+    // The version is checked before parsing the whole record, so that a future
+    // version gets a useful error, rather than a parse error.
+    #[derive(Deserialize)]
+    struct Version {
+        v: u32,
+    }
+
+    let Version { v } = serde_json::from_str(text)
+        .map_err(|error| ReplayError::Parse { line, error })?;
+    if v != FORMAT_VERSION {
+        return Err(ReplayError::UnsupportedVersion { line, version: v });
+    }
+
+    let record: Record = serde_json::from_str(text)
+        .map_err(|error| ReplayError::Parse { line, error })?;
+
+    Ok(record.event)
+}
+
+/// The result of a successful [`replay()`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ReplaySummary {
+    /// The number of events that were dispatched to the hooks.
+    pub dispatched: usize,
+    /// The number of events that could not be dispatched (see
+    /// [`Event::dispatch()`]).
+    pub skipped:    usize,
+}
+
+/// Read a recording from `reader`, and call each recorded hook on `hooks`, in
+/// order.
+///
+/// # Errors
+///
+/// Returns an error if the recording could not be read or parsed, after
+/// having called all of the hooks recorded prior to the error.
+pub fn replay<R, H>(reader: R, hooks: &H) -> Result<ReplaySummary, ReplayError>
+where
+    R: BufRead,
+    H: Hooks + ?Sized,
+{
+    let mut summary = ReplaySummary::default();
+
+    for event in Replayer::new(reader) {
+        if event?.dispatch(hooks) {
+            summary.dispatched += 1;
+        }
+        else {
+            summary.skipped += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct Calls(Mutex<Vec<String>>);
+
+    impl Calls {
+        fn push(&self, call: impl Into<String>) {
+            self.0.lock().push(call.into());
+        }
+    }
+
+    impl Hooks for Calls {
+        fn initialize(&self) {
+            self.push("initialize");
+        }
+
+        fn shutdown(&self) {
+            self.push("shutdown");
+        }
+
+        fn game_state(&self, state: eq::GameState) {
+            self.push(format!("game_state {state:?}"));
+        }
+
+        fn pulse(&self) {
+            self.push("pulse");
+        }
+
+        fn incoming_chat(&self, line: &str, color: eq::ChatColor) -> bool {
+            self.push(format!("incoming_chat {line} {color:?}"));
+            true
+        }
+
+        fn begin_zone(&self) {
+            self.push("begin_zone");
+        }
+
+        fn end_zone(&self) {
+            self.push("end_zone");
+        }
+
+        fn zoned(&self) {
+            self.push("zoned");
+        }
+
+        fn plugin_unload(&self, name: &str) {
+            self.push(format!("plugin_unload {name}"));
+        }
+    }
+
+    fn drive(hooks: &dyn Hooks) {
+        hooks.initialize();
+        hooks.game_state(eq::GameState::InGame);
+        hooks.pulse();
+        assert!(hooks.incoming_chat(
+            "You have entered the Plane of Knowledge.",
+            eq::Channel::Say.into()
+        ));
+        hooks.begin_zone();
+        hooks.game_state(eq::GameState::from(42));
+        hooks.end_zone();
+        hooks.zoned();
+        hooks.plugin_unload("MQ2Rust");
+        hooks.shutdown();
+    }
+
+    #[test]
+    fn test_record_replay_round_trip() {
+        let buffer = Buffer::default();
+        let recorder = Recorder::new(Calls::default(), buffer.clone());
+        drive(&recorder);
+
+        let replayed = Calls::default();
+        let recording = buffer.0.lock().clone();
+        let summary = replay(recording.as_slice(), &replayed).unwrap();
+
+        // The pulse isn't recorded by default.
+        let mut expected = recorder.inner().0.lock().clone();
+        expected.retain(|call| call != "pulse");
+
+        assert_eq!(*replayed.0.lock(), expected);
+        assert_eq!(
+            summary,
+            ReplaySummary {
+                dispatched: expected.len(),
+                skipped:    0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_record_per_frame() {
+        let buffer = Buffer::default();
+        let recorder =
+            Recorder::new(Calls::default(), buffer.clone()).record_per_frame(true);
+        recorder.pulse();
+
+        let recording = buffer.0.lock().clone();
+        let events = Replayer::new(recording.as_slice())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(events, [Event::Pulse]);
+    }
+
+    #[test]
+    fn test_format_is_stable() {
+        let buffer = Buffer::default();
+        let recorder = Recorder::new(Calls::default(), buffer.clone());
+        recorder.game_state(eq::GameState::InGame);
+        recorder.incoming_chat("hello", eq::Channel::Say.into());
+        recorder.plugin_unload("MQ2Rust");
+
+        let recording = String::from_utf8(buffer.0.lock().clone()).unwrap();
+        assert_eq!(
+            recording,
+            concat!(
+                r#"{"v":1,"hook":"game_state","state":5}"#,
+                "\n",
+                r#"{"v":1,"hook":"incoming_chat","line":"hello","color":256}"#,
+                "\n",
+                r#"{"v":1,"hook":"plugin_unload","name":"MQ2Rust"}"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_spawn_events_are_skipped() {
+        let recording = concat!(
+            r#"{"v":1,"hook":"add_spawn","spawn":{"name":"a_rat"}}"#,
+            "\n\n",
+            r#"{"v":1,"hook":"zoned"}"#,
+            "\n",
+        );
+
+        let events = Replayer::new(recording.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            events,
+            [
+                Event::AddSpawn {
+                    spawn: SpawnSnapshot {
+                        name: "a_rat".into(),
+                    },
+                },
+                Event::Zoned,
+            ]
+        );
+
+        let calls = Calls::default();
+        let summary = replay(recording.as_bytes(), &calls).unwrap();
+        assert_eq!(*calls.0.lock(), ["zoned"]);
+        assert_eq!(
+            summary,
+            ReplaySummary {
+                dispatched: 1,
+                skipped:    1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let recording = r#"{"v":2,"hook":"something_new"}"#;
+
+        let error = Replayer::new(recording.as_bytes()).next().unwrap();
+        assert!(matches!(
+            error,
+            Err(ReplayError::UnsupportedVersion {
+                line:    1,
+                version: 2,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_reports_line() {
+        let recording = "{\"v\":1,\"hook\":\"zoned\"}\nnot json\n";
+
+        let mut replayer = Replayer::new(recording.as_bytes());
+        assert!(matches!(replayer.next(), Some(Ok(Event::Zoned))));
+        assert!(matches!(
+            replayer.next(),
+            Some(Err(ReplayError::Parse { line: 2, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_write_error_stops_recording() {
+        struct Broken;
+
+        impl Write for Broken {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let recorder = Recorder::new(Calls::default(), Broken);
+        recorder.zoned();
+        recorder.zoned();
+
+        assert!(recorder.output.lock().is_none());
+        assert_eq!(*recorder.inner().0.lock(), ["zoned", "zoned"]);
+    }
+}
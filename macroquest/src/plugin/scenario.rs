@@ -0,0 +1,394 @@
+//! Scripted sequences of hooks, for testing plugins without the game.
+//!
+//! The order that MacroQuest calls hooks in, particularly around zoning, is
+//! easy to get wrong, and hard to reproduce by hand. A [`Scenario`] is a
+//! canned sequence of [`Event`]s, in the order that is documented on
+//! [`Hooks`], with plausible synthetic data, which can be run against a plugin
+//! so that assertions can be made about the plugin's state afterwards.
+//!
+//! The [`eq::Spawn`](crate::eq::Spawn) and
+//! [`eq::GroundItem`](crate::eq::GroundItem) types can only be created by the
+//! game itself, so the spawn and ground item hooks are skipped by
+//! [`Scenario::run()`]. They are still present in [`Scenario::events()`], so
+//! they can be inspected or handled by hand.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::atomic::{AtomicU32, Ordering};
+//!
+//! use macroquest::plugin::scenario::Scenario;
+//! use macroquest::plugin::Hooks;
+//!
+//! #[derive(Default)]
+//! struct MyPlugin {
+//!     zones: AtomicU32,
+//! }
+//!
+//! impl Hooks for MyPlugin {
+//!     fn zoned(&self) {
+//!         self.zones.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let plugin = MyPlugin::default();
+//! Scenario::login_to_game()
+//!     .then(Scenario::zone("poknowledge", "guildlobby", 10, 2))
+//!     .run(&plugin);
+//!
+//! assert_eq!(plugin.zones.load(Ordering::Relaxed), 2);
+//! ```
+
+use super::event::{Event, GroundItemSnapshot, SpawnSnapshot};
+use super::Hooks;
+use crate::eq::GameState;
+
+/// The zone that [`Scenario::login_to_game()`] enters.
+pub const LOGIN_ZONE: &str = "poknowledge";
+
+/// The number of spawns in the zone that [`Scenario::login_to_game()`] enters.
+pub const LOGIN_SPAWNS: usize = 5;
+
+/// The number of ground items in the zone that [`Scenario::login_to_game()`]
+/// enters.
+pub const LOGIN_GROUND_ITEMS: usize = 1;
+
+/// A sequence of hooks, which can be run against any [`Hooks`]
+/// implementation.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Scenario {
+    events: Vec<Event>,
+}
+
+impl Scenario {
+    /// Create an empty scenario.
+    #[must_use]
+    pub fn new() -> Self {
+        Scenario::default()
+    }
+
+    /// Going from the character select screen to being in game, in
+    /// [`LOGIN_ZONE`].
+    ///
+    /// The game state changes to [`GameState::PostCharacterSelect`], then the
+    /// zone is entered as it is when zoning (see [`Scenario::zone()`]), with
+    /// [`LOGIN_SPAWNS`] spawns and [`LOGIN_GROUND_ITEMS`] ground items, and
+    /// then the UI is cleaned and reloaded.
+    #[must_use]
+    pub fn login_to_game() -> Self {
+        let mut scenario = Scenario::new();
+
+        scenario.game_state(GameState::PostCharacterSelect);
+        scenario.enter_zone(LOGIN_ZONE, LOGIN_SPAWNS, LOGIN_GROUND_ITEMS);
+        scenario.events.push(Event::CleanUi);
+        scenario.events.push(Event::ReloadUi);
+
+        scenario
+    }
+
+    /// Zoning from `from` into `to`, where each zone has `spawns` spawns and
+    /// `ground_items` ground items.
+    ///
+    /// This follows the order documented on [`Hooks`]:
+    ///
+    /// 1. [`Hooks::begin_zone()`].
+    /// 2. [`Hooks::remove_spawn()`] and then [`Hooks::remove_ground_item()`]
+    ///    for everything in `from`.
+    /// 3. [`Hooks::game_state()`], with [`GameState::LoggingIn`].
+    /// 4. [`Hooks::end_zone()`].
+    /// 5. [`Hooks::add_spawn()`] for every spawn in `to`.
+    /// 6. [`Hooks::game_state()`], with [`GameState::InGame`].
+    /// 7. [`Hooks::add_ground_item()`] for every ground item in `to`.
+    /// 8. [`Hooks::zoned()`].
+    #[must_use]
+    pub fn zone(from: &str, to: &str, spawns: usize, ground_items: usize) -> Self {
+        let mut scenario = Scenario::new();
+
+        scenario.events.push(Event::BeginZone);
+        scenario
+            .events
+            .extend(spawn_names(from, spawns).map(|name| Event::RemoveSpawn {
+                spawn: SpawnSnapshot { name },
+            }));
+        scenario
+            .events
+            .extend(ground_item_names(from, ground_items).map(|name| {
+                Event::RemoveGroundItem {
+                    item: GroundItemSnapshot { name },
+                }
+            }));
+        scenario.game_state(GameState::LoggingIn);
+        scenario.enter_zone(to, spawns, ground_items);
+
+        scenario
+    }
+
+    /// Camping from being in game to the character select screen.
+    ///
+    /// The UI is cleaned, and then the game state changes to
+    /// [`GameState::CharacterSelect`].
+    #[must_use]
+    pub fn camp_to_char_select() -> Self {
+        let mut scenario = Scenario::new();
+
+        scenario.events.push(Event::CleanUi);
+        scenario.game_state(GameState::CharacterSelect);
+
+        scenario
+    }
+
+    /// Append the events from `next` to this scenario.
+    #[must_use]
+    pub fn then(mut self, next: Scenario) -> Self {
+        self.events.extend(next.events);
+        self
+    }
+
+    /// Append a single `event` to this scenario.
+    #[must_use]
+    pub fn event(mut self, event: Event) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// The events in this scenario, in order.
+    #[must_use]
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Call each hook in this scenario on `hooks`, in order.
+    ///
+    /// The spawn and ground item hooks are skipped (see
+    /// [`Event::dispatch()`]).
+    pub fn run<H>(&self, hooks: &H)
+    where
+        H: Hooks + ?Sized,
+    {
+        for event in &self.events {
+            event.dispatch(hooks);
+        }
+    }
+
+    fn game_state(&mut self, state: GameState) {
+        self.events.push(Event::GameState {
+            state: state.into(),
+        });
+    }
+
+    fn enter_zone(&mut self, zone: &str, spawns: usize, ground_items: usize) {
+        self.events.push(Event::EndZone);
+        self.events
+            .extend(spawn_names(zone, spawns).map(|name| Event::AddSpawn {
+                spawn: SpawnSnapshot { name },
+            }));
+        self.game_state(GameState::InGame);
+        self.events
+            .extend(ground_item_names(zone, ground_items).map(|name| {
+                Event::AddGroundItem {
+                    item: GroundItemSnapshot { name },
+                }
+            }));
+        self.events.push(Event::Zoned);
+    }
+}
+
+impl IntoIterator for Scenario {
+    type IntoIter = std::vec::IntoIter<Event>;
+    type Item = Event;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.into_iter()
+    }
+}
+
+// Spawn names in EverQuest are unique within a zone, with a numeric suffix,
+// like "a_gnoll00".
+fn spawn_names(zone: &str, count: usize) -> impl Iterator<Item = String> + '_ {
+    (0..count).map(move |i| format!("{zone}_npc{i:02}"))
+}
+
+fn ground_item_names(zone: &str, count: usize) -> impl Iterator<Item = String> + '_ {
+    (0..count).map(move |i| format!("{zone}_item{i:02}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::eq;
+
+    fn position(scenario: &Scenario, event: &Event) -> usize {
+        scenario
+            .events()
+            .iter()
+            .position(|e| e == event)
+            .unwrap_or_else(|| panic!("{event:?} is not in the scenario"))
+    }
+
+    fn positions<F>(scenario: &Scenario, f: F) -> Vec<usize>
+    where
+        F: Fn(&Event) -> bool,
+    {
+        scenario
+            .events()
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| f(e))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn game_state(state: GameState) -> Event {
+        Event::GameState {
+            state: state.into(),
+        }
+    }
+
+    #[test]
+    fn test_zone_ordering() {
+        let scenario = Scenario::zone("poknowledge", "guildlobby", 3, 2);
+
+        let begin_zone = position(&scenario, &Event::BeginZone);
+        let end_zone = position(&scenario, &Event::EndZone);
+        let zoned = position(&scenario, &Event::Zoned);
+        let removes = positions(&scenario, |e| {
+            matches!(
+                e,
+                Event::RemoveSpawn { .. } | Event::RemoveGroundItem { .. }
+            )
+        });
+        let add_spawns = positions(&scenario, |e| matches!(e, Event::AddSpawn { .. }));
+        let add_items =
+            positions(&scenario, |e| matches!(e, Event::AddGroundItem { .. }));
+        let states = positions(&scenario, |e| matches!(e, Event::GameState { .. }));
+
+        assert_eq!(removes.len(), 5);
+        assert_eq!(add_spawns.len(), 3);
+        assert_eq!(add_items.len(), 2);
+
+        // remove_spawn and remove_ground_item are called after begin_zone.
+        assert!(removes.iter().all(|&i| i > begin_zone));
+
+        // game_state is called once after begin_zone and the removes are done,
+        // and once after end_zone and add_spawn, but prior to add_ground_item
+        // and zoned.
+        assert_eq!(states.len(), 2);
+        assert!(states[0] > *removes.last().unwrap() && states[0] < end_zone);
+        assert!(states[1] > end_zone && states[1] > *add_spawns.last().unwrap());
+        assert!(states[1] < add_items[0] && states[1] < zoned);
+
+        // end_zone occurs before add_spawn and add_ground_item, and zoned
+        // occurs after all of them.
+        assert!(add_spawns.iter().chain(&add_items).all(|&i| i > end_zone));
+        assert!(add_spawns.iter().chain(&add_items).all(|&i| i < zoned));
+        assert_eq!(zoned, scenario.events().len() - 1);
+    }
+
+    #[test]
+    fn test_zone_synthetic_data() {
+        let scenario = Scenario::zone("poknowledge", "guildlobby", 1, 1);
+
+        assert!(scenario.events().contains(&Event::RemoveSpawn {
+            spawn: SpawnSnapshot {
+                name: "poknowledge_npc00".into(),
+            },
+        }));
+        assert!(scenario.events().contains(&Event::AddGroundItem {
+            item: GroundItemSnapshot {
+                name: "guildlobby_item00".into(),
+            },
+        }));
+    }
+
+    #[test]
+    fn test_login_to_game() {
+        let scenario = Scenario::login_to_game();
+
+        let events = scenario.events();
+        assert_eq!(events[0], game_state(GameState::PostCharacterSelect));
+        assert!(!events.contains(&Event::BeginZone));
+
+        // The UI is cleaned and reloaded when first entering the game.
+        let zoned = position(&scenario, &Event::Zoned);
+        let clean_ui = position(&scenario, &Event::CleanUi);
+        let reload_ui = position(&scenario, &Event::ReloadUi);
+        assert!(zoned < clean_ui && clean_ui < reload_ui);
+
+        assert_eq!(
+            positions(&scenario, |e| matches!(e, Event::AddSpawn { .. })).len(),
+            LOGIN_SPAWNS
+        );
+    }
+
+    #[test]
+    fn test_camp_to_char_select() {
+        // The UI is cleaned when reaching the character select screen.
+        assert_eq!(
+            Scenario::camp_to_char_select().events(),
+            [Event::CleanUi, game_state(GameState::CharacterSelect)]
+        );
+    }
+
+    #[test]
+    fn test_run_skips_spawns() {
+        #[derive(Default)]
+        struct Calls(Mutex<Vec<String>>);
+
+        impl Hooks for Calls {
+            fn game_state(&self, state: eq::GameState) {
+                self.0.lock().push(format!("game_state {state:?}"));
+            }
+
+            fn begin_zone(&self) {
+                self.0.lock().push("begin_zone".into());
+            }
+
+            fn end_zone(&self) {
+                self.0.lock().push("end_zone".into());
+            }
+
+            fn zoned(&self) {
+                self.0.lock().push("zoned".into());
+            }
+
+            fn macro_start(&self, name: &str) {
+                self.0.lock().push(format!("macro_start {name}"));
+            }
+        }
+
+        let calls = Calls::default();
+        Scenario::zone("a", "b", 2, 2)
+            .event(Event::MacroStart {
+                name: "hunt".into(),
+            })
+            .run(&calls);
+
+        assert_eq!(
+            *calls.0.lock(),
+            [
+                "begin_zone",
+                "game_state LoggingIn",
+                "end_zone",
+                "game_state InGame",
+                "zoned",
+                "macro_start hunt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_then() {
+        let scenario = Scenario::login_to_game().then(Scenario::camp_to_char_select());
+
+        assert_eq!(
+            scenario.clone().into_iter().collect::<Vec<_>>(),
+            [
+                Scenario::login_to_game().events(),
+                Scenario::camp_to_char_select().events()
+            ]
+            .concat()
+        );
+    }
+}
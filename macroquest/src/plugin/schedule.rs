@@ -0,0 +1,419 @@
+//! Scheduling work to run from the pulse at a given time on the game clock.
+//!
+//! Scheduled tasks are run on the game's main thread, by the crate, at the
+//! start of every pulse, prior to [`Hooks::pulse()`](crate::plugin::Hooks).
+//! Each pulse they are compared against [`mq::game_time()`](crate::mq),
+//! rather than against a real time clock, so they always line up with what the
+//! game clock actually says, regardless of the rate that it runs at.
+//!
+//! The game clock is unavailable while zoning or at character select, so no
+//! tasks are run then, and it may have jumped when it becomes available again
+//! (most notably after camping for a while). Any task whose time has passed
+//! while the game clock was unavailable is run on the first pulse after it
+//! becomes available, and recurring tasks are only run once for any number of
+//! missed intervals.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use super::workers::panic_message;
+use crate::eq::time::MINUTES_PER_HOUR;
+use crate::eq::GameTime;
+use crate::log::error;
+
+static SCHEDULER: Mutex<Scheduler> = Mutex::new(Scheduler::new());
+
+/// A handle to a scheduled task, which can be used to cancel it.
+///
+/// Dropping the handle does *not* cancel the task.
+#[derive(Clone)]
+pub struct ScheduledTask {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ScheduledTask {
+    /// Cancel the task, preventing it from running again.
+    ///
+    /// Cancelling a task from within itself is allowed, and prevents any
+    /// further runs of a recurring task.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the task has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl fmt::Debug for ScheduledTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScheduledTask")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Run `f` once, on the first pulse at or after the game clock reaches `at`.
+///
+/// If `at` has already passed, `f` will be run on the next pulse where the game
+/// clock is available.
+///
+/// # Examples
+///
+/// Running something at the next game dawn.
+///
+/// ```
+/// # use macroquest::plugin::schedule;
+/// if let Some(now) = macroquest::mq::game_time() {
+///     let dawn = now.next_at(7, 0).expect("7:00 is a valid time");
+///     schedule::run_at_game_time(dawn, || {
+///         macroquest::mq::write_chat("The sun is rising");
+///     });
+/// }
+/// ```
+pub fn run_at_game_time<F>(at: GameTime, f: F) -> ScheduledTask
+where
+    F: FnOnce() + Send + 'static,
+{
+    SCHEDULER.lock().push(Kind::Once {
+        at: at.as_minutes(),
+        f:  Box::new(f),
+    })
+}
+
+/// Run `f` every `hours` game hours, starting `hours` game hours after the
+/// game clock is first available.
+///
+/// # Panics
+///
+/// Panics if `hours` is zero.
+pub fn run_every_game_hours<F>(hours: u32, f: F) -> ScheduledTask
+where
+    F: FnMut() + Send + 'static,
+{
+    assert!(hours > 0, "the interval must be at least one game hour");
+
+    SCHEDULER.lock().push(Kind::Every {
+        interval: u64::from(hours) * MINUTES_PER_HOUR,
+        next:     None,
+        f:        Box::new(f),
+    })
+}
+
+/// Run any tasks that are due, given the current game time.
+pub(crate) fn pulse() {
+    if SCHEDULER.lock().is_empty() {
+        return;
+    }
+
+    let Some(now) = crate::mq::game_time()
+    else {
+        return;
+    };
+    let now = now.as_minutes();
+
+    // The tasks are run without holding the lock, so that they are able to
+    // schedule (or cancel) other tasks.
+    let due = SCHEDULER.lock().take_due(now);
+    let rescheduled = run(due, now);
+    SCHEDULER.lock().tasks.extend(rescheduled);
+}
+
+/// Drop all scheduled tasks.
+pub(crate) fn clear() {
+    let tasks = std::mem::take(&mut SCHEDULER.lock().tasks);
+    drop(tasks);
+}
+
+enum Kind {
+    Once {
+        at: u64,
+        f:  Box<dyn FnOnce() + Send>,
+    },
+    Every {
+        interval: u64,
+        next:     Option<u64>,
+        f:        Box<dyn FnMut() + Send>,
+    },
+}
+
+struct Task {
+    cancelled: Arc<AtomicBool>,
+    kind:      Kind,
+}
+
+impl Task {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn is_due(&mut self, now: u64) -> bool {
+        match &mut self.kind {
+            Kind::Once { at, .. } => *at <= now,
+            // The first interval starts from the first time that we've
+            // actually seen the game clock.
+            Kind::Every { interval, next, .. } => {
+                *next.get_or_insert(now + *interval) <= now
+            }
+        }
+    }
+}
+
+struct Scheduler {
+    tasks: Vec<Task>,
+}
+
+impl Scheduler {
+    const fn new() -> Self {
+        Scheduler { tasks: Vec::new() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    fn push(&mut self, kind: Kind) -> ScheduledTask {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.tasks.push(Task {
+            cancelled: Arc::clone(&cancelled),
+            kind,
+        });
+
+        ScheduledTask { cancelled }
+    }
+
+    /// Remove and return the tasks that are due, dropping any that have been
+    /// cancelled.
+    fn take_due(&mut self, now: u64) -> Vec<Task> {
+        let mut due = Vec::new();
+        let mut remaining = Vec::with_capacity(self.tasks.len());
+
+        for mut task in self.tasks.drain(..) {
+            if task.is_cancelled() {
+                continue;
+            }
+
+            if task.is_due(now) {
+                due.push(task);
+            }
+            else {
+                remaining.push(task);
+            }
+        }
+
+        self.tasks = remaining;
+        due
+    }
+}
+
+/// Run the given tasks, returning the recurring ones that should be scheduled
+/// again.
+fn run(tasks: Vec<Task>, now: u64) -> Vec<Task> {
+    let mut rescheduled = Vec::new();
+
+    for mut task in tasks {
+        // A task may have been cancelled by one that ran before it.
+        if task.is_cancelled() {
+            continue;
+        }
+
+        let result = match task.kind {
+            Kind::Once { f, .. } => panic::catch_unwind(AssertUnwindSafe(f)),
+            Kind::Every {
+                interval,
+                ref mut next,
+                ref mut f,
+            } => {
+                let previous = next.unwrap_or(now);
+                *next = Some(next_run(previous, interval, now));
+                let result = panic::catch_unwind(AssertUnwindSafe(f));
+
+                if result.is_ok() && !task.is_cancelled() {
+                    rescheduled.push(task);
+                }
+
+                result
+            }
+        };
+
+        if let Err(payload) = result {
+            error!(
+                error = panic_message(payload.as_ref()),
+                "scheduled task panicked, it will not be run again"
+            );
+        }
+    }
+
+    rescheduled
+}
+
+/// The next time a recurring task that was due at `previous` should run.
+///
+/// Normally this is exactly one interval after it was due, which keeps the
+/// task aligned to the game clock even when the pulse is late. However if the
+/// game clock has jumped since then, either forwards past the next run or
+/// backwards before the last one, then the task is realigned to one interval
+/// from now, rather than running for every missed interval or waiting for the
+/// clock to catch back up.
+fn next_run(previous: u64, interval: u64, now: u64) -> u64 {
+    let next = previous + interval;
+
+    if next <= now || next > now + interval {
+        now + interval
+    }
+    else {
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    const HOUR: u64 = MINUTES_PER_HOUR;
+
+    fn counter() -> (Arc<AtomicUsize>, impl FnMut() + Send + 'static) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = Arc::clone(&count);
+        (count, move || {
+            c.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    fn tick(scheduler: &mut Scheduler, now: u64) {
+        let due = scheduler.take_due(now);
+        let rescheduled = run(due, now);
+        scheduler.tasks.extend(rescheduled);
+    }
+
+    #[test]
+    fn test_once_runs_at_time() {
+        let mut scheduler = Scheduler::new();
+        let (count, mut f) = counter();
+        scheduler.push(Kind::Once {
+            at: 100,
+            f:  Box::new(move || f()),
+        });
+
+        tick(&mut scheduler, 99);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        tick(&mut scheduler, 100);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn test_once_runs_after_discontinuity() {
+        let mut scheduler = Scheduler::new();
+        let (count, mut f) = counter();
+        scheduler.push(Kind::Once {
+            at: 100,
+            f:  Box::new(move || f()),
+        });
+
+        // The clock jumped from before the target to well after it, such as
+        // after zoning.
+        tick(&mut scheduler, 50);
+        tick(&mut scheduler, 5_000);
+        tick(&mut scheduler, 5_001);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_every_starts_when_clock_first_seen() {
+        let mut scheduler = Scheduler::new();
+        let (count, f) = counter();
+        scheduler.push(Kind::Every {
+            interval: HOUR,
+            next:     None,
+            f:        Box::new(f),
+        });
+
+        tick(&mut scheduler, 1_000);
+        tick(&mut scheduler, 1_000 + HOUR - 1);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        tick(&mut scheduler, 1_000 + HOUR);
+        tick(&mut scheduler, 1_000 + HOUR + 1);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        tick(&mut scheduler, 1_000 + 2 * HOUR);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_every_runs_once_for_missed_intervals() {
+        let mut scheduler = Scheduler::new();
+        let (count, f) = counter();
+        scheduler.push(Kind::Every {
+            interval: HOUR,
+            next:     Some(HOUR),
+            f:        Box::new(f),
+        });
+
+        // Camped out for ten game hours.
+        tick(&mut scheduler, 10 * HOUR + 5);
+        tick(&mut scheduler, 10 * HOUR + 6);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        // Realigned to one interval from when it ran.
+        tick(&mut scheduler, 11 * HOUR + 4);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        tick(&mut scheduler, 11 * HOUR + 5);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_next_run() {
+        // On time, or a little late, stays aligned.
+        assert_eq!(next_run(HOUR, HOUR, HOUR), 2 * HOUR);
+        assert_eq!(next_run(HOUR, HOUR, HOUR + 1), 2 * HOUR);
+
+        // Missed one or more intervals, realigns to now.
+        assert_eq!(next_run(HOUR, HOUR, 2 * HOUR), 3 * HOUR);
+        assert_eq!(next_run(HOUR, HOUR, 10 * HOUR + 5), 11 * HOUR + 5);
+
+        // The clock went backwards, realigns to now.
+        assert_eq!(next_run(10 * HOUR, HOUR, 2 * HOUR), 3 * HOUR);
+    }
+
+    #[test]
+    fn test_cancelled_tasks_are_dropped() {
+        let mut scheduler = Scheduler::new();
+        let (count, f) = counter();
+        let handle = scheduler.push(Kind::Every {
+            interval: HOUR,
+            next:     Some(HOUR),
+            f:        Box::new(f),
+        });
+
+        tick(&mut scheduler, HOUR);
+        handle.cancel();
+        tick(&mut scheduler, 2 * HOUR);
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn test_panicking_task_is_not_rescheduled() {
+        let mut scheduler = Scheduler::new();
+        scheduler.push(Kind::Every {
+            interval: HOUR,
+            next:     Some(HOUR),
+            f:        Box::new(|| panic!("oh no")),
+        });
+
+        tick(&mut scheduler, HOUR);
+        assert!(scheduler.is_empty());
+    }
+}
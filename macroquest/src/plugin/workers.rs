@@ -0,0 +1,397 @@
+//! Worker threads whose lifetime is tied to the plugin.
+//!
+//! Plugins that spawn their own threads need to make sure that those threads
+//! have stopped before the plugin is unloaded, otherwise the game will crash
+//! once the thread tries to execute code from the unloaded DLL. However, simply
+//! joining those threads in [`Hooks::shutdown()`](crate::plugin::Hooks) means
+//! that a single stuck thread will freeze the game forever, so [`Workers`]
+//! signals its threads to stop, and then waits a bounded amount of time for
+//! them to do so.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use std::{fmt, io};
+
+use parking_lot::{Condvar, Mutex};
+
+use crate::log::{error, trace};
+
+/// A handle given to each worker, used to find out when it should stop.
+#[derive(Clone)]
+pub struct StopToken {
+    signal: Arc<Signal>,
+}
+
+struct Signal {
+    stopped: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl StopToken {
+    fn new() -> Self {
+        StopToken {
+            signal: Arc::new(Signal {
+                stopped: Mutex::new(false),
+                condvar: Condvar::new(),
+            }),
+        }
+    }
+
+    fn stop(&self) {
+        *self.signal.stopped.lock() = true;
+        self.signal.condvar.notify_all();
+    }
+
+    /// Whether this worker has been asked to stop.
+    #[must_use]
+    pub fn is_stopped(&self) -> bool {
+        *self.signal.stopped.lock()
+    }
+
+    /// Sleep until either the timeout has elapsed, or this worker has been
+    /// asked to stop, returning whether it has been asked to stop.
+    ///
+    /// This is useful for workers that need to do something periodically, as
+    /// they can use this in place of [`std::thread::sleep`] and still respond
+    /// promptly to being stopped.
+    #[must_use]
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut stopped = self.signal.stopped.lock();
+
+        while !*stopped {
+            if self
+                .signal
+                .condvar
+                .wait_until(&mut stopped, deadline)
+                .timed_out()
+            {
+                break;
+            }
+        }
+
+        *stopped
+    }
+}
+
+impl fmt::Debug for StopToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StopToken")
+            .field("stopped", &self.is_stopped())
+            .finish()
+    }
+}
+
+struct Worker {
+    name:   String,
+    handle: JoinHandle<()>,
+}
+
+/// A set of named worker threads, which are stopped when the plugin shuts
+/// down.
+///
+/// Each worker is given a [`StopToken`], which it should check regularly to
+/// see if it has been asked to stop. When [`Workers::shutdown()`] is called
+/// (or the [`Workers`] is dropped, which happens when the plugin instance is
+/// dropped after [`Hooks::shutdown()`](crate::plugin::Hooks::shutdown)), every
+/// worker is asked to stop, and then joined.
+///
+/// Any worker that hasn't stopped within the timeout is detached, and an error
+/// is logged, because blocking the unloading of the plugin would freeze the
+/// game. Any panic within a worker is caught and logged as well.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use macroquest::plugin::{Hooks, Plugin, Workers};
+/// # macroquest::plugin::setup!(MyPlugin);
+/// struct MyPlugin {
+///     workers: Workers,
+/// }
+///
+/// impl Plugin for MyPlugin {
+///     fn new() -> Self {
+///         MyPlugin {
+///             workers: Workers::new(),
+///         }
+///     }
+/// }
+///
+/// #[macroquest::plugin::hooks]
+/// impl Hooks for MyPlugin {
+///     fn initialize(&self) {
+///         self.workers
+///             .spawn("ticker", |stop| {
+///                 while !stop.wait_timeout(Duration::from_secs(1)) {
+///                     // Do some periodic work.
+///                 }
+///             })
+///             .expect("could not spawn worker");
+///     }
+///
+///     fn shutdown(&self) {
+///         self.workers.shutdown();
+///     }
+/// }
+/// ```
+pub struct Workers {
+    threads: Mutex<Vec<Worker>>,
+    stop:    StopToken,
+    timeout: Duration,
+}
+
+impl Workers {
+    /// The default amount of time that [`Workers::shutdown()`] will wait for
+    /// all of the workers to stop.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Create a new, empty, set of workers using the
+    /// [`Workers::DEFAULT_TIMEOUT`].
+    #[must_use]
+    pub fn new() -> Self {
+        Workers::with_timeout(Workers::DEFAULT_TIMEOUT)
+    }
+
+    /// Create a new, empty, set of workers that will wait up to `timeout` for
+    /// the workers to stop when shutting down.
+    #[must_use]
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Workers {
+            threads: Mutex::new(Vec::new()),
+            stop: StopToken::new(),
+            timeout,
+        }
+    }
+
+    /// Spawn a new named worker thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operating system failed to create the thread.
+    pub fn spawn<N, F>(&self, name: N, f: F) -> io::Result<()>
+    where
+        N: Into<String>,
+        F: FnOnce(StopToken) + Send + 'static,
+    {
+        let name = name.into();
+        let stop = self.stop.clone();
+        let worker_name = name.clone();
+
+        let handle = thread::Builder::new().name(name.clone()).spawn(move || {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(stop))) {
+                error!(
+                    worker = %worker_name,
+                    error = panic_message(payload.as_ref()),
+                    "worker panicked"
+                );
+            }
+        })?;
+
+        self.threads.lock().push(Worker { name, handle });
+
+        Ok(())
+    }
+
+    /// The number of workers that have been spawned and not yet shutdown.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.threads.lock().len()
+    }
+
+    /// Whether there are no workers that have been spawned and not yet
+    /// shutdown.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.threads.lock().is_empty()
+    }
+
+    /// Signal every worker to stop, and wait for them to do so, up to the
+    /// configured timeout.
+    ///
+    /// Returns the names of any workers that did not stop within the timeout,
+    /// which have been detached and will continue running.
+    ///
+    /// Once shutdown, any newly spawned worker will be told to stop
+    /// immediately.
+    pub fn shutdown(&self) -> Vec<String> {
+        let workers = std::mem::take(&mut *self.threads.lock());
+
+        self.stop.stop();
+
+        let deadline = Instant::now() + self.timeout;
+        while Instant::now() < deadline
+            && !workers.iter().all(|w| w.handle.is_finished())
+        {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut stuck = Vec::new();
+        for worker in workers {
+            if worker.handle.is_finished() {
+                // Panics have already been caught and logged inside of the
+                // worker itself, so there's nothing useful in this result.
+                let _ = worker.handle.join();
+                trace!(worker = %worker.name, "worker stopped");
+            }
+            else {
+                error!(
+                    worker = %worker.name,
+                    timeout = ?self.timeout,
+                    "worker failed to stop in time, detaching it, the game may \
+                     crash if it continues to run after the plugin is unloaded"
+                );
+                stuck.push(worker.name);
+            }
+        }
+
+        stuck
+    }
+}
+
+impl Default for Workers {
+    fn default() -> Self {
+        Workers::new()
+    }
+}
+
+impl Drop for Workers {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl fmt::Debug for Workers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let threads = self.threads.lock();
+        f.debug_struct("Workers")
+            .field(
+                "threads",
+                &threads.iter().map(|w| &w.name).collect::<Vec<_>>(),
+            )
+            .field("stop", &self.stop)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    }
+    else if let Some(message) = payload.downcast_ref::<String>() {
+        message.as_str()
+    }
+    else {
+        "Box<dyn Any>"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn test_shutdown_stops_workers() {
+        let workers = Workers::with_timeout(Duration::from_secs(5));
+        let exited = Arc::new(AtomicBool::new(false));
+
+        let e = Arc::clone(&exited);
+        workers
+            .spawn("polling", move |stop| {
+                while !stop.wait_timeout(Duration::from_secs(30)) {}
+                e.store(true, Ordering::SeqCst);
+            })
+            .unwrap();
+
+        assert_eq!(workers.len(), 1);
+        assert!(workers.shutdown().is_empty());
+        assert!(exited.load(Ordering::SeqCst));
+        assert!(workers.is_empty());
+    }
+
+    #[test]
+    fn test_shutdown_detaches_stuck_worker() {
+        let workers = Workers::with_timeout(Duration::from_millis(50));
+        let (release, stuck) = mpsc::channel::<()>();
+
+        workers
+            .spawn("good", |stop| while !stop.is_stopped() {})
+            .unwrap();
+        workers
+            .spawn("stuck", move |_stop| {
+                // This worker ignores the stop signal entirely.
+                let _ = stuck.recv();
+            })
+            .unwrap();
+
+        let start = Instant::now();
+        assert_eq!(workers.shutdown(), ["stuck"]);
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        release.send(()).unwrap();
+    }
+
+    #[test]
+    fn test_panicking_worker_is_contained() {
+        let workers = Workers::new();
+
+        workers.spawn("panics", |_| panic!("oh no")).unwrap();
+
+        assert!(workers.shutdown().is_empty());
+    }
+
+    #[test]
+    fn test_spawn_after_shutdown_is_stopped() {
+        let workers = Workers::new();
+        assert!(workers.shutdown().is_empty());
+
+        let (tx, rx) = mpsc::channel();
+        workers
+            .spawn("late", move |stop| tx.send(stop.is_stopped()).unwrap())
+            .unwrap();
+
+        assert!(rx.recv().unwrap());
+    }
+
+    #[test]
+    fn test_drop_shuts_down() {
+        let exited = Arc::new(AtomicBool::new(false));
+
+        {
+            let workers = Workers::new();
+            let e = Arc::clone(&exited);
+            workers
+                .spawn("dropped", move |stop| {
+                    while !stop.wait_timeout(Duration::from_secs(30)) {}
+                    e.store(true, Ordering::SeqCst);
+                })
+                .unwrap();
+        }
+
+        assert!(exited.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_wait_timeout_elapses() {
+        let token = StopToken::new();
+
+        assert!(!token.wait_timeout(Duration::from_millis(1)));
+
+        token.stop();
+        assert!(token.wait_timeout(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_panic_message() {
+        assert_eq!(panic_message(&"static"), "static");
+        assert_eq!(panic_message(&String::from("owned")), "owned");
+        assert_eq!(panic_message(&1), "Box<dyn Any>");
+    }
+}
@@ -0,0 +1,182 @@
+//! An async runtime that is driven by the MacroQuest pulse.
+//!
+//! This provides a [tokio](https://crates.io/crates/tokio) current thread
+//! runtime, which is driven forward a little bit on every pulse, rather than
+//! by a dedicated thread. This allows plugins to use async I/O (HTTP clients,
+//! websockets, etc) and timers without spinning up a thread of their own, and
+//! allows tasks spawned with [`spawn_local`] to safely interact with the game,
+//! as they are always polled on the game's main thread.
+//!
+//! The amount of time spent driving the runtime on each pulse is bounded by
+//! the [`budget`], to protect the game's frame time. Note that the budget is
+//! only checked in between polling tasks, so a task that blocks for a long
+//! time will still cause the game to hitch.
+//!
+//! # Examples
+//!
+//! ```
+//! # use macroquest::plugin::{Hooks, Plugin};
+//! # macroquest::plugin::setup!(MyPlugin);
+//! # struct MyPlugin;
+//! # impl Plugin for MyPlugin {
+//! #     fn new() -> Self {
+//! #         MyPlugin
+//! #     }
+//! # }
+//! use std::time::Duration;
+//!
+//! #[macroquest::plugin::hooks]
+//! impl Hooks for MyPlugin {
+//!     fn initialize(&self) {
+//!         macroquest::rt::spawn_local(async {
+//!             tokio::time::sleep(Duration::from_secs(5)).await;
+//!             macroquest::mq::write_chat("5 seconds have passed");
+//!         });
+//!     }
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+pub use tokio::runtime::Handle;
+use tokio::runtime::{Builder, Runtime};
+use tokio::task::{JoinHandle, LocalSet};
+
+/// The default amount of time spent driving the runtime on each pulse.
+pub const DEFAULT_BUDGET: Duration = Duration::from_millis(1);
+
+// The maximum number of times that we'll yield back to the runtime in a single
+// pulse, which prevents us from spending the entire budget spinning when there
+// is nothing to do.
+const MAX_TICKS: u32 = 8;
+
+static RUNTIME: Mutex<Option<Runtime>> = Mutex::new(None);
+static HANDLE: Mutex<Option<Handle>> = Mutex::new(None);
+static BUDGET_MICROS: AtomicU64 = AtomicU64::new(1_000);
+
+thread_local! {
+    static LOCAL: RefCell<Option<LocalSet>> = const { RefCell::new(None) };
+}
+
+/// Returns a [`Handle`] to the runtime, creating it if required.
+///
+/// The [`Handle`] may be freely sent to and used from other threads, however
+/// any task spawned with it will still only make progress on the game's main
+/// thread during the pulse.
+///
+/// # Panics
+///
+/// Panics if the runtime could not be created.
+#[must_use]
+pub fn handle() -> Handle {
+    if let Some(handle) = HANDLE.lock().as_ref() {
+        return handle.clone();
+    }
+
+    let mut runtime = RUNTIME.lock();
+    let runtime = runtime.get_or_insert_with(|| {
+        Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("could not create the tokio runtime")
+    });
+
+    let handle = runtime.handle().clone();
+    *HANDLE.lock() = Some(handle.clone());
+
+    handle
+}
+
+/// Spawn a `!Send` future onto the runtime.
+///
+/// Tasks spawned this way are always polled on the game's main thread, which
+/// makes them suitable for tasks that need to interact with the game.
+///
+/// This must be called from the game's main thread (i.e. from within a hook),
+/// tasks spawned from any other thread will never be polled.
+///
+/// # Panics
+///
+/// Panics if the runtime could not be created.
+pub fn spawn_local<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    // Make sure that the runtime exists, so that the pulse will drive it.
+    let _ = handle();
+
+    LOCAL.with(|local| {
+        if local.borrow().is_none() {
+            *local.borrow_mut() = Some(LocalSet::new());
+        }
+
+        local
+            .borrow()
+            .as_ref()
+            .expect("local set was just created")
+            .spawn_local(future)
+    })
+}
+
+/// The maximum amount of time spent driving the runtime on each pulse.
+#[must_use]
+pub fn budget() -> Duration {
+    Duration::from_micros(BUDGET_MICROS.load(Ordering::Relaxed))
+}
+
+/// Set the maximum amount of time spent driving the runtime on each pulse.
+///
+/// Larger budgets allow more progress to be made on each pulse, at the cost of
+/// potentially longer frame times. The default is [`DEFAULT_BUDGET`].
+pub fn set_budget(budget: Duration) {
+    let micros = u64::try_from(budget.as_micros()).unwrap_or(u64::MAX);
+    BUDGET_MICROS.store(micros, Ordering::Relaxed);
+}
+
+/// Drive the runtime forward, for no longer than the budget.
+pub(crate) fn pulse() {
+    let runtime = RUNTIME.lock();
+    let Some(runtime) = runtime.as_ref()
+    else {
+        return;
+    };
+
+    let deadline = Instant::now() + budget();
+    let tick = async move {
+        // Every time that we yield, the runtime gets a chance to poll any
+        // ready tasks and to check the I/O and timer drivers.
+        for _ in 0..MAX_TICKS {
+            tokio::task::yield_now().await;
+
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+    };
+
+    LOCAL.with(|local| match local.borrow().as_ref() {
+        Some(local) => runtime.block_on(local.run_until(tick)),
+        None => runtime.block_on(tick),
+    });
+}
+
+/// Shutdown the runtime, dropping any outstanding tasks.
+pub(crate) fn shutdown() {
+    HANDLE.lock().take();
+
+    if let Some(runtime) = RUNTIME.lock().take() {
+        // Dropping the local tasks may need to interact with the runtime (for
+        // instance to cancel timers), so we make sure that we're inside of it.
+        {
+            let _guard = runtime.enter();
+            LOCAL.with(|local| local.borrow_mut().take());
+        }
+
+        runtime.shutdown_timeout(Duration::from_secs(1));
+    }
+}
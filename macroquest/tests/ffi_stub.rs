@@ -0,0 +1,199 @@
+// Checks that ffi_stub.txt, which build.rs generates the docs.rs stub of
+// macroquest-sys from, lists every item that's actually in macroquest-sys.
+
+use std::path::Path;
+
+#[path = "../build/ffi_stub.rs"]
+mod ffi_stub;
+
+fn read(path: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("could not read {}: {error}", path.display()))
+}
+
+// Every item in the cxx bridges in the macroquest-sys source, in the same form
+// as ffi_stub::signatures().
+fn bridge_signatures(source: &str) -> Vec<String> {
+    let mut signatures = Vec::new();
+    let mut lines = source.lines();
+    let mut module = None;
+    let mut opaque = None;
+
+    while let Some(line) = lines.next() {
+        let content = line.trim();
+        let path = |module: Option<&str>, item: &str| match module {
+            Some(module) => format!("{module}::{}", ffi_stub::normalize(item)),
+            None => ffi_stub::normalize(item),
+        };
+
+        if let Some(rest) = line.strip_prefix("pub mod ") {
+            module = rest.strip_suffix(" {");
+        }
+        else if line == "}" {
+            module = None;
+        }
+        else if let Some(rest) = line.strip_prefix("pub const ") {
+            let (decl, _) = rest.split_once(" = ").unwrap();
+            signatures.push(path(module, &format!("const {decl}")));
+        }
+        else if content.starts_with("unsafe extern \"C++\"") {
+            opaque = None;
+        }
+        else if let Some(name) = content
+            .strip_prefix("pub type ")
+            .and_then(|rest| rest.strip_suffix(';'))
+        {
+            opaque = Some(name.to_owned());
+            signatures.push(path(module, &format!("type {name}")));
+        }
+        else if content.starts_with("struct ") || content.starts_with("enum ") {
+            let header = content.strip_suffix(" {").unwrap();
+            let parts: Vec<&str> = lines
+                .by_ref()
+                .map(str::trim)
+                .take_while(|part| *part != "}")
+                .filter(|part| !part.is_empty() && !part.starts_with("//"))
+                .map(|part| part.trim_end_matches(','))
+                .collect();
+
+            let item = format!("{header} {{ {} }}", parts.join(", "));
+            signatures.push(path(module, &item));
+        }
+        else if content.starts_with("fn ") {
+            let mut signature = content.to_owned();
+            while !signature.ends_with(';') {
+                signature.push(' ');
+                signature.push_str(lines.next().unwrap().trim());
+            }
+            signature.pop();
+
+            match &opaque {
+                Some(name) if ffi_stub::normalize(&signature).contains("(&self") => {
+                    signatures.push(path(module, &format!("{name}::{signature}")));
+                }
+                _ => signatures.push(path(module, &signature)),
+            }
+        }
+    }
+
+    signatures.sort();
+    signatures
+}
+
+#[test]
+fn test_stub_matches_bridge() {
+    let bridge = bridge_signatures(&read("../macroquest-sys/src/lib.rs"));
+    let stub = ffi_stub::signatures(&ffi_stub::parse(&read("ffi_stub.txt")));
+
+    let missing: Vec<&String> = bridge.iter().filter(|s| !stub.contains(s)).collect();
+    let extra: Vec<&String> = stub.iter().filter(|s| !bridge.contains(s)).collect();
+
+    assert!(
+        missing.is_empty() && extra.is_empty(),
+        "ffi_stub.txt is out of date with macroquest-sys\n\nmissing from \
+         ffi_stub.txt:\n{missing:#?}\n\nnot in macroquest-sys:\n{extra:#?}"
+    );
+}
+
+#[test]
+fn test_bridge_signatures() {
+    let source = r#"
+pub const VERSION: &[u8; 3] = b"v1\0";
+
+#[cxx::bridge(namespace = "mqrust::example")]
+pub mod example {
+    /// A point.
+    #[derive(Debug)]
+    struct Point {
+        x:   f32,
+        // Why not.
+        why: bool,
+    }
+
+    unsafe extern "C++" {
+        include!("example.h");
+
+        // Example Functions
+        #[must_use]
+        fn origin(
+            point: &mut Point,
+        ) -> bool;
+
+        pub type Thing;
+
+        #[must_use]
+        fn name(
+            &self,
+        ) -> &str;
+
+        fn first_thing() -> *const Thing;
+    }
+}
+"#;
+
+    assert_eq!(
+        bridge_signatures(source),
+        [
+            "const VERSION: &[u8; 3]",
+            "example::Thing::fn name(&self) -> &str",
+            "example::fn first_thing() -> *const Thing",
+            "example::fn origin(point: &mut Point) -> bool",
+            "example::struct Point { x: f32, why: bool }",
+            "example::type Thing",
+        ]
+    );
+}
+
+#[test]
+fn test_generate() {
+    let list = r#"
+const VERSION: &[u8; 3] = b"v1\0"
+
+mod example
+    #[derive(Debug)]
+    struct Point
+        x: f32
+        y: f32
+
+    #[derive(Debug)]
+    enum Kind
+        Small
+        Large
+
+    fn origin(point: &mut Point) -> bool
+
+    type Thing
+        fn name(&self) -> &str
+"#;
+    let modules = ffi_stub::parse(list);
+    let stub = ffi_stub::generate(&modules);
+
+    assert!(stub.contains("pub const VERSION: &[u8; 3] = b\"v1\\0\";"));
+    assert!(stub.contains("pub mod example {"));
+    assert!(stub.contains("    #[derive(Debug)]\n    pub struct Point {\n"));
+    assert!(stub.contains("        pub x: f32,"));
+    assert!(stub.contains("    pub const Large: Self = Kind { repr: 1 };"));
+    assert!(stub.contains("    pub fn origin(point: &mut Point) -> bool {"));
+    assert!(stub.contains("    pub struct Thing;"));
+    assert!(stub.contains("        pub fn name(&self) -> &str {"));
+
+    assert_eq!(
+        ffi_stub::signatures(&modules),
+        [
+            "const VERSION: &[u8; 3]",
+            "example::Thing::fn name(&self) -> &str",
+            "example::enum Kind { Small, Large }",
+            "example::fn origin(point: &mut Point) -> bool",
+            "example::struct Point { x: f32, y: f32 }",
+            "example::type Thing",
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "line 3: unknown item")]
+fn test_parse_unknown_item() {
+    let _ = ffi_stub::parse("mod example\n    fn ok()\n    static NOPE: u8\n");
+}
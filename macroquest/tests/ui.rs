@@ -0,0 +1,13 @@
+// Checks the compile errors that the plugin macros give when they're misused,
+// against the .stderr snapshot next to each case in tests/ui.
+//
+// Rerun with TRYBUILD=overwrite to update the snapshots after changing one of
+// the messages.
+
+#![cfg(target_os = "windows")]
+
+#[test]
+fn test_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use macroquest::plugin::Hooks;
+
+struct NotAPlugin;
+
+impl Hooks for NotAPlugin {}
+
+macroquest::plugin::setup!(NotAPlugin);
+
+fn main() {}